@@ -1,6 +1,19 @@
+use std::collections::HashMap;
+
 use crate::constants::{LAST_COLUMN, LAST_ROW};
-use crate::expressions::parser::stringify::DisplaceData;
+use crate::displacement::{
+    displace_range_columns, displace_range_rows, get_moved_interval, get_moved_range,
+};
+use crate::expressions::parser::stringify::{get_moved_index, DisplaceData};
+use crate::expressions::types::{Area, CellReferenceIndex};
+use crate::merge_cells::{contains, intersects};
 use crate::model::Model;
+use crate::types::{Cell, Col};
+
+/// A cell taken out of a sheet to put it somewhere else: its row, column, the cell and its formula
+type TakenCell = (i32, i32, Cell, Option<String>);
+
+const MERGED_CELL_ERROR: &str = "Cannot move part of a merged cell";
 
 // NOTE: There is a difference with Excel behaviour when deleting cells/rows/columns
 // In Excel if the whole range is deleted then it will substitute for #REF!
@@ -135,6 +148,28 @@ impl Model {
                 delta: column_count,
             }),
         );
+        self.displace_comments_columns(sheet, column, column_count)?;
+        self.displace_hyperlinks_columns(sheet, column, column_count)?;
+        self.displace_auto_filter_columns(sheet, column, column_count)?;
+        self.displace_tables_columns(sheet, column, column_count)?;
+        self.displace_conditional_formats_columns(sheet, column, column_count)?;
+        self.displace_data_validations_columns(sheet, column, column_count)?;
+        self.displace_pivot_tables(sheet, |range| {
+            displace_range_columns(range, column, column_count)
+        })?;
+
+        // Shift the widths, styles and visibility of the columns.
+        // Columns inserted inside a range of columns take its properties.
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        for col in worksheet.cols.iter_mut() {
+            if col.min >= column {
+                col.min += column_count;
+            }
+            if col.max >= column {
+                col.max = (col.max + column_count).min(LAST_COLUMN);
+            }
+        }
+        worksheet.cols.retain(|col| col.min <= LAST_COLUMN);
 
         Ok(())
     }
@@ -188,6 +223,15 @@ impl Model {
                 delta: -column_count,
             }),
         );
+        self.displace_comments_columns(sheet, column, -column_count)?;
+        self.displace_hyperlinks_columns(sheet, column, -column_count)?;
+        self.displace_auto_filter_columns(sheet, column, -column_count)?;
+        self.displace_tables_columns(sheet, column, -column_count)?;
+        self.displace_conditional_formats_columns(sheet, column, -column_count)?;
+        self.displace_data_validations_columns(sheet, column, -column_count)?;
+        self.displace_pivot_tables(sheet, |range| {
+            displace_range_columns(range, column, -column_count)
+        })?;
         let worksheet = &mut self.workbook.worksheet_mut(sheet)?;
 
         // deletes all the column styles
@@ -312,6 +356,13 @@ impl Model {
                 delta: row_count,
             }),
         );
+        self.displace_comments_rows(sheet, row, row_count)?;
+        self.displace_hyperlinks_rows(sheet, row, row_count)?;
+        self.displace_auto_filter_rows(sheet, row, row_count)?;
+        self.displace_tables_rows(sheet, row, row_count)?;
+        self.displace_conditional_formats_rows(sheet, row, row_count)?;
+        self.displace_data_validations_rows(sheet, row, row_count)?;
+        self.displace_pivot_tables(sheet, |range| displace_range_rows(range, row, row_count))?;
 
         Ok(())
     }
@@ -373,9 +424,415 @@ impl Model {
                 delta: -row_count,
             }),
         );
+        self.displace_comments_rows(sheet, row, -row_count)?;
+        self.displace_hyperlinks_rows(sheet, row, -row_count)?;
+        self.displace_auto_filter_rows(sheet, row, -row_count)?;
+        self.displace_tables_rows(sheet, row, -row_count)?;
+        self.displace_conditional_formats_rows(sheet, row, -row_count)?;
+        self.displace_data_validations_rows(sheet, row, -row_count)?;
+        self.displace_pivot_tables(sheet, |range| displace_range_rows(range, row, -row_count))?;
+        Ok(())
+    }
+
+    /// Removes the cells of the `sheet` in the range (row1, column1, row2, column2) and returns
+    /// them with their formulas
+    fn take_cells(
+        &mut self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+    ) -> Result<Vec<TakenCell>, String> {
+        let (row1, column1, row2, column2) = range;
+        let mut positions = Vec::new();
+        for (row, data_row) in &self.workbook.worksheet(sheet)?.sheet_data {
+            if (row1..=row2).contains(row) {
+                for column in data_row.keys() {
+                    if (column1..=column2).contains(column) {
+                        positions.push((*row, *column));
+                    }
+                }
+            }
+        }
+        positions.sort_unstable();
+        let mut cells = Vec::new();
+        for (row, column) in positions {
+            let formula = self.get_cell_formula(sheet, row, column)?;
+            let sheet_data = &mut self.workbook.worksheet_mut(sheet)?.sheet_data;
+            if let Some(cell) = sheet_data.get_mut(&row).and_then(|r| r.remove(&column)) {
+                cells.push((row, column, cell, formula));
+            }
+        }
+        Ok(cells)
+    }
+
+    /// Puts the `cells` in the `sheet`. The formulas are parsed again in their new position.
+    fn put_cells(&mut self, sheet: u32, cells: Vec<TakenCell>) -> Result<(), String> {
+        for (row, column, cell, formula) in cells {
+            self.workbook
+                .worksheet_mut(sheet)?
+                .update_cell(row, column, cell)?;
+            if let Some(formula) = formula {
+                self.update_cell_with_formula(sheet, row, column, formula)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves `row_count` rows starting at `row` by `delta` rows, with their values, styles,
+    /// comments, hyperlinks and merged cells. The rows in between are displaced to fill the gap.
+    /// References to the cells in the rows are updated in the whole workbook, and so are the
+    /// ranges of tables, the autofilter, conditional formats, data validations and pivot tables.
+    /// Tables and the autofilter cannot be split and their header and totals rows stay in place.
+    pub fn move_rows_action(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        row_count: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        if row_count <= 0 {
+            return Err("The number of rows to move must be positive".to_string());
+        }
+        if row < 1 || row + row_count - 1 > LAST_ROW {
+            return Err("Initial row out of boundaries".to_string());
+        }
+        if row + delta < 1 || row + row_count - 1 + delta > LAST_ROW {
+            return Err("Target row out of boundaries".to_string());
+        }
+        if delta == 0 {
+            return Ok(());
+        }
+        let first_row = row.min(row + delta);
+        let last_row = (row + row_count - 1).max(row + row_count - 1 + delta);
+        let move_row = |r: i32| get_moved_index(r, row, row_count, delta);
+        let merge_cells =
+            self.get_displaced_merged_cells(sheet, |(row1, column1, row2, column2)| {
+                let (row1, row2) = get_moved_interval(row1, row2, row, row_count, delta)
+                    .ok_or_else(|| MERGED_CELL_ERROR.to_string())?;
+                Ok(Some((row1, column1, row2, column2)))
+            })?;
+        let tables = self.get_tables_after_rows_move(sheet, row, row_count, delta)?;
+        let auto_filter = self.get_auto_filter_after_rows_move(sheet, row, row_count, delta)?;
+
+        // Update all formulas in the workbook
+        let displace_data = DisplaceData::RowMove {
+            sheet,
+            row,
+            row_count,
+            delta,
+        };
+        self.displace_cells(&displace_data);
+        let cells = self
+            .take_cells(sheet, (first_row, 1, last_row, LAST_COLUMN))?
+            .into_iter()
+            .map(|(r, column, cell, formula)| (move_row(r), column, cell, formula))
+            .collect();
+        self.put_cells(sheet, cells)?;
+        self.displace_comments(sheet, |(r, c)| Some((move_row(r), c)))?;
+        self.displace_hyperlinks(sheet, |(r, c)| Some((move_row(r), c)))?;
+        let move_range = |(row1, column1, row2, column2)| {
+            let (row1, row2) = get_moved_range(row1, row2, row, row_count, delta);
+            Some((row1, column1, row2, column2))
+        };
+        let displace_formula = |model: &mut Model, formula: &str, origin| {
+            model.displace_rule_formula(formula, origin, &displace_data)
+        };
+        self.displace_conditional_formats(sheet, displace_formula, move_range)?;
+        self.displace_data_validations(sheet, displace_formula, move_range)?;
+        self.displace_pivot_tables(sheet, move_range)?;
+        for table in tables {
+            self.set_table(&table.name.clone(), Some(table))?;
+        }
+
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        worksheet.merge_cells = merge_cells;
+        worksheet.auto_filter = auto_filter;
+        for row_data in worksheet.rows.iter_mut() {
+            row_data.r = move_row(row_data.r);
+        }
+        worksheet.rows.sort_by_key(|row_data| row_data.r);
+        Ok(())
+    }
+
+    /// Moves `column_count` columns starting at `column` by `delta` columns, with their values,
+    /// styles, widths, comments, hyperlinks and merged cells. The columns in between are
+    /// displaced to fill the gap.
+    /// References to the cells in the columns are updated in the whole workbook, and so are the
+    /// ranges of tables, the autofilter, conditional formats, data validations and pivot tables.
+    /// Tables and the autofilter cannot be split.
+    pub fn move_columns_action(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        column_count: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        if column_count <= 0 {
+            return Err("The number of columns to move must be positive".to_string());
+        }
+        if column < 1 || column + column_count - 1 > LAST_COLUMN {
+            return Err("Initial column out of boundaries".to_string());
+        }
+        if column + delta < 1 || column + column_count - 1 + delta > LAST_COLUMN {
+            return Err("Target column out of boundaries".to_string());
+        }
+        if delta == 0 {
+            return Ok(());
+        }
+        let first_column = column.min(column + delta);
+        let last_column = (column + column_count - 1).max(column + column_count - 1 + delta);
+        let move_column = |c: i32| get_moved_index(c, column, column_count, delta);
+        let merge_cells =
+            self.get_displaced_merged_cells(sheet, |(row1, column1, row2, column2)| {
+                let (column1, column2) =
+                    get_moved_interval(column1, column2, column, column_count, delta)
+                        .ok_or_else(|| MERGED_CELL_ERROR.to_string())?;
+                Ok(Some((row1, column1, row2, column2)))
+            })?;
+        let tables = self.get_tables_after_columns_move(sheet, column, column_count, delta)?;
+        let auto_filter =
+            self.get_auto_filter_after_columns_move(sheet, column, column_count, delta)?;
+
+        // Update all formulas in the workbook
+        let displace_data = DisplaceData::ColumnMove {
+            sheet,
+            column,
+            column_count,
+            delta,
+        };
+        self.displace_cells(&displace_data);
+        let cells = self
+            .take_cells(sheet, (1, first_column, LAST_ROW, last_column))?
+            .into_iter()
+            .map(|(row, c, cell, formula)| (row, move_column(c), cell, formula))
+            .collect();
+        self.put_cells(sheet, cells)?;
+        self.displace_comments(sheet, |(r, c)| Some((r, move_column(c))))?;
+        self.displace_hyperlinks(sheet, |(r, c)| Some((r, move_column(c))))?;
+        let move_range = |(row1, column1, row2, column2)| {
+            let (column1, column2) = get_moved_range(column1, column2, column, column_count, delta);
+            Some((row1, column1, row2, column2))
+        };
+        let displace_formula = |model: &mut Model, formula: &str, origin| {
+            model.displace_rule_formula(formula, origin, &displace_data)
+        };
+        self.displace_conditional_formats(sheet, displace_formula, move_range)?;
+        self.displace_data_validations(sheet, displace_formula, move_range)?;
+        self.displace_pivot_tables(sheet, move_range)?;
+        for table in tables {
+            self.set_table(&table.name.clone(), Some(table))?;
+        }
+
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        worksheet.merge_cells = merge_cells;
+        worksheet.auto_filter = auto_filter;
+        // The column ranges that meet the moved columns are split in single columns
+        let mut cols = Vec::new();
+        for col in &worksheet.cols {
+            if col.max < first_column || col.min > last_column {
+                cols.push(col.clone());
+                continue;
+            }
+            if col.min < first_column {
+                cols.push(Col {
+                    max: first_column - 1,
+                    ..col.clone()
+                });
+            }
+            for c in col.min.max(first_column)..=col.max.min(last_column) {
+                let c = move_column(c);
+                cols.push(Col {
+                    min: c,
+                    max: c,
+                    ..col.clone()
+                });
+            }
+            if col.max > last_column {
+                cols.push(Col {
+                    min: last_column + 1,
+                    ..col.clone()
+                });
+            }
+        }
+        cols.sort_by_key(|col| col.min);
+        worksheet.cols = cols;
+        Ok(())
+    }
+
+    /// Moves the cells in `area` so that its top left cell is (`target_row`, `target_column`),
+    /// with their values, styles, comments, hyperlinks and merged cells. The cells that were in the
+    /// target are overwritten and the ones left in the area are emptied.
+    /// References to the cells in the area are updated in the whole workbook. Tables, the
+    /// autofilter, conditional formats, data validations and pivot tables in the area move with it.
+    pub fn move_range_action(
+        &mut self,
+        area: &Area,
+        target_row: i32,
+        target_column: i32,
+    ) -> Result<(), String> {
+        let sheet = area.sheet;
+        self.workbook.worksheet(sheet)?;
+        if area.width <= 0 || area.height <= 0 {
+            return Err("Invalid area".to_string());
+        }
+        let source = (
+            area.row,
+            area.column,
+            area.row + area.height - 1,
+            area.column + area.width - 1,
+        );
+        if source.0 < 1 || source.1 < 1 || source.2 > LAST_ROW || source.3 > LAST_COLUMN {
+            return Err("Initial range out of boundaries".to_string());
+        }
+        let row_delta = target_row - area.row;
+        let column_delta = target_column - area.column;
+        let target = (
+            target_row,
+            target_column,
+            source.2 + row_delta,
+            source.3 + column_delta,
+        );
+        if target.0 < 1 || target.1 < 1 || target.2 > LAST_ROW || target.3 > LAST_COLUMN {
+            return Err("Target range out of boundaries".to_string());
+        }
+        if row_delta == 0 && column_delta == 0 {
+            return Ok(());
+        }
+        let is_in = |range: (i32, i32, i32, i32), (r, c): (i32, i32)| contains(range, (r, c, r, c));
+        // Merged cells in the area move with it and the ones in the target are removed
+        let merge_cells = self.get_displaced_merged_cells(sheet, |merged| {
+            if contains(source, merged) {
+                Ok(Some((
+                    merged.0 + row_delta,
+                    merged.1 + column_delta,
+                    merged.2 + row_delta,
+                    merged.3 + column_delta,
+                )))
+            } else if intersects(source, merged) {
+                Err(MERGED_CELL_ERROR.to_string())
+            } else if intersects(target, merged) {
+                Ok(None)
+            } else {
+                Ok(Some(merged))
+            }
+        })?;
+        let tables = self.get_tables_after_range_move(sheet, source, row_delta, column_delta)?;
+        let auto_filter =
+            self.get_auto_filter_after_range_move(sheet, source, row_delta, column_delta)?;
+
+        // Update the formulas that reference the area, outside the area and the target
+        self.forward_references(
+            area,
+            &CellReferenceIndex {
+                sheet,
+                row: target_row,
+                column: target_column,
+            },
+        )?;
+        let mut cells = Vec::new();
+        for (row, column, cell, formula) in self.take_cells(sheet, source)? {
+            let formula = match formula {
+                Some(formula) => Some(self.move_cell_value_to_area(
+                    &formula,
+                    &CellReferenceIndex { sheet, row, column },
+                    &CellReferenceIndex {
+                        sheet,
+                        row: row + row_delta,
+                        column: column + column_delta,
+                    },
+                    area,
+                )?),
+                None => None,
+            };
+            cells.push((row + row_delta, column + column_delta, cell, formula));
+        }
+        self.take_cells(sheet, target)?;
+        self.put_cells(sheet, cells)?;
+        let displace = |cell: (i32, i32)| {
+            if is_in(source, cell) {
+                Some((cell.0 + row_delta, cell.1 + column_delta))
+            } else if is_in(target, cell) {
+                None
+            } else {
+                Some(cell)
+            }
+        };
+        self.displace_comments(sheet, displace)?;
+        self.displace_hyperlinks(sheet, displace)?;
+        // Ranges in the area move with it and the ones in the target are deleted
+        let move_range = |range: (i32, i32, i32, i32)| {
+            if contains(source, range) {
+                Some((
+                    range.0 + row_delta,
+                    range.1 + column_delta,
+                    range.2 + row_delta,
+                    range.3 + column_delta,
+                ))
+            } else if contains(target, range) {
+                None
+            } else {
+                Some(range)
+            }
+        };
+        let move_formula = |model: &mut Model, formula: &str, origin| {
+            model.move_rule_formula(formula, origin, area, row_delta, column_delta)
+        };
+        self.displace_conditional_formats(sheet, move_formula, move_range)?;
+        self.displace_data_validations(sheet, move_formula, move_range)?;
+        self.displace_pivot_tables(sheet, move_range)?;
+        for table in tables {
+            self.set_table(&table.name.clone(), Some(table))?;
+        }
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        worksheet.merge_cells = merge_cells;
+        worksheet.auto_filter = auto_filter;
         Ok(())
     }
 
+    /// Rewrites the rows of the `range` (row1, column1, row2, column2) with the cells of the
+    /// `source_rows` in order. The rows of the range left at the bottom are cleared.
+    /// The cells move as they are, the references in their formulas to cells in the same row of
+    /// the range move with the row. References from other cells are left unchanged.
+    pub fn set_rows_order_action(
+        &mut self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+        source_rows: &[i32],
+    ) -> Result<(), String> {
+        let (row1, column1, _, column2) = range;
+        let target_rows: HashMap<i32, i32> = source_rows
+            .iter()
+            .enumerate()
+            .map(|(index, &source_row)| (source_row, row1 + index as i32))
+            .collect();
+        let mut cells = Vec::new();
+        for (row, column, cell, formula) in self.take_cells(sheet, range)? {
+            let Some(&target_row) = target_rows.get(&row) else {
+                continue;
+            };
+            let formula = match formula {
+                Some(formula) => Some(self.move_cell_value_to_area(
+                    &formula,
+                    &CellReferenceIndex { sheet, row, column },
+                    &CellReferenceIndex {
+                        sheet,
+                        row: target_row,
+                        column,
+                    },
+                    &Area {
+                        sheet,
+                        row,
+                        column: column1,
+                        width: column2 - column1 + 1,
+                        height: 1,
+                    },
+                )?),
+                None => None,
+            };
+            cells.push((target_row, column, cell, formula));
+        }
+        self.put_cells(sheet, cells)
+    }
+
     /// Displaces cells due to a move column action
     /// from initial_column to target_column = initial_column + column_delta
     /// References will be updated following:
@@ -384,36 +841,30 @@ impl Model {
     ///    * All cell references to columns in between (initial_column, target_column] will be displaced one to the left
     ///    * All other cell references are left unchanged
     /// Ranges. This is the tricky bit:
-    ///    * Column is one of the extremes of the range. The new extreme would be target_column.
+    ///    * Ranges that contain both initial_column and target_column are left unchanged.
+    ///    * Any other case, the extremes of the range are displaced like cell references.
     ///      Range is then normalized
-    ///    * Any other case, range is left unchanged.
-    /// NOTE: This does NOT move the data in the columns or move the colum styles
+    ///
+    /// The data, styles and width of the column move with it.
+    ///
+    /// See also:
+    /// * [Model::move_columns_action]
     pub fn move_column_action(
         &mut self,
         sheet: u32,
         column: i32,
         delta: i32,
-    ) -> Result<(), &'static str> {
+    ) -> Result<(), String> {
         // Check boundaries
         let target_column = column + delta;
         if !(1..=LAST_COLUMN).contains(&target_column) {
-            return Err("Target column out of boundaries");
+            return Err("Target column out of boundaries".to_string());
         }
         if !(1..=LAST_COLUMN).contains(&column) {
-            return Err("Initial column out of boundaries");
+            return Err("Initial column out of boundaries".to_string());
         }
 
-        // TODO: Add the actual displacement of data and styles
-
-        // Update all formulas in the workbook
-        self.displace_cells(
-            &(DisplaceData::ColumnMove {
-                sheet,
-                column,
-                delta,
-            }),
-        );
-
+        self.move_columns_action(sheet, column, 1, delta)?;
         Ok(())
     }
 }
@@ -0,0 +1,525 @@
+use std::cmp::Ordering;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    cell::CellValue,
+    displacement::{displace_index, displace_interval, displace_range_rows, get_moved_interval},
+    expressions::parser::stringify::get_moved_index,
+    formatter::parser::Parser,
+    functions::util::from_wildcard_to_regex,
+    merge_cells::{contains, parse_range_reference, range_to_string},
+    model::Model,
+    types::{
+        AutoFilter, ColumnFilter, CustomFilter, DateGroup, DateTimeGrouping, FilterColumn,
+        FilterOperator,
+    },
+};
+
+const AUTO_FILTER_MOVE_ERROR: &str = "Cannot move part of a filtered range";
+
+fn check_filter_columns(columns: &[FilterColumn], width: i32) -> Result<(), String> {
+    for (index, filter_column) in columns.iter().enumerate() {
+        let column_id = filter_column.column_id;
+        if column_id as i32 >= width {
+            return Err(format!("Invalid filter column: '{column_id}'"));
+        }
+        if columns[..index].iter().any(|c| c.column_id == column_id) {
+            return Err(format!("The column '{column_id}' has more than one filter"));
+        }
+        match &filter_column.filter {
+            ColumnFilter::Custom { conditions, .. } if !(1..=2).contains(&conditions.len()) => {
+                return Err("A custom filter needs one or two conditions".to_string());
+            }
+            ColumnFilter::Top10 { rank, percent, .. } if *rank == 0 || *percent && *rank > 100 => {
+                return Err(format!("Invalid top 10 rank: '{rank}'"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn operator_matches(operator: FilterOperator, ordering: Ordering) -> bool {
+    match operator {
+        FilterOperator::Equal => ordering == Ordering::Equal,
+        FilterOperator::NotEqual => ordering != Ordering::Equal,
+        FilterOperator::GreaterThan => ordering == Ordering::Greater,
+        FilterOperator::GreaterThanOrEqual => ordering != Ordering::Less,
+        FilterOperator::LessThan => ordering == Ordering::Less,
+        FilterOperator::LessThanOrEqual => ordering != Ordering::Greater,
+    }
+}
+
+/// `value` is the value of the cell and `text` its formatted value
+fn custom_filter_matches(condition: &CustomFilter, value: &CellValue, text: &str) -> bool {
+    let operator = condition.operator;
+    let target = condition.value.trim().parse::<f64>();
+    if let (CellValue::Number(number), Ok(target)) = (value, &target) {
+        return operator_matches(
+            operator,
+            number.partial_cmp(target).unwrap_or(Ordering::Equal),
+        );
+    }
+    match operator {
+        FilterOperator::Equal | FilterOperator::NotEqual => {
+            let matches = from_wildcard_to_regex(&condition.value.to_lowercase(), true)
+                .is_ok_and(|regex| regex.is_match(&text.to_lowercase()));
+            matches == (operator == FilterOperator::Equal)
+        }
+        _ => {
+            // Numbers and text cannot be compared and empty cells never match
+            if matches!(value, CellValue::Number(_) | CellValue::None) || target.is_ok() {
+                return false;
+            }
+            operator_matches(
+                operator,
+                text.to_lowercase().cmp(&condition.value.to_lowercase()),
+            )
+        }
+    }
+}
+
+/// `seconds` are the seconds since midnight
+fn date_group_matches(group: &DateGroup, date: NaiveDate, seconds: u32) -> bool {
+    let levels = [
+        (DateTimeGrouping::Year, date.year() == group.year),
+        (DateTimeGrouping::Month, date.month() == group.month),
+        (DateTimeGrouping::Day, date.day() == group.day),
+        (DateTimeGrouping::Hour, seconds / 3600 == group.hour),
+        (DateTimeGrouping::Minute, seconds / 60 % 60 == group.minute),
+        (DateTimeGrouping::Second, seconds % 60 == group.second),
+    ];
+    for (grouping, matches) in levels {
+        if !matches {
+            return false;
+        }
+        if grouping == group.grouping {
+            break;
+        }
+    }
+    true
+}
+
+impl Model {
+    /// Returns the date and the seconds since midnight of the cell if it has a date format
+    fn get_cell_date(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<(NaiveDate, u32)>, String> {
+        // 2_958_465 is 31 December 9999, the last date in the 1900 date system
+        let upper_bound = (2_958_466 - self.date_system_offset()) as f64;
+        let value = match self.get_cell_value_by_index(sheet, row, column)? {
+            CellValue::Number(value) if (0.0..upper_bound).contains(&value) => value,
+            _ => return Ok(None),
+        };
+        let num_fmt = self.get_style_for_cell(sheet, row, column)?.num_fmt;
+        let mut parser = Parser::new(&num_fmt);
+        parser.parse();
+        if !parser.parts.first().is_some_and(|part| part.is_date()) {
+            return Ok(None);
+        }
+        let days = value.floor();
+        let seconds = (((value - days) * 86_400.0).round() as u32).min(86_399);
+        Ok(Some((self.serial_number_to_date(days as i64), seconds)))
+    }
+
+    /// Returns whether the cells in `column` of each of the `rows` match the `filter`
+    fn filter_column_matches(
+        &self,
+        sheet: u32,
+        rows: &[i32],
+        column: i32,
+        filter: &ColumnFilter,
+    ) -> Result<Vec<bool>, String> {
+        let mut result = Vec::with_capacity(rows.len());
+        match filter {
+            ColumnFilter::Values {
+                values,
+                date_groups,
+                blank,
+            } => {
+                let values: Vec<String> = values.iter().map(|v| v.to_lowercase()).collect();
+                for &row in rows {
+                    let text = self.get_formatted_cell_value(sheet, row, column)?;
+                    let matches = if text.is_empty() {
+                        *blank
+                    } else if values.contains(&text.to_lowercase()) {
+                        true
+                    } else if date_groups.is_empty() {
+                        false
+                    } else {
+                        self.get_cell_date(sheet, row, column)?
+                            .is_some_and(|(date, seconds)| {
+                                date_groups
+                                    .iter()
+                                    .any(|group| date_group_matches(group, date, seconds))
+                            })
+                    };
+                    result.push(matches);
+                }
+            }
+            ColumnFilter::Custom { conditions, and } => {
+                for &row in rows {
+                    let value = self.get_cell_value_by_index(sheet, row, column)?;
+                    let text = self.get_formatted_cell_value(sheet, row, column)?;
+                    let mut matches = conditions
+                        .iter()
+                        .map(|condition| custom_filter_matches(condition, &value, &text));
+                    result.push(if *and {
+                        matches.all(|m| m)
+                    } else {
+                        matches.any(|m| m)
+                    });
+                }
+            }
+            ColumnFilter::Top10 {
+                rank,
+                percent,
+                bottom,
+            } => {
+                let mut values = Vec::with_capacity(rows.len());
+                for &row in rows {
+                    values.push(match self.get_cell_value_by_index(sheet, row, column)? {
+                        CellValue::Number(value) => Some(value),
+                        _ => None,
+                    });
+                }
+                let mut numbers: Vec<f64> = values.iter().flatten().copied().collect();
+                if numbers.is_empty() {
+                    return Ok(vec![false; rows.len()]);
+                }
+                numbers.sort_by(|a, b| a.total_cmp(b));
+                let count = if *percent {
+                    ((numbers.len() as f64 * *rank as f64 / 100.0).floor() as usize).max(1)
+                } else {
+                    *rank as usize
+                };
+                let count = count.clamp(1, numbers.len());
+                let threshold = if *bottom {
+                    numbers[count - 1]
+                } else {
+                    numbers[numbers.len() - count]
+                };
+                for value in values {
+                    result.push(value.is_some_and(|f| {
+                        if *bottom {
+                            f <= threshold
+                        } else {
+                            f >= threshold
+                        }
+                    }));
+                }
+            }
+            ColumnFilter::Color { color, cell_color } => {
+                for &row in rows {
+                    let style = self.get_effective_style_for_cell(sheet, row, column)?;
+                    let cell_color = if *cell_color {
+                        style.fill.fg_color
+                    } else {
+                        style.font.color
+                    };
+                    result.push(cell_color.is_some_and(|c| c.eq_ignore_ascii_case(color)));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the rows from `row1` to `row2` and whether they match all the `columns` filters.
+    /// `column1` is the first column of the filter.
+    fn get_filter_rows(
+        &self,
+        sheet: u32,
+        (row1, column1, row2): (i32, i32, i32),
+        columns: &[FilterColumn],
+    ) -> Result<Vec<(i32, bool)>, String> {
+        // There is nothing to filter below the last row with data
+        let row2 = row2.min(self.workbook.worksheet(sheet)?.dimension().max_row);
+        let rows: Vec<i32> = (row1..=row2).collect();
+        let mut visible = vec![true; rows.len()];
+        for filter_column in columns {
+            let column = column1 + filter_column.column_id as i32;
+            let matches =
+                self.filter_column_matches(sheet, &rows, column, &filter_column.filter)?;
+            for (visible, matches) in visible.iter_mut().zip(matches) {
+                *visible = *visible && matches;
+            }
+        }
+        Ok(rows.into_iter().zip(visible).collect())
+    }
+
+    /// Returns the index of the sheet of the `table`
+    pub(crate) fn get_table_sheet(&self, table: &str) -> Result<u32, String> {
+        let table = self
+            .workbook
+            .tables
+            .get(table)
+            .ok_or_else(|| format!("Table not found: '{table}'"))?;
+        self.workbook
+            .worksheets
+            .iter()
+            .position(|worksheet| worksheet.name == table.sheet_name)
+            .map(|index| index as u32)
+            .ok_or_else(|| format!("Sheet not found: '{}'", table.sheet_name))
+    }
+
+    /// Returns the autofilter of the `sheet`, if any
+    pub fn get_auto_filter(&self, sheet: u32) -> Result<Option<AutoFilter>, String> {
+        Ok(self.workbook.worksheet(sheet)?.auto_filter.clone())
+    }
+
+    /// Sets the autofilter of the `sheet`, or removes it if `auto_filter` is None.
+    /// Rows are not hidden or shown, see [Model::apply_auto_filter].
+    pub fn set_auto_filter(
+        &mut self,
+        sheet: u32,
+        auto_filter: Option<AutoFilter>,
+    ) -> Result<(), String> {
+        if let Some(auto_filter) = &auto_filter {
+            let (_, column1, _, column2) = parse_range_reference(&auto_filter.reference)?;
+            check_filter_columns(&auto_filter.columns, column2 - column1 + 1)?;
+        }
+        self.workbook.worksheet_mut(sheet)?.auto_filter = auto_filter;
+        Ok(())
+    }
+
+    /// Returns the rows of the autofilter of the `sheet` below the header row and whether they
+    /// match its criteria
+    pub(crate) fn get_auto_filter_rows(&self, sheet: u32) -> Result<Vec<(i32, bool)>, String> {
+        match &self.workbook.worksheet(sheet)?.auto_filter {
+            Some(auto_filter) => {
+                let (row1, column1, row2, _) = parse_range_reference(&auto_filter.reference)?;
+                self.get_filter_rows(sheet, (row1 + 1, column1, row2), &auto_filter.columns)
+            }
+            None => Err("The sheet has no autofilter".to_string()),
+        }
+    }
+
+    /// Hides the rows of the autofilter of the `sheet` that do not match its criteria and shows
+    /// the others
+    pub fn apply_auto_filter(&mut self, sheet: u32) -> Result<(), String> {
+        for (row, visible) in self.get_auto_filter_rows(sheet)? {
+            self.set_row_hidden(sheet, row, !visible)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the filters of the columns of the `table`
+    pub fn get_table_filter(&self, table: &str) -> Result<Vec<FilterColumn>, String> {
+        match self.workbook.tables.get(table) {
+            Some(table) => Ok(table.filter_columns.clone()),
+            None => Err(format!("Table not found: '{table}'")),
+        }
+    }
+
+    /// Sets the filters of the columns of the `table`.
+    /// Rows are not hidden or shown, see [Model::apply_table_filter].
+    pub fn set_table_filter(
+        &mut self,
+        table: &str,
+        columns: Vec<FilterColumn>,
+    ) -> Result<(), String> {
+        let table = self
+            .workbook
+            .tables
+            .get_mut(table)
+            .ok_or_else(|| format!("Table not found: '{table}'"))?;
+        let (_, column1, _, column2) = parse_range_reference(&table.reference)?;
+        check_filter_columns(&columns, column2 - column1 + 1)?;
+        table.has_filters = !columns.is_empty();
+        table.filter_columns = columns;
+        Ok(())
+    }
+
+    /// Returns the sheet of the `table`, its data rows and whether they match its filters
+    pub(crate) fn get_table_filter_rows(
+        &self,
+        table: &str,
+    ) -> Result<(u32, Vec<(i32, bool)>), String> {
+        let sheet = self.get_table_sheet(table)?;
+        let table = &self.workbook.tables[table];
+        let (row1, column1, row2, _) = parse_range_reference(&table.reference)?;
+        let row1 = row1 + table.header_row_count as i32;
+        let row2 = row2 - table.totals_row_count as i32;
+        let rows = self.get_filter_rows(sheet, (row1, column1, row2), &table.filter_columns)?;
+        Ok((sheet, rows))
+    }
+
+    /// Hides the data rows of the `table` that do not match its filters and shows the others
+    pub fn apply_table_filter(&mut self, table: &str) -> Result<(), String> {
+        let (sheet, rows) = self.get_table_filter_rows(table)?;
+        for (row, visible) in rows {
+            self.set_row_hidden(sheet, row, !visible)?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if the cell is in the data of a table or an autofilter with filtered columns
+    pub(crate) fn is_cell_in_filtered_range(&self, sheet: u32, row: i32, column: i32) -> bool {
+        let worksheet = match self.workbook.worksheet(sheet) {
+            Ok(worksheet) => worksheet,
+            Err(_) => return false,
+        };
+        let contains = |reference: &str| {
+            parse_range_reference(reference).is_ok_and(|(row1, column1, row2, column2)| {
+                (row1..=row2).contains(&row) && (column1..=column2).contains(&column)
+            })
+        };
+        if let Some(auto_filter) = &worksheet.auto_filter {
+            if !auto_filter.columns.is_empty() && contains(&auto_filter.reference) {
+                return true;
+            }
+        }
+        self.workbook.tables.values().any(|table| {
+            table.has_filters && table.sheet_name == worksheet.name && contains(&table.reference)
+        })
+    }
+
+    /// Moves the autofilter after inserting (`delta` > 0) or deleting (`delta` < 0) rows at `row`.
+    /// The autofilter is removed if all its rows are deleted.
+    pub(crate) fn displace_auto_filter_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        let auto_filter = match &mut worksheet.auto_filter {
+            Some(auto_filter) => auto_filter,
+            None => return Ok(()),
+        };
+        let range = parse_range_reference(&auto_filter.reference)?;
+        match displace_range_rows(range, row, delta) {
+            Some(range) => auto_filter.reference = range_to_string(range)?,
+            None => worksheet.auto_filter = None,
+        }
+        Ok(())
+    }
+
+    /// Moves the autofilter after inserting (`delta` > 0) or deleting (`delta` < 0) columns at
+    /// `column`. The filters of deleted columns are removed, and the autofilter if all its
+    /// columns are deleted.
+    pub(crate) fn displace_auto_filter_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        let auto_filter = match &mut worksheet.auto_filter {
+            Some(auto_filter) => auto_filter,
+            None => return Ok(()),
+        };
+        let (row1, column1, row2, column2) = parse_range_reference(&auto_filter.reference)?;
+        let (new_column1, new_column2) = match displace_interval(column1, column2, column, delta) {
+            Some(columns) => columns,
+            None => {
+                worksheet.auto_filter = None;
+                return Ok(());
+            }
+        };
+        auto_filter.columns.retain_mut(|filter_column| {
+            match displace_index(column1 + filter_column.column_id as i32, column, delta) {
+                Some(c) => {
+                    filter_column.column_id = (c - new_column1) as u32;
+                    true
+                }
+                None => false,
+            }
+        });
+        auto_filter.reference = range_to_string((row1, new_column1, row2, new_column2))?;
+        Ok(())
+    }
+
+    /// Returns the autofilter of the `sheet` after the `row_count` rows starting at `row` move by
+    /// `delta`. The rows of the autofilter can be moved inside it but not its header row.
+    pub(crate) fn get_auto_filter_after_rows_move(
+        &self,
+        sheet: u32,
+        row: i32,
+        row_count: i32,
+        delta: i32,
+    ) -> Result<Option<AutoFilter>, String> {
+        let auto_filter = match &self.workbook.worksheet(sheet)?.auto_filter {
+            Some(auto_filter) => auto_filter,
+            None => return Ok(None),
+        };
+        let (row1, column1, row2, column2) = parse_range_reference(&auto_filter.reference)?;
+        let (new_row1, new_row2) = get_moved_interval(row1, row2, row, row_count, delta)
+            .ok_or_else(|| AUTO_FILTER_MOVE_ERROR.to_string())?;
+        if get_moved_index(row1, row, row_count, delta) != new_row1 {
+            return Err(AUTO_FILTER_MOVE_ERROR.to_string());
+        }
+        Ok(Some(AutoFilter {
+            reference: range_to_string((new_row1, column1, new_row2, column2))?,
+            columns: auto_filter.columns.clone(),
+        }))
+    }
+
+    /// Returns the autofilter of the `sheet` after the `column_count` columns starting at `column`
+    /// move by `delta`. The filters of the columns moved inside the autofilter move with them.
+    pub(crate) fn get_auto_filter_after_columns_move(
+        &self,
+        sheet: u32,
+        column: i32,
+        column_count: i32,
+        delta: i32,
+    ) -> Result<Option<AutoFilter>, String> {
+        let auto_filter = match &self.workbook.worksheet(sheet)?.auto_filter {
+            Some(auto_filter) => auto_filter,
+            None => return Ok(None),
+        };
+        let (row1, column1, row2, column2) = parse_range_reference(&auto_filter.reference)?;
+        let (new_column1, new_column2) =
+            get_moved_interval(column1, column2, column, column_count, delta)
+                .ok_or_else(|| AUTO_FILTER_MOVE_ERROR.to_string())?;
+        let columns = auto_filter
+            .columns
+            .iter()
+            .map(|filter_column| {
+                let c = column1 + filter_column.column_id as i32;
+                FilterColumn {
+                    column_id: (get_moved_index(c, column, column_count, delta) - new_column1)
+                        as u32,
+                    ..filter_column.clone()
+                }
+            })
+            .collect();
+        Ok(Some(AutoFilter {
+            reference: range_to_string((row1, new_column1, row2, new_column2))?,
+            columns,
+        }))
+    }
+
+    /// Returns the autofilter of the `sheet` after the cells in `source` (row1, column1, row2,
+    /// column2) move by (`row_delta`, `column_delta`). It only moves if it is inside the `source`.
+    pub(crate) fn get_auto_filter_after_range_move(
+        &self,
+        sheet: u32,
+        source: (i32, i32, i32, i32),
+        row_delta: i32,
+        column_delta: i32,
+    ) -> Result<Option<AutoFilter>, String> {
+        let auto_filter = match &self.workbook.worksheet(sheet)?.auto_filter {
+            Some(auto_filter) => auto_filter,
+            None => return Ok(None),
+        };
+        let range = parse_range_reference(&auto_filter.reference)?;
+        if !contains(source, range) {
+            return Ok(Some(auto_filter.clone()));
+        }
+        let (row1, column1, row2, column2) = range;
+        Ok(Some(AutoFilter {
+            reference: range_to_string((
+                row1 + row_delta,
+                column1 + column_delta,
+                row2 + row_delta,
+                column2 + column_delta,
+            ))?,
+            columns: auto_filter.columns.clone(),
+        }))
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{
+    displacement::{
+        displace_anchored, displace_cell_columns, displace_cell_rows, filter_anchored, CellAnchored,
+    },
+    expressions::utils::{is_valid_column_number, is_valid_row, number_to_column},
+    model::{get_milliseconds_since_epoch, Model},
+    types::{Comment, CommentReply},
+};
+
+pub(crate) fn cell_ref(row: i32, column: i32) -> Result<String, String> {
+    if !is_valid_row(row) {
+        return Err(format!("Invalid row: '{row}'"));
+    }
+    match number_to_column(column) {
+        Some(column_name) if is_valid_column_number(column) => Ok(format!("{column_name}{row}")),
+        _ => Err(format!("Invalid column: '{column}'")),
+    }
+}
+
+impl Model {
+    fn comment_index(&self, sheet: u32, row: i32, column: i32) -> Result<Option<usize>, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .comments
+            .iter()
+            .position(|comment| comment.cell() == Some((row, column))))
+    }
+
+    fn get_comment_mut(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<&mut Comment, String> {
+        match self.comment_index(sheet, row, column)? {
+            Some(index) => Ok(&mut self.workbook.worksheet_mut(sheet)?.comments[index]),
+            None => Err("The cell has no comment".to_string()),
+        }
+    }
+
+    /// Returns all the comments in the `sheet`
+    pub fn get_comments(&self, sheet: u32) -> Result<Vec<Comment>, String> {
+        Ok(self.workbook.worksheet(sheet)?.comments.clone())
+    }
+
+    /// Returns the comment in the cell, if any
+    pub fn get_comment(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Comment>, String> {
+        Ok(self
+            .comment_index(sheet, row, column)?
+            .map(|index| self.workbook.worksheets[sheet as usize].comments[index].clone()))
+    }
+
+    /// Adds a comment to the cell. A cell can have only one comment, use replies for a conversation.
+    pub fn add_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> Result<(), String> {
+        if self.comment_index(sheet, row, column)?.is_some() {
+            return Err("The cell already has a comment".to_string());
+        }
+        let comment = Comment {
+            text: text.to_string(),
+            author_name: author_name.to_string(),
+            author_id,
+            cell_ref: cell_ref(row, column)?,
+            timestamp: Some(get_milliseconds_since_epoch()),
+            resolved: false,
+            replies: vec![],
+        };
+        self.workbook.worksheet_mut(sheet)?.comments.push(comment);
+        Ok(())
+    }
+
+    /// Changes the text of the comment in the cell
+    pub fn edit_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+    ) -> Result<(), String> {
+        self.get_comment_mut(sheet, row, column)?.text = text.to_string();
+        Ok(())
+    }
+
+    /// Marks the thread of the comment in the cell as resolved (or not)
+    pub fn resolve_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        resolved: bool,
+    ) -> Result<(), String> {
+        self.get_comment_mut(sheet, row, column)?.resolved = resolved;
+        Ok(())
+    }
+
+    /// Adds a reply to the thread of the comment in the cell
+    pub fn add_comment_reply(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> Result<(), String> {
+        let reply = CommentReply {
+            text: text.to_string(),
+            author_name: author_name.to_string(),
+            author_id,
+            timestamp: Some(get_milliseconds_since_epoch()),
+        };
+        self.get_comment_mut(sheet, row, column)?
+            .replies
+            .push(reply);
+        Ok(())
+    }
+
+    /// Deletes the comment in the cell and all its replies
+    pub fn delete_comment(&mut self, sheet: u32, row: i32, column: i32) -> Result<(), String> {
+        match self.comment_index(sheet, row, column)? {
+            Some(index) => {
+                self.workbook.worksheet_mut(sheet)?.comments.remove(index);
+                Ok(())
+            }
+            None => Err("The cell has no comment".to_string()),
+        }
+    }
+
+    /// Returns the comments in the cells of `row`
+    pub(crate) fn get_comments_in_row(&self, sheet: u32, row: i32) -> Result<Vec<Comment>, String> {
+        let comments = &self.workbook.worksheet(sheet)?.comments;
+        Ok(filter_anchored(comments, |(r, _)| r == row))
+    }
+
+    /// Returns the comments in the cells of `column`
+    pub(crate) fn get_comments_in_column(
+        &self,
+        sheet: u32,
+        column: i32,
+    ) -> Result<Vec<Comment>, String> {
+        let comments = &self.workbook.worksheet(sheet)?.comments;
+        Ok(filter_anchored(comments, |(_, c)| c == column))
+    }
+
+    /// Replaces the comment in the cell, adds it if there is none or removes it if `comment` is None
+    pub(crate) fn set_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        comment: Option<Comment>,
+    ) -> Result<(), String> {
+        let index = self.comment_index(sheet, row, column)?;
+        let comments = &mut self.workbook.worksheet_mut(sheet)?.comments;
+        match (index, comment) {
+            (Some(index), Some(comment)) => comments[index] = comment,
+            (Some(index), None) => {
+                comments.remove(index);
+            }
+            (None, Some(comment)) => comments.push(comment),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Moves the comments after inserting (`delta` > 0) or deleting (`delta` < 0) rows at `row`.
+    /// Comments in deleted rows are removed.
+    pub(crate) fn displace_comments_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        self.displace_comments(sheet, |cell| displace_cell_rows(cell, row, delta))
+    }
+
+    /// Moves the comments after inserting (`delta` > 0) or deleting (`delta` < 0) columns at `column`.
+    /// Comments in deleted columns are removed.
+    pub(crate) fn displace_comments_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        self.displace_comments(sheet, |cell| displace_cell_columns(cell, column, delta))
+    }
+
+    /// Moves each comment to the cell given by `displace`, or removes it if that is None
+    pub(crate) fn displace_comments(
+        &mut self,
+        sheet: u32,
+        displace: impl Fn((i32, i32)) -> Option<(i32, i32)>,
+    ) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        worksheet.comments = displace_anchored(&worksheet.comments, displace)?;
+        Ok(())
+    }
+}
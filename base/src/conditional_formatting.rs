@@ -0,0 +1,914 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    calc_result::CalcResult,
+    constants::{LAST_COLUMN, LAST_ROW},
+    displacement::{displace_range_columns, displace_range_rows},
+    expressions::{
+        parser::{
+            move_formula::{move_formula, MoveContext},
+            stringify::{to_string_displaced, DisplaceData},
+            Node,
+        },
+        types::{Area, CellReferenceIndex, CellReferenceRC},
+        utils::{number_to_column, parse_reference_a1},
+    },
+    functions::util::compare_values,
+    model::Model,
+    types::{
+        CellIsOperator, Cfvo, CfvoKind, ConditionalFormat, ConditionalFormatResult,
+        ConditionalFormatRule, DataBarResult, Dxf, Fill, IconResult, Style,
+    },
+};
+
+/// What a rule does to one of the cells it applies to
+enum RuleOutcome {
+    Style,
+    FillColor(String),
+    DataBar(DataBarResult),
+    Icon(IconResult),
+}
+
+/// Parses a space separated list of ranges like "A1:C3 E5" into (row1, column1, row2, column2) tuples
+pub(crate) fn parse_sqref(sqref: &str) -> Result<Vec<(i32, i32, i32, i32)>, String> {
+    let mut ranges = Vec::new();
+    for range in sqref.split_whitespace() {
+        let (first, last) = range.split_once(':').unwrap_or((range, range));
+        match (parse_reference_a1(first), parse_reference_a1(last)) {
+            (Some(left), Some(right)) => ranges.push((
+                left.row.min(right.row),
+                left.column.min(right.column),
+                left.row.max(right.row),
+                left.column.max(right.column),
+            )),
+            _ => return Err(format!("Invalid range: '{range}'")),
+        }
+    }
+    if ranges.is_empty() {
+        return Err("Empty range".to_string());
+    }
+    Ok(ranges)
+}
+
+/// Returns the list of ranges in a string like "A1:C3 E5"
+fn sqref_to_string(ranges: &[(i32, i32, i32, i32)]) -> Result<String, String> {
+    let mut references = Vec::new();
+    for &(row1, column1, row2, column2) in ranges {
+        match (number_to_column(column1), number_to_column(column2)) {
+            (Some(first), _) if row1 == row2 && column1 == column2 => {
+                references.push(format!("{first}{row1}"))
+            }
+            (Some(first), Some(last)) => references.push(format!("{first}{row1}:{last}{row2}")),
+            _ => return Err("Invalid column".to_string()),
+        }
+    }
+    Ok(references.join(" "))
+}
+
+/// Applies `displace_range` to each of the ranges of the `sqref`.
+/// Returns None if all the ranges are removed.
+pub(crate) fn displace_sqref(
+    sqref: &str,
+    displace_range: impl Fn((i32, i32, i32, i32)) -> Option<(i32, i32, i32, i32)>,
+) -> Result<Option<String>, String> {
+    let ranges: Vec<_> = parse_sqref(sqref)?
+        .into_iter()
+        .filter_map(displace_range)
+        .collect();
+    if ranges.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(sqref_to_string(&ranges)?))
+}
+
+fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let red = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let green = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let blue = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some((red, green, blue))
+}
+
+/// Returns the color a fraction `t` (between 0 and 1) of the way from `start` to `end`
+fn interpolate_color(start: &str, end: &str, t: f64) -> Option<String> {
+    let (r1, g1, b1) = parse_color(start)?;
+    let (r2, g2, b2) = parse_color(end)?;
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Some(format!(
+        "#{:02X}{:02X}{:02X}",
+        mix(r1, r2),
+        mix(g1, g2),
+        mix(b1, b2)
+    ))
+}
+
+/// Same as PERCENTILE.INC. `values` must be sorted and not empty
+fn percentile(values: &[f64], k: f64) -> f64 {
+    let rank = k.clamp(0.0, 1.0) * (values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    values[lower] + (values[upper] - values[lower]) * (rank - lower as f64)
+}
+
+/// `comparisons` are the results of comparing the value with each of the formulas of the operator
+pub(crate) fn operator_matches(operator: CellIsOperator, comparisons: &[i32]) -> bool {
+    match operator {
+        CellIsOperator::Between => {
+            comparisons[0] >= 0 && comparisons[1] <= 0 || comparisons[0] <= 0 && comparisons[1] >= 0
+        }
+        CellIsOperator::NotBetween => {
+            !(comparisons[0] >= 0 && comparisons[1] <= 0
+                || comparisons[0] <= 0 && comparisons[1] >= 0)
+        }
+        CellIsOperator::Equal => comparisons[0] == 0,
+        CellIsOperator::NotEqual => comparisons[0] != 0,
+        CellIsOperator::GreaterThan => comparisons[0] > 0,
+        CellIsOperator::LessThan => comparisons[0] < 0,
+        CellIsOperator::GreaterThanOrEqual => comparisons[0] >= 0,
+        CellIsOperator::LessThanOrEqual => comparisons[0] <= 0,
+    }
+}
+
+pub(crate) fn is_true(value: &CalcResult) -> bool {
+    match value {
+        CalcResult::Boolean(b) => *b,
+        CalcResult::Number(f) => *f != 0.0,
+        _ => false,
+    }
+}
+
+/// Key used to find duplicated values. Strings are compared case insensitive.
+fn duplicate_key(value: &CalcResult) -> Option<String> {
+    match value {
+        CalcResult::Number(f) => Some(format!("n{f}")),
+        CalcResult::String(s) => Some(format!("s{}", s.to_lowercase())),
+        CalcResult::Boolean(b) => Some(format!("b{b}")),
+        _ => None,
+    }
+}
+
+/// Applies the properties of `source` that are not already set in `target`
+fn merge_dxf(target: &mut Dxf, source: &Dxf) {
+    if target.num_fmt.is_none() {
+        target.num_fmt.clone_from(&source.num_fmt);
+    }
+    if let Some(source_font) = &source.font {
+        let font = target.font.get_or_insert_with(Default::default);
+        font.b = font.b.or(source_font.b);
+        font.i = font.i.or(source_font.i);
+        font.u = font.u.or(source_font.u);
+        font.strike = font.strike.or(source_font.strike);
+        if font.color.is_none() {
+            font.color.clone_from(&source_font.color);
+        }
+    }
+    if target.fill_color.is_none() {
+        target.fill_color.clone_from(&source.fill_color);
+    }
+    if let Some(source_border) = &source.border {
+        let border = target.border.get_or_insert_with(Default::default);
+        if border.left.is_none() {
+            border.left.clone_from(&source_border.left);
+        }
+        if border.right.is_none() {
+            border.right.clone_from(&source_border.right);
+        }
+        if border.top.is_none() {
+            border.top.clone_from(&source_border.top);
+        }
+        if border.bottom.is_none() {
+            border.bottom.clone_from(&source_border.bottom);
+        }
+    }
+}
+
+/// Returns the formulas of a rule, including the values of its thresholds
+pub(crate) fn rule_formulas_mut(rule: &mut ConditionalFormatRule) -> Vec<&mut String> {
+    match rule {
+        ConditionalFormatRule::CellIs { formulas, .. } => formulas.iter_mut().collect(),
+        ConditionalFormatRule::Expression { formula } => vec![formula],
+        ConditionalFormatRule::ColorScale { cfvos, .. }
+        | ConditionalFormatRule::IconSet { cfvos, .. } => cfvos
+            .iter_mut()
+            .filter_map(|cfvo| cfvo.value.as_mut())
+            .collect(),
+        ConditionalFormatRule::DataBar { min, max, .. } => [min, max]
+            .into_iter()
+            .filter_map(|cfvo| cfvo.value.as_mut())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl Model {
+    /// Parses a formula of a conditional format or a data validation.
+    /// Relative references are relative to `origin`, the top left cell of the ranges of the rule.
+    pub(crate) fn parse_rule_formula(
+        &mut self,
+        formula: &str,
+        origin: CellReferenceIndex,
+    ) -> Result<Node, String> {
+        let sheet_name = self.workbook.worksheet(origin.sheet)?.get_name();
+        let node = self.parser.parse(
+            formula,
+            &Some(CellReferenceRC {
+                sheet: sheet_name,
+                row: origin.row,
+                column: origin.column,
+            }),
+        );
+        if let Node::ParseErrorKind { message, .. } = node {
+            return Err(format!("Invalid formula '{formula}': {message}"));
+        }
+        Ok(node)
+    }
+
+    /// Returns the `formula` of a rule of the `sheet` with the references displaced
+    /// like the ones in the cells. Formulas that cannot be parsed are left unchanged.
+    pub(crate) fn displace_rule_formula(
+        &mut self,
+        formula: &str,
+        origin: CellReferenceIndex,
+        displace_data: &DisplaceData,
+    ) -> String {
+        let node = match self.parse_rule_formula(formula, origin) {
+            Ok(node) => node,
+            Err(_) => return formula.to_string(),
+        };
+        let context = CellReferenceRC {
+            sheet: self.workbook.worksheets[origin.sheet as usize].get_name(),
+            row: origin.row,
+            column: origin.column,
+        };
+        to_string_displaced(&node, &context, displace_data)
+    }
+
+    /// Returns the `formula` of a rule of the `sheet` after the cells in `area` move by
+    /// (`row_delta`, `column_delta`). The references to cells in the area move with them.
+    /// Formulas that cannot be parsed are left unchanged.
+    pub(crate) fn move_rule_formula(
+        &mut self,
+        formula: &str,
+        origin: CellReferenceIndex,
+        area: &Area,
+        row_delta: i32,
+        column_delta: i32,
+    ) -> String {
+        let node = match self.parse_rule_formula(formula, origin) {
+            Ok(node) => node,
+            Err(_) => return formula.to_string(),
+        };
+        let sheet_name = self.workbook.worksheets[origin.sheet as usize].get_name();
+        move_formula(
+            &node,
+            &MoveContext {
+                source_sheet_name: &sheet_name,
+                row: origin.row,
+                column: origin.column,
+                area,
+                target_sheet_name: &sheet_name,
+                row_delta,
+                column_delta,
+            },
+        )
+    }
+
+    /// Changes the formulas of the conditional formats of all sheets with `displace_formula`,
+    /// that gets the formula and the cell it is relative to, and the ranges of the ones in
+    /// `sheet` with `displace_range`.
+    /// Conditional formats are removed if all their ranges are.
+    pub(crate) fn displace_conditional_formats(
+        &mut self,
+        sheet: u32,
+        displace_formula: impl Fn(&mut Model, &str, CellReferenceIndex) -> String,
+        displace_range: impl Fn((i32, i32, i32, i32)) -> Option<(i32, i32, i32, i32)>,
+    ) -> Result<(), String> {
+        for cf_sheet in 0..self.workbook.worksheets.len() as u32 {
+            let conditional_formats = self
+                .workbook
+                .worksheet(cf_sheet)?
+                .conditional_formats
+                .clone();
+            let mut displaced = Vec::new();
+            for mut conditional_format in conditional_formats {
+                let ranges = parse_sqref(&conditional_format.sqref)?;
+                // Formulas are relative to the top left cell of the rule
+                let origin = CellReferenceIndex {
+                    sheet: cf_sheet,
+                    row: ranges[0].0,
+                    column: ranges[0].1,
+                };
+                for formula in rule_formulas_mut(&mut conditional_format.rule) {
+                    *formula = displace_formula(self, formula, origin);
+                }
+                if cf_sheet == sheet {
+                    match displace_sqref(&conditional_format.sqref, &displace_range)? {
+                        Some(sqref) => conditional_format.sqref = sqref,
+                        None => continue,
+                    }
+                }
+                displaced.push(conditional_format);
+            }
+            self.workbook.worksheet_mut(cf_sheet)?.conditional_formats = displaced;
+        }
+        Ok(())
+    }
+
+    /// Moves the conditional formats of the `sheet` after inserting (`delta` > 0) or deleting
+    /// (`delta` < 0) rows at `row`. The references in their formulas are updated in all sheets.
+    pub(crate) fn displace_conditional_formats_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let displace_data = DisplaceData::Row { sheet, row, delta };
+        self.displace_conditional_formats(
+            sheet,
+            |model, formula, origin| model.displace_rule_formula(formula, origin, &displace_data),
+            |range| displace_range_rows(range, row, delta),
+        )
+    }
+
+    /// Moves the conditional formats of the `sheet` after inserting (`delta` > 0) or deleting
+    /// (`delta` < 0) columns at `column`. The references in their formulas are updated in all sheets.
+    pub(crate) fn displace_conditional_formats_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let displace_data = DisplaceData::Column {
+            sheet,
+            column,
+            delta,
+        };
+        self.displace_conditional_formats(
+            sheet,
+            |model, formula, origin| model.displace_rule_formula(formula, origin, &displace_data),
+            |range| displace_range_columns(range, column, delta),
+        )
+    }
+
+    fn check_conditional_format(
+        &mut self,
+        sheet: u32,
+        conditional_format: &ConditionalFormat,
+    ) -> Result<(), String> {
+        let ranges = parse_sqref(&conditional_format.sqref)?;
+        let origin = CellReferenceIndex {
+            sheet,
+            row: ranges[0].0,
+            column: ranges[0].1,
+        };
+        let mut formulas = Vec::new();
+        match &conditional_format.rule {
+            ConditionalFormatRule::CellIs {
+                operator,
+                formulas: values,
+            } => {
+                let expected = match operator {
+                    CellIsOperator::Between | CellIsOperator::NotBetween => 2,
+                    _ => 1,
+                };
+                if values.len() != expected {
+                    return Err(format!("Operator '{operator}' needs {expected} formula(s)"));
+                }
+                formulas.extend(values.iter());
+            }
+            ConditionalFormatRule::Expression { formula } => formulas.push(formula),
+            ConditionalFormatRule::ColorScale { cfvos, colors } => {
+                if cfvos.len() != colors.len() || !(2..=3).contains(&cfvos.len()) {
+                    return Err("A color scale needs two or three colors".to_string());
+                }
+                for color in colors {
+                    if parse_color(color).is_none() {
+                        return Err(format!("Invalid color: '{color}'"));
+                    }
+                }
+            }
+            ConditionalFormatRule::IconSet { cfvos, .. } if cfvos.len() < 2 => {
+                return Err("An icon set needs at least two thresholds".to_string());
+            }
+            _ => {}
+        }
+        for formula in formulas {
+            self.parse_rule_formula(formula, origin)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the value of a threshold. `values` is the sorted list of numbers in the range.
+    fn get_cfvo_value(
+        &mut self,
+        cfvo: &Cfvo,
+        values: &[f64],
+        origin: CellReferenceIndex,
+    ) -> Option<f64> {
+        let min = *values.first()?;
+        let max = *values.last()?;
+        if cfvo.kind == CfvoKind::Min {
+            return Some(min);
+        }
+        if cfvo.kind == CfvoKind::Max {
+            return Some(max);
+        }
+        let value = cfvo.value.as_deref()?;
+        let number = match value.parse::<f64>() {
+            Ok(f) => f,
+            Err(_) => {
+                let node = self.parse_rule_formula(value, origin).ok()?;
+                match self.evaluate_node_in_context(&node, origin) {
+                    CalcResult::Number(f) => f,
+                    _ => return None,
+                }
+            }
+        };
+        match cfvo.kind {
+            CfvoKind::Percent => Some(min + (max - min) * number / 100.0),
+            CfvoKind::Percentile => Some(percentile(values, number / 100.0)),
+            _ => Some(number),
+        }
+    }
+
+    /// Decides which of the `cells` are affected by the `rule`
+    fn evaluate_conditional_format_rule(
+        &mut self,
+        rule: &ConditionalFormatRule,
+        origin: CellReferenceIndex,
+        cells: &[CellReferenceIndex],
+        values: &[CalcResult],
+    ) -> Vec<Option<RuleOutcome>> {
+        let mut numbers: Vec<f64> = values
+            .iter()
+            .filter_map(|value| match value {
+                CalcResult::Number(f) => Some(*f),
+                _ => None,
+            })
+            .collect();
+        numbers.sort_by(|a, b| a.total_cmp(b));
+        let as_number = |value: &CalcResult| match value {
+            CalcResult::Number(f) => Some(*f),
+            _ => None,
+        };
+        let style_if = |matches: bool| {
+            if matches {
+                Some(RuleOutcome::Style)
+            } else {
+                None
+            }
+        };
+        match rule {
+            ConditionalFormatRule::CellIs { operator, formulas } => {
+                let mut nodes = Vec::new();
+                for formula in formulas {
+                    match self.parse_rule_formula(formula, origin) {
+                        Ok(node) => nodes.push(node),
+                        Err(_) => return cells.iter().map(|_| None).collect(),
+                    }
+                }
+                let mut outcomes = Vec::new();
+                for (cell, value) in cells.iter().zip(values) {
+                    let value = match value {
+                        CalcResult::EmptyCell => CalcResult::Number(0.0),
+                        CalcResult::Error { .. } => {
+                            outcomes.push(None);
+                            continue;
+                        }
+                        _ => value.clone(),
+                    };
+                    let mut comparisons = Vec::new();
+                    for node in &nodes {
+                        match self.evaluate_node_in_context(node, *cell) {
+                            CalcResult::Error { .. } | CalcResult::Range { .. } => break,
+                            other => comparisons.push(compare_values(&value, &other)),
+                        }
+                    }
+                    if comparisons.len() != nodes.len() {
+                        outcomes.push(None);
+                        continue;
+                    }
+                    let matches = operator_matches(*operator, &comparisons);
+                    outcomes.push(style_if(matches));
+                }
+                outcomes
+            }
+            ConditionalFormatRule::Expression { formula } => {
+                let node = match self.parse_rule_formula(formula, origin) {
+                    Ok(node) => node,
+                    Err(_) => return cells.iter().map(|_| None).collect(),
+                };
+                cells
+                    .iter()
+                    .map(|cell| style_if(is_true(&self.evaluate_node_in_context(&node, *cell))))
+                    .collect()
+            }
+            ConditionalFormatRule::Top10 {
+                rank,
+                percent,
+                bottom,
+            } => {
+                if numbers.is_empty() {
+                    return cells.iter().map(|_| None).collect();
+                }
+                let count = if *percent {
+                    ((numbers.len() as f64 * *rank as f64 / 100.0).floor() as usize).max(1)
+                } else {
+                    *rank as usize
+                };
+                let count = count.clamp(1, numbers.len());
+                let threshold = if *bottom {
+                    numbers[count - 1]
+                } else {
+                    numbers[numbers.len() - count]
+                };
+                values
+                    .iter()
+                    .map(|value| {
+                        style_if(as_number(value).is_some_and(|f| {
+                            if *bottom {
+                                f <= threshold
+                            } else {
+                                f >= threshold
+                            }
+                        }))
+                    })
+                    .collect()
+            }
+            ConditionalFormatRule::AboveAverage {
+                above_average,
+                equal_average,
+                std_dev,
+            } => {
+                if numbers.is_empty() {
+                    return cells.iter().map(|_| None).collect();
+                }
+                let n = numbers.len() as f64;
+                let average = numbers.iter().sum::<f64>() / n;
+                let deviation = (numbers.iter().map(|f| (f - average).powi(2)).sum::<f64>() / n)
+                    .sqrt()
+                    * *std_dev as f64;
+                values
+                    .iter()
+                    .map(|value| {
+                        style_if(as_number(value).is_some_and(|f| {
+                            match (above_average, equal_average) {
+                                (true, true) => f >= average + deviation,
+                                (true, false) => f > average + deviation,
+                                (false, true) => f <= average - deviation,
+                                (false, false) => f < average - deviation,
+                            }
+                        }))
+                    })
+                    .collect()
+            }
+            ConditionalFormatRule::DuplicateValues | ConditionalFormatRule::UniqueValues => {
+                let mut counts: HashMap<String, usize> = HashMap::new();
+                for key in values.iter().filter_map(duplicate_key) {
+                    *counts.entry(key).or_default() += 1;
+                }
+                let duplicates = matches!(rule, ConditionalFormatRule::DuplicateValues);
+                values
+                    .iter()
+                    .map(|value| {
+                        style_if(duplicate_key(value).is_some_and(|key| {
+                            let count = counts.get(&key).copied().unwrap_or_default();
+                            (count > 1) == duplicates
+                        }))
+                    })
+                    .collect()
+            }
+            ConditionalFormatRule::ColorScale { cfvos, colors } => {
+                let mut thresholds = Vec::new();
+                for cfvo in cfvos {
+                    match self.get_cfvo_value(cfvo, &numbers, origin) {
+                        Some(threshold) => thresholds.push(threshold),
+                        None => return cells.iter().map(|_| None).collect(),
+                    }
+                }
+                values
+                    .iter()
+                    .map(|value| {
+                        let f = as_number(value)?;
+                        let last = thresholds.len() - 1;
+                        if f <= thresholds[0] {
+                            return Some(RuleOutcome::FillColor(colors[0].clone()));
+                        }
+                        if f >= thresholds[last] {
+                            return Some(RuleOutcome::FillColor(colors[last].clone()));
+                        }
+                        let index = thresholds.iter().rposition(|t| f >= *t).unwrap_or(0);
+                        let (low, high) = (thresholds[index], thresholds[index + 1]);
+                        let t = if high > low {
+                            (f - low) / (high - low)
+                        } else {
+                            0.0
+                        };
+                        interpolate_color(&colors[index], &colors[index + 1], t)
+                            .map(RuleOutcome::FillColor)
+                    })
+                    .collect()
+            }
+            ConditionalFormatRule::DataBar { min, max, color } => {
+                let (min, max) = match (
+                    self.get_cfvo_value(min, &numbers, origin),
+                    self.get_cfvo_value(max, &numbers, origin),
+                ) {
+                    (Some(min), Some(max)) => (min, max),
+                    _ => return cells.iter().map(|_| None).collect(),
+                };
+                values
+                    .iter()
+                    .map(|value| {
+                        let f = as_number(value)?;
+                        let length = if max > min {
+                            ((f - min) / (max - min)).clamp(0.0, 1.0)
+                        } else {
+                            1.0
+                        };
+                        Some(RuleOutcome::DataBar(DataBarResult {
+                            color: color.clone(),
+                            length,
+                        }))
+                    })
+                    .collect()
+            }
+            ConditionalFormatRule::IconSet {
+                icon_set,
+                cfvos,
+                reverse,
+                show_value,
+            } => {
+                let mut thresholds = Vec::new();
+                for cfvo in cfvos {
+                    match self.get_cfvo_value(cfvo, &numbers, origin) {
+                        Some(threshold) => thresholds.push(threshold),
+                        None => return cells.iter().map(|_| None).collect(),
+                    }
+                }
+                values
+                    .iter()
+                    .map(|value| {
+                        let f = as_number(value)?;
+                        // The first threshold is the lower bound of the first icon
+                        let index = thresholds[1..].iter().filter(|t| f >= **t).count() as u32;
+                        let index = if *reverse {
+                            thresholds.len() as u32 - 1 - index
+                        } else {
+                            index
+                        };
+                        Some(RuleOutcome::Icon(IconResult {
+                            icon_set: icon_set.clone(),
+                            index,
+                            show_value: *show_value,
+                        }))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Computes the outcome of the conditional formats of the `sheet` with the current values.
+    /// Ranges spanning whole rows or columns are limited to the used area of the sheet.
+    pub(crate) fn evaluate_conditional_formats(&mut self, sheet: u32) {
+        self.conditional_format_results
+            .retain(|(result_sheet, _, _), _| *result_sheet != sheet);
+        let (conditional_formats, dimension) = match self.workbook.worksheet(sheet) {
+            Ok(worksheet) => (worksheet.conditional_formats.clone(), worksheet.dimension()),
+            Err(_) => return,
+        };
+        // Cells where a rule with `stop_if_true` has matched
+        let mut stopped = HashSet::new();
+        for conditional_format in conditional_formats {
+            let ranges = match parse_sqref(&conditional_format.sqref) {
+                Ok(ranges) => ranges,
+                Err(_) => continue,
+            };
+            let origin = CellReferenceIndex {
+                sheet,
+                row: ranges[0].0,
+                column: ranges[0].1,
+            };
+            let mut seen = HashSet::new();
+            let mut cells = Vec::new();
+            for (row1, column1, mut row2, mut column2) in ranges {
+                // Ranges that run to the end of the sheet (A:A, A2:A1048576, 3:3, ...)
+                // are clamped to the sheet dimension
+                if row2 == LAST_ROW {
+                    row2 = dimension.max_row;
+                }
+                if column2 == LAST_COLUMN {
+                    column2 = dimension.max_column;
+                }
+                for row in row1..=row2 {
+                    for column in column1..=column2 {
+                        if seen.insert((row, column)) {
+                            cells.push(CellReferenceIndex { sheet, row, column });
+                        }
+                    }
+                }
+            }
+            let values: Vec<CalcResult> =
+                cells.iter().map(|cell| self.evaluate_cell(*cell)).collect();
+            let outcomes = self.evaluate_conditional_format_rule(
+                &conditional_format.rule,
+                origin,
+                &cells,
+                &values,
+            );
+            for (cell, outcome) in cells.iter().zip(outcomes) {
+                let key = (sheet, cell.row, cell.column);
+                if stopped.contains(&key) {
+                    continue;
+                }
+                let outcome = match outcome {
+                    Some(outcome) => outcome,
+                    None => continue,
+                };
+                let result = self.conditional_format_results.entry(key).or_default();
+                match outcome {
+                    RuleOutcome::Style => {
+                        if let Some(dxf) = &conditional_format.style {
+                            let style = result.style.get_or_insert_with(Default::default);
+                            let has_fill = style.fill_color.is_some();
+                            merge_dxf(style, dxf);
+                            // A color scale with higher priority wins
+                            if !has_fill && result.fill_color.is_some() {
+                                style.fill_color = None;
+                            }
+                        }
+                    }
+                    RuleOutcome::FillColor(color) => {
+                        let has_fill = result
+                            .style
+                            .as_ref()
+                            .is_some_and(|style| style.fill_color.is_some());
+                        if !has_fill && result.fill_color.is_none() {
+                            result.fill_color = Some(color);
+                        }
+                    }
+                    RuleOutcome::DataBar(data_bar) => {
+                        if result.data_bar.is_none() {
+                            result.data_bar = Some(data_bar);
+                        }
+                    }
+                    RuleOutcome::Icon(icon) => {
+                        if result.icon.is_none() {
+                            result.icon = Some(icon);
+                        }
+                    }
+                }
+                if conditional_format.stop_if_true {
+                    stopped.insert(key);
+                }
+            }
+        }
+    }
+
+    /// Returns the conditional formats of every sheet
+    pub(crate) fn get_all_conditional_formats(&self) -> Vec<Vec<ConditionalFormat>> {
+        self.workbook
+            .worksheets
+            .iter()
+            .map(|worksheet| worksheet.conditional_formats.clone())
+            .collect()
+    }
+
+    /// Restores the conditional formats of every sheet
+    pub(crate) fn set_all_conditional_formats(
+        &mut self,
+        conditional_formats: &[Vec<ConditionalFormat>],
+    ) {
+        for (worksheet, sheet_formats) in
+            self.workbook.worksheets.iter_mut().zip(conditional_formats)
+        {
+            worksheet.conditional_formats.clone_from(sheet_formats);
+        }
+    }
+
+    /// Returns the conditional formats of the `sheet`, sorted by priority
+    pub fn get_conditional_formats(&self, sheet: u32) -> Result<Vec<ConditionalFormat>, String> {
+        Ok(self.workbook.worksheet(sheet)?.conditional_formats.clone())
+    }
+
+    /// Inserts a conditional format in the `sheet` with priority `index` (0 is the highest priority).
+    /// The model needs to be evaluated for the changes to take effect.
+    pub fn insert_conditional_format(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        conditional_format: ConditionalFormat,
+    ) -> Result<(), String> {
+        self.check_conditional_format(sheet, &conditional_format)?;
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        if index > worksheet.conditional_formats.len() {
+            return Err("Invalid conditional format index".to_string());
+        }
+        worksheet
+            .conditional_formats
+            .insert(index, conditional_format);
+        Ok(())
+    }
+
+    /// Replaces the conditional format with priority `index` in the `sheet`.
+    /// The model needs to be evaluated for the changes to take effect.
+    pub fn set_conditional_format(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        conditional_format: ConditionalFormat,
+    ) -> Result<(), String> {
+        self.check_conditional_format(sheet, &conditional_format)?;
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        match worksheet.conditional_formats.get_mut(index) {
+            Some(old_value) => *old_value = conditional_format,
+            None => return Err("Invalid conditional format index".to_string()),
+        }
+        Ok(())
+    }
+
+    /// Deletes the conditional format with priority `index` in the `sheet`.
+    /// The model needs to be evaluated for the changes to take effect.
+    pub fn delete_conditional_format(&mut self, sheet: u32, index: usize) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        if index >= worksheet.conditional_formats.len() {
+            return Err("Invalid conditional format index".to_string());
+        }
+        worksheet.conditional_formats.remove(index);
+        Ok(())
+    }
+
+    /// Returns how the conditional formats change the display of the cell, if they do
+    pub fn get_conditional_format_result(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Option<ConditionalFormatResult> {
+        self.conditional_format_results
+            .get(&(sheet, row, column))
+            .cloned()
+    }
+
+    /// Returns the style of the cell with the conditional formats applied.
+    /// Data bars and icons are not part of the style, see [Model::get_conditional_format_result].
+    ///
+    /// See also:
+    /// * [Model::get_style_for_cell]
+    pub fn get_effective_style_for_cell(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Style, String> {
+        let mut style = self.get_style_for_cell(sheet, row, column)?;
+        let result = match self.conditional_format_results.get(&(sheet, row, column)) {
+            Some(result) => result,
+            None => return Ok(style),
+        };
+        let mut fill_color = result.fill_color.clone();
+        if let Some(dxf) = &result.style {
+            if let Some(num_fmt) = &dxf.num_fmt {
+                num_fmt.clone_into(&mut style.num_fmt);
+            }
+            if let Some(font) = &dxf.font {
+                style.font.b = font.b.unwrap_or(style.font.b);
+                style.font.i = font.i.unwrap_or(style.font.i);
+                style.font.u = font.u.unwrap_or(style.font.u);
+                style.font.strike = font.strike.unwrap_or(style.font.strike);
+                if font.color.is_some() {
+                    style.font.color.clone_from(&font.color);
+                    style.font.color_theme = None;
+                }
+            }
+            if dxf.fill_color.is_some() {
+                fill_color.clone_from(&dxf.fill_color);
+            }
+            if let Some(border) = &dxf.border {
+                if border.left.is_some() {
+                    style.border.left.clone_from(&border.left);
+                }
+                if border.right.is_some() {
+                    style.border.right.clone_from(&border.right);
+                }
+                if border.top.is_some() {
+                    style.border.top.clone_from(&border.top);
+                }
+                if border.bottom.is_some() {
+                    style.border.bottom.clone_from(&border.bottom);
+                }
+            }
+        }
+        if fill_color.is_some() {
+            style.fill = Fill {
+                pattern_type: "solid".to_string(),
+                fg_color: fill_color,
+                ..Default::default()
+            };
+        }
+        Ok(style)
+    }
+}
@@ -16,3 +16,6 @@ pub(crate) const LAST_ROW: i32 = 1_048_576;
 // NaiveDate::from_ymd(1900, 1, 1).num_days_from_ce() - 2
 // The 2 days offset is because of Excel 1900 bug
 pub(crate) const EXCEL_DATE_BASE: i32 = 693_594;
+
+// In the 1904 date system serial number 0 is 1 January 1904, which is 1_462 in the 1900 date system
+pub(crate) const EXCEL_DATE_1904_OFFSET: i64 = 1_462;
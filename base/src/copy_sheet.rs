@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use crate::{
+    conditional_formatting::{parse_sqref, rule_formulas_mut},
+    expressions::{
+        parser::{
+            stringify::{rename_sheet_in_node, to_rc_format, to_string},
+            Node, Parser,
+        },
+        types::CellReferenceRC,
+    },
+    model::Model,
+    types::{Cell, DefinedName, PivotSource, Table, Worksheet},
+};
+
+/// The worksheet, tables and sheet scoped defined names of a copy of a sheet
+type SheetCopy = (Worksheet, Vec<Table>, Vec<DefinedName>);
+
+/// Returns the `formula` (in A1 notation, relative to `context`) with the references to the sheet
+/// with index `sheet` renamed to `new_name`. Formulas that cannot be parsed are left unchanged.
+fn rename_sheet_in_formula(
+    parser: &mut Parser,
+    formula: &str,
+    context: &CellReferenceRC,
+    sheet: u32,
+    new_name: &str,
+) -> String {
+    let node = parser.parse(formula, &Some(context.clone()));
+    if let Node::ParseErrorKind { .. } = node {
+        return formula.to_string();
+    }
+    let mut renamed = node.clone();
+    rename_sheet_in_node(&mut renamed, sheet, new_name);
+    let renamed = to_string(&renamed, context);
+    if renamed == to_string(&node, context) {
+        formula.to_string()
+    } else {
+        renamed
+    }
+}
+
+impl Model {
+    /// Returns the first name like "Sheet1 (2)", "Sheet1 (3)", ... not used in the workbook.
+    /// A copy of "Sheet1 (2)" is called "Sheet1 (3)".
+    pub(crate) fn get_copy_sheet_name(&self, name: &str) -> String {
+        let base_name = match name.rsplit_once(" (") {
+            Some((base_name, number))
+                if number
+                    .strip_suffix(')')
+                    .is_some_and(|n| n.parse::<u32>().is_ok()) =>
+            {
+                base_name
+            }
+            _ => name,
+        };
+        let names: Vec<String> = self
+            .workbook
+            .get_worksheet_names()
+            .iter()
+            .map(|s| s.to_uppercase())
+            .collect();
+        let mut index = 2;
+        loop {
+            let suffix = format!(" ({index})");
+            // Sheet names have at most 31 characters
+            let length = 31 - suffix.len();
+            let sheet_name: String = base_name.chars().take(length).collect();
+            let sheet_name = format!("{sheet_name}{suffix}");
+            if !names.contains(&sheet_name.to_uppercase()) {
+                return sheet_name;
+            }
+            index += 1;
+        }
+    }
+
+    /// Renames the `tables` of a copied sheet with names already in use, like "Table1_2".
+    /// The pivot tables of the `worksheet` with those tables as source are updated.
+    fn rename_copied_tables(&self, worksheet: &mut Worksheet, tables: &mut [Table]) {
+        let mut used_names: Vec<String> = Vec::new();
+        let is_free = |name: &str, used_names: &[String]| {
+            self.check_table_name(name, None).is_ok()
+                && !used_names
+                    .iter()
+                    .any(|used| used.eq_ignore_ascii_case(name))
+        };
+        for table in tables.iter_mut() {
+            if !is_free(&table.name, &used_names) {
+                let mut index = 2;
+                while !is_free(&format!("{}_{index}", table.name), &used_names) {
+                    index += 1;
+                }
+                let name = format!("{}_{index}", table.name);
+                for pivot_table in &mut worksheet.pivot_tables {
+                    if let PivotSource::Table { name: source } = &mut pivot_table.source {
+                        if *source == table.name {
+                            *source = name.clone();
+                        }
+                    }
+                }
+                table.name = name.clone();
+                table.display_name = name;
+            }
+            used_names.push(table.name.clone());
+        }
+    }
+
+    /// Returns the formulas of the `sheet` with the references to the sheet itself renamed to
+    /// `new_name`
+    fn get_renamed_formulas(&self, sheet: u32, new_name: &str) -> Result<Vec<String>, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let nodes = match self.parsed_formulas.get(sheet as usize) {
+            Some(nodes) => nodes,
+            None => return Ok(worksheet.shared_formulas.clone()),
+        };
+        let mut formulas = Vec::new();
+        for (index, formula) in worksheet.shared_formulas.iter().enumerate() {
+            let formula = match nodes.get(index) {
+                Some(node) => {
+                    let mut renamed = node.clone();
+                    rename_sheet_in_node(&mut renamed, sheet, new_name);
+                    let renamed = to_rc_format(&renamed);
+                    if renamed == to_rc_format(node) {
+                        formula.clone()
+                    } else {
+                        renamed
+                    }
+                }
+                None => formula.clone(),
+            };
+            formulas.push(formula);
+        }
+        Ok(formulas)
+    }
+
+    /// Returns a copy of the `sheet` called `name` with its tables and sheet scoped defined names.
+    /// The formulas, conditional formats, data validations, defined names, tables and pivot
+    /// tables that refer to the sheet itself refer to the copy.
+    fn get_sheet_copy(&self, sheet: u32, name: &str, sheet_id: u32) -> Result<SheetCopy, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let mut copy = worksheet.clone();
+        copy.name = name.to_string();
+        copy.sheet_id = sheet_id;
+        copy.shared_formulas = self.get_renamed_formulas(sheet, name)?;
+        let mut parser = self.parser.clone();
+        let mut rename = |formula: &str, row: i32, column: i32| {
+            let context = CellReferenceRC {
+                sheet: worksheet.name.clone(),
+                row,
+                column,
+            };
+            rename_sheet_in_formula(&mut parser, formula, &context, sheet, name)
+        };
+        // Formulas of the rules are relative to the top left cell of their ranges
+        for conditional_format in &mut copy.conditional_formats {
+            if let Ok(ranges) = parse_sqref(&conditional_format.sqref) {
+                let (row, column, _, _) = ranges[0];
+                for formula in rule_formulas_mut(&mut conditional_format.rule) {
+                    *formula = rename(formula, row, column);
+                }
+            }
+        }
+        for data_validation in &mut copy.data_validations {
+            if let Ok(ranges) = parse_sqref(&data_validation.sqref) {
+                let (row, column, _, _) = ranges[0];
+                for formula in &mut data_validation.formulas {
+                    *formula = rename(formula, row, column);
+                }
+            }
+        }
+        let defined_names = self
+            .workbook
+            .defined_names
+            .iter()
+            .filter(|defined_name| defined_name.sheet_id == Some(worksheet.sheet_id))
+            .map(|defined_name| DefinedName {
+                name: defined_name.name.clone(),
+                formula: rename(&defined_name.formula, 1, 1),
+                sheet_id: Some(sheet_id),
+            })
+            .collect();
+        for pivot_table in &mut copy.pivot_tables {
+            if let PivotSource::Range { sheet_name, .. } = &mut pivot_table.source {
+                if *sheet_name == worksheet.name {
+                    *sheet_name = name.to_string();
+                }
+            }
+        }
+        let mut tables = self.get_tables(sheet)?;
+        for table in &mut tables {
+            table.sheet_name = name.to_string();
+        }
+        Ok((copy, tables, defined_names))
+    }
+
+    /// Inserts the `worksheet` at `index` with its `tables` and sheet scoped `defined_names`.
+    /// Nothing is validated.
+    pub(crate) fn insert_worksheet(
+        &mut self,
+        index: u32,
+        worksheet: Worksheet,
+        tables: Vec<Table>,
+        defined_names: Vec<DefinedName>,
+    ) -> Result<(), String> {
+        if index as usize > self.workbook.worksheets.len() {
+            return Err("Sheet index out of range".to_string());
+        }
+        for table in tables {
+            self.workbook.tables.insert(table.name.clone(), table);
+        }
+        self.workbook.defined_names.extend(defined_names);
+        self.workbook.worksheets.insert(index as usize, worksheet);
+        for view in self.workbook.views.values_mut() {
+            if view.sheet >= index {
+                view.sheet += 1;
+            }
+        }
+        self.reset_parsed_structures();
+        Ok(())
+    }
+
+    /// Inserts a copy of the `sheet` right after it, with a name like "Sheet1 (2)".
+    /// The copy has the data, styles, merged cells, column widths, comments, tables and sheet
+    /// scoped defined names of the sheet.
+    /// Returns the name and the index of the copy.
+    pub fn duplicate_sheet(&mut self, sheet: u32) -> Result<(String, u32), String> {
+        let name = self.get_copy_sheet_name(&self.workbook.worksheet(sheet)?.name);
+        let sheet_id = self.get_new_sheet_id();
+        let (mut worksheet, mut tables, defined_names) =
+            self.get_sheet_copy(sheet, &name, sheet_id)?;
+        self.rename_copied_tables(&mut worksheet, &mut tables);
+        self.insert_worksheet(sheet + 1, worksheet, tables, defined_names)?;
+        Ok((name, sheet + 1))
+    }
+
+    /// Returns a copy of the `sheet` ready to be inserted in the `target` workbook.
+    /// The styles and strings of the cells are added to the `target`. The sheet keeps its name
+    /// if it is not used in the `target`.
+    pub(crate) fn get_sheet_copy_for(
+        &self,
+        sheet: u32,
+        target: &mut Model,
+    ) -> Result<SheetCopy, String> {
+        let source_name = &self.workbook.worksheet(sheet)?.name;
+        let name = if target.get_sheet_index_by_name(source_name).is_some() {
+            target.get_copy_sheet_name(source_name)
+        } else {
+            source_name.clone()
+        };
+        let sheet_id = target.get_new_sheet_id();
+        let (mut worksheet, mut tables, defined_names) =
+            self.get_sheet_copy(sheet, &name, sheet_id)?;
+        target.rename_copied_tables(&mut worksheet, &mut tables);
+
+        let mut styles = HashMap::new();
+        let mut get_style = |target: &mut Model, index: i32| -> Result<i32, String> {
+            if let Some(new_index) = styles.get(&index) {
+                return Ok(*new_index);
+            }
+            let style = self.workbook.styles.get_style(index)?;
+            let new_index = target.workbook.styles.get_style_index_or_create(&style);
+            styles.insert(index, new_index);
+            Ok(new_index)
+        };
+        for data_row in worksheet.sheet_data.values_mut() {
+            for cell in data_row.values_mut() {
+                let style = get_style(target, cell.get_style())?;
+                cell.set_style(style);
+                if let Cell::SharedString { si, .. } = cell {
+                    let text = self
+                        .workbook
+                        .shared_strings
+                        .get(*si as usize)
+                        .cloned()
+                        .unwrap_or_default();
+                    *si = match self.workbook.rich_text.get(si) {
+                        // Strings with rich text are not shared with plain text cells
+                        Some(runs) => {
+                            let string_index = target.workbook.shared_strings.len() as i32;
+                            target.workbook.shared_strings.push(text);
+                            target.workbook.rich_text.insert(string_index, runs.clone());
+                            string_index
+                        }
+                        None => target.get_shared_string_index(&text),
+                    };
+                }
+            }
+        }
+        for row in &mut worksheet.rows {
+            row.s = get_style(target, row.s)?;
+        }
+        for col in &mut worksheet.cols {
+            if let Some(style) = col.style {
+                col.style = Some(get_style(target, style)?);
+            }
+        }
+        let view_ids: Vec<&u32> = target.workbook.views.keys().collect();
+        worksheet.views = Model::new_empty_worksheet(&name, sheet_id, &view_ids).views;
+        Ok((worksheet, tables, defined_names))
+    }
+
+    /// Copies the `sheet` at the end of the `target` workbook, see [Model::duplicate_sheet].
+    /// The styles and strings are added to the `target`, references to other sheets are kept by name.
+    /// Returns the index of the new sheet.
+    pub fn copy_sheet_to(&self, sheet: u32, target: &mut Model) -> Result<u32, String> {
+        let (worksheet, tables, defined_names) = self.get_sheet_copy_for(sheet, target)?;
+        let index = target.workbook.worksheets.len() as u32;
+        target.insert_worksheet(index, worksheet, tables, defined_names)?;
+        Ok(index)
+    }
+
+    /// Returns the index of `value` in the shared strings, adding it if needed
+    fn get_shared_string_index(&mut self, value: &str) -> i32 {
+        match self.shared_strings.get(value) {
+            Some(string_index) => *string_index as i32,
+            None => {
+                let string_index = self.workbook.shared_strings.len();
+                self.workbook.shared_strings.push(value.to_string());
+                self.shared_strings.insert(value.to_string(), string_index);
+                string_index as i32
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use crate::{
+    cell::CellValue,
+    formatter::format::parse_formatted_number,
+    model::Model,
+    types::{TextColumnFormat, TextSplit},
+};
+
+/// Splits the `text` in the parts that go to each column
+pub(crate) fn split_text(text: &str, split: &TextSplit) -> Vec<String> {
+    match split {
+        TextSplit::Delimited {
+            delimiters,
+            treat_consecutive_as_one,
+            text_qualifier,
+        } => {
+            let mut parts = Vec::new();
+            let mut part = String::new();
+            let mut is_qualified = false;
+            let mut chars = text.chars().peekable();
+            while let Some(c) = chars.next() {
+                if Some(c) == *text_qualifier {
+                    if is_qualified && chars.peek() == Some(&c) {
+                        part.push(c);
+                        chars.next();
+                    } else {
+                        is_qualified = !is_qualified;
+                    }
+                } else if !is_qualified && delimiters.contains(c) {
+                    parts.push(std::mem::take(&mut part));
+                } else {
+                    part.push(c);
+                }
+            }
+            parts.push(part);
+            if *treat_consecutive_as_one {
+                // The empty parts between consecutive delimiters are left out
+                let mut index = 0;
+                parts.retain(|part| {
+                    index += 1;
+                    index == 1 || !part.is_empty()
+                });
+            }
+            parts
+        }
+        TextSplit::FixedWidth { breaks } => {
+            let chars: Vec<char> = text.chars().collect();
+            let mut bounds = vec![0];
+            bounds.extend(
+                breaks
+                    .iter()
+                    .copied()
+                    .filter(|&position| position > 0 && position < chars.len()),
+            );
+            bounds.push(chars.len());
+            bounds.sort_unstable();
+            bounds.dedup();
+            bounds
+                .windows(2)
+                .map(|bound| chars[bound[0]..bound[1]].iter().collect())
+                .collect()
+        }
+    }
+}
+
+impl Model {
+    /// Returns the user input for a `text` in a column with the `format`. None if the column is
+    /// skipped or the text is empty.
+    pub(crate) fn get_text_column_input(
+        &self,
+        text: &str,
+        format: TextColumnFormat,
+    ) -> Option<String> {
+        if text.is_empty() {
+            return None;
+        }
+        match format {
+            TextColumnFormat::Skip => None,
+            TextColumnFormat::Text => Some(format!("'{text}")),
+            TextColumnFormat::General => {
+                // The separators of the locale are changed to the ones of the parser
+                let symbols = &self.locale.numbers.symbols;
+                let decimal = symbols.decimal.chars().next().unwrap_or('.');
+                let group = symbols.group.chars().next().unwrap_or(',');
+                let number: String = text
+                    .trim()
+                    .chars()
+                    .map(|c| match c {
+                        c if c == decimal => '.',
+                        c if c == group => ',',
+                        c => c,
+                    })
+                    .collect();
+                let currencies = self.get_currencies();
+                let date_1904 = self.workbook.settings.date_1904;
+                if parse_formatted_number(&number, &currencies, date_1904).is_ok() {
+                    Some(number)
+                } else {
+                    Some(format!("'{text}"))
+                }
+            }
+        }
+    }
+
+    /// Returns the rows from `row1` to `row2` that do not repeat the values in the `key_columns`
+    /// of a row above. Text is compared case insensitive.
+    pub(crate) fn get_unique_rows(
+        &self,
+        sheet: u32,
+        row1: i32,
+        row2: i32,
+        key_columns: &[i32],
+    ) -> Result<Vec<i32>, String> {
+        let mut keys = HashSet::new();
+        let mut rows = Vec::new();
+        for row in row1..=row2 {
+            let mut key = Vec::new();
+            for &column in key_columns {
+                key.push(match self.get_cell_value_by_index(sheet, row, column)? {
+                    CellValue::None => "".to_string(),
+                    CellValue::String(value) => format!("s{}", value.to_lowercase()),
+                    CellValue::Number(value) => format!("n{value}"),
+                    CellValue::Boolean(value) => format!("b{value}"),
+                });
+            }
+            if keys.insert(key) {
+                rows.push(row);
+            }
+        }
+        Ok(rows)
+    }
+}
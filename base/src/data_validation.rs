@@ -0,0 +1,347 @@
+use crate::{
+    calc_result::CalcResult,
+    conditional_formatting::{displace_sqref, is_true, operator_matches, parse_sqref},
+    displacement::{displace_range_columns, displace_range_rows},
+    expressions::{parser::stringify::DisplaceData, types::CellReferenceIndex},
+    functions::util::compare_values,
+    model::Model,
+    types::{CellIsOperator, DataValidation, DataValidationKind},
+};
+
+/// Returns the top left cell of the first range of the rule
+fn get_origin(sheet: u32, sqref: &str) -> Result<CellReferenceIndex, String> {
+    let ranges = parse_sqref(sqref)?;
+    Ok(CellReferenceIndex {
+        sheet,
+        row: ranges[0].0,
+        column: ranges[0].1,
+    })
+}
+
+/// A list like "\"Yes,No\"" is a literal list, anything else is a formula
+fn parse_literal_list(formula: &str) -> Option<Vec<String>> {
+    let list = formula.strip_prefix('"')?.strip_suffix('"')?;
+    Some(
+        list.split(',')
+            .map(|item| item.trim().to_string())
+            .collect(),
+    )
+}
+
+impl Model {
+    fn check_data_validation_rule(
+        &mut self,
+        sheet: u32,
+        data_validation: &DataValidation,
+    ) -> Result<(), String> {
+        let origin = get_origin(sheet, &data_validation.sqref)?;
+        let expected = match (data_validation.kind, data_validation.operator) {
+            (DataValidationKind::Any, _) => 0,
+            (DataValidationKind::List | DataValidationKind::Custom, _) => 1,
+            (_, CellIsOperator::Between | CellIsOperator::NotBetween) => 2,
+            _ => 1,
+        };
+        if data_validation.formulas.len() != expected {
+            return Err(format!(
+                "Data validation '{}' needs {expected} formula(s)",
+                data_validation.kind
+            ));
+        }
+        for formula in &data_validation.formulas {
+            if parse_literal_list(formula).is_none() {
+                self.parse_rule_formula(formula, origin)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the data validation that applies to the cell, if any
+    pub fn get_data_validation_for_cell(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<DataValidation>, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        for data_validation in &worksheet.data_validations {
+            let ranges = parse_sqref(&data_validation.sqref)?;
+            if ranges.iter().any(|(row1, column1, row2, column2)| {
+                (*row1..=*row2).contains(&row) && (*column1..=*column2).contains(&column)
+            }) {
+                return Ok(Some(data_validation.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the items of a list data validation as seen in the cell
+    fn get_data_validation_list(
+        &mut self,
+        data_validation: &DataValidation,
+        cell: CellReferenceIndex,
+    ) -> Result<Vec<String>, String> {
+        let formula = match data_validation.formulas.first() {
+            Some(formula) => formula,
+            None => return Ok(vec![]),
+        };
+        if let Some(list) = parse_literal_list(formula) {
+            return Ok(list);
+        }
+        let origin = get_origin(cell.sheet, &data_validation.sqref)?;
+        let node = self.parse_rule_formula(formula, origin)?;
+        let mut list = Vec::new();
+        match self.evaluate_node_in_context(&node, cell) {
+            CalcResult::Range { left, right } => {
+                for row in left.row..=right.row {
+                    for column in left.column..=right.column {
+                        let value = self.get_formatted_cell_value(left.sheet, row, column)?;
+                        if !value.is_empty() {
+                            list.push(value);
+                        }
+                    }
+                }
+            }
+            result => {
+                if let Ok(value) = self.cast_to_string(result, cell) {
+                    list.push(value);
+                }
+            }
+        }
+        Ok(list)
+    }
+
+    fn is_valid_data(
+        &mut self,
+        data_validation: &DataValidation,
+        cell: CellReferenceIndex,
+        value: CalcResult,
+    ) -> Result<bool, String> {
+        let is_blank = match &value {
+            CalcResult::EmptyCell => true,
+            CalcResult::String(s) => s.is_empty(),
+            _ => false,
+        };
+        if is_blank {
+            return Ok(data_validation.allow_blank);
+        }
+        if matches!(value, CalcResult::Error { .. }) {
+            return Ok(data_validation.kind == DataValidationKind::Any);
+        }
+        let origin = get_origin(cell.sheet, &data_validation.sqref)?;
+        let value = match data_validation.kind {
+            DataValidationKind::Any => return Ok(true),
+            DataValidationKind::List => {
+                let text = self
+                    .get_formatted_cell_value(cell.sheet, cell.row, cell.column)?
+                    .to_lowercase();
+                let list = self.get_data_validation_list(data_validation, cell)?;
+                return Ok(list.iter().any(|item| item.to_lowercase() == text));
+            }
+            DataValidationKind::Custom => {
+                let node = self.parse_rule_formula(&data_validation.formulas[0], origin)?;
+                return Ok(is_true(&self.evaluate_node_in_context(&node, cell)));
+            }
+            DataValidationKind::TextLength => match self.cast_to_string(value, cell) {
+                Ok(text) => CalcResult::Number(text.chars().count() as f64),
+                Err(_) => return Ok(false),
+            },
+            DataValidationKind::Whole => match value {
+                CalcResult::Number(f) if f.fract() == 0.0 => value,
+                _ => return Ok(false),
+            },
+            DataValidationKind::Decimal | DataValidationKind::Date | DataValidationKind::Time => {
+                match value {
+                    CalcResult::Number(_) => value,
+                    _ => return Ok(false),
+                }
+            }
+        };
+        let mut comparisons = Vec::new();
+        for formula in &data_validation.formulas {
+            let node = self.parse_rule_formula(formula, origin)?;
+            match self.evaluate_node_in_context(&node, cell) {
+                CalcResult::Number(f) => {
+                    comparisons.push(compare_values(&value, &CalcResult::Number(f)))
+                }
+                _ => return Ok(false),
+            }
+        }
+        Ok(operator_matches(data_validation.operator, &comparisons))
+    }
+
+    /// Returns the data validation the value of the cell does not satisfy, if any.
+    /// If the cell has a formula it is evaluated first.
+    pub fn check_data_validation(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<DataValidation>, String> {
+        let data_validation = match self.get_data_validation_for_cell(sheet, row, column)? {
+            Some(data_validation) => data_validation,
+            None => return Ok(None),
+        };
+        let cell = CellReferenceIndex { sheet, row, column };
+        self.cells.remove(&(sheet, row, column));
+        let value = self.evaluate_cell(cell);
+        if self.is_valid_data(&data_validation, cell, value)? {
+            Ok(None)
+        } else {
+            Ok(Some(data_validation))
+        }
+    }
+
+    /// Returns the options of the dropdown of the cell.
+    /// The list is empty unless the cell has a list data validation that shows a dropdown.
+    pub fn get_data_validation_options(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Vec<String>, String> {
+        match self.get_data_validation_for_cell(sheet, row, column)? {
+            Some(data_validation)
+                if data_validation.kind == DataValidationKind::List
+                    && data_validation.show_dropdown =>
+            {
+                let cell = CellReferenceIndex { sheet, row, column };
+                self.get_data_validation_list(&data_validation, cell)
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Changes the formulas of the data validations of all sheets with `displace_formula`, that
+    /// gets the formula and the cell it is relative to, and the ranges of the ones in `sheet`
+    /// with `displace_range`.
+    /// Data validations are removed if all their ranges are.
+    pub(crate) fn displace_data_validations(
+        &mut self,
+        sheet: u32,
+        displace_formula: impl Fn(&mut Model, &str, CellReferenceIndex) -> String,
+        displace_range: impl Fn((i32, i32, i32, i32)) -> Option<(i32, i32, i32, i32)>,
+    ) -> Result<(), String> {
+        for dv_sheet in 0..self.workbook.worksheets.len() as u32 {
+            let data_validations = self.workbook.worksheet(dv_sheet)?.data_validations.clone();
+            let mut displaced = Vec::new();
+            for mut data_validation in data_validations {
+                let origin = get_origin(dv_sheet, &data_validation.sqref)?;
+                for formula in data_validation.formulas.iter_mut() {
+                    if parse_literal_list(formula).is_none() {
+                        *formula = displace_formula(self, formula, origin);
+                    }
+                }
+                if dv_sheet == sheet {
+                    match displace_sqref(&data_validation.sqref, &displace_range)? {
+                        Some(sqref) => data_validation.sqref = sqref,
+                        None => continue,
+                    }
+                }
+                displaced.push(data_validation);
+            }
+            self.workbook.worksheet_mut(dv_sheet)?.data_validations = displaced;
+        }
+        Ok(())
+    }
+
+    /// Moves the data validations of the `sheet` after inserting (`delta` > 0) or deleting
+    /// (`delta` < 0) rows at `row`. The references in their formulas are updated in all sheets.
+    pub(crate) fn displace_data_validations_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let displace_data = DisplaceData::Row { sheet, row, delta };
+        self.displace_data_validations(
+            sheet,
+            |model, formula, origin| model.displace_rule_formula(formula, origin, &displace_data),
+            |range| displace_range_rows(range, row, delta),
+        )
+    }
+
+    /// Moves the data validations of the `sheet` after inserting (`delta` > 0) or deleting
+    /// (`delta` < 0) columns at `column`. The references in their formulas are updated in all sheets.
+    pub(crate) fn displace_data_validations_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let displace_data = DisplaceData::Column {
+            sheet,
+            column,
+            delta,
+        };
+        self.displace_data_validations(
+            sheet,
+            |model, formula, origin| model.displace_rule_formula(formula, origin, &displace_data),
+            |range| displace_range_columns(range, column, delta),
+        )
+    }
+
+    /// Returns the data validations of every sheet
+    pub(crate) fn get_all_data_validations(&self) -> Vec<Vec<DataValidation>> {
+        self.workbook
+            .worksheets
+            .iter()
+            .map(|worksheet| worksheet.data_validations.clone())
+            .collect()
+    }
+
+    /// Restores the data validations of every sheet
+    pub(crate) fn set_all_data_validations(&mut self, data_validations: &[Vec<DataValidation>]) {
+        for (worksheet, sheet_validations) in
+            self.workbook.worksheets.iter_mut().zip(data_validations)
+        {
+            worksheet.data_validations.clone_from(sheet_validations);
+        }
+    }
+
+    /// Returns the data validations of the `sheet`
+    pub fn get_data_validations(&self, sheet: u32) -> Result<Vec<DataValidation>, String> {
+        Ok(self.workbook.worksheet(sheet)?.data_validations.clone())
+    }
+
+    /// Inserts a data validation in the list of data validations of the `sheet` at `index`
+    pub fn insert_data_validation(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        data_validation: DataValidation,
+    ) -> Result<(), String> {
+        self.check_data_validation_rule(sheet, &data_validation)?;
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        if index > worksheet.data_validations.len() {
+            return Err("Invalid data validation index".to_string());
+        }
+        worksheet.data_validations.insert(index, data_validation);
+        Ok(())
+    }
+
+    /// Replaces the data validation at `index` in the `sheet`
+    pub fn set_data_validation(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        data_validation: DataValidation,
+    ) -> Result<(), String> {
+        self.check_data_validation_rule(sheet, &data_validation)?;
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        match worksheet.data_validations.get_mut(index) {
+            Some(old_value) => *old_value = data_validation,
+            None => return Err("Invalid data validation index".to_string()),
+        }
+        Ok(())
+    }
+
+    /// Deletes the data validation at `index` in the `sheet`
+    pub fn delete_data_validation(&mut self, sheet: u32, index: usize) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        if index >= worksheet.data_validations.len() {
+            return Err("Invalid data validation index".to_string());
+        }
+        worksheet.data_validations.remove(index);
+        Ok(())
+    }
+}
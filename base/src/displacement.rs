@@ -0,0 +1,181 @@
+//! Helpers to move the things anchored to cells or ranges of a sheet (comments, hyperlinks,
+//! autofilters, tables, conditional formats, ...) when rows or columns are inserted, deleted or
+//! moved.
+
+use crate::{
+    comments::cell_ref,
+    constants::{LAST_COLUMN, LAST_ROW},
+    expressions::{parser::stringify::get_moved_index, utils::parse_reference_a1},
+    types::{Comment, Hyperlink},
+};
+
+/// New index of `index` after inserting (`delta` > 0) or deleting (`delta` < 0) rows or columns
+/// at `at`. None if it was deleted.
+pub(crate) fn displace_index(index: i32, at: i32, delta: i32) -> Option<i32> {
+    if index < at {
+        Some(index)
+    } else if delta < 0 && index < at - delta {
+        None
+    } else {
+        Some(index + delta)
+    }
+}
+
+/// New first and last indices of an interval after inserting or deleting at `at`.
+/// None if all of it was deleted.
+pub(crate) fn displace_interval(first: i32, last: i32, at: i32, delta: i32) -> Option<(i32, i32)> {
+    let first = displace_index(first, at, delta).unwrap_or(at);
+    let last = displace_index(last, at, delta).unwrap_or(at - 1);
+    if last < first {
+        None
+    } else {
+        Some((first, last))
+    }
+}
+
+/// New `range` (row1, column1, row2, column2) after inserting (`delta` > 0) or deleting
+/// (`delta` < 0) rows at `row`. None if all of it was deleted.
+pub(crate) fn displace_range_rows(
+    (row1, column1, row2, column2): (i32, i32, i32, i32),
+    row: i32,
+    delta: i32,
+) -> Option<(i32, i32, i32, i32)> {
+    let (row1, row2) = displace_interval(row1, row2, row, delta)?;
+    Some((row1, column1, row2.min(LAST_ROW), column2))
+}
+
+/// New `range` (row1, column1, row2, column2) after inserting (`delta` > 0) or deleting
+/// (`delta` < 0) columns at `column`. None if all of it was deleted.
+pub(crate) fn displace_range_columns(
+    (row1, column1, row2, column2): (i32, i32, i32, i32),
+    column: i32,
+    delta: i32,
+) -> Option<(i32, i32, i32, i32)> {
+    let (column1, column2) = displace_interval(column1, column2, column, delta)?;
+    Some((row1, column1, row2, column2.min(LAST_COLUMN)))
+}
+
+/// Returns the new interval [index1, index2] of something that cannot be split, like a merged
+/// area or a table, when the `count` rows (or columns) starting at `start` move by `delta`.
+/// It has to be either in the moved rows, in the rows they pass over or contain both, otherwise
+/// it returns None.
+pub(crate) fn get_moved_interval(
+    index1: i32,
+    index2: i32,
+    start: i32,
+    count: i32,
+    delta: i32,
+) -> Option<(i32, i32)> {
+    let first = start.min(start + delta);
+    let last = (start + count - 1).max(start + count - 1 + delta);
+    if index2 < first || index1 > last || (index1 <= first && index2 >= last) {
+        return Some((index1, index2));
+    }
+    let is_moved = |index| index >= start && index < start + count;
+    if index1 < first || index2 > last || is_moved(index1) != is_moved(index2) {
+        return None;
+    }
+    Some((
+        get_moved_index(index1, start, count, delta),
+        get_moved_index(index2, start, count, delta),
+    ))
+}
+
+/// Returns the new interval [index1, index2] of a range when the `count` rows (or columns)
+/// starting at `start` move by `delta`, like the ranges in formulas: it does not change if it
+/// contains all the rows of the move, otherwise its extremes move.
+pub(crate) fn get_moved_range(
+    index1: i32,
+    index2: i32,
+    start: i32,
+    count: i32,
+    delta: i32,
+) -> (i32, i32) {
+    let first = start.min(start + delta);
+    let last = (start + count - 1).max(start + count - 1 + delta);
+    if index1 <= first && index2 >= last {
+        return (index1, index2);
+    }
+    let index1 = get_moved_index(index1, start, count, delta);
+    let index2 = get_moved_index(index2, start, count, delta);
+    (index1.min(index2), index1.max(index2))
+}
+
+/// Something attached to a single cell, referenced like "B3"
+pub(crate) trait CellAnchored: Clone {
+    fn cell_ref(&self) -> &str;
+    fn set_cell_ref(&mut self, cell_ref: String);
+
+    /// Returns the (row, column) of the cell
+    fn cell(&self) -> Option<(i32, i32)> {
+        parse_reference_a1(self.cell_ref()).map(|r| (r.row, r.column))
+    }
+}
+
+impl CellAnchored for Comment {
+    fn cell_ref(&self) -> &str {
+        &self.cell_ref
+    }
+    fn set_cell_ref(&mut self, cell_ref: String) {
+        self.cell_ref = cell_ref;
+    }
+}
+
+impl CellAnchored for Hyperlink {
+    fn cell_ref(&self) -> &str {
+        &self.cell_ref
+    }
+    fn set_cell_ref(&mut self, cell_ref: String) {
+        self.cell_ref = cell_ref;
+    }
+}
+
+/// Moves the `items` to the cells given by `displace`, the ones it returns None for are removed.
+/// Items with a reference we do not understand are left alone.
+pub(crate) fn displace_anchored<T: CellAnchored>(
+    items: &[T],
+    displace: impl Fn((i32, i32)) -> Option<(i32, i32)>,
+) -> Result<Vec<T>, String> {
+    let mut displaced = Vec::new();
+    for item in items {
+        match item.cell() {
+            Some(cell) => {
+                if let Some((row, column)) = displace(cell) {
+                    let mut item = item.clone();
+                    item.set_cell_ref(cell_ref(row, column)?);
+                    displaced.push(item);
+                }
+            }
+            None => displaced.push(item.clone()),
+        }
+    }
+    Ok(displaced)
+}
+
+/// Returns the `items` attached to cells for which `filter` is true
+pub(crate) fn filter_anchored<T: CellAnchored>(
+    items: &[T],
+    filter: impl Fn((i32, i32)) -> bool,
+) -> Vec<T> {
+    items
+        .iter()
+        .filter(|item| item.cell().is_some_and(&filter))
+        .cloned()
+        .collect()
+}
+
+/// Displacement of a cell (row, column) after inserting (`delta` > 0) or deleting (`delta` < 0)
+/// rows at `row`
+pub(crate) fn displace_cell_rows((r, c): (i32, i32), row: i32, delta: i32) -> Option<(i32, i32)> {
+    Some((displace_index(r, row, delta)?, c))
+}
+
+/// Displacement of a cell (row, column) after inserting (`delta` > 0) or deleting (`delta` < 0)
+/// columns at `column`
+pub(crate) fn displace_cell_columns(
+    (r, c): (i32, i32),
+    column: i32,
+    delta: i32,
+) -> Option<(i32, i32)> {
+    Some((r, displace_index(c, column, delta)?))
+}
@@ -82,6 +82,9 @@ pub enum LexerMode {
 pub struct Lexer {
     position: usize,
     next_token_position: Option<usize>,
+    /// Number of open braces of array constants before the position
+    array_depth: usize,
+    next_token_array_depth: usize,
     len: usize,
     chars: Vec<char>,
     mode: LexerMode,
@@ -98,6 +101,8 @@ impl Lexer {
             chars,
             position: 0,
             next_token_position: None,
+            array_depth: 0,
+            next_token_array_depth: 0,
             len,
             mode,
             locale: locale.clone(),
@@ -136,6 +141,7 @@ impl Lexer {
         self.len = self.chars.len();
         self.position = 0;
         self.next_token_position = None;
+        self.array_depth = 0;
     }
 
     /// Returns an error if the token is not the expected one.
@@ -151,9 +157,12 @@ impl Lexer {
     /// See also [advance_token](Self::advance_token)
     pub fn peek_token(&mut self) -> TokenType {
         let position = self.position;
+        let array_depth = self.array_depth;
         let tk = self.next_token();
         self.next_token_position = Some(self.position);
+        self.next_token_array_depth = self.array_depth;
         self.position = position;
+        self.array_depth = array_depth;
         tk
     }
 
@@ -162,6 +171,7 @@ impl Lexer {
     pub fn advance_token(&mut self) {
         if let Some(position) = self.next_token_position {
             self.position = position;
+            self.array_depth = self.next_token_array_depth;
             self.next_token_position = None;
         }
     }
@@ -181,12 +191,38 @@ impl Lexer {
                     '(' => TokenType::LeftParenthesis,
                     ')' => TokenType::RightParenthesis,
                     '=' => TokenType::Compare(OpCompare::Equal),
-                    '{' => TokenType::LeftBrace,
-                    '}' => TokenType::RightBrace,
-                    '[' => TokenType::LeftBracket,
+                    '{' => {
+                        self.array_depth += 1;
+                        TokenType::LeftBrace
+                    }
+                    '}' => {
+                        self.array_depth = self.array_depth.saturating_sub(1);
+                        TokenType::RightBrace
+                    }
+                    '[' => {
+                        // A structured reference without the table name like [@Price]
+                        let position = self.position;
+                        self.position -= 1;
+                        match self.consume_structured_reference("") {
+                            Ok(r) if self.mode == LexerMode::A1 => r,
+                            _ => {
+                                self.position = position;
+                                TokenType::LeftBracket
+                            }
+                        }
+                    }
                     ']' => TokenType::RightBracket,
                     ':' => TokenType::Colon,
-                    ';' => TokenType::Semicolon,
+                    ';' => {
+                        // In locales where the comma is the decimal separator
+                        // the semicolon separates function arguments.
+                        // It always separates the rows of array constants.
+                        if self.locale.numbers.symbols.decimal == "," && self.array_depth == 0 {
+                            TokenType::Comma
+                        } else {
+                            TokenType::Semicolon
+                        }
+                    }
                     ',' => {
                         if self.locale.numbers.symbols.decimal == "," {
                             match self.consume_number(',') {
@@ -203,6 +239,10 @@ impl Lexer {
                                 Ok(number) => TokenType::Number(number),
                                 Err(error) => TokenType::Illegal(error),
                             }
+                        } else if self.array_depth > 0 {
+                            // The period separates the columns of array constants
+                            // in locales where the comma is the decimal separator
+                            TokenType::Comma
                         } else {
                             // There is no TokenType::PERIOD
                             TokenType::Illegal(self.set_error("Expecting a number", self.position))
@@ -17,6 +17,12 @@ use super::Result;
 use super::{Lexer, LexerError};
 
 impl Lexer {
+    // A '[' is only a token at the start of a structured reference
+    fn expect_left_bracket(&mut self) -> Result<()> {
+        self.consume_whitespace();
+        self.expect_char('[')
+    }
+
     fn consume_table_specifier(&mut self) -> Result<Option<TableSpecifier>> {
         if self.peek_char() == Some('#') {
             // It's a specifier
@@ -51,12 +57,12 @@ impl Lexer {
 
     fn consume_column_reference(&mut self) -> Result<String> {
         self.consume_whitespace();
-        let end_char = if self.peek_char() == Some('[') {
+        // A column name without brackets ends at the closing bracket of the reference
+        let bracketed = self.peek_char() == Some('[');
+        if bracketed {
             self.position += 1;
-            ']'
-        } else {
-            ')'
-        };
+        }
+        let end_char = ']';
 
         let mut position = self.position;
         while position < self.len {
@@ -78,7 +84,7 @@ impl Lexer {
             }
         }
         let chars: String = self.chars[self.position..position].iter().collect();
-        if end_char == ']' {
+        if bracketed {
             position += 1;
         }
         self.position = position;
@@ -108,16 +114,44 @@ impl Lexer {
     ///
     /// Invalid:
     /// * MyTable[#Totals, [Jan]:[March]] => MyTable[[#Totals], [Jan]:[March]]
+    ///
+    /// Excel also uses '@' instead of '#This Row':
+    ///  1. MyTable[@MyColumn] => MyTable[[#This Row], [MyColumn]]
+    ///  2. MyTable[@[Jan]:[Dec]] => MyTable[[#This Row], [Jan]:[Dec]]
+    ///
+    /// Inside a table the name of the table can be omitted, then `table_name` is empty:
+    ///  1. [@MyColumn]
+    ///  2. [MyColumn]
     //
     // NOTES:
     // * MyTable[[#Totals]] is translated into MyTable[#Totals]
-    // * Excel shows '@' instead of '#This Row':
-    //     MyTable[[#This Row], [Jan]:[Dec]] => MyTable[@[Jan]:[Dec]]
-    //   But this is only a UI thing that we will ignore for now.
     pub(crate) fn consume_structured_reference(&mut self, table_name: &str) -> Result<TokenType> {
-        self.expect(TokenType::LeftBracket)?;
+        self.expect_left_bracket()?;
         let peek_char = self.peek_char();
+        if peek_char == Some('@') {
+            self.position += 1;
+            let column_reference = self.consume_column_reference()?;
+            let table_reference = if self.peek_char() == Some(':') {
+                self.position += 1;
+                let column_reference_right = self.consume_column_reference()?;
+                TableReference::RangeReference((column_reference, column_reference_right))
+            } else {
+                TableReference::ColumnReference(column_reference)
+            };
+            self.expect(TokenType::RightBracket)?;
+            return Ok(TokenType::StructuredReference {
+                table_name: table_name.to_string(),
+                specifier: Some(TableSpecifier::ThisRow),
+                table_reference: Some(table_reference),
+            });
+        }
         if peek_char == Some(']') {
+            if table_name.is_empty() {
+                return Err(LexerError {
+                    position: self.position,
+                    message: "Invalid structured reference".to_string(),
+                });
+            }
             // This is just a reference to the full table
             self.expect(TokenType::RightBracket)?;
             return Ok(TokenType::Ident(table_name.to_string()));
@@ -146,13 +180,13 @@ impl Lexer {
                 table_reference: Some(TableReference::ColumnReference(column_name)),
             });
         }
-        self.expect(TokenType::LeftBracket)?;
+        self.expect_left_bracket()?;
         let specifier = self.consume_table_specifier()?;
         if specifier.is_some() {
             let peek_token = self.peek_token();
             if peek_token == TokenType::Comma {
                 self.advance_token();
-                self.expect(TokenType::LeftBracket)?;
+                self.expect_left_bracket()?;
             } else if peek_token == TokenType::RightBracket {
                 return Ok(TokenType::StructuredReference {
                     table_name: table_name.to_string(),
@@ -46,3 +46,31 @@ fn test_english_locale_does_not_parse() {
     assert_eq!(lx.next_token(), TokenType::Number(34e-3));
     assert_eq!(lx.next_token(), TokenType::EOF);
 }
+
+#[test]
+fn test_german_locale_argument_separator() {
+    let mut lx = new_language_lexer("1,5;A1", "de", "en");
+    assert_eq!(lx.next_token(), TokenType::Number(1.5));
+    assert_eq!(lx.next_token(), TokenType::Comma);
+    assert!(matches!(lx.next_token(), TokenType::Reference { .. }));
+    assert_eq!(lx.next_token(), TokenType::EOF);
+}
+
+#[test]
+fn test_german_locale_array_separators() {
+    let mut lx = new_language_lexer("{1,5.2;3.4};1", "de", "en");
+    assert_eq!(lx.next_token(), TokenType::LeftBrace);
+    assert_eq!(lx.next_token(), TokenType::Number(1.5));
+    assert_eq!(lx.next_token(), TokenType::Comma);
+    assert_eq!(lx.next_token(), TokenType::Number(2.0));
+    assert_eq!(lx.peek_token(), TokenType::Semicolon);
+    lx.advance_token();
+    assert_eq!(lx.next_token(), TokenType::Number(3.0));
+    assert_eq!(lx.next_token(), TokenType::Comma);
+    assert_eq!(lx.next_token(), TokenType::Number(4.0));
+    assert_eq!(lx.peek_token(), TokenType::RightBrace);
+    lx.advance_token();
+    assert_eq!(lx.next_token(), TokenType::Comma);
+    assert_eq!(lx.next_token(), TokenType::Number(1.0));
+    assert_eq!(lx.next_token(), TokenType::EOF);
+}
@@ -71,3 +71,40 @@ fn table_totals_no_reference() {
     );
     assert_eq!(lx.next_token(), EOF);
 }
+
+#[test]
+fn table_this_row_short_form() {
+    let mut lx = new_lexer("tbInfo[@[Jan]:[Dec]]");
+    assert_eq!(
+        lx.next_token(),
+        StructuredReference {
+            table_name: "tbInfo".to_string(),
+            specifier: Some(TableSpecifier::ThisRow),
+            table_reference: Some(TableReference::RangeReference((
+                "Jan".to_string(),
+                "Dec".to_string()
+            )))
+        }
+    );
+    assert_eq!(lx.next_token(), EOF);
+
+    let mut lx = new_lexer("[@Price]*[@[Unit Cost]]");
+    assert_eq!(
+        lx.next_token(),
+        StructuredReference {
+            table_name: "".to_string(),
+            specifier: Some(TableSpecifier::ThisRow),
+            table_reference: Some(TableReference::ColumnReference("Price".to_string()))
+        }
+    );
+    lx.next_token();
+    assert_eq!(
+        lx.next_token(),
+        StructuredReference {
+            table_name: "".to_string(),
+            specifier: Some(TableSpecifier::ThisRow),
+            table_reference: Some(TableReference::ColumnReference("Unit Cost".to_string()))
+        }
+    );
+    assert_eq!(lx.next_token(), EOF);
+}
@@ -2,5 +2,6 @@
 pub mod lexer;
 pub mod parser;
 pub mod token;
+pub mod translate;
 pub mod types;
 pub mod utils;
@@ -40,7 +40,6 @@ use super::token::OpUnary;
 use super::token::TableReference;
 use super::token::TokenType;
 use super::types::*;
-use super::utils::number_to_column;
 
 use token::OpCompare;
 
@@ -220,6 +219,10 @@ impl Parser {
         self.worksheets = worksheets;
     }
 
+    pub fn set_tables(&mut self, tables: HashMap<String, Table>) {
+        self.tables = tables;
+    }
+
     pub fn parse(&mut self, formula: &str, context: &Option<CellReferenceRC>) -> Node {
         self.lexer.set_formula(formula);
         self.context.clone_from(context);
@@ -675,14 +678,38 @@ impl Parser {
                         }
                     };
                     // table-name => table
-                    let table = self.tables.get(&table_name).unwrap_or_else(|| {
-                        panic!(
-                            "Table not found: '{table_name}' at '{}!{}{}'",
-                            context.sheet,
-                            number_to_column(context.column).expect(""),
-                            context.row
-                        )
-                    });
+                    // Without a name it is the table with the cell
+                    let table = if table_name.is_empty() {
+                        self.tables.values().find(|table| {
+                            table.sheet_name == context.sheet
+                                && parse_range(&table.reference).is_ok_and(
+                                    |(column1, row1, column2, row2)| {
+                                        (row1..=row2).contains(&context.row)
+                                            && (column1..=column2).contains(&context.column)
+                                    },
+                                )
+                        })
+                    } else {
+                        self.tables.get(&table_name)
+                    };
+                    let table = match table {
+                        Some(table) => table,
+                        None if table_name.is_empty() => {
+                            return Node::ParseErrorKind {
+                                formula: self.lexer.get_formula(),
+                                position: 0,
+                                message: "A reference without table name must be in a table"
+                                    .to_string(),
+                            };
+                        }
+                        None => {
+                            return Node::ParseErrorKind {
+                                formula: self.lexer.get_formula(),
+                                position: 0,
+                                message: format!("Table not found: '{table_name}'"),
+                            };
+                        }
+                    };
                     let table_sheet_index = match self.get_sheet_index_by_name(&table.sheet_name) {
                         Some(i) => i,
                         None => {
@@ -708,10 +735,12 @@ impl Parser {
                     let header_row_count = table.header_row_count as i32;
                     row_end -= totals_row_count;
 
+                    let this_row = specifier == Some(token::TableSpecifier::ThisRow);
                     match specifier {
                         Some(token::TableSpecifier::ThisRow) => {
-                            row_start = context.row;
-                            row_end = context.row;
+                            // Like in Excel the row is relative, [@Price] in C5 is $B5
+                            row_start = 0;
+                            row_end = 0;
                         }
                         Some(token::TableSpecifier::Totals) => {
                             if totals_row_count != 0 {
@@ -743,11 +772,11 @@ impl Parser {
                             return Node::RangeKind {
                                 sheet_name,
                                 sheet_index: table_sheet_index,
-                                absolute_row1: true,
+                                absolute_row1: !this_row,
                                 absolute_column1: true,
                                 row1: row_start,
                                 column1: column_start,
-                                absolute_row2: true,
+                                absolute_row2: !this_row,
                                 absolute_column2: true,
                                 row2: row_end,
                                 column2: column_end,
@@ -770,7 +799,7 @@ impl Parser {
                                 return Node::ReferenceKind {
                                     sheet_name,
                                     sheet_index: table_sheet_index,
-                                    absolute_row: true,
+                                    absolute_row: !this_row,
                                     absolute_column: true,
                                     row: row_start,
                                     column: column_index,
@@ -779,11 +808,11 @@ impl Parser {
                             return Node::RangeKind {
                                 sheet_name,
                                 sheet_index: table_sheet_index,
-                                absolute_row1: true,
+                                absolute_row1: !this_row,
                                 absolute_column1: true,
                                 row1: row_start,
                                 column1: column_index,
-                                absolute_row2: true,
+                                absolute_row2: !this_row,
                                 absolute_column2: true,
                                 row2: row_end,
                                 column2: column_index,
@@ -818,11 +847,11 @@ impl Parser {
                             return Node::RangeKind {
                                 sheet_name,
                                 sheet_index: table_sheet_index,
-                                absolute_row1: true,
+                                absolute_row1: !this_row,
                                 absolute_column1: true,
                                 row1: row_start,
                                 column1: left_column_index,
-                                absolute_row2: true,
+                                absolute_row2: !this_row,
                                 absolute_column2: true,
                                 row2: row_end,
                                 column2: right_column_index,
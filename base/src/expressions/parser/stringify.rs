@@ -26,14 +26,51 @@ pub enum DisplaceData {
         column: i32,
         delta: i32,
     },
+    /// `row_count` rows starting at `row` move by `delta` rows
+    RowMove {
+        sheet: u32,
+        row: i32,
+        row_count: i32,
+        delta: i32,
+    },
+    /// `column_count` columns starting at `column` move by `delta` columns
     ColumnMove {
         sheet: u32,
         column: i32,
+        column_count: i32,
         delta: i32,
     },
     None,
 }
 
+/// Returns the new position of the row (or column) `index` when the `count` rows starting at
+/// `start` move by `delta` rows. The rows they pass over are displaced to fill the gap.
+pub(crate) fn get_moved_index(index: i32, start: i32, count: i32, delta: i32) -> i32 {
+    if index >= start && index < start + count {
+        index + delta
+    } else if delta > 0 && index >= start + count && index < start + count + delta {
+        index - count
+    } else if delta < 0 && index >= start + delta && index < start {
+        index + count
+    } else {
+        index
+    }
+}
+
+/// Returns None if a range from row (or column) `index1` to `index2` does not change when `count`
+/// rows starting at `start` move by `delta`, because it contains all the rows of the move.
+/// Otherwise its extremes move and it returns true if they have to be swapped.
+fn get_range_move(index1: i32, index2: i32, start: i32, count: i32, delta: i32) -> Option<bool> {
+    let first = start.min(start + delta);
+    let last = (start + count - 1).max(start + count - 1 + delta);
+    if index1 <= first && index2 >= last {
+        return None;
+    }
+    Some(
+        get_moved_index(index1, start, count, delta) > get_moved_index(index2, start, count, delta),
+    )
+}
+
 pub fn to_rc_format(node: &Node) -> String {
     stringify(node, None, &DisplaceData::None, false)
 }
@@ -154,23 +191,24 @@ pub(crate) fn stringify_reference(
                         }
                     }
                 }
+                DisplaceData::RowMove {
+                    sheet,
+                    row: move_row,
+                    row_count,
+                    delta,
+                } => {
+                    if sheet_index == *sheet && !full_row {
+                        row = get_moved_index(row, *move_row, *row_count, *delta);
+                    }
+                }
                 DisplaceData::ColumnMove {
                     sheet,
                     column: move_column,
+                    column_count,
                     delta,
                 } => {
-                    if sheet_index == *sheet {
-                        if column == *move_column {
-                            column += *delta;
-                        } else if (*delta > 0
-                            && column > *move_column
-                            && column <= *move_column + *delta)
-                            || (*delta < 0
-                                && column < *move_column
-                                && column >= *move_column + *delta)
-                        {
-                            column -= *delta;
-                        }
+                    if sheet_index == *sheet && !full_column {
+                        column = get_moved_index(column, *move_column, *column_count, *delta);
                     }
                 }
                 DisplaceData::None => {}
@@ -326,34 +364,92 @@ fn stringify(
                 && *absolute_column2
                 && (*column1 == 1)
                 && (*column2 == LAST_COLUMN);
-            let s1 = stringify_reference(
-                context,
-                displace_data,
-                &Reference {
-                    sheet_name,
-                    sheet_index: *sheet_index,
-                    row: *row1,
-                    column: *column1,
-                    absolute_row: *absolute_row1,
-                    absolute_column: *absolute_column1,
-                },
-                full_row,
-                full_column,
-            );
-            let s2 = stringify_reference(
-                context,
-                displace_data,
-                &Reference {
-                    sheet_name: &None,
-                    sheet_index: *sheet_index,
-                    row: *row2,
-                    column: *column2,
-                    absolute_row: *absolute_row2,
-                    absolute_column: *absolute_column2,
-                },
-                full_row,
-                full_column,
-            );
+            let mut reference1 = Reference {
+                sheet_name,
+                sheet_index: *sheet_index,
+                row: *row1,
+                column: *column1,
+                absolute_row: *absolute_row1,
+                absolute_column: *absolute_column1,
+            };
+            let mut reference2 = Reference {
+                sheet_name: &None,
+                sheet_index: *sheet_index,
+                row: *row2,
+                column: *column2,
+                absolute_row: *absolute_row2,
+                absolute_column: *absolute_column2,
+            };
+            let mut displace_data = displace_data;
+            match (context, displace_data) {
+                (
+                    Some(context),
+                    DisplaceData::RowMove {
+                        sheet,
+                        row,
+                        row_count,
+                        delta,
+                    },
+                ) if sheet == sheet_index && !full_row => {
+                    let index1 = if *absolute_row1 {
+                        *row1
+                    } else {
+                        *row1 + context.row
+                    };
+                    let index2 = if *absolute_row2 {
+                        *row2
+                    } else {
+                        *row2 + context.row
+                    };
+                    match get_range_move(index1, index2, *row, *row_count, *delta) {
+                        None => displace_data = &DisplaceData::None,
+                        Some(true) => {
+                            std::mem::swap(&mut reference1.row, &mut reference2.row);
+                            std::mem::swap(
+                                &mut reference1.absolute_row,
+                                &mut reference2.absolute_row,
+                            );
+                        }
+                        Some(false) => {}
+                    }
+                }
+                (
+                    Some(context),
+                    DisplaceData::ColumnMove {
+                        sheet,
+                        column,
+                        column_count,
+                        delta,
+                    },
+                ) if sheet == sheet_index && !full_column => {
+                    let index1 = if *absolute_column1 {
+                        *column1
+                    } else {
+                        *column1 + context.column
+                    };
+                    let index2 = if *absolute_column2 {
+                        *column2
+                    } else {
+                        *column2 + context.column
+                    };
+                    match get_range_move(index1, index2, *column, *column_count, *delta) {
+                        None => displace_data = &DisplaceData::None,
+                        Some(true) => {
+                            std::mem::swap(&mut reference1.column, &mut reference2.column);
+                            std::mem::swap(
+                                &mut reference1.absolute_column,
+                                &mut reference2.absolute_column,
+                            );
+                        }
+                        Some(false) => {}
+                    }
+                }
+                _ => {}
+            }
+            let s1 =
+                stringify_reference(context, displace_data, &reference1, full_row, full_column);
+            let s2 =
+                stringify_reference(context, displace_data, &reference2, full_row, full_column);
             format!("{}:{}", s1, s2)
         }
         WrongRangeKind {
@@ -610,3 +706,68 @@ pub(crate) fn rename_sheet_in_node(node: &mut Node, sheet_index: u32, new_name:
         Node::EmptyArgKind => {}
     }
 }
+
+/// Swaps the rows and columns of the references that are relative in both, so that a formula
+/// copied to the transposed position refers to the transposed cells.
+/// Absolute and mixed references do not change.
+pub(crate) fn transpose_references_in_node(node: &mut Node) {
+    match node {
+        // Transpose
+        Node::ReferenceKind {
+            absolute_row: false,
+            absolute_column: false,
+            row,
+            column,
+            ..
+        } => {
+            std::mem::swap(row, column);
+        }
+        Node::RangeKind {
+            absolute_row1: false,
+            absolute_column1: false,
+            row1,
+            column1,
+            absolute_row2: false,
+            absolute_column2: false,
+            row2,
+            column2,
+            ..
+        } => {
+            std::mem::swap(row1, column1);
+            std::mem::swap(row2, column2);
+        }
+
+        // Go next level
+        Node::OpRangeKind { left, right }
+        | Node::OpConcatenateKind { left, right }
+        | Node::OpSumKind { left, right, .. }
+        | Node::OpProductKind { left, right, .. }
+        | Node::OpPowerKind { left, right }
+        | Node::CompareKind { left, right, .. } => {
+            transpose_references_in_node(left);
+            transpose_references_in_node(right);
+        }
+        Node::FunctionKind { args, .. } | Node::InvalidFunctionKind { args, .. } => {
+            for arg in args {
+                transpose_references_in_node(arg);
+            }
+        }
+        Node::UnaryKind { right, .. } => {
+            transpose_references_in_node(right);
+        }
+
+        // Do nothing
+        Node::ReferenceKind { .. }
+        | Node::RangeKind { .. }
+        | Node::WrongReferenceKind { .. }
+        | Node::WrongRangeKind { .. }
+        | Node::BooleanKind(_)
+        | Node::NumberKind(_)
+        | Node::StringKind(_)
+        | Node::ErrorKind(_)
+        | Node::ParseErrorKind { .. }
+        | Node::ArrayKind(_)
+        | Node::VariableKind(_)
+        | Node::EmptyArgKind => {}
+    }
+}
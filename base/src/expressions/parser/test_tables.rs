@@ -49,6 +49,7 @@ fn create_test_table(
             },
             totals_row_dxf_id: None,
             has_filters: false,
+            filter_columns: vec![],
         },
     );
     table
@@ -73,7 +74,7 @@ fn simple_table() {
 
     let formula = "SUM(tblIncome[[#This Row],[Jan]:[Dec]])";
     let t = parser.parse(formula, &Some(cell_reference.clone()));
-    assert_eq!(to_string(&t, &cell_reference), "SUM($A$2:$E$2)");
+    assert_eq!(to_string(&t, &cell_reference), "SUM($A2:$E2)");
 
     // Cell A3
     let cell_reference = CellReferenceRC {
@@ -98,3 +99,38 @@ fn simple_table() {
         "SUBTOTAL(109,'Sheet One'!$A$2:$A$3)"
     );
 }
+
+#[test]
+fn this_row_short_form() {
+    let worksheets = vec!["Sheet One".to_string(), "Second Sheet".to_string()];
+    let column_names = ["Jan", "Feb", "Mar", "Apr", "Dec", "Year Total"];
+    let tables = create_test_table("tblIncome", &column_names, "A1", 3);
+
+    let mut parser = Parser::new(worksheets, tables);
+    // Cell 'Sheet One'!F3
+    let cell_reference = CellReferenceRC {
+        sheet: "Sheet One".to_string(),
+        row: 3,
+        column: 6,
+    };
+    let formulas = [
+        ("tblIncome[@Jan]*2", "$A3*2"),
+        ("SUM(tblIncome[@[Jan]:[Dec]])", "SUM($A3:$E3)"),
+        ("[@Feb]+[@[Year Total]]", "$B3+$F3"),
+        ("SUM([Jan])", "SUM($A$2:$A$3)"),
+        ("SUM([[#This Row],[Jan]:[Mar]])", "SUM($A3:$C3)"),
+    ];
+    for (formula, expected) in formulas {
+        let t = parser.parse(formula, &Some(cell_reference.clone()));
+        assert_eq!(to_string(&t, &cell_reference), expected, "{formula}");
+    }
+
+    // Outside of the table the name is needed
+    let cell_reference = CellReferenceRC {
+        sheet: "Sheet One".to_string(),
+        row: 10,
+        column: 1,
+    };
+    let t = parser.parse("[@Jan]", &Some(cell_reference.clone()));
+    assert!(matches!(t, super::Node::ParseErrorKind { .. }));
+}
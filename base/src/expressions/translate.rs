@@ -0,0 +1,164 @@
+//! Translates formulas between display languages and locales.
+//!
+//! Formulas are stored in the 'en' language and locale. A German user types and sees
+//! `SUMME(A1;1,5)` where we store `SUM(A1,1.5)`.
+//!
+//! Only function names, booleans, errors, numbers and argument and array separators change.
+//! Everything else (references, strings, defined names, ...) is copied verbatim.
+//!
+//! # Examples:
+//! ```
+//! use ironcalc_base::expressions::translate::{to_canonical_formula, to_localized_formula};
+//! use ironcalc_base::locale::get_locale;
+//! use ironcalc_base::language::get_language;
+//!
+//! let locale = get_locale("de").unwrap();
+//! let language = get_language("de").unwrap();
+//! let canonical = to_canonical_formula("SUMME(A1;1,5)", locale, language);
+//! assert_eq!(canonical, "SUM(A1,1.5)");
+//! let localized = to_localized_formula(&canonical, locale, language);
+//! assert_eq!(localized, "SUMME(A1;1,5)");
+//! ```
+
+use crate::language::{get_language, Language};
+use crate::locale::{get_locale, Locale};
+
+use super::lexer::{Lexer, LexerMode};
+use super::token::TokenType;
+
+/// Translates a formula (without the leading '=') written in the given language and locale
+/// into the 'en' language and locale in which formulas are stored.
+pub fn to_canonical_formula(formula: &str, locale: &Locale, language: &Language) -> String {
+    translate_formula(
+        formula,
+        locale,
+        language,
+        get_locale("en").expect(""),
+        get_language("en").expect(""),
+    )
+}
+
+/// Translates a formula (without the leading '=') from the 'en' language and locale
+/// into the given language and locale.
+pub fn to_localized_formula(formula: &str, locale: &Locale, language: &Language) -> String {
+    translate_formula(
+        formula,
+        get_locale("en").expect(""),
+        get_language("en").expect(""),
+        locale,
+        language,
+    )
+}
+
+/// Translates a formula (without the leading '=') from one language and locale into another.
+///
+/// If the formula cannot be tokenized the offending part is copied unchanged.
+pub fn translate_formula(
+    formula: &str,
+    from_locale: &Locale,
+    from_language: &Language,
+    to_locale: &Locale,
+    to_language: &Language,
+) -> String {
+    let chars: Vec<char> = formula.chars().collect();
+    let len = chars.len();
+    let mut lexer = Lexer::new(formula, LexerMode::A1, from_locale, from_language);
+    let mut result = String::new();
+    let mut array_depth = 0;
+    loop {
+        let start = (lexer.get_position() as usize).min(len);
+        let token = lexer.next_token();
+        let end = (lexer.get_position() as usize).min(len);
+        let text: String = chars[start..end].iter().collect();
+        let trimmed = text.trim_start();
+        // whitespace is preserved
+        result.push_str(&text[..text.len() - trimmed.len()]);
+        match token {
+            TokenType::EOF => break,
+            TokenType::Illegal(_) => {
+                // We don't know how to translate the rest of the formula
+                let offset = text.chars().count() - trimmed.chars().count();
+                result.extend(&chars[start + offset..]);
+                break;
+            }
+            TokenType::Number(_) => result.push_str(&trimmed.replace(
+                &from_locale.numbers.symbols.decimal,
+                &to_locale.numbers.symbols.decimal,
+            )),
+            TokenType::Boolean(value) => {
+                let booleans = &to_language.booleans;
+                result.push_str(if value {
+                    &booleans.r#true
+                } else {
+                    &booleans.r#false
+                });
+            }
+            TokenType::Error(error) => {
+                result.push_str(&error.to_localized_error_string(to_language))
+            }
+            TokenType::Comma if array_depth > 0 => result.push(array_column_separator(to_locale)),
+            TokenType::Comma => result.push(argument_separator(to_locale)),
+            TokenType::Semicolon if array_depth > 0 => result.push(array_row_separator(to_locale)),
+            TokenType::LeftBrace => {
+                array_depth += 1;
+                result.push_str(trimmed);
+            }
+            TokenType::RightBrace => {
+                array_depth -= 1;
+                result.push_str(trimmed);
+            }
+            // Some function names like LOG10 are also valid references
+            TokenType::Ident(_) | TokenType::Reference { sheet: None, .. }
+                if lexer.peek_token() == TokenType::LeftParenthesis =>
+            {
+                result.push_str(&translate_function_name(
+                    trimmed,
+                    from_language,
+                    to_language,
+                ))
+            }
+            _ => result.push_str(trimmed),
+        }
+    }
+    result
+}
+
+/// Returns the character that separates the arguments of a function in a locale
+pub fn argument_separator(locale: &Locale) -> char {
+    if locale.numbers.symbols.decimal == "," {
+        ';'
+    } else {
+        ','
+    }
+}
+
+/// Returns the character that separates the columns of an array constant in a locale
+pub fn array_column_separator(locale: &Locale) -> char {
+    if locale.numbers.symbols.decimal == "," {
+        '.'
+    } else {
+        ','
+    }
+}
+
+/// Returns the character that separates the rows of an array constant in a locale
+pub fn array_row_separator(_locale: &Locale) -> char {
+    ';'
+}
+
+fn translate_function_name(name: &str, from_language: &Language, to_language: &Language) -> String {
+    let name_upper = name.to_uppercase();
+    let english_name = match from_language
+        .functions
+        .iter()
+        .find(|(_, localized)| localized.to_uppercase() == name_upper)
+    {
+        Some((english_name, _)) => english_name.to_string(),
+        // Unknown names and names that are the same as in English
+        None => name.to_string(),
+    };
+    match to_language.functions.get(&english_name.to_uppercase()) {
+        Some(localized) => localized.to_string(),
+        None => english_name,
+    }
+}
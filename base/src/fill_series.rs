@@ -0,0 +1,465 @@
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+use crate::{
+    cell::CellValue, formatter::lexer::is_likely_date_number_format, model::Model,
+    number_format::to_precision, types::SeriesType,
+};
+
+/// How the names of days or months are written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NameCase {
+    /// As in the locale
+    Locale,
+    Upper,
+    Lower,
+}
+
+/// The values that fill a line of cells. Positions are counted from the first cell of the source,
+/// negative positions go upwards or to the left.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Series {
+    /// Copies the source cells, moving the references of formulas
+    Copy,
+    /// `start + step * position`
+    Linear { start: f64, step: f64 },
+    /// `start * factor ^ position`
+    Growth { start: f64, factor: f64 },
+    /// The date `start` moved `step * position` days, weekdays, months or years
+    Date {
+        start: f64,
+        step: i32,
+        unit: SeriesType,
+    },
+    /// Texts like "Item 9" followed by "Item 10"
+    Text {
+        prefix: String,
+        start: i64,
+        step: i64,
+        /// Minimum number of digits, for numbers with leading zeros like "Q01"
+        digits: usize,
+    },
+    /// Names of days or months
+    Names {
+        names: Vec<String>,
+        start: i64,
+        step: i64,
+        case: NameCase,
+    },
+}
+
+/// A value of the source of a series
+enum SourceValue {
+    Number { value: f64, is_date: bool },
+    Text(String),
+    Other,
+}
+
+/// Returns the step of `values` if they are evenly spaced
+fn get_constant_step(values: &[i64]) -> Option<i64> {
+    let step = values[1] - values[0];
+    if values.windows(2).all(|w| w[1] - w[0] == step) {
+        Some(step)
+    } else {
+        None
+    }
+}
+
+/// Splits a text like "Item 09" into ("Item ", 9, 2)
+fn split_numeric_suffix(text: &str) -> Option<(String, i64, usize)> {
+    let digits = text
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_digit())
+        .count();
+    // Plain numbers are not texts with a suffix
+    if digits == 0 || digits == text.chars().count() || digits > 15 {
+        return None;
+    }
+    let index = text.len() - digits;
+    let number = text[index..].parse::<i64>().ok()?;
+    Some((text[..index].to_string(), number, digits))
+}
+
+fn get_name_case(text: &str) -> NameCase {
+    if text.chars().any(|c| c.is_alphabetic()) {
+        if text == text.to_uppercase() && text != text.to_lowercase() {
+            return NameCase::Upper;
+        }
+        if text == text.to_lowercase() && text != text.to_uppercase() {
+            return NameCase::Lower;
+        }
+    }
+    NameCase::Locale
+}
+
+/// Least squares line through the `values` at positions 0, 1, 2, ...
+fn get_linear_trend(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let mut sxy = 0.0;
+    let mut sxx = 0.0;
+    for (x, y) in values.iter().enumerate() {
+        let dx = x as f64 - mean_x;
+        sxy += dx * (y - mean_y);
+        sxx += dx * dx;
+    }
+    let step = sxy / sxx;
+    (mean_y - step * mean_x, step)
+}
+
+/// Returns the factor if `values` is a geometric series that is not linear
+fn get_growth_factor(values: &[f64]) -> Option<f64> {
+    if values.len() < 3 || values.contains(&0.0) {
+        return None;
+    }
+    let factor = values[1] / values[0];
+    let is_geometric = values
+        .windows(2)
+        .all(|w| (w[1] / w[0] - factor).abs() <= 1e-12 * factor.abs());
+    if is_geometric && factor != 1.0 {
+        Some(factor)
+    } else {
+        None
+    }
+}
+
+/// The serial number of the last date, 31 December 9999
+const LAST_DATE: f64 = 2_958_465.0;
+
+/// Moves the `date` by `days` days, None if the result is out of range
+fn add_days(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    let delta = Days::new(days.unsigned_abs());
+    if days >= 0 {
+        date.checked_add_days(delta)
+    } else {
+        date.checked_sub_days(delta)
+    }
+}
+
+/// Moves the `date` by `days` weekdays, weekends are skipped.
+/// None if the result is out of range.
+fn add_weekdays(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    let is_weekend = |d: NaiveDate| matches!(d.weekday(), Weekday::Sat | Weekday::Sun);
+    let sign = days.signum();
+    let mut date = add_days(date, days / 5 * 7)?;
+    let mut remaining = (days % 5).abs();
+    while remaining > 0 {
+        date = add_days(date, sign)?;
+        if !is_weekend(date) {
+            remaining -= 1;
+        }
+    }
+    // Whole weeks from a weekend day land on the weekend
+    while days != 0 && is_weekend(date) {
+        date = add_days(date, sign)?;
+    }
+    Some(date)
+}
+
+impl Model {
+    fn get_source_value(&self, sheet: u32, row: i32, column: i32) -> Result<SourceValue, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        if worksheet
+            .cell(row, column)
+            .is_some_and(|cell| cell.has_formula())
+        {
+            return Ok(SourceValue::Other);
+        }
+        Ok(match self.get_cell_value_by_index(sheet, row, column)? {
+            CellValue::Number(value) => {
+                let num_fmt = self.get_style_for_cell(sheet, row, column)?.num_fmt;
+                SourceValue::Number {
+                    value,
+                    is_date: is_likely_date_number_format(&num_fmt),
+                }
+            }
+            CellValue::String(text) if !text.is_empty() => SourceValue::Text(text),
+            _ => SourceValue::Other,
+        })
+    }
+
+    /// Returns the lists of names of the locale that can make a series
+    fn get_name_lists(&self) -> Vec<&Vec<String>> {
+        let dates = &self.locale.dates;
+        vec![
+            &dates.day_names,
+            &dates.months,
+            &dates.day_names_short,
+            &dates.months_short,
+        ]
+    }
+
+    fn get_names_series(&self, texts: &[String]) -> Option<Series> {
+        for names in self.get_name_lists() {
+            let indices: Option<Vec<i64>> = texts
+                .iter()
+                .map(|text| {
+                    let lower = text.to_lowercase();
+                    names
+                        .iter()
+                        .position(|name| name.to_lowercase() == lower)
+                        .map(|index| index as i64)
+                })
+                .collect();
+            let indices = match indices {
+                Some(indices) => indices,
+                None => continue,
+            };
+            let len = names.len() as i64;
+            let step = if indices.len() == 1 {
+                1
+            } else {
+                // Steps are taken forwards, "Sunday, Saturday" goes backwards
+                let step = (indices[1] - indices[0]).rem_euclid(len);
+                let is_constant = indices
+                    .windows(2)
+                    .all(|w| (w[1] - w[0]).rem_euclid(len) == step);
+                if !is_constant || step == 0 {
+                    return None;
+                }
+                if step > len / 2 {
+                    step - len
+                } else {
+                    step
+                }
+            };
+            return Some(Series::Names {
+                names: names.clone(),
+                start: indices[0],
+                step,
+                case: get_name_case(&texts[0]),
+            });
+        }
+        None
+    }
+
+    fn get_text_series(texts: &[String]) -> Option<Series> {
+        let parts: Option<Vec<(String, i64, usize)>> =
+            texts.iter().map(|t| split_numeric_suffix(t)).collect();
+        let parts = parts?;
+        let prefix = &parts[0].0;
+        if parts.iter().any(|(p, _, _)| p != prefix) {
+            return None;
+        }
+        let numbers: Vec<i64> = parts.iter().map(|(_, n, _)| *n).collect();
+        let step = if numbers.len() == 1 {
+            1
+        } else {
+            get_constant_step(&numbers)?
+        };
+        // Only numbers with leading zeros keep their width
+        let digits = if parts[0].2 > 1 && parts[0].1.to_string().len() < parts[0].2 {
+            parts[0].2
+        } else {
+            1
+        };
+        Some(Series::Text {
+            prefix: prefix.clone(),
+            start: numbers[0],
+            step,
+            digits,
+        })
+    }
+
+    fn get_date_series(&self, values: &[f64]) -> Option<Series> {
+        if values.iter().any(|v| !(0.0..=LAST_DATE).contains(v)) {
+            return None;
+        }
+        let start = values[0];
+        if values.len() == 1 {
+            return Some(Series::Date {
+                start,
+                step: 1,
+                unit: SeriesType::Day,
+            });
+        }
+        if values.iter().any(|v| v.fract() != 0.0) {
+            return None;
+        }
+        let dates: Vec<NaiveDate> = values
+            .iter()
+            .map(|v| self.serial_number_to_date(*v as i64))
+            .collect();
+        if dates.iter().all(|d| d.day() == dates[0].day()) {
+            let months: Vec<i64> = dates
+                .iter()
+                .map(|d| d.year() as i64 * 12 + d.month0() as i64)
+                .collect();
+            if let Some(step) = get_constant_step(&months) {
+                if step != 0 {
+                    let (step, unit) = if step % 12 == 0 {
+                        (step / 12, SeriesType::Year)
+                    } else {
+                        (step, SeriesType::Month)
+                    };
+                    return Some(Series::Date {
+                        start,
+                        step: step as i32,
+                        unit,
+                    });
+                }
+            }
+        }
+        let days: Vec<i64> = values.iter().map(|v| *v as i64).collect();
+        let step = get_constant_step(&days)?;
+        Some(Series::Date {
+            start,
+            step: step as i32,
+            unit: SeriesType::Day,
+        })
+    }
+
+    /// Returns the series that continues the source `cells`, in order
+    pub(crate) fn get_fill_series(
+        &self,
+        sheet: u32,
+        cells: &[(i32, i32)],
+    ) -> Result<Series, String> {
+        let mut numbers = Vec::new();
+        let mut texts = Vec::new();
+        let mut all_dates = true;
+        for &(row, column) in cells {
+            match self.get_source_value(sheet, row, column)? {
+                SourceValue::Number { value, is_date } => {
+                    numbers.push(value);
+                    all_dates &= is_date;
+                }
+                SourceValue::Text(text) => texts.push(text),
+                SourceValue::Other => return Ok(Series::Copy),
+            }
+        }
+        if !texts.is_empty() {
+            if !numbers.is_empty() {
+                return Ok(Series::Copy);
+            }
+            let series = self
+                .get_names_series(&texts)
+                .or_else(|| Model::get_text_series(&texts));
+            return Ok(series.unwrap_or(Series::Copy));
+        }
+        if all_dates {
+            if let Some(series) = self.get_date_series(&numbers) {
+                return Ok(series);
+            }
+        }
+        // A single number is copied
+        if numbers.len() == 1 {
+            return Ok(Series::Copy);
+        }
+        if let Some(factor) = get_growth_factor(&numbers) {
+            return Ok(Series::Growth {
+                start: numbers[0],
+                factor,
+            });
+        }
+        let (start, step) = get_linear_trend(&numbers);
+        Ok(Series::Linear { start, step })
+    }
+
+    /// Returns the series of `series_type` that starts at `start` with `step`
+    pub(crate) fn get_typed_series(
+        series_type: SeriesType,
+        start: f64,
+        step: f64,
+    ) -> Result<Series, String> {
+        if step == 0.0 || !step.is_finite() {
+            return Err("The step of a series cannot be 0".to_string());
+        }
+        match series_type {
+            SeriesType::Linear => Ok(Series::Linear { start, step }),
+            SeriesType::Growth => Ok(Series::Growth {
+                start,
+                factor: step,
+            }),
+            unit => {
+                if step.fract() != 0.0 {
+                    return Err("The step of a date series must be a whole number".to_string());
+                }
+                Ok(Series::Date {
+                    start,
+                    step: step as i32,
+                    unit,
+                })
+            }
+        }
+    }
+
+    /// Returns the number at `position` of a numeric series
+    pub(crate) fn get_series_number(&self, series: &Series, position: i64) -> Option<f64> {
+        let n = position as f64;
+        match series {
+            Series::Linear { start, step } => Some(start + step * n),
+            Series::Growth { start, factor } => Some(start * factor.powf(n)),
+            Series::Date { start, step, unit } => {
+                if !(0.0..=LAST_DATE).contains(start) {
+                    return None;
+                }
+                let date = self.serial_number_to_date(start.floor() as i64);
+                let steps = *step as i64 * position;
+                let date = match unit {
+                    SeriesType::Weekday => add_weekdays(date, steps)?,
+                    SeriesType::Month | SeriesType::Year => {
+                        let months = if *unit == SeriesType::Year {
+                            steps * 12
+                        } else {
+                            steps
+                        };
+                        let delta = Months::new(u32::try_from(months.abs()).ok()?);
+                        if months >= 0 {
+                            date.checked_add_months(delta)?
+                        } else {
+                            date.checked_sub_months(delta)?
+                        }
+                    }
+                    _ => add_days(date, steps)?,
+                };
+                let serial_number = self
+                    .date_to_serial_number(date.day(), date.month(), date.year())
+                    .ok()?;
+                if serial_number as f64 > LAST_DATE {
+                    return None;
+                }
+                Some(serial_number as f64 + start.fract())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value at `position` of the `series` as it is typed in a cell.
+    /// The series cannot be [Series::Copy].
+    pub(crate) fn get_series_value(&self, series: &Series, position: i64) -> String {
+        match series {
+            Series::Copy => "".to_string(),
+            Series::Text {
+                prefix,
+                start,
+                step,
+                digits,
+            } => {
+                let number = (start + step * position).abs();
+                // The quote keeps the value a text
+                format!("'{prefix}{number:0digits$}")
+            }
+            Series::Names {
+                names,
+                start,
+                step,
+                case,
+            } => {
+                let index = (start + step * position).rem_euclid(names.len() as i64);
+                let name = &names[index as usize];
+                let name = match case {
+                    NameCase::Locale => name.clone(),
+                    NameCase::Upper => name.to_uppercase(),
+                    NameCase::Lower => name.to_lowercase(),
+                };
+                format!("'{name}")
+            }
+            _ => match self.get_series_number(series, position) {
+                Some(value) if value.is_finite() => format!("{}", to_precision(value, 15)),
+                _ => "#NUM!".to_string(),
+            },
+        }
+    }
+}
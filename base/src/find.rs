@@ -0,0 +1,84 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    expressions::types::CellReferenceIndex,
+    functions::util::from_wildcard_to_pattern,
+    model::Model,
+    types::{FindLookIn, FindMode, FindOptions, SearchOrder},
+};
+
+/// Returns the regular expression that finds `text` with the `options`
+pub(crate) fn get_find_regex(text: &str, options: &FindOptions) -> Result<Regex, String> {
+    if text.is_empty() {
+        return Err("The text to find cannot be empty".to_string());
+    }
+    let pattern = match options.mode {
+        FindMode::Text => regex::escape(text),
+        FindMode::Wildcard => from_wildcard_to_pattern(text),
+        FindMode::Regex => text.to_string(),
+    };
+    let pattern = if options.match_entire_cell {
+        format!("^(?:{pattern})$")
+    } else {
+        pattern
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!options.match_case)
+        .dot_matches_new_line(options.mode == FindMode::Wildcard)
+        .build()
+        .map_err(|_| format!("Invalid regular expression: '{text}'"))
+}
+
+impl Model {
+    /// Returns the text of the cell that is searched
+    fn get_find_text(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        look_in: FindLookIn,
+    ) -> Result<String, String> {
+        match look_in {
+            FindLookIn::Formulas => self.get_cell_content(sheet, row, column),
+            FindLookIn::Values => self.get_formatted_cell_value(sheet, row, column),
+        }
+    }
+
+    /// Returns the cells of the `sheets` that match the `regex`, sheet by sheet in the search
+    /// order of the `options`. If there is a `range` [row1, column1, row2, column2] only the cells
+    /// inside it are searched.
+    pub(crate) fn find_cells(
+        &self,
+        regex: &Regex,
+        options: &FindOptions,
+        sheets: &[u32],
+        range: Option<[i32; 4]>,
+    ) -> Result<Vec<CellReferenceIndex>, String> {
+        let mut cells = Vec::new();
+        for &sheet in sheets {
+            let worksheet = self.workbook.worksheet(sheet)?;
+            let mut positions = Vec::new();
+            for (row, data_row) in &worksheet.sheet_data {
+                for column in data_row.keys() {
+                    let is_inside = range.is_none_or(|[row1, column1, row2, column2]| {
+                        (row1..=row2).contains(row) && (column1..=column2).contains(column)
+                    });
+                    if is_inside {
+                        positions.push((*row, *column));
+                    }
+                }
+            }
+            match options.search_order {
+                SearchOrder::ByRows => positions.sort_unstable(),
+                SearchOrder::ByColumns => positions.sort_unstable_by_key(|&(r, c)| (c, r)),
+            }
+            for (row, column) in positions {
+                let text = self.get_find_text(sheet, row, column, options.look_in)?;
+                if !text.is_empty() && regex.is_match(&text) {
+                    cells.push(CellReferenceIndex { sheet, row, column });
+                }
+            }
+        }
+        Ok(cells)
+    }
+}
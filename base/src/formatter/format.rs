@@ -1,6 +1,10 @@
 use chrono::Datelike;
 
-use crate::{locale::Locale, number_format::to_precision};
+use crate::{
+    constants::EXCEL_DATE_1904_OFFSET,
+    locale::{get_locale, Locale},
+    number_format::to_precision,
+};
 
 use super::{
     dates::{date_to_serial_number, from_excel_date},
@@ -50,10 +54,9 @@ fn use_group_separator(use_thousands: bool, digit_index: i32, group_sizes: &str)
     false
 }
 
-pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Formatted {
-    let mut parser = Parser::new(format);
-    parser.parse();
-    let parts = parser.parts;
+/// Returns the section of the format used for `value` together with the value to format in it.
+/// Negative numbers in their own section are formatted without the sign.
+fn select_part(parts: &[ParsePart], value: f64) -> Option<(&ParsePart, f64)> {
     // There are four parts:
     // 1) Positive numbers
     // 2) Negative numbers
@@ -65,50 +68,52 @@ pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Form
     // When you skip code sections in your number format,
     // you must include a semicolon for each of the missing sections of code.
     // You can use the ampersand (&) text operator to join, or concatenate, two values.
-    let mut value = value_original;
-    let part;
     match parts.len() {
-        1 => {
-            part = &parts[0];
-        }
+        1 => Some((&parts[0], value)),
         2 => {
             if value >= 0.0 {
-                part = &parts[0]
-            } else {
-                value = -value;
-                part = &parts[1];
-            }
-        }
-        3 => {
-            if value > 0.0 {
-                part = &parts[0]
-            } else if value < 0.0 {
-                value = -value;
-                part = &parts[1];
+                Some((&parts[0], value))
             } else {
-                value = 0.0;
-                part = &parts[2];
+                Some((&parts[1], -value))
             }
         }
-        4 => {
+        3 | 4 => {
             if value > 0.0 {
-                part = &parts[0]
+                Some((&parts[0], value))
             } else if value < 0.0 {
-                value = -value;
-                part = &parts[1];
+                Some((&parts[1], -value))
             } else {
-                value = 0.0;
-                part = &parts[2];
+                Some((&parts[2], 0.0))
             }
         }
-        _ => {
+        _ => None,
+    }
+}
+
+pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Formatted {
+    format_number_in_date_system(value_original, format, locale, false)
+}
+
+/// Formats a number where date serial numbers are in the 1904 date system if `date_1904` is true
+pub fn format_number_in_date_system(
+    value_original: f64,
+    format: &str,
+    locale: &Locale,
+    date_1904: bool,
+) -> Formatted {
+    let mut parser = Parser::new(format);
+    parser.parse();
+    let parts = parser.parts;
+    let (part, mut value) = match select_part(&parts, value_original) {
+        Some(selected) => selected,
+        None => {
             return Formatted {
                 text: "#VALUE!".to_owned(),
                 color: None,
                 error: Some("Too many parts".to_owned()),
             };
         }
-    }
+    };
     match part {
         ParsePart::Error(..) => Formatted {
             text: "#VALUE!".to_owned(),
@@ -154,7 +159,14 @@ pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Form
         ParsePart::Date(p) => {
             let tokens = &p.tokens;
             let mut text = "".to_string();
-            if !(1.0..=2_958_465.0).contains(&value) {
+            // serial number in the 1900 date system
+            let (date_value, first_date) = if date_1904 {
+                let offset = EXCEL_DATE_1904_OFFSET as f64;
+                (value + offset, offset)
+            } else {
+                (value, 1.0)
+            };
+            if !(first_date..=2_958_465.0).contains(&date_value) {
                 // 2_958_465 is 31 December 9999
                 return Formatted {
                     text: "#VALUE!".to_owned(),
@@ -162,7 +174,7 @@ pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Form
                     error: Some("Date negative or too long".to_owned()),
                 };
             }
-            let date = from_excel_date(value as i64);
+            let date = from_excel_date(date_value as i64);
             for token in tokens {
                 match token {
                     TextToken::Literal(c) => {
@@ -429,6 +441,41 @@ pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Form
     }
 }
 
+/// Rounds `value` to the precision it is displayed with in `format`.
+/// This is used when the workbook calculates with "precision as displayed".
+/// Values with general, date or invalid formats are returned unchanged:
+/// 1.23456 with "0.00" ==> 1.23
+/// 0.123456 with "0.0%" ==> 0.123
+/// 1234.5 with "#,##0," ==> 1000
+pub(crate) fn to_displayed_precision(value: f64, format: &str) -> f64 {
+    let mut parser = Parser::new(format);
+    parser.parse();
+    let p = match select_part(&parser.parts, value) {
+        Some((ParsePart::Number(p), _)) => p,
+        _ => return value,
+    };
+    // We let the formatter write the digits of the section in a plain format
+    // (same decimals and notation, no literals or separators) and read them back
+    let mut plain_format = "0".to_string();
+    if p.precision > 0 {
+        plain_format.push('.');
+        plain_format.push_str(&"0".repeat(p.precision as usize));
+    }
+    if p.is_scientific {
+        plain_format.push_str("E+00");
+    }
+    let factor = 100.0_f64.powi(p.percent) / 1000.0_f64.powi(p.comma);
+    let locale = match get_locale("en") {
+        Ok(locale) => locale,
+        Err(_) => return value,
+    };
+    let text = format_number(value.abs() * factor, &plain_format, locale).text;
+    match text.parse::<f64>() {
+        Ok(displayed) => to_precision(displayed.copysign(value) / factor, 15),
+        Err(_) => value,
+    }
+}
+
 fn parse_day(day_str: &str) -> Result<(u32, String), String> {
     let bytes = day_str.bytes();
     let bytes_len = bytes.len();
@@ -533,7 +580,7 @@ fn parse_year(year_str: &str) -> Result<(i32, String), String> {
 // NOTE 1: The separator has to be the same
 // NOTE 2: In some engines "2/3" is implemented ad "2/March of the present year"
 // NOTE 3: I did not implement the "short date"
-fn parse_date(value: &str) -> Result<(i32, String), String> {
+fn parse_date(value: &str, date_1904: bool) -> Result<(i32, String), String> {
     let separator = if value.contains('/') {
         '/'
     } else if value.contains('-') {
@@ -565,9 +612,14 @@ fn parse_date(value: &str) -> Result<(i32, String), String> {
     let (month, month_format) = parse_month(month_str)?;
     let (year, year_format) = parse_year(year_str)?;
     let serial_number = match date_to_serial_number(day, month, year) {
+        Ok(n) if date_1904 => n - EXCEL_DATE_1904_OFFSET as i32,
         Ok(n) => n,
         Err(_) => return Err("Not a valid date".to_string()),
     };
+    if date_1904 && serial_number < 0 {
+        // There are no dates before 1 January 1904 in the 1904 date system
+        return Err("Not a valid date".to_string());
+    }
     if is_iso_date {
         Ok((
             serial_number,
@@ -586,9 +638,11 @@ fn parse_date(value: &str) -> Result<(i32, String), String> {
 /// "$ 123,345.678" => (123345.678, "$#,##0.00")
 /// "30.34%" => (0.3034, "0.00%")
 /// 100€ => (100, "100€")
+/// Dates are returned as serial numbers in the 1904 date system if `date_1904` is true
 pub(crate) fn parse_formatted_number(
     value: &str,
     currencies: &[&str],
+    date_1904: bool,
 ) -> Result<(f64, Option<String>), String> {
     let value = value.trim();
     let scientific_format = "0.00E+00";
@@ -642,7 +696,7 @@ pub(crate) fn parse_formatted_number(
         }
     }
 
-    if let Ok((serial_number, format)) = parse_date(value) {
+    if let Ok((serial_number, format)) = parse_date(value, date_1904) {
         return Ok((serial_number as f64, Some(format)));
     }
 
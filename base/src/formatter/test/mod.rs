@@ -1,2 +1,3 @@
+mod test_displayed_precision;
 mod test_general;
 mod test_parse_formatted_number;
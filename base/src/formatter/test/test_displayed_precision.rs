@@ -0,0 +1,41 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::formatter::format::{format_number, to_displayed_precision};
+use crate::locale::get_locale;
+
+#[test]
+fn number_formats() {
+    assert_eq!(to_displayed_precision(1.23456, "0.00"), 1.23);
+    assert_eq!(to_displayed_precision(-1.23556, "0.00"), -1.24);
+    assert_eq!(to_displayed_precision(1234.5678, "#,##0"), 1235.0);
+    assert_eq!(to_displayed_precision(0.1 + 0.2, "0.0"), 0.3);
+    assert_eq!(to_displayed_precision(0.123456, "0.0%"), 0.123);
+    assert_eq!(to_displayed_precision(1234.5, "#,##0,"), 1000.0);
+    assert_eq!(to_displayed_precision(123456.0, "0.00E+00"), 123000.0);
+}
+
+#[test]
+fn sections() {
+    // negative numbers use the second section
+    assert_eq!(to_displayed_precision(1.26, "0.0;(0)"), 1.3);
+    assert_eq!(to_displayed_precision(-1.26, "0.0;(0)"), -1.0);
+}
+
+#[test]
+fn unchanged() {
+    assert_eq!(to_displayed_precision(1.23456, "General"), 1.23456);
+    assert_eq!(to_displayed_precision(45005.75, "dd/mm/yyyy"), 45005.75);
+}
+
+#[test]
+fn agrees_with_formatter() {
+    let locale = get_locale("en").unwrap();
+    for value in [2.675, 1.005, 0.045, 99.995, 1234.565] {
+        let text = format_number(value, "0.00", locale).text;
+        assert_eq!(
+            to_displayed_precision(value, "0.00"),
+            text.parse::<f64>().unwrap(),
+            "{value}"
+        );
+    }
+}
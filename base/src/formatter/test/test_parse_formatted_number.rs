@@ -1,6 +1,10 @@
 #![allow(clippy::unwrap_used)]
 
-use crate::formatter::format::parse_formatted_number as parse;
+use crate::formatter::format::parse_formatted_number;
+
+fn parse(value: &str, currencies: &[&str]) -> Result<(f64, Option<String>), String> {
+    parse_formatted_number(value, currencies, false)
+}
 
 const PARSE_ERROR_MSG: &str = "Could not parse number";
 
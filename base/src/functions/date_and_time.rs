@@ -1,17 +1,49 @@
 use chrono::DateTime;
 use chrono::Datelike;
 use chrono::Months;
+use chrono::NaiveDate;
 use chrono::Timelike;
 
 use crate::expressions::types::CellReferenceIndex;
 use crate::formatter::dates::date_to_serial_number;
 use crate::model::get_milliseconds_since_epoch;
 use crate::{
-    calc_result::CalcResult, constants::EXCEL_DATE_BASE, expressions::parser::Node,
-    expressions::token::Error, formatter::dates::from_excel_date, model::Model,
+    calc_result::CalcResult,
+    constants::{EXCEL_DATE_1904_OFFSET, EXCEL_DATE_BASE},
+    expressions::parser::Node,
+    expressions::token::Error,
+    formatter::dates::from_excel_date,
+    model::Model,
 };
 
 impl Model {
+    /// Days between the origin of the 1900 date system and the origin of the workbook date system
+    pub(crate) fn date_system_offset(&self) -> i64 {
+        if self.workbook.settings.date_1904 {
+            EXCEL_DATE_1904_OFFSET
+        } else {
+            0
+        }
+    }
+
+    pub(crate) fn serial_number_to_date(&self, serial_number: i64) -> NaiveDate {
+        from_excel_date(serial_number + self.date_system_offset())
+    }
+
+    pub(crate) fn date_to_serial_number(
+        &self,
+        day: u32,
+        month: u32,
+        year: i32,
+    ) -> Result<i64, String> {
+        let serial_number =
+            date_to_serial_number(day, month, year)? as i64 - self.date_system_offset();
+        if serial_number < 0 {
+            return Err("Out of range parameters for date".to_string());
+        }
+        Ok(serial_number)
+    }
+
     pub(crate) fn fn_day(&mut self, args: &[Node], cell: CellReferenceIndex) -> CalcResult {
         let args_count = args.len();
         if args_count != 1 {
@@ -31,7 +63,7 @@ impl Model {
             }
             Err(s) => return s,
         };
-        let date = from_excel_date(serial_number);
+        let date = self.serial_number_to_date(serial_number);
         let day = date.day() as f64;
         CalcResult::Number(day)
     }
@@ -55,7 +87,7 @@ impl Model {
             }
             Err(s) => return s,
         };
-        let date = from_excel_date(serial_number);
+        let date = self.serial_number_to_date(serial_number);
         let month = date.month() as f64;
         CalcResult::Number(month)
     }
@@ -91,9 +123,9 @@ impl Model {
         let months_abs = months.unsigned_abs();
 
         let native_date = if months > 0 {
-            from_excel_date(serial_number) + Months::new(months_abs)
+            self.serial_number_to_date(serial_number) + Months::new(months_abs)
         } else {
-            from_excel_date(serial_number) - Months::new(months_abs)
+            self.serial_number_to_date(serial_number) - Months::new(months_abs)
         };
 
         // Instead of calculating the end of month we compute the first day of the following month
@@ -104,7 +136,7 @@ impl Model {
             month = 1;
             year += 1;
         }
-        match date_to_serial_number(1, month, year) {
+        match self.date_to_serial_number(1, month, year) {
             Ok(serial_number) => CalcResult::Number(serial_number as f64 - 1.0),
             Err(message) => CalcResult::Error {
                 error: Error::NUM,
@@ -162,7 +194,7 @@ impl Model {
             }
             Err(s) => return s,
         };
-        match date_to_serial_number(day, month, year) {
+        match self.date_to_serial_number(day, month, year) {
             Ok(serial_number) => CalcResult::Number(serial_number as f64),
             Err(message) => CalcResult::Error {
                 error: Error::NUM,
@@ -191,7 +223,7 @@ impl Model {
             }
             Err(s) => return s,
         };
-        let date = from_excel_date(serial_number);
+        let date = self.serial_number_to_date(serial_number);
         let year = date.year() as f64;
         CalcResult::Number(year)
     }
@@ -229,12 +261,13 @@ impl Model {
         let months_abs = months.unsigned_abs();
 
         let native_date = if months > 0 {
-            from_excel_date(serial_number) + Months::new(months_abs)
+            self.serial_number_to_date(serial_number) + Months::new(months_abs)
         } else {
-            from_excel_date(serial_number) - Months::new(months_abs)
+            self.serial_number_to_date(serial_number) - Months::new(months_abs)
         };
 
-        let serial_number = native_date.num_days_from_ce() - EXCEL_DATE_BASE;
+        let serial_number =
+            (native_date.num_days_from_ce() - EXCEL_DATE_BASE) as i64 - self.date_system_offset();
         if serial_number < 0 {
             return CalcResult::Error {
                 error: Error::NUM,
@@ -271,8 +304,9 @@ impl Model {
         // NaiveDate::from_ymd(1900, 1, 1).num_days_from_ce() - 2
         // The 2 days offset is because of Excel 1900 bug
         let days_from_1900 = local_time.num_days_from_ce() - 693_594;
+        let serial_number = days_from_1900 as i64 - self.date_system_offset();
 
-        CalcResult::Number(days_from_1900 as f64)
+        CalcResult::Number(serial_number as f64)
     }
 
     pub(crate) fn fn_now(&mut self, args: &[Node], cell: CellReferenceIndex) -> CalcResult {
@@ -301,8 +335,9 @@ impl Model {
         // NaiveDate::from_ymd(1900, 1, 1).num_days_from_ce() - 2
         // The 2 days offset is because of Excel 1900 bug
         let days_from_1900 = local_time.num_days_from_ce() - 693_594;
+        let serial_number = days_from_1900 as i64 - self.date_system_offset();
         let days = (local_time.num_seconds_from_midnight() as f64) / (60.0 * 60.0 * 24.0);
 
-        CalcResult::Number(days_from_1900 as f64 + days.fract())
+        CalcResult::Number(serial_number as f64 + days.fract())
     }
 }
@@ -18,12 +18,13 @@ fn is_valid_date(date: f64) -> bool {
     date > 0.0
 }
 
-fn is_less_than_one_year(start_date: i64, end_date: i64) -> bool {
+// `date_system_offset` converts the serial numbers of the workbook date system into the 1900 date system
+fn is_less_than_one_year(start_date: i64, end_date: i64, date_system_offset: i64) -> bool {
     if end_date - start_date < 365 {
         return true;
     }
-    let end = from_excel_date(end_date);
-    let start = from_excel_date(start_date);
+    let end = from_excel_date(end_date + date_system_offset);
+    let start = from_excel_date(start_date + date_system_offset);
     let end_year = end.year();
     let start_year = start.year();
     if end_year == start_year {
@@ -1436,7 +1437,11 @@ impl Model {
                 "settlement should be <= maturity".to_string(),
             );
         }
-        if !is_less_than_one_year(settlement as i64, maturity as i64) {
+        if !is_less_than_one_year(
+            settlement as i64,
+            maturity as i64,
+            self.date_system_offset(),
+        ) {
             return CalcResult::new_error(
                 Error::NUM,
                 cell,
@@ -1500,7 +1505,11 @@ impl Model {
                 "settlement should be <= maturity".to_string(),
             );
         }
-        if !is_less_than_one_year(settlement as i64, maturity as i64) {
+        if !is_less_than_one_year(
+            settlement as i64,
+            maturity as i64,
+            self.date_system_offset(),
+        ) {
             return CalcResult::new_error(
                 Error::NUM,
                 cell,
@@ -1550,7 +1559,11 @@ impl Model {
                 "settlement should be <= maturity".to_string(),
             );
         }
-        if !is_less_than_one_year(settlement as i64, maturity as i64) {
+        if !is_less_than_one_year(
+            settlement as i64,
+            maturity as i64,
+            self.date_system_offset(),
+        ) {
             return CalcResult::new_error(
                 Error::NUM,
                 cell,
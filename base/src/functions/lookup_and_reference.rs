@@ -1,7 +1,12 @@
 use crate::constants::{LAST_COLUMN, LAST_ROW};
 use crate::expressions::types::CellReferenceIndex;
+use crate::implicit_intersection::implicit_intersection;
+use crate::pivot_tables::{PivotCell, PivotValue};
 use crate::{
-    calc_result::CalcResult, expressions::parser::Node, expressions::token::Error, model::Model,
+    calc_result::{CalcResult, Range},
+    expressions::parser::Node,
+    expressions::token::Error,
+    model::Model,
     utils::ParsedReference,
 };
 
@@ -395,6 +400,122 @@ impl Model {
         }
     }
 
+    /// HYPERLINK(link_location, [friendly_name])
+    /// Returns `friendly_name` (or `link_location` if omitted) and makes the cell a link to `link_location`.
+    /// A location starting with '#' (like "#Sheet2!A1") points to a place in the workbook.
+    pub(crate) fn fn_hyperlink(&mut self, args: &[Node], cell: CellReferenceIndex) -> CalcResult {
+        if args.is_empty() || args.len() > 2 {
+            return CalcResult::new_args_number_error(cell);
+        }
+        let link_location = match self.get_string(&args[0], cell) {
+            Ok(s) => s,
+            Err(s) => return s,
+        };
+        let result = if args.len() == 2 {
+            match self.evaluate_node_in_context(&args[1], cell) {
+                CalcResult::Range { left, right } => {
+                    match implicit_intersection(&cell, &Range { left, right }) {
+                        Some(cell_reference) => self.evaluate_cell(cell_reference),
+                        None => {
+                            return CalcResult::new_error(
+                                Error::VALUE,
+                                cell,
+                                "Invalid reference".to_string(),
+                            )
+                        }
+                    }
+                }
+                CalcResult::EmptyCell | CalcResult::EmptyArg => CalcResult::Number(0.0),
+                result => result,
+            }
+        } else {
+            CalcResult::String(link_location.clone())
+        };
+        if !result.is_error() {
+            self.hyperlink_results
+                .insert((cell.sheet, cell.row, cell.column), link_location);
+        }
+        result
+    }
+
+    /// GETPIVOTDATA(data_field, pivot_table, [field1, item1], ...)
+    /// Returns the value of `data_field` in the pivot table containing the cell `pivot_table` for
+    /// the rows where each field has the given item. Fields must be row or column fields.
+    pub(crate) fn fn_getpivotdata(
+        &mut self,
+        args: &[Node],
+        cell: CellReferenceIndex,
+    ) -> CalcResult {
+        if args.len() < 2 || args.len() % 2 != 0 {
+            return CalcResult::new_args_number_error(cell);
+        }
+        let data_field = match self.get_string(&args[0], cell) {
+            Ok(s) => s,
+            Err(s) => return s,
+        };
+        let reference = match self.get_reference(&args[1], cell) {
+            Ok(range) => range.left,
+            Err(s) => return s,
+        };
+        let pivot_table =
+            match self.get_pivot_table_at(reference.sheet, reference.row, reference.column) {
+                Some(pivot_table) => pivot_table,
+                None => {
+                    return CalcResult::new_error(
+                        Error::REF,
+                        cell,
+                        "Pivot table not found".to_string(),
+                    )
+                }
+            };
+        let mut items = Vec::new();
+        for pair in args[2..].chunks(2) {
+            let field = match self.get_string(&pair[0], cell) {
+                Ok(s) => s,
+                Err(s) => return s,
+            };
+            let item = match self.evaluate_node_in_context(&pair[1], cell) {
+                CalcResult::Range { left, right } => {
+                    match implicit_intersection(&cell, &Range { left, right }) {
+                        Some(cell_reference) => self.evaluate_cell(cell_reference),
+                        None => {
+                            return CalcResult::new_error(
+                                Error::VALUE,
+                                cell,
+                                "Invalid reference".to_string(),
+                            )
+                        }
+                    }
+                }
+                result => result,
+            };
+            let item = match item {
+                CalcResult::Number(value) => PivotValue::Number(value),
+                CalcResult::String(value) => PivotValue::Text(value),
+                CalcResult::Boolean(value) => PivotValue::Boolean(value),
+                CalcResult::EmptyCell | CalcResult::EmptyArg => PivotValue::Blank,
+                error @ CalcResult::Error { .. } => return error,
+                _ => return CalcResult::new_error(Error::VALUE, cell, "Invalid item".to_string()),
+            };
+            items.push((field, item));
+        }
+        if let Err(message) = self.evaluate_pivot_source(&pivot_table) {
+            return CalcResult::new_error(Error::REF, cell, message);
+        }
+        match self.get_pivot_table_value(&pivot_table, &data_field, &items) {
+            Ok(Some(PivotCell::Number(value))) => CalcResult::Number(value),
+            Ok(Some(PivotCell::Error(error))) => {
+                CalcResult::new_error(error, cell, "Error in the pivot table".to_string())
+            }
+            Ok(_) => CalcResult::new_error(
+                Error::REF,
+                cell,
+                "The data is not in the pivot table".to_string(),
+            ),
+            Err(message) => CalcResult::new_error(Error::REF, cell, message),
+        }
+    }
+
     /// VLOOKUP(lookup_value, table_array, row_index, [is_sorted])
     /// We look for `lookup_value` in the first column of table array
     /// We return the value in column `column_index` of the same row in `table_array`
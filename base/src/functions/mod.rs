@@ -15,7 +15,7 @@ mod financial_util;
 mod information;
 mod logical;
 mod lookup_and_reference;
-mod mathematical;
+pub(crate) mod mathematical;
 mod statistical;
 mod subtotal;
 mod text;
@@ -92,7 +92,9 @@ pub enum Function {
     Type,
 
     // Lookup and reference
+    Getpivotdata,
     Hlookup,
+    Hyperlink,
     Index,
     Indirect,
     Lookup,
@@ -243,10 +245,11 @@ pub enum Function {
     Delta,
     Gestep,
     Subtotal,
+    Aggregate,
 }
 
 impl Function {
-    pub fn into_iter() -> IntoIter<Function, 192> {
+    pub fn into_iter() -> IntoIter<Function, 195> {
         [
             Function::And,
             Function::False,
@@ -293,7 +296,9 @@ impl Function {
             Function::Columns,
             Function::Index,
             Function::Indirect,
+            Function::Getpivotdata,
             Function::Hlookup,
+            Function::Hyperlink,
             Function::Lookup,
             Function::Match,
             Function::Offset,
@@ -440,6 +445,7 @@ impl Function {
             Function::Delta,
             Function::Gestep,
             Function::Subtotal,
+            Function::Aggregate,
         ]
         .into_iter()
     }
@@ -449,6 +455,7 @@ impl Function {
     /// Some functions in Excel like CONCAT are stringified as `_xlfn.CONCAT`.
     pub fn to_xlsx_string(&self) -> String {
         match self {
+            Function::Aggregate => "_xlfn.AGGREGATE".to_string(),
             Function::Concat => "_xlfn.CONCAT".to_string(),
             Function::Ifna => "_xlfn.IFNA".to_string(),
             Function::Ifs => "_xlfn.IFS".to_string(),
@@ -543,7 +550,9 @@ impl Function {
             "COLUMNS" => Some(Function::Columns),
             "INDEX" => Some(Function::Index),
             "INDIRECT" => Some(Function::Indirect),
+            "GETPIVOTDATA" => Some(Function::Getpivotdata),
             "HLOOKUP" => Some(Function::Hlookup),
+            "HYPERLINK" => Some(Function::Hyperlink),
             "LOOKUP" => Some(Function::Lookup),
             "MATCH" => Some(Function::Match),
             "OFFSET" => Some(Function::Offset),
@@ -703,6 +712,7 @@ impl Function {
             "GESTEP" => Some(Function::Gestep),
 
             "SUBTOTAL" => Some(Function::Subtotal),
+            "AGGREGATE" | "_XLFN.AGGREGATE" => Some(Function::Aggregate),
             _ => None,
         }
     }
@@ -756,7 +766,9 @@ impl fmt::Display for Function {
             Function::Columns => write!(f, "COLUMNS"),
             Function::Index => write!(f, "INDEX"),
             Function::Indirect => write!(f, "INDIRECT"),
+            Function::Getpivotdata => write!(f, "GETPIVOTDATA"),
             Function::Hlookup => write!(f, "HLOOKUP"),
+            Function::Hyperlink => write!(f, "HYPERLINK"),
             Function::Lookup => write!(f, "LOOKUP"),
             Function::Match => write!(f, "MATCH"),
             Function::Offset => write!(f, "OFFSET"),
@@ -905,6 +917,7 @@ impl fmt::Display for Function {
             Function::Gestep => write!(f, "GESTEP"),
 
             Function::Subtotal => write!(f, "SUBTOTAL"),
+            Function::Aggregate => write!(f, "AGGREGATE"),
         }
     }
 }
@@ -988,7 +1001,9 @@ impl Model {
             Function::Columns => self.fn_columns(args, cell),
             Function::Index => self.fn_index(args, cell),
             Function::Indirect => self.fn_indirect(args, cell),
+            Function::Getpivotdata => self.fn_getpivotdata(args, cell),
             Function::Hlookup => self.fn_hlookup(args, cell),
+            Function::Hyperlink => self.fn_hyperlink(args, cell),
             Function::Lookup => self.fn_lookup(args, cell),
             Function::Match => self.fn_match(args, cell),
             Function::Offset => self.fn_offset(args, cell),
@@ -1142,6 +1157,7 @@ impl Model {
             Function::Gestep => self.fn_gestep(args, cell),
 
             Function::Subtotal => self.fn_subtotal(args, cell),
+            Function::Aggregate => self.fn_aggregate(args, cell),
         }
     }
 }
@@ -1,10 +1,6 @@
 use crate::{
     calc_result::CalcResult,
-    expressions::{
-        parser::{parse_range, Node},
-        token::Error,
-        types::CellReferenceIndex,
-    },
+    expressions::{parser::Node, token::Error, types::CellReferenceIndex},
     functions::Function,
     model::Model,
 };
@@ -19,10 +15,59 @@ use crate::{
 /// If you try to hide rows in a table with filtered rows they will behave as if filtered
 /// // Also subtotals ignore subtotals
 ///
-#[derive(PartialEq)]
-enum SubTotalMode {
-    Full,
-    SkipHidden,
+/// The values ignored by SUBTOTAL and AGGREGATE. Filtered rows are always ignored.
+#[derive(Clone, Copy)]
+struct SubTotalMode {
+    /// Ignore the hidden rows
+    skip_hidden: bool,
+    /// Ignore the error values
+    skip_errors: bool,
+    /// Ignore nested SUBTOTAL and AGGREGATE functions
+    skip_nested: bool,
+}
+
+impl SubTotalMode {
+    const FULL: SubTotalMode = SubTotalMode {
+        skip_hidden: false,
+        skip_errors: false,
+        skip_nested: true,
+    };
+    const SKIP_HIDDEN: SubTotalMode = SubTotalMode {
+        skip_hidden: true,
+        skip_errors: false,
+        skip_nested: true,
+    };
+
+    /// Mode of the `options` argument of AGGREGATE
+    fn from_aggregate_options(options: i32) -> Option<SubTotalMode> {
+        if !(0..=7).contains(&options) {
+            return None;
+        }
+        Some(SubTotalMode {
+            skip_hidden: options % 2 == 1,
+            skip_errors: matches!(options % 4, 2 | 3),
+            skip_nested: options < 4,
+        })
+    }
+
+    fn skips(&self, status: &CellTableStatus) -> bool {
+        match status {
+            CellTableStatus::Normal => false,
+            CellTableStatus::Hidden => self.skip_hidden,
+            CellTableStatus::Filtered => true,
+        }
+    }
+
+    fn skips_node(&self, node: &Node) -> bool {
+        self.skip_nested
+            && matches!(
+                node,
+                Node::FunctionKind {
+                    kind: Function::Subtotal | Function::Aggregate,
+                    args: _
+                }
+            )
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -33,40 +78,13 @@ pub enum CellTableStatus {
 }
 
 impl Model {
-    fn get_table_for_cell(&self, sheet_index: u32, row: i32, column: i32) -> bool {
-        let worksheet = match self.workbook.worksheet(sheet_index) {
-            Ok(ws) => ws,
-            Err(_) => return false,
-        };
-        for table in self.workbook.tables.values() {
-            if worksheet.name != table.sheet_name {
-                continue;
-            }
-            // (column, row, column, row)
-            if let Ok((column1, row1, column2, row2)) = parse_range(&table.reference) {
-                if ((column >= column1) && (column <= column2)) && ((row >= row1) && (row <= row2))
-                {
-                    return table.has_filters;
-                }
-            }
-        }
-        false
-    }
-
     fn cell_hidden_status(&self, sheet_index: u32, row: i32, column: i32) -> CellTableStatus {
         let worksheet = self.workbook.worksheet(sheet_index).expect("");
-        let mut hidden = false;
-        for row_style in &worksheet.rows {
-            if row_style.r == row {
-                hidden = row_style.hidden;
-                break;
-            }
-        }
-        if !hidden {
+        if !worksheet.get_row(row).is_some_and(|r| r.hidden) {
             return CellTableStatus::Normal;
         }
-        // The row is hidden we need to know if the table has filters
-        if self.get_table_for_cell(sheet_index, row, column) {
+        // The row is hidden we need to know if it is in a table or an autofilter with filters
+        if self.is_cell_in_filtered_range(sheet_index, row, column) {
             CellTableStatus::Filtered
         } else {
             CellTableStatus::Hidden
@@ -95,7 +113,7 @@ impl Model {
                 matches!(
                     node,
                     Node::FunctionKind {
-                        kind: Function::Subtotal,
+                        kind: Function::Subtotal | Function::Aggregate,
                         args: _
                     }
                 )
@@ -113,10 +131,7 @@ impl Model {
         let mut result: Vec<f64> = Vec::new();
         for arg in args {
             match arg {
-                Node::FunctionKind {
-                    kind: Function::Subtotal,
-                    args: _,
-                } => {
+                node if mode.skips_node(node) => {
                     // skip
                 }
                 _ => {
@@ -126,7 +141,9 @@ impl Model {
                         }
                         CalcResult::Number(f) => result.push(f),
                         error @ CalcResult::Error { .. } => {
-                            return Err(error);
+                            if !mode.skip_errors {
+                                return Err(error);
+                            }
                         }
                         CalcResult::Range { left, right } => {
                             if left.sheet != right.sheet {
@@ -144,16 +161,13 @@ impl Model {
 
                             for row in row1..=row2 {
                                 let cell_status = self.cell_hidden_status(left.sheet, row, column1);
-                                if cell_status == CellTableStatus::Filtered {
-                                    continue;
-                                }
-                                if mode == SubTotalMode::SkipHidden
-                                    && cell_status == CellTableStatus::Hidden
-                                {
+                                if mode.skips(&cell_status) {
                                     continue;
                                 }
                                 for column in column1..=column2 {
-                                    if self.cell_is_subtotal(left.sheet, row, column) {
+                                    if mode.skip_nested
+                                        && self.cell_is_subtotal(left.sheet, row, column)
+                                    {
                                         continue;
                                     }
                                     match self.evaluate_cell(CellReferenceIndex {
@@ -164,7 +178,9 @@ impl Model {
                                         CalcResult::Number(value) => {
                                             result.push(value);
                                         }
-                                        error @ CalcResult::Error { .. } => return Err(error),
+                                        error @ CalcResult::Error { .. } if !mode.skip_errors => {
+                                            return Err(error);
+                                        }
                                         _ => {
                                             // We ignore booleans and strings
                                         }
@@ -188,34 +204,40 @@ impl Model {
             Ok(f) => f.trunc() as i32,
             Err(s) => return s,
         };
-        match value {
-            1 => self.subtotal_average(&args[1..], cell, SubTotalMode::Full),
-            2 => self.subtotal_count(&args[1..], cell, SubTotalMode::Full),
-            3 => self.subtotal_counta(&args[1..], cell, SubTotalMode::Full),
-            4 => self.subtotal_max(&args[1..], cell, SubTotalMode::Full),
-            5 => self.subtotal_min(&args[1..], cell, SubTotalMode::Full),
-            6 => self.subtotal_product(&args[1..], cell, SubTotalMode::Full),
-            7 => self.subtotal_stdevs(&args[1..], cell, SubTotalMode::Full),
-            8 => self.subtotal_stdevp(&args[1..], cell, SubTotalMode::Full),
-            9 => self.subtotal_sum(&args[1..], cell, SubTotalMode::Full),
-            10 => self.subtotal_vars(&args[1..], cell, SubTotalMode::Full),
-            11 => self.subtotal_varp(&args[1..], cell, SubTotalMode::Full),
-            101 => self.subtotal_average(&args[1..], cell, SubTotalMode::SkipHidden),
-            102 => self.subtotal_count(&args[1..], cell, SubTotalMode::SkipHidden),
-            103 => self.subtotal_counta(&args[1..], cell, SubTotalMode::SkipHidden),
-            104 => self.subtotal_max(&args[1..], cell, SubTotalMode::SkipHidden),
-            105 => self.subtotal_min(&args[1..], cell, SubTotalMode::SkipHidden),
-            106 => self.subtotal_product(&args[1..], cell, SubTotalMode::SkipHidden),
-            107 => self.subtotal_stdevs(&args[1..], cell, SubTotalMode::SkipHidden),
-            108 => self.subtotal_stdevp(&args[1..], cell, SubTotalMode::SkipHidden),
-            109 => self.subtotal_sum(&args[1..], cell, SubTotalMode::SkipHidden),
-            110 => self.subtotal_vars(&args[1..], cell, SubTotalMode::Full),
-            111 => self.subtotal_varp(&args[1..], cell, SubTotalMode::Full),
-            _ => CalcResult::new_error(
-                Error::VALUE,
-                cell,
-                format!("Invalid value for SUBTOTAL: {value}"),
-            ),
+        let mode = match value {
+            1..=11 => SubTotalMode::FULL,
+            101..=111 => SubTotalMode::SKIP_HIDDEN,
+            _ => {
+                return CalcResult::new_error(
+                    Error::VALUE,
+                    cell,
+                    format!("Invalid value for SUBTOTAL: {value}"),
+                )
+            }
+        };
+        self.subtotal_function(value % 100, &args[1..], cell, mode)
+    }
+
+    /// Evaluates the functions 1 to 11 of SUBTOTAL and AGGREGATE
+    fn subtotal_function(
+        &mut self,
+        function: i32,
+        args: &[Node],
+        cell: CellReferenceIndex,
+        mode: SubTotalMode,
+    ) -> CalcResult {
+        match function {
+            1 => self.subtotal_average(args, cell, mode),
+            2 => self.subtotal_count(args, cell, mode),
+            3 => self.subtotal_counta(args, cell, mode),
+            4 => self.subtotal_max(args, cell, mode),
+            5 => self.subtotal_min(args, cell, mode),
+            6 => self.subtotal_product(args, cell, mode),
+            7 => self.subtotal_stdevs(args, cell, mode),
+            8 => self.subtotal_stdevp(args, cell, mode),
+            9 => self.subtotal_sum(args, cell, mode),
+            10 => self.subtotal_vars(args, cell, mode),
+            _ => self.subtotal_varp(args, cell, mode),
         }
     }
 
@@ -354,10 +376,7 @@ impl Model {
         let mut counta = 0;
         for arg in args {
             match arg {
-                Node::FunctionKind {
-                    kind: Function::Subtotal,
-                    args: _,
-                } => {
+                node if mode.skips_node(node) => {
                     // skip
                 }
                 _ => {
@@ -381,16 +400,13 @@ impl Model {
 
                             for row in row1..=row2 {
                                 let cell_status = self.cell_hidden_status(left.sheet, row, column1);
-                                if cell_status == CellTableStatus::Filtered {
-                                    continue;
-                                }
-                                if mode == SubTotalMode::SkipHidden
-                                    && cell_status == CellTableStatus::Hidden
-                                {
+                                if mode.skips(&cell_status) {
                                     continue;
                                 }
                                 for column in column1..=column2 {
-                                    if self.cell_is_subtotal(left.sheet, row, column) {
+                                    if mode.skip_nested
+                                        && self.cell_is_subtotal(left.sheet, row, column)
+                                    {
                                         continue;
                                     }
                                     match self.evaluate_cell(CellReferenceIndex {
@@ -401,11 +417,17 @@ impl Model {
                                         CalcResult::EmptyCell | CalcResult::EmptyArg => {
                                             // skip
                                         }
+                                        CalcResult::Error { .. } if mode.skip_errors => {
+                                            // skip
+                                        }
                                         _ => counta += 1,
                                     }
                                 }
                             }
                         }
+                        CalcResult::Error { .. } if mode.skip_errors => {
+                            // skip
+                        }
                         CalcResult::String(_)
                         | CalcResult::Number(_)
                         | CalcResult::Boolean(_)
@@ -426,10 +448,7 @@ impl Model {
         let mut count = 0;
         for arg in args {
             match arg {
-                Node::FunctionKind {
-                    kind: Function::Subtotal,
-                    args: _,
-                } => {
+                node if mode.skips_node(node) => {
                     // skip
                 }
                 _ => {
@@ -450,16 +469,13 @@ impl Model {
 
                             for row in row1..=row2 {
                                 let cell_status = self.cell_hidden_status(left.sheet, row, column1);
-                                if cell_status == CellTableStatus::Filtered {
-                                    continue;
-                                }
-                                if mode == SubTotalMode::SkipHidden
-                                    && cell_status == CellTableStatus::Hidden
-                                {
+                                if mode.skips(&cell_status) {
                                     continue;
                                 }
                                 for column in column1..=column2 {
-                                    if self.cell_is_subtotal(left.sheet, row, column) {
+                                    if mode.skip_nested
+                                        && self.cell_is_subtotal(left.sheet, row, column)
+                                    {
                                         continue;
                                     }
                                     if let CalcResult::Number(_) =
@@ -582,4 +598,173 @@ impl Model {
         }
         CalcResult::Number(result)
     }
+
+    /// AGGREGATE(function, options, ref1, [ref2], ...) or AGGREGATE(function, options, array, k)
+    /// Like SUBTOTAL, with more functions and options to ignore hidden rows, errors and nested
+    /// SUBTOTAL and AGGREGATE functions. Filtered rows are always ignored.
+    pub(crate) fn fn_aggregate(&mut self, args: &[Node], cell: CellReferenceIndex) -> CalcResult {
+        if args.len() < 3 {
+            return CalcResult::new_args_number_error(cell);
+        }
+        let function = match self.get_number(&args[0], cell) {
+            Ok(f) => f.trunc() as i32,
+            Err(s) => return s,
+        };
+        let options = match self.get_number(&args[1], cell) {
+            Ok(f) => f.trunc() as i32,
+            Err(s) => return s,
+        };
+        let mode = match SubTotalMode::from_aggregate_options(options) {
+            Some(mode) => mode,
+            None => {
+                return CalcResult::new_error(
+                    Error::VALUE,
+                    cell,
+                    format!("Invalid options for AGGREGATE: {options}"),
+                )
+            }
+        };
+        let args = &args[2..];
+        if (14..=19).contains(&function) && args.len() != 2 {
+            return CalcResult::new_args_number_error(cell);
+        }
+        match function {
+            1..=11 => self.subtotal_function(function, args, cell, mode),
+            12 => self.aggregate_median(args, cell, mode),
+            13 => self.aggregate_mode(args, cell, mode),
+            14..=19 => {
+                let values = match self.subtotal_get_values(&args[..1], cell, mode) {
+                    Ok(s) => s,
+                    Err(s) => return s,
+                };
+                let k = match self.get_number(&args[1], cell) {
+                    Ok(f) => f,
+                    Err(s) => return s,
+                };
+                aggregate_k(function, values, k, cell)
+            }
+            _ => CalcResult::new_error(
+                Error::VALUE,
+                cell,
+                format!("Invalid function for AGGREGATE: {function}"),
+            ),
+        }
+    }
+
+    fn aggregate_median(
+        &mut self,
+        args: &[Node],
+        cell: CellReferenceIndex,
+        mode: SubTotalMode,
+    ) -> CalcResult {
+        let mut values = match self.subtotal_get_values(args, cell, mode) {
+            Ok(s) => s,
+            Err(s) => return s,
+        };
+        if values.is_empty() {
+            return CalcResult::new_error(Error::NUM, cell, "Empty list of values".to_string());
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+        CalcResult::Number(percentile_inc(&values, 0.5))
+    }
+
+    fn aggregate_mode(
+        &mut self,
+        args: &[Node],
+        cell: CellReferenceIndex,
+        mode: SubTotalMode,
+    ) -> CalcResult {
+        let values = match self.subtotal_get_values(args, cell, mode) {
+            Ok(s) => s,
+            Err(s) => return s,
+        };
+        // The most repeated value, the first one if there is a tie
+        let mut result = None;
+        let mut max_count = 1;
+        for value in &values {
+            let count = values.iter().filter(|v| *v == value).count();
+            if count > max_count {
+                max_count = count;
+                result = Some(*value);
+            }
+        }
+        match result {
+            Some(value) => CalcResult::Number(value),
+            None => CalcResult::new_error(Error::NA, cell, "No repeated values".to_string()),
+        }
+    }
+}
+
+/// Same as PERCENTILE.INC. `values` must be sorted and not empty
+fn percentile_inc(values: &[f64], k: f64) -> f64 {
+    let rank = k * (values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    values[lower] + (values[upper] - values[lower]) * (rank - lower as f64)
+}
+
+/// Same as PERCENTILE.EXC. `values` must be sorted
+fn percentile_exc(values: &[f64], k: f64) -> Option<f64> {
+    let rank = k * (values.len() + 1) as f64;
+    if rank < 1.0 || rank > values.len() as f64 {
+        return None;
+    }
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    Some(values[lower - 1] + (values[upper - 1] - values[lower - 1]) * (rank - lower as f64))
+}
+
+/// Evaluates the functions 14 to 19 of AGGREGATE: LARGE, SMALL, PERCENTILE.INC, QUARTILE.INC,
+/// PERCENTILE.EXC and QUARTILE.EXC
+fn aggregate_k(
+    function: i32,
+    mut values: Vec<f64>,
+    k: f64,
+    cell: CellReferenceIndex,
+) -> CalcResult {
+    let num_error = || CalcResult::new_error(Error::NUM, cell, "Invalid parameters".to_string());
+    if values.is_empty() {
+        return num_error();
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let n = values.len();
+    let result = match function {
+        14 | 15 => {
+            let k = k.ceil();
+            if k < 1.0 || k > n as f64 {
+                return num_error();
+            }
+            if function == 14 {
+                values[n - k as usize]
+            } else {
+                values[k as usize - 1]
+            }
+        }
+        16 | 17 => {
+            let k = if function == 17 { k.trunc() / 4.0 } else { k };
+            if !(0.0..=1.0).contains(&k) {
+                return num_error();
+            }
+            percentile_inc(&values, k)
+        }
+        _ => {
+            let k = if function == 19 {
+                let quart = k.trunc();
+                if !(1.0..=3.0).contains(&quart) {
+                    return num_error();
+                }
+                quart / 4.0
+            } else {
+                k
+            };
+            if k <= 0.0 || k >= 1.0 {
+                return num_error();
+            }
+            match percentile_exc(&values, k) {
+                Some(value) => value,
+                None => return num_error(),
+            }
+        }
+    };
+    CalcResult::Number(result)
 }
@@ -2,7 +2,7 @@ use crate::{
     calc_result::CalcResult,
     constants::{LAST_COLUMN, LAST_ROW},
     expressions::{parser::Node, token::Error, types::CellReferenceIndex},
-    formatter::format::{format_number, parse_formatted_number},
+    formatter::format::{format_number_in_date_system, parse_formatted_number},
     model::Model,
     number_format::to_precision,
 };
@@ -130,7 +130,12 @@ impl Model {
                 Ok(s) => s,
                 Err(s) => return s,
             };
-            let d = format_number(value, &format_code, &self.locale);
+            let d = format_number_in_date_system(
+                value,
+                &format_code,
+                &self.locale,
+                self.workbook.settings.date_1904,
+            );
             if let Some(_e) = d.error {
                 return CalcResult::Error {
                     error: Error::VALUE,
@@ -1045,7 +1050,8 @@ impl Model {
         match self.evaluate_node_in_context(&args[0], cell) {
             CalcResult::String(text) => {
                 let currencies = vec!["$", "€"];
-                if let Ok((value, _)) = parse_formatted_number(&text, &currencies) {
+                let date_1904 = self.workbook.settings.date_1904;
+                if let Ok((value, _)) = parse_formatted_number(&text, &currencies, date_1904) {
                     return CalcResult::Number(value);
                 };
                 CalcResult::Error {
@@ -80,11 +80,8 @@ pub(crate) fn compare_values(left: &CalcResult, right: &CalcResult) -> i32 {
     }
 }
 
-/// We convert an Excel wildcard into a Rust (Perl family) regex
-pub(crate) fn from_wildcard_to_regex(
-    wildcard: &str,
-    exact: bool,
-) -> Result<regex::Regex, regex::Error> {
+/// We convert an Excel wildcard into a Rust (Perl family) regex pattern
+pub(crate) fn from_wildcard_to_pattern(wildcard: &str) -> String {
     // 1. Escape all
     let reg = &escape(wildcard);
 
@@ -108,10 +105,19 @@ pub(crate) fn from_wildcard_to_regex(
     let reg = &reg.replace("??", "\\~");
 
     // And we have a valid Perl regex! (As Kim Kardashian said before me: "I know, right?")
+    reg.to_string()
+}
+
+/// We convert an Excel wildcard into a Rust (Perl family) regex
+pub(crate) fn from_wildcard_to_regex(
+    wildcard: &str,
+    exact: bool,
+) -> Result<regex::Regex, regex::Error> {
+    let reg = from_wildcard_to_pattern(wildcard);
     if exact {
         return Regex::new(&format!("^{}$", reg));
     }
-    Regex::new(reg)
+    Regex::new(&reg)
 }
 
 /// NUMBERS ///
@@ -0,0 +1,176 @@
+use crate::{
+    comments::cell_ref,
+    displacement::{
+        displace_anchored, displace_cell_columns, displace_cell_rows, filter_anchored, CellAnchored,
+    },
+    model::Model,
+    types::Hyperlink,
+};
+
+impl Model {
+    fn hyperlink_index(&self, sheet: u32, row: i32, column: i32) -> Result<Option<usize>, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .hyperlinks
+            .iter()
+            .position(|hyperlink| hyperlink.cell() == Some((row, column))))
+    }
+
+    /// Returns all the hyperlinks in the `sheet`
+    pub fn get_hyperlinks(&self, sheet: u32) -> Result<Vec<Hyperlink>, String> {
+        Ok(self.workbook.worksheet(sheet)?.hyperlinks.clone())
+    }
+
+    /// Returns the hyperlink attached to the cell, if any.
+    /// Links created by a HYPERLINK formula are not included, see [Model::get_cell_hyperlink].
+    pub fn get_hyperlink(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Hyperlink>, String> {
+        Ok(self
+            .hyperlink_index(sheet, row, column)?
+            .map(|index| self.workbook.worksheets[sheet as usize].hyperlinks[index].clone()))
+    }
+
+    /// Returns the link that is followed when the cell is clicked, if any.
+    /// That is the hyperlink attached to the cell or the result of a HYPERLINK formula.
+    pub fn get_cell_hyperlink(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Hyperlink>, String> {
+        if let Some(hyperlink) = self.get_hyperlink(sheet, row, column)? {
+            return Ok(Some(hyperlink));
+        }
+        let target = match self.hyperlink_results.get(&(sheet, row, column)) {
+            Some(target) => target,
+            None => return Ok(None),
+        };
+        let (url, location) = match target.strip_prefix('#') {
+            Some(location) => (None, Some(location.to_string())),
+            None => (Some(target.to_string()), None),
+        };
+        Ok(Some(Hyperlink {
+            cell_ref: cell_ref(row, column)?,
+            url,
+            location,
+            tooltip: None,
+        }))
+    }
+
+    /// Attaches a hyperlink to the cell, replacing the existing one if any.
+    /// The link points to an external `url` (like "https://ironcalc.com" or "mailto:info@ironcalc.com")
+    /// or to a `location` in the workbook (like "Sheet2!A1").
+    /// The `tooltip` is displayed when the mouse is over the cell.
+    pub fn set_hyperlink(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        url: Option<String>,
+        location: Option<String>,
+        tooltip: Option<String>,
+    ) -> Result<(), String> {
+        let url = url.filter(|url| !url.is_empty());
+        let location = location.filter(|location| !location.is_empty());
+        if url.is_none() && location.is_none() {
+            return Err("A hyperlink needs a url or a location".to_string());
+        }
+        let hyperlink = Hyperlink {
+            cell_ref: cell_ref(row, column)?,
+            url,
+            location,
+            tooltip: tooltip.filter(|tooltip| !tooltip.is_empty()),
+        };
+        self.update_hyperlink(sheet, row, column, Some(hyperlink))
+    }
+
+    /// Removes the hyperlink attached to the cell
+    pub fn delete_hyperlink(&mut self, sheet: u32, row: i32, column: i32) -> Result<(), String> {
+        match self.hyperlink_index(sheet, row, column)? {
+            Some(index) => {
+                self.workbook.worksheet_mut(sheet)?.hyperlinks.remove(index);
+                Ok(())
+            }
+            None => Err("The cell has no hyperlink".to_string()),
+        }
+    }
+
+    /// Returns the hyperlinks attached to the cells of `row`
+    pub(crate) fn get_hyperlinks_in_row(
+        &self,
+        sheet: u32,
+        row: i32,
+    ) -> Result<Vec<Hyperlink>, String> {
+        let hyperlinks = &self.workbook.worksheet(sheet)?.hyperlinks;
+        Ok(filter_anchored(hyperlinks, |(r, _)| r == row))
+    }
+
+    /// Returns the hyperlinks attached to the cells of `column`
+    pub(crate) fn get_hyperlinks_in_column(
+        &self,
+        sheet: u32,
+        column: i32,
+    ) -> Result<Vec<Hyperlink>, String> {
+        let hyperlinks = &self.workbook.worksheet(sheet)?.hyperlinks;
+        Ok(filter_anchored(hyperlinks, |(_, c)| c == column))
+    }
+
+    /// Replaces the hyperlink of the cell, adds it if there is none or removes it if `hyperlink` is None
+    pub(crate) fn update_hyperlink(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        hyperlink: Option<Hyperlink>,
+    ) -> Result<(), String> {
+        let index = self.hyperlink_index(sheet, row, column)?;
+        let hyperlinks = &mut self.workbook.worksheet_mut(sheet)?.hyperlinks;
+        match (index, hyperlink) {
+            (Some(index), Some(hyperlink)) => hyperlinks[index] = hyperlink,
+            (Some(index), None) => {
+                hyperlinks.remove(index);
+            }
+            (None, Some(hyperlink)) => hyperlinks.push(hyperlink),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Moves the hyperlinks after inserting (`delta` > 0) or deleting (`delta` < 0) rows at `row`.
+    /// Hyperlinks in deleted rows are removed.
+    pub(crate) fn displace_hyperlinks_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        self.displace_hyperlinks(sheet, |cell| displace_cell_rows(cell, row, delta))
+    }
+
+    /// Moves the hyperlinks after inserting (`delta` > 0) or deleting (`delta` < 0) columns at `column`.
+    /// Hyperlinks in deleted columns are removed.
+    pub(crate) fn displace_hyperlinks_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        self.displace_hyperlinks(sheet, |cell| displace_cell_columns(cell, column, delta))
+    }
+
+    /// Moves each hyperlink to the cell given by `displace`, or removes it if that is None
+    pub(crate) fn displace_hyperlinks(
+        &mut self,
+        sheet: u32,
+        displace: impl Fn((i32, i32)) -> Option<(i32, i32)>,
+    ) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        worksheet.hyperlinks = displace_anchored(&worksheet.hyperlinks, displace)?;
+        Ok(())
+    }
+}
@@ -29,6 +29,9 @@ pub struct Errors {
 pub struct Language {
     pub booleans: Booleans,
     pub errors: Errors,
+    /// Localized function names keyed by their English name.
+    /// Functions that are named like in English are not listed.
+    pub functions: HashMap<String, String>,
 }
 
 static LANGUAGES: Lazy<HashMap<String, Language>> = Lazy::new(|| {
@@ -41,3 +44,10 @@ pub fn get_language(id: &str) -> Result<&Language, String> {
         .ok_or(format!("Language is not supported: '{}'", id))?;
     Ok(language)
 }
+
+/// Returns the ids of all the languages
+pub fn get_language_ids() -> Vec<&'static str> {
+    let mut ids: Vec<&str> = LANGUAGES.keys().map(|id| id.as_str()).collect();
+    ids.sort_unstable();
+    ids
+}
@@ -37,13 +37,32 @@ pub mod types;
 pub mod worksheet;
 
 mod actions;
+mod auto_filter;
 mod cast;
+mod comments;
+mod conditional_formatting;
 mod constants;
+mod copy_sheet;
+mod data_tools;
+mod data_validation;
 mod diffs;
+mod displacement;
+mod fill_series;
+mod find;
 mod functions;
+mod hyperlinks;
 mod implicit_intersection;
+mod merge_cells;
 mod model;
+mod named_styles;
+mod outline;
+mod pivot_tables;
+mod protection;
+mod rich_text;
+mod sort;
 mod styles;
+mod tables;
+mod theme;
 mod units;
 mod user_model;
 mod utils;
@@ -59,4 +78,7 @@ pub use model::get_milliseconds_since_epoch;
 pub use model::Model;
 pub use user_model::BorderArea;
 pub use user_model::ClipboardData;
+pub use user_model::PasteMode;
+pub use user_model::PasteOperation;
+pub use user_model::PasteOptions;
 pub use user_model::UserModel;
@@ -30,6 +30,18 @@ pub struct Dates {
     pub months: Vec<String>,
     pub months_short: Vec<String>,
     pub months_letter: Vec<String>,
+    pub date_formats: DateFormats,
+    /// 0 for Sunday, 1 for Monday, ...
+    pub first_day_of_week: u8,
+}
+
+// See: https://cldr.unicode.org/translation/date-time/date-time-patterns
+#[derive(Encode, Decode, Clone)]
+pub struct DateFormats {
+    pub full: String,
+    pub long: String,
+    pub medium: String,
+    pub short: String,
 }
 
 #[derive(Encode, Decode, Clone)]
@@ -65,11 +77,54 @@ pub struct DecimalFormats {
     pub standard: String,
 }
 
-static LOCALES: Lazy<HashMap<String, Locale>> =
-    Lazy::new(|| bitcode::decode(include_bytes!("locales.bin")).expect("Failed parsing locale"));
+// Locales are generated with `generate_locale`, one file per locale.
+// 'en' is always bundled because formulas are stored in that locale,
+// the others can be left out with their cargo feature (see `Cargo.toml`).
+static BUNDLED_LOCALES: &[(&str, &[u8])] = &[
+    ("en", include_bytes!("locales/en.bin")),
+    #[cfg(feature = "locale-en-gb")]
+    ("en-GB", include_bytes!("locales/en-GB.bin")),
+    #[cfg(feature = "locale-es")]
+    ("es", include_bytes!("locales/es.bin")),
+    #[cfg(feature = "locale-de")]
+    ("de", include_bytes!("locales/de.bin")),
+    #[cfg(feature = "locale-fr")]
+    ("fr", include_bytes!("locales/fr.bin")),
+    #[cfg(feature = "locale-it")]
+    ("it", include_bytes!("locales/it.bin")),
+    #[cfg(feature = "locale-pt-br")]
+    ("pt-BR", include_bytes!("locales/pt-BR.bin")),
+    #[cfg(feature = "locale-nl")]
+    ("nl", include_bytes!("locales/nl.bin")),
+    #[cfg(feature = "locale-ja")]
+    ("ja", include_bytes!("locales/ja.bin")),
+    #[cfg(feature = "locale-zh-cn")]
+    ("zh-CN", include_bytes!("locales/zh-CN.bin")),
+    #[cfg(feature = "locale-ko")]
+    ("ko", include_bytes!("locales/ko.bin")),
+    #[cfg(feature = "locale-pl")]
+    ("pl", include_bytes!("locales/pl.bin")),
+    #[cfg(feature = "locale-sv")]
+    ("sv", include_bytes!("locales/sv.bin")),
+];
+
+static LOCALES: Lazy<HashMap<String, Locale>> = Lazy::new(|| {
+    BUNDLED_LOCALES
+        .iter()
+        .map(|(id, bytes)| {
+            let locale = bitcode::decode(bytes).expect("Failed parsing locale");
+            (id.to_string(), locale)
+        })
+        .collect()
+});
 
 pub fn get_locale(id: &str) -> Result<&Locale, String> {
     // TODO: pass the locale once we implement locales in Rust
     let locale = LOCALES.get(id).ok_or("Invalid locale")?;
     Ok(locale)
 }
+
+/// Returns the ids of all the locales in this build
+pub fn get_locale_ids() -> Vec<&'static str> {
+    BUNDLED_LOCALES.iter().map(|(id, _)| *id).collect()
+}
@@ -0,0 +1,193 @@
+use crate::{
+    conditional_formatting::parse_sqref, expressions::utils::number_to_column, model::Model,
+    types::Cell,
+};
+
+/// Parses a range like "B2:C4" (or a single cell like "B2") into (row1, column1, row2, column2)
+pub(crate) fn parse_range_reference(reference: &str) -> Result<(i32, i32, i32, i32), String> {
+    match parse_sqref(reference).as_deref() {
+        Ok([range]) => Ok(*range),
+        _ => Err(format!("Invalid range: '{reference}'")),
+    }
+}
+
+/// Returns a string like "B2:C4" for the range (row1, column1, row2, column2)
+pub(crate) fn range_to_string(range: (i32, i32, i32, i32)) -> Result<String, String> {
+    let (row1, column1, row2, column2) = range;
+    match (number_to_column(column1), number_to_column(column2)) {
+        (Some(first), Some(last)) => Ok(format!("{first}{row1}:{last}{row2}")),
+        _ => Err("Invalid column".to_string()),
+    }
+}
+
+/// Returns true if the range (row1, column1, row2, column2) `inner` is inside `outer`
+pub(crate) fn contains(outer: (i32, i32, i32, i32), inner: (i32, i32, i32, i32)) -> bool {
+    outer.0 <= inner.0 && outer.1 <= inner.1 && inner.2 <= outer.2 && inner.3 <= outer.3
+}
+
+/// Returns true if the ranges (row1, column1, row2, column2) have cells in common
+pub(crate) fn intersects(a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)) -> bool {
+    a.0 <= b.2 && b.0 <= a.2 && a.1 <= b.3 && b.1 <= a.3
+}
+
+impl Model {
+    fn get_merged_ranges(&self, sheet: u32) -> Result<Vec<(i32, i32, i32, i32)>, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .merge_cells
+            .iter()
+            .filter_map(|range| parse_range_reference(range).ok())
+            .collect())
+    }
+
+    /// Returns the merged areas of the `sheet` as a list of ranges like "B2:C4"
+    pub fn get_merged_cells(&self, sheet: u32) -> Result<Vec<String>, String> {
+        Ok(self.workbook.worksheet(sheet)?.merge_cells.clone())
+    }
+
+    /// Returns the merged area (row1, column1, row2, column2) that contains the cell, if any
+    pub fn get_merged_area(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<(i32, i32, i32, i32)>, String> {
+        Ok(self
+            .get_merged_ranges(sheet)?
+            .into_iter()
+            .find(|&range| intersects(range, (row, column, row, column))))
+    }
+
+    /// Returns the merged areas that intersect the range (row1, column1, row2, column2)
+    pub(crate) fn get_merged_cells_in_range(
+        &self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+    ) -> Result<Vec<String>, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .merge_cells
+            .iter()
+            .filter(|merged| {
+                parse_range_reference(merged).is_ok_and(|merged| intersects(merged, range))
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Returns the merged areas of the `sheet` moved by `displace`, which returns None for the
+    /// areas that are removed
+    pub(crate) fn get_displaced_merged_cells(
+        &self,
+        sheet: u32,
+        displace: impl Fn((i32, i32, i32, i32)) -> Result<Option<(i32, i32, i32, i32)>, String>,
+    ) -> Result<Vec<String>, String> {
+        let mut merge_cells = Vec::new();
+        for range in &self.workbook.worksheet(sheet)?.merge_cells {
+            match parse_range_reference(range) {
+                Ok(merged) => {
+                    if let Some(merged) = displace(merged)? {
+                        merge_cells.push(range_to_string(merged)?);
+                    }
+                }
+                // We leave the ranges we do not understand alone
+                Err(_) => merge_cells.push(range.clone()),
+            }
+        }
+        Ok(merge_cells)
+    }
+
+    /// Grows the range (row1, column1, row2, column2) until it doesn't cut through any merged area
+    pub(crate) fn expand_to_merged_cells(
+        &self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+    ) -> Result<(i32, i32, i32, i32), String> {
+        let merged_ranges = self.get_merged_ranges(sheet)?;
+        let mut range = range;
+        loop {
+            let mut expanded = range;
+            for &merged in &merged_ranges {
+                if intersects(merged, expanded) {
+                    expanded = (
+                        expanded.0.min(merged.0),
+                        expanded.1.min(merged.1),
+                        expanded.2.max(merged.2),
+                        expanded.3.max(merged.3),
+                    );
+                }
+            }
+            if expanded == range {
+                return Ok(range);
+            }
+            range = expanded;
+        }
+    }
+
+    /// Returns the cells with content in the merged `range` that are not the upper-left one.
+    /// Those are the cells that are cleared when the range is merged.
+    pub(crate) fn get_cells_hidden_by_merge(
+        &self,
+        sheet: u32,
+        range: &str,
+    ) -> Result<Vec<(i32, i32)>, String> {
+        let (row1, column1, row2, column2) = parse_range_reference(range)?;
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let mut cells = Vec::new();
+        for (row, data_row) in &worksheet.sheet_data {
+            if !(row1..=row2).contains(row) {
+                continue;
+            }
+            for (column, cell) in data_row {
+                if (column1..=column2).contains(column)
+                    && (*row, *column) != (row1, column1)
+                    && !matches!(cell, Cell::EmptyCell { .. })
+                {
+                    cells.push((*row, *column));
+                }
+            }
+        }
+        cells.sort();
+        Ok(cells)
+    }
+
+    /// Merges the cells in `range` (like "B2:C4").
+    /// As in Excel only the value of the upper-left cell is kept, the contents of the others are cleared.
+    pub fn merge_cells(&mut self, sheet: u32, range: &str) -> Result<(), String> {
+        let (row1, column1, row2, column2) = parse_range_reference(range)?;
+        if row1 == row2 && column1 == column2 {
+            return Err("Cannot merge a single cell".to_string());
+        }
+        if !self
+            .get_merged_cells_in_range(sheet, (row1, column1, row2, column2))?
+            .is_empty()
+        {
+            return Err("The range overlaps an existing merged area".to_string());
+        }
+        for (row, column) in self.get_cells_hidden_by_merge(sheet, range)? {
+            self.cell_clear_contents(sheet, row, column)?;
+        }
+        let range = range_to_string((row1, column1, row2, column2))?;
+        self.workbook.worksheet_mut(sheet)?.merge_cells.push(range);
+        Ok(())
+    }
+
+    /// Unmerges the merged area `range` (like "B2:C4")
+    pub fn unmerge_cells(&mut self, sheet: u32, range: &str) -> Result<(), String> {
+        let merged = parse_range_reference(range)?;
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        match worksheet
+            .merge_cells
+            .iter()
+            .position(|r| parse_range_reference(r).is_ok_and(|r| r == merged))
+        {
+            Some(index) => {
+                worksheet.merge_cells.remove(index);
+                Ok(())
+            }
+            None => Err(format!("Merged area '{range}' not found")),
+        }
+    }
+}
@@ -10,15 +10,19 @@ use crate::{
     expressions::{
         parser::{
             move_formula::{move_formula, MoveContext},
-            stringify::{to_rc_format, to_string},
+            stringify::{to_rc_format, to_string, transpose_references_in_node},
             Node, Parser,
         },
-        token::{get_error_by_name, Error, OpCompare, OpProduct, OpSum, OpUnary},
+        token::{
+            get_error_by_english_name, get_error_by_name, Error, OpCompare, OpProduct, OpSum,
+            OpUnary,
+        },
+        translate::{to_canonical_formula, to_localized_formula},
         types::*,
         utils::{self, is_valid_column_number, is_valid_row},
     },
     formatter::{
-        format::{format_number, parse_formatted_number},
+        format::{format_number_in_date_system, parse_formatted_number, to_displayed_precision},
         lexer::is_likely_date_number_format,
     },
     functions::util::compare_values,
@@ -108,6 +112,10 @@ pub struct Model {
     pub(crate) parser: Parser,
     /// The list of cells with formulas that are evaluated of being evaluated
     pub(crate) cells: HashMap<(u32, i32, i32), CellState>,
+    /// The outcome of the conditional formats, computed when the model is evaluated
+    pub(crate) conditional_format_results: HashMap<(u32, i32, i32), ConditionalFormatResult>,
+    /// The targets of the cells whose formula is a HYPERLINK, computed when the model is evaluated
+    pub(crate) hyperlink_results: HashMap<(u32, i32, i32), String>,
     /// The locale of the model
     pub(crate) locale: Locale,
     /// Tha language used
@@ -563,12 +571,20 @@ impl Model {
                             },
                         );
                     }
+                    let v = if self.workbook.settings.precision_as_displayed {
+                        match self.workbook.styles.get_style(s) {
+                            Ok(style) => to_displayed_precision(*value, &style.num_fmt),
+                            Err(_) => *value,
+                        }
+                    } else {
+                        *value
+                    };
                     *self.workbook.worksheets[sheet as usize]
                         .sheet_data
                         .get_mut(&row)
                         .expect("expected a row")
                         .get_mut(&column)
-                        .expect("expected a column") = Cell::CellFormulaNumber { f, s, v: *value };
+                        .expect("expected a column") = Cell::CellFormulaNumber { f, s, v };
                 }
                 CalcResult::String(value) => {
                     *self.workbook.worksheets[sheet as usize]
@@ -783,6 +799,7 @@ impl Model {
                     }
                 }
                 let node = &self.parsed_formulas[cell_reference.sheet as usize][f as usize].clone();
+                self.hyperlink_results.remove(&key);
                 let result = self.evaluate_node_in_context(node, cell_reference);
                 self.set_cell_value(cell_reference, &result);
                 // mark cell as evaluated
@@ -874,11 +891,14 @@ impl Model {
             .parse()
             .map_err(|_| format!("Invalid timezone: {}", workbook.settings.tz))?;
 
-        // FIXME: Add support for display languages
+        // The display language can be changed with `Model::set_language`
         let language = get_language("en").expect("").clone();
         let mut shared_strings = HashMap::new();
         for (index, s) in workbook.shared_strings.iter().enumerate() {
-            shared_strings.insert(s.to_string(), index);
+            // Strings with rich text are never reused for plain text
+            if !workbook.rich_text.contains_key(&(index as i32)) {
+                shared_strings.insert(s.to_string(), index);
+            }
         }
 
         let mut model = Model {
@@ -888,6 +908,8 @@ impl Model {
             parsed_defined_names: HashMap::new(),
             parser,
             cells,
+            conditional_format_results: HashMap::new(),
+            hyperlink_results: HashMap::new(),
             language,
             locale,
             tz,
@@ -1112,6 +1134,27 @@ impl Model {
         value: &str,
         source: &CellReferenceIndex,
         target: &CellReferenceIndex,
+    ) -> Result<String, String> {
+        self.copy_value(value, source, target, false)
+    }
+
+    /// Like [Model::extend_copied_value] for a value pasted with rows and columns swapped.
+    /// The references relative in both the row and the column are transposed.
+    pub(crate) fn transpose_copied_value(
+        &mut self,
+        value: &str,
+        source: &CellReferenceIndex,
+        target: &CellReferenceIndex,
+    ) -> Result<String, String> {
+        self.copy_value(value, source, target, true)
+    }
+
+    fn copy_value(
+        &mut self,
+        value: &str,
+        source: &CellReferenceIndex,
+        target: &CellReferenceIndex,
+        transpose: bool,
     ) -> Result<String, String> {
         let source_sheet_name = match self.workbook.worksheets.get(source.sheet as usize) {
             Some(ws) => ws.get_name(),
@@ -1131,13 +1174,16 @@ impl Model {
                 row: source.row,
                 column: source.column,
             };
-            let formula = &self.parser.parse(formula_str, &Some(cell_reference));
+            let mut formula = self.parser.parse(formula_str, &Some(cell_reference));
+            if transpose {
+                transpose_references_in_node(&mut formula);
+            }
             let cell_reference = CellReferenceRC {
                 sheet: target_sheet_name,
                 row: target.row,
                 column: target.column,
             };
-            return Ok(format!("={}", to_string(formula, &cell_reference)));
+            return Ok(format!("={}", to_string(&formula, &cell_reference)));
         };
         Ok(value.to_string())
     }
@@ -1445,14 +1491,12 @@ impl Model {
                     self.set_cell_style(sheet, row, column, &style)?
                 }
             } else {
-                // The list of currencies is '$', '€' and the local currency
-                let mut currencies = vec!["$", "€"];
-                let currency = &self.locale.currency.symbol;
-                if !currencies.iter().any(|e| e == currency) {
-                    currencies.push(currency);
-                }
+                let currencies = self.get_currencies();
                 //  We try to parse as number
-                if let Ok((v, number_format)) = parse_formatted_number(&value, &currencies) {
+                let date_1904 = self.workbook.settings.date_1904;
+                if let Ok((v, number_format)) =
+                    parse_formatted_number(&value, &currencies, date_1904)
+                {
                     if let Some(num_fmt) = number_format {
                         // Should not apply the format in the following cases:
                         // - we assign a date to already date-formatted cell
@@ -1470,16 +1514,25 @@ impl Model {
                     worksheet.set_cell_with_number(row, column, v, new_style_index)?;
                     return Ok(());
                 }
-                // We try to parse as boolean
-                if let Ok(v) = value.to_lowercase().parse::<bool>() {
+                // We try to parse as boolean, in the display language or in English
+                let upper = value.to_uppercase();
+                let boolean = if upper == self.language.booleans.r#true {
+                    Some(true)
+                } else if upper == self.language.booleans.r#false {
+                    Some(false)
+                } else {
+                    value.to_lowercase().parse::<bool>().ok()
+                };
+                if let Some(v) = boolean {
                     let worksheet = self.workbook.worksheet_mut(sheet)?;
                     worksheet.set_cell_with_boolean(row, column, v, new_style_index)?;
                     return Ok(());
                 }
                 // Check is it is error value
-                let upper = value.to_uppercase();
                 let worksheet = self.workbook.worksheet_mut(sheet)?;
-                match get_error_by_name(&upper, &self.language) {
+                match get_error_by_name(&upper, &self.language)
+                    .or_else(|| get_error_by_english_name(&upper))
+                {
                     Some(error) => {
                         worksheet.set_cell_with_error(row, column, error, new_style_index)?;
                     }
@@ -1534,7 +1587,17 @@ impl Model {
         Ok(formula_index)
     }
 
-    fn set_cell_with_string(
+    /// The currencies recognized in user input: '$', '€' and the local currency
+    pub(crate) fn get_currencies(&self) -> Vec<&str> {
+        let mut currencies = vec!["$", "€"];
+        let currency = self.locale.currency.symbol.as_str();
+        if !currencies.contains(&currency) {
+            currencies.push(currency);
+        }
+        currencies
+    }
+
+    pub(crate) fn set_cell_with_string(
         &mut self,
         sheet: u32,
         row: i32,
@@ -1657,9 +1720,10 @@ impl Model {
         match self.workbook.worksheet(sheet_index)?.cell(row, column) {
             Some(cell) => {
                 let format = self.get_style_for_cell(sheet_index, row, column)?.num_fmt;
+                let date_1904 = self.workbook.settings.date_1904;
                 let formatted_value =
                     cell.formatted_value(&self.workbook.shared_strings, &self.language, |value| {
-                        format_number(value, &format, &self.locale).text
+                        format_number_in_date_system(value, &format, &self.locale, date_1904).text
                     });
                 Ok(formatted_value)
             }
@@ -1698,6 +1762,70 @@ impl Model {
         }
     }
 
+    /// Returns the text for the formula or value of the cell like [Model::get_cell_content],
+    /// but with formulas in the display language and the locale of the workbook.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ironcalc_base::Model;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut model = Model::new_empty("model", "de", "UTC")?;
+    /// model.set_language("de")?;
+    /// let (sheet, row, column) = (0, 1, 1);
+    /// model.set_user_input(sheet, row, column, "=SUM(B1,2.5)".to_string())?;
+    /// assert_eq!(model.get_cell_content(sheet, row, column)?, "=SUM(B1,2.5)");
+    /// assert_eq!(model.get_localized_cell_content(sheet, row, column)?, "=SUMME(B1;2,5)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// See also:
+    /// * [Model::get_canonical_user_input()]
+    pub fn get_localized_cell_content(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<String, String> {
+        let content = self.get_cell_content(sheet, row, column)?;
+        match content.strip_prefix('=') {
+            Some(formula) => Ok(format!(
+                "={}",
+                to_localized_formula(formula, &self.locale, &self.language)
+            )),
+            None => Ok(content),
+        }
+    }
+
+    /// Translates user input with a formula in the display language and the locale of the workbook
+    /// into the form [Model::set_user_input] expects. Other values are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ironcalc_base::Model;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut model = Model::new_empty("model", "de", "UTC")?;
+    /// model.set_language("de")?;
+    /// assert_eq!(model.get_canonical_user_input("=WENN(A1;1,5;0)"), "=IF(A1,1.5,0)");
+    /// assert_eq!(model.get_canonical_user_input("Hallo"), "Hallo");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// See also:
+    /// * [Model::get_localized_cell_content()]
+    pub fn get_canonical_user_input(&self, value: &str) -> String {
+        match value.strip_prefix('=') {
+            Some(formula) => format!(
+                "={}",
+                to_canonical_formula(formula, &self.locale, &self.language)
+            ),
+            None => value.to_string(),
+        }
+    }
+
     /// Returns a list of all cells
     pub fn get_all_cells(&self) -> Vec<CellIndex> {
         let mut cells = Vec::new();
@@ -1720,10 +1848,36 @@ impl Model {
         cells
     }
 
+    /// Evaluates the cells of `sheet` with a top-down recursive algorithm.
+    /// Cells in other sheets are only evaluated if they are needed to compute a cell in `sheet`.
+    ///
+    /// See also:
+    /// * [Model::evaluate]
+    pub fn evaluate_sheet(&mut self, sheet: u32) -> Result<(), String> {
+        // Check that the sheet exists
+        self.workbook.worksheet(sheet)?;
+        // clear all computation artifacts
+        self.cells.clear();
+        self.hyperlink_results.retain(|&(s, _, _), _| s != sheet);
+
+        let cells = self.get_all_cells();
+
+        for cell in cells.iter().filter(|cell| cell.index == sheet) {
+            self.evaluate_cell(CellReferenceIndex {
+                sheet: cell.index,
+                row: cell.row,
+                column: cell.column,
+            });
+        }
+        self.evaluate_conditional_formats(sheet);
+        Ok(())
+    }
+
     /// Evaluates the model with a top-down recursive algorithm
     pub fn evaluate(&mut self) {
         // clear all computation artifacts
         self.cells.clear();
+        self.hyperlink_results.clear();
 
         let cells = self.get_all_cells();
 
@@ -1734,6 +1888,11 @@ impl Model {
                 column: cell.column,
             });
         }
+
+        self.conditional_format_results.clear();
+        for sheet in 0..self.workbook.worksheets.len() as u32 {
+            self.evaluate_conditional_formats(sheet);
+        }
     }
 
     /// Removes the content of the cell but leaves the style.
@@ -1903,6 +2062,50 @@ impl Model {
         Ok(())
     }
 
+    /// Sets the display language of the model.
+    /// Function names, booleans and errors are shown and entered in this language.
+    /// NB: Formulas are always stored in English and this is not preserved in the JSON.
+    pub fn set_language(&mut self, language_id: &str) -> Result<(), String> {
+        self.language = get_language(language_id)?.clone();
+        Ok(())
+    }
+
+    /// Sets the date system of the workbook.
+    /// In the 1904 date system serial number 0 is 1 January 1904, otherwise serial number 1 is 1 January 1900.
+    /// Existing values are not changed, the model should be re-evaluated.
+    pub fn set_date_1904(&mut self, date_1904: bool) {
+        self.workbook.settings.date_1904 = date_1904;
+    }
+
+    /// Returns true if the workbook uses the 1904 date system
+    pub fn is_date_1904(&self) -> bool {
+        self.workbook.settings.date_1904
+    }
+
+    /// Sets the "precision as displayed" calculation option.
+    /// If set, the results of formulas are rounded to the precision shown by the number format of their cell.
+    /// The model should be re-evaluated.
+    pub fn set_precision_as_displayed(&mut self, precision_as_displayed: bool) {
+        self.workbook.settings.precision_as_displayed = precision_as_displayed;
+    }
+
+    /// Returns true if the results of formulas are rounded to their displayed precision
+    pub fn is_precision_as_displayed(&self) -> bool {
+        self.workbook.settings.precision_as_displayed
+    }
+
+    /// Sets the calculation mode of the workbook.
+    /// Note that the model itself never evaluates on its own, the mode is a hint for the [UserModel](crate::UserModel)
+    /// and is saved with the workbook.
+    pub fn set_calc_mode(&mut self, calc_mode: CalcMode) {
+        self.workbook.settings.calc_mode = calc_mode;
+    }
+
+    /// Returns the calculation mode of the workbook
+    pub fn get_calc_mode(&self) -> CalcMode {
+        self.workbook.settings.calc_mode
+    }
+
     /// Returns the number of frozen rows in `sheet`
     pub fn get_frozen_rows_count(&self, sheet: u32) -> Result<i32, String> {
         if let Some(worksheet) = self.workbook.worksheets.get(sheet as usize) {
@@ -1982,6 +2185,39 @@ impl Model {
             .worksheet_mut(sheet)?
             .set_row_height(column, height)
     }
+
+    /// Returns true if the row is hidden
+    #[inline]
+    pub fn is_row_hidden(&self, sheet: u32, row: i32) -> Result<bool, String> {
+        self.workbook.worksheet(sheet)?.is_row_hidden(row)
+    }
+
+    /// Hides or unhides a row
+    #[inline]
+    pub fn set_row_hidden(&mut self, sheet: u32, row: i32, hidden: bool) -> Result<(), String> {
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_row_hidden(row, hidden)
+    }
+
+    /// Returns true if the column is hidden
+    #[inline]
+    pub fn is_column_hidden(&self, sheet: u32, column: i32) -> Result<bool, String> {
+        self.workbook.worksheet(sheet)?.is_column_hidden(column)
+    }
+
+    /// Hides or unhides a column
+    #[inline]
+    pub fn set_column_hidden(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        hidden: bool,
+    ) -> Result<(), String> {
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_column_hidden(column, hidden)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,329 @@
+use crate::{
+    model::Model,
+    types::{Border, BorderItem, BorderStyle, Fill, Font, FontScheme, Style, Theme, ThemeColor},
+};
+
+/// A color of a built-in style: a color of the theme with an optional tint or a fixed color
+#[derive(Clone, Copy)]
+enum BuiltinColor {
+    Theme(i32, Option<&'static str>),
+    Rgb(&'static str),
+}
+
+struct BuiltinStyle {
+    name: &'static str,
+    builtin_id: i32,
+    num_fmt: &'static str,
+    font_size: i32,
+    bold: bool,
+    scheme: FontScheme,
+    font_color: BuiltinColor,
+    fill_color: Option<BuiltinColor>,
+    /// The border on the four sides of the cell
+    outline: Option<(BorderStyle, BuiltinColor)>,
+    top: Option<(BorderStyle, BuiltinColor)>,
+    bottom: Option<(BorderStyle, BuiltinColor)>,
+}
+
+const DARK_1: BuiltinColor = BuiltinColor::Theme(1, None);
+const DARK_2: BuiltinColor = BuiltinColor::Theme(3, None);
+const ACCENT_1: BuiltinColor = BuiltinColor::Theme(4, None);
+
+const fn style(name: &'static str, builtin_id: i32) -> BuiltinStyle {
+    BuiltinStyle {
+        name,
+        builtin_id,
+        num_fmt: "general",
+        font_size: 11,
+        bold: false,
+        scheme: FontScheme::Minor,
+        font_color: DARK_1,
+        fill_color: None,
+        outline: None,
+        top: None,
+        bottom: None,
+    }
+}
+
+// The cell styles Excel offers in every workbook, see ECMA-376-1:2016 section 18.8.7
+static BUILTIN_STYLES: [BuiltinStyle; 17] = [
+    style("Normal", 0),
+    BuiltinStyle {
+        font_size: 18,
+        scheme: FontScheme::Major,
+        font_color: DARK_2,
+        ..style("Title", 15)
+    },
+    BuiltinStyle {
+        font_size: 15,
+        bold: true,
+        font_color: DARK_2,
+        bottom: Some((BorderStyle::Thick, ACCENT_1)),
+        ..style("Heading 1", 16)
+    },
+    BuiltinStyle {
+        font_size: 13,
+        bold: true,
+        font_color: DARK_2,
+        bottom: Some((
+            BorderStyle::Thick,
+            BuiltinColor::Theme(4, Some("0.499984740745262")),
+        )),
+        ..style("Heading 2", 17)
+    },
+    BuiltinStyle {
+        bold: true,
+        font_color: DARK_2,
+        bottom: Some((
+            BorderStyle::Medium,
+            BuiltinColor::Theme(4, Some("0.39997558519241921")),
+        )),
+        ..style("Heading 3", 18)
+    },
+    BuiltinStyle {
+        bold: true,
+        font_color: DARK_2,
+        ..style("Heading 4", 19)
+    },
+    BuiltinStyle {
+        font_color: BuiltinColor::Rgb("#006100"),
+        fill_color: Some(BuiltinColor::Rgb("#C6EFCE")),
+        ..style("Good", 26)
+    },
+    BuiltinStyle {
+        font_color: BuiltinColor::Rgb("#9C0006"),
+        fill_color: Some(BuiltinColor::Rgb("#FFC7CE")),
+        ..style("Bad", 27)
+    },
+    BuiltinStyle {
+        font_color: BuiltinColor::Rgb("#9C5700"),
+        fill_color: Some(BuiltinColor::Rgb("#FFEB9C")),
+        ..style("Neutral", 28)
+    },
+    BuiltinStyle {
+        font_color: BuiltinColor::Rgb("#3F3F76"),
+        fill_color: Some(BuiltinColor::Rgb("#FFCC99")),
+        outline: Some((BorderStyle::Thin, BuiltinColor::Rgb("#7F7F7F"))),
+        ..style("Input", 20)
+    },
+    BuiltinStyle {
+        bold: true,
+        font_color: BuiltinColor::Rgb("#3F3F3F"),
+        fill_color: Some(BuiltinColor::Rgb("#F2F2F2")),
+        outline: Some((BorderStyle::Thin, BuiltinColor::Rgb("#3F3F3F"))),
+        ..style("Output", 21)
+    },
+    BuiltinStyle {
+        bold: true,
+        font_color: BuiltinColor::Rgb("#FA7D00"),
+        fill_color: Some(BuiltinColor::Rgb("#F2F2F2")),
+        outline: Some((BorderStyle::Thin, BuiltinColor::Rgb("#7F7F7F"))),
+        ..style("Calculation", 22)
+    },
+    BuiltinStyle {
+        fill_color: Some(BuiltinColor::Rgb("#FFFFCC")),
+        outline: Some((BorderStyle::Thin, BuiltinColor::Rgb("#B2B2B2"))),
+        ..style("Note", 10)
+    },
+    BuiltinStyle {
+        font_color: BuiltinColor::Rgb("#7F7F7F"),
+        ..style("Explanatory Text", 53)
+    },
+    BuiltinStyle {
+        bold: true,
+        top: Some((BorderStyle::Thin, ACCENT_1)),
+        bottom: Some((BorderStyle::Double, ACCENT_1)),
+        ..style("Total", 25)
+    },
+    BuiltinStyle {
+        num_fmt: "#,##0.00",
+        ..style("Comma", 3)
+    },
+    BuiltinStyle {
+        num_fmt: "0%",
+        ..style("Percent", 5)
+    },
+];
+
+impl BuiltinStyle {
+    /// Returns the style with the colors of the `theme`
+    fn get_style(&self, theme: &Theme) -> Style {
+        // The color as displayed and the color of the theme it comes from
+        let get_color = |color: BuiltinColor| match color {
+            BuiltinColor::Theme(index, tint) => {
+                let theme_color = ThemeColor {
+                    theme: index,
+                    tint: tint.map(|t| t.to_string()),
+                };
+                (Some(theme.get_theme_color(&theme_color)), Some(theme_color))
+            }
+            BuiltinColor::Rgb(color) => (Some(color.to_string()), None),
+        };
+        let (color, color_theme) = get_color(self.font_color);
+        let font = Font {
+            sz: self.font_size,
+            b: self.bold,
+            color,
+            color_theme,
+            name: match self.scheme {
+                FontScheme::Major => theme.major_font.clone(),
+                _ => theme.minor_font.clone(),
+            },
+            scheme: self.scheme.clone(),
+            ..Default::default()
+        };
+        let fill = match self.fill_color {
+            Some(color) => {
+                let (fg_color, fg_color_theme) = get_color(color);
+                Fill {
+                    pattern_type: "solid".to_string(),
+                    fg_color,
+                    fg_color_theme,
+                    ..Default::default()
+                }
+            }
+            None => Fill::default(),
+        };
+        let get_border = |border: &Option<(BorderStyle, BuiltinColor)>| {
+            border.as_ref().map(|(style, color)| {
+                let (color, color_theme) = get_color(*color);
+                BorderItem {
+                    style: style.clone(),
+                    color,
+                    color_theme,
+                }
+            })
+        };
+        let border = Border {
+            left: get_border(&self.outline),
+            right: get_border(&self.outline),
+            top: get_border(self.top.as_ref().map_or(&self.outline, |_| &self.top)),
+            bottom: get_border(self.bottom.as_ref().map_or(&self.outline, |_| &self.bottom)),
+            ..Default::default()
+        };
+        Style {
+            alignment: None,
+            protection: None,
+            num_fmt: self.num_fmt.to_string(),
+            fill,
+            font,
+            border,
+            quote_prefix: false,
+        }
+    }
+}
+
+fn get_builtin_style(style_name: &str) -> Option<&'static BuiltinStyle> {
+    BUILTIN_STYLES
+        .iter()
+        .find(|builtin| builtin.name.to_lowercase() == style_name.to_lowercase())
+}
+
+impl Model {
+    /// Returns the names of the named styles of the workbook followed by the built-in styles
+    /// not yet in the workbook, like "Heading 1" or "Good"
+    pub fn get_named_styles(&self) -> Vec<String> {
+        let mut names = self.workbook.styles.get_named_styles();
+        for builtin in &BUILTIN_STYLES {
+            if !names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(builtin.name))
+            {
+                names.push(builtin.name.to_string());
+            }
+        }
+        names
+    }
+
+    /// Returns the style of the named style. Built-in styles not yet in the workbook use the
+    /// colors and fonts of the theme.
+    pub fn get_named_style(&self, style_name: &str) -> Result<Style, String> {
+        match self.workbook.styles.get_named_style(style_name) {
+            Ok(style) => Ok(style),
+            Err(message) => match get_builtin_style(style_name) {
+                Some(builtin) => Ok(builtin.get_style(&self.workbook.theme)),
+                None => Err(message),
+            },
+        }
+    }
+
+    /// Adds the built-in style `style_name` to the workbook if it is not there already.
+    /// Returns true if the style was added.
+    pub(crate) fn add_builtin_named_style(&mut self, style_name: &str) -> Result<bool, String> {
+        if self
+            .workbook
+            .styles
+            .get_named_style_xf_id(style_name)
+            .is_ok()
+        {
+            return Ok(false);
+        }
+        let builtin = match get_builtin_style(style_name) {
+            Some(builtin) => builtin,
+            None => return Err(format!("Style '{}' not found", style_name)),
+        };
+        let style = builtin.get_style(&self.workbook.theme);
+        self.workbook
+            .styles
+            .add_named_style(builtin.name, &style, Some(builtin.builtin_id))?;
+        Ok(true)
+    }
+
+    /// Adds the named style `style_name`. Fails if there is a style with that name, including
+    /// the built-in styles.
+    pub fn create_named_style(&mut self, style_name: &str, style: &Style) -> Result<(), String> {
+        let style_name = style_name.trim();
+        if style_name.is_empty() {
+            return Err("The name of the style cannot be empty".to_string());
+        }
+        if self
+            .get_named_styles()
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(style_name))
+        {
+            return Err("A style with that name already exists".to_string());
+        }
+        self.workbook.styles.create_named_style(style_name, style)
+    }
+
+    /// Changes the named style and the cells that use it, see [crate::types::Styles::set_named_style]
+    pub fn set_named_style(&mut self, style_name: &str, style: &Style) -> Result<(), String> {
+        self.add_builtin_named_style(style_name).ok();
+        self.workbook.styles.set_named_style(style_name, style)
+    }
+
+    /// Returns the name of the named style of the cell
+    pub fn get_cell_style_name(&self, sheet: u32, row: i32, column: i32) -> Result<String, String> {
+        let style_index = self.get_cell_style_index(sheet, row, column)?;
+        self.workbook.styles.get_style_name(style_index)
+    }
+
+    /// Sets the named style `style_name` in the cell, adding it if it is a built-in style
+    pub fn apply_named_style(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        style_name: &str,
+    ) -> Result<(), String> {
+        self.add_builtin_named_style(style_name).ok();
+        self.set_cell_style_by_name(sheet, row, column, style_name)
+    }
+
+    /// Sets the `style` in the cell as a variation of the named style `style_name`
+    pub(crate) fn set_cell_style_with_name(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        style: &Style,
+        style_name: &str,
+    ) -> Result<(), String> {
+        let styles = &mut self.workbook.styles;
+        let xf_id = styles.get_named_style_xf_id(style_name).unwrap_or(0);
+        let style_index = styles.get_style_index_or_create_with_xf_id(style, xf_id);
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_cell_style(row, column, style_index)
+    }
+}
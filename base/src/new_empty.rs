@@ -17,7 +17,8 @@ use crate::{
     locale::get_locale,
     model::{get_milliseconds_since_epoch, Model, ParsedDefinedName},
     types::{
-        Metadata, SheetState, Workbook, WorkbookSettings, WorkbookView, Worksheet, WorksheetView,
+        CalcMode, Metadata, SheetState, Workbook, WorkbookSettings, WorkbookView, Worksheet,
+        WorksheetView,
     },
     utils::ParsedReference,
 };
@@ -38,7 +39,7 @@ fn is_valid_sheet_name(name: &str) -> bool {
 
 impl Model {
     /// Creates a new worksheet. Note that it does not check if the name or the sheet_id exists
-    fn new_empty_worksheet(name: &str, sheet_id: u32, view_ids: &[&u32]) -> Worksheet {
+    pub(crate) fn new_empty_worksheet(name: &str, sheet_id: u32, view_ids: &[&u32]) -> Worksheet {
         let mut views = HashMap::new();
         for id in view_ids {
             views.insert(
@@ -56,6 +57,10 @@ impl Model {
             cols: vec![],
             rows: vec![],
             comments: vec![],
+            hyperlinks: vec![],
+            conditional_formats: vec![],
+            data_validations: vec![],
+            auto_filter: None,
             dimension: "A1".to_string(),
             merge_cells: vec![],
             name: name.to_string(),
@@ -68,6 +73,8 @@ impl Model {
             frozen_rows: 0,
             show_grid_lines: true,
             views,
+            protection: None,
+            pivot_tables: vec![],
         }
     }
 
@@ -146,6 +153,7 @@ impl Model {
     pub(crate) fn reset_parsed_structures(&mut self) {
         self.parser
             .set_worksheets(self.workbook.get_worksheet_names());
+        self.update_parser_tables();
         self.parsed_formulas = vec![];
         self.parse_formulas();
         self.parsed_defined_names = HashMap::new();
@@ -279,12 +287,47 @@ impl Model {
         self.parser.set_lexer_mode(LexerMode::A1);
         // Update the name of the worksheet
         let worksheets = &mut self.workbook.worksheets;
+        let old_name = worksheets[sheet_index as usize].get_name();
         worksheets[sheet_index as usize].set_name(new_name);
+        for table in self.workbook.tables.values_mut() {
+            if table.sheet_name == old_name {
+                table.sheet_name = new_name.to_string();
+            }
+        }
+        self.rename_pivot_sources_sheet(&old_name, new_name);
         self.reset_parsed_structures();
         Ok(())
     }
 
-    /// Deletes a sheet by index. Fails if:
+    /// Moves the sheet at index `sheet` to `new_index`.
+    /// Formulas refer to other sheets by name so they do not change.
+    /// Fails if any of the indices is out of bounds.
+    pub fn move_sheet(&mut self, sheet: u32, new_index: u32) -> Result<(), String> {
+        let sheet_count = self.workbook.worksheets.len() as u32;
+        if sheet >= sheet_count || new_index >= sheet_count {
+            return Err("Sheet index out of bounds".to_string());
+        }
+        if sheet == new_index {
+            return Ok(());
+        }
+        let worksheet = self.workbook.worksheets.remove(sheet as usize);
+        self.workbook
+            .worksheets
+            .insert(new_index as usize, worksheet);
+        for view in self.workbook.views.values_mut() {
+            if view.sheet == sheet {
+                view.sheet = new_index;
+            } else if sheet < view.sheet && view.sheet <= new_index {
+                view.sheet -= 1;
+            } else if new_index <= view.sheet && view.sheet < sheet {
+                view.sheet += 1;
+            }
+        }
+        self.reset_parsed_structures();
+        Ok(())
+    }
+
+    /// Deletes a sheet by index with its tables and sheet scoped defined names. Fails if:
     ///   * The sheet does not exists
     ///   * It is the last sheet
     pub fn delete_sheet(&mut self, sheet_index: u32) -> Result<(), String> {
@@ -296,7 +339,19 @@ impl Model {
         if sheet_index >= sheet_count {
             return Err("Sheet index too large".to_string());
         }
-        self.workbook.worksheets.remove(sheet_index as usize);
+        let worksheet = self.workbook.worksheets.remove(sheet_index as usize);
+        self.workbook
+            .tables
+            .retain(|_, table| table.sheet_name != worksheet.name);
+        self.workbook
+            .defined_names
+            .retain(|defined_name| defined_name.sheet_id != Some(worksheet.sheet_id));
+        // The views keep the same sheet selected
+        for view in self.workbook.views.values_mut() {
+            if view.sheet > sheet_index || view.sheet == sheet_count - 1 {
+                view.sheet -= 1;
+            }
+        }
         self.reset_parsed_structures();
         Ok(())
     }
@@ -366,13 +421,18 @@ impl Model {
         // String versions of the locale are added here to simplify the serialize/deserialize logic
         let workbook = Workbook {
             shared_strings: vec![],
+            rich_text: HashMap::new(),
             defined_names: vec![],
             worksheets: vec![Model::new_empty_worksheet("Sheet1", 1, &[&0])],
             styles: Default::default(),
+            theme: Default::default(),
             name: name.to_string(),
             settings: WorkbookSettings {
                 tz: timezone.to_string(),
                 locale: locale_id.to_string(),
+                date_1904: false,
+                precision_as_displayed: false,
+                calc_mode: CalcMode::Automatic,
             },
             metadata: Metadata {
                 application: APPLICATION.to_string(),
@@ -384,6 +444,7 @@ impl Model {
             },
             tables: HashMap::new(),
             views,
+            protection: None,
         };
         let parsed_formulas = Vec::new();
         let worksheets = &workbook.worksheets;
@@ -401,6 +462,8 @@ impl Model {
             parsed_defined_names: HashMap::new(),
             parser,
             cells,
+            conditional_format_results: HashMap::new(),
+            hyperlink_results: HashMap::new(),
             locale,
             language,
             tz,
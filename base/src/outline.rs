@@ -0,0 +1,188 @@
+use crate::{
+    constants::{LAST_COLUMN, LAST_ROW},
+    model::Model,
+};
+
+/// Maximum outline level of rows and columns, as in Excel
+pub(crate) const MAX_OUTLINE_LEVEL: u8 = 7;
+
+/// Returns the contiguous range of indices around `index` with an outline level of at least `level`.
+/// `last` is the last valid index.
+fn find_group(
+    level_of: impl Fn(i32) -> u8,
+    index: i32,
+    level: u8,
+    last: i32,
+) -> Option<(i32, i32)> {
+    if level == 0 || level_of(index) < level {
+        return None;
+    }
+    let mut first = index;
+    while first > 1 && level_of(first - 1) >= level {
+        first -= 1;
+    }
+    let mut end = index;
+    while end < last && level_of(end + 1) >= level {
+        end += 1;
+    }
+    Some((first, end))
+}
+
+fn check_outline_level(level: u8) -> Result<(), String> {
+    if level > MAX_OUTLINE_LEVEL {
+        return Err(format!(
+            "Outline level must be between 0 and {MAX_OUTLINE_LEVEL}"
+        ));
+    }
+    Ok(())
+}
+
+impl Model {
+    /// Returns the outline level of the row, 0 if it is not grouped
+    pub fn get_row_outline_level(&self, sheet: u32, row: i32) -> Result<u8, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .get_row(row)
+            .map_or(0, |r| r.outline_level))
+    }
+
+    /// Sets the outline level of the row
+    pub fn set_row_outline_level(&mut self, sheet: u32, row: i32, level: u8) -> Result<(), String> {
+        check_outline_level(level)?;
+        self.workbook
+            .worksheet_mut(sheet)?
+            .row_mut(row)?
+            .outline_level = level;
+        Ok(())
+    }
+
+    /// Returns the outline level of the column, 0 if it is not grouped
+    pub fn get_column_outline_level(&self, sheet: u32, column: i32) -> Result<u8, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .get_column(column)
+            .map_or(0, |c| c.outline_level))
+    }
+
+    /// Sets the outline level of the column
+    pub fn set_column_outline_level(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        level: u8,
+    ) -> Result<(), String> {
+        check_outline_level(level)?;
+        self.workbook
+            .worksheet_mut(sheet)?
+            .column_mut(column)?
+            .outline_level = level;
+        Ok(())
+    }
+
+    /// Returns true if the group of rows that ends right before `row` is collapsed
+    pub fn is_row_collapsed(&self, sheet: u32, row: i32) -> Result<bool, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .get_row(row)
+            .is_some_and(|r| r.collapsed))
+    }
+
+    /// Marks the group of rows that ends right before `row` as collapsed (or not)
+    pub fn set_row_collapsed(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        collapsed: bool,
+    ) -> Result<(), String> {
+        self.workbook.worksheet_mut(sheet)?.row_mut(row)?.collapsed = collapsed;
+        Ok(())
+    }
+
+    /// Returns true if the group of columns that ends right before `column` is collapsed
+    pub fn is_column_collapsed(&self, sheet: u32, column: i32) -> Result<bool, String> {
+        Ok(self
+            .workbook
+            .worksheet(sheet)?
+            .get_column(column)
+            .is_some_and(|c| c.collapsed))
+    }
+
+    /// Marks the group of columns that ends right before `column` as collapsed (or not)
+    pub fn set_column_collapsed(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        collapsed: bool,
+    ) -> Result<(), String> {
+        self.workbook
+            .worksheet_mut(sheet)?
+            .column_mut(column)?
+            .collapsed = collapsed;
+        Ok(())
+    }
+
+    /// Returns the first and last rows of the group of outline `level` that contains `row`, if any
+    pub fn get_row_group(
+        &self,
+        sheet: u32,
+        row: i32,
+        level: u8,
+    ) -> Result<Option<(i32, i32)>, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let level_of = |r: i32| worksheet.get_row(r).map_or(0, |r| r.outline_level);
+        Ok(find_group(level_of, row, level, LAST_ROW))
+    }
+
+    /// Returns the first and last columns of the group of outline `level` that contains `column`, if any
+    pub fn get_column_group(
+        &self,
+        sheet: u32,
+        column: i32,
+        level: u8,
+    ) -> Result<Option<(i32, i32)>, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let level_of = |c: i32| worksheet.get_column(c).map_or(0, |c| c.outline_level);
+        Ok(find_group(level_of, column, level, LAST_COLUMN))
+    }
+
+    /// Returns true if the row belongs to a collapsed group with an outline level above `level`.
+    /// Those rows stay hidden when the group of outline `level` is expanded.
+    pub(crate) fn is_row_in_collapsed_group(
+        &self,
+        sheet: u32,
+        row: i32,
+        level: u8,
+    ) -> Result<bool, String> {
+        let row_level = self.get_row_outline_level(sheet, row)?;
+        for inner_level in (level + 1)..=row_level {
+            if let Some((_, last)) = self.get_row_group(sheet, row, inner_level)? {
+                if last < LAST_ROW && self.is_row_collapsed(sheet, last + 1)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns true if the column belongs to a collapsed group with an outline level above `level`.
+    /// Those columns stay hidden when the group of outline `level` is expanded.
+    pub(crate) fn is_column_in_collapsed_group(
+        &self,
+        sheet: u32,
+        column: i32,
+        level: u8,
+    ) -> Result<bool, String> {
+        let column_level = self.get_column_outline_level(sheet, column)?;
+        for inner_level in (level + 1)..=column_level {
+            if let Some((_, last)) = self.get_column_group(sheet, column, inner_level)? {
+                if last < LAST_COLUMN && self.is_column_collapsed(sheet, last + 1)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+}
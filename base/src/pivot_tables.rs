@@ -0,0 +1,854 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    cell::CellValue,
+    constants::{LAST_COLUMN, LAST_ROW},
+    expressions::{
+        parser::parse_range,
+        token::{get_error_by_english_name, get_error_by_name, Error},
+        types::CellReferenceIndex,
+        utils::{number_to_column, parse_reference_a1},
+    },
+    merge_cells::{parse_range_reference, range_to_string},
+    model::Model,
+    tables::get_unique_column_name,
+    types::{CellType, PivotFunction, PivotSource, PivotTable},
+};
+
+/// A value of the source of a pivot table
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PivotValue {
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    Error(Error),
+    Blank,
+}
+
+impl PivotValue {
+    // Numbers go first, then texts, booleans, errors and blanks
+    fn rank(&self) -> u8 {
+        match self {
+            PivotValue::Number(_) => 0,
+            PivotValue::Text(_) => 1,
+            PivotValue::Boolean(_) => 2,
+            PivotValue::Error(_) => 3,
+            PivotValue::Blank => 4,
+        }
+    }
+}
+
+/// A value of a row or column field. Values are grouped by the text they display.
+#[derive(Debug, Clone)]
+struct PivotItem {
+    value: PivotValue,
+    text: String,
+}
+
+impl PivotItem {
+    fn key(&self) -> String {
+        self.text.to_lowercase()
+    }
+}
+
+/// The keys of the row items and the column items of a group of data rows, no column items for
+/// the grand totals
+type GroupKey = (Vec<String>, Option<Vec<String>>);
+
+/// The keys of the `items`, two lists of items with the same keys are in the same group
+fn group_key(items: &[PivotItem]) -> Vec<String> {
+    items.iter().map(|item| item.key()).collect()
+}
+
+fn compare_items(a: &PivotItem, b: &PivotItem) -> Ordering {
+    a.value.rank().cmp(&b.value.rank()).then_with(|| {
+        match (&a.value, &b.value) {
+            (PivotValue::Number(x), PivotValue::Number(y)) => {
+                x.partial_cmp(y).unwrap_or(Ordering::Equal)
+            }
+            (PivotValue::Boolean(x), PivotValue::Boolean(y)) => x.cmp(y),
+            _ => Ordering::Equal,
+        }
+        .then_with(|| a.key().cmp(&b.key()))
+    })
+}
+
+fn compare_keys(a: &[PivotItem], b: &[PivotItem]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| compare_items(x, y))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// A cell of a rendered pivot table
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PivotCell {
+    Empty,
+    Number(f64),
+    Text(String),
+    Error(Error),
+}
+
+impl PivotCell {
+    /// Returns the value as it is typed in the cell
+    pub(crate) fn to_user_input(&self) -> String {
+        match self {
+            PivotCell::Empty => "".to_string(),
+            PivotCell::Number(value) => format!("{value}"),
+            // The quote keeps texts like "2024" or "TRUE" as texts
+            PivotCell::Text(text) => format!("'{text}"),
+            PivotCell::Error(error) => error.to_string(),
+        }
+    }
+}
+
+/// Returns the `function` of the `values`. Blank values are ignored.
+fn aggregate(function: PivotFunction, values: &[&PivotValue]) -> PivotCell {
+    let mut numbers = Vec::new();
+    let mut count = 0;
+    for value in values {
+        match value {
+            PivotValue::Number(number) => numbers.push(*number),
+            PivotValue::Error(error) => {
+                if !matches!(function, PivotFunction::Count | PivotFunction::CountNums) {
+                    return PivotCell::Error(error.clone());
+                }
+            }
+            PivotValue::Blank => continue,
+            PivotValue::Text(_) | PivotValue::Boolean(_) => {}
+        }
+        count += 1;
+    }
+    let n = numbers.len() as f64;
+    let sum: f64 = numbers.iter().sum();
+    // Sum of the squares of the deviations
+    let squares = |numbers: &[f64]| {
+        let mean = sum / n;
+        numbers.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+    };
+    let result = match function {
+        PivotFunction::Count => count as f64,
+        PivotFunction::CountNums => n,
+        PivotFunction::Sum => sum,
+        PivotFunction::Product if numbers.is_empty() => 0.0,
+        PivotFunction::Product => numbers.iter().product(),
+        PivotFunction::Max => numbers.iter().cloned().reduce(f64::max).unwrap_or(0.0),
+        PivotFunction::Min => numbers.iter().cloned().reduce(f64::min).unwrap_or(0.0),
+        PivotFunction::Average if numbers.is_empty() => return PivotCell::Error(Error::DIV),
+        PivotFunction::Average => sum / n,
+        PivotFunction::Var | PivotFunction::StdDev if numbers.len() < 2 => {
+            return PivotCell::Error(Error::DIV)
+        }
+        PivotFunction::Varp | PivotFunction::StdDevp if numbers.is_empty() => {
+            return PivotCell::Error(Error::DIV)
+        }
+        PivotFunction::Var => squares(&numbers) / (n - 1.0),
+        PivotFunction::StdDev => (squares(&numbers) / (n - 1.0)).sqrt(),
+        PivotFunction::Varp => squares(&numbers) / n,
+        PivotFunction::StdDevp => (squares(&numbers) / n).sqrt(),
+    };
+    PivotCell::Number(result)
+}
+
+/// Returns the default caption of the values of a `field`, like "Sum of Sales"
+pub(crate) fn get_value_field_name(function: PivotFunction, field: &str) -> String {
+    let function = match function {
+        PivotFunction::Average => "Average",
+        PivotFunction::Count => "Count",
+        PivotFunction::CountNums => "Count",
+        PivotFunction::Max => "Max",
+        PivotFunction::Min => "Min",
+        PivotFunction::Product => "Product",
+        PivotFunction::StdDev => "StdDev",
+        PivotFunction::StdDevp => "StdDevp",
+        PivotFunction::Sum => "Sum",
+        PivotFunction::Var => "Var",
+        PivotFunction::Varp => "Varp",
+    };
+    format!("{function} of {field}")
+}
+
+/// The fields and data rows of the source of a pivot table
+pub(crate) struct PivotData {
+    pub(crate) sheet: u32,
+    /// (row1, column1, row2, column2) of the data without the names of the fields
+    pub(crate) range: (i32, i32, i32, i32),
+    pub(crate) fields: Vec<String>,
+    rows: Vec<Vec<PivotItem>>,
+}
+
+impl PivotData {
+    fn get_field_index(&self, name: &str) -> Result<usize, String> {
+        let lower = name.to_lowercase();
+        self.fields
+            .iter()
+            .position(|field| field.to_lowercase() == lower)
+            .ok_or_else(|| format!("Field not found: '{name}'"))
+    }
+}
+
+/// A column of values of a rendered pivot table
+struct ValueColumn {
+    /// None for the grand totals
+    column_key: Option<Vec<PivotItem>>,
+    /// Index of the value field, None if there are no value fields
+    value: Option<usize>,
+}
+
+impl Model {
+    fn get_pivot_item(&self, sheet: u32, row: i32, column: i32) -> Result<PivotItem, String> {
+        let text = self.get_formatted_cell_value(sheet, row, column)?;
+        let value = if self.get_cell_type(sheet, row, column)? == CellType::ErrorValue {
+            let error = get_error_by_name(&text, &self.language)
+                .or_else(|| get_error_by_english_name(&text))
+                .unwrap_or(Error::ERROR);
+            PivotValue::Error(error)
+        } else {
+            match self.get_cell_value_by_index(sheet, row, column)? {
+                CellValue::Number(value) => PivotValue::Number(value),
+                CellValue::String(value) if value.is_empty() => PivotValue::Blank,
+                CellValue::String(value) => PivotValue::Text(value),
+                CellValue::Boolean(value) => PivotValue::Boolean(value),
+                CellValue::None => PivotValue::Blank,
+            }
+        };
+        let text = if value == PivotValue::Blank {
+            "(blank)".to_string()
+        } else {
+            text
+        };
+        Ok(PivotItem { value, text })
+    }
+
+    /// Reads the fields and the data rows of the `source` of a pivot table
+    pub(crate) fn get_pivot_data(&self, source: &PivotSource) -> Result<PivotData, String> {
+        let (sheet, fields, (row1, column1, row2, column2)) = match source {
+            PivotSource::Range {
+                sheet_name,
+                reference,
+            } => {
+                let sheet = self
+                    .get_sheet_index_by_name(sheet_name)
+                    .ok_or_else(|| format!("Sheet not found: '{sheet_name}'"))?;
+                let (row1, column1, row2, column2) = parse_range_reference(reference)?;
+                if row1 == row2 {
+                    return Err("The source of a pivot table needs a row of data".to_string());
+                }
+                let mut fields = Vec::new();
+                for column in column1..=column2 {
+                    let name = self.get_formatted_cell_value(sheet, row1, column)?;
+                    if name.trim().is_empty() {
+                        return Err(
+                            "The names of the fields of a pivot table cannot be empty".to_string()
+                        );
+                    }
+                    fields.push(get_unique_column_name(&name, &fields));
+                }
+                // Whole columns like A:D end at the last row with data
+                let max_row = self.workbook.worksheet(sheet)?.dimension().max_row;
+                (
+                    sheet,
+                    fields,
+                    (row1 + 1, column1, row2.min(max_row), column2),
+                )
+            }
+            PivotSource::Table { name } => {
+                let table = self.get_table(name)?;
+                let area = self.get_table_data_area(name)?;
+                let fields = table.columns.iter().map(|c| c.name.clone()).collect();
+                (
+                    area.sheet,
+                    fields,
+                    (
+                        area.row,
+                        area.column,
+                        area.row + area.height - 1,
+                        area.column + area.width - 1,
+                    ),
+                )
+            }
+        };
+        let mut rows = Vec::new();
+        for row in row1..=row2 {
+            let mut items = Vec::new();
+            for column in column1..=column2 {
+                items.push(self.get_pivot_item(sheet, row, column)?);
+            }
+            rows.push(items);
+        }
+        Ok(PivotData {
+            sheet,
+            range: (row1, column1, row2, column2),
+            fields,
+            rows,
+        })
+    }
+
+    /// Returns the names of the fields of the `source` of a pivot table, each with its items as
+    /// they are displayed and in the order of the pivot table
+    pub fn get_pivot_fields(
+        &self,
+        source: &PivotSource,
+    ) -> Result<Vec<(String, Vec<String>)>, String> {
+        let data = self.get_pivot_data(source)?;
+        let mut fields = Vec::new();
+        for (index, name) in data.fields.iter().enumerate() {
+            let mut seen = HashSet::new();
+            let mut items: Vec<&PivotItem> = Vec::new();
+            for row in &data.rows {
+                let item = &row[index];
+                if seen.insert(item.key()) {
+                    items.push(item);
+                }
+            }
+            items.sort_by(|a, b| compare_items(a, b));
+            let items = items.into_iter().map(|item| item.text.clone()).collect();
+            fields.push((name.clone(), items));
+        }
+        Ok(fields)
+    }
+
+    /// Fails if the fields of the `pivot_table` are not in its source
+    pub(crate) fn check_pivot_table(&self, pivot_table: &PivotTable) -> Result<(), String> {
+        let data = self.get_pivot_data(&pivot_table.source)?;
+        parse_reference_a1(&pivot_table.location)
+            .ok_or_else(|| format!("Invalid location: '{}'", pivot_table.location))?;
+        let mut axis_fields = Vec::new();
+        for field in pivot_table
+            .row_fields
+            .iter()
+            .chain(&pivot_table.column_fields)
+        {
+            let index = data.get_field_index(field)?;
+            if axis_fields.contains(&index) {
+                return Err(format!("Field used twice: '{field}'"));
+            }
+            axis_fields.push(index);
+        }
+        for value_field in &pivot_table.value_fields {
+            data.get_field_index(&value_field.field)?;
+        }
+        for filter in &pivot_table.filters {
+            data.get_field_index(&filter.field)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the indices of the data rows that are not filtered out
+    fn get_pivot_rows(
+        &self,
+        pivot_table: &PivotTable,
+        data: &PivotData,
+    ) -> Result<Vec<usize>, String> {
+        let mut filters = Vec::new();
+        for filter in &pivot_table.filters {
+            let hidden: Vec<String> = filter
+                .hidden_items
+                .iter()
+                .map(|item| item.to_lowercase())
+                .collect();
+            filters.push((data.get_field_index(&filter.field)?, hidden));
+        }
+        Ok((0..data.rows.len())
+            .filter(|&row| {
+                filters
+                    .iter()
+                    .all(|(field, hidden)| !hidden.contains(&data.rows[row][*field].key()))
+            })
+            .collect())
+    }
+
+    /// Returns the cells of the rendered `pivot_table`, row by row
+    pub(crate) fn get_pivot_table_cells(
+        &self,
+        pivot_table: &PivotTable,
+    ) -> Result<Vec<Vec<PivotCell>>, String> {
+        if pivot_table.row_fields.is_empty()
+            && pivot_table.column_fields.is_empty()
+            && pivot_table.value_fields.is_empty()
+        {
+            return Ok(vec![]);
+        }
+        let data = self.get_pivot_data(&pivot_table.source)?;
+        let rows = self.get_pivot_rows(pivot_table, &data)?;
+        let get_fields = |fields: &[String]| -> Result<Vec<usize>, String> {
+            fields.iter().map(|f| data.get_field_index(f)).collect()
+        };
+        let row_fields = get_fields(&pivot_table.row_fields)?;
+        let column_fields = get_fields(&pivot_table.column_fields)?;
+        let value_fields: Vec<(usize, &str, PivotFunction)> = pivot_table
+            .value_fields
+            .iter()
+            .map(|v| Ok((data.get_field_index(&v.field)?, v.name.as_str(), v.function)))
+            .collect::<Result<_, String>>()?;
+
+        let get_key = |row: usize, fields: &[usize]| -> Vec<PivotItem> {
+            fields.iter().map(|f| data.rows[row][*f].clone()).collect()
+        };
+        let same_key = |a: &[PivotItem], b: &[PivotItem]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.key() == y.key())
+        };
+        let get_keys = |fields: &[usize]| {
+            let mut seen = HashSet::new();
+            let mut keys: Vec<Vec<PivotItem>> = Vec::new();
+            for &row in &rows {
+                let key = get_key(row, fields);
+                if seen.insert(group_key(&key)) {
+                    keys.push(key);
+                }
+            }
+            keys.sort_by(|a, b| compare_keys(a, b));
+            keys
+        };
+        let row_keys = get_keys(&row_fields);
+        let column_keys = get_keys(&column_fields);
+
+        // The data rows of each group of rows (a prefix of the row items) and column (the column
+        // items, None for the grand totals), in one pass
+        let mut groups: HashMap<GroupKey, Vec<usize>> = HashMap::new();
+        for &row in &rows {
+            let row_key = group_key(&get_key(row, &row_fields));
+            let column_key = group_key(&get_key(row, &column_fields));
+            for length in 0..=row_key.len() {
+                let prefix = row_key[..length].to_vec();
+                for column in [Some(column_key.clone()), None] {
+                    groups
+                        .entry((prefix.clone(), column))
+                        .or_default()
+                        .push(row);
+                }
+            }
+        }
+        // The aggregates of each group, one for each value field
+        let aggregates: HashMap<GroupKey, Vec<PivotCell>> = groups
+            .into_iter()
+            .map(|(key, group)| {
+                let cells = value_fields
+                    .iter()
+                    .map(|&(field, _, function)| {
+                        let values: Vec<&PivotValue> = group
+                            .iter()
+                            .map(|&row| &data.rows[row][field].value)
+                            .collect();
+                        aggregate(function, &values)
+                    })
+                    .collect();
+                (key, cells)
+            })
+            .collect();
+
+        // The value of a cell for the rows starting with `row_prefix`
+        let get_value = |row_prefix: &[PivotItem], value_column: &ValueColumn| -> PivotCell {
+            let value = match value_column.value {
+                Some(value) => value,
+                None => return PivotCell::Empty,
+            };
+            let key = (
+                group_key(row_prefix),
+                value_column.column_key.as_deref().map(group_key),
+            );
+            match aggregates.get(&key) {
+                Some(cells) => cells[value].clone(),
+                None => PivotCell::Empty,
+            }
+        };
+
+        let value_count = value_fields.len();
+        let value_indices: Vec<Option<usize>> = if value_count == 0 {
+            vec![None]
+        } else {
+            (0..value_count).map(Some).collect()
+        };
+        let mut value_columns = Vec::new();
+        for key in &column_keys {
+            for value in &value_indices {
+                value_columns.push(ValueColumn {
+                    column_key: Some(key.clone()),
+                    value: *value,
+                });
+            }
+        }
+        // Without column fields the values are already the totals of the rows
+        if pivot_table.row_grand_totals && !column_fields.is_empty() && value_count > 0 {
+            for value in &value_indices {
+                value_columns.push(ValueColumn {
+                    column_key: None,
+                    value: *value,
+                });
+            }
+        }
+
+        let left = row_fields.len().max(1);
+        let text = |s: &str| PivotCell::Text(s.to_string());
+        let value_name = |value: Option<usize>| match value {
+            Some(value) => text(value_fields[value].1),
+            None => PivotCell::Empty,
+        };
+        let mut cells: Vec<Vec<PivotCell>> = Vec::new();
+
+        // The header has a row with the names of the column fields and a row for each level of
+        // the columns, the last one with the names of the row fields
+        let levels = column_fields.len() + usize::from(value_count > 1);
+        let mut field_names: Vec<PivotCell> =
+            pivot_table.row_fields.iter().map(|f| text(f)).collect();
+        field_names.resize(left, PivotCell::Empty);
+        if levels == 0 {
+            let mut header = field_names;
+            header.extend(value_columns.iter().map(|v| value_name(v.value)));
+            cells.push(header);
+        } else {
+            let mut header = vec![PivotCell::Empty; left];
+            if value_count == 1 {
+                header[0] = text(value_fields[0].1);
+            }
+            header.extend(pivot_table.column_fields.iter().map(|f| text(f)));
+            if value_count > 1 {
+                header.push(text("Values"));
+            }
+            cells.push(header);
+            for level in 0..levels {
+                let mut header = if level == levels - 1 {
+                    field_names.clone()
+                } else {
+                    vec![PivotCell::Empty; left]
+                };
+                for value_column in &value_columns {
+                    header.push(match &value_column.column_key {
+                        Some(key) if level < key.len() => text(&key[level].text),
+                        Some(_) => value_name(value_column.value),
+                        None if level > 0 => PivotCell::Empty,
+                        None if value_count == 1 => text("Grand Total"),
+                        None => match value_column.value {
+                            Some(value) => text(&format!("Total {}", value_fields[value].1)),
+                            None => PivotCell::Empty,
+                        },
+                    });
+                }
+                cells.push(header);
+            }
+        }
+
+        let values_row = |label: Vec<PivotCell>, prefix: &[PivotItem]| {
+            let mut row = label;
+            row.extend(value_columns.iter().map(|v| get_value(prefix, v)));
+            row
+        };
+        if row_fields.is_empty() {
+            let label = if value_count == 1 {
+                text(value_fields[0].1)
+            } else {
+                text("Total")
+            };
+            cells.push(values_row(vec![label], &[]));
+        } else {
+            for (index, key) in row_keys.iter().enumerate() {
+                let label = key.iter().map(|item| text(&item.text)).collect();
+                cells.push(values_row(label, key));
+                if !pivot_table.subtotals {
+                    continue;
+                }
+                // Close the groups that end in this row, the innermost first
+                let next = row_keys.get(index + 1);
+                for level in (0..row_fields.len() - 1).rev() {
+                    let prefix = &key[..=level];
+                    if next.is_some_and(|next| same_key(&next[..=level], prefix)) {
+                        continue;
+                    }
+                    let mut label = vec![PivotCell::Empty; left];
+                    label[level] = text(&format!("{} Total", key[level].text));
+                    cells.push(values_row(label, prefix));
+                }
+            }
+            if pivot_table.column_grand_totals && value_count > 0 {
+                let mut label = vec![PivotCell::Empty; left];
+                label[0] = text("Grand Total");
+                cells.push(values_row(label, &[]));
+            }
+        }
+
+        let width = cells.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in &mut cells {
+            row.resize(width, PivotCell::Empty);
+        }
+        Ok(cells)
+    }
+
+    /// Returns the range (row1, column1, row2, column2) that the `cells` of a pivot table at
+    /// `location` fill
+    pub(crate) fn get_pivot_table_range(
+        location: &str,
+        cells: &[Vec<PivotCell>],
+    ) -> Result<Option<(i32, i32, i32, i32)>, String> {
+        let top_left = parse_reference_a1(location)
+            .ok_or_else(|| format!("Invalid location: '{location}'"))?;
+        let height = cells.len() as i32;
+        let width = cells.first().map(|row| row.len()).unwrap_or(0) as i32;
+        if height == 0 || width == 0 {
+            return Ok(None);
+        }
+        let row2 = top_left.row + height - 1;
+        let column2 = top_left.column + width - 1;
+        if row2 > LAST_ROW || column2 > LAST_COLUMN {
+            return Err("The pivot table does not fit in the sheet".to_string());
+        }
+        Ok(Some((top_left.row, top_left.column, row2, column2)))
+    }
+
+    /// Returns the pivot tables of the `sheet`
+    pub fn get_pivot_tables(&self, sheet: u32) -> Result<Vec<PivotTable>, String> {
+        Ok(self.workbook.worksheet(sheet)?.pivot_tables.clone())
+    }
+
+    /// Returns the pivot table called `name` in the `sheet`
+    pub fn get_pivot_table(&self, sheet: u32, name: &str) -> Result<PivotTable, String> {
+        self.workbook
+            .worksheet(sheet)?
+            .pivot_tables
+            .iter()
+            .find(|pivot_table| pivot_table.name == name)
+            .cloned()
+            .ok_or_else(|| format!("Pivot table not found: '{name}'"))
+    }
+
+    /// Adds, replaces or removes (if `pivot_table` is None) the pivot table called `name`.
+    /// The cells are not changed.
+    pub fn set_pivot_table(
+        &mut self,
+        sheet: u32,
+        name: &str,
+        pivot_table: Option<PivotTable>,
+    ) -> Result<(), String> {
+        let pivot_tables = &mut self.workbook.worksheet_mut(sheet)?.pivot_tables;
+        let index = pivot_tables.iter().position(|p| p.name == name);
+        match (index, pivot_table) {
+            (Some(index), Some(pivot_table)) => pivot_tables[index] = pivot_table,
+            (None, Some(pivot_table)) => pivot_tables.push(pivot_table),
+            (Some(index), None) => {
+                pivot_tables.remove(index);
+            }
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the pivot tables of every sheet
+    pub(crate) fn get_all_pivot_tables(&self) -> Vec<Vec<PivotTable>> {
+        self.workbook
+            .worksheets
+            .iter()
+            .map(|worksheet| worksheet.pivot_tables.clone())
+            .collect()
+    }
+
+    /// Restores the pivot tables of every sheet
+    pub(crate) fn set_all_pivot_tables(&mut self, pivot_tables: &[Vec<PivotTable>]) {
+        for (worksheet, sheet_pivot_tables) in self.workbook.worksheets.iter_mut().zip(pivot_tables)
+        {
+            worksheet.pivot_tables.clone_from(sheet_pivot_tables);
+        }
+    }
+
+    /// Updates the pivot tables after the cells of the `sheet` move. `displace_range` returns the
+    /// new range (row1, column1, row2, column2) of a range of the sheet, None if it was deleted.
+    /// Sources in the sheet that are deleted become "#REF!" and the pivot tables whose top left
+    /// cell is deleted are removed.
+    /// The pivot tables that change lose the parts of the xlsx file they were imported from.
+    pub(crate) fn displace_pivot_tables(
+        &mut self,
+        sheet: u32,
+        displace_range: impl Fn((i32, i32, i32, i32)) -> Option<(i32, i32, i32, i32)>,
+    ) -> Result<(), String> {
+        let sheet_name = self.workbook.worksheet(sheet)?.get_name();
+        for (index, worksheet) in self.workbook.worksheets.iter_mut().enumerate() {
+            let mut pivot_tables = Vec::new();
+            for mut pivot_table in worksheet.pivot_tables.drain(..) {
+                let old_value = pivot_table.clone();
+                if let PivotSource::Range {
+                    sheet_name: source_sheet,
+                    reference,
+                } = &mut pivot_table.source
+                {
+                    if *source_sheet == sheet_name {
+                        if let Ok(range) = parse_range_reference(reference) {
+                            *reference = match displace_range(range) {
+                                Some(range) => range_to_string(range)?,
+                                None => "#REF!".to_string(),
+                            };
+                        }
+                    }
+                }
+                if index as u32 == sheet {
+                    let location = parse_reference_a1(&pivot_table.location)
+                        .ok_or_else(|| format!("Invalid location: '{}'", pivot_table.location))?;
+                    let Some((row, column, _, _)) = displace_range((
+                        location.row,
+                        location.column,
+                        location.row,
+                        location.column,
+                    )) else {
+                        continue;
+                    };
+                    let column_name =
+                        number_to_column(column).ok_or_else(|| "Invalid column".to_string())?;
+                    pivot_table.location = format!("{column_name}{row}");
+                    if let Some(reference) = &pivot_table.reference {
+                        pivot_table.reference = match parse_range_reference(reference) {
+                            Ok(range) => displace_range(range).map(range_to_string).transpose()?,
+                            Err(_) => None,
+                        };
+                    }
+                }
+                if pivot_table != old_value {
+                    pivot_table.xlsx_parts = None;
+                }
+                pivot_tables.push(pivot_table);
+            }
+            worksheet.pivot_tables = pivot_tables;
+        }
+        Ok(())
+    }
+
+    /// Changes the sheet of the sources in the sheet `old_name` to `new_name`
+    pub(crate) fn rename_pivot_sources_sheet(&mut self, old_name: &str, new_name: &str) {
+        self.update_pivot_sources(|source| match source {
+            PivotSource::Range { sheet_name, .. } if sheet_name == old_name => {
+                *sheet_name = new_name.to_string();
+                true
+            }
+            _ => false,
+        });
+    }
+
+    /// Changes the sources that are the table `name` to the table `new_name`
+    pub(crate) fn rename_pivot_sources_table(&mut self, name: &str, new_name: &str) {
+        self.update_pivot_sources(|source| match source {
+            PivotSource::Table { name: table } if table.eq_ignore_ascii_case(name) => {
+                *table = new_name.to_string();
+                true
+            }
+            _ => false,
+        });
+    }
+
+    /// Updates the sources of all the pivot tables with `update`, that returns true if it changed
+    /// the source. Those pivot tables lose the parts of the xlsx file they were imported from.
+    fn update_pivot_sources(&mut self, update: impl Fn(&mut PivotSource) -> bool) {
+        for worksheet in &mut self.workbook.worksheets {
+            for pivot_table in &mut worksheet.pivot_tables {
+                if update(&mut pivot_table.source) {
+                    pivot_table.xlsx_parts = None;
+                }
+            }
+        }
+    }
+
+    /// Returns the first name like "PivotTable1", "PivotTable2", ... not used in the `sheet`
+    pub(crate) fn get_new_pivot_table_name(&self, sheet: u32) -> Result<String, String> {
+        let pivot_tables = &self.workbook.worksheet(sheet)?.pivot_tables;
+        let mut index = 1;
+        while pivot_tables
+            .iter()
+            .any(|p| p.name.eq_ignore_ascii_case(&format!("PivotTable{index}")))
+        {
+            index += 1;
+        }
+        Ok(format!("PivotTable{index}"))
+    }
+
+    /// Returns the pivot table rendered in the cell, if any
+    pub(crate) fn get_pivot_table_at(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Option<PivotTable> {
+        self.workbook
+            .worksheet(sheet)
+            .ok()?
+            .pivot_tables
+            .iter()
+            .find(|pivot_table| {
+                pivot_table.reference.as_ref().is_some_and(|reference| {
+                    parse_range(reference).is_ok_and(|(column1, row1, column2, row2)| {
+                        (row1..=row2).contains(&row) && (column1..=column2).contains(&column)
+                    })
+                })
+            })
+            .cloned()
+    }
+
+    /// Evaluates the cells of the source of the `pivot_table`, they might have formulas that have
+    /// not been evaluated yet
+    pub(crate) fn evaluate_pivot_source(&mut self, pivot_table: &PivotTable) -> Result<(), String> {
+        let data = self.get_pivot_data(&pivot_table.source)?;
+        let (row1, column1, row2, column2) = data.range;
+        for row in row1 - 1..=row2 {
+            for column in column1..=column2 {
+                self.evaluate_cell(CellReferenceIndex {
+                    sheet: data.sheet,
+                    row,
+                    column,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the value of GETPIVOTDATA for the `data_field` of the `pivot_table` in the rows
+    /// with the `items` in their fields. None if the data is not in the pivot table.
+    pub(crate) fn get_pivot_table_value(
+        &self,
+        pivot_table: &PivotTable,
+        data_field: &str,
+        items: &[(String, PivotValue)],
+    ) -> Result<Option<PivotCell>, String> {
+        let data = self.get_pivot_data(&pivot_table.source)?;
+        let lower = data_field.to_lowercase();
+        let value_field = match pivot_table
+            .value_fields
+            .iter()
+            .find(|v| v.name.to_lowercase() == lower || v.field.to_lowercase() == lower)
+        {
+            Some(value_field) => value_field,
+            None => return Ok(None),
+        };
+        let field = data.get_field_index(&value_field.field)?;
+        let mut conditions = Vec::new();
+        for (name, value) in items {
+            let lower = name.to_lowercase();
+            let visible = pivot_table
+                .row_fields
+                .iter()
+                .chain(&pivot_table.column_fields)
+                .any(|f| f.to_lowercase() == lower);
+            if !visible {
+                return Ok(None);
+            }
+            conditions.push((data.get_field_index(name)?, value));
+        }
+        let matches = |item: &PivotItem, value: &PivotValue| match (&item.value, value) {
+            (PivotValue::Number(x), PivotValue::Number(y)) => x == y,
+            (PivotValue::Boolean(x), PivotValue::Boolean(y)) => x == y,
+            (_, PivotValue::Text(text)) => item.key() == text.to_lowercase(),
+            _ => false,
+        };
+        let values: Vec<&PivotValue> = self
+            .get_pivot_rows(pivot_table, &data)?
+            .into_iter()
+            .filter(|&row| {
+                conditions
+                    .iter()
+                    .all(|(index, value)| matches(&data.rows[row][*index], value))
+            })
+            .map(|row| &data.rows[row][field].value)
+            .collect();
+        if values.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(aggregate(value_field.function, &values)))
+    }
+}
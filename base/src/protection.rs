@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::{
+    functions::mathematical::random,
+    model::Model,
+    types::{PasswordHash, SheetProtection, WorkbookProtection},
+};
+
+/// Number of iterations of the hash of new passwords, the same as Excel
+const SPIN_COUNT: u32 = 100_000;
+
+/// Length in bytes of the salt of new passwords
+const SALT_LENGTH: usize = 16;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+fn base64_decode(text: &str) -> Result<Vec<u8>, String> {
+    let error = || format!("Invalid base64 text: '{text}'");
+    let data = text.trim_end_matches('=');
+    let padding = text.len() - data.len();
+    if text.len() % 4 != 0 || padding > 2 {
+        return Err(error());
+    }
+    let mut bytes = Vec::new();
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in data.bytes() {
+        let value = BASE64_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(error)? as u32;
+        n = (n << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+            n &= (1 << bits) - 1;
+        }
+    }
+    // The bits left over from the last character must be zero
+    if n != 0 {
+        return Err(error());
+    }
+    Ok(bytes)
+}
+
+/// The legacy 16 bit hash of the password, as an hexadecimal string.
+/// Only the first 15 characters of the password are used.
+/// See ECMA-376-4:2016 section 14.7.1
+fn legacy_password_hash(password: &str) -> String {
+    let mut hash: u16 = 0;
+    let characters: Vec<u16> = password.chars().take(15).map(|c| c as u16).collect();
+    for (index, &character) in characters.iter().enumerate() {
+        let value = (character as u32) << (index + 1);
+        let rotated_bits = value >> 15;
+        hash ^= ((value & 0x7fff) | rotated_bits) as u16;
+    }
+    hash ^= characters.len() as u16;
+    hash ^= 0xCE4B;
+    format!("{hash:04X}")
+}
+
+/// Iterated hash of the salt and the password in UTF-16LE.
+/// See ECMA-376-1:2016 section 18.3.1.85
+fn iterated_hash<D: Digest>(password: &str, salt: &[u8], spin_count: u32) -> Vec<u8> {
+    let mut hasher = D::new();
+    hasher.update(salt);
+    for unit in password.encode_utf16() {
+        hasher.update(unit.to_le_bytes());
+    }
+    let mut hash = hasher.finalize().to_vec();
+    for iterator in 0..spin_count {
+        let mut hasher = D::new();
+        hasher.update(&hash);
+        hasher.update(iterator.to_le_bytes());
+        hash = hasher.finalize().to_vec();
+    }
+    hash
+}
+
+/// Returns the hash of a new password, using SHA-512 with a random salt as Excel does
+fn new_password_hash(password: &str) -> PasswordHash {
+    let salt: Vec<u8> = (0..SALT_LENGTH).map(|_| (random() * 256.0) as u8).collect();
+    let hash = iterated_hash::<Sha512>(password, &salt, SPIN_COUNT);
+    PasswordHash::Salted {
+        algorithm_name: "SHA-512".to_string(),
+        hash_value: base64_encode(&hash),
+        salt_value: base64_encode(&salt),
+        spin_count: SPIN_COUNT,
+    }
+}
+
+/// Returns true if the `password` matches the `password_hash`
+fn check_password(password_hash: &PasswordHash, password: &str) -> Result<bool, String> {
+    match password_hash {
+        PasswordHash::Legacy(hash) => {
+            let expected = u16::from_str_radix(hash, 16)
+                .map_err(|_| format!("Invalid password hash: '{hash}'"))?;
+            let hash = legacy_password_hash(password);
+            Ok(u16::from_str_radix(&hash, 16) == Ok(expected))
+        }
+        PasswordHash::Salted {
+            algorithm_name,
+            hash_value,
+            salt_value,
+            spin_count,
+        } => {
+            let salt = base64_decode(salt_value)
+                .map_err(|_| format!("Invalid password salt: '{salt_value}'"))?;
+            let expected = base64_decode(hash_value)
+                .map_err(|_| format!("Invalid password hash: '{hash_value}'"))?;
+            let hash = match algorithm_name.as_str() {
+                "SHA-512" => iterated_hash::<Sha512>(password, &salt, *spin_count),
+                "SHA-256" => iterated_hash::<Sha256>(password, &salt, *spin_count),
+                _ => return Err(format!("Unsupported hash algorithm: '{algorithm_name}'")),
+            };
+            Ok(hash == expected)
+        }
+    }
+}
+
+/// Fails unless the `password` unlocks the protection with `password_hash`
+fn unlock(password_hash: &Option<PasswordHash>, password: Option<&str>) -> Result<(), String> {
+    if let Some(password_hash) = password_hash {
+        if !check_password(password_hash, password.unwrap_or(""))? {
+            return Err("The password is not correct".to_string());
+        }
+    }
+    Ok(())
+}
+
+impl Model {
+    /// Returns the protection of the sheet, None if it is not protected
+    pub fn get_sheet_protection(&self, sheet: u32) -> Result<Option<SheetProtection>, String> {
+        Ok(self.workbook.worksheet(sheet)?.protection.clone())
+    }
+
+    /// Returns true if the sheet is protected
+    pub fn is_sheet_protected(&self, sheet: u32) -> Result<bool, String> {
+        Ok(self.workbook.worksheet(sheet)?.protection.is_some())
+    }
+
+    /// Protects the sheet with the options in `protection`.
+    /// The `password` (if any and not empty) will be needed to unprotect the sheet.
+    pub fn protect_sheet(
+        &mut self,
+        sheet: u32,
+        protection: SheetProtection,
+        password: Option<&str>,
+    ) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        if worksheet.protection.is_some() {
+            return Err("The sheet is already protected".to_string());
+        }
+        worksheet.protection = Some(SheetProtection {
+            password_hash: password.filter(|p| !p.is_empty()).map(new_password_hash),
+            ..protection
+        });
+        Ok(())
+    }
+
+    /// Removes the protection of the sheet. Fails if the sheet has a password and it doesn't match.
+    pub fn unprotect_sheet(&mut self, sheet: u32, password: Option<&str>) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        match &worksheet.protection {
+            Some(protection) => unlock(&protection.password_hash, password)?,
+            None => return Err("The sheet is not protected".to_string()),
+        }
+        worksheet.protection = None;
+        Ok(())
+    }
+
+    /// Returns the protection of the workbook, None if it is not protected
+    pub fn get_workbook_protection(&self) -> Option<WorkbookProtection> {
+        self.workbook.protection.clone()
+    }
+
+    /// Protects the structure and/or the windows of the workbook.
+    /// The `password` (if any and not empty) will be needed to unprotect the workbook.
+    pub fn protect_workbook(
+        &mut self,
+        lock_structure: bool,
+        lock_windows: bool,
+        password: Option<&str>,
+    ) -> Result<(), String> {
+        if self.workbook.protection.is_some() {
+            return Err("The workbook is already protected".to_string());
+        }
+        self.workbook.protection = Some(WorkbookProtection {
+            password_hash: password.filter(|p| !p.is_empty()).map(new_password_hash),
+            lock_structure,
+            lock_windows,
+        });
+        Ok(())
+    }
+
+    /// Removes the protection of the workbook. Fails if it has a password and it doesn't match.
+    pub fn unprotect_workbook(&mut self, password: Option<&str>) -> Result<(), String> {
+        match &self.workbook.protection {
+            Some(protection) => unlock(&protection.password_hash, password)?,
+            None => return Err("The workbook is not protected".to_string()),
+        }
+        self.workbook.protection = None;
+        Ok(())
+    }
+
+    fn is_style_locked(&self, style_index: i32) -> bool {
+        self.workbook
+            .styles
+            .cell_xfs
+            .get(style_index as usize)
+            .and_then(|xf| xf.protection.as_ref())
+            .is_none_or(|protection| protection.locked)
+    }
+
+    /// Returns true if the cell is locked. Locked cells cannot be changed when the sheet is protected.
+    pub fn is_cell_locked(&self, sheet: u32, row: i32, column: i32) -> Result<bool, String> {
+        let style_index = self.get_cell_style_index(sheet, row, column)?;
+        Ok(self.is_style_locked(style_index))
+    }
+
+    /// Returns true if the formula of the cell is hidden when the sheet is protected
+    pub fn is_cell_hidden(&self, sheet: u32, row: i32, column: i32) -> Result<bool, String> {
+        let style_index = self.get_cell_style_index(sheet, row, column)?;
+        Ok(self
+            .workbook
+            .styles
+            .cell_xfs
+            .get(style_index as usize)
+            .and_then(|xf| xf.protection.as_ref())
+            .is_some_and(|protection| protection.hidden))
+    }
+
+    /// Returns true if the cell can be changed: the sheet is not protected or the cell is not locked
+    pub fn is_cell_editable(&self, sheet: u32, row: i32, column: i32) -> Result<bool, String> {
+        Ok(!self.is_sheet_protected(sheet)? || !self.is_cell_locked(sheet, row, column)?)
+    }
+
+    /// Fails if the sheet is protected and any cell in the range (row1, column1, row2, column2) is locked
+    pub(crate) fn check_range_editable(
+        &self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+    ) -> Result<(), String> {
+        if !self.is_sheet_protected(sheet)? {
+            return Ok(());
+        }
+        let (row1, column1, row2, column2) = range;
+        let error = Err("Cannot change locked cells: the sheet is protected".to_string());
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let width = (column2 - column1 + 1) as usize;
+        let height = (row2 - row1 + 1) as usize;
+
+        // Empty cells take the style of their row if it is formatted
+        let formatted_rows: HashMap<i32, i32> = worksheet
+            .rows
+            .iter()
+            .filter(|r| r.custom_format && (row1..=row2).contains(&r.r))
+            .map(|r| (r.r, r.s))
+            .collect();
+        for (row, style) in &formatted_rows {
+            let cell_count = worksheet.sheet_data.get(row).map_or(0, |data_row| {
+                data_row
+                    .keys()
+                    .filter(|c| (column1..=column2).contains(*c))
+                    .count()
+            });
+            if cell_count < width && self.is_style_locked(*style) {
+                return error;
+            }
+        }
+        // Rows (not formatted) with a cell, for each column
+        let mut rows_with_cell = vec![0; width];
+        for (row, data_row) in &worksheet.sheet_data {
+            if !(row1..=row2).contains(row) {
+                continue;
+            }
+            for (column, cell) in data_row {
+                if !(column1..=column2).contains(column) {
+                    continue;
+                }
+                if self.is_style_locked(cell.get_style()) {
+                    return error;
+                }
+                if !formatted_rows.contains_key(row) {
+                    rows_with_cell[(column - column1) as usize] += 1;
+                }
+            }
+        }
+        // The rest of the empty cells take the style of their column
+        for column in column1..=column2 {
+            let empty_cells =
+                height - formatted_rows.len() - rows_with_cell[(column - column1) as usize];
+            if empty_cells > 0 {
+                let style = worksheet
+                    .cols
+                    .iter()
+                    .find(|c| c.min <= column && column <= c.max)
+                    .and_then(|c| c.style)
+                    .unwrap_or(0);
+                if self.is_style_locked(style) {
+                    return error;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if the sheet is protected and its options do not allow the action
+    pub(crate) fn check_sheet_allows(
+        &self,
+        sheet: u32,
+        allowed: fn(&SheetProtection) -> bool,
+        action: &str,
+    ) -> Result<(), String> {
+        match &self.workbook.worksheet(sheet)?.protection {
+            Some(protection) if !allowed(protection) => {
+                Err(format!("Cannot {action}: the sheet is protected"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails if the sheet is protected
+    pub(crate) fn check_sheet_not_protected(&self, sheet: u32, action: &str) -> Result<(), String> {
+        self.check_sheet_allows(sheet, |_| false, action)
+    }
+
+    /// Fails if the structure of the workbook is locked
+    pub(crate) fn check_workbook_structure(&self) -> Result<(), String> {
+        match &self.workbook.protection {
+            Some(protection) if protection.lock_structure => Err(
+                "Cannot change the sheets: the structure of the workbook is protected".to_string(),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
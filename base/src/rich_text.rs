@@ -0,0 +1,49 @@
+use crate::{
+    model::Model,
+    types::{Cell, TextRun},
+};
+
+impl Model {
+    /// Returns the runs of the cell if its value is a string with rich text
+    pub fn get_cell_rich_text(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Vec<TextRun>>, String> {
+        match self.workbook.worksheet(sheet)?.cell(row, column) {
+            Some(Cell::SharedString { si, .. }) => Ok(self.workbook.rich_text.get(si).cloned()),
+            _ => Ok(None),
+        }
+    }
+
+    /// Sets the value of the cell to a string with rich text, each run with its own font.
+    /// Formulas and string functions see the concatenated text of the runs.
+    pub fn set_cell_rich_text(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        runs: Vec<TextRun>,
+    ) -> Result<(), String> {
+        let runs: Vec<TextRun> = runs
+            .into_iter()
+            .filter(|run| !run.text.is_empty())
+            .collect();
+        if runs.is_empty() {
+            return Err("Rich text needs at least one run".to_string());
+        }
+        let text: String = runs.iter().map(|run| run.text.as_str()).collect();
+        let style = self.get_cell_style_index(sheet, row, column)?;
+        if runs.iter().all(|run| run.font.is_none()) {
+            return self.set_cell_with_string(sheet, row, column, &text, style);
+        }
+        // Strings with rich text are not shared with plain text cells
+        let string_index = self.workbook.shared_strings.len() as i32;
+        self.workbook.shared_strings.push(text);
+        self.workbook.rich_text.insert(string_index, runs);
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_cell_with_string(row, column, string_index, style)
+    }
+}
@@ -0,0 +1,127 @@
+use std::cmp::Ordering;
+
+use crate::{
+    cell::CellValue,
+    model::Model,
+    types::{CellType, SortBy, SortKey},
+};
+
+/// The value of a cell as it is compared when sorting.
+/// The order of the variants is the sort order.
+#[derive(PartialEq, PartialOrd)]
+enum SortValue {
+    Number(f64),
+    Text(String),
+    Boolean(bool),
+    // Errors are not sorted among themselves
+    Error,
+}
+
+/// The cell of a row in a sort key column
+enum SortCell {
+    Empty,
+    /// The `usize` is compared first: the position in the custom list or 0 if the cell has
+    /// the sorted color
+    Value(usize, SortValue),
+}
+
+/// Compares two cells. Empty cells always go last.
+fn compare_sort_cells(a: &SortCell, b: &SortCell, descending: bool) -> Ordering {
+    match (a, b) {
+        (SortCell::Empty, SortCell::Empty) => Ordering::Equal,
+        (SortCell::Empty, _) => Ordering::Greater,
+        (_, SortCell::Empty) => Ordering::Less,
+        (SortCell::Value(index_a, value_a), SortCell::Value(index_b, value_b)) => {
+            let ordering = index_a
+                .cmp(index_b)
+                .then(value_a.partial_cmp(value_b).unwrap_or(Ordering::Equal));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+    }
+}
+
+impl Model {
+    fn get_sort_value(&self, sheet: u32, row: i32, column: i32) -> Result<SortValue, String> {
+        if self.get_cell_type(sheet, row, column)? == CellType::ErrorValue {
+            return Ok(SortValue::Error);
+        }
+        Ok(match self.get_cell_value_by_index(sheet, row, column)? {
+            CellValue::Number(value) => SortValue::Number(value),
+            CellValue::String(value) => SortValue::Text(value.to_lowercase()),
+            CellValue::Boolean(value) => SortValue::Boolean(value),
+            CellValue::None => SortValue::Text("".to_string()),
+        })
+    }
+
+    fn get_sort_cell(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        sort_by: &SortBy,
+    ) -> Result<SortCell, String> {
+        if let SortBy::CellColor { color } = sort_by {
+            let fill_color = self.get_style_for_cell(sheet, row, column)?.fill.fg_color;
+            let index = if fill_color.is_some_and(|c| c.eq_ignore_ascii_case(color)) {
+                0
+            } else {
+                1
+            };
+            return Ok(SortCell::Value(index, SortValue::Error));
+        }
+        if self.get_cell_content(sheet, row, column)?.is_empty() {
+            return Ok(SortCell::Empty);
+        }
+        let index = match sort_by {
+            SortBy::CustomList { list } => {
+                let text = self
+                    .get_formatted_cell_value(sheet, row, column)?
+                    .to_lowercase();
+                list.iter()
+                    .position(|item| item.to_lowercase() == text)
+                    .unwrap_or(list.len())
+            }
+            _ => 0,
+        };
+        Ok(SortCell::Value(
+            index,
+            self.get_sort_value(sheet, row, column)?,
+        ))
+    }
+
+    /// Returns the rows from `row1` to `row2` in the order given by the sort `keys`.
+    /// Rows that are equal for all the keys keep their relative order.
+    pub(crate) fn get_sorted_rows(
+        &self,
+        sheet: u32,
+        (row1, column1, row2, column2): (i32, i32, i32, i32),
+        keys: &[SortKey],
+    ) -> Result<Vec<i32>, String> {
+        if keys.is_empty() {
+            return Err("At least one sort key is needed".to_string());
+        }
+        let mut rows = Vec::new();
+        for row in row1..=row2 {
+            let mut cells = Vec::with_capacity(keys.len());
+            for key in keys {
+                if key.column < column1 || key.column > column2 {
+                    return Err(format!("Invalid sort column: '{}'", key.column));
+                }
+                cells.push(self.get_sort_cell(sheet, row, key.column, &key.sort_by)?);
+            }
+            rows.push((row, cells));
+        }
+        rows.sort_by(|(_, cells_a), (_, cells_b)| {
+            keys.iter()
+                .zip(cells_a.iter().zip(cells_b))
+                .map(|(key, (a, b))| compare_sort_cells(a, b, key.descending))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+        Ok(rows.into_iter().map(|(row, _)| row).collect())
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     model::Model,
     number_format::{get_default_num_fmt_id, get_new_num_fmt_index, get_num_fmt},
-    types::{Border, CellStyles, CellXfs, Fill, Font, NumFmt, Style, Styles},
+    types::{Border, CellStyleXfs, CellStyles, CellXfs, Fill, Font, NumFmt, Style, Styles},
 };
 
 // TODO: Move Styles and all related types from crate::types here
@@ -43,7 +43,8 @@ impl Styles {
         None
     }
 
-    pub fn create_new_style(&mut self, style: &Style) -> i32 {
+    /// Returns the ids of the number format, font, fill and border of the `style`, adding them if needed
+    fn get_style_ids_or_create(&mut self, style: &Style) -> (i32, i32, i32, i32) {
         let font = &style.font;
         let font_id = if let Some(index) = self.get_font_index(font) {
             index
@@ -76,8 +77,13 @@ impl Styles {
                 num_fmt_id,
             });
         }
+        (num_fmt_id, font_id, fill_id, border_id)
+    }
+
+    fn create_new_style_with_xf_id(&mut self, style: &Style, xf_id: i32) -> i32 {
+        let (num_fmt_id, font_id, fill_id, border_id) = self.get_style_ids_or_create(style);
         self.cell_xfs.push(CellXfs {
-            xf_id: 0,
+            xf_id,
             num_fmt_id,
             font_id,
             fill_id,
@@ -90,10 +96,15 @@ impl Styles {
             apply_fill: false,
             quote_prefix: style.quote_prefix,
             alignment: style.alignment.clone(),
+            protection: style.protection.clone(),
         });
         self.cell_xfs.len() as i32 - 1
     }
 
+    pub fn create_new_style(&mut self, style: &Style) -> i32 {
+        self.create_new_style_with_xf_id(style, 0)
+    }
+
     pub fn get_style_index(&self, style: &Style) -> Option<i32> {
         for (index, cell_xf) in self.cell_xfs.iter().enumerate() {
             let border_id = cell_xf.border_id as usize;
@@ -104,6 +115,7 @@ impl Styles {
             if style
                 == &(Style {
                     alignment: cell_xf.alignment.clone(),
+                    protection: cell_xf.protection.clone(),
                     num_fmt: get_num_fmt(num_fmt_id, &self.num_fmts),
                     fill: self.fills[fill_id].clone(),
                     font: self.fonts[font_id].clone(),
@@ -126,28 +138,68 @@ impl Styles {
         }
     }
 
-    /// Adds a named cell style from an existing index
+    /// Returns the index of a style equal to `style` based on the named style with index `xf_id`,
+    /// creating it if needed
+    pub(crate) fn get_style_index_or_create_with_xf_id(
+        &mut self,
+        style: &Style,
+        xf_id: i32,
+    ) -> i32 {
+        let index = (0..self.cell_xfs.len() as i32).find(|&index| {
+            self.cell_xfs[index as usize].xf_id == xf_id
+                && self.get_style(index).as_ref() == Ok(style)
+        });
+        match index {
+            Some(index) => index,
+            None => self.create_new_style_with_xf_id(style, xf_id),
+        }
+    }
+
+    /// Adds a named cell style from an existing index.
+    /// The cells with that style index use the new named style.
     /// Fails if the named style already exists or if there is not a style with that index
     pub fn add_named_cell_style(
         &mut self,
         style_name: &str,
         style_index: i32,
     ) -> Result<(), String> {
-        if self.get_style_index_by_name(style_name).is_ok() {
+        if self.get_named_style_xf_id(style_name).is_ok() {
             return Err("A style with that name already exists".to_string());
         }
-        if self.cell_xfs.len() < style_index as usize {
+        if style_index < 0 || self.cell_xfs.len() <= style_index as usize {
             return Err("There is no style with that index".to_string());
         }
-        let cell_style = CellStyles {
+        let cell_xf = &self.cell_xfs[style_index as usize];
+        self.cell_style_xfs.push(CellStyleXfs {
+            num_fmt_id: cell_xf.num_fmt_id,
+            font_id: cell_xf.font_id,
+            fill_id: cell_xf.fill_id,
+            border_id: cell_xf.border_id,
+            alignment: cell_xf.alignment.clone(),
+            protection: cell_xf.protection.clone(),
+            ..Default::default()
+        });
+        let xf_id = self.cell_style_xfs.len() as i32 - 1;
+        self.cell_xfs[style_index as usize].xf_id = xf_id;
+        self.cell_styles.push(CellStyles {
             name: style_name.to_string(),
-            xf_id: style_index,
-            builtin_id: 0,
-        };
-        self.cell_styles.push(cell_style);
+            xf_id,
+            builtin_id: None,
+        });
         Ok(())
     }
 
+    /// Returns the index in `cell_style_xfs` of the named style or fails.
+    /// Names of styles are case insensitive.
+    pub(crate) fn get_named_style_xf_id(&self, style_name: &str) -> Result<i32, String> {
+        for cell_style in &self.cell_styles {
+            if cell_style.name.to_lowercase() == style_name.to_lowercase() {
+                return Ok(cell_style.xf_id);
+            }
+        }
+        Err(format!("Style '{}' not found", style_name))
+    }
+
     // Returns the index of the style or fails.
     // NB: this method is case sensitive
     pub fn get_style_index_by_name(&self, style_name: &str) -> Result<i32, String> {
@@ -159,9 +211,138 @@ impl Styles {
         Err(format!("Style '{}' not found", style_name))
     }
 
+    /// Returns the index of a style with the named style, creating it if needed
+    pub fn get_style_index_by_name_or_create(&mut self, style_name: &str) -> Result<i32, String> {
+        let xf_id = self.get_named_style_xf_id(style_name)?;
+        let style = self.get_named_style(style_name)?;
+        Ok(self.get_style_index_or_create_with_xf_id(&style, xf_id))
+    }
+
+    /// Returns the names of the named styles of the workbook
+    pub fn get_named_styles(&self) -> Vec<String> {
+        self.cell_styles
+            .iter()
+            .map(|cell_style| cell_style.name.clone())
+            .collect()
+    }
+
+    /// Returns the style of the named style
+    pub fn get_named_style(&self, style_name: &str) -> Result<Style, String> {
+        let xf_id = self.get_named_style_xf_id(style_name)?;
+        let cell_style_xf = self
+            .cell_style_xfs
+            .get(xf_id as usize)
+            .ok_or("Invalid index provided".to_string())?;
+        Ok(Style {
+            alignment: cell_style_xf.alignment.clone(),
+            protection: cell_style_xf.protection.clone(),
+            num_fmt: get_num_fmt(cell_style_xf.num_fmt_id, &self.num_fmts),
+            fill: self.fills[cell_style_xf.fill_id as usize].clone(),
+            font: self.fonts[cell_style_xf.font_id as usize].clone(),
+            border: self.borders[cell_style_xf.border_id as usize].clone(),
+            quote_prefix: false,
+        })
+    }
+
+    /// Returns the name of the named style the style with `index` is based on
+    pub fn get_style_name(&self, index: i32) -> Result<String, String> {
+        let cell_xf = self
+            .cell_xfs
+            .get(index as usize)
+            .ok_or("Invalid index provided".to_string())?;
+        let cell_style = self
+            .cell_styles
+            .iter()
+            .find(|cell_style| cell_style.xf_id == cell_xf.xf_id)
+            .or_else(|| self.cell_styles.iter().find(|c| c.builtin_id == Some(0)));
+        Ok(match cell_style {
+            Some(cell_style) => cell_style.name.clone(),
+            None => "Normal".to_string(),
+        })
+    }
+
     pub fn create_named_style(&mut self, style_name: &str, style: &Style) -> Result<(), String> {
-        let style_index = self.create_new_style(style);
-        self.add_named_cell_style(style_name, style_index)
+        self.add_named_style(style_name, style, None)
+    }
+
+    /// Adds the named style `style_name` with the built-in id `builtin_id`, if it is a built-in style
+    pub(crate) fn add_named_style(
+        &mut self,
+        style_name: &str,
+        style: &Style,
+        builtin_id: Option<i32>,
+    ) -> Result<(), String> {
+        if self.get_named_style_xf_id(style_name).is_ok() {
+            return Err("A style with that name already exists".to_string());
+        }
+        let (num_fmt_id, font_id, fill_id, border_id) = self.get_style_ids_or_create(style);
+        self.cell_style_xfs.push(CellStyleXfs {
+            num_fmt_id,
+            font_id,
+            fill_id,
+            border_id,
+            alignment: style.alignment.clone(),
+            protection: style.protection.clone(),
+            ..Default::default()
+        });
+        self.cell_styles.push(CellStyles {
+            name: style_name.to_string(),
+            xf_id: self.cell_style_xfs.len() as i32 - 1,
+            builtin_id,
+        });
+        Ok(())
+    }
+
+    /// Removes the named style. The cells keep their style.
+    pub(crate) fn delete_named_style(&mut self, style_name: &str) -> Result<(), String> {
+        let xf_id = self.get_named_style_xf_id(style_name)?;
+        self.cell_styles
+            .retain(|cell_style| cell_style.xf_id != xf_id);
+        Ok(())
+    }
+
+    /// Changes the named style. The styles based on it change the number format, font, fill,
+    /// border, alignment and protection to the ones of the named style, except those they override.
+    pub(crate) fn set_named_style(
+        &mut self,
+        style_name: &str,
+        style: &Style,
+    ) -> Result<(), String> {
+        let xf_id = self.get_named_style_xf_id(style_name)?;
+        let (num_fmt_id, font_id, fill_id, border_id) = self.get_style_ids_or_create(style);
+        let cell_style_xf = self
+            .cell_style_xfs
+            .get_mut(xf_id as usize)
+            .ok_or("Invalid index provided".to_string())?;
+        let old_style_xf = cell_style_xf.clone();
+        cell_style_xf.num_fmt_id = num_fmt_id;
+        cell_style_xf.font_id = font_id;
+        cell_style_xf.fill_id = fill_id;
+        cell_style_xf.border_id = border_id;
+        cell_style_xf.alignment = style.alignment.clone();
+        cell_style_xf.protection = style.protection.clone();
+        // The parts of the cell formats that override the named style are kept
+        for cell_xf in self.cell_xfs.iter_mut().filter(|c| c.xf_id == xf_id) {
+            if cell_xf.num_fmt_id == old_style_xf.num_fmt_id {
+                cell_xf.num_fmt_id = num_fmt_id;
+            }
+            if cell_xf.font_id == old_style_xf.font_id {
+                cell_xf.font_id = font_id;
+            }
+            if cell_xf.fill_id == old_style_xf.fill_id {
+                cell_xf.fill_id = fill_id;
+            }
+            if cell_xf.border_id == old_style_xf.border_id {
+                cell_xf.border_id = border_id;
+            }
+            if cell_xf.alignment == old_style_xf.alignment {
+                cell_xf.alignment = style.alignment.clone();
+            }
+            if cell_xf.protection == old_style_xf.protection {
+                cell_xf.protection = style.protection.clone();
+            }
+        }
+        Ok(())
     }
 
     pub(crate) fn get_style_with_quote_prefix(&mut self, index: i32) -> Result<i32, String> {
@@ -202,9 +383,11 @@ impl Styles {
         let num_fmt_id = cell_xf.num_fmt_id;
         let quote_prefix = cell_xf.quote_prefix;
         let alignment = cell_xf.alignment.clone();
+        let protection = cell_xf.protection.clone();
 
         Ok(Style {
             alignment,
+            protection,
             num_fmt: get_num_fmt(num_fmt_id, &self.num_fmts),
             fill: self.fills[fill_id].clone(),
             font: self.fonts[font_id].clone(),
@@ -223,7 +406,18 @@ impl Model {
         column: i32,
         style: &Style,
     ) -> Result<(), String> {
-        let style_index = self.workbook.styles.get_style_index_or_create(style);
+        // The cell keeps its named style
+        let old_index = self.get_cell_style_index(sheet, row, column)?;
+        let xf_id = self
+            .workbook
+            .styles
+            .cell_xfs
+            .get(old_index as usize)
+            .map_or(0, |cell_xf| cell_xf.xf_id);
+        let style_index = self
+            .workbook
+            .styles
+            .get_style_index_or_create_with_xf_id(style, xf_id);
         self.workbook
             .worksheet_mut(sheet)?
             .set_cell_style(row, column, style_index)
@@ -252,14 +446,20 @@ impl Model {
         column: i32,
         style_name: &str,
     ) -> Result<(), String> {
-        let style_index = self.workbook.styles.get_style_index_by_name(style_name)?;
+        let style_index = self
+            .workbook
+            .styles
+            .get_style_index_by_name_or_create(style_name)?;
         self.workbook
             .worksheet_mut(sheet)?
             .set_cell_style(row, column, style_index)
     }
 
     pub fn set_sheet_style(&mut self, sheet: u32, style_name: &str) -> Result<(), String> {
-        let style_index = self.workbook.styles.get_style_index_by_name(style_name)?;
+        let style_index = self
+            .workbook
+            .styles
+            .get_style_index_by_name_or_create(style_name)?;
         self.workbook.worksheet_mut(sheet)?.set_style(style_index)?;
         Ok(())
     }
@@ -270,7 +470,10 @@ impl Model {
         row: i32,
         style_name: &str,
     ) -> Result<(), String> {
-        let style_index = self.workbook.styles.get_style_index_by_name(style_name)?;
+        let style_index = self
+            .workbook
+            .styles
+            .get_style_index_by_name_or_create(style_name)?;
         self.workbook
             .worksheet_mut(sheet)?
             .set_row_style(row, style_index)?;
@@ -283,7 +486,10 @@ impl Model {
         column: i32,
         style_name: &str,
     ) -> Result<(), String> {
-        let style_index = self.workbook.styles.get_style_index_by_name(style_name)?;
+        let style_index = self
+            .workbook
+            .styles
+            .get_style_index_by_name_or_create(style_name)?;
         self.workbook
             .worksheet_mut(sheet)?
             .set_column_style(column, style_index)?;
@@ -0,0 +1,706 @@
+use crate::{
+    conditional_formatting::rule_formulas_mut,
+    displacement::{displace_index, displace_interval, displace_range_rows, get_moved_interval},
+    expressions::{
+        lexer::{Lexer, LexerMode},
+        parser::stringify::get_moved_index,
+        token::{TableReference, TableSpecifier, TokenType},
+        types::Area,
+        utils::is_valid_identifier,
+    },
+    language::get_language,
+    locale::get_locale,
+    merge_cells::{contains, intersects, parse_range_reference, range_to_string},
+    model::Model,
+    types::{FilterColumn, Table, TableColumn, TotalsRowFunction},
+};
+
+const TABLE_MOVE_ERROR: &str = "Cannot move part of a table";
+
+/// Returns `name`, or `name` followed by the first number that makes it different from all the
+/// `names` (case insensitive)
+pub(crate) fn get_unique_column_name(name: &str, names: &[String]) -> String {
+    let exists = |candidate: &str| {
+        names
+            .iter()
+            .any(|name| name.to_lowercase() == candidate.to_lowercase())
+    };
+    if !exists(name) {
+        return name.to_string();
+    }
+    let mut index = 2;
+    while exists(&format!("{name}{index}")) {
+        index += 1;
+    }
+    format!("{name}{index}")
+}
+
+/// Escapes the special characters of a column name in a structured reference
+fn escape_column_name(name: &str) -> String {
+    name.replace('\'', "''")
+        .replace('[', "'[")
+        .replace(']', "']")
+        .replace('#', "'#")
+        .replace('@', "'@")
+}
+
+/// Writes a structured reference like `Table1[[#This Row],[Price]]`
+fn structured_reference_to_string(
+    table_name: &str,
+    specifier: &Option<TableSpecifier>,
+    table_reference: &Option<TableReference>,
+) -> String {
+    let specifier = specifier.as_ref().map(|specifier| match specifier {
+        TableSpecifier::All => "#All",
+        TableSpecifier::Data => "#Data",
+        TableSpecifier::Headers => "#Headers",
+        TableSpecifier::ThisRow => "#This Row",
+        TableSpecifier::Totals => "#Totals",
+    });
+    let columns = table_reference.as_ref().map(|reference| match reference {
+        TableReference::ColumnReference(column) => format!("[{}]", escape_column_name(column)),
+        TableReference::RangeReference((left, right)) => format!(
+            "[{}]:[{}]",
+            escape_column_name(left),
+            escape_column_name(right)
+        ),
+    });
+    match (specifier, columns) {
+        (Some(specifier), Some(columns)) => format!("{table_name}[[{specifier}],{columns}]"),
+        (Some(specifier), None) => format!("{table_name}[{specifier}]"),
+        (None, Some(columns)) => format!("{table_name}[{columns}]"),
+        (None, None) => format!("{table_name}[]"),
+    }
+}
+
+/// Copies the `formula` replacing the tokens for which `replace` returns some text.
+/// Whitespace is preserved and if the formula cannot be tokenized the rest is copied unchanged.
+fn replace_tokens(
+    formula: &str,
+    mut replace: impl FnMut(TokenType, &str) -> Option<String>,
+) -> String {
+    let chars: Vec<char> = formula.chars().collect();
+    let len = chars.len();
+    let mut lexer = Lexer::new(
+        formula,
+        LexerMode::A1,
+        get_locale("en").expect(""),
+        get_language("en").expect(""),
+    );
+    let mut result = String::new();
+    loop {
+        let start = (lexer.get_position() as usize).min(len);
+        let token = lexer.next_token();
+        let end = (lexer.get_position() as usize).min(len);
+        let text: String = chars[start..end].iter().collect();
+        let trimmed = text.trim_start();
+        result.push_str(&text[..text.len() - trimmed.len()]);
+        match token {
+            TokenType::EOF => break,
+            TokenType::Illegal(_) => {
+                let offset = text.chars().count() - trimmed.chars().count();
+                result.extend(&chars[start + offset..]);
+                break;
+            }
+            token => match replace(token, trimmed) {
+                Some(replacement) => result.push_str(&replacement),
+                None => result.push_str(trimmed),
+            },
+        }
+    }
+    result
+}
+
+/// Renames the table `name` to `new_name` and, if given, its column (old name, new name) in the
+/// structured references of a `formula`. References without a table name are to the table if
+/// `in_table` is true.
+pub(crate) fn rename_structured_references(
+    formula: &str,
+    in_table: bool,
+    name: &str,
+    new_name: &str,
+    column: Option<(&str, &str)>,
+) -> String {
+    let is_table = |table_name: &str| table_name.to_lowercase() == name.to_lowercase();
+    let rename_column = |column_name: &mut String| {
+        if let Some((old_column, new_column)) = column {
+            if column_name.to_lowercase() == old_column.to_lowercase() {
+                *column_name = new_column.to_string();
+            }
+        }
+    };
+    replace_tokens(formula, |token, text| match token {
+        // A reference to the full table like Table1[]
+        TokenType::Ident(table_name) if text.ends_with(']') && is_table(&table_name) => {
+            Some(format!("{new_name}[]"))
+        }
+        TokenType::StructuredReference {
+            table_name,
+            specifier,
+            mut table_reference,
+        } if (table_name.is_empty() && in_table) || is_table(&table_name) => {
+            match &mut table_reference {
+                Some(TableReference::ColumnReference(column_name)) => rename_column(column_name),
+                Some(TableReference::RangeReference((left, right))) => {
+                    rename_column(left);
+                    rename_column(right);
+                }
+                None => {}
+            }
+            let table_name = if table_name.is_empty() { "" } else { new_name };
+            Some(structured_reference_to_string(
+                table_name,
+                &specifier,
+                &table_reference,
+            ))
+        }
+        _ => None,
+    })
+}
+
+/// Replaces the references in a `formula` of the first data `row` of the `table` to cells of that
+/// row in the table by structured references like `Table1[[#This Row],[Price]]`, so the formula
+/// is the same in all the rows. `column1` is the first column of the table.
+pub(crate) fn to_this_row_references(
+    formula: &str,
+    table: &Table,
+    row: i32,
+    column1: i32,
+) -> String {
+    let column_name = |column: i32| {
+        let index = usize::try_from(column - column1).ok()?;
+        table.columns.get(index).map(|c| c.name.clone())
+    };
+    let in_sheet = |sheet: &Option<String>| sheet.as_ref().is_none_or(|s| *s == table.sheet_name);
+    let this_row = |table_reference| {
+        structured_reference_to_string(
+            &table.name,
+            &Some(TableSpecifier::ThisRow),
+            &Some(table_reference),
+        )
+    };
+    replace_tokens(formula, |token, _| match token {
+        TokenType::Reference {
+            sheet,
+            row: reference_row,
+            column,
+            absolute_row: false,
+            ..
+        } if reference_row == row && in_sheet(&sheet) => Some(this_row(
+            TableReference::ColumnReference(column_name(column)?),
+        )),
+        TokenType::Range { sheet, left, right }
+            if !left.absolute_row
+                && !right.absolute_row
+                && left.row == row
+                && right.row == row
+                && in_sheet(&sheet) =>
+        {
+            Some(this_row(TableReference::RangeReference((
+                column_name(left.column)?,
+                column_name(right.column)?,
+            ))))
+        }
+        _ => None,
+    })
+}
+
+/// Returns the formula of the totals row of the `column` of the `table`, if it has a function
+pub(crate) fn get_totals_row_formula(table: &Table, column: &TableColumn) -> Option<String> {
+    // SUBTOTAL function numbers that ignore hidden rows
+    let function_number = match column.totals_row_function.as_ref()? {
+        TotalsRowFunction::Average => 101,
+        TotalsRowFunction::CountNums => 102,
+        TotalsRowFunction::Count => 103,
+        TotalsRowFunction::Max => 104,
+        TotalsRowFunction::Min => 105,
+        TotalsRowFunction::StdDev => 107,
+        TotalsRowFunction::Sum => 109,
+        TotalsRowFunction::Var => 110,
+        TotalsRowFunction::Custom => {
+            return column
+                .totals_row_formula
+                .as_ref()
+                .map(|formula| format!("={formula}"))
+        }
+    };
+    Some(format!(
+        "=SUBTOTAL({function_number},{}[[{}]])",
+        table.name,
+        escape_column_name(&column.name)
+    ))
+}
+
+impl Model {
+    /// Makes the parser aware of the current tables, it resolves the structured references
+    pub(crate) fn update_parser_tables(&mut self) {
+        self.parser.set_tables(self.workbook.tables.clone());
+    }
+
+    /// Returns the table called `name`
+    pub fn get_table(&self, name: &str) -> Result<Table, String> {
+        self.workbook
+            .tables
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Table not found: '{name}'"))
+    }
+
+    /// Returns the tables of the `sheet` sorted by name
+    pub fn get_tables(&self, sheet: u32) -> Result<Vec<Table>, String> {
+        let sheet_name = self.workbook.worksheet(sheet)?.get_name();
+        let mut tables: Vec<Table> = self
+            .workbook
+            .tables
+            .values()
+            .filter(|table| table.sheet_name == sheet_name)
+            .cloned()
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tables)
+    }
+
+    /// Adds, replaces or removes (if `table` is None) the table called `name`.
+    /// The table is not validated and the cells are not changed.
+    pub fn set_table(&mut self, name: &str, table: Option<Table>) -> Result<(), String> {
+        match table {
+            Some(table) => {
+                if table.name != name {
+                    return Err(format!("Invalid table name: '{}'", table.name));
+                }
+                parse_range_reference(&table.reference)?;
+                self.workbook.tables.insert(name.to_string(), table);
+            }
+            None => {
+                self.workbook.tables.remove(name);
+            }
+        }
+        self.update_parser_tables();
+        Ok(())
+    }
+
+    /// Renames the table `name` to `new_name` and, if given, its column (old name, new name) in
+    /// the formulas kept as text: the calculated columns and totals rows of the tables, defined
+    /// names, conditional formats and data validations.
+    /// Formulas in cells do not change, their structured references were resolved when parsed.
+    pub(crate) fn rename_table_references(
+        &mut self,
+        name: &str,
+        new_name: &str,
+        column: Option<(&str, &str)>,
+    ) {
+        let rename = |formula: &mut String, in_table: bool| {
+            *formula = rename_structured_references(formula, in_table, name, new_name, column);
+        };
+        for table in self.workbook.tables.values_mut() {
+            let in_table = table.name == name || table.name == new_name;
+            for table_column in &mut table.columns {
+                let formulas = [
+                    &mut table_column.calculated_column_formula,
+                    &mut table_column.totals_row_formula,
+                ];
+                for formula in formulas.into_iter().flatten() {
+                    rename(formula, in_table);
+                }
+            }
+        }
+        for defined_name in &mut self.workbook.defined_names {
+            rename(&mut defined_name.formula, false);
+        }
+        for worksheet in &mut self.workbook.worksheets {
+            for conditional_format in &mut worksheet.conditional_formats {
+                for formula in rule_formulas_mut(&mut conditional_format.rule) {
+                    rename(formula, false);
+                }
+            }
+            for data_validation in &mut worksheet.data_validations {
+                for formula in &mut data_validation.formulas {
+                    rename(formula, false);
+                }
+            }
+        }
+        if name != new_name {
+            self.rename_pivot_sources_table(name, new_name);
+        }
+        self.update_parser_tables();
+        self.parse_defined_names();
+    }
+
+    /// Fails if `name` cannot be the name of a new table. `current` is the table being renamed.
+    pub(crate) fn check_table_name(&self, name: &str, current: Option<&str>) -> Result<(), String> {
+        if !is_valid_identifier(name) {
+            return Err(format!("Invalid table name: '{name}'"));
+        }
+        let lower = name.to_lowercase();
+        let table_exists = self
+            .workbook
+            .tables
+            .keys()
+            .any(|table| table.to_lowercase() == lower && Some(table.as_str()) != current);
+        let defined_name_exists = self
+            .workbook
+            .defined_names
+            .iter()
+            .any(|defined_name| defined_name.name.to_lowercase() == lower);
+        if table_exists || defined_name_exists {
+            return Err(format!("Name already in use: '{name}'"));
+        }
+        Ok(())
+    }
+
+    /// Fails if a table cannot be in the `range` of the `sheet`. `current` is the table being
+    /// resized.
+    pub(crate) fn check_table_range(
+        &self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+        current: Option<&str>,
+    ) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        range_to_string(range)?;
+        let overlaps = |reference: &str| {
+            parse_range_reference(reference).is_ok_and(|other| intersects(other, range))
+        };
+        if self.workbook.tables.values().any(|table| {
+            table.sheet_name == worksheet.name
+                && Some(table.name.as_str()) != current
+                && overlaps(&table.reference)
+        }) {
+            return Err("Tables cannot overlap".to_string());
+        }
+        if let Some(auto_filter) = &worksheet.auto_filter {
+            if overlaps(&auto_filter.reference) {
+                return Err("A table cannot overlap the autofilter of the sheet".to_string());
+            }
+        }
+        if !self.get_merged_cells_in_range(sheet, range)?.is_empty() {
+            return Err("A table cannot contain merged cells".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the first name like "Table1", "Table2", ... that is not in use
+    pub(crate) fn get_new_table_name(&self) -> String {
+        let mut index = 1;
+        while self
+            .check_table_name(&format!("Table{index}"), None)
+            .is_err()
+        {
+            index += 1;
+        }
+        format!("Table{index}")
+    }
+
+    /// Returns the name for a new column of a table with header from the header cell.
+    /// `index` is the position of the column in the table.
+    pub(crate) fn get_table_column_name(
+        &self,
+        sheet: u32,
+        header_row: Option<i32>,
+        column: i32,
+        index: usize,
+        names: &[String],
+    ) -> Result<String, String> {
+        let header = match header_row {
+            Some(row) => self.get_formatted_cell_value(sheet, row, column)?,
+            None => "".to_string(),
+        };
+        let name = if header.trim().is_empty() {
+            format!("Column{}", index + 1)
+        } else {
+            header
+        };
+        Ok(get_unique_column_name(&name, names))
+    }
+
+    /// Returns the area of the data rows of the `table`
+    pub(crate) fn get_table_data_area(&self, table: &str) -> Result<Area, String> {
+        let table = self.get_table(table)?;
+        let sheet = self.get_table_sheet(&table.name)?;
+        let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+        let row1 = row1 + table.header_row_count as i32;
+        let row2 = row2 - table.totals_row_count as i32;
+        Ok(Area {
+            sheet,
+            row: row1,
+            column: column1,
+            width: column2 - column1 + 1,
+            height: row2 - row1 + 1,
+        })
+    }
+
+    /// Returns the `table` with the range `range`.
+    /// The columns in both ranges are kept, the new ones are named after their header cells.
+    pub(crate) fn get_resized_table(
+        &self,
+        table: &Table,
+        range: (i32, i32, i32, i32),
+    ) -> Result<Table, String> {
+        let sheet = self.get_table_sheet(&table.name)?;
+        let (_, old_column1, _, _) = parse_range_reference(&table.reference)?;
+        let (row1, column1, _, column2) = range;
+        let header_row = if table.header_row_count > 0 {
+            Some(row1)
+        } else {
+            None
+        };
+        let mut next_id = table.columns.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        let kept: Vec<Option<&TableColumn>> = (column1..=column2)
+            .map(|column| {
+                if column < old_column1 {
+                    None
+                } else {
+                    table.columns.get((column - old_column1) as usize)
+                }
+            })
+            .collect();
+        let mut names: Vec<String> = kept.iter().flatten().map(|c| c.name.clone()).collect();
+        let mut columns = Vec::new();
+        for (index, (column, table_column)) in (column1..=column2).zip(kept).enumerate() {
+            match table_column {
+                Some(table_column) => columns.push(table_column.clone()),
+                None => {
+                    let name =
+                        self.get_table_column_name(sheet, header_row, column, index, &names)?;
+                    names.push(name.clone());
+                    columns.push(TableColumn {
+                        id: next_id,
+                        name,
+                        ..Default::default()
+                    });
+                    next_id += 1;
+                }
+            }
+        }
+        let mut filter_columns = table.filter_columns.clone();
+        filter_columns.retain_mut(|filter_column| {
+            let column = old_column1 + filter_column.column_id as i32;
+            if column < column1 || column > column2 {
+                return false;
+            }
+            filter_column.column_id = (column - column1) as u32;
+            true
+        });
+        Ok(Table {
+            reference: range_to_string(range)?,
+            columns,
+            has_filters: !filter_columns.is_empty(),
+            filter_columns,
+            ..table.clone()
+        })
+    }
+
+    /// Moves the tables of the `sheet` after inserting (`delta` > 0) or deleting (`delta` < 0)
+    /// rows at `row`. Tables are removed if all their rows are deleted.
+    pub(crate) fn displace_tables_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let sheet_name = self.workbook.worksheet(sheet)?.get_name();
+        let mut removed = Vec::new();
+        for table in self.workbook.tables.values_mut() {
+            if table.sheet_name != sheet_name {
+                continue;
+            }
+            let range = parse_range_reference(&table.reference)?;
+            match displace_range_rows(range, row, delta) {
+                Some(range) => table.reference = range_to_string(range)?,
+                None => removed.push(table.name.clone()),
+            }
+        }
+        for name in removed {
+            self.workbook.tables.remove(&name);
+        }
+        self.update_parser_tables();
+        Ok(())
+    }
+
+    /// Moves the tables of the `sheet` after inserting (`delta` > 0) or deleting (`delta` < 0)
+    /// columns at `column`. Columns inserted inside a table are added to it.
+    /// Tables are removed if all their columns are deleted.
+    pub(crate) fn displace_tables_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let sheet_name = self.workbook.worksheet(sheet)?.get_name();
+        let names: Vec<String> = self
+            .workbook
+            .tables
+            .values()
+            .filter(|table| table.sheet_name == sheet_name)
+            .map(|table| table.name.clone())
+            .collect();
+        for name in names {
+            let table = self.workbook.tables[&name].clone();
+            let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+            let (new_column1, new_column2) =
+                match displace_interval(column1, column2, column, delta) {
+                    Some(columns) => columns,
+                    None => {
+                        self.workbook.tables.remove(&name);
+                        continue;
+                    }
+                };
+            let mut columns: Vec<Option<TableColumn>> =
+                vec![None; (new_column2 - new_column1 + 1) as usize];
+            for (index, table_column) in table.columns.iter().enumerate() {
+                if let Some(c) = displace_index(column1 + index as i32, column, delta) {
+                    columns[(c - new_column1) as usize] = Some(table_column.clone());
+                }
+            }
+            let mut names: Vec<String> = columns.iter().flatten().map(|c| c.name.clone()).collect();
+            let mut next_id = table.columns.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+            let mut new_columns = Vec::new();
+            for (index, table_column) in columns.into_iter().enumerate() {
+                new_columns.push(match table_column {
+                    Some(table_column) => table_column,
+                    None => {
+                        let name = get_unique_column_name(&format!("Column{}", index + 1), &names);
+                        names.push(name.clone());
+                        next_id += 1;
+                        TableColumn {
+                            id: next_id - 1,
+                            name,
+                            ..Default::default()
+                        }
+                    }
+                });
+            }
+            let mut filter_columns = table.filter_columns.clone();
+            filter_columns.retain_mut(|filter_column| {
+                match displace_index(column1 + filter_column.column_id as i32, column, delta) {
+                    Some(c) => {
+                        filter_column.column_id = (c - new_column1) as u32;
+                        true
+                    }
+                    None => false,
+                }
+            });
+            let table = Table {
+                reference: range_to_string((row1, new_column1, row2, new_column2))?,
+                columns: new_columns,
+                has_filters: !filter_columns.is_empty(),
+                filter_columns,
+                ..table
+            };
+            self.workbook.tables.insert(name, table);
+        }
+        self.update_parser_tables();
+        Ok(())
+    }
+
+    fn get_sheet_tables(&self, sheet: u32) -> Result<Vec<&Table>, String> {
+        let sheet_name = self.workbook.worksheet(sheet)?.get_name();
+        Ok(self
+            .workbook
+            .tables
+            .values()
+            .filter(|table| table.sheet_name == sheet_name)
+            .collect())
+    }
+
+    /// Returns the tables of the `sheet` after the `row_count` rows starting at `row` move by
+    /// `delta`. The rows of a table can be moved inside it but not its header and totals rows.
+    pub(crate) fn get_tables_after_rows_move(
+        &self,
+        sheet: u32,
+        row: i32,
+        row_count: i32,
+        delta: i32,
+    ) -> Result<Vec<Table>, String> {
+        let mut tables = Vec::new();
+        for table in self.get_sheet_tables(sheet)? {
+            let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+            let (new_row1, new_row2) = get_moved_interval(row1, row2, row, row_count, delta)
+                .ok_or_else(|| TABLE_MOVE_ERROR.to_string())?;
+            let header_rows = row1..row1 + table.header_row_count as i32;
+            let totals_rows = row2 - table.totals_row_count as i32 + 1..=row2;
+            if header_rows
+                .chain(totals_rows)
+                .any(|r| get_moved_index(r, row, row_count, delta) != r + new_row1 - row1)
+            {
+                return Err(TABLE_MOVE_ERROR.to_string());
+            }
+            tables.push(Table {
+                reference: range_to_string((new_row1, column1, new_row2, column2))?,
+                ..table.clone()
+            });
+        }
+        Ok(tables)
+    }
+
+    /// Returns the tables of the `sheet` after the `column_count` columns starting at `column`
+    /// move by `delta`. Columns moved inside a table change their order in it.
+    pub(crate) fn get_tables_after_columns_move(
+        &self,
+        sheet: u32,
+        column: i32,
+        column_count: i32,
+        delta: i32,
+    ) -> Result<Vec<Table>, String> {
+        let mut tables = Vec::new();
+        for table in self.get_sheet_tables(sheet)? {
+            let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+            let (new_column1, new_column2) =
+                get_moved_interval(column1, column2, column, column_count, delta)
+                    .ok_or_else(|| TABLE_MOVE_ERROR.to_string())?;
+            let move_index = |index: usize| {
+                get_moved_index(column1 + index as i32, column, column_count, delta) - new_column1
+            };
+            let mut columns: Vec<(i32, TableColumn)> = table
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(index, table_column)| (move_index(index), table_column.clone()))
+                .collect();
+            columns.sort_by_key(|(index, _)| *index);
+            let filter_columns = table
+                .filter_columns
+                .iter()
+                .map(|filter_column| FilterColumn {
+                    column_id: move_index(filter_column.column_id as usize) as u32,
+                    ..filter_column.clone()
+                })
+                .collect();
+            tables.push(Table {
+                reference: range_to_string((row1, new_column1, row2, new_column2))?,
+                columns: columns.into_iter().map(|(_, column)| column).collect(),
+                filter_columns,
+                ..table.clone()
+            });
+        }
+        Ok(tables)
+    }
+
+    /// Returns the tables of the `sheet` after the cells in `source` (row1, column1, row2, column2)
+    /// move by (`row_delta`, `column_delta`). Only the tables inside the `source` move.
+    pub(crate) fn get_tables_after_range_move(
+        &self,
+        sheet: u32,
+        source: (i32, i32, i32, i32),
+        row_delta: i32,
+        column_delta: i32,
+    ) -> Result<Vec<Table>, String> {
+        let mut tables = Vec::new();
+        for table in self.get_sheet_tables(sheet)? {
+            let range = parse_range_reference(&table.reference)?;
+            let (row1, column1, row2, column2) = range;
+            if contains(source, range) {
+                tables.push(Table {
+                    reference: range_to_string((
+                        row1 + row_delta,
+                        column1 + column_delta,
+                        row2 + row_delta,
+                        column2 + column_delta,
+                    ))?,
+                    ..table.clone()
+                });
+            }
+        }
+        Ok(tables)
+    }
+}
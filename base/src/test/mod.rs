@@ -1,11 +1,16 @@
 mod test_actions;
+mod test_auto_filter;
 mod test_binary_search;
 mod test_cell;
 mod test_cell_clear_contents;
 mod test_circular_references;
 mod test_column_width;
+mod test_comments;
+mod test_conditional_formatting;
 mod test_criteria;
 mod test_currency;
+mod test_data_validation;
+mod test_date_1904;
 mod test_date_and_time;
 mod test_error_propagation;
 mod test_fn_average;
@@ -27,16 +32,20 @@ mod test_fn_textjoin;
 mod test_forward_references;
 mod test_frozen_rows_columns;
 mod test_general;
+mod test_hyperlinks;
 mod test_math;
+mod test_merge_cells;
 mod test_metadata;
 mod test_model_cell_clear_all;
 mod test_model_is_empty_cell;
 mod test_move_formula;
 mod test_quote_prefix;
+mod test_rich_text;
 mod test_set_user_input;
 mod test_sheet_markup;
 mod test_sheets;
 mod test_styles;
+mod test_theme;
 mod test_trigonometric;
 mod test_workbook;
 mod test_worksheet;
@@ -51,7 +60,12 @@ mod test_extend;
 mod test_fn_type;
 mod test_frozen_rows_and_columns;
 mod test_get_cell_content;
+mod test_language;
+mod test_locales;
+mod test_outline;
 mod test_percentage;
+mod test_precision_as_displayed;
+mod test_protection;
 mod test_set_functions_error_handling;
 mod test_today;
 mod test_types;
@@ -0,0 +1,386 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    model::Model,
+    test::util::{new_empty_model, new_model_with_data},
+    types::{AutoFilter, ColumnFilter, CustomFilter, FilterColumn, FilterOperator},
+};
+
+// A1:B7 with a header row, A7 is empty
+const AMOUNTS: &[&[&str]] = &[
+    &["Name", "Amount"],
+    &["Apple", "10"],
+    &["Banana", "20"],
+    &["Cherry", "30"],
+    &["apple", "40"],
+    &["Date", "50"],
+    &["", "60"],
+];
+
+fn filter(model: &mut Model, columns: Vec<FilterColumn>) -> Vec<i32> {
+    model
+        .set_auto_filter(
+            0,
+            Some(AutoFilter {
+                reference: "A1:B7".to_string(),
+                columns,
+            }),
+        )
+        .unwrap();
+    model.apply_auto_filter(0).unwrap();
+    (1..=8)
+        .filter(|&row| model.is_row_hidden(0, row).unwrap())
+        .collect()
+}
+
+fn custom(operator: FilterOperator, value: &str) -> CustomFilter {
+    CustomFilter {
+        operator,
+        value: value.to_string(),
+    }
+}
+
+#[test]
+fn values_filter() {
+    let mut model = new_model_with_data(AMOUNTS);
+    let hidden = filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 0,
+            filter: ColumnFilter::Values {
+                values: vec!["apple".to_string(), "Date".to_string()],
+                date_groups: vec![],
+                blank: false,
+            },
+        }],
+    );
+    assert_eq!(hidden, vec![3, 4, 7]);
+
+    let hidden = filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 0,
+            filter: ColumnFilter::Values {
+                values: vec![],
+                date_groups: vec![],
+                blank: true,
+            },
+        }],
+    );
+    assert_eq!(hidden, vec![2, 3, 4, 5, 6]);
+
+    // Removing the criteria shows all the rows
+    let hidden = filter(&mut model, vec![]);
+    assert_eq!(hidden, Vec::<i32>::new());
+}
+
+#[test]
+fn custom_filter() {
+    let mut model = new_model_with_data(AMOUNTS);
+    let hidden = filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 1,
+            filter: ColumnFilter::Custom {
+                conditions: vec![
+                    custom(FilterOperator::GreaterThan, "15"),
+                    custom(FilterOperator::LessThanOrEqual, "40"),
+                ],
+                and: true,
+            },
+        }],
+    );
+    assert_eq!(hidden, vec![2, 6, 7]);
+
+    let hidden = filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 0,
+            filter: ColumnFilter::Custom {
+                conditions: vec![
+                    custom(FilterOperator::Equal, "a*"),
+                    custom(FilterOperator::Equal, "?herry"),
+                ],
+                and: false,
+            },
+        }],
+    );
+    assert_eq!(hidden, vec![3, 6, 7]);
+
+    // Text comparisons are case insensitive and empty cells never match
+    let hidden = filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 0,
+            filter: ColumnFilter::Custom {
+                conditions: vec![custom(FilterOperator::LessThan, "c")],
+                and: true,
+            },
+        }],
+    );
+    assert_eq!(hidden, vec![4, 6, 7]);
+}
+
+#[test]
+fn top10_filter() {
+    let mut model = new_model_with_data(AMOUNTS);
+    let hidden = filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 1,
+            filter: ColumnFilter::Top10 {
+                rank: 2,
+                percent: false,
+                bottom: false,
+            },
+        }],
+    );
+    assert_eq!(hidden, vec![2, 3, 4, 5]);
+
+    let hidden = filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 1,
+            filter: ColumnFilter::Top10 {
+                rank: 50,
+                percent: true,
+                bottom: true,
+            },
+        }],
+    );
+    assert_eq!(hidden, vec![5, 6, 7]);
+}
+
+#[test]
+fn filters_on_several_columns() {
+    let mut model = new_model_with_data(AMOUNTS);
+    let hidden = filter(
+        &mut model,
+        vec![
+            FilterColumn {
+                column_id: 0,
+                filter: ColumnFilter::Values {
+                    values: vec!["apple".to_string()],
+                    date_groups: vec![],
+                    blank: false,
+                },
+            },
+            FilterColumn {
+                column_id: 1,
+                filter: ColumnFilter::Custom {
+                    conditions: vec![custom(FilterOperator::GreaterThan, "20")],
+                    and: true,
+                },
+            },
+        ],
+    );
+    assert_eq!(hidden, vec![2, 3, 4, 6, 7]);
+}
+
+#[test]
+fn invalid_filters() {
+    let mut model = new_model_with_data(AMOUNTS);
+    let top10 = ColumnFilter::Top10 {
+        rank: 1,
+        percent: false,
+        bottom: false,
+    };
+    assert!(model
+        .set_auto_filter(
+            0,
+            Some(AutoFilter {
+                reference: "A1:B7".to_string(),
+                columns: vec![FilterColumn {
+                    column_id: 2,
+                    filter: top10.clone(),
+                }],
+            }),
+        )
+        .is_err());
+    assert!(model
+        .set_auto_filter(
+            0,
+            Some(AutoFilter {
+                reference: "A1:B7".to_string(),
+                columns: vec![
+                    FilterColumn {
+                        column_id: 1,
+                        filter: top10.clone(),
+                    },
+                    FilterColumn {
+                        column_id: 1,
+                        filter: top10,
+                    },
+                ],
+            }),
+        )
+        .is_err());
+    assert_eq!(
+        model.set_auto_filter(
+            0,
+            Some(AutoFilter {
+                reference: "A1:".to_string(),
+                columns: vec![],
+            }),
+        ),
+        Err("Invalid range: 'A1:'".to_string())
+    );
+    assert_eq!(model.get_auto_filter(0).unwrap(), None);
+    assert_eq!(
+        model.apply_auto_filter(0),
+        Err("The sheet has no autofilter".to_string())
+    );
+}
+
+#[test]
+fn subtotal_and_aggregate_ignore_filtered_rows() {
+    let mut model = new_model_with_data(AMOUNTS);
+    model._set("D1", "=SUBTOTAL(9, B2:B7)");
+    model._set("D2", "=SUBTOTAL(109, B2:B7)");
+    model._set("D3", "=AGGREGATE(9, 4, B2:B7)");
+    model._set("D4", "=SUM(B2:B7)");
+    model._set("D5", "=SUBTOTAL(3, A2:A7)");
+    filter(
+        &mut model,
+        vec![FilterColumn {
+            column_id: 1,
+            filter: ColumnFilter::Custom {
+                conditions: vec![custom(FilterOperator::GreaterThanOrEqual, "40")],
+                and: true,
+            },
+        }],
+    );
+    model.evaluate();
+    assert_eq!(model._get_text("D1"), "150");
+    assert_eq!(model._get_text("D2"), "150");
+    assert_eq!(model._get_text("D3"), "150");
+    assert_eq!(model._get_text("D4"), "210");
+    assert_eq!(model._get_text("D5"), "2");
+
+    // Manually hidden rows are only skipped by 101-111
+    model.set_auto_filter(0, None).unwrap();
+    model.set_row_hidden(0, 2, false).unwrap();
+    model.set_row_hidden(0, 3, false).unwrap();
+    model.set_row_hidden(0, 4, false).unwrap();
+    model.set_row_hidden(0, 7, true).unwrap();
+    model.evaluate();
+    assert_eq!(model._get_text("D1"), "210");
+    assert_eq!(model._get_text("D2"), "150");
+    assert_eq!(model._get_text("D3"), "210");
+}
+
+#[test]
+fn aggregate_options() {
+    let mut model = new_empty_model();
+    model._set("A1", "1");
+    model._set("A2", "=1/0");
+    model._set("A3", "3");
+    model._set("A4", "=SUBTOTAL(9, A1:A3)");
+    model._set("A5", "4");
+    model.set_row_hidden(0, 5, true).unwrap();
+
+    // Errors are propagated with options 0, 1, 4 and 5
+    model._set("B1", "=AGGREGATE(9, 4, A1:A5)");
+    model._set("B2", "=AGGREGATE(9, 2, A1:A5)");
+    model._set("B3", "=AGGREGATE(9, 6, A1:A3, A5)");
+    model._set("B4", "=AGGREGATE(9, 7, A1:A3, A5)");
+    model._set("B5", "=AGGREGATE(9, 3, A1:A5)");
+    model._set("B6", "=AGGREGATE(4, 6, A1:A5)");
+    model._set("B7", "=AGGREGATE(9, 8, A1:A5)");
+    model._set("B8", "=AGGREGATE(20, 6, A1:A5)");
+    model.evaluate();
+
+    assert_eq!(model._get_text("B1"), "#DIV/0!");
+    // Nested subtotals are skipped
+    assert_eq!(model._get_text("B2"), "8");
+    assert_eq!(model._get_text("B3"), "8");
+    assert_eq!(model._get_text("B4"), "4");
+    assert_eq!(model._get_text("B5"), "4");
+    assert_eq!(model._get_text("B6"), "4");
+    assert_eq!(model._get_text("B7"), "#VALUE!");
+    assert_eq!(model._get_text("B8"), "#VALUE!");
+}
+
+#[test]
+fn aggregate_functions() {
+    let mut model = new_empty_model();
+    for (row, value) in ["3", "1", "4", "1", "5", "9", "2", "6"].iter().enumerate() {
+        model._set(&format!("A{}", row + 1), value);
+    }
+    model._set("A9", "=1/0");
+    model._set("B1", "=AGGREGATE(1, 6, A1:A9)");
+    model._set("B2", "=AGGREGATE(2, 6, A1:A9)");
+    model._set("B3", "=AGGREGATE(4, 6, A1:A9)");
+    model._set("B4", "=AGGREGATE(5, 6, A1:A9)");
+    model._set("B5", "=AGGREGATE(12, 6, A1:A9)");
+    model._set("B6", "=AGGREGATE(13, 6, A1:A9)");
+    model._set("B7", "=AGGREGATE(14, 6, A1:A9, 2)");
+    model._set("B8", "=AGGREGATE(15, 6, A1:A9, 3)");
+    model._set("B9", "=AGGREGATE(16, 6, A1:A9, 0.5)");
+    model._set("B10", "=AGGREGATE(17, 6, A1:A9, 1)");
+    model._set("B11", "=AGGREGATE(18, 6, A1:A9, 0.5)");
+    model._set("B12", "=AGGREGATE(19, 6, A1:A9, 3)");
+    model._set("B13", "=AGGREGATE(14, 6, A1:A9)");
+    model._set("B14", "=AGGREGATE(14, 6, A1:A9, 9)");
+    model._set("B15", "=AGGREGATE(18, 6, A1:A9, 1)");
+    model.evaluate();
+
+    assert_eq!(model._get_text("B1"), "3.875");
+    assert_eq!(model._get_text("B2"), "8");
+    assert_eq!(model._get_text("B3"), "9");
+    assert_eq!(model._get_text("B4"), "1");
+    assert_eq!(model._get_text("B5"), "3.5");
+    assert_eq!(model._get_text("B6"), "1");
+    assert_eq!(model._get_text("B7"), "6");
+    assert_eq!(model._get_text("B8"), "2");
+    assert_eq!(model._get_text("B9"), "3.5");
+    assert_eq!(model._get_text("B10"), "1.75");
+    assert_eq!(model._get_text("B11"), "3.5");
+    assert_eq!(model._get_text("B12"), "5.75");
+    assert_eq!(model._get_text("B13"), "#ERROR!");
+    assert_eq!(model._get_text("B14"), "#NUM!");
+    assert_eq!(model._get_text("B15"), "#NUM!");
+}
+
+#[test]
+fn displace_on_insert_and_delete() {
+    let mut model = new_model_with_data(AMOUNTS);
+    model
+        .set_auto_filter(
+            0,
+            Some(AutoFilter {
+                reference: "A1:B7".to_string(),
+                columns: vec![FilterColumn {
+                    column_id: 1,
+                    filter: ColumnFilter::Top10 {
+                        rank: 1,
+                        percent: false,
+                        bottom: false,
+                    },
+                }],
+            }),
+        )
+        .unwrap();
+
+    model.insert_rows(0, 1, 2).unwrap();
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "A3:B9");
+
+    model.insert_columns(0, 2, 1).unwrap();
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "A3:C9");
+    assert_eq!(auto_filter.columns[0].column_id, 2);
+
+    model.delete_rows(0, 5, 2).unwrap();
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "A3:C7");
+
+    // Deleting a filtered column removes its filter
+    model.delete_columns(0, 3, 1).unwrap();
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "A3:B7");
+    assert_eq!(auto_filter.columns, vec![]);
+
+    model.delete_columns(0, 1, 2).unwrap();
+    assert_eq!(model.get_auto_filter(0).unwrap(), None);
+}
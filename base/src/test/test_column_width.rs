@@ -12,6 +12,9 @@ fn test_column_width() {
         max: 16384,
         min: 1,
         style: Some(6),
+        hidden: false,
+        outline_level: 0,
+        collapsed: false,
         width: 8.7,
     }];
     model.workbook.worksheets[0].cols = cols;
@@ -37,6 +40,9 @@ fn test_column_width_lower_edge() {
         max: 16,
         min: 5,
         style: Some(1),
+        hidden: false,
+        outline_level: 0,
+        collapsed: false,
         width: 10.0,
     }];
     model.workbook.worksheets[0].cols = cols;
@@ -64,6 +70,9 @@ fn test_column_width_higher_edge() {
         max: 16,
         min: 5,
         style: Some(1),
+        hidden: false,
+        outline_level: 0,
+        collapsed: false,
         width: 10.0,
     }];
     model.workbook.worksheets[0].cols = cols;
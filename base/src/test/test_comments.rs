@@ -0,0 +1,101 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::mock_time::get_milliseconds_since_epoch;
+use crate::test::util::new_empty_model;
+use crate::types::{Comment, CommentReply};
+
+#[test]
+fn add_edit_and_delete() {
+    let mut model = new_empty_model();
+    model
+        .add_comment(0, 2, 3, "Check this", "Jane", Some("jane".to_string()))
+        .unwrap();
+    let now = get_milliseconds_since_epoch();
+    assert_eq!(
+        model.get_comment(0, 2, 3).unwrap(),
+        Some(Comment {
+            text: "Check this".to_string(),
+            author_name: "Jane".to_string(),
+            author_id: Some("jane".to_string()),
+            cell_ref: "C2".to_string(),
+            timestamp: Some(now),
+            resolved: false,
+            replies: vec![],
+        })
+    );
+    assert_eq!(model.get_comment(0, 3, 3).unwrap(), None);
+
+    model.edit_comment(0, 2, 3, "Check this, please").unwrap();
+    model
+        .add_comment_reply(0, 2, 3, "Done", "John", None)
+        .unwrap();
+    model.resolve_comment(0, 2, 3, true).unwrap();
+    let comment = model.get_comment(0, 2, 3).unwrap().unwrap();
+    assert_eq!(comment.text, "Check this, please");
+    assert!(comment.resolved);
+    assert_eq!(
+        comment.replies,
+        vec![CommentReply {
+            text: "Done".to_string(),
+            author_name: "John".to_string(),
+            author_id: None,
+            timestamp: Some(now),
+        }]
+    );
+
+    model.delete_comment(0, 2, 3).unwrap();
+    assert!(model.get_comments(0).unwrap().is_empty());
+}
+
+#[test]
+fn invalid_comments() {
+    let mut model = new_empty_model();
+    model.add_comment(0, 1, 1, "First", "Jane", None).unwrap();
+    assert_eq!(
+        model.add_comment(0, 1, 1, "Second", "Jane", None),
+        Err("The cell already has a comment".to_string())
+    );
+    assert_eq!(
+        model.edit_comment(0, 2, 2, "Text"),
+        Err("The cell has no comment".to_string())
+    );
+    assert_eq!(
+        model.delete_comment(0, 2, 2),
+        Err("The cell has no comment".to_string())
+    );
+    assert_eq!(
+        model.add_comment(0, 0, 1, "Text", "Jane", None),
+        Err("Invalid row: '0'".to_string())
+    );
+    assert_eq!(
+        model.add_comment(1, 1, 1, "Text", "Jane", None),
+        Err("Invalid sheet index".to_string())
+    );
+}
+
+#[test]
+fn comments_move_with_cells() {
+    let mut model = new_empty_model();
+    model.add_comment(0, 2, 2, "B2", "Jane", None).unwrap();
+    model.add_comment(0, 5, 5, "E5", "Jane", None).unwrap();
+
+    model.insert_rows(0, 3, 2).unwrap();
+    model.insert_columns(0, 1, 1).unwrap();
+    let cell_refs = |model: &crate::Model| {
+        model
+            .get_comments(0)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.cell_ref)
+            .collect::<Vec<String>>()
+    };
+    assert_eq!(cell_refs(&model), vec!["C2", "F7"]);
+
+    // Comments in deleted rows and columns are deleted
+    model.delete_rows(0, 6, 2).unwrap();
+    assert_eq!(cell_refs(&model), vec!["C2"]);
+    model.delete_columns(0, 1, 1).unwrap();
+    assert_eq!(cell_refs(&model), vec!["B2"]);
+    model.delete_columns(0, 2, 1).unwrap();
+    assert!(cell_refs(&model).is_empty());
+}
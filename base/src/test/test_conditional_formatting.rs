@@ -0,0 +1,484 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::types::{
+    CellIsOperator, Cfvo, CfvoKind, ConditionalFormat, ConditionalFormatRule, Dxf, DxfFont,
+};
+use crate::Model;
+
+fn red_fill() -> Option<Dxf> {
+    Some(Dxf {
+        fill_color: Some("#FF0000".to_string()),
+        ..Default::default()
+    })
+}
+
+fn new_rule(sqref: &str, rule: ConditionalFormatRule) -> ConditionalFormat {
+    ConditionalFormat {
+        sqref: sqref.to_string(),
+        rule,
+        style: red_fill(),
+        stop_if_true: false,
+    }
+}
+
+fn is_red(model: &Model, row: i32) -> bool {
+    model
+        .get_effective_style_for_cell(0, row, 1)
+        .unwrap()
+        .fill
+        .fg_color
+        == Some("#FF0000".to_string())
+}
+
+/// Fills A1:A10 with 1, 2, ..., 10
+fn new_model_with_values() -> Model {
+    let mut model = new_empty_model();
+    for row in 1..=10 {
+        model._set(&format!("A{row}"), &row.to_string());
+    }
+    model
+}
+
+#[test]
+fn cell_is() {
+    let mut model = new_model_with_values();
+    model
+        .insert_conditional_format(
+            0,
+            0,
+            new_rule(
+                "A1:A10",
+                ConditionalFormatRule::CellIs {
+                    operator: CellIsOperator::Between,
+                    formulas: vec!["3".to_string(), "$B$1".to_string()],
+                },
+            ),
+        )
+        .unwrap();
+    model._set("B1", "5");
+    model.evaluate();
+    let red: Vec<bool> = (1..=10).map(|row| is_red(&model, row)).collect();
+    assert_eq!(
+        red,
+        vec![false, false, true, true, true, false, false, false, false, false]
+    );
+    // The style of the cell is unchanged
+    assert_eq!(
+        model.get_style_for_cell(0, 3, 1).unwrap().fill.fg_color,
+        None
+    );
+
+    // Results follow the values
+    model._set("B1", "3");
+    model.evaluate();
+    assert!(is_red(&model, 3));
+    assert!(!is_red(&model, 4));
+}
+
+#[test]
+fn expression() {
+    let mut model = new_model_with_values();
+    // Relative references are relative to the top left cell
+    model
+        .insert_conditional_format(
+            0,
+            0,
+            new_rule(
+                "A1:A10",
+                ConditionalFormatRule::Expression {
+                    formula: "A1*A1>$B$1".to_string(),
+                },
+            ),
+        )
+        .unwrap();
+    model._set("B1", "50");
+    model.evaluate();
+    assert!(!is_red(&model, 1));
+    assert!(!is_red(&model, 7));
+    assert!(is_red(&model, 8));
+    assert!(is_red(&model, 10));
+}
+
+#[test]
+fn top_and_average() {
+    let mut model = new_model_with_values();
+    let top = new_rule(
+        "A1:A10",
+        ConditionalFormatRule::Top10 {
+            rank: 2,
+            percent: false,
+            bottom: false,
+        },
+    );
+    let bottom_percent = new_rule(
+        "A1:A10",
+        ConditionalFormatRule::Top10 {
+            rank: 30,
+            percent: true,
+            bottom: true,
+        },
+    );
+    model.insert_conditional_format(0, 0, top).unwrap();
+    model
+        .insert_conditional_format(0, 1, bottom_percent)
+        .unwrap();
+    model.evaluate();
+    let red: Vec<i32> = (1..=10).filter(|row| is_red(&model, *row)).collect();
+    assert_eq!(red, vec![1, 2, 3, 9, 10]);
+
+    let mut model = new_model_with_values();
+    let above = new_rule(
+        "A1:A10",
+        ConditionalFormatRule::AboveAverage {
+            above_average: true,
+            equal_average: false,
+            std_dev: 0,
+        },
+    );
+    let below_std_dev = new_rule(
+        "A1:A10",
+        ConditionalFormatRule::AboveAverage {
+            above_average: false,
+            equal_average: false,
+            std_dev: 1,
+        },
+    );
+    model.insert_conditional_format(0, 0, above).unwrap();
+    model
+        .insert_conditional_format(0, 1, below_std_dev)
+        .unwrap();
+    model.evaluate();
+    // The average is 5.5 and the standard deviation 2.87
+    let red: Vec<i32> = (1..=10).filter(|row| is_red(&model, *row)).collect();
+    assert_eq!(red, vec![1, 2, 6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn duplicate_and_unique_values() {
+    let mut model = new_empty_model();
+    model._set("A1", "apple");
+    model._set("A2", "APPLE");
+    model._set("A3", "pear");
+    model._set("A4", "3");
+    model._set("A5", "=1+2");
+    model
+        .insert_conditional_format(
+            0,
+            0,
+            new_rule("A1:A5", ConditionalFormatRule::DuplicateValues),
+        )
+        .unwrap();
+    model
+        .insert_conditional_format(
+            0,
+            1,
+            ConditionalFormat {
+                sqref: "A1:A5".to_string(),
+                rule: ConditionalFormatRule::UniqueValues,
+                style: Some(Dxf {
+                    font: Some(DxfFont {
+                        b: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                stop_if_true: false,
+            },
+        )
+        .unwrap();
+    model.evaluate();
+    let red: Vec<i32> = (1..=5).filter(|row| is_red(&model, *row)).collect();
+    assert_eq!(red, vec![1, 2, 4, 5]);
+    assert!(model.get_effective_style_for_cell(0, 3, 1).unwrap().font.b);
+    assert!(!model.get_effective_style_for_cell(0, 1, 1).unwrap().font.b);
+}
+
+#[test]
+fn priority_and_stop_if_true() {
+    let mut model = new_model_with_values();
+    let mut first = new_rule(
+        "A1:A10",
+        ConditionalFormatRule::CellIs {
+            operator: CellIsOperator::GreaterThan,
+            formulas: vec!["8".to_string()],
+        },
+    );
+    first.style = Some(Dxf {
+        fill_color: Some("#00FF00".to_string()),
+        ..Default::default()
+    });
+    let second = ConditionalFormat {
+        sqref: "A1:A10".to_string(),
+        rule: ConditionalFormatRule::CellIs {
+            operator: CellIsOperator::GreaterThan,
+            formulas: vec!["5".to_string()],
+        },
+        style: Some(Dxf {
+            fill_color: Some("#FF0000".to_string()),
+            font: Some(DxfFont {
+                i: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        stop_if_true: false,
+    };
+    model
+        .insert_conditional_format(0, 0, first.clone())
+        .unwrap();
+    model.insert_conditional_format(0, 1, second).unwrap();
+    model.evaluate();
+
+    // A9 matches both rules, the first one wins the fill
+    let style = model.get_effective_style_for_cell(0, 9, 1).unwrap();
+    assert_eq!(style.fill.fg_color, Some("#00FF00".to_string()));
+    assert!(style.font.i);
+    assert!(is_red(&model, 6));
+
+    first.stop_if_true = true;
+    model.set_conditional_format(0, 0, first).unwrap();
+    model.evaluate();
+    let style = model.get_effective_style_for_cell(0, 9, 1).unwrap();
+    assert_eq!(style.fill.fg_color, Some("#00FF00".to_string()));
+    assert!(!style.font.i);
+
+    model.delete_conditional_format(0, 0).unwrap();
+    model.evaluate();
+    assert!(is_red(&model, 9));
+    assert_eq!(model.get_conditional_formats(0).unwrap().len(), 1);
+}
+
+#[test]
+fn color_scale_data_bar_and_icon_set() {
+    let mut model = new_empty_model();
+    model._set("A1", "0");
+    model._set("A2", "50");
+    model._set("A3", "100");
+    model._set("A4", "25");
+    model._set("A5", "text");
+    let color_scale = ConditionalFormatRule::ColorScale {
+        cfvos: vec![
+            Cfvo {
+                kind: CfvoKind::Min,
+                value: None,
+            },
+            Cfvo {
+                kind: CfvoKind::Max,
+                value: None,
+            },
+        ],
+        colors: vec!["#000000".to_string(), "#FFFFFF".to_string()],
+    };
+    let data_bar = ConditionalFormatRule::DataBar {
+        min: Cfvo {
+            kind: CfvoKind::Number,
+            value: Some("0".to_string()),
+        },
+        max: Cfvo {
+            kind: CfvoKind::Number,
+            value: Some("200".to_string()),
+        },
+        color: "#638EC6".to_string(),
+    };
+    let icon_set = ConditionalFormatRule::IconSet {
+        icon_set: "3Arrows".to_string(),
+        cfvos: vec![
+            Cfvo {
+                kind: CfvoKind::Percent,
+                value: Some("0".to_string()),
+            },
+            Cfvo {
+                kind: CfvoKind::Percent,
+                value: Some("33".to_string()),
+            },
+            Cfvo {
+                kind: CfvoKind::Percent,
+                value: Some("67".to_string()),
+            },
+        ],
+        reverse: false,
+        show_value: true,
+    };
+    for (index, rule) in [color_scale, data_bar, icon_set].into_iter().enumerate() {
+        let conditional_format = ConditionalFormat {
+            sqref: "A1:A5".to_string(),
+            rule,
+            style: None,
+            stop_if_true: false,
+        };
+        model
+            .insert_conditional_format(0, index, conditional_format)
+            .unwrap();
+    }
+    model.evaluate();
+
+    let fill = |row| {
+        model
+            .get_effective_style_for_cell(0, row, 1)
+            .unwrap()
+            .fill
+            .fg_color
+    };
+    assert_eq!(fill(1), Some("#000000".to_string()));
+    assert_eq!(fill(2), Some("#808080".to_string()));
+    assert_eq!(fill(3), Some("#FFFFFF".to_string()));
+    assert_eq!(fill(4), Some("#404040".to_string()));
+    assert_eq!(fill(5), None);
+
+    let result = model.get_conditional_format_result(0, 2, 1).unwrap();
+    assert_eq!(result.data_bar.unwrap().length, 0.25);
+    let icons: Vec<u32> = (1..=4)
+        .map(|row| {
+            model
+                .get_conditional_format_result(0, row, 1)
+                .unwrap()
+                .icon
+                .unwrap()
+                .index
+        })
+        .collect();
+    assert_eq!(icons, vec![0, 1, 2, 0]);
+    assert_eq!(model.get_conditional_format_result(0, 5, 1), None);
+}
+
+#[test]
+fn invalid_conditional_formats() {
+    let mut model = new_empty_model();
+    let between = new_rule(
+        "A1:A10",
+        ConditionalFormatRule::CellIs {
+            operator: CellIsOperator::Between,
+            formulas: vec!["1".to_string()],
+        },
+    );
+    assert_eq!(
+        model.insert_conditional_format(0, 0, between),
+        Err("Operator 'between' needs 2 formula(s)".to_string())
+    );
+    let bad_range = new_rule("A1:", ConditionalFormatRule::DuplicateValues);
+    assert_eq!(
+        model.insert_conditional_format(0, 0, bad_range),
+        Err("Invalid range: 'A1:'".to_string())
+    );
+    let duplicates = new_rule("A1:A10", ConditionalFormatRule::DuplicateValues);
+    assert_eq!(
+        model.insert_conditional_format(0, 1, duplicates),
+        Err("Invalid conditional format index".to_string())
+    );
+    assert_eq!(
+        model.delete_conditional_format(0, 0),
+        Err("Invalid conditional format index".to_string())
+    );
+}
+
+#[test]
+fn displaced_with_rows_and_columns() {
+    let mut model = new_model_with_values();
+    model.new_sheet();
+    model
+        .insert_conditional_format(
+            0,
+            0,
+            new_rule(
+                "A1:A10 C5",
+                ConditionalFormatRule::Expression {
+                    formula: "A1>$B$10".to_string(),
+                },
+            ),
+        )
+        .unwrap();
+    model
+        .insert_conditional_format(
+            1,
+            0,
+            new_rule(
+                "A1",
+                ConditionalFormatRule::CellIs {
+                    operator: CellIsOperator::Equal,
+                    formulas: vec!["Sheet1!$A$8".to_string()],
+                },
+            ),
+        )
+        .unwrap();
+
+    model.insert_rows(0, 3, 2).unwrap();
+    let rule = &model.get_conditional_formats(0).unwrap()[0];
+    assert_eq!(rule.sqref, "A1:A12 C7");
+    assert_eq!(
+        rule.rule,
+        ConditionalFormatRule::Expression {
+            formula: "A1>$B$12".to_string()
+        }
+    );
+    // Formulas in other sheets follow the cells too
+    assert_eq!(
+        model.get_conditional_formats(1).unwrap()[0].rule,
+        ConditionalFormatRule::CellIs {
+            operator: CellIsOperator::Equal,
+            formulas: vec!["Sheet1!$A$10".to_string()],
+        }
+    );
+
+    model.delete_columns(0, 2, 1).unwrap();
+    let rule = &model.get_conditional_formats(0).unwrap()[0];
+    assert_eq!(rule.sqref, "A1:A12 B7");
+    assert_eq!(
+        rule.rule,
+        ConditionalFormatRule::Expression {
+            formula: "A1>#REF!".to_string()
+        }
+    );
+
+    // Ranges that are deleted are removed from the rule
+    model.delete_columns(0, 2, 1).unwrap();
+    assert_eq!(model.get_conditional_formats(0).unwrap()[0].sqref, "A1:A12");
+    model.delete_columns(0, 1, 1).unwrap();
+    assert!(model.get_conditional_formats(0).unwrap().is_empty());
+}
+
+#[test]
+fn blank_cells_outside_the_used_area() {
+    let mut model = new_empty_model();
+    model._set("A1", "1");
+    model
+        .insert_conditional_format(
+            0,
+            0,
+            new_rule(
+                "A1:A5",
+                ConditionalFormatRule::Expression {
+                    formula: "ISBLANK(A1)".to_string(),
+                },
+            ),
+        )
+        .unwrap();
+    model.evaluate();
+    assert!(!is_red(&model, 1));
+    assert!(is_red(&model, 2));
+    assert!(is_red(&model, 5));
+    assert!(!is_red(&model, 6));
+}
+
+#[test]
+fn ranges_to_the_end_of_the_sheet() {
+    let mut model = new_model_with_values();
+    model
+        .insert_conditional_format(
+            0,
+            0,
+            new_rule(
+                "A2:A1048576",
+                ConditionalFormatRule::CellIs {
+                    operator: CellIsOperator::GreaterThan,
+                    formulas: vec!["8".to_string()],
+                },
+            ),
+        )
+        .unwrap();
+    model.evaluate();
+    assert!(!is_red(&model, 8));
+    assert!(is_red(&model, 9));
+    assert!(is_red(&model, 10));
+    assert!(!is_red(&model, 11));
+}
@@ -84,3 +84,16 @@ fn test_build_criteria_is_less_wildcard() {
         " Daniel Gonzalez".to_string()
     )));
 }
+
+#[test]
+fn test_build_criteria_wildcard_line_breaks() {
+    // In formulas wildcards do not match line breaks
+    let c = CalcResult::String("D*z".to_string());
+    let fn_criteria = build_criteria(&c);
+    assert!(fn_criteria(&CalcResult::String(
+        "Daniel Gonzalez".to_string()
+    )));
+    assert!(!fn_criteria(&CalcResult::String(
+        "Daniel\nGonzalez".to_string()
+    )));
+}
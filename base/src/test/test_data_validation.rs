@@ -0,0 +1,243 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::types::{CellIsOperator, DataValidation, DataValidationErrorStyle, DataValidationKind};
+use crate::Model;
+
+fn new_data_validation(
+    sqref: &str,
+    kind: DataValidationKind,
+    operator: CellIsOperator,
+    formulas: &[&str],
+) -> DataValidation {
+    DataValidation {
+        sqref: sqref.to_string(),
+        kind,
+        operator,
+        formulas: formulas.iter().map(|f| f.to_string()).collect(),
+        allow_blank: true,
+        show_dropdown: true,
+        show_input_message: false,
+        input_title: None,
+        input_message: None,
+        show_error_message: true,
+        error_style: DataValidationErrorStyle::Stop,
+        error_title: None,
+        error_message: None,
+    }
+}
+
+fn is_valid(model: &mut Model, cell: &str, value: &str) -> bool {
+    model._set(cell, value);
+    model.evaluate();
+    let cell = model.parse_reference(&format!("Sheet1!{cell}")).unwrap();
+    model
+        .check_data_validation(cell.sheet, cell.row, cell.column)
+        .unwrap()
+        .is_none()
+}
+
+#[test]
+fn numbers() {
+    let mut model = new_empty_model();
+    let whole = new_data_validation(
+        "A1:A10",
+        DataValidationKind::Whole,
+        CellIsOperator::Between,
+        &["1", "$C$1"],
+    );
+    let decimal = new_data_validation(
+        "B1:B10",
+        DataValidationKind::Decimal,
+        CellIsOperator::GreaterThan,
+        &["0.5"],
+    );
+    model.insert_data_validation(0, 0, whole).unwrap();
+    model.insert_data_validation(0, 1, decimal).unwrap();
+    model._set("C1", "10");
+
+    assert!(is_valid(&mut model, "A1", "1"));
+    assert!(is_valid(&mut model, "A2", "10"));
+    assert!(!is_valid(&mut model, "A3", "11"));
+    assert!(!is_valid(&mut model, "A4", "2.5"));
+    assert!(!is_valid(&mut model, "A5", "hello"));
+    // Formulas are evaluated
+    assert!(is_valid(&mut model, "A6", "=2*3"));
+    assert!(!is_valid(&mut model, "A7", "=C1*3"));
+    assert!(is_valid(&mut model, "A8", ""));
+
+    assert!(is_valid(&mut model, "B1", "0.75"));
+    assert!(!is_valid(&mut model, "B2", "0.25"));
+    // Cells outside of the ranges are not validated
+    assert!(is_valid(&mut model, "D1", "hello"));
+}
+
+#[test]
+fn dates_and_text_length() {
+    let mut model = new_empty_model();
+    let date = new_data_validation(
+        "A1:A10",
+        DataValidationKind::Date,
+        CellIsOperator::GreaterThanOrEqual,
+        &["DATE(2024,1,1)"],
+    );
+    let text_length = new_data_validation(
+        "B1:B10",
+        DataValidationKind::TextLength,
+        CellIsOperator::LessThanOrEqual,
+        &["5"],
+    );
+    let time = new_data_validation(
+        "C1:C10",
+        DataValidationKind::Time,
+        CellIsOperator::LessThan,
+        &["0.5"],
+    );
+    model.insert_data_validation(0, 0, date).unwrap();
+    model.insert_data_validation(0, 1, text_length).unwrap();
+    model.insert_data_validation(0, 2, time).unwrap();
+
+    assert!(is_valid(&mut model, "A1", "2024-03-01"));
+    assert!(!is_valid(&mut model, "A2", "2023-12-31"));
+    assert!(is_valid(&mut model, "B1", "hello"));
+    assert!(!is_valid(&mut model, "B2", "hello world"));
+    assert!(is_valid(&mut model, "B3", "12345"));
+    assert!(is_valid(&mut model, "C1", "0.25"));
+    assert!(!is_valid(&mut model, "C2", "0.75"));
+}
+
+#[test]
+fn lists() {
+    let mut model = new_empty_model();
+    let literal = new_data_validation(
+        "A1:A10",
+        DataValidationKind::List,
+        CellIsOperator::Between,
+        &["\"Yes,No, Maybe\""],
+    );
+    let range = new_data_validation(
+        "B1:B10",
+        DataValidationKind::List,
+        CellIsOperator::Between,
+        &["$D$1:$D$4"],
+    );
+    model.insert_data_validation(0, 0, literal).unwrap();
+    model.insert_data_validation(0, 1, range).unwrap();
+    model._set("D1", "Red");
+    model._set("D2", "Green");
+    model._set("D4", "=1+1");
+    model.evaluate();
+
+    assert_eq!(
+        model.get_data_validation_options(0, 1, 1),
+        Ok(vec![
+            "Yes".to_string(),
+            "No".to_string(),
+            "Maybe".to_string()
+        ])
+    );
+    assert_eq!(
+        model.get_data_validation_options(0, 5, 2),
+        Ok(vec![
+            "Red".to_string(),
+            "Green".to_string(),
+            "2".to_string()
+        ])
+    );
+    assert_eq!(model.get_data_validation_options(0, 1, 3), Ok(vec![]));
+
+    assert!(is_valid(&mut model, "A1", "yes"));
+    assert!(is_valid(&mut model, "A2", "Maybe"));
+    assert!(!is_valid(&mut model, "A3", "Perhaps"));
+    assert!(is_valid(&mut model, "B1", "Green"));
+    assert!(is_valid(&mut model, "B2", "2"));
+    assert!(!is_valid(&mut model, "B3", "Blue"));
+}
+
+#[test]
+fn custom() {
+    let mut model = new_empty_model();
+    // Relative references are relative to the top left cell of the range
+    let custom = new_data_validation(
+        "B2:B10",
+        DataValidationKind::Custom,
+        CellIsOperator::Between,
+        &["B2>A2"],
+    );
+    model.insert_data_validation(0, 0, custom).unwrap();
+    model._set("A3", "5");
+    model._set("A4", "5");
+    assert!(is_valid(&mut model, "B3", "6"));
+    assert!(!is_valid(&mut model, "B4", "4"));
+}
+
+#[test]
+fn invalid_data_validations() {
+    let mut model = new_empty_model();
+    let between = new_data_validation(
+        "A1:A10",
+        DataValidationKind::Whole,
+        CellIsOperator::Between,
+        &["1"],
+    );
+    assert_eq!(
+        model.insert_data_validation(0, 0, between),
+        Err("Data validation 'whole' needs 2 formula(s)".to_string())
+    );
+    let bad_range = new_data_validation(
+        "A1:B",
+        DataValidationKind::Any,
+        CellIsOperator::Between,
+        &[],
+    );
+    assert_eq!(
+        model.insert_data_validation(0, 0, bad_range),
+        Err("Invalid range: 'A1:B'".to_string())
+    );
+    assert_eq!(
+        model.delete_data_validation(0, 0),
+        Err("Invalid data validation index".to_string())
+    );
+}
+
+#[test]
+fn displaced_with_rows() {
+    let mut model = new_empty_model();
+    model
+        .insert_data_validation(
+            0,
+            0,
+            new_data_validation(
+                "A2:A10 C3",
+                DataValidationKind::List,
+                CellIsOperator::Between,
+                &["$E$2:$E$5"],
+            ),
+        )
+        .unwrap();
+    model
+        .insert_data_validation(
+            0,
+            1,
+            new_data_validation(
+                "B4",
+                DataValidationKind::List,
+                CellIsOperator::Between,
+                &["\"Yes,No\""],
+            ),
+        )
+        .unwrap();
+
+    model.insert_rows(0, 1, 1).unwrap();
+    let data_validations = model.get_data_validations(0).unwrap();
+    assert_eq!(data_validations[0].sqref, "A3:A11 C4");
+    assert_eq!(data_validations[0].formulas, vec!["$E$3:$E$6".to_string()]);
+    assert_eq!(data_validations[1].sqref, "B5");
+    assert_eq!(data_validations[1].formulas, vec!["\"Yes,No\"".to_string()]);
+
+    model.delete_rows(0, 4, 2).unwrap();
+    let data_validations = model.get_data_validations(0).unwrap();
+    assert_eq!(data_validations.len(), 1);
+    assert_eq!(data_validations[0].sqref, "A3:A9");
+    assert_eq!(data_validations[0].formulas, vec!["$E$3:$E$4".to_string()]);
+}
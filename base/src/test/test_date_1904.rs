@@ -0,0 +1,99 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{cell::CellValue, test::util::new_empty_model};
+
+#[test]
+fn date_functions() {
+    let mut model = new_empty_model();
+    model.set_date_1904(true);
+    model._set("A1", "=DATE(1904, 1, 1)");
+    model._set("A2", "=DATE(2023, 3, 20)");
+    // There are no dates before 1904
+    model._set("A3", "=DATE(1903, 12, 31)");
+    model._set("A4", "=DAY(0)");
+    model._set("A5", "=MONTH(0)");
+    model._set("A6", "=YEAR(0)");
+    model._set("A7", "=YEAR(43543)");
+    model._set("A8", "=EDATE(0, 1)");
+    model._set("A9", "=EOMONTH(0, 0)");
+    model._set("A10", "=EDATE(31, -2)");
+    model.evaluate();
+
+    assert_eq!(model._get_text("A1"), *"01/01/1904");
+    assert_eq!(
+        model.get_cell_value_by_index(0, 1, 1),
+        Ok(CellValue::Number(0.0))
+    );
+    assert_eq!(model._get_text("A2"), *"20/03/2023");
+    assert_eq!(
+        model.get_cell_value_by_index(0, 2, 1),
+        Ok(CellValue::Number(43543.0))
+    );
+    assert_eq!(model._get_text("A3"), *"#NUM!");
+    assert_eq!(model._get_text("A4"), *"1");
+    assert_eq!(model._get_text("A5"), *"1");
+    assert_eq!(model._get_text("A6"), *"1904");
+    assert_eq!(model._get_text("A7"), *"2023");
+    assert_eq!(
+        model.get_cell_value_by_index(0, 8, 1),
+        Ok(CellValue::Number(31.0))
+    );
+    assert_eq!(
+        model.get_cell_value_by_index(0, 9, 1),
+        Ok(CellValue::Number(30.0))
+    );
+    assert_eq!(model._get_text("A10"), *"#NUM!");
+}
+
+#[test]
+fn formatting_and_parsing() {
+    let mut model = new_empty_model();
+    model.set_date_1904(true);
+    model._set("A1", "=TEXT(0, \"yyyy/m/d\")");
+    model._set("A2", "=TEXT(43543, \"yyyy/m/d\")");
+    model._set("A3", "=VALUE(\"2023-03-20\")");
+    model._set("A4", "2023-03-20");
+    model.evaluate();
+
+    assert_eq!(model._get_text("A1"), *"1904/1/1");
+    assert_eq!(model._get_text("A2"), *"2023/3/20");
+    assert_eq!(
+        model.get_cell_value_by_index(0, 3, 1),
+        Ok(CellValue::Number(43543.0))
+    );
+    assert_eq!(model._get_text("A4"), *"2023-03-20");
+    assert_eq!(
+        model.get_cell_value_by_index(0, 4, 1),
+        Ok(CellValue::Number(43543.0))
+    );
+}
+
+#[test]
+fn switch_date_system() {
+    let mut model = new_empty_model();
+    assert!(!model.is_date_1904());
+    model._set("A1", "=DATE(2023, 3, 20)");
+    model._set(
+        "A2",
+        "=TBILLPRICE(DATE(2023, 3, 31), DATE(2024, 3, 31), 0.05)",
+    );
+    model._set("A3", "45005");
+    model.evaluate();
+    assert_eq!(
+        model.get_cell_value_by_index(0, 1, 1),
+        Ok(CellValue::Number(45005.0))
+    );
+    let tbill_price = model._get_text("A2");
+
+    // Values are kept, their meaning changes
+    model.set_date_1904(true);
+    assert!(model.is_date_1904());
+    model.evaluate();
+    assert_eq!(model._get_text("A1"), *"20/03/2023");
+    assert_eq!(
+        model.get_cell_value_by_index(0, 1, 1),
+        Ok(CellValue::Number(43543.0))
+    );
+    assert_eq!(model._get_text("A2"), tbill_price);
+    assert_eq!(model._get_text("A3"), *"45005");
+}
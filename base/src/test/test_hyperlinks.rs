@@ -0,0 +1,151 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::types::Hyperlink;
+
+#[test]
+fn set_and_delete() {
+    let mut model = new_empty_model();
+    model
+        .set_hyperlink(
+            0,
+            2,
+            3,
+            Some("https://ironcalc.com".to_string()),
+            None,
+            Some("Our web page".to_string()),
+        )
+        .unwrap();
+    model
+        .set_hyperlink(0, 4, 1, None, Some("Sheet1!B7".to_string()), None)
+        .unwrap();
+    assert_eq!(
+        model.get_hyperlink(0, 2, 3).unwrap(),
+        Some(Hyperlink {
+            cell_ref: "C2".to_string(),
+            url: Some("https://ironcalc.com".to_string()),
+            location: None,
+            tooltip: Some("Our web page".to_string()),
+        })
+    );
+    assert_eq!(model.get_hyperlinks(0).unwrap().len(), 2);
+    assert_eq!(model.get_hyperlink(0, 3, 3).unwrap(), None);
+
+    // A cell has at most one hyperlink
+    model
+        .set_hyperlink(
+            0,
+            2,
+            3,
+            Some("mailto:info@ironcalc.com".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    let hyperlinks = model.get_hyperlinks(0).unwrap();
+    assert_eq!(hyperlinks.len(), 2);
+    assert_eq!(
+        model.get_hyperlink(0, 2, 3).unwrap().unwrap().url,
+        Some("mailto:info@ironcalc.com".to_string())
+    );
+
+    model.delete_hyperlink(0, 2, 3).unwrap();
+    assert_eq!(model.get_hyperlink(0, 2, 3).unwrap(), None);
+    assert_eq!(
+        model.delete_hyperlink(0, 2, 3),
+        Err("The cell has no hyperlink".to_string())
+    );
+    assert_eq!(
+        model.set_hyperlink(0, 1, 1, Some("".to_string()), None, None),
+        Err("A hyperlink needs a url or a location".to_string())
+    );
+    assert!(model
+        .set_hyperlink(
+            0,
+            0,
+            1,
+            Some("https://ironcalc.com".to_string()),
+            None,
+            None
+        )
+        .is_err());
+}
+
+#[test]
+fn hyperlinks_move_with_cells() {
+    let mut model = new_empty_model();
+    model
+        .set_hyperlink(
+            0,
+            2,
+            2,
+            Some("https://ironcalc.com".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    model
+        .set_hyperlink(0, 5, 5, None, Some("Sheet1!A1".to_string()), None)
+        .unwrap();
+
+    model.insert_rows(0, 1, 2).unwrap();
+    model.insert_columns(0, 3, 1).unwrap();
+    assert!(model.get_hyperlink(0, 4, 2).unwrap().is_some());
+    assert_eq!(
+        model.get_hyperlink(0, 7, 6).unwrap().unwrap().cell_ref,
+        "F7"
+    );
+
+    model.delete_rows(0, 4, 1).unwrap();
+    assert_eq!(model.get_hyperlinks(0).unwrap().len(), 1);
+    assert!(model.get_hyperlink(0, 6, 6).unwrap().is_some());
+}
+
+#[test]
+fn hyperlink_function() {
+    let mut model = new_empty_model();
+    model._set("A1", "=HYPERLINK(\"https://ironcalc.com\", \"IronCalc\")");
+    model._set("A2", "=HYPERLINK(\"#Sheet1!C3\")");
+    model._set("A3", "=HYPERLINK(\"https://ironcalc.com\", 42)");
+    model._set("A4", "=HYPERLINK(\"https://ironcalc.com\", 1/0)");
+    model._set("A5", "=HYPERLINK()");
+    model._set("A6", "=HYPERLINK(B1, B2)");
+    model._set("B1", "mailto:info@ironcalc.com");
+    model._set("B2", "Email");
+    model.evaluate();
+
+    assert_eq!(model._get_text("A1"), "IronCalc");
+    assert_eq!(model._get_text("A2"), "#Sheet1!C3");
+    assert_eq!(model._get_text("A3"), "42");
+    assert_eq!(model._get_text("A4"), "#DIV/0!");
+    assert_eq!(model._get_text("A5"), "#ERROR!");
+    assert_eq!(model._get_text("A6"), "Email");
+
+    assert_eq!(
+        model.get_cell_hyperlink(0, 1, 1).unwrap(),
+        Some(Hyperlink {
+            cell_ref: "A1".to_string(),
+            url: Some("https://ironcalc.com".to_string()),
+            location: None,
+            tooltip: None,
+        })
+    );
+    assert_eq!(
+        model.get_cell_hyperlink(0, 2, 1).unwrap().unwrap().location,
+        Some("Sheet1!C3".to_string())
+    );
+    assert!(model.get_cell_hyperlink(0, 3, 1).unwrap().is_some());
+    assert_eq!(model.get_cell_hyperlink(0, 4, 1).unwrap(), None);
+    assert_eq!(
+        model.get_cell_hyperlink(0, 6, 1).unwrap().unwrap().url,
+        Some("mailto:info@ironcalc.com".to_string())
+    );
+    // Only the cells with a HYPERLINK formula are links
+    assert_eq!(model.get_cell_hyperlink(0, 1, 2).unwrap(), None);
+    assert_eq!(model.get_hyperlinks(0).unwrap(), vec![]);
+
+    // The link goes away with the formula
+    model._set("A1", "IronCalc");
+    model.evaluate();
+    assert_eq!(model.get_cell_hyperlink(0, 1, 1).unwrap(), None);
+}
@@ -0,0 +1,139 @@
+#![allow(clippy::unwrap_used)]
+
+use std::collections::{HashMap, HashSet};
+
+use crate::functions::Function;
+use crate::language::{get_language, get_language_ids};
+use crate::test::util::new_empty_model;
+use crate::types::CellType;
+use crate::Model;
+
+#[test]
+fn localized_formula_is_stored_in_english() {
+    let mut model = Model::new_empty("model", "de", "UTC").unwrap();
+    model.set_language("de").unwrap();
+    model._set("A1", "2");
+    let input = model.get_canonical_user_input("=WENN(WAHR;SUMME(A1;0,5))");
+    assert_eq!(input, "=IF(TRUE,SUM(A1,0.5))");
+    model.set_user_input(0, 1, 2, input).unwrap();
+    model.evaluate();
+
+    assert_eq!(model._get_text("B1"), *"2.5");
+    assert_eq!(model._get_formula("B1"), *"=IF(TRUE,SUM(A1,0.5))");
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 2).unwrap(),
+        "=WENN(WAHR;SUMME(A1;0,5))"
+    );
+}
+
+#[test]
+fn same_workbook_different_languages() {
+    let mut model = Model::new_empty("model", "en", "UTC").unwrap();
+    model._set(
+        "A1",
+        "=IF(ISNUMBER(B1), VLOOKUP(B1, C1:D3, 2, FALSE), #N/A)",
+    );
+
+    model.set_language("es").unwrap();
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 1).unwrap(),
+        "=SI(ESNUMERO(B1),BUSCARV(B1,C1:D3,2,FALSO),#N/A)"
+    );
+    model.set_language("fr").unwrap();
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 1).unwrap(),
+        "=SI(ESTNUM(B1),RECHERCHEV(B1,C1:D3,2,FAUX),#N/A)"
+    );
+    model.set_language("en").unwrap();
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 1).unwrap(),
+        "=IF(ISNUMBER(B1),VLOOKUP(B1,C1:D3,2,FALSE),#N/A)"
+    );
+}
+
+#[test]
+fn strings_references_and_names_are_kept() {
+    let mut model = Model::new_empty("model", "de", "UTC").unwrap();
+    model.set_language("de").unwrap();
+    assert_eq!(
+        model.get_canonical_user_input("=VERKETTEN(\"SUMME;1,5\";Sheet1!A1;myName)"),
+        "=CONCATENATE(\"SUMME;1,5\",Sheet1!A1,myName)"
+    );
+    // English function names are understood as well
+    assert_eq!(model.get_canonical_user_input("=sum(A1;2)"), "=sum(A1,2)");
+    // Function names that look like references
+    assert_eq!(
+        model.get_canonical_user_input("=GDA2(A1;A2;A3;A4)"),
+        "=DB(A1,A2,A3,A4)"
+    );
+    // Unknown functions and invalid formulas are left untouched
+    assert_eq!(model.get_canonical_user_input("=FOO(1;2)+"), "=FOO(1,2)+");
+    assert_eq!(model.get_canonical_user_input("=1;2\"3"), "=1,2\"3");
+}
+
+#[test]
+fn array_constants_keep_rows_and_columns() {
+    let mut model = Model::new_empty("model", "de", "UTC").unwrap();
+    model.set_language("de").unwrap();
+    let input = model.get_canonical_user_input("=SUMME({1,5.2;3.4};1)");
+    assert_eq!(input, "=SUM({1.5,2;3,4},1)");
+    model.set_user_input(0, 1, 1, input).unwrap();
+    model._set("A2", "=ROWS({1,2;3,4})");
+
+    // the rows of the array are kept in the parsed formula
+    assert_eq!(model._get_formula("A1"), *"=SUM({1.5,2;3,4},1)");
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 1).unwrap(),
+        "=SUMME({1,5.2;3.4};1)"
+    );
+    assert_eq!(
+        model.get_localized_cell_content(0, 2, 1).unwrap(),
+        "=ZEILEN({1.2;3.4})"
+    );
+    let localized = model.get_localized_cell_content(0, 2, 1).unwrap();
+    assert_eq!(
+        model.get_canonical_user_input(&localized),
+        "=ROWS({1,2;3,4})"
+    );
+}
+
+#[test]
+fn localized_values() {
+    let mut model = new_empty_model();
+    model.set_language("de").unwrap();
+    model._set("A1", "wahr");
+    model._set("A2", "#WERT!");
+    model._set("A3", "#VALUE!");
+    model._set("A4", "TRUE");
+    model.evaluate();
+
+    assert_eq!(model.get_cell_type(0, 1, 1), Ok(CellType::LogicalValue));
+    assert_eq!(model._get_text("A2"), *"#WERT!");
+    assert_eq!(model._get_text("A3"), *"#WERT!");
+    assert_eq!(model.get_cell_type(0, 4, 1), Ok(CellType::LogicalValue));
+}
+
+#[test]
+fn unknown_language() {
+    let mut model = new_empty_model();
+    assert!(model.set_language("xx").is_err());
+}
+
+#[test]
+fn localized_function_names_are_unambiguous() {
+    let english_names: HashSet<String> = Function::into_iter().map(|f| f.to_string()).collect();
+    for id in get_language_ids() {
+        let functions = &get_language(id).unwrap().functions;
+        let mut names: HashMap<String, &String> = HashMap::new();
+        for (english_name, localized_name) in functions {
+            let localized_name = localized_name.to_uppercase();
+            if let Some(other) = names.insert(localized_name.clone(), english_name) {
+                panic!("'{localized_name}' is both {other} and {english_name} in '{id}'");
+            }
+            assert!(
+                localized_name == *english_name || !english_names.contains(&localized_name),
+                "{english_name} is translated to the English name '{localized_name}' in '{id}'"
+            );
+        }
+    }
+}
@@ -0,0 +1,91 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::language::get_language;
+use crate::locale::{get_locale, get_locale_ids};
+use crate::Model;
+
+#[test]
+fn bundled_locales() {
+    let ids = get_locale_ids();
+    assert!(ids.contains(&"en"));
+    for id in ids {
+        let locale = get_locale(id).unwrap();
+        let dates = &locale.dates;
+        assert_eq!(dates.day_names.len(), 7);
+        assert_eq!(dates.months.len(), 12);
+        assert!(dates.first_day_of_week < 7);
+        assert!(!dates.date_formats.short.is_empty());
+    }
+    assert!(get_locale("xx").is_err());
+}
+
+#[test]
+fn first_day_of_week_and_date_formats() {
+    let en = get_locale("en").unwrap();
+    assert_eq!(en.dates.first_day_of_week, 0);
+    assert_eq!(en.dates.date_formats.short, "M/d/yy");
+
+    let de = get_locale("de").unwrap();
+    assert_eq!(de.dates.first_day_of_week, 1);
+    assert_eq!(de.dates.date_formats.medium, "dd.MM.y");
+}
+
+#[test]
+fn new_languages() {
+    for id in ["it", "pt", "nl", "pl", "sv"] {
+        assert!(get_language(id).is_ok(), "missing language {id}");
+    }
+    let mut model = Model::new_empty("model", "en", "UTC").unwrap();
+    model
+        .set_user_input(0, 1, 1, "=IF(A2>1,SUM(A3:A4),#N/A)".to_string())
+        .unwrap();
+
+    model.set_language("it").unwrap();
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 1).unwrap(),
+        "=SE(A2>1,SOMMA(A3:A4),#N/D)"
+    );
+    model.set_language("nl").unwrap();
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 1).unwrap(),
+        "=ALS(A2>1,SOM(A3:A4),#N/B)"
+    );
+}
+
+#[test]
+fn requested_locales_are_bundled() {
+    let ids = get_locale_ids();
+    for id in ["fr", "it", "pt-BR", "nl", "ja", "zh-CN", "ko", "pl", "sv"] {
+        assert!(ids.contains(&id), "missing locale {id}");
+    }
+
+    let fr = get_locale("fr").unwrap();
+    assert_eq!(fr.numbers.symbols.decimal, ",");
+    assert_eq!(fr.numbers.symbols.group, "\u{202f}");
+    assert_eq!(fr.dates.first_day_of_week, 1);
+    assert_eq!(fr.dates.months[0], "janvier");
+
+    let ja = get_locale("ja").unwrap();
+    assert_eq!(ja.numbers.symbols.decimal, ".");
+    assert_eq!(ja.dates.date_formats.short, "y/MM/dd");
+
+    assert_eq!(get_locale("pt-BR").unwrap().dates.first_day_of_week, 0);
+    assert_eq!(
+        get_locale("sv").unwrap().dates.date_formats.short,
+        "y-MM-dd"
+    );
+    assert_eq!(get_locale("pl").unwrap().numbers.symbols.group, "\u{a0}");
+}
+
+#[test]
+fn localized_names_added_after_the_first_languages() {
+    let mut model = Model::new_empty("model", "en", "UTC").unwrap();
+    model
+        .set_user_input(0, 1, 1, "=HYPERLINK(\"https://example.com\")".to_string())
+        .unwrap();
+    model.set_language("es").unwrap();
+    assert_eq!(
+        model.get_localized_cell_content(0, 1, 1).unwrap(),
+        "=HIPERVINCULO(\"https://example.com\")"
+    );
+}
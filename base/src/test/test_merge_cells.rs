@@ -0,0 +1,55 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+
+#[test]
+fn merge_keeps_upper_left_value() {
+    let mut model = new_empty_model();
+    model._set("B2", "1");
+    model._set("C2", "2");
+    model._set("B3", "=C2*2");
+    model._set("D1", "=SUM(B2:C3)");
+    model.evaluate();
+    assert_eq!(model._get_text("D1"), "7");
+
+    model.merge_cells(0, "B2:C3").unwrap();
+    model.evaluate();
+    assert_eq!(model.get_merged_cells(0).unwrap(), vec!["B2:C3"]);
+    assert_eq!(model._get_text("B2"), "1");
+    assert_eq!(model._get_text("C2"), "");
+    assert_eq!(model._get_text("B3"), "");
+    assert_eq!(model._get_text("D1"), "1");
+
+    assert_eq!(model.get_merged_area(0, 3, 3).unwrap(), Some((2, 2, 3, 3)));
+    assert_eq!(model.get_merged_area(0, 4, 3).unwrap(), None);
+
+    model.unmerge_cells(0, "B2:C3").unwrap();
+    assert!(model.get_merged_cells(0).unwrap().is_empty());
+    assert_eq!(model.get_merged_area(0, 2, 2).unwrap(), None);
+}
+
+#[test]
+fn invalid_merges() {
+    let mut model = new_empty_model();
+    model.merge_cells(0, "B2:C3").unwrap();
+    assert_eq!(
+        model.merge_cells(0, "C3:D4"),
+        Err("The range overlaps an existing merged area".to_string())
+    );
+    assert_eq!(
+        model.merge_cells(0, "E5"),
+        Err("Cannot merge a single cell".to_string())
+    );
+    assert_eq!(
+        model.merge_cells(0, "E5:F6 G7:H8"),
+        Err("Invalid range: 'E5:F6 G7:H8'".to_string())
+    );
+    assert_eq!(
+        model.unmerge_cells(0, "B2:C4"),
+        Err("Merged area 'B2:C4' not found".to_string())
+    );
+    assert_eq!(
+        model.merge_cells(3, "A1:B1"),
+        Err("Invalid sheet index".to_string())
+    );
+}
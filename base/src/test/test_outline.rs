@@ -0,0 +1,84 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+
+#[test]
+fn hide_rows_and_columns() {
+    let mut model = new_empty_model();
+    model.set_column_width(0, 3, 200.0).unwrap();
+    model.set_column_hidden(0, 3, true).unwrap();
+    model.set_row_hidden(0, 5, true).unwrap();
+
+    assert!(model.is_column_hidden(0, 3).unwrap());
+    assert!(!model.is_column_hidden(0, 4).unwrap());
+    assert!(model.is_row_hidden(0, 5).unwrap());
+    assert!(!model.is_row_hidden(0, 6).unwrap());
+    // The size is kept so it is restored when unhidden
+    assert_eq!(model.get_column_width(0, 3).unwrap(), 200.0);
+
+    model.set_column_hidden(0, 3, false).unwrap();
+    assert!(!model.is_column_hidden(0, 3).unwrap());
+    assert_eq!(model.get_column_width(0, 3).unwrap(), 200.0);
+
+    assert!(model.set_row_hidden(0, 0, true).is_err());
+    assert!(model.is_column_hidden(0, 0).is_err());
+}
+
+#[test]
+fn hide_column_in_range() {
+    let mut model = new_empty_model();
+    model.set_column_width(0, 2, 150.0).unwrap();
+    let mut col = model.workbook.worksheets[0].cols[0].clone();
+    col.min = 2;
+    col.max = 6;
+    model.workbook.worksheets[0].cols = vec![col];
+
+    model.set_column_hidden(0, 4, true).unwrap();
+    for column in 2..=6 {
+        assert_eq!(model.is_column_hidden(0, column).unwrap(), column == 4);
+        assert_eq!(model.get_column_width(0, column).unwrap(), 150.0);
+    }
+    assert_eq!(model.workbook.worksheets[0].cols.len(), 3);
+}
+
+#[test]
+fn outline_levels() {
+    let mut model = new_empty_model();
+    model.set_row_outline_level(0, 3, 1).unwrap();
+    model.set_row_outline_level(0, 4, 2).unwrap();
+    model.set_row_outline_level(0, 5, 1).unwrap();
+    model.set_column_outline_level(0, 2, 1).unwrap();
+
+    assert_eq!(model.get_row_outline_level(0, 4).unwrap(), 2);
+    assert_eq!(model.get_row_outline_level(0, 6).unwrap(), 0);
+    assert_eq!(model.get_column_outline_level(0, 2).unwrap(), 1);
+
+    assert_eq!(model.get_row_group(0, 4, 1).unwrap(), Some((3, 5)));
+    assert_eq!(model.get_row_group(0, 4, 2).unwrap(), Some((4, 4)));
+    assert_eq!(model.get_row_group(0, 3, 2).unwrap(), None);
+    assert_eq!(model.get_row_group(0, 6, 1).unwrap(), None);
+    assert_eq!(model.get_column_group(0, 2, 1).unwrap(), Some((2, 2)));
+
+    assert_eq!(
+        model.set_row_outline_level(0, 3, 8),
+        Err("Outline level must be between 0 and 7".to_string())
+    );
+}
+
+#[test]
+fn hidden_columns_move_with_cells() {
+    let mut model = new_empty_model();
+    model.set_column_hidden(0, 3, true).unwrap();
+    model.set_row_hidden(0, 3, true).unwrap();
+
+    model.insert_columns(0, 2, 1).unwrap();
+    model.insert_rows(0, 2, 1).unwrap();
+    assert!(!model.is_column_hidden(0, 3).unwrap());
+    assert!(model.is_column_hidden(0, 4).unwrap());
+    assert!(model.is_row_hidden(0, 4).unwrap());
+
+    model.delete_columns(0, 1, 2).unwrap();
+    model.delete_rows(0, 1, 2).unwrap();
+    assert!(model.is_column_hidden(0, 2).unwrap());
+    assert!(model.is_row_hidden(0, 2).unwrap());
+}
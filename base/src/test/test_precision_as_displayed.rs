@@ -0,0 +1,39 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{cell::CellValue, test::util::new_empty_model};
+
+#[test]
+fn rounds_formula_results() {
+    let mut model = new_empty_model();
+    model._set("A1", "1.23456");
+    model._set("B1", "=A1");
+    model._set("C1", "=B1*100");
+    let mut style = model.get_style_for_cell(0, 1, 2).unwrap();
+    style.num_fmt = "0.00".to_string();
+    model.set_cell_style(0, 1, 2, &style).unwrap();
+    model.evaluate();
+    assert_eq!(
+        model.get_cell_value_by_index(0, 1, 2),
+        Ok(CellValue::Number(1.23456))
+    );
+    assert_eq!(model._get_text("C1"), *"123.456");
+
+    model.set_precision_as_displayed(true);
+    assert!(model.is_precision_as_displayed());
+    model.evaluate();
+    // Constants are not changed
+    assert_eq!(
+        model.get_cell_value_by_index(0, 1, 1),
+        Ok(CellValue::Number(1.23456))
+    );
+    assert_eq!(
+        model.get_cell_value_by_index(0, 1, 2),
+        Ok(CellValue::Number(1.23))
+    );
+    // Dependent formulas use the rounded value
+    assert_eq!(model._get_text("C1"), *"123");
+
+    model.set_precision_as_displayed(false);
+    model.evaluate();
+    assert_eq!(model._get_text("C1"), *"123.456");
+}
@@ -0,0 +1,202 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    test::util::new_empty_model,
+    types::{PasswordHash, Protection, SheetProtection, Style, WorkbookProtection},
+};
+
+#[test]
+fn protect_and_unprotect_sheet() {
+    let mut model = new_empty_model();
+    assert!(!model.is_sheet_protected(0).unwrap());
+    assert!(model.is_cell_editable(0, 1, 1).unwrap());
+
+    model
+        .protect_sheet(0, SheetProtection::default(), Some("secret"))
+        .unwrap();
+    assert!(model.is_sheet_protected(0).unwrap());
+    // Cells are locked by default
+    assert!(model.is_cell_locked(0, 1, 1).unwrap());
+    assert!(!model.is_cell_editable(0, 1, 1).unwrap());
+    assert!(matches!(
+        model
+            .get_sheet_protection(0)
+            .unwrap()
+            .unwrap()
+            .password_hash,
+        Some(PasswordHash::Salted { .. })
+    ));
+
+    assert_eq!(
+        model.protect_sheet(0, SheetProtection::default(), None),
+        Err("The sheet is already protected".to_string())
+    );
+    assert_eq!(
+        model.unprotect_sheet(0, Some("wrong")),
+        Err("The password is not correct".to_string())
+    );
+    model.unprotect_sheet(0, Some("secret")).unwrap();
+    assert!(model.is_cell_editable(0, 1, 1).unwrap());
+    assert_eq!(
+        model.unprotect_sheet(0, None),
+        Err("The sheet is not protected".to_string())
+    );
+}
+
+#[test]
+fn legacy_password_hash() {
+    let mut model = new_empty_model();
+    model.workbook.worksheets[0].protection = Some(SheetProtection {
+        password_hash: Some(PasswordHash::Legacy("DAA7".to_string())),
+        ..Default::default()
+    });
+    assert!(model.unprotect_sheet(0, Some("Secret")).is_err());
+    assert!(model.unprotect_sheet(0, None).is_err());
+    model.unprotect_sheet(0, Some("secret")).unwrap();
+}
+
+#[test]
+fn legacy_password_hash_long_password() {
+    // Only the first 15 characters are hashed
+    let mut model = new_empty_model();
+    model.workbook.worksheets[0].protection = Some(SheetProtection {
+        password_hash: Some(PasswordHash::Legacy("A98E".to_string())),
+        ..Default::default()
+    });
+    let password = "correct horse battery staple, and then some more characters";
+    assert!(model.unprotect_sheet(0, Some("correct horse")).is_err());
+    model.unprotect_sheet(0, Some(password)).unwrap();
+
+    model.workbook.worksheets[0].protection = Some(SheetProtection {
+        password_hash: Some(PasswordHash::Legacy("A98E".to_string())),
+        ..Default::default()
+    });
+    model.unprotect_sheet(0, Some("correct horse b")).unwrap();
+}
+
+#[test]
+fn invalid_salted_hash() {
+    let mut model = new_empty_model();
+    model
+        .protect_sheet(0, SheetProtection::default(), Some("secret"))
+        .unwrap();
+    let Some(PasswordHash::Salted { hash_value, .. }) = model.workbook.worksheets[0]
+        .protection
+        .as_ref()
+        .and_then(|protection| protection.password_hash.clone())
+    else {
+        panic!("Expected a salted hash");
+    };
+    for salt_value in ["not base64!", "AAAAA", "AB==", "A===", "AAAA=AAA"] {
+        model.workbook.worksheets[0].protection = Some(SheetProtection {
+            password_hash: Some(PasswordHash::Salted {
+                algorithm_name: "SHA-512".to_string(),
+                hash_value: hash_value.clone(),
+                salt_value: salt_value.to_string(),
+                spin_count: 1,
+            }),
+            ..Default::default()
+        });
+        assert_eq!(
+            model.unprotect_sheet(0, Some("secret")),
+            Err(format!("Invalid password salt: '{salt_value}'"))
+        );
+    }
+}
+
+#[test]
+fn unlocked_and_hidden_cells() {
+    let mut model = new_empty_model();
+    let style = Style {
+        protection: Some(Protection {
+            locked: false,
+            hidden: true,
+        }),
+        ..model.get_style_for_cell(0, 2, 2).unwrap()
+    };
+    model.set_cell_style(0, 2, 2, &style).unwrap();
+    model
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    assert!(!model.is_cell_locked(0, 2, 2).unwrap());
+    assert!(model.is_cell_hidden(0, 2, 2).unwrap());
+    assert!(model.is_cell_editable(0, 2, 2).unwrap());
+    assert!(!model.is_cell_hidden(0, 1, 1).unwrap());
+
+    assert!(model.check_range_editable(0, (2, 2, 2, 2)).is_ok());
+    assert!(model.check_range_editable(0, (2, 2, 3, 2)).is_err());
+    // An empty password is no password
+    model.unprotect_sheet(0, None).unwrap();
+    assert!(model.check_range_editable(0, (2, 2, 3, 2)).is_ok());
+}
+
+#[test]
+fn unlocked_columns() {
+    let mut model = new_empty_model();
+    let style = Style {
+        protection: Some(Protection {
+            locked: false,
+            hidden: false,
+        }),
+        ..model.get_style_for_cell(0, 1, 3).unwrap()
+    };
+    let style_index = model.workbook.styles.get_style_index_or_create(&style);
+    model
+        .workbook
+        .worksheet_mut(0)
+        .unwrap()
+        .set_column_style(3, style_index)
+        .unwrap();
+    model
+        .protect_sheet(0, SheetProtection::default(), Some(""))
+        .unwrap();
+    assert_eq!(
+        model
+            .get_sheet_protection(0)
+            .unwrap()
+            .unwrap()
+            .password_hash,
+        None
+    );
+    assert!(model.check_range_editable(0, (1, 3, 1_000, 3)).is_ok());
+    // A locked cell in the unlocked column
+    model
+        .workbook
+        .worksheet_mut(0)
+        .unwrap()
+        .set_cell_style(500, 3, 0)
+        .unwrap();
+    assert!(model.check_range_editable(0, (1, 3, 1_000, 3)).is_err());
+    assert!(model.check_range_editable(0, (1, 3, 499, 3)).is_ok());
+    assert!(model.check_range_editable(0, (1, 2, 1, 3)).is_err());
+}
+
+#[test]
+fn protect_workbook() {
+    let mut model = new_empty_model();
+    assert_eq!(model.get_workbook_protection(), None);
+    model.protect_workbook(true, false, None).unwrap();
+    assert!(model.check_workbook_structure().is_err());
+    assert_eq!(
+        model.protect_workbook(true, false, None),
+        Err("The workbook is already protected".to_string())
+    );
+    model.unprotect_workbook(None).unwrap();
+    assert!(model.check_workbook_structure().is_ok());
+    assert_eq!(
+        model.unprotect_workbook(None),
+        Err("The workbook is not protected".to_string())
+    );
+
+    model.workbook.protection = Some(WorkbookProtection {
+        password_hash: Some(PasswordHash::Legacy("DAA7".to_string())),
+        lock_structure: true,
+        lock_windows: false,
+    });
+    assert!(model.unprotect_workbook(Some("1234")).is_err());
+    model.unprotect_workbook(Some("secret")).unwrap();
+
+    model.protect_workbook(false, true, None).unwrap();
+    assert!(model.check_workbook_structure().is_ok());
+    assert!(model.get_workbook_protection().unwrap().lock_windows);
+}
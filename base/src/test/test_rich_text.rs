@@ -0,0 +1,88 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::types::{Font, FontVerticalAlignment, TextRun};
+use crate::Model;
+
+fn runs() -> Vec<TextRun> {
+    vec![
+        TextRun {
+            text: "H".to_string(),
+            font: None,
+        },
+        TextRun {
+            text: "2".to_string(),
+            font: Some(Font {
+                vert_align: FontVerticalAlignment::Subscript,
+                ..Default::default()
+            }),
+        },
+        TextRun {
+            text: "O".to_string(),
+            font: Some(Font {
+                b: true,
+                color: Some("#FF0000".to_string()),
+                ..Default::default()
+            }),
+        },
+    ]
+}
+
+#[test]
+fn set_and_get_rich_text() {
+    let mut model = new_empty_model();
+    model.set_cell_rich_text(0, 1, 1, runs()).unwrap();
+    model._set("A2", "H2O");
+    model._set("B1", "=LEN(A1)");
+    model._set("B2", "=A1=A2");
+    model._set("B3", "=UPPER(A1)&\"!\"");
+    model.evaluate();
+
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), Some(runs()));
+    assert_eq!(model._get_text("A1"), "H2O");
+    // String functions see the plain text
+    assert_eq!(model._get_text("B1"), "3");
+    assert_eq!(model._get_text("B2"), "TRUE");
+    assert_eq!(model._get_text("B3"), "H2O!");
+    // The plain text cell does not share the string with rich text
+    assert_eq!(model.get_cell_rich_text(0, 2, 1).unwrap(), None);
+    assert_eq!(model.get_cell_rich_text(0, 1, 2).unwrap(), None);
+
+    // Overwriting the cell removes the rich text
+    model._set("A1", "H2O");
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), None);
+}
+
+#[test]
+fn runs_without_fonts_are_plain_text() {
+    let mut model = new_empty_model();
+    let runs = vec![
+        TextRun {
+            text: "Hello ".to_string(),
+            font: None,
+        },
+        TextRun {
+            text: "World".to_string(),
+            font: None,
+        },
+    ];
+    model.set_cell_rich_text(0, 1, 1, runs).unwrap();
+    assert_eq!(model._get_text("A1"), "Hello World");
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), None);
+
+    assert_eq!(
+        model.set_cell_rich_text(0, 1, 1, vec![]),
+        Err("Rich text needs at least one run".to_string())
+    );
+}
+
+#[test]
+fn rich_text_to_and_from_bytes() {
+    let mut model = new_empty_model();
+    model.set_cell_rich_text(0, 1, 1, runs()).unwrap();
+    let mut model = Model::from_bytes(&model.to_bytes()).unwrap();
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), Some(runs()));
+
+    model._set("A2", "H2O");
+    assert_eq!(model.get_cell_rich_text(0, 2, 1).unwrap(), None);
+}
@@ -0,0 +1,205 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::theme::{hex_to_rgb, hsl_to_rgb, rgb_to_hex, rgb_to_hsl};
+use crate::types::{BorderItem, BorderStyle, FontScheme, Theme, ThemeColor};
+
+#[test]
+fn test_known_colors() {
+    let theme = Theme::default();
+    let color1 = theme.get_color(0, -0.05);
+    assert_eq!(color1, "#F2F2F2");
+
+    let color2 = theme.get_color(5, -0.25);
+    // Excel returns "#C65911" (rounding error)
+    assert_eq!(color2, "#C55911");
+
+    let color3 = theme.get_color(4, 0.6);
+    // Excel returns "#b4c6e7" (rounding error)
+    assert_eq!(color3, "#B5C8E8");
+}
+
+#[test]
+fn test_rgb_hex() {
+    struct ColorTest {
+        hex: String,
+        rgb: [i32; 3],
+        hsl: [i32; 3],
+    }
+    let color_tests = [
+        ColorTest {
+            hex: "#FFFFFF".to_string(),
+            rgb: [255, 255, 255],
+            hsl: [0, 0, 100],
+        },
+        ColorTest {
+            hex: "#000000".to_string(),
+            rgb: [0, 0, 0],
+            hsl: [0, 0, 0],
+        },
+        ColorTest {
+            hex: "#44546A".to_string(),
+            rgb: [68, 84, 106],
+            hsl: [215, 22, 34],
+        },
+        ColorTest {
+            hex: "#E7E6E6".to_string(),
+            rgb: [231, 230, 230],
+            hsl: [0, 2, 90],
+        },
+        ColorTest {
+            hex: "#4472C4".to_string(),
+            rgb: [68, 114, 196],
+            hsl: [218, 52, 52],
+        },
+        ColorTest {
+            hex: "#ED7D31".to_string(),
+            rgb: [237, 125, 49],
+            hsl: [24, 84, 56],
+        },
+        ColorTest {
+            hex: "#A5A5A5".to_string(),
+            rgb: [165, 165, 165],
+            hsl: [0, 0, 65],
+        },
+        ColorTest {
+            hex: "#FFC000".to_string(),
+            rgb: [255, 192, 0],
+            hsl: [45, 100, 50],
+        },
+        ColorTest {
+            hex: "#5B9BD5".to_string(),
+            rgb: [91, 155, 213],
+            hsl: [209, 59, 60],
+        },
+        ColorTest {
+            hex: "#70AD47".to_string(),
+            rgb: [112, 173, 71],
+            hsl: [96, 42, 48],
+        },
+        ColorTest {
+            hex: "#0563C1".to_string(),
+            rgb: [5, 99, 193],
+            hsl: [210, 95, 39],
+        },
+        ColorTest {
+            hex: "#954F72".to_string(),
+            rgb: [149, 79, 114],
+            hsl: [330, 31, 45],
+        },
+    ];
+    for color in color_tests.iter() {
+        let rgb = color.rgb;
+        let hsl = color.hsl;
+        assert_eq!(rgb, hex_to_rgb(&color.hex));
+        assert_eq!(hsl, rgb_to_hsl(rgb));
+        assert_eq!(rgb_to_hex(rgb), color.hex);
+        // The round trip has rounding errors
+        // FIXME: We could also hardcode the hsl21 in the testcase
+        let rgb2 = hsl_to_rgb(hsl);
+        let diff = (rgb2[0] - rgb[0]).abs() + (rgb2[1] - rgb[1]).abs() + (rgb2[2] - rgb[2]).abs();
+        assert!(diff < 4);
+    }
+}
+
+#[test]
+fn set_theme_changes_theme_colors() {
+    let mut model = new_empty_model();
+    let theme = Theme::default();
+    model._set("A1", "Title");
+    let mut style = model.get_style_for_cell(0, 1, 1).unwrap();
+    let accent = ThemeColor {
+        theme: 4,
+        tint: Some("0.59999389629810485".to_string()),
+    };
+    let accent_color = theme.get_theme_color(&accent);
+    assert_eq!(accent_color, "#B5C8E8");
+    style.fill.fg_color = Some(accent_color.clone());
+    style.fill.fg_color_theme = Some(accent.clone());
+    style.fill.pattern_type = "solid".to_string();
+    style.border.bottom = Some(BorderItem {
+        style: BorderStyle::Thin,
+        color: Some(accent_color.clone()),
+        color_theme: Some(accent),
+    });
+    // A color that is not in the theme
+    style.font.color = Some("#123456".to_string());
+    model.set_cell_style(0, 1, 1, &style).unwrap();
+    // The same color, but not taken from the theme
+    model._set("A2", "Plain");
+    let mut style = model.get_style_for_cell(0, 2, 1).unwrap();
+    style.fill.fg_color = Some(accent_color.clone());
+    style.fill.pattern_type = "solid".to_string();
+    model.set_cell_style(0, 2, 1, &style).unwrap();
+
+    let mut new_theme = theme.clone();
+    new_theme.colors[4] = "#C00000".to_string();
+    model.set_theme(&new_theme).unwrap();
+
+    let expected = new_theme.get_color(4, 0.599_993_896_298_104_8);
+    assert_ne!(expected, accent_color);
+    let style = model.get_style_for_cell(0, 1, 1).unwrap();
+    assert_eq!(style.fill.fg_color, Some(expected.clone()));
+    assert_eq!(style.border.bottom.unwrap().color, Some(expected.clone()));
+    assert_eq!(style.font.color, Some("#123456".to_string()));
+    let style = model.get_style_for_cell(0, 2, 1).unwrap();
+    assert_eq!(style.fill.fg_color, Some(accent_color.clone()));
+    assert_eq!(model.workbook.theme, new_theme);
+
+    // Back to the original theme
+    model.set_theme(&theme).unwrap();
+    let style = model.get_style_for_cell(0, 1, 1).unwrap();
+    assert_eq!(style.fill.fg_color, Some(accent_color));
+}
+
+#[test]
+fn set_theme_changes_fonts() {
+    let mut model = new_empty_model();
+    model._set("A1", "Title");
+    let mut style = model.get_style_for_cell(0, 1, 1).unwrap();
+    style.font.name = "Calibri Light".to_string();
+    style.font.scheme = FontScheme::Major;
+    model.set_cell_style(0, 1, 1, &style).unwrap();
+    model._set("A2", "Not in the theme");
+    let mut style = model.get_style_for_cell(0, 2, 1).unwrap();
+    style.font.name = "Arial".to_string();
+    model.set_cell_style(0, 2, 1, &style).unwrap();
+
+    let theme = Theme {
+        major_font: "Georgia".to_string(),
+        minor_font: "Verdana".to_string(),
+        ..Default::default()
+    };
+    model.set_theme(&theme).unwrap();
+    assert_eq!(
+        model.get_style_for_cell(0, 1, 1).unwrap().font.name,
+        "Georgia"
+    );
+    assert_eq!(
+        model.get_style_for_cell(0, 2, 1).unwrap().font.name,
+        "Arial"
+    );
+    // Cells with the default style
+    assert_eq!(
+        model.get_style_for_cell(0, 3, 1).unwrap().font.name,
+        "Verdana"
+    );
+}
+
+#[test]
+fn set_theme_errors() {
+    let mut model = new_empty_model();
+    let mut theme = Theme::default();
+    theme.colors.pop();
+    assert_eq!(
+        model.set_theme(&theme),
+        Err("A theme must have 12 colors".to_string())
+    );
+    let mut theme = Theme::default();
+    theme.colors[0] = "red".to_string();
+    assert_eq!(
+        model.set_theme(&theme),
+        Err("Invalid color: 'red'.".to_string())
+    );
+    assert_eq!(model.workbook.theme, Theme::default());
+}
@@ -1,20 +1,42 @@
 mod test_add_delete_sheets;
+mod test_auto_filter;
 mod test_autofill_columns;
 mod test_autofill_rows;
 mod test_border;
+mod test_calc_mode;
 mod test_clear_cells;
+mod test_comments;
+mod test_conditional_formatting;
+mod test_data_tools;
+mod test_data_validation;
+mod test_date_1904;
 mod test_diff_queue;
 mod test_evaluation;
+mod test_fill_series;
+mod test_find_replace;
 mod test_general;
 mod test_grid_lines;
+mod test_hyperlinks;
 mod test_keyboard_navigation;
+mod test_language;
+mod test_merge_cells;
+mod test_move_cells;
+mod test_move_copy_sheets;
+mod test_named_styles;
 mod test_on_area_selection;
 mod test_on_expand_selected_range;
 mod test_on_paste_styles;
+mod test_outline;
 mod test_paste_csv;
+mod test_paste_special;
+mod test_pivot_tables;
+mod test_protection;
 mod test_rename_sheet;
+mod test_rich_text;
 mod test_row_column;
+mod test_sort;
 mod test_styles;
+mod test_tables;
 mod test_to_from_bytes;
 mod test_undo_redo;
 mod test_view;
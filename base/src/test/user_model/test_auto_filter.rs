@@ -0,0 +1,358 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    test::util::{area, new_empty_model, new_model_with_data},
+    types::{
+        ColumnFilter, CustomFilter, DateGroup, DateTimeGrouping, FilterOperator, SheetProtection,
+        Table, TableColumn, TableStyleInfo,
+    },
+    UserModel,
+};
+
+fn values(values: &[&str]) -> ColumnFilter {
+    ColumnFilter::Values {
+        values: values.iter().map(|v| v.to_string()).collect(),
+        date_groups: vec![],
+        blank: false,
+    }
+}
+
+fn hidden_rows(model: &UserModel) -> Vec<i32> {
+    (1..=10)
+        .filter(|&row| model.is_row_hidden(0, row).unwrap())
+        .collect()
+}
+
+// A1:B5 with a header row
+const FRUITS: &[&[&str]] = &[
+    &["Fruit", "Price"],
+    &["Apple", "3"],
+    &["Pear", "5"],
+    &["Apple", "7"],
+    &["Plum", "2"],
+];
+
+#[test]
+fn add_filter_undo_redo() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_auto_filter(&area(0, 1, 1, 2, 5)).unwrap();
+    assert_eq!(
+        model.add_auto_filter(&area(0, 1, 1, 2, 5)),
+        Err("The sheet already has an autofilter".to_string())
+    );
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "A1:B5");
+    assert_eq!(auto_filter.columns, vec![]);
+
+    model
+        .set_auto_filter_column(0, 0, Some(values(&["apple"])))
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![3, 5]);
+
+    model
+        .set_auto_filter_column(
+            0,
+            1,
+            Some(ColumnFilter::Custom {
+                conditions: vec![CustomFilter {
+                    operator: FilterOperator::GreaterThan,
+                    value: "4".to_string(),
+                }],
+                and: true,
+            }),
+        )
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![2, 3, 5]);
+
+    model.undo().unwrap();
+    assert_eq!(hidden_rows(&model), vec![3, 5]);
+    assert_eq!(model.get_auto_filter(0).unwrap().unwrap().columns.len(), 1);
+
+    model.undo().unwrap();
+    assert_eq!(hidden_rows(&model), Vec::<i32>::new());
+
+    model.redo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(hidden_rows(&model), vec![2, 3, 5]);
+
+    // Clearing a column filter shows its rows again
+    model.set_auto_filter_column(0, 0, None).unwrap();
+    assert_eq!(hidden_rows(&model), vec![2, 5]);
+}
+
+#[test]
+fn reapply_and_remove() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model
+        .set_user_input(0, 7, 1, "=SUBTOTAL(9, B2:B5)")
+        .unwrap();
+    model.add_auto_filter(&area(0, 1, 1, 2, 5)).unwrap();
+    model
+        .set_auto_filter_column(0, 0, Some(values(&["Apple"])))
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![3, 5]);
+    assert_eq!(model.get_formatted_cell_value(0, 7, 1).unwrap(), "10");
+
+    // Rows are not filtered again until the filter is reapplied
+    model.set_user_input(0, 5, 1, "Apple").unwrap();
+    assert_eq!(hidden_rows(&model), vec![3, 5]);
+    model.reapply_auto_filter(0).unwrap();
+    assert_eq!(hidden_rows(&model), vec![3]);
+    assert_eq!(model.get_formatted_cell_value(0, 7, 1).unwrap(), "12");
+
+    model.remove_auto_filter(0).unwrap();
+    assert_eq!(model.get_auto_filter(0).unwrap(), None);
+    assert_eq!(hidden_rows(&model), Vec::<i32>::new());
+    assert_eq!(model.get_formatted_cell_value(0, 7, 1).unwrap(), "17");
+
+    model.undo().unwrap();
+    assert_eq!(hidden_rows(&model), vec![3]);
+    assert!(model.get_auto_filter(0).unwrap().is_some());
+    assert_eq!(
+        model.remove_auto_filter(1),
+        Err("Invalid sheet index".to_string())
+    );
+}
+
+#[test]
+fn date_and_color_filters() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Date").unwrap();
+    model.set_user_input(0, 2, 1, "2024-03-01").unwrap();
+    model.set_user_input(0, 3, 1, "2024-05-17").unwrap();
+    model.set_user_input(0, 4, 1, "2023-03-01").unwrap();
+    model
+        .update_range_style(&area(0, 3, 1, 1, 1), "fill.fg_color", "#FF0000")
+        .unwrap();
+    model.add_auto_filter(&area(0, 1, 1, 1, 4)).unwrap();
+
+    let date_group = DateGroup {
+        grouping: DateTimeGrouping::Year,
+        year: 2024,
+        month: 0,
+        day: 0,
+        hour: 0,
+        minute: 0,
+        second: 0,
+    };
+    model
+        .set_auto_filter_column(
+            0,
+            0,
+            Some(ColumnFilter::Values {
+                values: vec![],
+                date_groups: vec![date_group.clone()],
+                blank: false,
+            }),
+        )
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![4]);
+
+    model
+        .set_auto_filter_column(
+            0,
+            0,
+            Some(ColumnFilter::Values {
+                values: vec![],
+                date_groups: vec![DateGroup {
+                    grouping: DateTimeGrouping::Month,
+                    month: 3,
+                    ..date_group
+                }],
+                blank: false,
+            }),
+        )
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![3, 4]);
+
+    model
+        .set_auto_filter_column(
+            0,
+            0,
+            Some(ColumnFilter::Color {
+                color: "#ff0000".to_string(),
+                cell_color: true,
+            }),
+        )
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![2, 4]);
+}
+
+#[test]
+fn date_filter_with_out_of_range_dates() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Date").unwrap();
+    model.set_user_input(0, 2, 1, "2024-03-01").unwrap();
+    model.set_user_input(0, 3, 1, "1000000000").unwrap();
+    model
+        .update_range_style(&area(0, 2, 1, 1, 2), "num_fmt", "yyyy-mm-dd")
+        .unwrap();
+    model.add_auto_filter(&area(0, 1, 1, 1, 3)).unwrap();
+
+    // A number too large to be a date is not in any date group
+    model
+        .set_auto_filter_column(
+            0,
+            0,
+            Some(ColumnFilter::Values {
+                values: vec![],
+                date_groups: vec![DateGroup {
+                    grouping: DateTimeGrouping::Year,
+                    year: 2024,
+                    month: 0,
+                    day: 0,
+                    hour: 0,
+                    minute: 0,
+                    second: 0,
+                }],
+                blank: false,
+            }),
+        )
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![3]);
+}
+
+#[test]
+fn date_filter_with_out_of_range_dates_1904() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_date_1904(true);
+    model.set_user_input(0, 1, 1, "Date").unwrap();
+    // 31 December 9999 is the last date, 2_958_465 - 1_462 in the 1904 date system
+    model.set_user_input(0, 2, 1, "2957003").unwrap();
+    model.set_user_input(0, 3, 1, "2957004").unwrap();
+    model
+        .update_range_style(&area(0, 2, 1, 1, 2), "num_fmt", "yyyy-mm-dd")
+        .unwrap();
+    model.add_auto_filter(&area(0, 1, 1, 1, 3)).unwrap();
+
+    model
+        .set_auto_filter_column(
+            0,
+            0,
+            Some(ColumnFilter::Values {
+                values: vec![],
+                date_groups: vec![
+                    DateGroup {
+                        grouping: DateTimeGrouping::Year,
+                        year: 9999,
+                        month: 0,
+                        day: 0,
+                        hour: 0,
+                        minute: 0,
+                        second: 0,
+                    },
+                    // Would match A3 if it was read as a date
+                    DateGroup {
+                        grouping: DateTimeGrouping::Year,
+                        year: 10000,
+                        month: 0,
+                        day: 0,
+                        hour: 0,
+                        minute: 0,
+                        second: 0,
+                    },
+                ],
+                blank: false,
+            }),
+        )
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![3]);
+}
+
+#[test]
+fn table_filter() {
+    let mut base = new_empty_model();
+    base.workbook.tables.insert(
+        "Fruits".to_string(),
+        Table {
+            name: "Fruits".to_string(),
+            display_name: "Fruits".to_string(),
+            sheet_name: "Sheet1".to_string(),
+            reference: "A1:B6".to_string(),
+            totals_row_count: 1,
+            header_row_count: 1,
+            header_row_dxf_id: None,
+            data_dxf_id: None,
+            totals_row_dxf_id: None,
+            columns: vec![
+                TableColumn {
+                    id: 1,
+                    name: "Fruit".to_string(),
+                    ..Default::default()
+                },
+                TableColumn {
+                    id: 2,
+                    name: "Price".to_string(),
+                    ..Default::default()
+                },
+            ],
+            style_info: TableStyleInfo::default(),
+            has_filters: false,
+            filter_columns: vec![],
+        },
+    );
+    let mut model = UserModel::from_model(base);
+    for (row, (fruit, price)) in [("Apple", "3"), ("Pear", "5"), ("Apple", "7"), ("Plum", "2")]
+        .iter()
+        .enumerate()
+    {
+        model.set_user_input(0, row as i32 + 2, 1, fruit).unwrap();
+        model.set_user_input(0, row as i32 + 2, 2, price).unwrap();
+    }
+    model.set_user_input(0, 6, 1, "Total").unwrap();
+
+    model
+        .set_table_filter_column("Fruits", 0, Some(values(&["Pear", "Plum"])))
+        .unwrap();
+    // The totals row is never hidden
+    assert_eq!(hidden_rows(&model), vec![2, 4]);
+    assert_eq!(model.get_table_filter("Fruits").unwrap().len(), 1);
+
+    model.undo().unwrap();
+    assert_eq!(hidden_rows(&model), Vec::<i32>::new());
+    assert_eq!(model.get_table_filter("Fruits").unwrap(), vec![]);
+
+    model.redo().unwrap();
+    assert_eq!(hidden_rows(&model), vec![2, 4]);
+
+    model.set_user_input(0, 2, 1, "Plum").unwrap();
+    model.reapply_table_filter("Fruits").unwrap();
+    assert_eq!(hidden_rows(&model), vec![4]);
+
+    assert_eq!(
+        model.set_table_filter_column("Vegetables", 0, None),
+        Err("Table not found: 'Vegetables'".to_string())
+    );
+    assert!(model
+        .set_table_filter_column("Fruits", 2, Some(values(&[])))
+        .is_err());
+}
+
+#[test]
+fn protected_sheet() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_auto_filter(&area(0, 1, 1, 2, 5)).unwrap();
+    model
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    assert!(model
+        .set_auto_filter_column(0, 0, Some(values(&["Apple"])))
+        .is_err());
+    assert!(model.remove_auto_filter(0).is_err());
+    model.unprotect_sheet(0, None).unwrap();
+
+    model
+        .protect_sheet(
+            0,
+            SheetProtection {
+                allow_auto_filter: true,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+    model
+        .set_auto_filter_column(0, 0, Some(values(&["Apple"])))
+        .unwrap();
+    assert_eq!(hidden_rows(&model), vec![3, 5]);
+}
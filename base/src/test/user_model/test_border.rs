@@ -37,6 +37,7 @@ fn borders_all() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -59,6 +60,7 @@ fn borders_all() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -77,6 +79,7 @@ fn borders_all() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -97,6 +100,7 @@ fn borders_all() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -115,6 +119,7 @@ fn borders_all() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -190,6 +195,7 @@ fn borders_inner() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -210,6 +216,7 @@ fn borders_inner() {
         let border_item = BorderItem {
             style: BorderStyle::Thin,
             color: Some("#FF5566".to_string()),
+            color_theme: None,
         };
         // It should be right and bottom
         let expected_border = Border {
@@ -229,6 +236,7 @@ fn borders_inner() {
         let border_item = BorderItem {
             style: BorderStyle::Thin,
             color: Some("#FF5566".to_string()),
+            color_theme: None,
         };
         // It should be only left and top
         let expected_border = Border {
@@ -275,6 +283,7 @@ fn borders_outer() {
         let border_item = BorderItem {
             style: BorderStyle::Thin,
             color: Some("#FF5566".to_string()),
+            color_theme: None,
         };
         // It should be only left and top
         let expected_border = Border {
@@ -294,6 +303,7 @@ fn borders_outer() {
         let border_item = BorderItem {
             style: BorderStyle::Thin,
             color: Some("#FF5566".to_string()),
+            color_theme: None,
         };
         // It should be only left and top
         let expected_border = Border {
@@ -316,6 +326,7 @@ fn borders_outer() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -334,6 +345,7 @@ fn borders_outer() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -354,6 +366,7 @@ fn borders_outer() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -372,6 +385,7 @@ fn borders_outer() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -418,6 +432,7 @@ fn borders_top() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -440,6 +455,7 @@ fn borders_top() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -530,6 +546,7 @@ fn borders_right() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -576,6 +593,7 @@ fn borders_bottom() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -622,6 +640,7 @@ fn borders_left() {
             let border_item = BorderItem {
                 style: BorderStyle::Thin,
                 color: Some("#FF5566".to_string()),
+                color_theme: None,
             };
             let expected_border = Border {
                 diagonal_up: false,
@@ -0,0 +1,110 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::CellReferenceIndex;
+use crate::test::util::new_empty_model;
+use crate::types::CalcMode;
+use crate::UserModel;
+
+#[test]
+fn manual_calc_mode() {
+    let mut model = UserModel::from_model(new_empty_model());
+    assert_eq!(model.get_calc_mode(), CalcMode::Automatic);
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 1, 2, "=A1*2").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2), Ok("2".to_string()));
+    assert!(model.get_stale_cells().is_empty());
+
+    model.set_calc_mode(CalcMode::Manual);
+    model.set_user_input(0, 1, 1, "5").unwrap();
+    // The formula has not been evaluated
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2), Ok("2".to_string()));
+    assert_eq!(
+        model.get_stale_cells(),
+        vec![CellReferenceIndex {
+            sheet: 0,
+            row: 1,
+            column: 2
+        }]
+    );
+
+    model.evaluate();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 2),
+        Ok("10".to_string())
+    );
+    assert!(model.get_stale_cells().is_empty());
+
+    // Going back to automatic evaluates pending changes
+    model.set_user_input(0, 1, 1, "7").unwrap();
+    model.set_calc_mode(CalcMode::Automatic);
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 2),
+        Ok("14".to_string())
+    );
+    assert!(model.get_stale_cells().is_empty());
+
+    model.undo().unwrap();
+    assert_eq!(model.get_calc_mode(), CalcMode::Manual);
+    model.redo().unwrap();
+    assert_eq!(model.get_calc_mode(), CalcMode::Automatic);
+}
+
+#[test]
+fn evaluate_sheet() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.new_sheet().unwrap();
+    model.set_calc_mode(CalcMode::Manual);
+    model.set_user_input(0, 1, 1, "=1+1").unwrap();
+    model.set_user_input(1, 1, 1, "=2+2").unwrap();
+    assert_eq!(model.get_stale_cells().len(), 2);
+
+    model.evaluate_sheet(1).unwrap();
+    assert_eq!(model.get_formatted_cell_value(1, 1, 1), Ok("4".to_string()));
+    assert_eq!(
+        model.get_stale_cells(),
+        vec![CellReferenceIndex {
+            sheet: 0,
+            row: 1,
+            column: 1
+        }]
+    );
+
+    model.evaluate();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("2".to_string()));
+    assert!(model.get_stale_cells().is_empty());
+
+    assert_eq!(
+        model.evaluate_sheet(2),
+        Err("Invalid sheet index".to_string())
+    );
+}
+
+#[test]
+fn calc_mode_is_saved() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 1, 2, "=A1*2").unwrap();
+    model.set_calc_mode(CalcMode::Manual);
+    model.set_user_input(0, 1, 1, "5").unwrap();
+
+    // The model is not evaluated when it is opened
+    let mut model = UserModel::from_bytes(&model.to_bytes()).unwrap();
+    assert_eq!(model.get_calc_mode(), CalcMode::Manual);
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2), Ok("2".to_string()));
+    // but we know its formulas might be out of date
+    assert_eq!(
+        model.get_stale_cells(),
+        vec![CellReferenceIndex {
+            sheet: 0,
+            row: 1,
+            column: 2
+        }]
+    );
+
+    model.evaluate();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 2),
+        Ok("10".to_string())
+    );
+    assert!(model.get_stale_cells().is_empty());
+}
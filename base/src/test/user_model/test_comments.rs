@@ -0,0 +1,73 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::UserModel;
+
+#[test]
+fn undo_redo_comments() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.add_comment(0, 1, 1, "Hello", "Jane", None).unwrap();
+    model
+        .add_comment_reply(0, 1, 1, "Hi", "John", None)
+        .unwrap();
+    model.resolve_comment(0, 1, 1, true).unwrap();
+    model.edit_comment(0, 1, 1, "Hello!").unwrap();
+
+    model.undo().unwrap();
+    assert_eq!(model.get_comment(0, 1, 1).unwrap().unwrap().text, "Hello");
+    model.undo().unwrap();
+    assert!(!model.get_comment(0, 1, 1).unwrap().unwrap().resolved);
+    model.undo().unwrap();
+    assert!(model
+        .get_comment(0, 1, 1)
+        .unwrap()
+        .unwrap()
+        .replies
+        .is_empty());
+    model.undo().unwrap();
+    assert_eq!(model.get_comment(0, 1, 1), Ok(None));
+
+    model.redo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_comment(0, 1, 1).unwrap().unwrap().replies.len(),
+        1
+    );
+
+    model.delete_comment(0, 1, 1).unwrap();
+    assert!(model.get_comments(0).unwrap().is_empty());
+    model.undo().unwrap();
+    let comment = model.get_comment(0, 1, 1).unwrap().unwrap();
+    assert_eq!(comment.text, "Hello");
+    assert_eq!(comment.replies.len(), 1);
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = UserModel::from_model(new_empty_model());
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert_eq!(model2.get_comments(0), model.get_comments(0));
+}
+
+#[test]
+fn delete_row_and_column_with_comments() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 2, 2, "42").unwrap();
+    model.add_comment(0, 2, 2, "Note", "Jane", None).unwrap();
+    model.add_comment(0, 3, 2, "Below", "Jane", None).unwrap();
+
+    model.delete_row(0, 2).unwrap();
+    assert_eq!(model.get_comment(0, 2, 2).unwrap().unwrap().text, "Below");
+    model.undo().unwrap();
+    assert_eq!(model.get_comment(0, 2, 2).unwrap().unwrap().text, "Note");
+    assert_eq!(model.get_comment(0, 3, 2).unwrap().unwrap().text, "Below");
+
+    model.delete_column(0, 2).unwrap();
+    assert!(model.get_comments(0).unwrap().is_empty());
+    model.undo().unwrap();
+    assert_eq!(model.get_comments(0).unwrap().len(), 2);
+    assert_eq!(model.get_comment(0, 2, 2).unwrap().unwrap().text, "Note");
+
+    model.insert_row(0, 1).unwrap();
+    assert_eq!(model.get_comment(0, 3, 2).unwrap().unwrap().text, "Note");
+    model.undo().unwrap();
+    assert_eq!(model.get_comment(0, 2, 2).unwrap().unwrap().text, "Note");
+}
@@ -0,0 +1,116 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::types::{CellIsOperator, ConditionalFormat, ConditionalFormatRule, Dxf};
+use crate::UserModel;
+
+fn fill_color(model: &UserModel, row: i32) -> Option<String> {
+    model
+        .get_effective_cell_style(0, row, 1)
+        .unwrap()
+        .fill
+        .fg_color
+}
+
+#[test]
+fn add_set_and_delete() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 2, 1, "10").unwrap();
+    let mut conditional_format = ConditionalFormat {
+        sqref: "A1:A2".to_string(),
+        rule: ConditionalFormatRule::CellIs {
+            operator: CellIsOperator::GreaterThan,
+            formulas: vec!["5".to_string()],
+        },
+        style: Some(Dxf {
+            fill_color: Some("#FF0000".to_string()),
+            ..Default::default()
+        }),
+        stop_if_true: false,
+    };
+    model
+        .add_conditional_format(0, conditional_format.clone())
+        .unwrap();
+    assert_eq!(fill_color(&model, 1), None);
+    assert_eq!(fill_color(&model, 2), Some("#FF0000".to_string()));
+
+    // Changing a value updates the result
+    model.set_user_input(0, 1, 1, "6").unwrap();
+    assert_eq!(fill_color(&model, 1), Some("#FF0000".to_string()));
+
+    conditional_format.rule = ConditionalFormatRule::CellIs {
+        operator: CellIsOperator::LessThan,
+        formulas: vec!["8".to_string()],
+    };
+    model
+        .set_conditional_format(0, 0, conditional_format.clone())
+        .unwrap();
+    assert_eq!(fill_color(&model, 1), Some("#FF0000".to_string()));
+    assert_eq!(fill_color(&model, 2), None);
+
+    model.delete_conditional_format(0, 0).unwrap();
+    assert!(model.get_conditional_formats(0).unwrap().is_empty());
+    assert_eq!(fill_color(&model, 1), None);
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_conditional_formats(0).unwrap(),
+        vec![conditional_format]
+    );
+    assert_eq!(fill_color(&model, 1), Some("#FF0000".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(fill_color(&model, 2), Some("#FF0000".to_string()));
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(model.get_conditional_formats(0).unwrap().is_empty());
+    assert_eq!(fill_color(&model, 2), None);
+
+    model.redo().unwrap();
+    assert_eq!(fill_color(&model, 2), Some("#FF0000".to_string()));
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = UserModel::from_model(new_empty_model());
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert_eq!(model2.get_conditional_formats(0).unwrap().len(), 1);
+    assert_eq!(fill_color(&model2, 2), Some("#FF0000".to_string()));
+}
+
+#[test]
+fn insert_and_delete_rows() {
+    let mut model = UserModel::from_model(new_empty_model());
+    let conditional_format = ConditionalFormat {
+        sqref: "A1".to_string(),
+        rule: ConditionalFormatRule::Expression {
+            formula: "$B$3>0".to_string(),
+        },
+        style: None,
+        stop_if_true: false,
+    };
+    model
+        .add_conditional_format(0, conditional_format.clone())
+        .unwrap();
+
+    model.insert_row(0, 1).unwrap();
+    let conditional_formats = model.get_conditional_formats(0).unwrap();
+    assert_eq!(conditional_formats[0].sqref, "A2");
+    assert_eq!(
+        conditional_formats[0].rule,
+        ConditionalFormatRule::Expression {
+            formula: "$B$4>0".to_string()
+        }
+    );
+
+    // The row of the rule is deleted
+    model.delete_row(0, 2).unwrap();
+    assert!(model.get_conditional_formats(0).unwrap().is_empty());
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_conditional_formats(0).unwrap(),
+        vec![conditional_format]
+    );
+}
@@ -0,0 +1,229 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    cell::CellValue,
+    test::util::area,
+    types::{CellType, TextColumnFormat, TextSplit},
+    UserModel,
+};
+
+fn get_row(model: &UserModel, row: i32, columns: std::ops::RangeInclusive<i32>) -> Vec<String> {
+    columns
+        .map(|column| model.get_formatted_cell_value(0, row, column).unwrap())
+        .collect()
+}
+
+fn delimited(delimiters: &str) -> TextSplit {
+    TextSplit::Delimited {
+        delimiters: delimiters.to_string(),
+        treat_consecutive_as_one: false,
+        text_qualifier: Some('"'),
+    }
+}
+
+fn set_rows(model: &mut UserModel, rows: &[[&str; 3]]) {
+    for (row, values) in rows.iter().enumerate() {
+        for (column, value) in values.iter().enumerate() {
+            model
+                .set_user_input(0, row as i32 + 1, column as i32 + 1, value)
+                .unwrap();
+        }
+    }
+}
+
+#[test]
+fn remove_duplicates() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    set_rows(
+        &mut model,
+        &[
+            ["Name", "City", "Amount"],
+            ["Ann", "Paris", "1"],
+            ["Bob", "Rome", "2"],
+            ["ann", "Paris", "3"],
+            ["Carl", "Rome", "4"],
+            ["Bob", "Rome", "5"],
+        ],
+    );
+    model.set_user_input(0, 2, 4, "=C2*10").unwrap();
+    model.set_user_input(0, 5, 4, "=C5*10").unwrap();
+
+    let range = area(0, 1, 1, 3, 6);
+    assert_eq!(model.remove_duplicates(&range, &[1, 2], true), Ok(2));
+    assert_eq!(get_row(&model, 1, 1..=3), vec!["Name", "City", "Amount"]);
+    assert_eq!(get_row(&model, 2, 1..=3), vec!["Ann", "Paris", "1"]);
+    assert_eq!(get_row(&model, 3, 1..=3), vec!["Bob", "Rome", "2"]);
+    assert_eq!(get_row(&model, 4, 1..=3), vec!["Carl", "Rome", "4"]);
+    assert_eq!(get_row(&model, 5, 1..=3), vec!["", "", ""]);
+    assert_eq!(get_row(&model, 6, 1..=3), vec!["", "", ""]);
+    // Cells outside the range do not move
+    assert_eq!(model.get_cell_content(0, 5, 4), Ok("=C5*10".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 4),
+        Ok("10".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(get_row(&model, 4, 1..=3), vec!["ann", "Paris", "3"]);
+    assert_eq!(get_row(&model, 6, 1..=3), vec!["Bob", "Rome", "5"]);
+
+    // Only by the city
+    assert_eq!(model.remove_duplicates(&range, &[2], true), Ok(3));
+    assert_eq!(get_row(&model, 3, 1..=3), vec!["Bob", "Rome", "2"]);
+    assert_eq!(get_row(&model, 4, 1..=3), vec!["", "", ""]);
+
+    // Without header the first row is data
+    model.undo().unwrap();
+    assert_eq!(model.remove_duplicates(&range, &[2], false), Ok(3));
+    assert_eq!(get_row(&model, 1, 1..=3), vec!["Name", "City", "Amount"]);
+
+    assert_eq!(
+        model.remove_duplicates(&range, &[], true),
+        Err("At least one column is needed to find duplicates".to_string())
+    );
+    assert_eq!(
+        model.remove_duplicates(&range, &[5], true),
+        Err("Column 5 is not in the range".to_string())
+    );
+}
+
+#[test]
+fn remove_duplicates_keeps_text_that_looks_like_a_number() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    set_rows(
+        &mut model,
+        &[["'1", "a", "'007"], ["'1", "b", "1"], ["'2", "c", "'0.50"]],
+    );
+    assert_eq!(
+        model.remove_duplicates(&area(0, 1, 1, 3, 3), &[1], false),
+        Ok(1)
+    );
+    assert_eq!(model.get_cell_content(0, 2, 1), Ok("2".to_string()));
+    assert_eq!(model.get_cell_type(0, 2, 1), Ok(CellType::Text));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("0.50".to_string()));
+    assert_eq!(model.get_cell_type(0, 2, 3), Ok(CellType::Text));
+    assert_eq!(model.get_cell_type(0, 1, 3), Ok(CellType::Text));
+
+    model.undo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("0.50".to_string()));
+    assert_eq!(model.get_cell_type(0, 2, 3), Ok(CellType::Text));
+}
+
+#[test]
+fn text_to_columns_delimited() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model
+        .set_user_input(0, 1, 1, "Ann,1.5,\"Paris, France\"")
+        .unwrap();
+    model.set_user_input(0, 2, 1, "Bob,2,Rome").unwrap();
+    model.set_user_input(0, 3, 1, "=\"a,b\"").unwrap();
+    model.set_user_input(0, 4, 1, "Carl").unwrap();
+    model.set_user_input(0, 4, 2, "old").unwrap();
+
+    model
+        .text_to_columns(&area(0, 1, 1, 1, 4), &delimited(","), &[])
+        .unwrap();
+    assert_eq!(
+        get_row(&model, 1, 1..=3),
+        vec!["Ann", "1.5", "Paris, France"]
+    );
+    assert_eq!(get_row(&model, 2, 1..=3), vec!["Bob", "2", "Rome"]);
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("2".to_string()));
+    model.set_user_input(0, 5, 2, "=B1+B2").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 5, 2),
+        Ok("3.5".to_string())
+    );
+    // Formulas are not split
+    assert_eq!(model.get_cell_content(0, 3, 1), Ok("=\"a,b\"".to_string()));
+    // The columns of the other rows are replaced
+    assert_eq!(get_row(&model, 4, 1..=3), vec!["Carl", "", ""]);
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 1, 1),
+        Ok("Ann,1.5,\"Paris, France\"".to_string())
+    );
+    assert_eq!(model.get_cell_content(0, 1, 2), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 4, 2), Ok("old".to_string()));
+
+    model.redo().unwrap();
+    assert_eq!(
+        get_row(&model, 1, 1..=3),
+        vec!["Ann", "1.5", "Paris, France"]
+    );
+}
+
+#[test]
+fn text_to_columns_formats() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "007;;x;42;TRUE").unwrap();
+    let split = TextSplit::Delimited {
+        delimiters: ";".to_string(),
+        treat_consecutive_as_one: true,
+        text_qualifier: None,
+    };
+    let formats = [
+        TextColumnFormat::Text,
+        TextColumnFormat::Skip,
+        TextColumnFormat::General,
+    ];
+    model
+        .text_to_columns(&area(0, 1, 1, 1, 1), &split, &formats)
+        .unwrap();
+    // "007" is text, "x" skipped, 42 is a number and TRUE is text
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("007".to_string()));
+    model.set_user_input(0, 2, 1, "=ISTEXT(A1)").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("TRUE".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 2),
+        Ok("42".to_string())
+    );
+    model.set_user_input(0, 2, 2, "=ISNUMBER(B1)").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 2),
+        Ok("TRUE".to_string())
+    );
+    model.set_user_input(0, 2, 3, "=ISTEXT(C1)").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 3),
+        Ok("TRUE".to_string())
+    );
+}
+
+#[test]
+fn text_to_columns_fixed_width() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "AB123xyz").unwrap();
+    model.set_user_input(0, 2, 1, "CD4").unwrap();
+    let split = TextSplit::FixedWidth { breaks: vec![5, 2] };
+    model
+        .text_to_columns(&area(0, 1, 1, 1, 2), &split, &[])
+        .unwrap();
+    assert_eq!(get_row(&model, 1, 1..=3), vec!["AB", "123", "xyz"]);
+    assert_eq!(get_row(&model, 2, 1..=3), vec!["CD", "4", ""]);
+
+    assert_eq!(
+        model.text_to_columns(&area(0, 1, 1, 2, 2), &split, &[]),
+        Err("Text to columns works on a single column".to_string())
+    );
+}
+
+#[test]
+fn text_to_columns_locale() {
+    let mut model = UserModel::new_empty("model", "de", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "1.234,5|x").unwrap();
+    model
+        .text_to_columns(&area(0, 1, 1, 1, 1), &delimited("|"), &[])
+        .unwrap();
+    assert_eq!(
+        model.model.get_cell_value_by_index(0, 1, 1),
+        Ok(CellValue::Number(1234.5))
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2), Ok("x".to_string()));
+}
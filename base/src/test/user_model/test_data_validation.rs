@@ -0,0 +1,144 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::types::{CellIsOperator, DataValidation, DataValidationErrorStyle, DataValidationKind};
+use crate::UserModel;
+
+fn new_data_validation(error_style: DataValidationErrorStyle) -> DataValidation {
+    DataValidation {
+        sqref: "A1:A10".to_string(),
+        kind: DataValidationKind::Whole,
+        operator: CellIsOperator::LessThan,
+        formulas: vec!["10".to_string()],
+        allow_blank: true,
+        show_dropdown: false,
+        show_input_message: true,
+        input_title: Some("Quantity".to_string()),
+        input_message: Some("Less than 10".to_string()),
+        show_error_message: true,
+        error_style,
+        error_title: None,
+        error_message: Some("Too many".to_string()),
+    }
+}
+
+#[test]
+fn stop_rejects_input() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "5").unwrap();
+    model
+        .add_data_validation(0, new_data_validation(DataValidationErrorStyle::Stop))
+        .unwrap();
+    assert_eq!(
+        model
+            .get_data_validation_for_cell(0, 3, 1)
+            .unwrap()
+            .unwrap()
+            .input_message,
+        Some("Less than 10".to_string())
+    );
+
+    assert_eq!(
+        model.set_user_input(0, 1, 1, "12"),
+        Err("Too many".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("5".to_string()));
+    assert_eq!(
+        model.set_user_input(0, 2, 1, "=5*5"),
+        Err("Too many".to_string())
+    );
+    assert_eq!(model.get_cell_content(0, 2, 1), Ok("".to_string()));
+
+    model.set_user_input(0, 1, 1, "7").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("7".to_string()));
+
+    // The rejected inputs are not in the history
+    model.undo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("5".to_string()));
+    model.undo().unwrap();
+    assert!(model.get_data_validations(0).unwrap().is_empty());
+    model.set_user_input(0, 1, 1, "12").unwrap();
+}
+
+#[test]
+fn warning_accepts_input() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model
+        .add_data_validation(0, new_data_validation(DataValidationErrorStyle::Warning))
+        .unwrap();
+    model.set_user_input(0, 1, 1, "12").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("12".to_string())
+    );
+    let data_validation = model.check_data_validation(0, 1, 1).unwrap().unwrap();
+    assert_eq!(
+        data_validation.error_style,
+        DataValidationErrorStyle::Warning
+    );
+    assert_eq!(data_validation.error_message, Some("Too many".to_string()));
+}
+
+#[test]
+fn set_and_delete() {
+    let mut model = UserModel::from_model(new_empty_model());
+    let mut data_validation = new_data_validation(DataValidationErrorStyle::Stop);
+    model
+        .add_data_validation(0, data_validation.clone())
+        .unwrap();
+    data_validation.kind = DataValidationKind::List;
+    data_validation.show_dropdown = true;
+    data_validation.formulas = vec!["\"a,b\"".to_string()];
+    model
+        .set_data_validation(0, 0, data_validation.clone())
+        .unwrap();
+    assert_eq!(
+        model.get_data_validation_options(0, 1, 1),
+        Ok(vec!["a".to_string(), "b".to_string()])
+    );
+    model.delete_data_validation(0, 0).unwrap();
+    assert_eq!(model.get_data_validation_options(0, 1, 1), Ok(vec![]));
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_data_validations(0).unwrap(),
+        vec![data_validation.clone()]
+    );
+    model.undo().unwrap();
+    assert_eq!(model.get_data_validation_options(0, 1, 1), Ok(vec![]));
+    model.redo().unwrap();
+    model.redo().unwrap();
+    assert!(model.get_data_validations(0).unwrap().is_empty());
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = UserModel::from_model(new_empty_model());
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert!(model2.get_data_validations(0).unwrap().is_empty());
+}
+
+#[test]
+fn insert_and_delete_columns() {
+    let mut model = UserModel::from_model(new_empty_model());
+    let data_validation = DataValidation {
+        formulas: vec!["$C$1".to_string()],
+        ..new_data_validation(DataValidationErrorStyle::Stop)
+    };
+    model
+        .add_data_validation(0, data_validation.clone())
+        .unwrap();
+
+    model.insert_column(0, 1).unwrap();
+    let data_validations = model.get_data_validations(0).unwrap();
+    assert_eq!(data_validations[0].sqref, "B1:B10");
+    assert_eq!(data_validations[0].formulas, vec!["$D$1".to_string()]);
+
+    model.delete_column(0, 2).unwrap();
+    assert!(model.get_data_validations(0).unwrap().is_empty());
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_data_validations(0).unwrap(),
+        vec![data_validation]
+    );
+}
@@ -0,0 +1,45 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+use crate::UserModel;
+
+#[test]
+fn set_date_1904() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "=YEAR(100)").unwrap();
+    assert!(!model.is_date_1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("1900".to_string())
+    );
+
+    model.set_date_1904(true);
+    assert!(model.is_date_1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("1904".to_string())
+    );
+
+    model.undo().unwrap();
+    assert!(!model.is_date_1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("1900".to_string())
+    );
+
+    model.redo().unwrap();
+    assert!(model.is_date_1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("1904".to_string())
+    );
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = UserModel::from_model(new_empty_model());
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert!(model2.is_date_1904());
+    assert_eq!(
+        model2.get_formatted_cell_value(0, 1, 1),
+        Ok("1904".to_string())
+    );
+}
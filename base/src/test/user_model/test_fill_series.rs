@@ -0,0 +1,297 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::Area;
+use crate::test::util::new_empty_model;
+use crate::types::SeriesType;
+use crate::UserModel;
+
+fn column_area(row: i32, column: i32, height: i32) -> Area {
+    Area {
+        sheet: 0,
+        row,
+        column,
+        width: 1,
+        height,
+    }
+}
+
+fn get_column(model: &UserModel, column: i32, rows: std::ops::RangeInclusive<i32>) -> Vec<String> {
+    rows.map(|row| model.get_formatted_cell_value(0, row, column).unwrap())
+        .collect()
+}
+
+#[test]
+fn linear_numbers() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 2, 1, "3").unwrap();
+    model.set_user_input(0, 3, 1, "5").unwrap();
+    model.auto_fill_rows(&column_area(1, 1, 3), 6).unwrap();
+    assert_eq!(get_column(&model, 1, 4..=6), vec!["7", "9", "11"]);
+
+    // Not evenly spaced values follow the trend
+    model.set_user_input(0, 1, 2, "1").unwrap();
+    model.set_user_input(0, 2, 2, "2").unwrap();
+    model.set_user_input(0, 3, 2, "4.5").unwrap();
+    model.auto_fill_rows(&column_area(1, 2, 3), 4).unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2).unwrap(), "6");
+
+    // Decimal steps do not accumulate rounding errors
+    model.set_user_input(0, 1, 3, "0.1").unwrap();
+    model.set_user_input(0, 2, 3, "0.2").unwrap();
+    model.auto_fill_rows(&column_area(1, 3, 2), 10).unwrap();
+    assert_eq!(model.get_cell_content(0, 10, 3).unwrap(), "1");
+}
+
+#[test]
+fn fill_upwards_and_left() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 5, 1, "10").unwrap();
+    model.set_user_input(0, 6, 1, "20").unwrap();
+    model.auto_fill_rows(&column_area(5, 1, 2), 3).unwrap();
+    assert_eq!(get_column(&model, 1, 3..=4), vec!["-10", "0"]);
+
+    model.set_user_input(0, 1, 5, "Mon").unwrap();
+    model
+        .auto_fill_columns(
+            &Area {
+                sheet: 0,
+                row: 1,
+                column: 5,
+                width: 1,
+                height: 1,
+            },
+            3,
+        )
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 4).unwrap(), "Sun");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 3).unwrap(), "Sat");
+}
+
+#[test]
+fn growth_series() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "2").unwrap();
+    model.set_user_input(0, 2, 1, "4").unwrap();
+    model.set_user_input(0, 3, 1, "8").unwrap();
+    model.auto_fill_rows(&column_area(1, 1, 3), 5).unwrap();
+    assert_eq!(get_column(&model, 1, 4..=5), vec!["16", "32"]);
+}
+
+#[test]
+fn dates() {
+    let mut model = UserModel::from_model(new_empty_model());
+    // A single date steps by day
+    model.set_user_input(0, 1, 1, "2024-02-28").unwrap();
+    model.auto_fill_rows(&column_area(1, 1, 1), 3).unwrap();
+    assert_eq!(
+        get_column(&model, 1, 2..=3),
+        vec!["2024-02-29", "2024-03-01"]
+    );
+
+    // Same day of the month steps by month, clamped to the end of the month
+    model.set_user_input(0, 1, 2, "2024-01-31").unwrap();
+    model.set_user_input(0, 2, 2, "2024-03-31").unwrap();
+    model.auto_fill_rows(&column_area(1, 2, 2), 4).unwrap();
+    assert_eq!(
+        get_column(&model, 2, 3..=4),
+        vec!["2024-05-31", "2024-07-31"]
+    );
+
+    model.set_user_input(0, 1, 3, "2020-02-29").unwrap();
+    model.set_user_input(0, 2, 3, "2021-02-28").unwrap();
+    model.auto_fill_rows(&column_area(1, 3, 2), 3).unwrap();
+    // Not the same day of the month, it steps by 365 days
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 3).unwrap(),
+        "2022-02-28"
+    );
+
+    model.set_user_input(0, 1, 4, "2020-03-15").unwrap();
+    model.set_user_input(0, 2, 4, "2022-03-15").unwrap();
+    model.auto_fill_rows(&column_area(1, 4, 2), 3).unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 4).unwrap(),
+        "2024-03-15"
+    );
+}
+
+#[test]
+fn day_and_month_names() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Friday").unwrap();
+    model.auto_fill_rows(&column_area(1, 1, 1), 4).unwrap();
+    assert_eq!(
+        get_column(&model, 1, 2..=4),
+        vec!["Saturday", "Sunday", "Monday"]
+    );
+
+    model.set_user_input(0, 1, 2, "JAN").unwrap();
+    model.set_user_input(0, 2, 2, "APR").unwrap();
+    model.auto_fill_rows(&column_area(1, 2, 2), 5).unwrap();
+    assert_eq!(get_column(&model, 2, 3..=5), vec!["JUL", "OCT", "JAN"]);
+}
+
+#[test]
+fn text_with_numbers() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Item 9").unwrap();
+    model.auto_fill_rows(&column_area(1, 1, 1), 3).unwrap();
+    assert_eq!(get_column(&model, 1, 2..=3), vec!["Item 10", "Item 11"]);
+
+    model.set_user_input(0, 1, 2, "Q01").unwrap();
+    model.set_user_input(0, 2, 2, "Q03").unwrap();
+    model.auto_fill_rows(&column_area(1, 2, 2), 4).unwrap();
+    assert_eq!(get_column(&model, 2, 3..=4), vec!["Q05", "Q07"]);
+
+    // Different prefixes are repeated
+    model.set_user_input(0, 1, 3, "A1").unwrap();
+    model.set_user_input(0, 2, 3, "B2").unwrap();
+    model.auto_fill_rows(&column_area(1, 3, 2), 4).unwrap();
+    assert_eq!(get_column(&model, 3, 3..=4), vec!["A1", "B2"]);
+}
+
+#[test]
+fn formulas_are_copied() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 2, 1, "=A1+1").unwrap();
+    model.auto_fill_rows(&column_area(1, 1, 2), 4).unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 1).unwrap(), "1");
+    assert_eq!(model.get_cell_content(0, 4, 1).unwrap(), "=A3+1");
+}
+
+#[test]
+fn fill_series_with_stop() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model
+        .fill_series(
+            &column_area(1, 1, 1),
+            true,
+            SeriesType::Linear,
+            4.0,
+            Some(10.0),
+        )
+        .unwrap();
+    assert_eq!(get_column(&model, 1, 1..=4), vec!["1", "5", "9", ""]);
+
+    model.set_user_input(0, 1, 2, "3").unwrap();
+    model
+        .fill_series(&column_area(1, 2, 5), true, SeriesType::Growth, 2.0, None)
+        .unwrap();
+    assert_eq!(
+        get_column(&model, 2, 1..=5),
+        vec!["3", "6", "12", "24", "48"]
+    );
+
+    // Decreasing series
+    model.set_user_input(0, 1, 3, "10").unwrap();
+    model
+        .fill_series(
+            &column_area(1, 3, 1),
+            true,
+            SeriesType::Linear,
+            -5.0,
+            Some(0.0),
+        )
+        .unwrap();
+    assert_eq!(get_column(&model, 3, 1..=4), vec!["10", "5", "0", ""]);
+
+    assert_eq!(
+        model.fill_series(&column_area(1, 3, 1), true, SeriesType::Linear, 0.0, None),
+        Err("The step of a series cannot be 0".to_string())
+    );
+}
+
+#[test]
+fn fill_series_dates() {
+    let mut model = UserModel::from_model(new_empty_model());
+    // A Friday
+    model.set_user_input(0, 1, 1, "2024-03-01").unwrap();
+    model
+        .fill_series(
+            &Area {
+                sheet: 0,
+                row: 1,
+                column: 1,
+                width: 4,
+                height: 1,
+            },
+            false,
+            SeriesType::Weekday,
+            1.0,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 2).unwrap(),
+        "2024-03-04"
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 4).unwrap(),
+        "2024-03-06"
+    );
+
+    model.set_user_input(0, 2, 1, "2024-01-31").unwrap();
+    model
+        .fill_series(&column_area(2, 1, 3), true, SeriesType::Month, 1.0, None)
+        .unwrap();
+    assert_eq!(
+        get_column(&model, 1, 3..=4),
+        vec!["2024-02-29", "2024-03-31"]
+    );
+}
+
+#[test]
+fn dates_out_of_range() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "2024-03-01").unwrap();
+    model
+        .fill_series(&column_area(1, 1, 3), true, SeriesType::Day, 1e9, None)
+        .unwrap();
+    assert_eq!(get_column(&model, 1, 2..=3), vec!["#NUM!", "#NUM!"]);
+
+    model.set_user_input(0, 1, 2, "2024-03-01").unwrap();
+    model
+        .fill_series(&column_area(1, 2, 2), true, SeriesType::Weekday, 1e9, None)
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 2, 2).unwrap(), "#NUM!");
+
+    // 31 December 9999 is the last date
+    model.set_user_input(0, 1, 3, "9999-12-30").unwrap();
+    model
+        .fill_series(&column_area(1, 3, 3), true, SeriesType::Day, 1.0, None)
+        .unwrap();
+    assert_eq!(get_column(&model, 3, 2..=3), vec!["9999-12-31", "#NUM!"]);
+
+    // Numbers formatted as dates past the last date are not dates
+    model.set_user_input(0, 1, 4, "2024-03-01").unwrap();
+    model.set_user_input(0, 2, 4, "2024-03-02").unwrap();
+    model.set_user_input(0, 1, 4, "1000000000").unwrap();
+    model.set_user_input(0, 2, 4, "1000000001").unwrap();
+    model.auto_fill_rows(&column_area(1, 4, 2), 3).unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 4).unwrap(), "1000000002");
+}
+
+#[test]
+fn undo_redo() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Item 1").unwrap();
+    model.set_user_input(0, 1, 2, "2").unwrap();
+    model.auto_fill_rows(&column_area(1, 1, 1), 3).unwrap();
+    model
+        .fill_series(&column_area(1, 2, 3), true, SeriesType::Linear, 2.0, None)
+        .unwrap();
+    assert_eq!(get_column(&model, 2, 1..=3), vec!["2", "4", "6"]);
+
+    model.undo().unwrap();
+    assert_eq!(get_column(&model, 2, 1..=3), vec!["2", "", ""]);
+    model.undo().unwrap();
+    assert_eq!(get_column(&model, 1, 1..=3), vec!["Item 1", "", ""]);
+    model.redo().unwrap();
+    assert_eq!(
+        get_column(&model, 1, 1..=3),
+        vec!["Item 1", "Item 2", "Item 3"]
+    );
+}
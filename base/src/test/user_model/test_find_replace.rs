@@ -0,0 +1,265 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::CellReferenceIndex;
+use crate::test::util::new_empty_model;
+use crate::types::{FindLookIn, FindMode, FindOptions, FindWithin, SearchOrder};
+use crate::UserModel;
+
+fn cell(sheet: u32, row: i32, column: i32) -> CellReferenceIndex {
+    CellReferenceIndex { sheet, row, column }
+}
+
+fn new_model() -> UserModel {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Apple").unwrap();
+    model.set_user_input(0, 1, 3, "pineapple").unwrap();
+    model.set_user_input(0, 2, 1, "Banana").unwrap();
+    model.set_user_input(0, 3, 2, "apple pie").unwrap();
+    model.set_user_input(0, 4, 1, "=A1&\"s\"").unwrap();
+    model
+}
+
+#[test]
+fn find_all_options() {
+    let model = new_model();
+    let options = FindOptions::default();
+    assert_eq!(
+        model.find_all("apple", &options),
+        Ok(vec![cell(0, 1, 1), cell(0, 1, 3), cell(0, 3, 2)])
+    );
+
+    let options = FindOptions {
+        look_in: FindLookIn::Values,
+        ..Default::default()
+    };
+    assert_eq!(
+        model.find_all("apple", &options),
+        Ok(vec![
+            cell(0, 1, 1),
+            cell(0, 1, 3),
+            cell(0, 3, 2),
+            cell(0, 4, 1)
+        ])
+    );
+
+    let options = FindOptions {
+        match_case: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        model.find_all("apple", &options),
+        Ok(vec![cell(0, 1, 3), cell(0, 3, 2)])
+    );
+
+    let options = FindOptions {
+        match_entire_cell: true,
+        ..Default::default()
+    };
+    assert_eq!(model.find_all("apple", &options), Ok(vec![cell(0, 1, 1)]));
+
+    let options = FindOptions {
+        search_order: SearchOrder::ByColumns,
+        ..Default::default()
+    };
+    assert_eq!(
+        model.find_all("apple", &options),
+        Ok(vec![cell(0, 1, 1), cell(0, 3, 2), cell(0, 1, 3)])
+    );
+
+    // Formulas are searched by default
+    assert_eq!(
+        model.find_all("A1&", &FindOptions::default()),
+        Ok(vec![cell(0, 4, 1)])
+    );
+}
+
+#[test]
+fn wildcards_and_regex() {
+    let model = new_model();
+    let options = FindOptions {
+        mode: FindMode::Wildcard,
+        match_entire_cell: true,
+        ..Default::default()
+    };
+    assert_eq!(model.find_all("?anana", &options), Ok(vec![cell(0, 2, 1)]));
+    assert_eq!(
+        model.find_all("*apple", &options),
+        Ok(vec![cell(0, 1, 1), cell(0, 1, 3)])
+    );
+
+    let options = FindOptions {
+        mode: FindMode::Wildcard,
+        match_case: true,
+        ..Default::default()
+    };
+    assert_eq!(model.find_all("A*e", &options), Ok(vec![cell(0, 1, 1)]));
+    assert_eq!(model.find_all("p~?", &options), Ok(vec![]));
+
+    // Unlike in formulas, wildcards in find match line breaks
+    let mut lines = UserModel::from_model(new_empty_model());
+    lines.set_user_input(0, 1, 1, "First\nSecond").unwrap();
+    let options = FindOptions {
+        mode: FindMode::Wildcard,
+        match_entire_cell: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        lines.find_all("first*second", &options),
+        Ok(vec![cell(0, 1, 1)])
+    );
+
+    let options = FindOptions {
+        mode: FindMode::Regex,
+        ..Default::default()
+    };
+    assert_eq!(
+        model.find_all("^(apple|banana)$", &options),
+        Ok(vec![cell(0, 1, 1), cell(0, 2, 1)])
+    );
+    assert_eq!(
+        model.find_all("(apple", &options),
+        Err("Invalid regular expression: '(apple'".to_string())
+    );
+    assert_eq!(
+        model.find_all("", &options),
+        Err("The text to find cannot be empty".to_string())
+    );
+
+    // In text mode special characters are literal
+    model.find_all("(apple", &FindOptions::default()).unwrap();
+}
+
+#[test]
+fn find_next_and_previous() {
+    let mut model = new_model();
+    let options = FindOptions::default();
+    model.set_selected_cell(1, 1).unwrap();
+    assert_eq!(model.find_next("apple", &options), Ok(Some(cell(0, 1, 3))));
+    assert_eq!(model.get_selected_cell(), (0, 1, 3));
+    assert_eq!(model.find_next("apple", &options), Ok(Some(cell(0, 3, 2))));
+    // Wraps around
+    assert_eq!(model.find_next("apple", &options), Ok(Some(cell(0, 1, 1))));
+    assert_eq!(
+        model.find_previous("apple", &options),
+        Ok(Some(cell(0, 3, 2)))
+    );
+    assert_eq!(
+        model.find_previous("apple", &options),
+        Ok(Some(cell(0, 1, 3)))
+    );
+    assert_eq!(model.find_next("cherry", &options), Ok(None));
+    assert_eq!(model.get_selected_cell(), (0, 1, 3));
+}
+
+#[test]
+fn find_in_selection_and_workbook() {
+    let mut model = new_model();
+    model.new_sheet().unwrap();
+    model.set_user_input(1, 5, 5, "Apple").unwrap();
+
+    model.set_selected_sheet(0).unwrap();
+    model.set_selected_cell(2, 1).unwrap();
+    model.set_selected_range(2, 1, 3, 2).unwrap();
+    let options = FindOptions {
+        within: FindWithin::Selection,
+        ..Default::default()
+    };
+    assert_eq!(model.find_all("apple", &options), Ok(vec![cell(0, 3, 2)]));
+    assert_eq!(model.find_next("apple", &options), Ok(Some(cell(0, 3, 2))));
+    // The selected range is kept
+    assert_eq!(model.get_selected_view().range, [2, 1, 3, 2]);
+
+    let options = FindOptions {
+        within: FindWithin::Workbook,
+        ..Default::default()
+    };
+    assert_eq!(
+        model.find_all("apple", &options),
+        Ok(vec![
+            cell(0, 1, 1),
+            cell(0, 1, 3),
+            cell(0, 3, 2),
+            cell(1, 5, 5)
+        ])
+    );
+    assert_eq!(model.find_next("apple", &options), Ok(Some(cell(1, 5, 5))));
+    assert_eq!(model.get_selected_sheet(), 1);
+    assert_eq!(model.find_next("apple", &options), Ok(Some(cell(0, 1, 1))));
+    assert_eq!(model.get_selected_sheet(), 0);
+}
+
+#[test]
+fn replace_all_is_one_undo_step() {
+    let mut model = new_model();
+    let options = FindOptions::default();
+    assert_eq!(model.replace_all("apple", "pear", &options), Ok(3));
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("pear".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("pinepear".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("pear pie".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 4, 1),
+        Ok("pears".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("Apple".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("pineapple".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("apple pie".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 4, 1),
+        Ok("Apples".to_string())
+    );
+
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("pinepear".to_string()));
+}
+
+#[test]
+fn replace_formulas_and_groups() {
+    let mut model = new_model();
+    let options = FindOptions::default();
+    assert_eq!(model.replace_all("A1", "C1", &options), Ok(1));
+    assert_eq!(model.get_cell_content(0, 4, 1), Ok("=C1&\"s\"".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 4, 1),
+        Ok("pineapples".to_string())
+    );
+
+    let options = FindOptions {
+        mode: FindMode::Regex,
+        match_case: true,
+        ..Default::default()
+    };
+    assert_eq!(model.replace_all("(\\w+) pie", "$1 tart", &options), Ok(1));
+    assert_eq!(
+        model.get_cell_content(0, 3, 2),
+        Ok("apple tart".to_string())
+    );
+
+    // In text mode the replacement is literal
+    let options = FindOptions::default();
+    assert_eq!(model.replace_all("tart", "$1", &options), Ok(1));
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("apple $1".to_string()));
+}
+
+#[test]
+fn replace_selected_cell() {
+    let mut model = new_model();
+    let options = FindOptions::default();
+    model.set_selected_cell(1, 1).unwrap();
+    assert_eq!(
+        model.replace("apple", "pear", &options),
+        Ok(Some(cell(0, 1, 3)))
+    );
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("pear".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("pineapple".to_string()));
+
+    // The selected cell does not match, only the next cell is selected
+    model.set_selected_cell(2, 1).unwrap();
+    assert_eq!(
+        model.replace("apple", "pear", &options),
+        Ok(Some(cell(0, 3, 2)))
+    );
+    assert_eq!(model.get_cell_content(0, 2, 1), Ok("Banana".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("apple pie".to_string()));
+}
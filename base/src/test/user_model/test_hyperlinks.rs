@@ -0,0 +1,88 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{test::util::new_empty_model, UserModel};
+
+#[test]
+fn set_and_delete_undo_redo() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model
+        .set_hyperlink(
+            0,
+            1,
+            1,
+            Some("https://ironcalc.com".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    model
+        .set_hyperlink(0, 1, 1, None, Some("Sheet1!D4".to_string()), None)
+        .unwrap();
+    model.delete_hyperlink(0, 1, 1).unwrap();
+    assert_eq!(model.get_hyperlink(0, 1, 1).unwrap(), None);
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_hyperlink(0, 1, 1).unwrap().unwrap().location,
+        Some("Sheet1!D4".to_string())
+    );
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_hyperlink(0, 1, 1).unwrap().unwrap().url,
+        Some("https://ironcalc.com".to_string())
+    );
+    model.undo().unwrap();
+    assert_eq!(model.get_hyperlinks(0).unwrap(), vec![]);
+
+    model.redo().unwrap();
+    model.redo().unwrap();
+    let hyperlink = model.get_hyperlink(0, 1, 1).unwrap().unwrap();
+    assert_eq!(hyperlink.url, None);
+    assert_eq!(hyperlink.location, Some("Sheet1!D4".to_string()));
+    model.redo().unwrap();
+    assert_eq!(model.get_hyperlink(0, 1, 1).unwrap(), None);
+}
+
+#[test]
+fn delete_row_and_column_undo() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model
+        .set_hyperlink(
+            0,
+            3,
+            2,
+            Some("https://ironcalc.com".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    model.delete_row(0, 3).unwrap();
+    assert_eq!(model.get_hyperlinks(0).unwrap(), vec![]);
+    model.undo().unwrap();
+    assert!(model.get_hyperlink(0, 3, 2).unwrap().is_some());
+
+    model.delete_column(0, 2).unwrap();
+    assert_eq!(model.get_hyperlinks(0).unwrap(), vec![]);
+    model.undo().unwrap();
+    assert!(model.get_hyperlink(0, 3, 2).unwrap().is_some());
+}
+
+#[test]
+fn hyperlink_formula() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model
+        .set_user_input(
+            0,
+            1,
+            1,
+            "=HYPERLINK(\"https://ironcalc.com\", \"IronCalc\")",
+        )
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "IronCalc");
+    assert_eq!(
+        model.get_cell_hyperlink(0, 1, 1).unwrap().unwrap().url,
+        Some("https://ironcalc.com".to_string())
+    );
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_hyperlink(0, 1, 1).unwrap(), None);
+}
@@ -0,0 +1,63 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{test::util::new_empty_model, UserModel};
+
+#[test]
+fn formulas_in_display_language() {
+    let mut model = UserModel::new_empty("model", "de", "UTC").unwrap();
+    model.set_language("de").unwrap();
+    model.set_user_input(0, 1, 1, "3").unwrap();
+    model.set_user_input(0, 1, 2, "=SUMME(A1;0,5)").unwrap();
+
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 2),
+        Ok("3.5".to_string())
+    );
+    assert_eq!(
+        model.get_cell_content(0, 1, 2),
+        Ok("=SUMME(A1;0,5)".to_string())
+    );
+
+    // The argument and decimal separators depend on the locale of the workbook
+    model.set_language("en").unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 1, 2),
+        Ok("=SUM(A1;0,5)".to_string())
+    );
+}
+
+#[test]
+fn diffs_are_language_independent() {
+    let mut model1 = UserModel::from_model(new_empty_model());
+    model1.set_language("es").unwrap();
+    model1
+        .set_user_input(0, 1, 1, "=SI(VERDADERO,1,2)")
+        .unwrap();
+    model1.set_user_input(0, 2, 1, "=SUMA(A1:A1)").unwrap();
+    let send_queue = model1.flush_send_queue();
+
+    let mut model2 = UserModel::from_model(new_empty_model());
+    model2.set_language("fr").unwrap();
+    model2.apply_external_diffs(&send_queue).unwrap();
+
+    assert_eq!(
+        model2.get_formatted_cell_value(0, 2, 1),
+        Ok("1".to_string())
+    );
+    assert_eq!(
+        model2.get_cell_content(0, 1, 1),
+        Ok("=SI(VRAI,1,2)".to_string())
+    );
+    assert_eq!(
+        model2.get_cell_content(0, 2, 1),
+        Ok("=SOMME(A1:A1)".to_string())
+    );
+
+    model1.undo().unwrap();
+    assert_eq!(model1.get_cell_content(0, 2, 1), Ok("".to_string()));
+    model1.redo().unwrap();
+    assert_eq!(
+        model1.get_cell_content(0, 2, 1),
+        Ok("=SUMA(A1:A1)".to_string())
+    );
+}
@@ -0,0 +1,158 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::{area, new_empty_model};
+use crate::UserModel;
+
+#[test]
+fn merge_and_unmerge() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 2, 2, "Hello").unwrap();
+    model.set_user_input(0, 2, 3, "World").unwrap();
+    model.set_user_input(0, 1, 1, "=C2").unwrap();
+    model.merge_cells(&area(0, 2, 2, 2, 2)).unwrap();
+
+    assert_eq!(model.get_merged_cells(0), Ok(vec!["B2:C3".to_string()]));
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("Hello".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("0".to_string()));
+
+    model.undo().unwrap();
+    assert!(model.get_merged_cells(0).unwrap().is_empty());
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("World".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("World".to_string())
+    );
+
+    model.redo().unwrap();
+    assert_eq!(model.get_merged_cells(0), Ok(vec!["B2:C3".to_string()]));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("".to_string()));
+
+    // Merging a larger area absorbs the existing one
+    model.merge_cells(&area(0, 1, 2, 3, 4)).unwrap();
+    assert_eq!(model.get_merged_cells(0), Ok(vec!["B1:D4".to_string()]));
+    model.undo().unwrap();
+    assert_eq!(model.get_merged_cells(0), Ok(vec!["B2:C3".to_string()]));
+
+    assert_eq!(
+        model.merge_cells(&area(0, 3, 3, 2, 2)),
+        Err("The range partially overlaps a merged area".to_string())
+    );
+
+    // Unmerging any cell of the area unmerges the whole area
+    model.unmerge_cells(&area(0, 3, 3, 1, 1)).unwrap();
+    assert!(model.get_merged_cells(0).unwrap().is_empty());
+    model.undo().unwrap();
+    assert_eq!(model.get_merged_cells(0), Ok(vec!["B2:C3".to_string()]));
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = UserModel::from_model(new_empty_model());
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert_eq!(model2.get_merged_cells(0), Ok(vec!["B2:C3".to_string()]));
+    assert_eq!(model2.get_cell_content(0, 2, 2), Ok("Hello".to_string()));
+}
+
+#[test]
+fn navigation_and_selection() {
+    let mut model = UserModel::from_model(new_empty_model());
+    // B2:C3
+    model.merge_cells(&area(0, 2, 2, 2, 2)).unwrap();
+
+    model.set_selected_cell(3, 3).unwrap();
+    let view = model.get_selected_view();
+    assert_eq!((view.row, view.column), (2, 2));
+    assert_eq!(view.range, [2, 2, 3, 3]);
+
+    // We jump over the merged area
+    model.on_arrow_right().unwrap();
+    let view = model.get_selected_view();
+    assert_eq!((view.row, view.column), (2, 4));
+    assert_eq!(view.range, [2, 4, 2, 4]);
+
+    model.on_arrow_left().unwrap();
+    let view = model.get_selected_view();
+    assert_eq!((view.row, view.column), (2, 2));
+    assert_eq!(view.range, [2, 2, 3, 3]);
+
+    model.on_arrow_down().unwrap();
+    let view = model.get_selected_view();
+    assert_eq!((view.row, view.column), (4, 2));
+
+    model.on_arrow_up().unwrap();
+    let view = model.get_selected_view();
+    assert_eq!((view.row, view.column), (2, 2));
+    assert_eq!(view.range, [2, 2, 3, 3]);
+
+    // A range that cuts through the merged area is expanded
+    model.set_selected_cell(1, 1).unwrap();
+    model.set_selected_range(1, 1, 2, 2).unwrap();
+    assert_eq!(model.get_selected_view().range, [1, 1, 3, 3]);
+
+    model.on_area_selecting(2, 2).unwrap();
+    assert_eq!(model.get_selected_view().range, [1, 1, 3, 3]);
+
+    // Expanding the selection from the merged area
+    model.set_selected_cell(2, 2).unwrap();
+    model.on_expand_selected_range("ArrowRight").unwrap();
+    assert_eq!(model.get_selected_view().range, [2, 2, 3, 4]);
+    model.on_expand_selected_range("ArrowLeft").unwrap();
+    assert_eq!(model.get_selected_view().range, [2, 2, 3, 3]);
+}
+
+#[test]
+fn clear_and_style() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 2, 2, "42").unwrap();
+    model.merge_cells(&area(0, 2, 2, 2, 2)).unwrap();
+
+    // Styling part of the merged area styles all of it
+    model
+        .update_range_style(&area(0, 3, 3, 1, 1), "font.b", "true")
+        .unwrap();
+    assert!(model.get_cell_style(0, 2, 2).unwrap().font.b);
+
+    model.range_clear_contents(&area(0, 3, 3, 1, 1)).unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("".to_string()));
+}
+
+#[test]
+fn copy_and_paste() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Title").unwrap();
+    model.merge_cells(&area(0, 1, 1, 2, 1)).unwrap();
+    // Something merged in the target area
+    model.merge_cells(&area(0, 5, 2, 1, 2)).unwrap();
+
+    model.set_selected_range(1, 1, 2, 2).unwrap();
+    let copy = model.copy_to_clipboard().unwrap();
+    model.set_selected_cell(5, 1).unwrap();
+    model
+        .paste_from_clipboard((1, 1, 2, 2), &copy.data, false)
+        .unwrap();
+    assert_eq!(model.get_cell_content(0, 5, 1), Ok("Title".to_string()));
+    assert_eq!(
+        model.get_merged_cells(0),
+        Ok(vec!["A1:B1".to_string(), "A5:B5".to_string()])
+    );
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_merged_cells(0),
+        Ok(vec!["A1:B1".to_string(), "B5:B6".to_string()])
+    );
+
+    // Cutting moves the merged area
+    model.set_selected_cell(1, 1).unwrap();
+    model.set_selected_range(1, 1, 2, 2).unwrap();
+    let copy = model.copy_to_clipboard().unwrap();
+    model.set_selected_cell(8, 1).unwrap();
+    model
+        .paste_from_clipboard((1, 1, 2, 2), &copy.data, true)
+        .unwrap();
+    assert_eq!(
+        model.get_merged_cells(0),
+        Ok(vec!["B5:B6".to_string(), "A8:B8".to_string()])
+    );
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 8, 1), Ok("Title".to_string()));
+}
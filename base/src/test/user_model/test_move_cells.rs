@@ -0,0 +1,352 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::constants::{LAST_COLUMN, LAST_ROW};
+use crate::test::util::{area, new_empty_model};
+use crate::types::{
+    CellIsOperator, ColumnFilter, ConditionalFormat, ConditionalFormatRule, DataValidation,
+    DataValidationErrorStyle, DataValidationKind,
+};
+use crate::UserModel;
+
+fn get_values(model: &UserModel, cells: &[(i32, i32)]) -> Vec<String> {
+    cells
+        .iter()
+        .map(|(row, column)| model.get_formatted_cell_value(0, *row, *column).unwrap())
+        .collect()
+}
+
+#[test]
+fn move_rows_down() {
+    let mut model = UserModel::from_model(new_empty_model());
+    for row in 1..=5 {
+        model.set_user_input(0, row, 1, &format!("{row}")).unwrap();
+    }
+    model.set_user_input(0, 1, 3, "=A2*10").unwrap();
+    model.set_user_input(0, 2, 3, "=SUM(A1:A5)").unwrap();
+    model.set_user_input(0, 1, 4, "=C1+A4").unwrap();
+    model
+        .update_range_style(&area(0, 2, 1, 1, 1), "font.b", "true")
+        .unwrap();
+    model.set_row_height(0, 2, 40.0).unwrap();
+
+    // Rows 1 and 2 go below row 4
+    model.move_rows(0, 1, 2, 2).unwrap();
+    let column_a = [(1, 1), (2, 1), (3, 1), (4, 1), (5, 1)];
+    assert_eq!(get_values(&model, &column_a), vec!["3", "4", "1", "2", "5"]);
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "=A4*10");
+    assert_eq!(model.get_cell_content(0, 4, 3).unwrap(), "=SUM(A1:A5)");
+    assert_eq!(model.get_cell_content(0, 3, 4).unwrap(), "=C3+A2");
+    assert_eq!(model.get_formatted_cell_value(0, 3, 4).unwrap(), "24");
+    assert!(model.get_cell_style(0, 4, 1).unwrap().font.b);
+    assert!(!model.get_cell_style(0, 2, 1).unwrap().font.b);
+    assert_eq!(model.get_row_height(0, 4).unwrap(), 40.0);
+
+    model.undo().unwrap();
+    assert_eq!(get_values(&model, &column_a), vec!["1", "2", "3", "4", "5"]);
+    assert_eq!(model.get_cell_content(0, 1, 3).unwrap(), "=A2*10");
+    assert_eq!(model.get_cell_content(0, 1, 4).unwrap(), "=C1+A4");
+    assert!(model.get_cell_style(0, 2, 1).unwrap().font.b);
+    assert_eq!(model.get_row_height(0, 2).unwrap(), 40.0);
+
+    model.redo().unwrap();
+    assert_eq!(get_values(&model, &column_a), vec!["3", "4", "1", "2", "5"]);
+    assert_eq!(model.get_cell_content(0, 3, 4).unwrap(), "=C3+A2");
+}
+
+#[test]
+fn move_rows_up_with_references_in_other_sheets() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.new_sheet().unwrap();
+    model.set_user_input(0, 1, 1, "a").unwrap();
+    model.set_user_input(0, 2, 1, "b").unwrap();
+    model.set_user_input(0, 3, 1, "c").unwrap();
+    model.set_user_input(1, 1, 1, "=Sheet1!A3").unwrap();
+    model.set_user_input(1, 2, 1, "=Sheet1!$A$1").unwrap();
+
+    model.move_rows(0, 3, 1, -2).unwrap();
+    assert_eq!(
+        get_values(&model, &[(1, 1), (2, 1), (3, 1)]),
+        vec!["c", "a", "b"]
+    );
+    assert_eq!(model.get_cell_content(1, 1, 1).unwrap(), "=Sheet1!A1");
+    assert_eq!(model.get_cell_content(1, 2, 1).unwrap(), "=Sheet1!$A$2");
+    assert_eq!(model.get_formatted_cell_value(1, 1, 1).unwrap(), "c");
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(1, 1, 1).unwrap(), "=Sheet1!A3");
+    assert_eq!(model.get_cell_content(1, 2, 1).unwrap(), "=Sheet1!$A$1");
+}
+
+#[test]
+fn move_columns() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "A").unwrap();
+    model.set_user_input(0, 1, 2, "B").unwrap();
+    model.set_user_input(0, 1, 3, "C").unwrap();
+    model.set_user_input(0, 2, 1, "=C1&B1").unwrap();
+    model.set_column_width(0, 3, 200.0).unwrap();
+    model
+        .add_comment(0, 1, 3, "A comment", "Author", None)
+        .unwrap();
+    let width_b = model.get_column_width(0, 2).unwrap();
+
+    // Column C goes to the start
+    model.move_columns(0, 3, 1, -2).unwrap();
+    assert_eq!(
+        get_values(&model, &[(1, 1), (1, 2), (1, 3)]),
+        vec!["C", "A", "B"]
+    );
+    assert_eq!(model.get_cell_content(0, 2, 2).unwrap(), "=A1&C1");
+    assert_eq!(model.get_formatted_cell_value(0, 2, 2).unwrap(), "CB");
+    assert_eq!(model.get_column_width(0, 1).unwrap(), 200.0);
+    assert_eq!(model.get_column_width(0, 3).unwrap(), width_b);
+    assert!(model.get_comment(0, 1, 1).unwrap().is_some());
+    assert!(model.get_comment(0, 1, 3).unwrap().is_none());
+
+    model.undo().unwrap();
+    assert_eq!(
+        get_values(&model, &[(1, 1), (1, 2), (1, 3)]),
+        vec!["A", "B", "C"]
+    );
+    assert_eq!(model.get_cell_content(0, 2, 1).unwrap(), "=C1&B1");
+    assert_eq!(model.get_column_width(0, 3).unwrap(), 200.0);
+    assert!(model.get_comment(0, 1, 3).unwrap().is_some());
+}
+
+#[test]
+fn move_range() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 2, 1, "=A1+1").unwrap();
+    model.set_user_input(0, 3, 1, "=A2*$B$1").unwrap();
+    model.set_user_input(0, 1, 2, "10").unwrap();
+    model.set_user_input(0, 1, 3, "=SUM(A1:A2)").unwrap();
+    model.set_user_input(0, 5, 4, "overwritten").unwrap();
+
+    // A1:A2 goes to D4
+    model.move_range(&area(0, 1, 1, 1, 2), 4, 4).unwrap();
+    assert_eq!(
+        get_values(&model, &[(1, 1), (2, 1), (4, 4), (5, 4)]),
+        vec!["", "", "1", "2"]
+    );
+    // References inside the moved range move with it
+    assert_eq!(model.get_cell_content(0, 5, 4).unwrap(), "=D4+1");
+    assert_eq!(model.get_cell_content(0, 3, 1).unwrap(), "=D5*$B$1");
+    assert_eq!(model.get_cell_content(0, 1, 3).unwrap(), "=SUM(D4:D5)");
+    assert_eq!(model.get_formatted_cell_value(0, 3, 1).unwrap(), "20");
+
+    model.undo().unwrap();
+    assert_eq!(
+        get_values(&model, &[(1, 1), (2, 1), (4, 4), (5, 4)]),
+        vec!["1", "2", "", "overwritten"]
+    );
+    assert_eq!(model.get_cell_content(0, 3, 1).unwrap(), "=A2*$B$1");
+    assert_eq!(model.get_cell_content(0, 1, 3).unwrap(), "=SUM(A1:A2)");
+
+    model.redo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 4).unwrap(), "2");
+}
+
+#[test]
+fn move_overlapping_range() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 2, 1, "2").unwrap();
+    model.set_user_input(0, 3, 1, "3").unwrap();
+    model.move_range(&area(0, 1, 1, 1, 3), 2, 1).unwrap();
+    assert_eq!(
+        get_values(&model, &[(1, 1), (2, 1), (3, 1), (4, 1)]),
+        vec!["", "1", "2", "3"]
+    );
+    model.undo().unwrap();
+    assert_eq!(
+        get_values(&model, &[(1, 1), (2, 1), (3, 1), (4, 1)]),
+        vec!["1", "2", "3", ""]
+    );
+}
+
+#[test]
+fn merged_cells() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.merge_cells(&area(0, 1, 1, 2, 1)).unwrap();
+    model.move_rows(0, 1, 1, 3).unwrap();
+    assert_eq!(model.get_merged_cells(0).unwrap(), vec!["A4:B4"]);
+
+    model.move_range(&area(0, 4, 1, 2, 1), 6, 3).unwrap();
+    assert_eq!(model.get_merged_cells(0).unwrap(), vec!["C6:D6"]);
+
+    // Only part of the merged cell
+    assert_eq!(
+        model.move_range(&area(0, 6, 3, 1, 1), 8, 3),
+        Err("Cannot move part of a merged cell".to_string())
+    );
+    model.merge_cells(&area(0, 10, 1, 1, 2)).unwrap();
+    assert_eq!(
+        model.move_rows(0, 10, 1, 5),
+        Err("Cannot move part of a merged cell".to_string())
+    );
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_merged_cells(0).unwrap(), vec!["A4:B4"]);
+}
+
+#[test]
+fn tables_and_autofilters() {
+    let mut model = UserModel::from_model(new_empty_model());
+    for (column, header) in ["Name", "Price", "Cost"].iter().enumerate() {
+        model
+            .set_user_input(0, 1, column as i32 + 1, header)
+            .unwrap();
+    }
+    let table = model.add_table(&area(0, 1, 1, 3, 4), true).unwrap();
+    let column_names = |model: &UserModel| -> Vec<String> {
+        let table = model.get_table(&table).unwrap();
+        table.columns.iter().map(|c| c.name.clone()).collect()
+    };
+
+    // Columns moved inside a table change their order
+    model.move_columns(0, 1, 1, 2).unwrap();
+    assert_eq!(column_names(&model), vec!["Price", "Cost", "Name"]);
+    assert_eq!(model.get_table(&table).unwrap().reference, "A1:C4");
+    model.undo().unwrap();
+    assert_eq!(column_names(&model), vec!["Name", "Price", "Cost"]);
+
+    model.move_rows(0, 2, 1, 2).unwrap();
+    assert_eq!(model.get_table(&table).unwrap().reference, "A1:C4");
+    assert_eq!(
+        model.move_rows(0, 1, 1, 5),
+        Err("Cannot move part of a table".to_string())
+    );
+    assert_eq!(
+        model.move_rows(0, 4, 1, -3),
+        Err("Cannot move part of a table".to_string())
+    );
+    model.move_rows(0, 1, 4, 5).unwrap();
+    assert_eq!(model.get_table(&table).unwrap().reference, "A6:C9");
+    model.undo().unwrap();
+    assert_eq!(model.get_table(&table).unwrap().reference, "A1:C4");
+
+    model.move_range(&area(0, 1, 1, 3, 4), 1, 10).unwrap();
+    assert_eq!(model.get_table(&table).unwrap().reference, "J1:L4");
+    model.undo().unwrap();
+
+    model.set_user_input(0, 1, 5, "Fruit").unwrap();
+    model.set_user_input(0, 1, 6, "Color").unwrap();
+    model.add_auto_filter(&area(0, 1, 5, 2, 4)).unwrap();
+    model
+        .set_auto_filter_column(
+            0,
+            0,
+            Some(ColumnFilter::Values {
+                values: vec!["Apple".to_string()],
+                date_groups: vec![],
+                blank: false,
+            }),
+        )
+        .unwrap();
+    model.move_columns(0, 5, 1, 1).unwrap();
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "E1:F4");
+    assert_eq!(auto_filter.columns[0].column_id, 1);
+
+    model.move_range(&area(0, 1, 5, 2, 4), 11, 5).unwrap();
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "E11:F14");
+    model.undo().unwrap();
+    model.undo().unwrap();
+    let auto_filter = model.get_auto_filter(0).unwrap().unwrap();
+    assert_eq!(auto_filter.reference, "E1:F4");
+    assert_eq!(auto_filter.columns[0].column_id, 0);
+}
+
+#[test]
+fn conditional_formats_and_data_validations() {
+    let mut model = UserModel::from_model(new_empty_model());
+    let conditional_format = ConditionalFormat {
+        sqref: "A1:A3".to_string(),
+        rule: ConditionalFormatRule::Expression {
+            formula: "A1>$B$1".to_string(),
+        },
+        style: None,
+        stop_if_true: false,
+    };
+    let data_validation = DataValidation {
+        sqref: "A1:A3".to_string(),
+        kind: DataValidationKind::Whole,
+        operator: CellIsOperator::LessThan,
+        formulas: vec!["$C$1".to_string()],
+        allow_blank: true,
+        show_dropdown: false,
+        show_input_message: false,
+        input_title: None,
+        input_message: None,
+        show_error_message: true,
+        error_style: DataValidationErrorStyle::Stop,
+        error_title: None,
+        error_message: None,
+    };
+    model
+        .add_conditional_format(0, conditional_format.clone())
+        .unwrap();
+    model
+        .add_data_validation(0, data_validation.clone())
+        .unwrap();
+    let rules = |model: &UserModel| {
+        let conditional_format = &model.get_conditional_formats(0).unwrap()[0];
+        let data_validation = &model.get_data_validations(0).unwrap()[0];
+        (
+            conditional_format.sqref.clone(),
+            conditional_format.rule.clone(),
+            data_validation.sqref.clone(),
+            data_validation.formulas[0].clone(),
+        )
+    };
+    let expected = |sqref: &str, formula: &str, dv_formula: &str| {
+        (
+            sqref.to_string(),
+            ConditionalFormatRule::Expression {
+                formula: formula.to_string(),
+            },
+            sqref.to_string(),
+            dv_formula.to_string(),
+        )
+    };
+
+    model.move_rows(0, 1, 3, 5).unwrap();
+    assert_eq!(rules(&model), expected("A6:A8", "A6>$B$6", "$C$6"));
+    model.undo().unwrap();
+    assert_eq!(rules(&model), expected("A1:A3", "A1>$B$1", "$C$1"));
+
+    model.move_columns(0, 1, 1, 3).unwrap();
+    assert_eq!(rules(&model), expected("D1:D3", "D1>$A$1", "$B$1"));
+    model.undo().unwrap();
+
+    model.move_range(&area(0, 1, 1, 3, 3), 1, 5).unwrap();
+    assert_eq!(rules(&model), expected("E1:E3", "E1>$F$1", "$G$1"));
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_conditional_formats(0).unwrap(),
+        vec![conditional_format]
+    );
+    assert_eq!(
+        model.get_data_validations(0).unwrap(),
+        vec![data_validation]
+    );
+}
+
+#[test]
+fn errors() {
+    let mut model = UserModel::from_model(new_empty_model());
+    assert_eq!(
+        model.move_rows(0, LAST_ROW, 1, 1),
+        Err("Target row out of boundaries".to_string())
+    );
+    assert_eq!(
+        model.move_columns(0, 1, 0, 1),
+        Err("The number of columns to move must be positive".to_string())
+    );
+    assert_eq!(
+        model.move_range(&area(0, 1, LAST_COLUMN - 1, 2, 1), 1, LAST_COLUMN),
+        Err("Target range out of boundaries".to_string())
+    );
+}
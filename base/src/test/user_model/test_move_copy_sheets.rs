@@ -0,0 +1,369 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    constants::DEFAULT_COLUMN_WIDTH,
+    test::util::{area, new_empty_model},
+    types::{
+        CellIsOperator, ConditionalFormat, ConditionalFormatRule, DataValidation,
+        DataValidationErrorStyle, DataValidationKind, DefinedName,
+    },
+    UserModel,
+};
+
+fn get_sheet_names(model: &UserModel) -> Vec<String> {
+    model
+        .get_worksheets_properties()
+        .into_iter()
+        .map(|properties| properties.name)
+        .collect()
+}
+
+/// A model with B1 = 5, a name "Rate" scoped to Sheet1 pointing to it, a global name "Total",
+/// A1 = Rate*2, and a conditional format and a data validation that refer to B1 by sheet name
+fn new_model_with_rules() -> UserModel {
+    let mut base = new_empty_model();
+    let sheet_id = base.workbook.worksheets[0].sheet_id;
+    base.workbook.defined_names.push(DefinedName {
+        name: "Rate".to_string(),
+        formula: "Sheet1!$B$1".to_string(),
+        sheet_id: Some(sheet_id),
+    });
+    base.workbook.defined_names.push(DefinedName {
+        name: "Total".to_string(),
+        formula: "Sheet1!$C$1".to_string(),
+        sheet_id: None,
+    });
+    base.reset_parsed_structures();
+    let mut model = UserModel::from_model(base);
+    model.set_user_input(0, 1, 2, "5").unwrap();
+    model.set_user_input(0, 1, 1, "=Rate*2").unwrap();
+    model
+        .add_conditional_format(
+            0,
+            ConditionalFormat {
+                sqref: "A1:A3".to_string(),
+                rule: ConditionalFormatRule::Expression {
+                    formula: "A1>Sheet1!$B$1".to_string(),
+                },
+                style: None,
+                stop_if_true: false,
+            },
+        )
+        .unwrap();
+    model
+        .add_data_validation(
+            0,
+            DataValidation {
+                sqref: "D1:D3".to_string(),
+                kind: DataValidationKind::Whole,
+                operator: CellIsOperator::LessThan,
+                formulas: vec!["Sheet1!$B$1".to_string()],
+                allow_blank: true,
+                show_dropdown: false,
+                show_input_message: false,
+                input_title: None,
+                input_message: None,
+                show_error_message: true,
+                error_style: DataValidationErrorStyle::Stop,
+                error_title: None,
+                error_message: None,
+            },
+        )
+        .unwrap();
+    model
+}
+
+/// The names scoped to the `sheet` with their formulas
+fn get_local_names(model: &UserModel, sheet: u32) -> Vec<(String, String)> {
+    let sheet_id = model.model.workbook.worksheets[sheet as usize].sheet_id;
+    model
+        .model
+        .workbook
+        .defined_names
+        .iter()
+        .filter(|defined_name| defined_name.sheet_id == Some(sheet_id))
+        .map(|defined_name| (defined_name.name.clone(), defined_name.formula.clone()))
+        .collect()
+}
+
+#[test]
+fn move_sheet_keeps_references() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.new_sheet().unwrap();
+    model.new_sheet().unwrap();
+    model.set_user_input(1, 1, 1, "42").unwrap();
+    model.set_user_input(0, 1, 1, "=Sheet2!A1*2").unwrap();
+    model.set_selected_sheet(1).unwrap();
+
+    model.move_sheet(1, 2).unwrap();
+    assert_eq!(get_sheet_names(&model), vec!["Sheet1", "Sheet3", "Sheet2"]);
+    assert_eq!(model.get_selected_sheet(), 2);
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("84".to_string())
+    );
+    assert_eq!(
+        model.get_cell_content(0, 1, 1),
+        Ok("=Sheet2!A1*2".to_string())
+    );
+
+    model.move_sheet(0, 2).unwrap();
+    assert_eq!(get_sheet_names(&model), vec!["Sheet3", "Sheet2", "Sheet1"]);
+    assert_eq!(model.get_selected_sheet(), 1);
+    assert_eq!(
+        model.get_formatted_cell_value(2, 1, 1),
+        Ok("84".to_string())
+    );
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(get_sheet_names(&model), vec!["Sheet1", "Sheet2", "Sheet3"]);
+    assert_eq!(model.get_selected_sheet(), 1);
+
+    model.redo().unwrap();
+    assert_eq!(get_sheet_names(&model), vec!["Sheet1", "Sheet3", "Sheet2"]);
+
+    assert_eq!(
+        model.move_sheet(0, 3),
+        Err("Sheet index out of bounds".to_string())
+    );
+}
+
+#[test]
+fn duplicate_sheet() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.new_sheet().unwrap();
+    model.set_user_input(0, 1, 1, "Hello").unwrap();
+    model
+        .set_user_input(0, 2, 1, "=Sheet1!A1&\" world\"")
+        .unwrap();
+    model.set_user_input(0, 3, 1, "=Sheet2!A1+1").unwrap();
+    model
+        .update_range_style(&area(0, 1, 1, 1, 1), "font.b", "true")
+        .unwrap();
+    model
+        .set_column_width(0, 3, 2.0 * DEFAULT_COLUMN_WIDTH)
+        .unwrap();
+    model.merge_cells(&area(0, 5, 1, 2, 2)).unwrap();
+    model
+        .add_comment(0, 1, 1, "A comment", "Author", None)
+        .unwrap();
+
+    model.duplicate_sheet(0).unwrap();
+    assert_eq!(
+        get_sheet_names(&model),
+        vec!["Sheet1", "Sheet1 (2)", "Sheet2"]
+    );
+    assert_eq!(model.get_selected_sheet(), 1);
+    assert_eq!(
+        model.get_formatted_cell_value(1, 1, 1),
+        Ok("Hello".to_string())
+    );
+    assert!(model.get_cell_style(1, 1, 1).unwrap().font.b);
+    assert_eq!(model.get_column_width(1, 3), Ok(2.0 * DEFAULT_COLUMN_WIDTH));
+    assert_eq!(model.get_merged_cells(1), Ok(vec!["A5:B6".to_string()]));
+    assert_eq!(model.get_comments(1).unwrap().len(), 1);
+    // References to the sheet itself point to the copy
+    assert_eq!(
+        model.get_cell_content(1, 2, 1),
+        Ok("='Sheet1 (2)'!A1&\" world\"".to_string())
+    );
+    assert_eq!(
+        model.get_cell_content(1, 3, 1),
+        Ok("=Sheet2!A1+1".to_string())
+    );
+    model.set_user_input(1, 1, 1, "Bye").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(1, 2, 1),
+        Ok("Bye world".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("Hello world".to_string())
+    );
+
+    // A copy of a copy
+    model.duplicate_sheet(1).unwrap();
+    assert_eq!(
+        get_sheet_names(&model),
+        vec!["Sheet1", "Sheet1 (2)", "Sheet1 (3)", "Sheet2"]
+    );
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(get_sheet_names(&model), vec!["Sheet1", "Sheet2"]);
+    assert!(model.get_selected_sheet() < 2);
+
+    model.redo().unwrap();
+    assert_eq!(
+        get_sheet_names(&model),
+        vec!["Sheet1", "Sheet1 (2)", "Sheet2"]
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(1, 2, 1),
+        Ok("Hello world".to_string())
+    );
+}
+
+#[test]
+fn duplicate_sheet_with_table() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "Name").unwrap();
+    model.set_user_input(0, 2, 1, "Apple").unwrap();
+    let name = model.add_table(&area(0, 1, 1, 1, 2), true).unwrap();
+
+    model.duplicate_sheet(0).unwrap();
+    let tables = model.get_tables(1).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].name, format!("{name}_2"));
+    assert_eq!(tables[0].sheet_name, "Sheet1 (2)");
+    assert_eq!(model.get_tables(0).unwrap()[0].name, name);
+
+    model.undo().unwrap();
+    assert!(model.get_table(&format!("{name}_2")).is_err());
+}
+
+#[test]
+fn duplicate_sheet_with_rules_and_defined_names() {
+    let mut model = new_model_with_rules();
+
+    model.duplicate_sheet(0).unwrap();
+    let conditional_formats = model.get_conditional_formats(1).unwrap();
+    assert_eq!(
+        conditional_formats[0].rule,
+        ConditionalFormatRule::Expression {
+            formula: "A1>'Sheet1 (2)'!$B$1".to_string(),
+        }
+    );
+    assert_eq!(
+        model.get_data_validations(1).unwrap()[0].formulas,
+        vec!["'Sheet1 (2)'!$B$1".to_string()]
+    );
+    assert_eq!(
+        get_local_names(&model, 1),
+        vec![("Rate".to_string(), "'Sheet1 (2)'!$B$1".to_string())]
+    );
+    // The rules and names of the original sheet are unchanged
+    assert_eq!(
+        model.get_data_validations(0).unwrap()[0].formulas,
+        vec!["Sheet1!$B$1".to_string()]
+    );
+    assert_eq!(
+        get_local_names(&model, 0),
+        vec![("Rate".to_string(), "Sheet1!$B$1".to_string())]
+    );
+    assert_eq!(model.model.workbook.defined_names.len(), 3);
+
+    // The name in the copy refers to the copy
+    model.set_user_input(1, 1, 2, "7").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(1, 1, 1),
+        Ok("14".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("10".to_string())
+    );
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.model.workbook.defined_names.len(), 2);
+    model.redo().unwrap();
+    assert_eq!(
+        get_local_names(&model, 1),
+        vec![("Rate".to_string(), "'Sheet1 (2)'!$B$1".to_string())]
+    );
+}
+
+#[test]
+fn copy_sheet_with_rules_and_defined_names_between_workbooks() {
+    let source = new_model_with_rules();
+    let mut target = UserModel::new_empty("target", "en", "UTC").unwrap();
+
+    target.copy_sheet_from(&source, 0).unwrap();
+    assert_eq!(
+        target.get_conditional_formats(1).unwrap()[0].rule,
+        ConditionalFormatRule::Expression {
+            formula: "A1>'Sheet1 (2)'!$B$1".to_string(),
+        }
+    );
+    assert_eq!(
+        target.get_data_validations(1).unwrap()[0].formulas,
+        vec!["'Sheet1 (2)'!$B$1".to_string()]
+    );
+    // Only the names scoped to the sheet are copied
+    assert_eq!(
+        get_local_names(&target, 1),
+        vec![("Rate".to_string(), "'Sheet1 (2)'!$B$1".to_string())]
+    );
+    assert_eq!(target.model.workbook.defined_names.len(), 1);
+    assert_eq!(
+        target.get_formatted_cell_value(1, 1, 1),
+        Ok("10".to_string())
+    );
+
+    target.undo().unwrap();
+    assert!(target.model.workbook.defined_names.is_empty());
+    target.redo().unwrap();
+    assert_eq!(
+        get_local_names(&target, 1),
+        vec![("Rate".to_string(), "'Sheet1 (2)'!$B$1".to_string())]
+    );
+}
+
+#[test]
+fn copy_sheet_between_workbooks() {
+    let mut source = UserModel::new_empty("source", "en", "UTC").unwrap();
+    source.set_user_input(0, 1, 1, "Hello").unwrap();
+    source.set_user_input(0, 1, 2, "=Sheet1!A1&\"!\"").unwrap();
+    source
+        .update_range_style(&area(0, 1, 1, 1, 1), "fill.fg_color", "#FF0000")
+        .unwrap();
+    source
+        .update_range_style(&area(0, 1, 2, 1, 1), "font.i", "true")
+        .unwrap();
+
+    let mut target = UserModel::new_empty("target", "en", "UTC").unwrap();
+    target.set_user_input(0, 1, 1, "Other").unwrap();
+    target
+        .update_range_style(&area(0, 2, 2, 1, 1), "font.b", "true")
+        .unwrap();
+
+    target.copy_sheet_from(&source, 0).unwrap();
+    assert_eq!(get_sheet_names(&target), vec!["Sheet1", "Sheet1 (2)"]);
+    assert_eq!(target.get_selected_sheet(), 1);
+    assert_eq!(
+        target.get_formatted_cell_value(1, 1, 1),
+        Ok("Hello".to_string())
+    );
+    assert_eq!(
+        target.get_cell_content(1, 1, 2),
+        Ok("='Sheet1 (2)'!A1&\"!\"".to_string())
+    );
+    assert_eq!(
+        target.get_formatted_cell_value(1, 1, 2),
+        Ok("Hello!".to_string())
+    );
+    assert_eq!(
+        target.get_cell_style(1, 1, 1).unwrap().fill.fg_color,
+        Some("#FF0000".to_string())
+    );
+    assert!(target.get_cell_style(1, 1, 2).unwrap().font.i);
+    assert!(!target.get_cell_style(1, 1, 2).unwrap().font.b);
+    assert_eq!(
+        target.get_formatted_cell_value(0, 1, 1),
+        Ok("Other".to_string())
+    );
+
+    target.undo().unwrap();
+    assert_eq!(get_sheet_names(&target), vec!["Sheet1"]);
+    assert_eq!(target.get_selected_sheet(), 0);
+
+    target.redo().unwrap();
+    assert_eq!(get_sheet_names(&target), vec!["Sheet1", "Sheet1 (2)"]);
+    assert_eq!(
+        target.get_formatted_cell_value(1, 1, 2),
+        Ok("Hello!".to_string())
+    );
+}
@@ -0,0 +1,212 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{test::util::area, types::Theme, UserModel};
+
+#[test]
+fn builtin_styles_are_listed() {
+    let model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    let names = model.get_named_styles();
+    assert_eq!(names[0], "Normal");
+    assert!(names.contains(&"Heading 1".to_string()));
+    assert!(names.contains(&"Good".to_string()));
+    assert_eq!(
+        names
+            .iter()
+            .filter(|name| name.as_str() == "Normal")
+            .count(),
+        1
+    );
+
+    let style = model.get_named_style("Heading 1").unwrap();
+    assert!(style.font.b);
+    assert_eq!(style.font.sz, 15);
+    assert_eq!(style.font.color, Some("#44546A".to_string()));
+    assert_eq!(
+        style.border.bottom.unwrap().color,
+        Some("#4472C4".to_string())
+    );
+
+    assert!(model.get_named_style("Not a style").is_err());
+}
+
+#[test]
+fn apply_builtin_style_undo_redo() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "Sales").unwrap();
+    assert_eq!(model.get_cell_style_name(0, 1, 1).unwrap(), "Normal");
+
+    model
+        .apply_named_style(&area(0, 1, 1, 2, 1), "Heading 1")
+        .unwrap();
+    assert_eq!(model.get_cell_style_name(0, 1, 1).unwrap(), "Heading 1");
+    assert_eq!(model.get_cell_style_name(0, 1, 2).unwrap(), "Heading 1");
+    let style = model.get_cell_style(0, 1, 1).unwrap();
+    assert!(style.font.b);
+    assert_eq!(style.font.sz, 15);
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_style_name(0, 1, 1).unwrap(), "Normal");
+    assert!(!model.get_cell_style(0, 1, 1).unwrap().font.b);
+
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_style_name(0, 1, 2).unwrap(), "Heading 1");
+    assert!(model.get_cell_style(0, 1, 2).unwrap().font.b);
+}
+
+#[test]
+fn direct_formatting_keeps_style_name() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model
+        .apply_named_style(&area(0, 1, 1, 1, 1), "Good")
+        .unwrap();
+    model
+        .update_range_style(&area(0, 1, 1, 1, 1), "font.i", "true")
+        .unwrap();
+    assert_eq!(model.get_cell_style_name(0, 1, 1).unwrap(), "Good");
+    let style = model.get_cell_style(0, 1, 1).unwrap();
+    assert!(style.font.i);
+    assert_eq!(style.fill.fg_color, Some("#C6EFCE".to_string()));
+}
+
+#[test]
+fn create_and_modify_named_style() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    let mut style = model.get_named_style("Normal").unwrap();
+    style.font.b = true;
+    model.create_named_style("  Important ", &style).unwrap();
+    assert!(model.get_named_styles().contains(&"Important".to_string()));
+
+    assert_eq!(
+        model.create_named_style("important", &style),
+        Err("A style with that name already exists".to_string())
+    );
+    assert_eq!(
+        model.create_named_style("Title", &style),
+        Err("A style with that name already exists".to_string())
+    );
+    assert_eq!(
+        model.create_named_style(" ", &style),
+        Err("The name of the style cannot be empty".to_string())
+    );
+
+    model
+        .apply_named_style(&area(0, 2, 2, 1, 1), "Important")
+        .unwrap();
+    assert!(model.get_cell_style(0, 2, 2).unwrap().font.b);
+
+    style.font.color = Some("#FF0000".to_string());
+    model.set_named_style("Important", &style).unwrap();
+    assert_eq!(
+        model.get_cell_style(0, 2, 2).unwrap().font.color,
+        Some("#FF0000".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_ne!(
+        model.get_cell_style(0, 2, 2).unwrap().font.color,
+        Some("#FF0000".to_string())
+    );
+    assert!(model.get_cell_style(0, 2, 2).unwrap().font.b);
+
+    // undo the style, undo the creation
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(!model.get_named_styles().contains(&"Important".to_string()));
+
+    model.redo().unwrap();
+    assert!(model.get_named_styles().contains(&"Important".to_string()));
+}
+
+#[test]
+fn modify_named_style_keeps_cell_overrides() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "3").unwrap();
+    model.set_user_input(0, 1, 2, "4").unwrap();
+    model
+        .update_range_style(&area(0, 1, 1, 1, 1), "font.b", "true")
+        .unwrap();
+    model
+        .update_range_style(&area(0, 1, 1, 1, 1), "num_fmt", "0.00")
+        .unwrap();
+
+    let mut style = model.get_named_style("Normal").unwrap();
+    style.font.sz = 14;
+    style.fill.pattern_type = "solid".to_string();
+    style.fill.fg_color = Some("#FFFF00".to_string());
+    model.set_named_style("Normal", &style).unwrap();
+
+    // The bold font and the number format of A1 are kept, the fill changes
+    let a1 = model.get_cell_style(0, 1, 1).unwrap();
+    assert!(a1.font.b);
+    assert_eq!(a1.num_fmt, "0.00");
+    assert_eq!(a1.fill.fg_color, Some("#FFFF00".to_string()));
+    assert_eq!(model.get_cell_style_name(0, 1, 1).unwrap(), "Normal");
+    // B1 has no overrides and takes the whole named style
+    let b1 = model.get_cell_style(0, 1, 2).unwrap();
+    assert_eq!(b1.font.sz, 14);
+    assert_eq!(b1.fill.fg_color, Some("#FFFF00".to_string()));
+
+    model.undo().unwrap();
+    let a1 = model.get_cell_style(0, 1, 1).unwrap();
+    assert!(a1.font.b);
+    assert_eq!(a1.num_fmt, "0.00");
+    assert_eq!(a1.fill.fg_color, None);
+    assert_ne!(model.get_cell_style(0, 1, 2).unwrap().font.sz, 14);
+}
+
+#[test]
+fn set_theme_undo_redo() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model
+        .apply_named_style(&area(0, 1, 1, 1, 1), "Title")
+        .unwrap();
+    assert_eq!(
+        model.get_cell_style(0, 1, 1).unwrap().font.color,
+        Some("#44546A".to_string())
+    );
+
+    let mut theme = Theme {
+        name: "Blue".to_string(),
+        ..Default::default()
+    };
+    theme.colors[3] = "#17406D".to_string();
+    theme.major_font = "Cambria".to_string();
+    model.set_theme(&theme).unwrap();
+    assert_eq!(model.get_theme().name, "Blue");
+    let style = model.get_cell_style(0, 1, 1).unwrap();
+    assert_eq!(style.font.color, Some("#17406D".to_string()));
+    assert_eq!(style.font.name, "Cambria");
+
+    model.undo().unwrap();
+    assert_eq!(model.get_theme(), &Theme::default());
+    let style = model.get_cell_style(0, 1, 1).unwrap();
+    assert_eq!(style.font.color, Some("#44546A".to_string()));
+    assert_eq!(style.font.name, "Calibri Light");
+
+    model.redo().unwrap();
+    assert_eq!(model.get_theme().major_font, "Cambria");
+}
+
+#[test]
+fn direct_colors_do_not_change_with_the_theme() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model
+        .apply_named_style(&area(0, 1, 1, 1, 2), "Title")
+        .unwrap();
+    // The same color as the theme, but chosen by the user
+    model
+        .update_range_style(&area(0, 2, 1, 1, 1), "font.color", "#44546A")
+        .unwrap();
+
+    let mut theme = Theme::default();
+    theme.colors[3] = "#17406D".to_string();
+    model.set_theme(&theme).unwrap();
+    assert_eq!(
+        model.get_cell_style(0, 1, 1).unwrap().font.color,
+        Some("#17406D".to_string())
+    );
+    assert_eq!(
+        model.get_cell_style(0, 2, 1).unwrap().font.color,
+        Some("#44546A".to_string())
+    );
+}
@@ -13,6 +13,7 @@ fn simple_pasting() {
         pattern_type: "solid".to_string(),
         fg_color: Some("#FF5577".to_string()),
         bg_color: Some("#33FF44".to_string()),
+        ..Default::default()
     };
     let styles = vec![vec![style.clone()]];
 
@@ -0,0 +1,156 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{constants::DEFAULT_ROW_HEIGHT, test::util::new_empty_model, UserModel};
+
+#[test]
+fn hide_rows_and_columns_undo_redo() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model
+        .set_user_input(0, 1, 1, "=SUBTOTAL(109, A2:A4)")
+        .unwrap();
+    model.set_user_input(0, 2, 1, "1").unwrap();
+    model.set_user_input(0, 3, 1, "2").unwrap();
+    model.set_user_input(0, 4, 1, "3").unwrap();
+
+    model.set_rows_hidden(0, 3, 4, true).unwrap();
+    model.set_columns_hidden(0, 2, 2, true).unwrap();
+    assert!(model.is_row_hidden(0, 3).unwrap());
+    assert!(model.is_row_hidden(0, 4).unwrap());
+    assert!(model.is_column_hidden(0, 2).unwrap());
+    // SUBTOTAL ignores hidden rows
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "1");
+
+    model.undo().unwrap();
+    assert!(!model.is_column_hidden(0, 2).unwrap());
+    model.undo().unwrap();
+    assert!(!model.is_row_hidden(0, 3).unwrap());
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "6");
+
+    model.redo().unwrap();
+    assert!(model.is_row_hidden(0, 4).unwrap());
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "1");
+    model.redo().unwrap();
+    assert!(model.is_column_hidden(0, 2).unwrap());
+
+    assert!(model.set_rows_hidden(0, 4, 3, true).is_err());
+}
+
+#[test]
+fn group_collapse_and_expand_rows() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.group_rows(0, 2, 6).unwrap();
+    model.group_rows(0, 3, 4).unwrap();
+    assert_eq!(model.get_row_outline_level(0, 2).unwrap(), 1);
+    assert_eq!(model.get_row_outline_level(0, 3).unwrap(), 2);
+
+    // collapse the inner group and then the outer group
+    model.collapse_row_group(0, 3).unwrap();
+    assert!(model.is_row_hidden(0, 3).unwrap());
+    assert!(model.is_row_hidden(0, 4).unwrap());
+    assert!(!model.is_row_hidden(0, 5).unwrap());
+    assert!(model.is_row_collapsed(0, 5).unwrap());
+
+    model.collapse_row_group(0, 2).unwrap();
+    for row in 2..=6 {
+        assert!(model.is_row_hidden(0, row).unwrap());
+    }
+    assert!(model.is_row_collapsed(0, 7).unwrap());
+
+    // expanding the outer group keeps the inner group collapsed
+    model.expand_row_group(0, 6).unwrap();
+    assert!(!model.is_row_hidden(0, 2).unwrap());
+    assert!(model.is_row_hidden(0, 3).unwrap());
+    assert!(model.is_row_hidden(0, 4).unwrap());
+    assert!(!model.is_row_hidden(0, 5).unwrap());
+    assert!(!model.is_row_collapsed(0, 7).unwrap());
+
+    model.undo().unwrap();
+    for row in 2..=6 {
+        assert!(model.is_row_hidden(0, row).unwrap());
+    }
+    model.redo().unwrap();
+    assert!(!model.is_row_hidden(0, 6).unwrap());
+
+    model.ungroup_rows(0, 3, 4).unwrap();
+    assert_eq!(model.get_row_outline_level(0, 3).unwrap(), 1);
+    model.undo().unwrap();
+    assert_eq!(model.get_row_outline_level(0, 3).unwrap(), 2);
+
+    assert_eq!(
+        model.collapse_row_group(0, 10),
+        Err("The row is not in a group".to_string())
+    );
+    assert_eq!(
+        model.ungroup_rows(0, 10, 12),
+        Err("The rows are not grouped".to_string())
+    );
+}
+
+#[test]
+fn group_collapse_and_expand_columns() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.group_columns(0, 2, 3).unwrap();
+    model.collapse_column_group(0, 2).unwrap();
+    assert!(model.is_column_hidden(0, 2).unwrap());
+    assert!(model.is_column_hidden(0, 3).unwrap());
+    assert!(model.is_column_collapsed(0, 4).unwrap());
+
+    model.expand_column_group(0, 3).unwrap();
+    assert!(!model.is_column_hidden(0, 2).unwrap());
+    assert!(!model.is_column_hidden(0, 3).unwrap());
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_column_outline_level(0, 2).unwrap(), 0);
+    assert!(!model.is_column_hidden(0, 2).unwrap());
+
+    for _ in 0..7 {
+        model.group_columns(0, 2, 2).unwrap();
+    }
+    assert_eq!(
+        model.group_columns(0, 2, 2),
+        Err("Cannot group more than 7 levels".to_string())
+    );
+}
+
+#[test]
+fn navigation_skips_hidden_rows_and_columns() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_rows_hidden(0, 2, 3, true).unwrap();
+    model.set_columns_hidden(0, 2, 2, true).unwrap();
+
+    model.on_arrow_down().unwrap();
+    assert_eq!(model.get_selected_view().row, 4);
+    model.on_arrow_up().unwrap();
+    assert_eq!(model.get_selected_view().row, 1);
+
+    model.on_arrow_right().unwrap();
+    assert_eq!(model.get_selected_view().column, 3);
+    model.on_arrow_left().unwrap();
+    assert_eq!(model.get_selected_view().column, 1);
+
+    model.set_rows_hidden(0, 1, 1, true).unwrap();
+    model.set_selected_cell(4, 1).unwrap();
+    // There are no visible rows above
+    model.on_arrow_up().unwrap();
+    assert_eq!(model.get_selected_view().row, 4);
+}
+
+#[test]
+fn scroll_and_page_down_with_hidden_rows() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_rows_hidden(0, 2, 11, true).unwrap();
+    model.set_top_left_visible_cell(21, 1).unwrap();
+    // rows 2 to 11 do not take any space
+    assert_eq!(model.get_scroll_y().unwrap(), 10.0 * DEFAULT_ROW_HEIGHT);
+
+    let mut model = UserModel::from_model(new_empty_model());
+    model.on_page_down().unwrap();
+    let row = model.get_selected_view().row;
+
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_rows_hidden(0, 2, 6, true).unwrap();
+    model.on_page_down().unwrap();
+    assert_eq!(model.get_selected_view().row, row + 5);
+}
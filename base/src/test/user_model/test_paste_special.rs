@@ -0,0 +1,263 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{types::CellType, ClipboardData, PasteMode, PasteOperation, PasteOptions, UserModel};
+
+type Range = (i32, i32, i32, i32);
+
+fn copy(model: &mut UserModel, range: Range) -> (Range, ClipboardData) {
+    model.set_selected_cell(range.0, range.1).unwrap();
+    model
+        .set_selected_range(range.0, range.1, range.2, range.3)
+        .unwrap();
+    let clipboard = model.copy_to_clipboard().unwrap();
+    (clipboard.range, clipboard.data)
+}
+
+fn paste(
+    model: &mut UserModel,
+    clipboard: &(Range, ClipboardData),
+    row: i32,
+    column: i32,
+    options: PasteOptions,
+) {
+    model.set_selected_cell(row, column).unwrap();
+    model
+        .paste_special_from_clipboard(clipboard.0, &clipboard.1, &options)
+        .unwrap();
+}
+
+fn mode(mode: PasteMode) -> PasteOptions {
+    PasteOptions {
+        mode,
+        ..Default::default()
+    }
+}
+
+fn operation(operation: PasteOperation) -> PasteOptions {
+    PasteOptions {
+        mode: PasteMode::Values,
+        operation,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn paste_values_after_source_changes() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "2").unwrap();
+    model.set_user_input(0, 1, 2, "=A1*3").unwrap();
+    model.set_user_input(0, 1, 3, "123").unwrap();
+    model.set_user_input(0, 1, 4, "'123").unwrap();
+    model.set_user_input(0, 1, 5, "=1/0").unwrap();
+    model.set_user_input(0, 1, 6, "=A1>1").unwrap();
+    let clipboard = copy(&mut model, (1, 1, 1, 6));
+
+    // the value is kept in the clipboard
+    model.set_user_input(0, 1, 1, "10").unwrap();
+    paste(&mut model, &clipboard, 3, 1, mode(PasteMode::Values));
+
+    assert_eq!(model.get_cell_content(0, 3, 1), Ok("2".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("6".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("123".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 4), Ok("123".to_string()));
+    assert_eq!(model.get_cell_type(0, 3, 4), Ok(CellType::Text));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 5),
+        Ok("#DIV/0!".to_string())
+    );
+    assert_eq!(model.get_cell_content(0, 3, 6), Ok("TRUE".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("".to_string()));
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("6".to_string()));
+}
+
+#[test]
+fn paste_formulas_and_formats() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "=B1+1").unwrap();
+    let mut style = model.get_cell_style(0, 1, 1).unwrap();
+    style.font.b = true;
+    style.num_fmt = "0.00".to_string();
+    model.model.set_cell_style(0, 1, 1, &style).unwrap();
+    let clipboard = copy(&mut model, (1, 1, 1, 1));
+
+    paste(&mut model, &clipboard, 3, 3, mode(PasteMode::Formulas));
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("=D3+1".to_string()));
+    assert!(!model.get_cell_style(0, 3, 3).unwrap().font.b);
+
+    model.set_user_input(0, 5, 5, "42").unwrap();
+    paste(&mut model, &clipboard, 5, 5, mode(PasteMode::Formats));
+    assert_eq!(model.get_cell_content(0, 5, 5), Ok("42".to_string()));
+    let style = model.get_cell_style(0, 5, 5).unwrap();
+    assert!(style.font.b);
+    assert_eq!(style.num_fmt, "0.00");
+
+    model.undo().unwrap();
+    assert!(!model.get_cell_style(0, 5, 5).unwrap().font.b);
+}
+
+#[test]
+fn paste_column_widths() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_column_width(0, 1, 200.0).unwrap();
+    model.set_column_width(0, 2, 50.0).unwrap();
+    model.set_user_input(0, 1, 1, "Hello").unwrap();
+    let clipboard = copy(&mut model, (1, 1, 1, 2));
+
+    let default_width = model.get_column_width(0, 5).unwrap();
+    paste(&mut model, &clipboard, 3, 4, mode(PasteMode::ColumnWidths));
+    assert_eq!(model.get_column_width(0, 4), Ok(200.0));
+    assert_eq!(model.get_column_width(0, 5), Ok(50.0));
+    assert_eq!(model.get_cell_content(0, 3, 4), Ok("".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(model.get_column_width(0, 5), Ok(default_width));
+}
+
+#[test]
+fn paste_transpose() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 1, 2, "2").unwrap();
+    model.set_user_input(0, 1, 3, "3").unwrap();
+    model.set_user_input(0, 2, 1, "a").unwrap();
+    let clipboard = copy(&mut model, (1, 1, 2, 3));
+
+    let options = PasteOptions {
+        transpose: true,
+        ..Default::default()
+    };
+    paste(&mut model, &clipboard, 5, 1, options);
+    assert_eq!(model.get_cell_content(0, 5, 1), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 6, 1), Ok("2".to_string()));
+    assert_eq!(model.get_cell_content(0, 7, 1), Ok("3".to_string()));
+    assert_eq!(model.get_cell_content(0, 5, 2), Ok("a".to_string()));
+    assert_eq!(model.get_selected_view().range, [5, 1, 7, 2]);
+}
+
+#[test]
+fn paste_transpose_formulas() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 1, 2, "2").unwrap();
+    model.set_user_input(0, 1, 3, "=A1+B1").unwrap();
+    model.set_user_input(0, 2, 1, "=SUM(A1:C1)").unwrap();
+    model.set_user_input(0, 2, 2, "=$A$1+A$1").unwrap();
+    let clipboard = copy(&mut model, (1, 1, 2, 3));
+
+    let options = PasteOptions {
+        transpose: true,
+        ..Default::default()
+    };
+    paste(&mut model, &clipboard, 1, 5, options);
+    // Relative references point to the transposed cells
+    assert_eq!(model.get_cell_content(0, 3, 5), Ok("=E1+E2".to_string()));
+    assert_eq!(
+        model.get_cell_content(0, 1, 6),
+        Ok("=SUM(E1:E3)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 6), Ok("6".to_string()));
+    // Absolute and mixed references are displaced as in a normal paste
+    assert_eq!(model.get_cell_content(0, 2, 6), Ok("=$A$1+E$1".to_string()));
+}
+
+#[test]
+fn paste_skip_blanks() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "new").unwrap();
+    model.set_user_input(0, 1, 3, "new").unwrap();
+    model.set_user_input(0, 5, 1, "old").unwrap();
+    model.set_user_input(0, 5, 2, "old").unwrap();
+    model.set_user_input(0, 5, 3, "old").unwrap();
+    let clipboard = copy(&mut model, (1, 1, 1, 3));
+
+    let options = PasteOptions {
+        skip_blanks: true,
+        ..Default::default()
+    };
+    paste(&mut model, &clipboard, 5, 1, options);
+    assert_eq!(model.get_cell_content(0, 5, 1), Ok("new".to_string()));
+    assert_eq!(model.get_cell_content(0, 5, 2), Ok("old".to_string()));
+    assert_eq!(model.get_cell_content(0, 5, 3), Ok("new".to_string()));
+}
+
+#[test]
+fn paste_operations() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "4").unwrap();
+    let clipboard = copy(&mut model, (1, 1, 1, 1));
+    model.set_user_input(0, 3, 1, "10").unwrap();
+    model.set_user_input(0, 3, 2, "10").unwrap();
+    model.set_user_input(0, 3, 3, "10").unwrap();
+    model.set_user_input(0, 3, 4, "10").unwrap();
+    model.set_user_input(0, 3, 6, "text").unwrap();
+
+    paste(&mut model, &clipboard, 3, 1, operation(PasteOperation::Add));
+    paste(
+        &mut model,
+        &clipboard,
+        3,
+        2,
+        operation(PasteOperation::Subtract),
+    );
+    paste(
+        &mut model,
+        &clipboard,
+        3,
+        3,
+        operation(PasteOperation::Multiply),
+    );
+    paste(
+        &mut model,
+        &clipboard,
+        3,
+        4,
+        operation(PasteOperation::Divide),
+    );
+    // blank cells count as zero
+    paste(
+        &mut model,
+        &clipboard,
+        3,
+        5,
+        operation(PasteOperation::Subtract),
+    );
+    // text is not changed
+    paste(&mut model, &clipboard, 3, 6, operation(PasteOperation::Add));
+
+    assert_eq!(model.get_cell_content(0, 3, 1), Ok("14".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("6".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("40".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 4), Ok("2.5".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 5), Ok("-4".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 6), Ok("text".to_string()));
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 5), Ok("".to_string()));
+}
+
+#[test]
+fn paste_operation_with_formulas() {
+    let mut model = UserModel::new_empty("model", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "0").unwrap();
+    model.set_user_input(0, 1, 2, "5").unwrap();
+    let clipboard = copy(&mut model, (1, 1, 1, 2));
+    model.set_user_input(0, 3, 1, "7").unwrap();
+    model.set_user_input(0, 3, 2, "=A3*2").unwrap();
+
+    paste(
+        &mut model,
+        &clipboard,
+        3,
+        1,
+        operation(PasteOperation::Divide),
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 1),
+        Ok("#DIV/0!".to_string())
+    );
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("=A3*2/5".to_string()));
+}
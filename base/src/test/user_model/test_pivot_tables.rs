@@ -0,0 +1,605 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    test::util::{area, new_model_with_data},
+    types::{
+        PivotFilter, PivotFunction, PivotSource, PivotTable, PivotTableParts, PivotValueField,
+        SheetProtection,
+    },
+    UserModel,
+};
+
+fn source() -> PivotSource {
+    PivotSource::Range {
+        sheet_name: "Sheet1".to_string(),
+        reference: "A1:C7".to_string(),
+    }
+}
+
+const SALES: &[&[&str]] = &[
+    &["Region", "Product", "Sales"],
+    &["East", "Apple", "10"],
+    &["East", "Pear", "20"],
+    &["West", "Apple", "30"],
+    &["West", "Apple", "5"],
+    &["East", "Apple", "1"],
+    &["North", "Pear", "7"],
+];
+
+fn sum_of_sales() -> PivotValueField {
+    PivotValueField {
+        field: "Sales".to_string(),
+        function: PivotFunction::Sum,
+        name: "Sum of Sales".to_string(),
+    }
+}
+
+/// Adds a pivot table at E1 with the `row_fields`, `column_fields` and the sum of sales
+fn add_pivot_table(model: &mut UserModel, row_fields: &[&str], column_fields: &[&str]) {
+    let name = model.add_pivot_table(&source(), 0, 1, 5).unwrap();
+    let pivot_table = PivotTable {
+        row_fields: row_fields.iter().map(|f| f.to_string()).collect(),
+        column_fields: column_fields.iter().map(|f| f.to_string()).collect(),
+        value_fields: vec![sum_of_sales()],
+        ..model.get_pivot_table(0, &name).unwrap()
+    };
+    model.update_pivot_table(0, &name, pivot_table).unwrap();
+}
+
+/// Returns the formatted values of the cells from (`row1`, `column1`) to (`row2`, `column2`)
+fn values(model: &UserModel, row1: i32, column1: i32, row2: i32, column2: i32) -> Vec<Vec<String>> {
+    (row1..=row2)
+        .map(|row| {
+            (column1..=column2)
+                .map(|column| model.get_formatted_cell_value(0, row, column).unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn add_pivot_table_empty() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    let name = model.add_pivot_table(&source(), 0, 1, 5).unwrap();
+    assert_eq!(name, "PivotTable1");
+    let pivot_table = model.get_pivot_table(0, "PivotTable1").unwrap();
+    assert_eq!(pivot_table.location, "E1");
+    assert_eq!(pivot_table.reference, None);
+    assert!(pivot_table.row_grand_totals);
+    assert!(pivot_table.column_grand_totals);
+    assert_eq!(model.get_pivot_tables(0).unwrap(), vec![pivot_table]);
+
+    let name = model.add_pivot_table(&source(), 0, 1, 10).unwrap();
+    assert_eq!(name, "PivotTable2");
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(model.get_pivot_tables(0).unwrap().is_empty());
+    model.redo().unwrap();
+    assert_eq!(model.get_pivot_tables(0).unwrap().len(), 1);
+}
+
+#[test]
+fn row_fields() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    assert_eq!(
+        values(&model, 1, 5, 5, 6),
+        vec![
+            vec!["Region", "Sum of Sales"],
+            vec!["East", "31"],
+            vec!["North", "7"],
+            vec!["West", "35"],
+            vec!["Grand Total", "73"],
+        ]
+    );
+    let pivot_table = model.get_pivot_table(0, "PivotTable1").unwrap();
+    assert_eq!(pivot_table.reference, Some("E1:F5".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(values(&model, 1, 5, 1, 6), vec![vec!["", ""]]);
+    assert_eq!(
+        model.get_pivot_table(0, "PivotTable1").unwrap().reference,
+        None
+    );
+    model.redo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 6).unwrap(), "73");
+}
+
+#[test]
+fn row_and_column_fields() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &["Product"]);
+    assert_eq!(
+        values(&model, 1, 5, 6, 8),
+        vec![
+            vec!["Sum of Sales", "Product", "", ""],
+            vec!["Region", "Apple", "Pear", "Grand Total"],
+            vec!["East", "11", "20", "31"],
+            vec!["North", "", "7", "7"],
+            vec!["West", "35", "", "35"],
+            vec!["Grand Total", "46", "27", "73"],
+        ]
+    );
+}
+
+#[test]
+fn subtotals() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region", "Product"], &[]);
+    assert_eq!(
+        values(&model, 1, 5, 9, 7),
+        vec![
+            vec!["Region", "Product", "Sum of Sales"],
+            vec!["East", "Apple", "11"],
+            vec!["East", "Pear", "20"],
+            vec!["East Total", "", "31"],
+            vec!["North", "Pear", "7"],
+            vec!["North Total", "", "7"],
+            vec!["West", "Apple", "35"],
+            vec!["West Total", "", "35"],
+            vec!["Grand Total", "", "73"],
+        ]
+    );
+
+    // Without subtotals and grand totals
+    let pivot_table = PivotTable {
+        subtotals: false,
+        column_grand_totals: false,
+        ..model.get_pivot_table(0, "PivotTable1").unwrap()
+    };
+    model
+        .update_pivot_table(0, "PivotTable1", pivot_table)
+        .unwrap();
+    assert_eq!(
+        values(&model, 1, 5, 6, 7),
+        vec![
+            vec!["Region", "Product", "Sum of Sales"],
+            vec!["East", "Apple", "11"],
+            vec!["East", "Pear", "20"],
+            vec!["North", "Pear", "7"],
+            vec!["West", "Apple", "35"],
+            vec!["", "", ""],
+        ]
+    );
+    // The cells of the larger pivot table are cleared
+    assert_eq!(model.get_formatted_cell_value(0, 9, 7).unwrap(), "");
+}
+
+#[test]
+fn several_values() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    let name = model.add_pivot_table(&source(), 0, 1, 5).unwrap();
+    let pivot_table = PivotTable {
+        row_fields: vec!["Product".to_string()],
+        value_fields: vec![
+            sum_of_sales(),
+            PivotValueField {
+                field: "Sales".to_string(),
+                function: PivotFunction::Average,
+                name: "".to_string(),
+            },
+            PivotValueField {
+                field: "Region".to_string(),
+                function: PivotFunction::Count,
+                name: "".to_string(),
+            },
+        ],
+        ..model.get_pivot_table(0, &name).unwrap()
+    };
+    model.update_pivot_table(0, &name, pivot_table).unwrap();
+    assert_eq!(
+        values(&model, 1, 5, 5, 8),
+        vec![
+            vec!["", "Values", "", ""],
+            vec![
+                "Product",
+                "Sum of Sales",
+                "Average of Sales",
+                "Count of Region"
+            ],
+            vec!["Apple", "46", "11.5", "4"],
+            vec!["Pear", "27", "13.5", "2"],
+            vec!["Grand Total", "73", "12.166666667", "6"],
+        ]
+    );
+}
+
+#[test]
+fn only_values() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &[], &[]);
+    assert_eq!(
+        values(&model, 1, 5, 2, 6),
+        vec![vec!["", "Sum of Sales"], vec!["Sum of Sales", "73"]]
+    );
+}
+
+#[test]
+fn filters() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Product"], &[]);
+    let pivot_table = PivotTable {
+        filters: vec![PivotFilter {
+            field: "Region".to_string(),
+            hidden_items: vec!["west".to_string()],
+        }],
+        ..model.get_pivot_table(0, "PivotTable1").unwrap()
+    };
+    model
+        .update_pivot_table(0, "PivotTable1", pivot_table)
+        .unwrap();
+    assert_eq!(
+        values(&model, 1, 5, 4, 6),
+        vec![
+            vec!["Product", "Sum of Sales"],
+            vec!["Apple", "11"],
+            vec!["Pear", "27"],
+            vec!["Grand Total", "38"],
+        ]
+    );
+}
+
+#[test]
+fn refresh() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    model.set_user_input(0, 2, 3, "100").unwrap();
+    model.set_user_input(0, 7, 1, "South").unwrap();
+    // The pivot table does not change until it is refreshed
+    assert_eq!(model.get_formatted_cell_value(0, 2, 6).unwrap(), "31");
+
+    model.refresh_pivot_table(0, "PivotTable1").unwrap();
+    assert_eq!(
+        values(&model, 1, 5, 5, 6),
+        vec![
+            vec!["Region", "Sum of Sales"],
+            vec!["East", "121"],
+            vec!["South", "7"],
+            vec!["West", "35"],
+            vec!["Grand Total", "163"],
+        ]
+    );
+    model.undo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 2, 6).unwrap(), "31");
+    assert_eq!(model.get_formatted_cell_value(0, 3, 5).unwrap(), "North");
+}
+
+#[test]
+fn delete_pivot_table() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    model.delete_pivot_table(0, "PivotTable1").unwrap();
+    assert!(model.get_pivot_tables(0).unwrap().is_empty());
+    assert_eq!(model.get_formatted_cell_value(0, 5, 6).unwrap(), "");
+
+    model.undo().unwrap();
+    assert_eq!(model.get_pivot_tables(0).unwrap().len(), 1);
+    assert_eq!(model.get_formatted_cell_value(0, 5, 6).unwrap(), "73");
+}
+
+#[test]
+fn rename_pivot_table() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    let pivot_table = PivotTable {
+        name: "Sales".to_string(),
+        ..model.get_pivot_table(0, "PivotTable1").unwrap()
+    };
+    model
+        .update_pivot_table(0, "PivotTable1", pivot_table)
+        .unwrap();
+    assert!(model.get_pivot_table(0, "PivotTable1").is_err());
+    assert_eq!(
+        model.get_pivot_table(0, "Sales").unwrap().reference,
+        Some("E1:F5".to_string())
+    );
+}
+
+#[test]
+fn errors() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    let name = model.add_pivot_table(&source(), 0, 1, 5).unwrap();
+    let pivot_table = model.get_pivot_table(0, &name).unwrap();
+
+    let unknown_field = PivotTable {
+        row_fields: vec!["Color".to_string()],
+        ..pivot_table.clone()
+    };
+    assert_eq!(
+        model.update_pivot_table(0, &name, unknown_field),
+        Err("Field not found: 'Color'".to_string())
+    );
+
+    model.set_user_input(0, 3, 6, "Something").unwrap();
+    let in_the_way = PivotTable {
+        row_fields: vec!["Region".to_string()],
+        value_fields: vec![sum_of_sales()],
+        ..pivot_table.clone()
+    };
+    assert_eq!(
+        model.update_pivot_table(0, &name, in_the_way),
+        Err("There is already data in the cells of the pivot table".to_string())
+    );
+
+    let overlapping = PivotTable {
+        location: "B2".to_string(),
+        row_fields: vec!["Region".to_string()],
+        ..pivot_table
+    };
+    assert_eq!(
+        model.update_pivot_table(0, &name, overlapping),
+        Err("A pivot table cannot overlap its source".to_string())
+    );
+
+    model.set_user_input(0, 1, 2, "").unwrap();
+    assert_eq!(
+        model.add_pivot_table(&source(), 0, 1, 10),
+        Err("The names of the fields of a pivot table cannot be empty".to_string())
+    );
+}
+
+#[test]
+fn table_source() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    let table = model.add_table(&area(0, 1, 1, 3, 7), true).unwrap();
+    let source = PivotSource::Table { name: table };
+    let name = model.add_pivot_table(&source, 0, 1, 5).unwrap();
+    let pivot_table = PivotTable {
+        row_fields: vec!["Product".to_string()],
+        value_fields: vec![sum_of_sales()],
+        ..model.get_pivot_table(0, &name).unwrap()
+    };
+    model.update_pivot_table(0, &name, pivot_table).unwrap();
+    assert_eq!(
+        values(&model, 1, 5, 4, 6),
+        vec![
+            vec!["Product", "Sum of Sales"],
+            vec!["Apple", "46"],
+            vec!["Pear", "27"],
+            vec!["Grand Total", "73"],
+        ]
+    );
+}
+
+#[test]
+fn protected_sheet() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    model
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    assert_eq!(
+        model.add_pivot_table(&source(), 0, 1, 5),
+        Err("Cannot add a pivot table: the sheet is protected".to_string())
+    );
+
+    let protection = SheetProtection {
+        allow_pivot_tables: true,
+        ..Default::default()
+    };
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    model.protect_sheet(0, protection, None).unwrap();
+    model.add_pivot_table(&source(), 0, 1, 5).unwrap();
+}
+
+#[test]
+fn getpivotdata() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &["Product"]);
+    model
+        .set_user_input(0, 10, 1, r#"=GETPIVOTDATA("Sales",E1,"Region","East")"#)
+        .unwrap();
+    model
+        .set_user_input(0, 11, 1, r#"=GETPIVOTDATA("Sum of Sales",$F$3)"#)
+        .unwrap();
+    model
+        .set_user_input(
+            0,
+            12,
+            1,
+            r#"=GETPIVOTDATA("Sales",E1,"Region","west","Product","Apple")"#,
+        )
+        .unwrap();
+    model
+        .set_user_input(0, 13, 1, r#"=GETPIVOTDATA("Sales",E1,"Region","South")"#)
+        .unwrap();
+    model
+        .set_user_input(0, 14, 1, r#"=GETPIVOTDATA("Sales",A1,"Region","East")"#)
+        .unwrap();
+    model
+        .set_user_input(0, 15, 1, r#"=GETPIVOTDATA("Sales",E1,"Sales",10)"#)
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 10, 1).unwrap(), "31");
+    assert_eq!(model.get_formatted_cell_value(0, 11, 1).unwrap(), "73");
+    assert_eq!(model.get_formatted_cell_value(0, 12, 1).unwrap(), "35");
+    assert_eq!(model.get_formatted_cell_value(0, 13, 1).unwrap(), "#REF!");
+    assert_eq!(model.get_formatted_cell_value(0, 14, 1).unwrap(), "#REF!");
+    // Only the fields in the rows and columns of the pivot table can be used
+    assert_eq!(model.get_formatted_cell_value(0, 15, 1).unwrap(), "#REF!");
+}
+
+/// Returns the source, location and rendered range of the pivot table "PivotTable1"
+fn position(model: &UserModel) -> (PivotSource, String, Option<String>) {
+    let pivot_table = model.get_pivot_table(0, "PivotTable1").unwrap();
+    (
+        pivot_table.source,
+        pivot_table.location,
+        pivot_table.reference,
+    )
+}
+
+fn range_source(reference: &str) -> PivotSource {
+    PivotSource::Range {
+        sheet_name: "Sheet1".to_string(),
+        reference: reference.to_string(),
+    }
+}
+
+#[test]
+fn sources_follow_renames() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    let table = model.add_table(&area(0, 1, 1, 3, 7), true).unwrap();
+    let table_source = PivotSource::Table {
+        name: table.clone(),
+    };
+    let name = model.add_pivot_table(&table_source, 0, 1, 10).unwrap();
+
+    model.rename_sheet(0, "Data").unwrap();
+    assert_eq!(
+        model.get_pivot_table(0, "PivotTable1").unwrap().source,
+        PivotSource::Range {
+            sheet_name: "Data".to_string(),
+            reference: "A1:C7".to_string(),
+        }
+    );
+    model.undo().unwrap();
+    assert_eq!(position(&model).0, source());
+
+    model.rename_table(&table, "Sales").unwrap();
+    assert_eq!(
+        model.get_pivot_table(0, &name).unwrap().source,
+        PivotSource::Table {
+            name: "Sales".to_string()
+        }
+    );
+    model.refresh_pivot_table(0, &name).unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_pivot_table(0, &name).unwrap().source,
+        table_source
+    );
+}
+
+#[test]
+fn insert_and_delete_rows_and_columns() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    model.insert_row(0, 3).unwrap();
+    model.insert_column(0, 1).unwrap();
+    assert_eq!(
+        position(&model),
+        (
+            range_source("B1:D8"),
+            "F1".to_string(),
+            Some("F1:G6".to_string())
+        )
+    );
+    model.refresh_pivot_table(0, "PivotTable1").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 6, 7).unwrap(), "73");
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        position(&model),
+        (source(), "E1".to_string(), Some("E1:F5".to_string()))
+    );
+
+    // The pivot table is removed with its top left cell
+    model.delete_row(0, 1).unwrap();
+    assert!(model.get_pivot_tables(0).unwrap().is_empty());
+    model.undo().unwrap();
+    assert_eq!(
+        position(&model),
+        (source(), "E1".to_string(), Some("E1:F5".to_string()))
+    );
+
+    for _ in 0..3 {
+        model.delete_column(0, 1).unwrap();
+    }
+    assert_eq!(
+        position(&model),
+        (
+            range_source("#REF!"),
+            "B1".to_string(),
+            Some("B1:C5".to_string())
+        )
+    );
+    assert!(model.refresh_pivot_table(0, "PivotTable1").is_err());
+}
+
+#[test]
+fn move_rows_columns_and_ranges() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    model.move_columns(0, 1, 3, 10).unwrap();
+    assert_eq!(
+        position(&model),
+        (
+            range_source("K1:M7"),
+            "B1".to_string(),
+            Some("B1:C5".to_string())
+        )
+    );
+    model.refresh_pivot_table(0, "PivotTable1").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 3).unwrap(), "73");
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        position(&model),
+        (source(), "E1".to_string(), Some("E1:F5".to_string()))
+    );
+
+    model.move_rows(0, 1, 7, 2).unwrap();
+    assert_eq!(
+        position(&model),
+        (
+            range_source("A3:C9"),
+            "E3".to_string(),
+            Some("E3:F7".to_string())
+        )
+    );
+    model.undo().unwrap();
+
+    model.move_range(&area(0, 1, 1, 3, 7), 10, 1).unwrap();
+    assert_eq!(
+        position(&model),
+        (
+            range_source("A10:C16"),
+            "E1".to_string(),
+            Some("E1:F5".to_string())
+        )
+    );
+}
+
+#[test]
+fn changes_drop_the_xlsx_parts() {
+    let mut model = UserModel::from_model(new_model_with_data(SALES));
+    add_pivot_table(&mut model, &["Region"], &[]);
+    let pivot_table = PivotTable {
+        xlsx_parts: Some(PivotTableParts {
+            definition: "<pivotTableDefinition/>".to_string(),
+            cache_definition: "<pivotCacheDefinition/>".to_string(),
+            cache_records: None,
+        }),
+        ..model.get_pivot_table(0, "PivotTable1").unwrap()
+    };
+    model
+        .model
+        .set_pivot_table(0, "PivotTable1", Some(pivot_table))
+        .unwrap();
+
+    // Rows below the pivot table and its source change nothing
+    model.insert_row(0, 20).unwrap();
+    assert!(model
+        .get_pivot_table(0, "PivotTable1")
+        .unwrap()
+        .xlsx_parts
+        .is_some());
+
+    model.rename_sheet(0, "Data").unwrap();
+    assert!(model
+        .get_pivot_table(0, "PivotTable1")
+        .unwrap()
+        .xlsx_parts
+        .is_none());
+    model.undo().unwrap();
+
+    model.insert_row(0, 2).unwrap();
+    assert!(model
+        .get_pivot_table(0, "PivotTable1")
+        .unwrap()
+        .xlsx_parts
+        .is_none());
+}
@@ -0,0 +1,126 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    test::util::{area, new_empty_model},
+    types::SheetProtection,
+    UserModel,
+};
+
+#[test]
+fn only_unlocked_cells_can_be_edited() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Price").unwrap();
+    model
+        .update_range_style(&area(0, 1, 2, 1, 1), "protection.locked", "false")
+        .unwrap();
+    model
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    // Edits done before protecting the sheet cannot be undone
+    assert!(!model.can_undo());
+
+    model.set_user_input(0, 1, 2, "10").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "10");
+    let locked_error = Err("Cannot change locked cells: the sheet is protected".to_string());
+    assert_eq!(model.set_user_input(0, 1, 1, "Cost"), locked_error);
+    assert_eq!(
+        model.range_clear_contents(&area(0, 1, 1, 2, 1)),
+        locked_error
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "Price");
+    model.range_clear_contents(&area(0, 1, 2, 1, 1)).unwrap();
+
+    assert_eq!(
+        model.update_range_style(&area(0, 1, 2, 1, 1), "font.b", "true"),
+        Err("Cannot format cells: the sheet is protected".to_string())
+    );
+    assert_eq!(
+        model.update_range_style(&area(0, 1, 1, 1, 1), "protection.locked", "false"),
+        Err("Cannot change the protection of cells: the sheet is protected".to_string())
+    );
+    assert_eq!(
+        model.insert_row(0, 3),
+        Err("Cannot insert rows: the sheet is protected".to_string())
+    );
+    assert_eq!(
+        model.set_column_width(0, 2, 200.0),
+        Err("Cannot format columns: the sheet is protected".to_string())
+    );
+    assert!(model.merge_cells(&area(0, 3, 3, 2, 2)).is_err());
+    assert!(model.add_comment(0, 1, 1, "Hi", "Me", None).is_err());
+
+    model.unprotect_sheet(0, None).unwrap();
+    model.set_user_input(0, 1, 1, "Cost").unwrap();
+    model.insert_row(0, 3).unwrap();
+}
+
+#[test]
+fn allowed_actions() {
+    let mut model = UserModel::from_model(new_empty_model());
+    let protection = SheetProtection {
+        allow_format_cells: true,
+        allow_insert_rows: true,
+        allow_format_columns: true,
+        ..Default::default()
+    };
+    model.protect_sheet(0, protection, None).unwrap();
+    let width = model.get_column_width(0, 2).unwrap();
+    model
+        .update_range_style(&area(0, 1, 1, 1, 1), "font.b", "true")
+        .unwrap();
+    model.insert_row(0, 3).unwrap();
+    model.set_column_width(0, 2, 200.0).unwrap();
+    assert!(model.delete_row(0, 3).is_err());
+    model.undo().unwrap();
+    assert_eq!(model.get_column_width(0, 2).unwrap(), width);
+}
+
+#[test]
+fn hidden_cells() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "=2+3").unwrap();
+    model
+        .update_range_style(&area(0, 1, 1, 1, 1), "protection.hidden", "true")
+        .unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1).unwrap(), "=2+3");
+    model
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1).unwrap(), "");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "5");
+}
+
+#[test]
+fn workbook_structure() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.protect_workbook(true, false, None).unwrap();
+    let error =
+        Err("Cannot change the sheets: the structure of the workbook is protected".to_string());
+    assert_eq!(model.new_sheet(), error);
+    assert_eq!(model.rename_sheet(0, "Data"), error);
+    assert_eq!(model.delete_sheet(0), error);
+    // Cells can still be edited
+    model.set_user_input(0, 1, 1, "42").unwrap();
+
+    model.unprotect_workbook(None).unwrap();
+    model.new_sheet().unwrap();
+}
+
+#[test]
+fn protection_is_sent_to_peers() {
+    let mut model1 = UserModel::from_model(new_empty_model());
+    model1
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    model1.protect_workbook(true, true, None).unwrap();
+    let send_queue = model1.flush_send_queue();
+
+    let mut model2 = UserModel::from_model(new_empty_model());
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert_eq!(
+        model2.get_sheet_protection(0).unwrap(),
+        Some(SheetProtection::default())
+    );
+    assert!(model2.set_user_input(0, 1, 1, "1").is_err());
+    assert!(model2.get_workbook_protection().unwrap().lock_structure);
+}
@@ -0,0 +1,51 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    test::util::new_empty_model,
+    types::{Font, TextRun},
+    UserModel,
+};
+
+#[test]
+fn set_rich_text_undo_redo() {
+    let mut model = UserModel::from_model(new_empty_model());
+    let runs = vec![
+        TextRun {
+            text: "Bold".to_string(),
+            font: Some(Font {
+                b: true,
+                ..Default::default()
+            }),
+        },
+        TextRun {
+            text: " text".to_string(),
+            font: None,
+        },
+    ];
+    model.set_user_input(0, 1, 1, "Plain").unwrap();
+    model.set_user_input(0, 1, 2, "=LEN(A1)").unwrap();
+    model.set_cell_rich_text(0, 1, 1, runs.clone()).unwrap();
+    assert_eq!(
+        model.get_cell_rich_text(0, 1, 1).unwrap(),
+        Some(runs.clone())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "9");
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), None);
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "Plain");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "5");
+
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_cell_rich_text(0, 1, 1).unwrap(),
+        Some(runs.clone())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "9");
+
+    // Typing in the cell replaces the rich text and undo brings it back
+    model.set_user_input(0, 1, 1, "Bold text").unwrap();
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), None);
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), Some(runs));
+}
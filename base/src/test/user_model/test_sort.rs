@@ -0,0 +1,272 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    test::util::{area, new_empty_model},
+    types::{CellType, SheetProtection, SortBy, SortKey, Table, TableColumn, TableStyleInfo},
+    UserModel,
+};
+
+fn key(column: i32, descending: bool) -> SortKey {
+    SortKey {
+        column,
+        descending,
+        sort_by: SortBy::Value,
+    }
+}
+
+fn column_values(
+    model: &UserModel,
+    column: i32,
+    rows: std::ops::RangeInclusive<i32>,
+) -> Vec<String> {
+    rows.map(|row| model.get_formatted_cell_value(0, row, column).unwrap())
+        .collect()
+}
+
+fn set_column(model: &mut UserModel, column: i32, first_row: i32, values: &[&str]) {
+    for (index, value) in values.iter().enumerate() {
+        model
+            .set_user_input(0, first_row + index as i32, column, value)
+            .unwrap();
+    }
+}
+
+#[test]
+fn sort_by_value() {
+    let mut model = UserModel::from_model(new_empty_model());
+    set_column(
+        &mut model,
+        1,
+        1,
+        &["banana", "10", "", "TRUE", "Apple", "=1/0", "2", "cherry"],
+    );
+    model
+        .sort_range(&area(0, 1, 1, 1, 8), &[key(1, false)], false)
+        .unwrap();
+    assert_eq!(
+        column_values(&model, 1, 1..=8),
+        vec!["2", "10", "Apple", "banana", "cherry", "TRUE", "#DIV/0!", ""]
+    );
+
+    // Empty cells go last when sorting in descending order too
+    model
+        .sort_range(&area(0, 1, 1, 1, 8), &[key(1, true)], false)
+        .unwrap();
+    assert_eq!(
+        column_values(&model, 1, 1..=8),
+        vec!["#DIV/0!", "TRUE", "cherry", "banana", "Apple", "10", "2", ""]
+    );
+}
+
+#[test]
+fn sort_keeps_text_that_looks_like_a_number() {
+    let mut model = UserModel::from_model(new_empty_model());
+    set_column(&mut model, 1, 1, &["'123", "5", "'2"]);
+    model
+        .sort_range(&area(0, 1, 1, 1, 3), &[key(1, false)], false)
+        .unwrap();
+    // Numbers go before text
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("5".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 1), Ok("123".to_string()));
+    assert_eq!(model.get_cell_type(0, 2, 1), Ok(CellType::Text));
+    assert_eq!(model.get_cell_type(0, 3, 1), Ok(CellType::Text));
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_type(0, 1, 1), Ok(CellType::Text));
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_type(0, 2, 1), Ok(CellType::Text));
+}
+
+#[test]
+fn sort_multiple_keys_with_header() {
+    let mut model = UserModel::from_model(new_empty_model());
+    set_column(
+        &mut model,
+        1,
+        1,
+        &["Name", "Bob", "Ann", "Bob", "Ann", "Cid"],
+    );
+    set_column(&mut model, 2, 1, &["Score", "3", "5", "7", "1", "4"]);
+    // The formulas reference their own row
+    set_column(
+        &mut model,
+        3,
+        1,
+        &["Double", "=B2*2", "=B3*2", "=B4*2", "=B5*2", "=B6*2"],
+    );
+    model.set_user_input(0, 8, 1, "=SUM(C2:C6)").unwrap();
+    model
+        .update_range_style(&area(0, 3, 1, 1, 1), "font.b", "true")
+        .unwrap();
+
+    model
+        .sort_range(&area(0, 1, 1, 3, 6), &[key(1, false), key(2, true)], true)
+        .unwrap();
+    assert_eq!(
+        column_values(&model, 1, 1..=6),
+        vec!["Name", "Ann", "Ann", "Bob", "Bob", "Cid"]
+    );
+    assert_eq!(
+        column_values(&model, 2, 2..=6),
+        vec!["5", "1", "7", "3", "4"]
+    );
+    assert_eq!(
+        column_values(&model, 3, 2..=6),
+        vec!["10", "2", "14", "6", "8"]
+    );
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "=B3*2");
+    // References from outside the range are not changed
+    assert_eq!(model.get_cell_content(0, 8, 1).unwrap(), "=SUM(C2:C6)");
+    assert_eq!(model.get_formatted_cell_value(0, 8, 1).unwrap(), "40");
+    // The style moves with the cell
+    assert!(model.get_cell_style(0, 2, 1).unwrap().font.b);
+    assert!(!model.get_cell_style(0, 3, 1).unwrap().font.b);
+
+    model.undo().unwrap();
+    assert_eq!(
+        column_values(&model, 1, 1..=6),
+        vec!["Name", "Bob", "Ann", "Bob", "Ann", "Cid"]
+    );
+    assert!(model.get_cell_style(0, 3, 1).unwrap().font.b);
+    assert_eq!(model.get_cell_content(0, 2, 3).unwrap(), "=B2*2");
+
+    model.redo().unwrap();
+    assert_eq!(
+        column_values(&model, 2, 2..=6),
+        vec!["5", "1", "7", "3", "4"]
+    );
+    assert!(model.get_cell_style(0, 2, 1).unwrap().font.b);
+}
+
+#[test]
+fn sort_by_color_and_custom_list() {
+    let mut model = UserModel::from_model(new_empty_model());
+    set_column(
+        &mut model,
+        1,
+        1,
+        &["Low", "High", "Medium", "Other", "High"],
+    );
+    model
+        .update_range_style(&area(0, 3, 1, 1, 1), "fill.fg_color", "#FFFF00")
+        .unwrap();
+    model
+        .update_range_style(&area(0, 5, 1, 1, 1), "fill.fg_color", "#FFFF00")
+        .unwrap();
+
+    let by_color = SortKey {
+        column: 1,
+        descending: false,
+        sort_by: SortBy::CellColor {
+            color: "#ffff00".to_string(),
+        },
+    };
+    model
+        .sort_range(&area(0, 1, 1, 1, 5), &[by_color], false)
+        .unwrap();
+    assert_eq!(
+        column_values(&model, 1, 1..=5),
+        vec!["Medium", "High", "Low", "High", "Other"]
+    );
+
+    let by_list = SortKey {
+        column: 1,
+        descending: false,
+        sort_by: SortBy::CustomList {
+            list: vec!["high".to_string(), "medium".to_string(), "low".to_string()],
+        },
+    };
+    model
+        .sort_range(&area(0, 1, 1, 1, 5), &[by_list], false)
+        .unwrap();
+    assert_eq!(
+        column_values(&model, 1, 1..=5),
+        vec!["High", "High", "Medium", "Low", "Other"]
+    );
+    assert_eq!(
+        model.get_cell_style(0, 3, 1).unwrap().fill.fg_color,
+        Some("#FFFF00".to_string())
+    );
+}
+
+#[test]
+fn sort_table() {
+    let mut base = new_empty_model();
+    base.workbook.tables.insert(
+        "Scores".to_string(),
+        Table {
+            name: "Scores".to_string(),
+            display_name: "Scores".to_string(),
+            sheet_name: "Sheet1".to_string(),
+            reference: "A1:A5".to_string(),
+            totals_row_count: 1,
+            header_row_count: 1,
+            header_row_dxf_id: None,
+            data_dxf_id: None,
+            totals_row_dxf_id: None,
+            columns: vec![TableColumn {
+                id: 1,
+                name: "Score".to_string(),
+                ..Default::default()
+            }],
+            style_info: TableStyleInfo::default(),
+            has_filters: false,
+            filter_columns: vec![],
+        },
+    );
+    let mut model = UserModel::from_model(base);
+    set_column(&mut model, 1, 1, &["Score", "3", "1", "2", "=SUM(A2:A4)"]);
+    model.sort_table("Scores", &[key(1, true)]).unwrap();
+    assert_eq!(
+        column_values(&model, 1, 1..=5),
+        vec!["Score", "3", "2", "1", "6"]
+    );
+    assert_eq!(
+        model.sort_table("Other", &[key(1, true)]),
+        Err("Table not found: 'Other'".to_string())
+    );
+}
+
+#[test]
+fn sort_errors() {
+    let mut model = UserModel::from_model(new_empty_model());
+    set_column(&mut model, 1, 1, &["2", "1"]);
+    assert_eq!(
+        model.sort_range(&area(0, 1, 1, 1, 2), &[], false),
+        Err("At least one sort key is needed".to_string())
+    );
+    assert_eq!(
+        model.sort_range(&area(0, 1, 1, 1, 2), &[key(2, false)], false),
+        Err("Invalid sort column: '2'".to_string())
+    );
+
+    model.merge_cells(&area(0, 1, 2, 1, 2)).unwrap();
+    assert_eq!(
+        model.sort_range(&area(0, 1, 1, 2, 2), &[key(1, false)], false),
+        Err("Cannot sort a range with merged cells".to_string())
+    );
+
+    model
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    assert_eq!(
+        model.sort_range(&area(0, 1, 1, 1, 2), &[key(1, false)], false),
+        Err("Cannot sort: the sheet is protected".to_string())
+    );
+    model.unprotect_sheet(0, None).unwrap();
+    model
+        .protect_sheet(
+            0,
+            SheetProtection {
+                allow_sort: true,
+                ..Default::default()
+            },
+            None,
+        )
+        .unwrap();
+    // The cells are locked
+    assert!(model
+        .sort_range(&area(0, 1, 1, 1, 2), &[key(1, false)], false)
+        .is_err());
+    assert_eq!(column_values(&model, 1, 1..=2), vec!["2", "1"]);
+}
@@ -249,6 +249,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Thin,
             color: Some("#F1F1F1".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -261,6 +262,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Thin,
             color: None,
+            color_theme: None,
         })
     );
 
@@ -273,6 +275,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Dotted,
             color: Some("#F1F1F2".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -285,6 +288,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Double,
             color: Some("#F1F1F3".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -297,6 +301,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Medium,
             color: Some("#F1F1F4".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -320,6 +325,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Thin,
             color: None,
+            color_theme: None,
         })
     );
     assert_eq!(
@@ -327,6 +333,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Dotted,
             color: Some("#F1F1F2".to_owned()),
+            color_theme: None,
         })
     );
     assert_eq!(
@@ -334,6 +341,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Double,
             color: Some("#F1F1F3".to_owned()),
+            color_theme: None,
         })
     );
     assert_eq!(
@@ -341,6 +349,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Medium,
             color: Some("#F1F1F4".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -355,6 +364,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Thin,
             color: None,
+            color_theme: None,
         })
     );
     assert_eq!(
@@ -362,6 +372,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Dotted,
             color: Some("#F1F1F2".to_owned()),
+            color_theme: None,
         })
     );
     assert_eq!(
@@ -369,6 +380,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Double,
             color: Some("#F1F1F3".to_owned()),
+            color_theme: None,
         })
     );
     assert_eq!(
@@ -376,6 +388,7 @@ fn basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Medium,
             color: Some("#F1F1F4".to_owned()),
+            color_theme: None,
         })
     );
 }
@@ -587,6 +600,7 @@ fn more_basic_borders() {
         Some(BorderItem {
             style: BorderStyle::Thick,
             color: Some("#F1F1F1".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -599,6 +613,7 @@ fn more_basic_borders() {
         Some(BorderItem {
             style: BorderStyle::SlantDashDot,
             color: Some("#F1F1F1".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -611,6 +626,7 @@ fn more_basic_borders() {
         Some(BorderItem {
             style: BorderStyle::MediumDashDot,
             color: Some("#F1F1F1".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -623,6 +639,7 @@ fn more_basic_borders() {
         Some(BorderItem {
             style: BorderStyle::MediumDashDotDot,
             color: Some("#F1F1F1".to_owned()),
+            color_theme: None,
         })
     );
 
@@ -635,6 +652,7 @@ fn more_basic_borders() {
         Some(BorderItem {
             style: BorderStyle::MediumDashed,
             color: Some("#F1F1F1".to_owned()),
+            color_theme: None,
         })
     );
 }
@@ -695,6 +713,7 @@ fn empty_removes_border() {
         Some(BorderItem {
             style: BorderStyle::MediumDashDotDot,
             color: Some("#F1F1F1".to_owned()),
+            color_theme: None,
         })
     );
 
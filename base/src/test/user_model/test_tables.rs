@@ -0,0 +1,656 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::{
+    test::util::{area, new_empty_model, new_model_with_data},
+    types::{ConditionalFormat, ConditionalFormatRule, SheetProtection, TotalsRowFunction},
+    UserModel,
+};
+
+fn column_names(model: &UserModel, table: &str) -> Vec<String> {
+    model
+        .get_table(table)
+        .unwrap()
+        .columns
+        .iter()
+        .map(|column| column.name.clone())
+        .collect()
+}
+
+const FRUITS: &[&[&str]] = &[&["Fruit", "Price"], &["Apple", "3"], &["Pear", "5"]];
+
+#[test]
+fn add_table_with_header() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    let name = model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    assert_eq!(name, "Table1");
+
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.reference, "A1:B3");
+    assert_eq!(table.sheet_name, "Sheet1");
+    assert_eq!(table.header_row_count, 1);
+    assert_eq!(table.totals_row_count, 0);
+    assert_eq!(column_names(&model, "Table1"), vec!["Fruit", "Price"]);
+    assert_eq!(model.get_tables(0).unwrap(), vec![table]);
+
+    model.undo().unwrap();
+    assert!(model.get_tables(0).unwrap().is_empty());
+    model.redo().unwrap();
+    assert_eq!(model.get_tables(0).unwrap().len(), 1);
+}
+
+#[test]
+fn add_table_without_header() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.set_user_input(0, 1, 3, "Price").unwrap();
+    model.add_table(&area(0, 2, 1, 3, 2), false).unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.header_row_count, 0);
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Column1", "Column2", "Column3"]
+    );
+    // The cells of the table are not changed
+    assert_eq!(model.get_formatted_cell_value(0, 2, 1).unwrap(), "Apple");
+}
+
+#[test]
+fn empty_and_repeated_headers() {
+    let mut model = UserModel::from_model(new_empty_model());
+    model.set_user_input(0, 1, 1, "Name").unwrap();
+    model.set_user_input(0, 1, 2, "name").unwrap();
+    model.set_user_input(0, 1, 4, "2024").unwrap();
+    model.add_table(&area(0, 1, 1, 4, 1), true).unwrap();
+
+    // A range with only the header row gets one row of data
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.reference, "A1:D2");
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Name", "name2", "Column3", "2024"]
+    );
+    // The header cells have the names of the columns
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "name2");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 3).unwrap(), "Column3");
+
+    model.undo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "name");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 3).unwrap(), "");
+}
+
+#[test]
+fn invalid_ranges() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    assert_eq!(
+        model.add_table(&area(0, 3, 2, 2, 2), true),
+        Err("Tables cannot overlap".to_string())
+    );
+
+    model.merge_cells(&area(0, 10, 1, 2, 1)).unwrap();
+    assert_eq!(
+        model.add_table(&area(0, 9, 1, 2, 3), true),
+        Err("A table cannot contain merged cells".to_string())
+    );
+
+    model.add_auto_filter(&area(0, 20, 1, 2, 3)).unwrap();
+    assert_eq!(
+        model.add_table(&area(0, 21, 2, 2, 3), true),
+        Err("A table cannot overlap the autofilter of the sheet".to_string())
+    );
+
+    assert_eq!(
+        model.add_table(&area(0, 5, 5, 2, 2), true).unwrap(),
+        "Table2"
+    );
+}
+
+#[test]
+fn rename_table() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model
+        .set_user_input(0, 5, 1, "=SUM(Table1[Price])")
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 1).unwrap(), "8");
+
+    model.rename_table("Table1", "Fruits").unwrap();
+    assert!(model.get_table("Table1").is_err());
+    let table = model.get_table("Fruits").unwrap();
+    assert_eq!(table.display_name, "Fruits");
+    // Existing formulas keep working
+    model.set_user_input(0, 2, 2, "10").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 1).unwrap(), "15");
+    model
+        .set_user_input(0, 6, 1, "=SUM(Fruits[Price])")
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 6, 1).unwrap(), "15");
+
+    assert_eq!(
+        model.rename_table("Fruits", "A1"),
+        Err("Invalid table name: 'A1'".to_string())
+    );
+    model.add_table(&area(0, 10, 1, 2, 2), false).unwrap();
+    assert_eq!(
+        model.rename_table("Table1", "fruits"),
+        Err("Name already in use: 'fruits'".to_string())
+    );
+
+    // Undo the new table, the two edits and the rename
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(model.get_table("Fruits").is_err());
+    assert!(model.get_table("Table1").is_ok());
+}
+
+#[test]
+fn renames_update_formulas_kept_as_text() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.set_table_totals_row("Table1", true).unwrap();
+    // Like in an imported workbook, the custom formula of the totals row is kept as text
+    let mut table = model.get_table("Table1").unwrap();
+    table.columns[1].totals_row_function = Some(TotalsRowFunction::Custom);
+    table.columns[1].totals_row_formula = Some("MAX(Table1[Price])*2".to_string());
+    model.model.set_table("Table1", Some(table)).unwrap();
+    model
+        .add_conditional_format(
+            0,
+            ConditionalFormat {
+                sqref: "B2:B3".to_string(),
+                rule: ConditionalFormatRule::Expression {
+                    formula: "Table1[[#This Row],[Price]]>4".to_string(),
+                },
+                style: None,
+                stop_if_true: false,
+            },
+        )
+        .unwrap();
+    model
+        .set_user_input(0, 6, 1, "=SUM(Table1[Price])")
+        .unwrap();
+
+    model
+        .rename_table_column("Table1", "Price", "Cost")
+        .unwrap();
+    model.rename_table("Table1", "Fruits").unwrap();
+    let totals_row_formula = |model: &UserModel, table: &str| {
+        model.get_table(table).unwrap().columns[1]
+            .totals_row_formula
+            .clone()
+    };
+    assert_eq!(
+        totals_row_formula(&model, "Fruits"),
+        Some("MAX(Fruits[[Cost]])*2".to_string())
+    );
+    assert_eq!(
+        model.get_conditional_formats(0).unwrap()[0].rule,
+        ConditionalFormatRule::Expression {
+            formula: "Fruits[[#This Row],[Cost]]>4".to_string()
+        }
+    );
+    // Formulas in cells were resolved to ranges when entered
+    assert_eq!(model.get_cell_content(0, 6, 1).unwrap(), "=SUM($B$2:$B$3)");
+    // The renamed references can be used
+    model.set_user_input(0, 7, 1, "=MIN(Fruits[Cost])").unwrap();
+    assert_eq!(model.get_cell_content(0, 7, 1).unwrap(), "=MIN($B$2:$B$3)");
+    assert_eq!(model.get_formatted_cell_value(0, 7, 1).unwrap(), "3");
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 7, 1).unwrap(), "");
+    assert_eq!(
+        totals_row_formula(&model, "Table1"),
+        Some("MAX(Table1[Price])*2".to_string())
+    );
+    assert_eq!(
+        model.get_conditional_formats(0).unwrap()[0].rule,
+        ConditionalFormatRule::Expression {
+            formula: "Table1[[#This Row],[Price]]>4".to_string()
+        }
+    );
+    model.redo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(
+        totals_row_formula(&model, "Fruits"),
+        Some("MAX(Fruits[[Cost]])*2".to_string())
+    );
+}
+
+#[test]
+fn unknown_table_in_formula() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model
+        .set_user_input(0, 5, 1, "=SUM(Table1[Price])")
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 1).unwrap(), "#ERROR!");
+}
+
+#[test]
+fn delete_table() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.delete_table("Table1").unwrap();
+    assert!(model.get_tables(0).unwrap().is_empty());
+    // The cells are kept
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "Fruit");
+    assert_eq!(model.get_formatted_cell_value(0, 3, 2).unwrap(), "5");
+    assert_eq!(
+        model.delete_table("Table1"),
+        Err("Table not found: 'Table1'".to_string())
+    );
+    model.undo().unwrap();
+    assert!(model.get_table("Table1").is_ok());
+}
+
+#[test]
+fn resize_table() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.set_user_input(0, 1, 3, "Stock").unwrap();
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+
+    model.resize_table("Table1", &area(0, 1, 1, 4, 5)).unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.reference, "A1:D5");
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Fruit", "Price", "Stock", "Column4"]
+    );
+    let ids: Vec<u32> = table.columns.iter().map(|column| column.id).collect();
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+    assert_eq!(model.get_formatted_cell_value(0, 1, 4).unwrap(), "Column4");
+
+    model.resize_table("Table1", &area(0, 1, 2, 1, 2)).unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "B1:B2");
+    assert_eq!(column_names(&model, "Table1"), vec!["Price"]);
+
+    assert_eq!(
+        model.resize_table("Table1", &area(0, 2, 2, 1, 2)),
+        Err("The header row of a table cannot move".to_string())
+    );
+    assert_eq!(
+        model.resize_table("Table1", &area(0, 1, 8, 1, 3)),
+        Err("The new range must overlap the table".to_string())
+    );
+    assert_eq!(
+        model.resize_table("Table1", &area(0, 1, 2, 1, 1)),
+        Err("A table needs at least one row of data".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:D5");
+    model.undo().unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B3");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 4).unwrap(), "");
+}
+
+#[test]
+fn add_and_rename_columns() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.add_table_column("Table1").unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:C3");
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Fruit", "Price", "Column3"]
+    );
+
+    model
+        .set_user_input(0, 5, 1, "=SUM(Table1[Price])")
+        .unwrap();
+    model
+        .rename_table_column("Table1", "Price", "Cost")
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "Cost");
+    assert_eq!(model.get_formatted_cell_value(0, 5, 1).unwrap(), "8");
+    assert_eq!(
+        model.rename_table_column("Table1", "Cost", "fruit"),
+        Err("Column name already in use: 'fruit'".to_string())
+    );
+    assert_eq!(
+        model.rename_table_column("Table1", "Price", "Value"),
+        Err("Column not found: 'Price'".to_string())
+    );
+
+    // Typing in a header cell renames the column
+    model.set_user_input(0, 1, 3, "Stock").unwrap();
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Fruit", "Cost", "Stock"]
+    );
+    // Repeated names are made unique, also in the cell
+    model.set_user_input(0, 1, 3, "Fruit").unwrap();
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Fruit", "Cost", "Fruit2"]
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 3).unwrap(), "Fruit2");
+
+    model.undo().unwrap();
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Fruit", "Cost", "Stock"]
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 3).unwrap(), "Stock");
+}
+
+#[test]
+fn header_row() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.set_table_header_row("Table1", false).unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.reference, "A2:B3");
+    assert_eq!(table.header_row_count, 0);
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "");
+    assert_eq!(column_names(&model, "Table1"), vec!["Fruit", "Price"]);
+
+    model.set_table_header_row("Table1", true).unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.reference, "A1:B3");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "Fruit");
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().header_row_count, 1);
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "Fruit");
+
+    // The header row needs empty cells above the table
+    model.add_table(&area(0, 6, 1, 2, 2), false).unwrap();
+    model.set_user_input(0, 5, 2, "Busy").unwrap();
+    assert_eq!(
+        model.set_table_header_row("Table2", true),
+        Err("The cells of row 5 next to the table are not empty".to_string())
+    );
+}
+
+#[test]
+fn totals_row() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.set_table_totals_row("Table1", true).unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.reference, "A1:B4");
+    assert_eq!(table.totals_row_count, 1);
+    assert_eq!(table.columns[0].totals_row_label, Some("Total".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 4, 1).unwrap(), "Total");
+
+    model.set_table_totals_row("Table1", false).unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.reference, "A1:B3");
+    assert_eq!(table.columns[0].totals_row_label, None);
+    assert_eq!(model.get_formatted_cell_value(0, 4, 1).unwrap(), "");
+
+    model.undo().unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B4");
+    assert_eq!(model.get_formatted_cell_value(0, 4, 1).unwrap(), "Total");
+}
+
+#[test]
+fn typing_below_the_table_grows_it() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model
+        .set_user_input(0, 5, 1, "=SUM(Table1[Price])")
+        .unwrap();
+    model.set_user_input(0, 4, 1, "Plum").unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B4");
+    model.set_user_input(0, 4, 2, "7").unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B4");
+
+    // Cells next to the table do not change it
+    model.set_user_input(0, 5, 3, "Kiwi").unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B4");
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B3");
+    assert_eq!(model.get_formatted_cell_value(0, 4, 1).unwrap(), "");
+}
+
+#[test]
+fn insert_and_delete_rows_and_columns() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+
+    model.insert_row(0, 2).unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B4");
+    model.insert_column(0, 2).unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:C4");
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Fruit", "Column2", "Price"]
+    );
+
+    model.delete_column(0, 1).unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:B4");
+    assert_eq!(column_names(&model, "Table1"), vec!["Column2", "Price"]);
+    model.undo().unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:C4");
+    assert_eq!(
+        column_names(&model, "Table1"),
+        vec!["Fruit", "Column2", "Price"]
+    );
+
+    model.insert_row(0, 1).unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A2:C5");
+    model.delete_row(0, 3).unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A2:C4");
+    model.undo().unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A2:C5");
+}
+
+#[test]
+fn sheet_changes() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.rename_sheet(0, "Fruits").unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().sheet_name, "Fruits");
+
+    model.new_sheet().unwrap();
+    model
+        .set_user_input(1, 1, 1, "=SUM(Table1[Price])")
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(1, 1, 1).unwrap(), "8");
+    assert_eq!(
+        model.get_cell_content(1, 1, 1).unwrap(),
+        "=SUM(Fruits!$B$2:$B$3)"
+    );
+}
+
+#[test]
+fn protected_sheet() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model
+        .protect_sheet(0, SheetProtection::default(), None)
+        .unwrap();
+    assert_eq!(
+        model.add_table(&area(0, 6, 1, 2, 3), true),
+        Err("Cannot add a table: the sheet is protected".to_string())
+    );
+    assert_eq!(
+        model.rename_table("Table1", "Fruits"),
+        Err("Cannot rename a table: the sheet is protected".to_string())
+    );
+    assert_eq!(
+        model.set_table_totals_row("Table1", true),
+        Err("Cannot change a table: the sheet is protected".to_string())
+    );
+}
+
+#[test]
+fn totals_row_functions() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.set_table_totals_row("Table1", true).unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(
+        table.columns[1].totals_row_function,
+        Some(TotalsRowFunction::Sum)
+    );
+    assert_eq!(
+        model.get_cell_content(0, 4, 2).unwrap(),
+        "=SUBTOTAL(109,$B$2:$B$3)"
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2).unwrap(), "8");
+
+    let functions = [
+        (TotalsRowFunction::Average, "4"),
+        (TotalsRowFunction::Count, "2"),
+        (TotalsRowFunction::CountNums, "2"),
+        (TotalsRowFunction::Max, "5"),
+        (TotalsRowFunction::Min, "3"),
+        (TotalsRowFunction::StdDev, "1.414213562"),
+        (TotalsRowFunction::Var, "2"),
+    ];
+    for (function, expected) in functions {
+        model
+            .set_table_totals_row_function("Table1", "Price", Some(function))
+            .unwrap();
+        assert_eq!(model.get_formatted_cell_value(0, 4, 2).unwrap(), expected);
+    }
+    assert_eq!(
+        model.set_table_totals_row_function("Table1", "Price", Some(TotalsRowFunction::Custom)),
+        Err("Type a formula in the totals row to use a custom function".to_string())
+    );
+
+    model
+        .set_table_totals_row_function("Table1", "Price", None)
+        .unwrap();
+    assert_eq!(model.get_cell_content(0, 4, 2).unwrap(), "");
+    model.undo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2).unwrap(), "2");
+
+    // Hidden rows are not in the totals
+    model.set_rows_hidden(0, 2, 2, true).unwrap();
+    model
+        .set_table_totals_row_function("Table1", "Price", Some(TotalsRowFunction::Sum))
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2).unwrap(), "5");
+}
+
+#[test]
+fn totals_row_custom_and_labels() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.set_table_totals_row("Table1", true).unwrap();
+
+    model.set_user_input(0, 4, 2, "=MAX(B2:B3)*2").unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(
+        table.columns[1].totals_row_function,
+        Some(TotalsRowFunction::Custom)
+    );
+    assert_eq!(
+        table.columns[1].totals_row_formula,
+        Some("MAX(B2:B3)*2".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2).unwrap(), "10");
+
+    model.set_user_input(0, 4, 1, "All").unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.columns[0].totals_row_label, Some("All".to_string()));
+
+    model.undo().unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.columns[0].totals_row_label, Some("Total".to_string()));
+
+    // The totals follow the table when it is resized
+    model
+        .set_table_totals_row_function("Table1", "Price", Some(TotalsRowFunction::Sum))
+        .unwrap();
+    model.resize_table("Table1", &area(0, 1, 1, 2, 5)).unwrap();
+    model.set_user_input(0, 4, 2, "7").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 1).unwrap(), "Total");
+    assert_eq!(model.get_formatted_cell_value(0, 5, 2).unwrap(), "15");
+}
+
+#[test]
+fn calculated_columns() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.set_user_input(0, 1, 3, "Double").unwrap();
+    model.add_table(&area(0, 1, 1, 3, 3), true).unwrap();
+
+    model.set_user_input(0, 2, 3, "=[@Price]*2").unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 3).unwrap(), "=$B2*2");
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "=$B3*2");
+    assert_eq!(model.get_formatted_cell_value(0, 3, 3).unwrap(), "10");
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(
+        table.columns[2].calculated_column_formula,
+        Some("Table1[[#This Row],[Price]]*2".to_string())
+    );
+
+    // Changing a formula of a calculated column changes all of them
+    model.set_user_input(0, 3, 3, "=Table1[@Price]+1").unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 3).unwrap(), "=$B2+1");
+    assert_eq!(model.get_formatted_cell_value(0, 2, 3).unwrap(), "4");
+
+    // A value is an exception
+    model.set_user_input(0, 3, 3, "0").unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 3).unwrap(), "=$B2+1");
+
+    // New rows get the formula
+    model.set_user_input(0, 4, 1, "Plum").unwrap();
+    assert_eq!(model.get_table("Table1").unwrap().reference, "A1:C4");
+    assert_eq!(model.get_cell_content(0, 4, 3).unwrap(), "=$B4+1");
+    model.insert_row(0, 3).unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "=$B3+1");
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "0");
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 3).unwrap(), "=$B2*2");
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "=$B3*2");
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "");
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.columns[2].calculated_column_formula, None);
+}
+
+#[test]
+fn calculated_column_formula_is_the_same_in_all_rows() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.set_user_input(0, 1, 3, "Total").unwrap();
+    model.set_user_input(0, 1, 5, "2").unwrap();
+    model.add_table(&area(0, 1, 1, 3, 3), true).unwrap();
+
+    // Typed in the last row, with references to its row and to other cells
+    model.set_user_input(0, 3, 3, "=B3*$E$1+A2").unwrap();
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(
+        table.columns[2].calculated_column_formula,
+        Some("Table1[[#This Row],[Price]]*$E$1+A1".to_string())
+    );
+    assert_eq!(model.get_cell_content(0, 2, 3).unwrap(), "=$B2*$E$1+A1");
+
+    // Like in an imported workbook
+    let mut table = model.get_table("Table1").unwrap();
+    table.columns[2].calculated_column_formula = Some("[@Price]*3".to_string());
+    model.model.set_table("Table1", Some(table)).unwrap();
+    model
+        .rename_table_column("Table1", "Price", "Cost")
+        .unwrap();
+    assert_eq!(
+        model.get_table("Table1").unwrap().columns[2].calculated_column_formula,
+        Some("[[#This Row],[Cost]]*3".to_string())
+    );
+    model.set_user_input(0, 4, 1, "Plum").unwrap();
+    assert_eq!(model.get_cell_content(0, 4, 3).unwrap(), "=$B4*3");
+}
+
+#[test]
+fn formulas_are_not_copied_over_values() {
+    let mut model = UserModel::from_model(new_model_with_data(FRUITS));
+    model.add_table(&area(0, 1, 1, 2, 3), true).unwrap();
+    model.set_user_input(0, 2, 2, "=1+2").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 3, 2).unwrap(), "5");
+    let table = model.get_table("Table1").unwrap();
+    assert_eq!(table.columns[1].calculated_column_formula, None);
+}
@@ -1,6 +1,6 @@
 #![allow(clippy::unwrap_used)]
 
-use crate::expressions::types::CellReferenceIndex;
+use crate::expressions::types::{Area, CellReferenceIndex};
 use crate::model::Model;
 use crate::types::Cell;
 
@@ -8,6 +8,33 @@ pub fn new_empty_model() -> Model {
     Model::new_empty("model", "en", "UTC").unwrap()
 }
 
+/// Returns a new evaluated model with the `data` in the first sheet starting at A1.
+/// Empty strings are left as empty cells.
+pub fn new_model_with_data(data: &[&[&str]]) -> Model {
+    let mut model = new_empty_model();
+    for (row, values) in data.iter().enumerate() {
+        for (column, value) in values.iter().enumerate() {
+            if !value.is_empty() {
+                model
+                    .set_user_input(0, row as i32 + 1, column as i32 + 1, value.to_string())
+                    .unwrap();
+            }
+        }
+    }
+    model.evaluate();
+    model
+}
+
+pub fn area(sheet: u32, row: i32, column: i32, width: i32, height: i32) -> Area {
+    Area {
+        sheet,
+        row,
+        column,
+        width,
+        height,
+    }
+}
+
 impl Model {
     fn _parse_reference(&self, cell: &str) -> CellReferenceIndex {
         if cell.contains('!') {
@@ -0,0 +1,201 @@
+use core::cmp::max;
+use core::cmp::min;
+
+use crate::{
+    model::Model,
+    types::{BorderItem, FontScheme, Theme, ThemeColor},
+    utils::is_valid_hex_color,
+};
+
+// https://gist.github.com/emanuel-sanabria-developer/5793377
+// https://github.com/ClosedXML/ClosedXML/wiki/Excel-Indexed-Colors
+
+// Warning: Excel uses a weird normalization for HSL colors (0, 255)
+// We use a more standard one but our HSL numbers will not coincide with Excel's
+
+pub(crate) fn hex_to_rgb(h: &str) -> [i32; 3] {
+    let r = i32::from_str_radix(&h[1..3], 16).unwrap_or(0);
+    let g = i32::from_str_radix(&h[3..5], 16).unwrap_or(0);
+    let b = i32::from_str_radix(&h[5..7], 16).unwrap_or(0);
+    [r, g, b]
+}
+
+pub(crate) fn rgb_to_hex(rgb: [i32; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])
+}
+
+pub(crate) fn rgb_to_hsl(rgb: [i32; 3]) -> [i32; 3] {
+    let r = rgb[0];
+    let g = rgb[1];
+    let b = rgb[2];
+    let red = r as f64 / 255.0;
+    let green = g as f64 / 255.0;
+    let blue = b as f64 / 255.0;
+    let max_color = max(max(r, g), b);
+    let min_color = min(min(r, g), b);
+    let chroma = (max_color - min_color) as f64 / 255.0;
+    if chroma == 0.0 {
+        return [0, 0, (red * 100.0).round() as i32];
+    }
+
+    let hue;
+    let luminosity = (max_color + min_color) as f64 / (255.0 * 2.0);
+    let saturation = if luminosity > 0.5 {
+        0.5 * chroma / (1.0 - luminosity)
+    } else {
+        0.5 * chroma / luminosity
+    };
+    if max_color == r {
+        if green >= blue {
+            hue = 60.0 * (green - blue) / chroma;
+        } else {
+            hue = ((green - blue) / chroma + 6.0) * 60.0;
+        }
+    } else if max_color == g {
+        hue = ((blue - red) / chroma + 2.0) * 60.0;
+    } else {
+        hue = ((red - green) / chroma + 4.0) * 60.0;
+    }
+    let hue = hue.round() as i32;
+    let saturation = (saturation * 100.0).round() as i32;
+    let luminosity = (luminosity * 100.0).round() as i32;
+    [hue, saturation, luminosity]
+}
+
+fn hue_to_rgb(p: f64, q: f64, t: f64) -> f64 {
+    let mut c = t;
+    if c < 0.0 {
+        c += 1.0;
+    }
+    if c > 1.0 {
+        c -= 1.0;
+    }
+    if c < 1.0 / 6.0 {
+        return p + (q - p) * 6.0 * t;
+    };
+    if c < 0.5 {
+        return q;
+    };
+    if c < 2.0 / 3.0 {
+        return p + (q - p) * (2.0 / 3.0 - t) * 6.0;
+    };
+    p
+}
+
+pub(crate) fn hsl_to_rgb(hsl: [i32; 3]) -> [i32; 3] {
+    let hue = (hsl[0] as f64) / 360.0;
+    let saturation = (hsl[1] as f64) / 100.0;
+    let luminosity = (hsl[2] as f64) / 100.0;
+    let red;
+    let green;
+    let blue;
+
+    if saturation == 0.0 {
+        // achromatic
+        red = luminosity * 255.0;
+        green = luminosity * 255.0;
+        blue = luminosity * 255.0;
+    } else {
+        let q = if luminosity < 0.5 {
+            luminosity * (1.0 + saturation)
+        } else {
+            luminosity + saturation - luminosity * saturation
+        };
+        let p = 2.0 * luminosity - q;
+        red = 255.0 * hue_to_rgb(p, q, hue + 1.0 / 3.0);
+        green = 255.0 * hue_to_rgb(p, q, hue);
+        blue = 255.0 * hue_to_rgb(p, q, hue - 1.0 / 3.0);
+    }
+    [
+        red.round() as i32,
+        green.round() as i32,
+        blue.round() as i32,
+    ]
+}
+
+/* 18.8.3 bgColor tint algorithm */
+pub(crate) fn hex_with_tint_to_rgb(hex: &str, tint: f64) -> String {
+    if tint == 0.0 {
+        return hex.to_string();
+    }
+    let mut hsl = rgb_to_hsl(hex_to_rgb(hex));
+    let l = hsl[2] as f64;
+    if tint < 0.0 {
+        // Lum’ = Lum * (1.0 + tint)
+        hsl[2] = (l * (1.0 + tint)).round() as i32;
+    } else {
+        // HLSMAX here would be 100, for Excel 255
+        // Lum‘ = Lum * (1.0-tint) + (HLSMAX – HLSMAX * (1.0-tint))
+        hsl[2] = (l + (100.0 - l) * tint).round() as i32;
+    };
+    rgb_to_hex(hsl_to_rgb(hsl))
+}
+
+impl Theme {
+    /// Returns the color with index `theme` of the theme lightened or darkened by `tint`
+    pub fn get_color(&self, theme: i32, tint: f64) -> String {
+        let color = match self.colors.get(theme as usize) {
+            Some(color) => color,
+            None => "#000000",
+        };
+        hex_with_tint_to_rgb(color, tint)
+    }
+
+    /// Returns the color of the theme `color` refers to
+    pub fn get_theme_color(&self, color: &ThemeColor) -> String {
+        let tint = color.tint.as_deref().and_then(|t| t.parse::<f64>().ok());
+        self.get_color(color.theme, tint.unwrap_or(0.0))
+    }
+}
+
+impl Model {
+    /// Changes the theme of the workbook.
+    /// The colors of the styles taken from the theme and the fonts that use the heading or body
+    /// font of the theme change accordingly.
+    pub fn set_theme(&mut self, theme: &Theme) -> Result<(), String> {
+        if theme.colors.len() != 12 {
+            return Err("A theme must have 12 colors".to_string());
+        }
+        if let Some(color) = theme.colors.iter().find(|c| !is_valid_hex_color(c)) {
+            return Err(format!("Invalid color: '{color}'."));
+        }
+        let old_theme = std::mem::replace(&mut self.workbook.theme, theme.clone());
+        let styles = &mut self.workbook.styles;
+
+        let recolor = |color: &mut Option<String>, color_theme: &Option<ThemeColor>| {
+            if let Some(theme_color) = color_theme {
+                *color = Some(theme.get_theme_color(theme_color));
+            }
+        };
+        let recolor_border = |border: &mut Option<BorderItem>| {
+            if let Some(item) = border {
+                recolor(&mut item.color, &item.color_theme);
+            }
+        };
+
+        for font in &mut styles.fonts {
+            recolor(&mut font.color, &font.color_theme);
+            match font.scheme {
+                FontScheme::Major if font.name == old_theme.major_font => {
+                    font.name = theme.major_font.clone();
+                }
+                FontScheme::Minor if font.name == old_theme.minor_font => {
+                    font.name = theme.minor_font.clone();
+                }
+                _ => {}
+            }
+        }
+        for fill in &mut styles.fills {
+            recolor(&mut fill.fg_color, &fill.fg_color_theme);
+            recolor(&mut fill.bg_color, &fill.bg_color_theme);
+        }
+        for border in &mut styles.borders {
+            recolor_border(&mut border.left);
+            recolor_border(&mut border.right);
+            recolor_border(&mut border.top);
+            recolor_border(&mut border.bottom);
+            recolor_border(&mut border.diagonal);
+        }
+        Ok(())
+    }
+}
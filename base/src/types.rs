@@ -12,6 +12,14 @@ fn is_false(b: &bool) -> bool {
     !*b
 }
 
+fn default_as_true() -> bool {
+    true
+}
+
+fn is_true(b: &bool) -> bool {
+    *b
+}
+
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct Metadata {
     pub application: String,
@@ -26,6 +34,38 @@ pub struct Metadata {
 pub struct WorkbookSettings {
     pub tz: String,
     pub locale: String,
+    /// Serial numbers count days from 1 January 1904 instead of 1 January 1900
+    pub date_1904: bool,
+    /// Results of formulas are rounded to the precision of their number format
+    pub precision_as_displayed: bool,
+    /// When the workbook is recalculated
+    pub calc_mode: CalcMode,
+}
+
+/// Calculation modes of a workbook
+///
+/// Excel Specification:
+///    18.18.5 ST_CalcMode (Calculation Mode)
+///    auto, autoNoTable, manual
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CalcMode {
+    /// The workbook is recalculated after each change
+    Automatic,
+    /// Same as automatic, data tables are only recalculated on request.
+    /// We do not support data tables, so this behaves as [CalcMode::Automatic]
+    AutomaticExceptTables,
+    /// The workbook is only recalculated on request
+    Manual,
+}
+
+impl Display for CalcMode {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CalcMode::Automatic => write!(formatter, "auto"),
+            CalcMode::AutomaticExceptTables => write!(formatter, "autoNoTable"),
+            CalcMode::Manual => write!(formatter, "manual"),
+        }
+    }
 }
 
 /// A Workbook View tracks of the selected sheet for each view
@@ -43,14 +83,51 @@ pub struct WorkbookView {
 #[derive(Encode, Decode, Debug, PartialEq, Clone)]
 pub struct Workbook {
     pub shared_strings: Vec<String>,
+    /// The runs of the shared strings with rich text, by index in `shared_strings`.
+    /// The plain text of those strings is still in `shared_strings`.
+    pub rich_text: HashMap<i32, Vec<TextRun>>,
     pub defined_names: Vec<DefinedName>,
     pub worksheets: Vec<Worksheet>,
     pub styles: Styles,
+    pub theme: Theme,
     pub name: String,
     pub settings: WorkbookSettings,
     pub metadata: Metadata,
     pub tables: HashMap<String, Table>,
     pub views: HashMap<u32, WorkbookView>,
+    /// None if the workbook is not protected
+    pub protection: Option<WorkbookProtection>,
+}
+
+/// The theme of the workbook: the colors and fonts the styles can refer to.
+/// See ECMA-376-1:2016 section 20.1.6
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct Theme {
+    pub name: String,
+    /// Light 1, dark 1, light 2, dark 2, accent 1 to 6, hyperlink and followed hyperlink.
+    /// This is the order of the `theme` index of the colors in the styles.
+    pub colors: Vec<String>,
+    /// The font of the headings
+    pub major_font: String,
+    /// The font of the body
+    pub minor_font: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: "Office Theme".to_string(),
+            colors: [
+                "#FFFFFF", "#000000", "#E7E6E6", "#44546A", "#4472C4", "#ED7D31", "#A5A5A5",
+                "#FFC000", "#5B9BD5", "#70AD47", "#0563C1", "#954F72",
+            ]
+            .iter()
+            .map(|color| color.to_string())
+            .collect(),
+            major_font: "Calibri Light".to_string(),
+            minor_font: "Calibri".to_string(),
+        }
+    }
 }
 
 /// A defined name. The `sheet_id` is the sheet index in case the name is local
@@ -112,11 +189,20 @@ pub struct Worksheet {
     pub color: Option<String>,
     pub merge_cells: Vec<String>,
     pub comments: Vec<Comment>,
+    pub hyperlinks: Vec<Hyperlink>,
+    /// Sorted by priority, the first one has the highest priority
+    pub conditional_formats: Vec<ConditionalFormat>,
+    pub data_validations: Vec<DataValidation>,
+    /// None if the sheet has no autofilter
+    pub auto_filter: Option<AutoFilter>,
     pub frozen_rows: i32,
     pub frozen_columns: i32,
     pub views: HashMap<u32, WorksheetView>,
     /// Whether or not to show the grid lines in the worksheet
     pub show_grid_lines: bool,
+    /// None if the sheet is not protected
+    pub protection: Option<SheetProtection>,
+    pub pivot_tables: Vec<PivotTable>,
 }
 
 /// Internal representation of Excel's sheet_data
@@ -133,6 +219,10 @@ pub struct Row {
     pub custom_height: bool,
     pub s: i32,
     pub hidden: bool,
+    /// Outline (grouping) level of the row, from 0 (not grouped) to 7
+    pub outline_level: u8,
+    /// The group of rows that ends right before this one is collapsed
+    pub collapsed: bool,
 }
 
 // ECMA-376-1:2016 section 18.3.1.13
@@ -146,6 +236,11 @@ pub struct Col {
     pub width: f64,
     pub custom_width: bool,
     pub style: Option<i32>,
+    pub hidden: bool,
+    /// Outline (grouping) level of the columns, from 0 (not grouped) to 7
+    pub outline_level: u8,
+    /// The group of columns that ends right before these is collapsed
+    pub collapsed: bool,
 }
 
 /// Cell type enum matching Excel TYPE() function values.
@@ -226,16 +321,380 @@ impl Default for Cell {
     }
 }
 
-#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+/// A comment (or note) in a cell. Modern threaded comments can have replies and be resolved.
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct Comment {
     pub text: String,
     pub author_name: String,
     pub author_id: Option<String>,
     pub cell_ref: String,
+    /// Milliseconds since January 1, 1970, if known
+    pub timestamp: Option<i64>,
+    pub resolved: bool,
+    pub replies: Vec<CommentReply>,
+}
+
+/// A reply in the thread of a comment
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct CommentReply {
+    pub text: String,
+    pub author_name: String,
+    pub author_id: Option<String>,
+    /// Milliseconds since January 1, 1970, if known
+    pub timestamp: Option<i64>,
+}
+
+/// A hyperlink in a cell. It points either to an external resource (`url`), like a web page or a
+/// mailto link, or to a place in the workbook (`location`), like "Sheet2!A1".
+/// See ECMA-376-1:2016 section 18.3.1.47
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct Hyperlink {
+    pub cell_ref: String,
+    pub url: Option<String>,
+    pub location: Option<String>,
+    pub tooltip: Option<String>,
+}
+
+/// The hash of the password of a protected sheet or workbook
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PasswordHash {
+    /// The legacy 16 bit hash as an hexadecimal string, like "CC3D"
+    Legacy(String),
+    /// A salted and iterated hash. The hash and the salt are base64 encoded.
+    Salted {
+        algorithm_name: String,
+        hash_value: String,
+        salt_value: String,
+        spin_count: u32,
+    },
+}
+
+/// The protection of a sheet. Locked cells of a protected sheet cannot be changed and
+/// the `allow_*` flags tell what else users can still do.
+/// See ECMA-376-1:2016 section 18.3.1.85
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct SheetProtection {
+    pub password_hash: Option<PasswordHash>,
+    pub allow_select_locked_cells: bool,
+    pub allow_select_unlocked_cells: bool,
+    pub allow_format_cells: bool,
+    pub allow_format_columns: bool,
+    pub allow_format_rows: bool,
+    pub allow_insert_columns: bool,
+    pub allow_insert_rows: bool,
+    pub allow_insert_hyperlinks: bool,
+    pub allow_delete_columns: bool,
+    pub allow_delete_rows: bool,
+    pub allow_sort: bool,
+    pub allow_auto_filter: bool,
+    pub allow_pivot_tables: bool,
+    pub allow_edit_objects: bool,
+    pub allow_edit_scenarios: bool,
+}
+
+impl Default for SheetProtection {
+    // The defaults in Excel: users can only select cells
+    fn default() -> Self {
+        SheetProtection {
+            password_hash: None,
+            allow_select_locked_cells: true,
+            allow_select_unlocked_cells: true,
+            allow_format_cells: false,
+            allow_format_columns: false,
+            allow_format_rows: false,
+            allow_insert_columns: false,
+            allow_insert_rows: false,
+            allow_insert_hyperlinks: false,
+            allow_delete_columns: false,
+            allow_delete_rows: false,
+            allow_sort: false,
+            allow_auto_filter: false,
+            allow_pivot_tables: false,
+            allow_edit_objects: false,
+            allow_edit_scenarios: false,
+        }
+    }
+}
+
+/// The protection of a workbook. With a locked structure sheets cannot be added, deleted,
+/// renamed, hidden or moved.
+/// See ECMA-376-1:2016 section 18.2.29
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+pub struct WorkbookProtection {
+    pub password_hash: Option<PasswordHash>,
+    pub lock_structure: bool,
+    pub lock_windows: bool,
+}
+
+/// A rule that changes the way the cells in a range are displayed depending on their values.
+/// The rules of a worksheet are sorted by priority, the first one has the highest priority.
+///
+/// ECMA-376-1:2016 section 18.3.1.18 (conditionalFormatting) and 18.3.1.10 (cfRule)
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct ConditionalFormat {
+    /// Space separated list of ranges the rule applies to, like "A1:A10 C1:C10"
+    pub sqref: String,
+    pub rule: ConditionalFormatRule,
+    /// Style applied to the matching cells. Not used by color scales, data bars and icon sets
+    pub style: Option<Dxf>,
+    /// If true, rules with lower priority are not applied to the cells matching this one
+    pub stop_if_true: bool,
+}
+
+/// Formulas are written without the leading '=' and relative references are relative to
+/// the top left cell of the first range in `sqref`, like in Excel.
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ConditionalFormatRule {
+    /// Compares the value of the cell with one formula (two for `between` and `notBetween`)
+    CellIs {
+        operator: CellIsOperator,
+        formulas: Vec<String>,
+    },
+    /// The cell matches if the formula evaluates to true
+    Expression {
+        formula: String,
+    },
+    /// The top (or bottom) `rank` values, or `rank` percent of the values
+    Top10 {
+        rank: u32,
+        percent: bool,
+        bottom: bool,
+    },
+    /// Values above (or below) the average, or `std_dev` standard deviations above (or below) it
+    AboveAverage {
+        above_average: bool,
+        equal_average: bool,
+        std_dev: u32,
+    },
+    DuplicateValues,
+    UniqueValues,
+    /// Fills the cell with a color interpolated between two or three colors
+    ColorScale {
+        cfvos: Vec<Cfvo>,
+        colors: Vec<String>,
+    },
+    /// Draws a bar whose length is proportional to the value
+    DataBar {
+        min: Cfvo,
+        max: Cfvo,
+        color: String,
+    },
+    /// Shows an icon of the set depending on the thresholds, the first threshold is the lower bound
+    IconSet {
+        icon_set: String,
+        cfvos: Vec<Cfvo>,
+        reverse: bool,
+        show_value: bool,
+    },
+}
+
+/// ECMA-376-1:2016 section 18.18.15 ST_ConditionalFormattingOperator.
+/// Data validations use the same operators (18.18.22 ST_DataValidationOperator)
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum CellIsOperator {
+    Between,
+    NotBetween,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    LessThan,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
+}
+
+impl Display for CellIsOperator {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CellIsOperator::Between => write!(formatter, "between"),
+            CellIsOperator::NotBetween => write!(formatter, "notBetween"),
+            CellIsOperator::Equal => write!(formatter, "equal"),
+            CellIsOperator::NotEqual => write!(formatter, "notEqual"),
+            CellIsOperator::GreaterThan => write!(formatter, "greaterThan"),
+            CellIsOperator::LessThan => write!(formatter, "lessThan"),
+            CellIsOperator::GreaterThanOrEqual => write!(formatter, "greaterThanOrEqual"),
+            CellIsOperator::LessThanOrEqual => write!(formatter, "lessThanOrEqual"),
+        }
+    }
+}
+
+/// Conditional format value object, a threshold of color scales, data bars and icon sets
+///
+/// ECMA-376-1:2016 section 18.3.1.11
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct Cfvo {
+    pub kind: CfvoKind,
+    /// A number or a formula. Not used with `min` and `max`
+    pub value: Option<String>,
+}
+
+/// ECMA-376-1:2016 section 18.18.13 ST_CfvoType
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum CfvoKind {
+    Min,
+    Max,
+    Number,
+    Percent,
+    Percentile,
+    Formula,
+}
+
+impl Display for CfvoKind {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CfvoKind::Min => write!(formatter, "min"),
+            CfvoKind::Max => write!(formatter, "max"),
+            CfvoKind::Number => write!(formatter, "num"),
+            CfvoKind::Percent => write!(formatter, "percent"),
+            CfvoKind::Percentile => write!(formatter, "percentile"),
+            CfvoKind::Formula => write!(formatter, "formula"),
+        }
+    }
+}
+
+/// Differential formatting. Only the properties that are set are applied on top of the style of the cell.
+///
+/// ECMA-376-1:2016 section 18.8.14
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+pub struct Dxf {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_fmt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<DxfFont>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<Border>,
+}
+
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+pub struct DxfFont {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub b: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub i: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub u: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strike: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// The outcome of the conditional formats of a worksheet in a cell
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct ConditionalFormatResult {
+    /// Style of the matching rules, properties of rules with higher priority win
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<Dxf>,
+    /// Background color computed by a color scale
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_bar: Option<DataBarResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<IconResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct DataBarResult {
+    pub color: String,
+    /// Length of the bar relative to the width of the cell, between 0 and 1
+    pub length: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct IconResult {
+    /// Name of the icon set, like "3TrafficLights1"
+    pub icon_set: String,
+    /// Index of the icon in the set, 0 is the icon of the lowest values
+    pub index: u32,
+    pub show_value: bool,
+}
+
+/// Restricts the values that can be entered in a range of cells.
+///
+/// ECMA-376-1:2016 section 18.3.1.32 (dataValidation)
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct DataValidation {
+    /// Space separated list of ranges the rule applies to, like "A1:A10 C1:C10"
+    pub sqref: String,
+    pub kind: DataValidationKind,
+    /// Not used by `any`, `list` and `custom`
+    pub operator: CellIsOperator,
+    /// Formulas without the leading '='. Relative references are relative to the top left cell
+    /// of the first range in `sqref`. A list is either a range or a literal like "\"Yes,No\""
+    pub formulas: Vec<String>,
+    /// Blank cells are valid
+    pub allow_blank: bool,
+    /// Show a dropdown with the options of a list
+    pub show_dropdown: bool,
+    pub show_input_message: bool,
+    pub input_title: Option<String>,
+    pub input_message: Option<String>,
+    /// If false invalid values are accepted without an alert
+    pub show_error_message: bool,
+    pub error_style: DataValidationErrorStyle,
+    pub error_title: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// ECMA-376-1:2016 section 18.18.21 ST_DataValidationType
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum DataValidationKind {
+    Any,
+    Whole,
+    Decimal,
+    List,
+    Date,
+    Time,
+    TextLength,
+    Custom,
+}
+
+impl Display for DataValidationKind {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DataValidationKind::Any => write!(formatter, "none"),
+            DataValidationKind::Whole => write!(formatter, "whole"),
+            DataValidationKind::Decimal => write!(formatter, "decimal"),
+            DataValidationKind::List => write!(formatter, "list"),
+            DataValidationKind::Date => write!(formatter, "date"),
+            DataValidationKind::Time => write!(formatter, "time"),
+            DataValidationKind::TextLength => write!(formatter, "textLength"),
+            DataValidationKind::Custom => write!(formatter, "custom"),
+        }
+    }
+}
+
+/// What happens when an invalid value is entered.
+/// `Stop` rejects the value, `Warning` and `Information` accept it after an alert.
+///
+/// ECMA-376-1:2016 section 18.18.20 ST_DataValidationErrorStyle
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DataValidationErrorStyle {
+    Stop,
+    Warning,
+    Information,
+}
+
+impl Display for DataValidationErrorStyle {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DataValidationErrorStyle::Stop => write!(formatter, "stop"),
+            DataValidationErrorStyle::Warning => write!(formatter, "warning"),
+            DataValidationErrorStyle::Information => write!(formatter, "information"),
+        }
+    }
 }
 
 // ECMA-376-1:2016 section 18.5.1.2
-#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct Table {
     pub name: String,
     pub display_name: String,
@@ -248,12 +707,34 @@ pub struct Table {
     pub totals_row_dxf_id: Option<u32>,
     pub columns: Vec<TableColumn>,
     pub style_info: TableStyleInfo,
+    /// True if some of the columns of the table are filtered
     pub has_filters: bool,
+    pub filter_columns: Vec<FilterColumn>,
 }
 
-// totals_row_label vs totals_row_function might be mutually exclusive. Use an enum?
-// the totals_row_function is an enum not String methinks
-#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+/// Function of a column in the totals row of a table.
+/// All but `Custom` are computed with SUBTOTAL so they ignore the rows hidden by filters.
+///
+/// ECMA-376-1:2016 section 18.18.86
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum TotalsRowFunction {
+    Average,
+    /// Counts the non empty cells
+    Count,
+    /// Counts the numbers
+    CountNums,
+    /// The formula is in `totals_row_formula`
+    Custom,
+    Max,
+    Min,
+    StdDev,
+    Sum,
+    Var,
+}
+
+// A column of the totals row has either a label, a function or nothing
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct TableColumn {
     pub id: u32,
     pub name: String,
@@ -261,7 +742,11 @@ pub struct TableColumn {
     pub header_row_dxf_id: Option<u32>,
     pub data_dxf_id: Option<u32>,
     pub totals_row_dxf_id: Option<u32>,
-    pub totals_row_function: Option<String>,
+    pub totals_row_function: Option<TotalsRowFunction>,
+    /// Formula of the totals row when the function is `Custom`, without the leading '='
+    pub totals_row_formula: Option<String>,
+    /// Formula of all the data cells of the column, without the leading '='
+    pub calculated_column_formula: Option<String>,
 }
 
 impl Default for TableColumn {
@@ -271,6 +756,8 @@ impl Default for TableColumn {
             name: "Column".to_string(),
             totals_row_label: None,
             totals_row_function: None,
+            totals_row_formula: None,
+            calculated_column_formula: None,
             data_dxf_id: None,
             header_row_dxf_id: None,
             totals_row_dxf_id: None,
@@ -278,7 +765,7 @@ impl Default for TableColumn {
     }
 }
 
-#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
 pub struct TableStyleInfo {
     pub name: Option<String>,
     pub show_first_column: bool,
@@ -287,6 +774,146 @@ pub struct TableStyleInfo {
     pub show_column_stripes: bool,
 }
 
+/// An autofilter hides the rows of a range that do not match the criteria of its columns.
+/// Like in Excel filters are not dynamic: the rows are hidden when the filter is applied and
+/// stay that way if the values change until the filter is applied again.
+///
+/// ECMA-376-1:2016 section 18.3.1.2
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct AutoFilter {
+    /// Range of the filter including the header row, like "A1:D20"
+    pub reference: String,
+    pub columns: Vec<FilterColumn>,
+}
+
+/// ECMA-376-1:2016 section 18.3.2.7
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct FilterColumn {
+    /// Index of the column in the range of the filter, 0 is the first column
+    pub column_id: u32,
+    pub filter: ColumnFilter,
+}
+
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ColumnFilter {
+    /// Cells showing one of the `values` (case insensitive), with a date in one of the
+    /// `date_groups` or, if `blank` is true, empty cells
+    Values {
+        values: Vec<String>,
+        date_groups: Vec<DateGroup>,
+        blank: bool,
+    },
+    /// One or two comparisons, if `and` is true the cell has to match both of them
+    Custom {
+        conditions: Vec<CustomFilter>,
+        and: bool,
+    },
+    /// The top (or bottom) `rank` values, or `rank` percent of the values
+    Top10 {
+        rank: u32,
+        percent: bool,
+        bottom: bool,
+    },
+    /// Cells with the fill color `color` or, if `cell_color` is false, with the font color `color`
+    Color { color: String, cell_color: bool },
+}
+
+/// Compares the cell with `value`. Numbers are compared as numbers and text case insensitive.
+/// With `equal` and `not_equal` the value can have the wildcards '*' and '?'.
+///
+/// ECMA-376-1:2016 section 18.3.2.2
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct CustomFilter {
+    pub operator: FilterOperator,
+    pub value: String,
+}
+
+/// ECMA-376-1:2016 section 18.18.31 ST_FilterOperator
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterOperator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+}
+
+impl Display for FilterOperator {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FilterOperator::Equal => write!(formatter, "equal"),
+            FilterOperator::NotEqual => write!(formatter, "notEqual"),
+            FilterOperator::GreaterThan => write!(formatter, "greaterThan"),
+            FilterOperator::GreaterThanOrEqual => write!(formatter, "greaterThanOrEqual"),
+            FilterOperator::LessThan => write!(formatter, "lessThan"),
+            FilterOperator::LessThanOrEqual => write!(formatter, "lessThanOrEqual"),
+        }
+    }
+}
+
+/// All the dates in a year, a month, a day, ... depending on `grouping`.
+/// The fields below the grouping are not used.
+///
+/// ECMA-376-1:2016 section 18.3.2.4
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct DateGroup {
+    pub grouping: DateTimeGrouping,
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+/// ECMA-376-1:2016 section 18.18.22 ST_DateTimeGrouping
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum DateTimeGrouping {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl Display for DateTimeGrouping {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DateTimeGrouping::Year => write!(formatter, "year"),
+            DateTimeGrouping::Month => write!(formatter, "month"),
+            DateTimeGrouping::Day => write!(formatter, "day"),
+            DateTimeGrouping::Hour => write!(formatter, "hour"),
+            DateTimeGrouping::Minute => write!(formatter, "minute"),
+            DateTimeGrouping::Second => write!(formatter, "second"),
+        }
+    }
+}
+
+/// A key to sort the rows of a range by
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct SortKey {
+    /// Column of the sheet, it must be in the range
+    pub column: i32,
+    pub descending: bool,
+    pub sort_by: SortBy,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SortBy {
+    /// Numbers, text (case insensitive), booleans and errors. Empty cells always go last.
+    Value,
+    /// Cells with the fill color `color` go first (last if descending)
+    CellColor { color: String },
+    /// Values in the order of `list` (case insensitive) and then the other values
+    CustomList { list: Vec<String> },
+}
+
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct Styles {
     pub num_fmts: Vec<NumFmt>,
@@ -298,6 +925,18 @@ pub struct Styles {
     pub cell_styles: Vec<CellStyles>,
 }
 
+/// A color of the styles taken from the [Theme]. The styles keep the color as it is displayed,
+/// this keeps track of where it comes from so it changes with the theme.
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct ThemeColor {
+    /// The index of the color in [Theme::colors]
+    pub theme: i32,
+    /// The tint, from -1.0 (darker) to 1.0 (lighter), as written in the xlsx file
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tint: Option<String>,
+}
+
 impl Default for Styles {
     fn default() -> Self {
         Styles {
@@ -316,6 +955,8 @@ impl Default for Styles {
 pub struct Style {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alignment: Option<Alignment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protection: Option<Protection>,
     pub num_fmt: String,
     pub fill: Fill,
     pub font: Font,
@@ -361,6 +1002,31 @@ impl Display for FontScheme {
     }
 }
 
+/// Vertical position of the text relative to the baseline, used for superscripts and subscripts.
+/// See ECMA-376-1:2016 section 22.9.2.17 ST_VerticalAlignRun
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FontVerticalAlignment {
+    #[default]
+    Baseline,
+    Superscript,
+    Subscript,
+}
+
+impl Display for FontVerticalAlignment {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FontVerticalAlignment::Baseline => write!(formatter, "baseline"),
+            FontVerticalAlignment::Superscript => write!(formatter, "superscript"),
+            FontVerticalAlignment::Subscript => write!(formatter, "subscript"),
+        }
+    }
+}
+
+fn is_baseline(vert_align: &FontVerticalAlignment) -> bool {
+    *vert_align == FontVerticalAlignment::Baseline
+}
+
 #[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct Font {
     #[serde(default = "default_as_false")]
@@ -377,6 +1043,10 @@ pub struct Font {
     pub i: bool,
     pub sz: i32,
     pub color: Option<String>,
+    /// The color of the theme `color` comes from, if any
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_theme: Option<ThemeColor>,
     pub name: String,
     // This is the font family fallback
     // 1 -> serif
@@ -385,6 +1055,17 @@ pub struct Font {
     // ...
     pub family: i32,
     pub scheme: FontScheme,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_baseline")]
+    pub vert_align: FontVerticalAlignment,
+}
+
+/// A fragment of a string with rich text. A run without a font uses the font of the cell.
+/// See ECMA-376-1:2016 section 18.4.4
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct TextRun {
+    pub text: String,
+    pub font: Option<Font>,
 }
 
 impl Default for Font {
@@ -396,9 +1077,11 @@ impl Default for Font {
             i: false,
             sz: 11,
             color: Some("#000000".to_string()),
+            color_theme: None,
             name: "Calibri".to_string(),
             family: 2,
             scheme: FontScheme::Minor,
+            vert_align: FontVerticalAlignment::Baseline,
         }
     }
 }
@@ -409,8 +1092,16 @@ pub struct Fill {
     pub pattern_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fg_color: Option<String>,
+    /// The color of the theme `fg_color` comes from, if any
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg_color_theme: Option<ThemeColor>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bg_color: Option<String>,
+    /// The color of the theme `bg_color` comes from, if any
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_color_theme: Option<ThemeColor>,
 }
 
 impl Default for Fill {
@@ -418,7 +1109,9 @@ impl Default for Fill {
         Fill {
             pattern_type: "none".to_string(),
             fg_color: Default::default(),
+            fg_color_theme: Default::default(),
             bg_color: Default::default(),
+            bg_color_theme: Default::default(),
         }
     }
 }
@@ -513,6 +1206,29 @@ pub struct Alignment {
     pub wrap_text: bool,
 }
 
+/// Protection of a cell, it only has effect if the sheet is protected.
+/// See ECMA-376-1:2016 section 18.8.33
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct Protection {
+    /// The cell cannot be changed
+    #[serde(default = "default_as_true")]
+    #[serde(skip_serializing_if = "is_true")]
+    pub locked: bool,
+    /// The formula of the cell is not shown
+    #[serde(default = "default_as_false")]
+    #[serde(skip_serializing_if = "is_false")]
+    pub hidden: bool,
+}
+
+impl Default for Protection {
+    fn default() -> Self {
+        Protection {
+            locked: true,
+            hidden: false,
+        }
+    }
+}
+
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct CellStyleXfs {
     pub num_fmt_id: i32,
@@ -525,6 +1241,8 @@ pub struct CellStyleXfs {
     pub apply_protection: bool,
     pub apply_font: bool,
     pub apply_fill: bool,
+    pub alignment: Option<Alignment>,
+    pub protection: Option<Protection>,
 }
 
 impl Default for CellStyleXfs {
@@ -540,6 +1258,8 @@ impl Default for CellStyleXfs {
             apply_protection: true,
             apply_font: true,
             apply_fill: true,
+            alignment: None,
+            protection: None,
         }
     }
 }
@@ -559,21 +1279,23 @@ pub struct CellXfs {
     pub apply_fill: bool,
     pub quote_prefix: bool,
     pub alignment: Option<Alignment>,
+    pub protection: Option<Protection>,
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
 pub struct CellStyles {
     pub name: String,
     pub xf_id: i32,
-    pub builtin_id: i32,
+    /// The id of the built-in style, like 0 for "Normal". Custom styles do not have one.
+    pub builtin_id: Option<i32>,
 }
 
 impl Default for CellStyles {
     fn default() -> Self {
         CellStyles {
-            name: "normal".to_string(),
+            name: "Normal".to_string(),
             xf_id: 0,
-            builtin_id: 0,
+            builtin_id: Some(0),
         }
     }
 }
@@ -612,6 +1334,10 @@ impl Display for BorderStyle {
 pub struct BorderItem {
     pub style: BorderStyle,
     pub color: Option<String>,
+    /// The color of the theme `color` comes from, if any
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_theme: Option<ThemeColor>,
 }
 
 #[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
@@ -644,3 +1370,190 @@ pub struct SheetProperties {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
 }
+
+/// A pivot table summarizes the data of a range or a table.
+/// The data rows are grouped by the values of the row and column fields and the value fields are
+/// aggregated for each group.
+/// Like in Excel the pivot table is not dynamic, it is rendered in the cells of the sheet and it
+/// changes only when it is refreshed.
+///
+/// ECMA-376-1:2016 section 18.10.1.73
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct PivotTable {
+    /// Unique in the sheet
+    pub name: String,
+    pub source: PivotSource,
+    /// Top left cell of the pivot table, like "C3"
+    pub location: String,
+    /// The range of the rendered pivot table, None if it has not been rendered
+    pub reference: Option<String>,
+    /// The fields are the names of the columns of the source
+    pub row_fields: Vec<String>,
+    pub column_fields: Vec<String>,
+    pub value_fields: Vec<PivotValueField>,
+    pub filters: Vec<PivotFilter>,
+    /// Shows the grand total of each row, a column at the right
+    pub row_grand_totals: bool,
+    /// Shows the grand total of each column, a row at the bottom
+    pub column_grand_totals: bool,
+    /// Shows a total row for each group of the row fields but the last one
+    pub subtotals: bool,
+    /// The parts of the xlsx file the pivot table was imported from. They are dropped when the
+    /// pivot table changes.
+    pub xlsx_parts: Option<PivotTableParts>,
+}
+
+/// Where the data of a pivot table comes from
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PivotSource {
+    /// A range with the names of the fields in the first row, like "A1:D20"
+    Range {
+        sheet_name: String,
+        reference: String,
+    },
+    Table {
+        name: String,
+    },
+}
+
+/// A field of the source that is aggregated by the pivot table
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct PivotValueField {
+    pub field: String,
+    pub function: PivotFunction,
+    /// The caption of the values, like "Sum of Sales"
+    pub name: String,
+}
+
+/// ECMA-376-1:2016 section 18.18.17
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum PivotFunction {
+    Average,
+    /// Counts the non empty cells
+    Count,
+    /// Counts the numbers
+    CountNums,
+    Max,
+    Min,
+    Product,
+    StdDev,
+    StdDevp,
+    Sum,
+    Var,
+    Varp,
+}
+
+/// Rows with any of the `hidden_items` in the `field` are left out of the pivot table
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct PivotFilter {
+    pub field: String,
+    /// The items as they are displayed
+    pub hidden_items: Vec<String>,
+}
+
+/// The XML parts of a pivot table in an xlsx file
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct PivotTableParts {
+    pub definition: String,
+    pub cache_definition: String,
+    pub cache_records: Option<String>,
+}
+
+/// The kind of series filled by [crate::UserModel::fill_series]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum SeriesType {
+    /// Adds the step to each value
+    Linear,
+    /// Multiplies each value by the step
+    Growth,
+    /// Dates, the step is in days
+    Day,
+    /// Dates skipping Saturdays and Sundays
+    Weekday,
+    /// Dates, the step is in months. Days past the end of the month are moved to the last day.
+    Month,
+    Year,
+}
+
+/// Options to find and replace text in the cells
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Default)]
+pub struct FindOptions {
+    /// Search in the formulas or in the displayed values. Replacing always looks in the formulas.
+    pub look_in: FindLookIn,
+    /// The text has to match the whole content of the cell
+    pub match_entire_cell: bool,
+    pub match_case: bool,
+    pub mode: FindMode,
+    pub search_order: SearchOrder,
+    pub within: FindWithin,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum FindLookIn {
+    /// The formula or the value as it was typed
+    #[default]
+    Formulas,
+    /// The value as it is displayed
+    Values,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum FindMode {
+    #[default]
+    Text,
+    /// `*` matches any text and `?` any character, `~` escapes them
+    Wildcard,
+    /// A regular expression. The replacement can refer to its groups like `$1`.
+    Regex,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchOrder {
+    #[default]
+    ByRows,
+    ByColumns,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum FindWithin {
+    /// The selected range, or the whole sheet if a single cell is selected
+    Selection,
+    #[default]
+    Sheet,
+    Workbook,
+}
+
+/// How [crate::UserModel::text_to_columns] splits the text of each cell
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TextSplit {
+    /// Splits at any of the characters in `delimiters`. Text between two `text_qualifier`
+    /// characters is not split, a doubled qualifier inside it is the qualifier itself.
+    Delimited {
+        delimiters: String,
+        treat_consecutive_as_one: bool,
+        text_qualifier: Option<char>,
+    },
+    /// Splits at the character positions in `breaks`, "abcdef" with breaks [2, 4] gives "ab",
+    /// "cd" and "ef"
+    FixedWidth { breaks: Vec<usize> },
+}
+
+/// How each column of [crate::UserModel::text_to_columns] is converted
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum TextColumnFormat {
+    /// Numbers and dates, with the separators of the locale, are converted and the rest is text
+    #[default]
+    General,
+    Text,
+    /// The column is left out
+    Skip,
+}
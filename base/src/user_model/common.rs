@@ -1,27 +1,46 @@
 #![deny(missing_docs)]
 
-use std::{collections::HashMap, fmt::Debug, io::Cursor};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    io::Cursor,
+};
 
 use csv::{ReaderBuilder, WriterBuilder};
 use csv_sniffer::Sniffer;
+use regex::{NoExpand, Regex};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    constants::{self, DEFAULT_ROW_HEIGHT, LAST_COLUMN, LAST_ROW},
+    cell::CellValue,
+    constants::{DEFAULT_ROW_HEIGHT, LAST_COLUMN, LAST_ROW},
+    data_tools::split_text,
     expressions::{
         types::{Area, CellReferenceIndex},
         utils::{is_valid_column_number, is_valid_row},
     },
+    fill_series::Series,
+    find::get_find_regex,
+    merge_cells::{intersects, parse_range_reference, range_to_string},
     model::Model,
+    number_format::to_excel_precision_str,
+    outline::MAX_OUTLINE_LEVEL,
+    pivot_tables::{get_value_field_name, PivotCell},
+    tables::{get_totals_row_formula, to_this_row_references},
     types::{
-        Alignment, BorderItem, BorderStyle, CellType, Col, HorizontalAlignment, SheetProperties,
-        Style, VerticalAlignment,
+        Alignment, AutoFilter, BorderItem, BorderStyle, CalcMode, CellType, Col, ColumnFilter,
+        Comment, ConditionalFormat, ConditionalFormatResult, DataValidation,
+        DataValidationErrorStyle, FilterColumn, FindLookIn, FindMode, FindOptions,
+        HorizontalAlignment, Hyperlink, PivotSource, PivotTable, Protection, SeriesType,
+        SheetProperties, SheetProtection, SortKey, Style, Table, TableColumn, TableStyleInfo,
+        TextColumnFormat, TextRun, TextSplit, Theme, TotalsRowFunction, VerticalAlignment,
+        WorkbookProtection,
     },
     utils::is_valid_hex_color,
 };
 
 use crate::user_model::history::{
-    ColumnData, Diff, DiffList, DiffType, History, QueueDiffs, RowData,
+    ColumnData, Diff, DiffList, DiffType, History, MoveData, QueueDiffs, RowData,
 };
 /// Data for the clipboard
 pub type ClipboardData = HashMap<i32, HashMap<i32, ClipboardCell>>;
@@ -31,7 +50,16 @@ pub type ClipboardTuple = (i32, i32, i32, i32);
 #[derive(Serialize, Deserialize)]
 pub struct ClipboardCell {
     text: String,
+    /// The evaluated value of the cell as user input, quoted if needed
+    #[serde(default)]
+    value: String,
     style: Style,
+    /// Number of rows and columns of the merged area if this is its upper-left cell
+    #[serde(default)]
+    merge: Option<(i32, i32)>,
+    /// Width of the column of the cell
+    #[serde(default)]
+    column_width: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -41,6 +69,57 @@ pub struct Clipboard {
     pub(crate) range: (i32, i32, i32, i32),
 }
 
+/// What [UserModel::paste_special_from_clipboard] pastes
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PasteMode {
+    /// Contents and formats, like [UserModel::paste_from_clipboard]
+    #[default]
+    All,
+    /// The evaluated values of the cells
+    Values,
+    /// The contents of the cells, with the references of formulas displaced
+    Formulas,
+    /// The styles and merged cells
+    Formats,
+    /// The widths of the columns
+    ColumnWidths,
+}
+
+/// How the pasted values are combined with the values of the target cells
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PasteOperation {
+    /// The pasted values replace the values of the target cells
+    #[default]
+    None,
+    /// The pasted values are added to the values of the target cells
+    Add,
+    /// The pasted values are subtracted from the values of the target cells
+    Subtract,
+    /// The values of the target cells are multiplied by the pasted values
+    Multiply,
+    /// The values of the target cells are divided by the pasted values
+    Divide,
+}
+
+/// The options of [UserModel::paste_special_from_clipboard]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct PasteOptions {
+    /// What is pasted
+    #[serde(default)]
+    pub mode: PasteMode,
+    /// The operation with the values of the target cells
+    #[serde(default)]
+    pub operation: PasteOperation,
+    /// Blank cells of the clipboard do not replace the target cells
+    #[serde(default)]
+    pub skip_blanks: bool,
+    /// Rows of the clipboard are pasted as columns and columns as rows
+    #[serde(default)]
+    pub transpose: bool,
+}
+
 #[derive(Serialize, Deserialize, PartialEq)]
 pub enum BorderType {
     All,
@@ -105,6 +184,7 @@ fn border(value: &str) -> Result<Option<BorderItem>, String> {
             Ok(Some(BorderItem {
                 style,
                 color: color(color_str)?,
+                color_theme: None,
             }))
         }
         _ => Err(format!("Invalid border value: '{value}'.")),
@@ -138,6 +218,19 @@ fn vertical(value: &str) -> Result<VerticalAlignment, String> {
     }
 }
 
+/// Replaces the filter of the column `column_id`, adds it or removes it if `filter` is None
+fn set_filter_column(
+    columns: &mut Vec<FilterColumn>,
+    column_id: u32,
+    filter: Option<ColumnFilter>,
+) {
+    columns.retain(|c| c.column_id != column_id);
+    if let Some(filter) = filter {
+        columns.push(FilterColumn { column_id, filter });
+        columns.sort_by_key(|c| c.column_id);
+    }
+}
+
 /// # A wrapper around [`Model`] for a spreadsheet end user.
 /// UserModel is a wrapper around Model with undo/redo history, _diffs_, automatic evaluation and view management.
 ///
@@ -169,6 +262,27 @@ pub struct UserModel {
     history: History,
     send_queue: Vec<QueueDiffs>,
     pause_evaluation: bool,
+    // ids of the sheets with formulas that have not been evaluated since the last change
+    stale_sheets: HashSet<u32>,
+}
+
+// Sheets with formulas that might be out of date in a model just loaded in manual mode
+fn unevaluated_sheets(model: &Model) -> HashSet<u32> {
+    if model.get_calc_mode() != CalcMode::Manual {
+        return HashSet::new();
+    }
+    model
+        .workbook
+        .worksheets
+        .iter()
+        .filter(|worksheet| {
+            worksheet
+                .sheet_data
+                .values()
+                .any(|row| row.values().any(|cell| cell.has_formula()))
+        })
+        .map(|worksheet| worksheet.sheet_id)
+        .collect()
 }
 
 impl Debug for UserModel {
@@ -180,11 +294,13 @@ impl Debug for UserModel {
 impl UserModel {
     /// Creates a user model from an existing model
     pub fn from_model(model: Model) -> UserModel {
+        let stale_sheets = unevaluated_sheets(&model);
         UserModel {
             model,
             history: History::default(),
             send_queue: vec![],
             pause_evaluation: false,
+            stale_sheets,
         }
     }
 
@@ -199,20 +315,24 @@ impl UserModel {
             history: History::default(),
             send_queue: vec![],
             pause_evaluation: false,
+            stale_sheets: HashSet::new(),
         })
     }
 
-    /// Creates a model from it's internal representation
+    /// Creates a model from it's internal representation.
+    /// The model is not evaluated, stored values are used until the next evaluation.
     ///
     /// See also:
     /// * [Model::from_bytes]
     pub fn from_bytes(s: &[u8]) -> Result<UserModel, String> {
         let model = Model::from_bytes(s)?;
+        let stale_sheets = unevaluated_sheets(&model);
         Ok(UserModel {
             model,
             history: History::default(),
             send_queue: vec![],
             pause_evaluation: false,
+            stale_sheets,
         })
     }
 
@@ -234,6 +354,15 @@ impl UserModel {
         self.model.workbook.name = name.to_string();
     }
 
+    /// Sets the language in which formulas are entered and displayed.
+    /// This is a per user setting, it is not part of the history.
+    ///
+    /// See also:
+    /// * [Model::set_language]
+    pub fn set_language(&mut self, language_id: &str) -> Result<(), String> {
+        self.model.set_language(language_id)
+    }
+
     /// Undoes last change if any, places the change in the redo list and evaluates the model if needed
     ///
     /// See also:
@@ -296,9 +425,80 @@ impl UserModel {
     ///
     /// See also:
     /// * [Model::evaluate]
+    /// * [UserModel::evaluate_sheet]
     /// * [UserModel::pause_evaluation]
     pub fn evaluate(&mut self) {
-        self.model.evaluate()
+        self.model.evaluate();
+        self.stale_sheets.clear();
+    }
+
+    /// Forces an evaluation of the cells in `sheet`.
+    /// Cells in other sheets are only evaluated if they are needed to compute `sheet`.
+    ///
+    /// See also:
+    /// * [Model::evaluate_sheet]
+    /// * [UserModel::evaluate]
+    pub fn evaluate_sheet(&mut self, sheet: u32) -> Result<(), String> {
+        self.model.evaluate_sheet(sheet)?;
+        let sheet_id = self.model.workbook.worksheet(sheet)?.sheet_id;
+        self.stale_sheets.remove(&sheet_id);
+        Ok(())
+    }
+
+    /// Sets the calculation mode of the workbook.
+    /// In [CalcMode::Manual] the model is only evaluated when requested.
+    ///
+    /// See also:
+    /// * [UserModel::evaluate]
+    /// * [UserModel::get_stale_cells]
+    pub fn set_calc_mode(&mut self, calc_mode: CalcMode) {
+        let old_value = self.model.get_calc_mode();
+        self.model.set_calc_mode(calc_mode);
+        self.push_diff_list(vec![Diff::SetCalcMode {
+            new_value: calc_mode,
+            old_value,
+        }]);
+        if calc_mode != CalcMode::Manual && !self.stale_sheets.is_empty() {
+            self.evaluate_if_not_paused();
+        }
+    }
+
+    /// Returns the calculation mode of the workbook
+    pub fn get_calc_mode(&self) -> CalcMode {
+        self.model.get_calc_mode()
+    }
+
+    /// Returns the list of cells with formulas that have not been evaluated since the last change.
+    /// This happens in manual calculation mode or while the evaluation is paused.
+    /// Dependencies are not tracked: after a change every formula in the workbook is listed.
+    /// The same happens with a workbook opened in manual mode, as its values might have been
+    /// saved before the last changes.
+    pub fn get_stale_cells(&self) -> Vec<CellReferenceIndex> {
+        let mut cells = Vec::new();
+        for (sheet, worksheet) in self.model.workbook.worksheets.iter().enumerate() {
+            if !self.stale_sheets.contains(&worksheet.sheet_id) {
+                continue;
+            }
+            let mut sorted_rows: Vec<_> = worksheet.sheet_data.keys().collect();
+            sorted_rows.sort_unstable();
+            for row in sorted_rows {
+                let row_data = &worksheet.sheet_data[row];
+                let mut sorted_columns: Vec<_> = row_data
+                    .iter()
+                    .filter(|(_, cell)| cell.has_formula())
+                    .map(|(column, _)| *column)
+                    .collect();
+                sorted_columns.sort_unstable();
+                for column in sorted_columns {
+                    cells.push(CellReferenceIndex {
+                        sheet: sheet as u32,
+                        row: *row,
+                        column,
+                    });
+                }
+            }
+        }
+        cells
     }
 
     /// Returns the list of pending diffs and removes them from the queue
@@ -336,10 +536,16 @@ impl UserModel {
         Ok(())
     }
 
-    /// Set the input in a cell
+    /// Set the input in a cell.
+    /// Formulas are written in the display language and the locale of the workbook.
+    ///
+    /// If the value does not satisfy the data validation of the cell and its error style is
+    /// `stop` the cell is left unchanged and the error message is returned. With the `warning` and
+    /// `information` styles the value is set, use [UserModel::check_data_validation] to alert the user.
     ///
     /// See also:
     /// * [Model::set_user_input]
+    /// * [Model::get_canonical_user_input]
     pub fn set_user_input(
         &mut self,
         sheet: u32,
@@ -353,24 +559,48 @@ impl UserModel {
         if !is_valid_row(row) {
             return Err("Invalid row".to_string());
         }
+        self.model
+            .check_range_editable(sheet, (row, column, row, column))?;
         let old_value = self
             .model
             .workbook
             .worksheet(sheet)?
             .cell(row, column)
             .cloned();
+        // Diffs are language independent
+        let new_value = self.model.get_canonical_user_input(value);
         self.model
-            .set_user_input(sheet, row, column, value.to_string())?;
+            .set_user_input(sheet, row, column, new_value.clone())?;
 
-        self.evaluate_if_not_paused();
+        if let Some(data_validation) = self.model.check_data_validation(sheet, row, column)? {
+            if data_validation.show_error_message
+                && data_validation.error_style == DataValidationErrorStyle::Stop
+            {
+                match old_value {
+                    Some(cell) => self
+                        .model
+                        .workbook
+                        .worksheet_mut(sheet)?
+                        .update_cell(row, column, cell)?,
+                    None => self.model.cell_clear_all(sheet, row, column)?,
+                }
+                return Err(data_validation.error_message.unwrap_or_else(|| {
+                    "The value doesn't match the data validation restrictions defined for this cell"
+                        .to_string()
+                }));
+            }
+        }
 
         let mut diff_list = vec![Diff::SetCellValue {
             sheet,
             row,
             column,
-            new_value: value.to_string(),
+            new_value,
             old_value: Box::new(old_value),
         }];
+        self.update_tables_after_input(&mut diff_list, sheet, row, column)?;
+
+        self.evaluate_if_not_paused();
 
         let line_count = value.split("\n").count();
         let row_height = self.model.get_row_height(sheet, row)?;
@@ -389,13 +619,63 @@ impl UserModel {
         Ok(())
     }
 
-    /// Returns the content of a cell
+    /// Returns the runs of the cell if its value is a string with rich text
     ///
     /// See also:
-    /// * [Model::get_cell_content]
+    /// * [Model::get_cell_rich_text]
     #[inline]
+    pub fn get_cell_rich_text(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Vec<TextRun>>, String> {
+        self.model.get_cell_rich_text(sheet, row, column)
+    }
+
+    /// Sets the value of the cell to a string with rich text
+    ///
+    /// See also:
+    /// * [Model::set_cell_rich_text]
+    pub fn set_cell_rich_text(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        runs: Vec<TextRun>,
+    ) -> Result<(), String> {
+        self.model
+            .check_range_editable(sheet, (row, column, row, column))?;
+        let old_value = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .cell(row, column)
+            .cloned();
+        self.model
+            .set_cell_rich_text(sheet, row, column, runs.clone())?;
+        self.evaluate_if_not_paused();
+        self.push_diff_list(vec![Diff::SetCellRichText {
+            sheet,
+            row,
+            column,
+            new_value: runs,
+            old_value: Box::new(old_value),
+        }]);
+        Ok(())
+    }
+
+    /// Returns the content of a cell.
+    /// Formulas are shown in the display language and the locale of the workbook.
+    /// The content of hidden cells in a protected sheet is empty.
+    ///
+    /// See also:
+    /// * [Model::get_localized_cell_content]
     pub fn get_cell_content(&self, sheet: u32, row: i32, column: i32) -> Result<String, String> {
-        self.model.get_cell_content(sheet, row, column)
+        if self.model.is_sheet_protected(sheet)? && self.model.is_cell_hidden(sheet, row, column)? {
+            return Ok("".to_string());
+        }
+        self.model.get_localized_cell_content(sheet, row, column)
     }
 
     /// Returns the formatted value of a cell
@@ -425,6 +705,7 @@ impl UserModel {
     /// See also:
     /// * [Model::new_sheet]
     pub fn new_sheet(&mut self) -> Result<(), String> {
+        self.model.check_workbook_structure()?;
         let (name, index) = self.model.new_sheet();
         self.set_selected_sheet(index)?;
         self.push_diff_list(vec![Diff::NewSheet { index, name }]);
@@ -436,6 +717,7 @@ impl UserModel {
     /// See also:
     /// * [Model::delete_sheet]
     pub fn delete_sheet(&mut self, sheet: u32) -> Result<(), String> {
+        self.model.check_workbook_structure()?;
         self.push_diff_list(vec![Diff::DeleteSheet { sheet }]);
         // There is no coming back
         self.history.clear();
@@ -456,6 +738,7 @@ impl UserModel {
     /// See also:
     /// * [Model::rename_sheet_by_index]
     pub fn rename_sheet(&mut self, sheet: u32, new_name: &str) -> Result<(), String> {
+        self.model.check_workbook_structure()?;
         let old_value = self.model.workbook.worksheet(sheet)?.name.clone();
         if old_value == new_name {
             return Ok(());
@@ -469,6 +752,54 @@ impl UserModel {
         Ok(())
     }
 
+    /// Moves the sheet at index `sheet` to `new_index`
+    ///
+    /// See also:
+    /// * [Model::move_sheet]
+    pub fn move_sheet(&mut self, sheet: u32, new_index: u32) -> Result<(), String> {
+        self.model.check_workbook_structure()?;
+        self.model.move_sheet(sheet, new_index)?;
+        self.push_diff_list(vec![Diff::MoveSheet { sheet, new_index }]);
+        Ok(())
+    }
+
+    /// Inserts a copy of the sheet right after it and selects the copy
+    ///
+    /// See also:
+    /// * [Model::duplicate_sheet]
+    pub fn duplicate_sheet(&mut self, sheet: u32) -> Result<(), String> {
+        self.model.check_workbook_structure()?;
+        let (_, index) = self.model.duplicate_sheet(sheet)?;
+        self.set_selected_sheet(index)?;
+        self.push_diff_list(vec![Diff::DuplicateSheet { sheet }]);
+        Ok(())
+    }
+
+    /// Copies the `sheet` of the `source` workbook at the end of this one and selects the copy
+    ///
+    /// See also:
+    /// * [Model::copy_sheet_to]
+    pub fn copy_sheet_from(&mut self, source: &UserModel, sheet: u32) -> Result<(), String> {
+        self.model.check_workbook_structure()?;
+        let (worksheet, tables, defined_names) =
+            source.model.get_sheet_copy_for(sheet, &mut self.model)?;
+        let index = self.model.workbook.worksheets.len() as u32;
+        self.model.insert_worksheet(
+            index,
+            worksheet.clone(),
+            tables.clone(),
+            defined_names.clone(),
+        )?;
+        self.set_selected_sheet(index)?;
+        self.push_diff_list(vec![Diff::CopySheet {
+            index,
+            worksheet: Box::new(worksheet),
+            tables,
+            defined_names,
+        }]);
+        Ok(())
+    }
+
     /// Sets sheet color
     ///
     /// Note: an empty string will remove the color
@@ -477,6 +808,7 @@ impl UserModel {
     /// * [Model::set_sheet_color]
     /// * [UserModel::get_worksheets_properties]
     pub fn set_sheet_color(&mut self, sheet: u32, color: &str) -> Result<(), String> {
+        self.model.check_workbook_structure()?;
         let old_value = match &self.model.workbook.worksheet(sheet)?.color {
             Some(c) => c.clone(),
             None => "".to_string(),
@@ -495,7 +827,11 @@ impl UserModel {
     /// See also:
     /// * [Model::cell_clear_all]
     pub fn range_clear_all(&mut self, range: &Area) -> Result<(), String> {
+        let range = &self.expand_area_to_merged_cells(range)?;
         let sheet = range.sheet;
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_cells, "format cells")?;
+        self.check_area_editable(range)?;
         let mut diff_list = Vec::new();
         for row in range.row..range.row + range.height {
             for column in range.column..range.column + range.width {
@@ -525,7 +861,9 @@ impl UserModel {
     /// See also:
     /// * [Model::cell_clear_contents]
     pub fn range_clear_contents(&mut self, range: &Area) -> Result<(), String> {
+        let range = &self.expand_area_to_merged_cells(range)?;
         let sheet = range.sheet;
+        self.check_area_editable(range)?;
         let mut diff_list = Vec::new();
         for row in range.row..range.row + range.height {
             for column in range.column..range.column + range.width {
@@ -553,9 +891,16 @@ impl UserModel {
     /// See also:
     /// * [Model::insert_rows]
     pub fn insert_row(&mut self, sheet: u32, row: i32) -> Result<(), String> {
-        let diff_list = vec![Diff::InsertRow { sheet, row }];
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_insert_rows, "insert rows")?;
+        let mut diff_list = vec![Diff::InsertRow { sheet, row }];
+        self.model.insert_rows(sheet, row, 1)?;
+        self.fill_calculated_columns_with_diff(&mut diff_list, sheet, row)?;
+        if diff_list.len() > 1 {
+            self.evaluate_if_not_paused();
+        }
         self.push_diff_list(diff_list);
-        self.model.insert_rows(sheet, row, 1)
+        Ok(())
     }
 
     /// Deletes a row
@@ -563,6 +908,10 @@ impl UserModel {
     /// See also:
     /// * [Model::delete_rows]
     pub fn delete_row(&mut self, sheet: u32, row: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_delete_rows, "delete rows")?;
+        self.model
+            .check_range_editable(sheet, (row, 1, row, LAST_COLUMN))?;
         let mut row_data = None;
         let worksheet = self.model.workbook.worksheet(sheet)?;
         for rd in &worksheet.rows {
@@ -571,10 +920,16 @@ impl UserModel {
                 break;
             }
         }
-        let data = worksheet.sheet_data.get(&row).unwrap().clone();
+        let data = worksheet.sheet_data.get(&row).cloned().unwrap_or_default();
         let old_data = Box::new(RowData {
             row: row_data,
             data,
+            comments: self.model.get_comments_in_row(sheet, row)?,
+            hyperlinks: self.model.get_hyperlinks_in_row(sheet, row)?,
+            tables: self.model.get_tables(sheet)?,
+            conditional_formats: self.model.get_all_conditional_formats(),
+            data_validations: self.model.get_all_data_validations(),
+            pivot_tables: self.model.get_all_pivot_tables(),
         });
         let diff_list = vec![Diff::DeleteRow {
             sheet,
@@ -590,6 +945,8 @@ impl UserModel {
     /// See also:
     /// * [Model::insert_columns]
     pub fn insert_column(&mut self, sheet: u32, column: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_insert_columns, "insert columns")?;
         let diff_list = vec![Diff::InsertColumn { sheet, column }];
         self.push_diff_list(diff_list);
         self.model.insert_columns(sheet, column, 1)
@@ -604,6 +961,10 @@ impl UserModel {
         if !is_valid_column_number(column) {
             return Err(format!("Column number '{column}' is not valid."));
         }
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_delete_columns, "delete columns")?;
+        self.model
+            .check_range_editable(sheet, (1, column, LAST_ROW, column))?;
 
         let mut column_data = None;
         for col in &worksheet.cols {
@@ -613,9 +974,7 @@ impl UserModel {
                 column_data = Some(Col {
                     min: column,
                     max: column,
-                    width: col.width,
-                    custom_width: col.custom_width,
-                    style: col.style,
+                    ..col.clone()
                 });
                 break;
             }
@@ -634,17 +993,233 @@ impl UserModel {
             old_data: Box::new(ColumnData {
                 column: column_data,
                 data,
+                comments: self.model.get_comments_in_column(sheet, column)?,
+                hyperlinks: self.model.get_hyperlinks_in_column(sheet, column)?,
+                tables: self.model.get_tables(sheet)?,
+                conditional_formats: self.model.get_all_conditional_formats(),
+                data_validations: self.model.get_all_data_validations(),
+                pivot_tables: self.model.get_all_pivot_tables(),
             }),
         }];
         self.push_diff_list(diff_list);
         self.model.delete_columns(sheet, column, 1)
     }
 
+    /// Moves `row_count` rows starting at `row` by `delta` rows, like dragging the selected rows
+    /// to a new position. The rows in between are displaced to fill the gap.
+    ///
+    /// See also:
+    /// * [Model::move_rows_action]
+    pub fn move_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        row_count: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let first_row = row.min(row + delta);
+        let last_row = (row + row_count - 1).max(row + row_count - 1 + delta);
+        self.model
+            .check_range_editable(sheet, (first_row, 1, last_row, LAST_COLUMN))?;
+        let old_data =
+            self.move_with_data(sheet, (first_row, 1, last_row, LAST_COLUMN), |model| {
+                model.move_rows_action(sheet, row, row_count, delta)
+            })?;
+        self.push_diff_list(vec![Diff::MoveRows {
+            sheet,
+            row,
+            row_count,
+            delta,
+            old_data,
+        }]);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Moves `column_count` columns starting at `column` by `delta` columns, like dragging the
+    /// selected columns to a new position. The columns in between are displaced to fill the gap.
+    ///
+    /// See also:
+    /// * [Model::move_columns_action]
+    pub fn move_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        column_count: i32,
+        delta: i32,
+    ) -> Result<(), String> {
+        let first_column = column.min(column + delta);
+        let last_column = (column + column_count - 1).max(column + column_count - 1 + delta);
+        self.model
+            .check_range_editable(sheet, (1, first_column, LAST_ROW, last_column))?;
+        let old_data =
+            self.move_with_data(sheet, (1, first_column, LAST_ROW, last_column), |model| {
+                model.move_columns_action(sheet, column, column_count, delta)
+            })?;
+        self.push_diff_list(vec![Diff::MoveColumns {
+            sheet,
+            column,
+            column_count,
+            delta,
+            old_data,
+        }]);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Moves the cells in `area` to (`target_row`, `target_column`), like dragging the border
+    /// of the selected range. The cells in the target are overwritten.
+    ///
+    /// See also:
+    /// * [Model::move_range_action]
+    pub fn move_range(
+        &mut self,
+        area: &Area,
+        target_row: i32,
+        target_column: i32,
+    ) -> Result<(), String> {
+        let sheet = area.sheet;
+        let row_delta = target_row - area.row;
+        let column_delta = target_column - area.column;
+        self.check_area_editable(area)?;
+        self.check_area_editable(&Area {
+            row: target_row,
+            column: target_column,
+            ..*area
+        })?;
+        let range = (
+            area.row.min(target_row),
+            area.column.min(target_column),
+            area.row + area.height - 1 + row_delta.max(0),
+            area.column + area.width - 1 + column_delta.max(0),
+        );
+        let old_data = self.move_with_data(sheet, range, |model| {
+            model.move_range_action(area, target_row, target_column)
+        })?;
+        self.push_diff_list(vec![Diff::MoveRange {
+            sheet,
+            row: area.row,
+            column: area.column,
+            width: area.width,
+            height: area.height,
+            target_row,
+            target_column,
+            old_data,
+        }]);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Runs the `move_action` and returns the data needed to undo it.
+    /// All the cells that move have to be in the `range` of the `sheet`.
+    fn move_with_data(
+        &mut self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+        move_action: impl FnOnce(&mut Model) -> Result<(), String>,
+    ) -> Result<Box<MoveData>, String> {
+        let (row1, column1, row2, column2) = range;
+        let is_in_range = |cell_sheet: u32, row: i32, column: i32| {
+            cell_sheet == sheet
+                && (row1..=row2).contains(&row)
+                && (column1..=column2).contains(&column)
+        };
+        let worksheet = self.model.workbook.worksheet(sheet)?;
+        let mut cells = Vec::new();
+        for (row, data_row) in &worksheet.sheet_data {
+            if (row1..=row2).contains(row) {
+                for (column, cell) in data_row {
+                    if (column1..=column2).contains(column) {
+                        cells.push((*row, *column, cell.clone()));
+                    }
+                }
+            }
+        }
+        let mut old_data = MoveData {
+            range,
+            cells,
+            formulas: Vec::new(),
+            comments: worksheet.comments.clone(),
+            hyperlinks: worksheet.hyperlinks.clone(),
+            merge_cells: worksheet.merge_cells.clone(),
+            rows: worksheet.rows.clone(),
+            cols: worksheet.cols.clone(),
+            tables: self.model.get_tables(sheet)?,
+            auto_filter: worksheet.auto_filter.clone(),
+            conditional_formats: self.model.get_all_conditional_formats(),
+            data_validations: self.model.get_all_data_validations(),
+            pivot_tables: self.model.get_all_pivot_tables(),
+        };
+        let mut formulas = Vec::new();
+        for cell in self.model.get_all_cells() {
+            if is_in_range(cell.index, cell.row, cell.column) {
+                continue;
+            }
+            if let Some(formula) = self
+                .model
+                .get_cell_formula(cell.index, cell.row, cell.column)?
+            {
+                formulas.push((cell.index, cell.row, cell.column, formula));
+            }
+        }
+
+        move_action(&mut self.model)?;
+
+        for (formula_sheet, row, column, formula) in formulas {
+            if self
+                .model
+                .get_cell_formula(formula_sheet, row, column)?
+                .as_ref()
+                != Some(&formula)
+            {
+                old_data
+                    .formulas
+                    .push((formula_sheet, row, column, formula));
+            }
+        }
+        Ok(Box::new(old_data))
+    }
+
+    /// Puts back the parts of the `sheet` changed by a move
+    fn restore_move_data(&mut self, sheet: u32, old_data: &MoveData) -> Result<(), String> {
+        let (row1, column1, row2, column2) = old_data.range;
+        let worksheet = self.model.workbook.worksheet_mut(sheet)?;
+        for (row, data_row) in worksheet.sheet_data.iter_mut() {
+            if (row1..=row2).contains(row) {
+                data_row.retain(|column, _| !(column1..=column2).contains(column));
+            }
+        }
+        for (row, column, cell) in &old_data.cells {
+            worksheet.update_cell(*row, *column, cell.clone())?;
+        }
+        worksheet.comments = old_data.comments.clone();
+        worksheet.hyperlinks = old_data.hyperlinks.clone();
+        worksheet.merge_cells = old_data.merge_cells.clone();
+        worksheet.rows = old_data.rows.clone();
+        worksheet.cols = old_data.cols.clone();
+        worksheet.auto_filter = old_data.auto_filter.clone();
+        for table in &old_data.tables {
+            self.model.set_table(&table.name, Some(table.clone()))?;
+        }
+        self.model
+            .set_all_conditional_formats(&old_data.conditional_formats);
+        self.model
+            .set_all_data_validations(&old_data.data_validations);
+        self.model.set_all_pivot_tables(&old_data.pivot_tables);
+        for (formula_sheet, row, column, formula) in &old_data.formulas {
+            self.model
+                .update_cell_with_formula(*formula_sheet, *row, *column, formula.clone())?;
+        }
+        Ok(())
+    }
+
     /// Sets the width of a column
     ///
     /// See also:
     /// * [Model::set_column_width]
     pub fn set_column_width(&mut self, sheet: u32, column: i32, width: f64) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_columns, "format columns")?;
         let old_value = self.model.get_column_width(sheet, column)?;
         self.push_diff_list(vec![Diff::SetColumnWidth {
             sheet,
@@ -660,6 +1235,8 @@ impl UserModel {
     /// See also:
     /// * [Model::set_row_height]
     pub fn set_row_height(&mut self, sheet: u32, row: i32, height: f64) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_rows, "format rows")?;
         let old_value = self.model.get_row_height(sheet, row)?;
         self.push_diff_list(vec![Diff::SetRowHeight {
             sheet,
@@ -688,75 +1265,357 @@ impl UserModel {
         self.model.get_column_width(sheet, column)
     }
 
-    /// Returns the number of frozen rows in the sheet
+    /// Returns true if the row is hidden
     ///
     /// See also:
-    /// * [Model::get_frozen_rows_count()]
+    /// * [Model::is_row_hidden]
     #[inline]
-    pub fn get_frozen_rows_count(&self, sheet: u32) -> Result<i32, String> {
-        self.model.get_frozen_rows_count(sheet)
+    pub fn is_row_hidden(&self, sheet: u32, row: i32) -> Result<bool, String> {
+        self.model.is_row_hidden(sheet, row)
     }
 
-    /// Returns the number of frozen columns in the sheet
-    ///
-    /// See also:
-    /// * [Model::get_frozen_columns_count()]
-    #[inline]
-    pub fn get_frozen_columns_count(&self, sheet: u32) -> Result<i32, String> {
-        self.model.get_frozen_columns_count(sheet)
+    /// Hides (or unhides) the rows from `row_start` to `row_end`, both included
+    pub fn set_rows_hidden(
+        &mut self,
+        sheet: u32,
+        row_start: i32,
+        row_end: i32,
+        hidden: bool,
+    ) -> Result<(), String> {
+        self.check_row_range(row_start, row_end)?;
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_rows, "format rows")?;
+        let mut diff_list = Vec::new();
+        for row in row_start..=row_end {
+            self.set_row_hidden_with_diff(&mut diff_list, sheet, row, hidden)?;
+        }
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
     }
 
-    /// Sets the number of frozen rows in sheet
+    /// Returns the outline level of the row, 0 if it is not grouped
     ///
     /// See also:
-    /// * [Model::set_frozen_rows()]
-    pub fn set_frozen_rows_count(&mut self, sheet: u32, frozen_rows: i32) -> Result<(), String> {
-        let old_value = self.model.get_frozen_rows_count(sheet)?;
-        self.push_diff_list(vec![Diff::SetFrozenRowsCount {
-            sheet,
-            new_value: frozen_rows,
-            old_value,
-        }]);
-        self.model.set_frozen_rows(sheet, frozen_rows)
+    /// * [Model::get_row_outline_level]
+    #[inline]
+    pub fn get_row_outline_level(&self, sheet: u32, row: i32) -> Result<u8, String> {
+        self.model.get_row_outline_level(sheet, row)
     }
 
-    /// Sets the number of frozen columns in sheet
+    /// Returns true if the group of rows that ends right before `row` is collapsed
     ///
     /// See also:
-    /// * [Model::set_frozen_columns()]
-    pub fn set_frozen_columns_count(
-        &mut self,
-        sheet: u32,
-        frozen_columns: i32,
-    ) -> Result<(), String> {
-        let old_value = self.model.get_frozen_columns_count(sheet)?;
-        self.push_diff_list(vec![Diff::SetFrozenColumnsCount {
-            sheet,
-            new_value: frozen_columns,
-            old_value,
-        }]);
-        self.model.set_frozen_columns(sheet, frozen_columns)
+    /// * [Model::is_row_collapsed]
+    #[inline]
+    pub fn is_row_collapsed(&self, sheet: u32, row: i32) -> Result<bool, String> {
+        self.model.is_row_collapsed(sheet, row)
     }
 
-    /// Paste `styles` in the selected area
-    pub fn on_paste_styles(&mut self, styles: &[Vec<Style>]) -> Result<(), String> {
-        let styles_heigh = styles.len() as i32;
-        let styles_width = styles[0].len() as i32;
-        let sheet = if let Some(view) = self.model.workbook.views.get(&self.model.view_id) {
-            view.sheet
-        } else {
-            return Ok(());
-        };
-        let range = if let Ok(worksheet) = self.model.workbook.worksheet(sheet) {
-            if let Some(view) = worksheet.views.get(&self.model.view_id) {
-                view.range
-            } else {
-                return Ok(());
+    /// Groups the rows from `row_start` to `row_end`, increasing their outline level by one
+    pub fn group_rows(&mut self, sheet: u32, row_start: i32, row_end: i32) -> Result<(), String> {
+        self.check_row_range(row_start, row_end)?;
+        self.model.check_sheet_not_protected(sheet, "group rows")?;
+        let mut levels = Vec::new();
+        for row in row_start..=row_end {
+            let level = self.model.get_row_outline_level(sheet, row)?;
+            if level >= MAX_OUTLINE_LEVEL {
+                return Err(format!("Cannot group more than {MAX_OUTLINE_LEVEL} levels"));
             }
-        } else {
-            return Ok(());
-        };
-
+            levels.push((row, level));
+        }
+        let mut diff_list = Vec::new();
+        for (row, old_value) in levels {
+            self.model
+                .set_row_outline_level(sheet, row, old_value + 1)?;
+            diff_list.push(Diff::SetRowOutlineLevel {
+                sheet,
+                row,
+                old_value,
+                new_value: old_value + 1,
+            });
+        }
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Ungroups the rows from `row_start` to `row_end`, decreasing their outline level by one
+    pub fn ungroup_rows(&mut self, sheet: u32, row_start: i32, row_end: i32) -> Result<(), String> {
+        self.check_row_range(row_start, row_end)?;
+        self.model
+            .check_sheet_not_protected(sheet, "ungroup rows")?;
+        let mut diff_list = Vec::new();
+        for row in row_start..=row_end {
+            let old_value = self.model.get_row_outline_level(sheet, row)?;
+            if old_value > 0 {
+                self.model
+                    .set_row_outline_level(sheet, row, old_value - 1)?;
+                diff_list.push(Diff::SetRowOutlineLevel {
+                    sheet,
+                    row,
+                    old_value,
+                    new_value: old_value - 1,
+                });
+            }
+        }
+        if diff_list.is_empty() {
+            return Err("The rows are not grouped".to_string());
+        }
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Collapses the innermost group that contains `row`, hiding all its rows
+    pub fn collapse_row_group(&mut self, sheet: u32, row: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_rows, "format rows")?;
+        let (_, first, last) = self.get_innermost_row_group(sheet, row)?;
+        let mut diff_list = Vec::new();
+        for row in first..=last {
+            self.set_row_hidden_with_diff(&mut diff_list, sheet, row, true)?;
+        }
+        self.set_row_collapsed_with_diff(&mut diff_list, sheet, last + 1, true)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Expands the innermost group that contains `row`.
+    /// The rows of collapsed groups inside it stay hidden.
+    pub fn expand_row_group(&mut self, sheet: u32, row: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_rows, "format rows")?;
+        let (level, first, last) = self.get_innermost_row_group(sheet, row)?;
+        let mut diff_list = Vec::new();
+        for row in first..=last {
+            if !self.model.is_row_in_collapsed_group(sheet, row, level)? {
+                self.set_row_hidden_with_diff(&mut diff_list, sheet, row, false)?;
+            }
+        }
+        self.set_row_collapsed_with_diff(&mut diff_list, sheet, last + 1, false)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns true if the column is hidden
+    ///
+    /// See also:
+    /// * [Model::is_column_hidden]
+    #[inline]
+    pub fn is_column_hidden(&self, sheet: u32, column: i32) -> Result<bool, String> {
+        self.model.is_column_hidden(sheet, column)
+    }
+
+    /// Hides (or unhides) the columns from `column_start` to `column_end`, both included
+    pub fn set_columns_hidden(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+        hidden: bool,
+    ) -> Result<(), String> {
+        self.check_column_range(column_start, column_end)?;
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_columns, "format columns")?;
+        let mut diff_list = Vec::new();
+        for column in column_start..=column_end {
+            self.set_column_hidden_with_diff(&mut diff_list, sheet, column, hidden)?;
+        }
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the outline level of the column, 0 if it is not grouped
+    ///
+    /// See also:
+    /// * [Model::get_column_outline_level]
+    #[inline]
+    pub fn get_column_outline_level(&self, sheet: u32, column: i32) -> Result<u8, String> {
+        self.model.get_column_outline_level(sheet, column)
+    }
+
+    /// Returns true if the group of columns that ends right before `column` is collapsed
+    ///
+    /// See also:
+    /// * [Model::is_column_collapsed]
+    #[inline]
+    pub fn is_column_collapsed(&self, sheet: u32, column: i32) -> Result<bool, String> {
+        self.model.is_column_collapsed(sheet, column)
+    }
+
+    /// Groups the columns from `column_start` to `column_end`, increasing their outline level by one
+    pub fn group_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), String> {
+        self.check_column_range(column_start, column_end)?;
+        self.model
+            .check_sheet_not_protected(sheet, "group columns")?;
+        let mut levels = Vec::new();
+        for column in column_start..=column_end {
+            let level = self.model.get_column_outline_level(sheet, column)?;
+            if level >= MAX_OUTLINE_LEVEL {
+                return Err(format!("Cannot group more than {MAX_OUTLINE_LEVEL} levels"));
+            }
+            levels.push((column, level));
+        }
+        let mut diff_list = Vec::new();
+        for (column, old_value) in levels {
+            self.model
+                .set_column_outline_level(sheet, column, old_value + 1)?;
+            diff_list.push(Diff::SetColumnOutlineLevel {
+                sheet,
+                column,
+                old_value,
+                new_value: old_value + 1,
+            });
+        }
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Ungroups the columns from `column_start` to `column_end`, decreasing their outline level by one
+    pub fn ungroup_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), String> {
+        self.check_column_range(column_start, column_end)?;
+        self.model
+            .check_sheet_not_protected(sheet, "ungroup columns")?;
+        let mut diff_list = Vec::new();
+        for column in column_start..=column_end {
+            let old_value = self.model.get_column_outline_level(sheet, column)?;
+            if old_value > 0 {
+                self.model
+                    .set_column_outline_level(sheet, column, old_value - 1)?;
+                diff_list.push(Diff::SetColumnOutlineLevel {
+                    sheet,
+                    column,
+                    old_value,
+                    new_value: old_value - 1,
+                });
+            }
+        }
+        if diff_list.is_empty() {
+            return Err("The columns are not grouped".to_string());
+        }
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Collapses the innermost group that contains `column`, hiding all its columns
+    pub fn collapse_column_group(&mut self, sheet: u32, column: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_columns, "format columns")?;
+        let (_, first, last) = self.get_innermost_column_group(sheet, column)?;
+        let mut diff_list = Vec::new();
+        for column in first..=last {
+            self.set_column_hidden_with_diff(&mut diff_list, sheet, column, true)?;
+        }
+        self.set_column_collapsed_with_diff(&mut diff_list, sheet, last + 1, true)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Expands the innermost group that contains `column`.
+    /// The columns of collapsed groups inside it stay hidden.
+    pub fn expand_column_group(&mut self, sheet: u32, column: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_columns, "format columns")?;
+        let (level, first, last) = self.get_innermost_column_group(sheet, column)?;
+        let mut diff_list = Vec::new();
+        for column in first..=last {
+            if !self
+                .model
+                .is_column_in_collapsed_group(sheet, column, level)?
+            {
+                self.set_column_hidden_with_diff(&mut diff_list, sheet, column, false)?;
+            }
+        }
+        self.set_column_collapsed_with_diff(&mut diff_list, sheet, last + 1, false)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the number of frozen rows in the sheet
+    ///
+    /// See also:
+    /// * [Model::get_frozen_rows_count()]
+    #[inline]
+    pub fn get_frozen_rows_count(&self, sheet: u32) -> Result<i32, String> {
+        self.model.get_frozen_rows_count(sheet)
+    }
+
+    /// Returns the number of frozen columns in the sheet
+    ///
+    /// See also:
+    /// * [Model::get_frozen_columns_count()]
+    #[inline]
+    pub fn get_frozen_columns_count(&self, sheet: u32) -> Result<i32, String> {
+        self.model.get_frozen_columns_count(sheet)
+    }
+
+    /// Sets the number of frozen rows in sheet
+    ///
+    /// See also:
+    /// * [Model::set_frozen_rows()]
+    pub fn set_frozen_rows_count(&mut self, sheet: u32, frozen_rows: i32) -> Result<(), String> {
+        let old_value = self.model.get_frozen_rows_count(sheet)?;
+        self.push_diff_list(vec![Diff::SetFrozenRowsCount {
+            sheet,
+            new_value: frozen_rows,
+            old_value,
+        }]);
+        self.model.set_frozen_rows(sheet, frozen_rows)
+    }
+
+    /// Sets the number of frozen columns in sheet
+    ///
+    /// See also:
+    /// * [Model::set_frozen_columns()]
+    pub fn set_frozen_columns_count(
+        &mut self,
+        sheet: u32,
+        frozen_columns: i32,
+    ) -> Result<(), String> {
+        let old_value = self.model.get_frozen_columns_count(sheet)?;
+        self.push_diff_list(vec![Diff::SetFrozenColumnsCount {
+            sheet,
+            new_value: frozen_columns,
+            old_value,
+        }]);
+        self.model.set_frozen_columns(sheet, frozen_columns)
+    }
+
+    /// Paste `styles` in the selected area
+    pub fn on_paste_styles(&mut self, styles: &[Vec<Style>]) -> Result<(), String> {
+        let styles_heigh = styles.len() as i32;
+        let styles_width = styles[0].len() as i32;
+        let sheet = if let Some(view) = self.model.workbook.views.get(&self.model.view_id) {
+            view.sheet
+        } else {
+            return Ok(());
+        };
+        let range = if let Ok(worksheet) = self.model.workbook.worksheet(sheet) {
+            if let Some(view) = worksheet.views.get(&self.model.view_id) {
+                view.range
+            } else {
+                return Ok(());
+            }
+        } else {
+            return Ok(());
+        };
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_cells, "format cells")?;
+
         // If the pasted area is smaller than the selected area we increase it
         let [row_start, column_start, row_end, column_end] = range;
         let last_row = row_end.max(row_start + styles_heigh - 1);
@@ -796,7 +1655,10 @@ impl UserModel {
         range: &Area,
         border_area: &BorderArea,
     ) -> Result<(), String> {
+        let range = &self.expand_area_to_merged_cells(range)?;
         let sheet = range.sheet;
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_cells, "format cells")?;
         let mut diff_list = Vec::new();
         let last_row = range.row + range.height - 1;
         let last_column = range.column + range.width - 1;
@@ -1008,7 +1870,15 @@ impl UserModel {
         style_path: &str,
         value: &str,
     ) -> Result<(), String> {
+        let range = &self.expand_area_to_merged_cells(range)?;
         let sheet = range.sheet;
+        if style_path.starts_with("protection") {
+            self.model
+                .check_sheet_not_protected(sheet, "change the protection of cells")?;
+        } else {
+            self.model
+                .check_sheet_allows(sheet, |p| p.allow_format_cells, "format cells")?;
+        }
         let mut diff_list = Vec::new();
         for row in range.row..range.row + range.height {
             for column in range.column..range.column + range.width {
@@ -1029,13 +1899,16 @@ impl UserModel {
                     }
                     "font.color" => {
                         style.font.color = color(value)?;
+                        style.font.color_theme = None;
                     }
                     "fill.bg_color" => {
                         style.fill.bg_color = color(value)?;
+                        style.fill.bg_color_theme = None;
                         style.fill.pattern_type = "solid".to_string();
                     }
                     "fill.fg_color" => {
                         style.fill.fg_color = color(value)?;
+                        style.fill.fg_color_theme = None;
                         style.fill.pattern_type = "solid".to_string();
                     }
                     "num_fmt" => {
@@ -1089,6 +1962,26 @@ impl UserModel {
                             style.alignment = Some(alignment)
                         }
                     },
+                    "protection.locked" => match style.protection {
+                        Some(ref mut p) => p.locked = boolean(value)?,
+                        None => {
+                            let protection = Protection {
+                                locked: boolean(value)?,
+                                ..Default::default()
+                            };
+                            style.protection = Some(protection)
+                        }
+                    },
+                    "protection.hidden" => match style.protection {
+                        Some(ref mut p) => p.hidden = boolean(value)?,
+                        None => {
+                            let protection = Protection {
+                                hidden: boolean(value)?,
+                                ..Default::default()
+                            };
+                            style.protection = Some(protection)
+                        }
+                    },
                     _ => {
                         return Err(format!("Invalid style path: '{style_path}'."));
                     }
@@ -1116,8 +2009,124 @@ impl UserModel {
         self.model.get_style_for_cell(sheet, row, column)
     }
 
+    /// Returns the names of the named styles of the workbook and the built-in styles
+    ///
+    /// See also:
+    /// * [Model::get_named_styles]
+    pub fn get_named_styles(&self) -> Vec<String> {
+        self.model.get_named_styles()
+    }
+
+    /// Returns the style of a named style
+    ///
+    /// See also:
+    /// * [Model::get_named_style]
+    pub fn get_named_style(&self, style_name: &str) -> Result<Style, String> {
+        self.model.get_named_style(style_name)
+    }
+
+    /// Returns the name of the named style of a cell
+    pub fn get_cell_style_name(&self, sheet: u32, row: i32, column: i32) -> Result<String, String> {
+        self.model.get_cell_style_name(sheet, row, column)
+    }
+
+    /// Adds the built-in style `style_name` to the workbook if needed
+    fn add_builtin_named_style(&mut self, style_name: &str, diff_list: &mut DiffList) {
+        if let Ok(true) = self.model.add_builtin_named_style(style_name) {
+            diff_list.push(Diff::CreateNamedStyle {
+                style_name: style_name.to_string(),
+                style: None,
+            });
+        }
+    }
+
+    /// Applies the named style `style_name` to the cells in the range.
+    /// The cells lose their formatting.
+    pub fn apply_named_style(&mut self, range: &Area, style_name: &str) -> Result<(), String> {
+        let range = &self.expand_area_to_merged_cells(range)?;
+        let sheet = range.sheet;
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_format_cells, "format cells")?;
+        let mut diff_list = Vec::new();
+        self.add_builtin_named_style(style_name, &mut diff_list);
+        // Fails if the style does not exist
+        self.model.workbook.styles.get_named_style(style_name)?;
+        for row in range.row..range.row + range.height {
+            for column in range.column..range.column + range.width {
+                let old_value = self.model.get_style_for_cell(sheet, row, column)?;
+                let old_style_name = self.model.get_cell_style_name(sheet, row, column)?;
+                self.model
+                    .apply_named_style(sheet, row, column, style_name)?;
+                diff_list.push(Diff::ApplyNamedStyle {
+                    sheet,
+                    row,
+                    column,
+                    style_name: style_name.to_string(),
+                    old_value: Box::new(old_value),
+                    old_style_name,
+                });
+            }
+        }
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Adds a new named style
+    ///
+    /// See also:
+    /// * [Model::create_named_style]
+    pub fn create_named_style(&mut self, style_name: &str, style: &Style) -> Result<(), String> {
+        self.model.create_named_style(style_name, style)?;
+        self.push_diff_list(vec![Diff::CreateNamedStyle {
+            style_name: style_name.trim().to_string(),
+            style: Some(Box::new(style.clone())),
+        }]);
+        Ok(())
+    }
+
+    /// Changes a named style. The cells with that named style change too.
+    ///
+    /// See also:
+    /// * [Model::set_named_style]
+    pub fn set_named_style(&mut self, style_name: &str, style: &Style) -> Result<(), String> {
+        let mut diff_list = Vec::new();
+        self.add_builtin_named_style(style_name, &mut diff_list);
+        let old_value = self.model.workbook.styles.get_named_style(style_name)?;
+        self.model.set_named_style(style_name, style)?;
+        diff_list.push(Diff::SetNamedStyle {
+            style_name: style_name.to_string(),
+            old_value: Box::new(old_value),
+            new_value: Box::new(style.clone()),
+        });
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Returns the theme of the workbook
+    pub fn get_theme(&self) -> &Theme {
+        &self.model.workbook.theme
+    }
+
+    /// Changes the theme of the workbook, restyling the cells that use its colors and fonts
+    ///
+    /// See also:
+    /// * [Model::set_theme]
+    pub fn set_theme(&mut self, theme: &Theme) -> Result<(), String> {
+        let old_value = self.model.workbook.theme.clone();
+        self.model.set_theme(theme)?;
+        self.push_diff_list(vec![Diff::SetTheme {
+            old_value: Box::new(old_value),
+            new_value: Box::new(theme.clone()),
+        }]);
+        Ok(())
+    }
+
     /// Fills the cells from `source_area` until `to_row`.
     /// This simulates the user clicking on the cell outline handle and dragging it downwards (or upwards)
+    ///
+    /// Each column of the source continues its series if it has one: numbers with a constant step
+    /// or ratio, dates, names of days and months and texts ending in a number. Otherwise the source
+    /// cells are repeated.
     pub fn auto_fill_rows(&mut self, source_area: &Area, to_row: i32) -> Result<(), String> {
         let mut diff_list = Vec::new();
         let sheet = source_area.sheet;
@@ -1167,8 +2176,15 @@ impl UserModel {
         } else {
             return Err("Invalid parameters for autofill".to_string());
         }
+        let first_row = to_row.min(row1 + height1);
+        let last_row = to_row.max(row1 - 1);
+        self.model
+            .check_range_editable(sheet, (first_row, column1, last_row, column1 + width1 - 1))?;
 
         for column in column1..column1 + width1 {
+            let source_cells: Vec<(i32, i32)> =
+                (row1..row1 + height1).map(|row| (row, column)).collect();
+            let series = self.model.get_fill_series(sheet, &source_cells)?;
             let mut index = 0;
             for row_ref in &row_range {
                 // Save value and style first
@@ -1183,9 +2199,12 @@ impl UserModel {
 
                 // compute the new value and set it
                 let source_row = anchor_row + index;
-                let target_value = self
-                    .model
-                    .extend_to(sheet, source_row, column, row, column)?;
+                let target_value = match series {
+                    Series::Copy => self
+                        .model
+                        .extend_to(sheet, source_row, column, row, column)?,
+                    _ => self.model.get_series_value(&series, (row - row1) as i64),
+                };
                 self.model
                     .set_user_input(sheet, row, column, target_value.to_string())?;
 
@@ -1213,12 +2232,14 @@ impl UserModel {
             }
         }
         self.push_diff_list(diff_list);
-        self.evaluate();
+        self.evaluate_if_not_paused();
         Ok(())
     }
 
     /// Fills the cells from `source_area` until `to_column`.
     /// This simulates the user clicking on the cell outline handle and dragging it to the right (or to the left)
+    ///
+    /// Each row of the source continues its series like in [UserModel::auto_fill_rows]
     pub fn auto_fill_columns(&mut self, source_area: &Area, to_column: i32) -> Result<(), String> {
         let mut diff_list = Vec::new();
         let sheet = source_area.sheet;
@@ -1268,8 +2289,16 @@ impl UserModel {
         } else {
             return Err("Invalid parameters for autofill".to_string());
         }
+        let first_column = to_column.min(column1 + width1);
+        let last_column = to_column.max(column1 - 1);
+        self.model
+            .check_range_editable(sheet, (row1, first_column, row1 + height1 - 1, last_column))?;
 
         for row in row1..row1 + height1 {
+            let source_cells: Vec<(i32, i32)> = (column1..column1 + width1)
+                .map(|column| (row, column))
+                .collect();
+            let series = self.model.get_fill_series(sheet, &source_cells)?;
             let mut index = 0;
             for column_ref in &column_range {
                 let column = *column_ref;
@@ -1284,9 +2313,14 @@ impl UserModel {
 
                 // compute the new value and set it
                 let source_column = anchor_column + index;
-                let target_value = self
-                    .model
-                    .extend_to(sheet, row, source_column, row, column)?;
+                let target_value = match series {
+                    Series::Copy => self
+                        .model
+                        .extend_to(sheet, row, source_column, row, column)?,
+                    _ => self
+                        .model
+                        .get_series_value(&series, (column - column1) as i64),
+                };
                 self.model
                     .set_user_input(sheet, row, column, target_value.to_string())?;
 
@@ -1314,7 +2348,234 @@ impl UserModel {
             }
         }
         self.push_diff_list(diff_list);
-        self.evaluate();
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Fills each line of `area` with a series starting at its first cell, downwards if `down` is
+    /// true or to the right otherwise. Lines that do not start with a number are left untouched.
+    /// With a `stop` value the series ends before passing it, and an area of a single cell in the
+    /// direction of the fill is extended until the stop value.
+    pub fn fill_series(
+        &mut self,
+        area: &Area,
+        down: bool,
+        series_type: SeriesType,
+        step: f64,
+        stop: Option<f64>,
+    ) -> Result<(), String> {
+        let sheet = area.sheet;
+        let row1 = area.row;
+        let column1 = area.column;
+        let mut width = area.width;
+        let mut height = area.height;
+        if self.model.workbook.worksheet(sheet).is_err() {
+            return Err(format!("Invalid worksheet index: '{sheet}'"));
+        }
+        if !is_valid_column_number(column1) || !is_valid_column_number(column1 + width - 1) {
+            return Err(format!("Invalid column: '{}'", column1 + width - 1));
+        }
+        if !is_valid_row(row1) || !is_valid_row(row1 + height - 1) {
+            return Err(format!("Invalid row: '{}'", row1 + height - 1));
+        }
+        // Checks the step
+        Model::get_typed_series(series_type, 0.0, step)?;
+        if stop.is_some() {
+            if down && height == 1 {
+                height = LAST_ROW - row1 + 1;
+            } else if !down && width == 1 {
+                width = LAST_COLUMN - column1 + 1;
+            }
+        }
+        let (lines, length) = if down {
+            (width, height)
+        } else {
+            (height, width)
+        };
+        let cell_at = |line: i32, position: i32| {
+            if down {
+                (row1 + position, column1 + line)
+            } else {
+                (row1 + line, column1 + position)
+            }
+        };
+
+        let mut values = Vec::new();
+        for line in 0..lines {
+            let (row, column) = cell_at(line, 0);
+            let start = match self.model.get_cell_value_by_index(sheet, row, column)? {
+                CellValue::Number(start) => start,
+                _ => continue,
+            };
+            let series = Model::get_typed_series(series_type, start, step)?;
+            let style = self.model.get_style_for_cell(sheet, row, column)?;
+            let increasing = self
+                .model
+                .get_series_number(&series, 1)
+                .is_some_and(|value| value >= start);
+            for position in 1..length {
+                if let Some(stop) = stop {
+                    match self.model.get_series_number(&series, position as i64) {
+                        Some(value) if increasing && value <= stop => {}
+                        Some(value) if !increasing && value >= stop => {}
+                        _ => break,
+                    }
+                }
+                let (row, column) = cell_at(line, position);
+                let value = self.model.get_series_value(&series, position as i64);
+                values.push((row, column, value, style.clone()));
+            }
+        }
+        if let Some((row2, column2)) = values
+            .iter()
+            .map(|(row, column, _, _)| (*row, *column))
+            .reduce(|a, b| (a.0.max(b.0), a.1.max(b.1)))
+        {
+            let (first_row, first_column) = if down {
+                (row1 + 1, column1)
+            } else {
+                (row1, column1 + 1)
+            };
+            self.model
+                .check_range_editable(sheet, (first_row, first_column, row2, column2))?;
+        }
+
+        let mut diff_list = Vec::new();
+        for (row, column, value, style) in values {
+            let old_value = self
+                .model
+                .workbook
+                .worksheet(sheet)?
+                .cell(row, column)
+                .cloned();
+            let old_style = self.model.get_style_for_cell(sheet, row, column)?;
+            self.model
+                .set_user_input(sheet, row, column, value.clone())?;
+            self.model.set_cell_style(sheet, row, column, &style)?;
+            diff_list.push(Diff::SetCellStyle {
+                sheet,
+                row,
+                column,
+                old_value: Box::new(old_style),
+                new_value: Box::new(style),
+            });
+            diff_list.push(Diff::SetCellValue {
+                sheet,
+                row,
+                column,
+                new_value: value,
+                old_value: Box::new(old_value),
+            });
+        }
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns all the cells that match `text` with the `options`
+    pub fn find_all(
+        &self,
+        text: &str,
+        options: &FindOptions,
+    ) -> Result<Vec<CellReferenceIndex>, String> {
+        let regex = get_find_regex(text, options)?;
+        let (sheets, range) = self.get_find_scope(options);
+        self.model.find_cells(&regex, options, &sheets, range)
+    }
+
+    /// Replaces `text` with `replacement` in the selected cell, if it matches, and selects the next
+    /// match. Replacing always looks in the formulas.
+    /// Returns the next cell that matches, if any.
+    pub fn replace(
+        &mut self,
+        text: &str,
+        replacement: &str,
+        options: &FindOptions,
+    ) -> Result<Option<CellReferenceIndex>, String> {
+        let options = &FindOptions {
+            look_in: FindLookIn::Formulas,
+            ..options.clone()
+        };
+        let regex = get_find_regex(text, options)?;
+        let (sheet, row, column) = self.get_selected_cell();
+        let (sheets, range) = self.get_find_scope(options);
+        let is_in_scope = sheets.contains(&sheet)
+            && range.is_none_or(|[row1, column1, row2, column2]| {
+                (row1..=row2).contains(&row) && (column1..=column2).contains(&column)
+            });
+        if is_in_scope
+            && !self
+                .model
+                .find_cells(&regex, options, &[sheet], Some([row, column, row, column]))?
+                .is_empty()
+        {
+            let cell = CellReferenceIndex { sheet, row, column };
+            self.replace_cells(&regex, replacement, options.mode, &[cell])?;
+        }
+        self.find_next(text, options)
+    }
+
+    /// Replaces `text` with `replacement` in all the cells that match, in a single undo step.
+    /// Replacing always looks in the formulas.
+    /// Returns the number of cells that matched.
+    pub fn replace_all(
+        &mut self,
+        text: &str,
+        replacement: &str,
+        options: &FindOptions,
+    ) -> Result<usize, String> {
+        let options = &FindOptions {
+            look_in: FindLookIn::Formulas,
+            ..options.clone()
+        };
+        let regex = get_find_regex(text, options)?;
+        let cells = self.find_all(text, options)?;
+        self.replace_cells(&regex, replacement, options.mode, &cells)?;
+        Ok(cells.len())
+    }
+
+    fn replace_cells(
+        &mut self,
+        regex: &Regex,
+        replacement: &str,
+        mode: FindMode,
+        cells: &[CellReferenceIndex],
+    ) -> Result<(), String> {
+        for cell in cells {
+            self.model
+                .check_range_editable(cell.sheet, (cell.row, cell.column, cell.row, cell.column))?;
+        }
+        let mut diff_list = Vec::new();
+        for &CellReferenceIndex { sheet, row, column } in cells {
+            let content = self.model.get_cell_content(sheet, row, column)?;
+            let new_value = match mode {
+                FindMode::Regex => regex.replace_all(&content, replacement),
+                _ => regex.replace_all(&content, NoExpand(replacement)),
+            }
+            .to_string();
+            if new_value == content {
+                continue;
+            }
+            let old_value = self
+                .model
+                .workbook
+                .worksheet(sheet)?
+                .cell(row, column)
+                .cloned();
+            self.model
+                .set_user_input(sheet, row, column, new_value.clone())?;
+            diff_list.push(Diff::SetCellValue {
+                sheet,
+                row,
+                column,
+                new_value,
+                old_value: Box::new(old_value),
+            });
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+            self.evaluate_if_not_paused();
+        }
         Ok(())
     }
 
@@ -1345,142 +2606,1728 @@ impl UserModel {
         Ok(self.model.workbook.worksheet(sheet)?.show_grid_lines)
     }
 
-    /// Returns a copy of the selected area
-    pub fn copy_to_clipboard(&self) -> Result<Clipboard, String> {
-        let selected_area = self.get_selected_view();
-        let sheet = selected_area.sheet;
-        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+    /// Switches the workbook to the 1904 date system (`true`) or the 1900 date system (`false`)
+    ///
+    /// See also:
+    /// * [Model::set_date_1904]
+    pub fn set_date_1904(&mut self, date_1904: bool) {
+        let old_value = self.model.is_date_1904();
+        self.model.set_date_1904(date_1904);
+        self.push_diff_list(vec![Diff::SetDate1904 {
+            new_value: date_1904,
+            old_value,
+        }]);
+        self.evaluate_if_not_paused();
+    }
 
-        let mut data = HashMap::new();
-        let [row_start, column_start, row_end, column_end] = selected_area.range;
-        for row in row_start..=row_end {
-            let mut data_row = HashMap::new();
-            let mut text_row = Vec::new();
-            for column in column_start..=column_end {
-                let text = self.get_formatted_cell_value(sheet, row, column)?;
-                let content = self.get_cell_content(sheet, row, column)?;
-                let style = self.get_cell_style(sheet, row, column)?;
-                data_row.insert(
-                    column,
-                    ClipboardCell {
-                        text: content,
-                        style,
-                    },
-                );
-                text_row.push(text);
-            }
-            wtr.write_record(text_row).unwrap();
-            data.insert(row, data_row);
-        }
+    /// Returns true if the workbook uses the 1904 date system
+    pub fn is_date_1904(&self) -> bool {
+        self.model.is_date_1904()
+    }
 
-        let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+    /// Rounds the results of formulas to the precision they are displayed with (`true`)
+    /// or keeps the full precision (`false`)
+    ///
+    /// See also:
+    /// * [Model::set_precision_as_displayed]
+    pub fn set_precision_as_displayed(&mut self, precision_as_displayed: bool) {
+        let old_value = self.model.is_precision_as_displayed();
+        self.model
+            .set_precision_as_displayed(precision_as_displayed);
+        self.push_diff_list(vec![Diff::SetPrecisionAsDisplayed {
+            new_value: precision_as_displayed,
+            old_value,
+        }]);
+        self.evaluate_if_not_paused();
+    }
 
-        Ok(Clipboard {
-            csv,
-            data,
-            range: (row_start, column_start, row_end, column_end),
-        })
+    /// Returns true if the results of formulas are rounded to their displayed precision
+    pub fn is_precision_as_displayed(&self) -> bool {
+        self.model.is_precision_as_displayed()
     }
 
-    /// Paste text that we copied
-    pub fn paste_from_clipboard(
+    /// Returns the conditional formats of the `sheet`, sorted by priority
+    pub fn get_conditional_formats(&self, sheet: u32) -> Result<Vec<ConditionalFormat>, String> {
+        self.model.get_conditional_formats(sheet)
+    }
+
+    /// Adds a conditional format to the `sheet` with the lowest priority
+    ///
+    /// See also:
+    /// * [Model::insert_conditional_format]
+    pub fn add_conditional_format(
         &mut self,
-        source_range: ClipboardTuple,
-        clipboard: &ClipboardData,
-        is_cut: bool,
+        sheet: u32,
+        conditional_format: ConditionalFormat,
     ) -> Result<(), String> {
-        let mut diff_list = Vec::new();
-        let view = self.get_selected_view();
-        let (source_first_row, source_first_column, source_last_row, source_last_column) =
-            source_range;
-        let sheet = view.sheet;
-        let [selected_row, selected_column, _, _] = view.range;
-        let mut max_row = selected_row;
-        let mut max_column = selected_column;
-        let area = &Area {
+        self.model
+            .check_sheet_not_protected(sheet, "change conditional formats")?;
+        let index = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .conditional_formats
+            .len();
+        self.model
+            .insert_conditional_format(sheet, index, conditional_format.clone())?;
+        self.push_diff_list(vec![Diff::AddConditionalFormat {
             sheet,
-            row: source_first_row,
-            column: source_first_column,
-            width: source_last_column - source_first_column + 1,
-            height: source_last_row - source_first_row + 1,
+            index,
+            value: Box::new(conditional_format),
+        }]);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Replaces the conditional format with priority `index` in the `sheet`
+    ///
+    /// See also:
+    /// * [Model::set_conditional_format]
+    pub fn set_conditional_format(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        conditional_format: ConditionalFormat,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(sheet, "change conditional formats")?;
+        let old_value = match self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .conditional_formats
+            .get(index)
+        {
+            Some(old_value) => old_value.clone(),
+            None => return Err("Invalid conditional format index".to_string()),
         };
-        for (source_row, data_row) in clipboard {
-            let delta_row = source_row - source_first_row;
-            let target_row = selected_row + delta_row;
-            max_row = max_row.max(target_row);
-            for (source_column, value) in data_row {
-                let delta_column = source_column - source_first_column;
-                let target_column = selected_column + delta_column;
-                max_column = max_column.max(target_column);
+        self.model
+            .set_conditional_format(sheet, index, conditional_format.clone())?;
+        self.push_diff_list(vec![Diff::SetConditionalFormat {
+            sheet,
+            index,
+            old_value: Box::new(old_value),
+            new_value: Box::new(conditional_format),
+        }]);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
 
-                // We are copying the value in
-                // (source_row, source_column) to (target_row , target_column)
-                // References in formulas are displaced
+    /// Deletes the conditional format with priority `index` in the `sheet`
+    ///
+    /// See also:
+    /// * [Model::delete_conditional_format]
+    pub fn delete_conditional_format(&mut self, sheet: u32, index: usize) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(sheet, "change conditional formats")?;
+        let old_value = match self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .conditional_formats
+            .get(index)
+        {
+            Some(old_value) => old_value.clone(),
+            None => return Err("Invalid conditional format index".to_string()),
+        };
+        self.model.delete_conditional_format(sheet, index)?;
+        self.push_diff_list(vec![Diff::DeleteConditionalFormat {
+            sheet,
+            index,
+            old_value: Box::new(old_value),
+        }]);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
 
-                // remain in the copied area
-                let source = &CellReferenceIndex {
-                    sheet,
-                    column: *source_column,
-                    row: *source_row,
-                };
-                let target = &CellReferenceIndex {
-                    sheet,
-                    column: target_column,
-                    row: target_row,
-                };
-                let new_value = if is_cut {
-                    self.model
-                        .move_cell_value_to_area(&value.text, source, target, area)?
-                } else {
-                    self.model
-                        .extend_copied_value(&value.text, source, target)?
-                };
+    /// Returns how the conditional formats change the display of the cell, if they do
+    ///
+    /// See also:
+    /// * [Model::get_conditional_format_result]
+    pub fn get_conditional_format_result(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Option<ConditionalFormatResult> {
+        self.model.get_conditional_format_result(sheet, row, column)
+    }
 
-                let old_value = self
-                    .model
-                    .workbook
-                    .worksheet(sheet)?
-                    .cell(target_row, target_column)
-                    .cloned();
+    /// Returns the style of the cell with the conditional formats applied
+    ///
+    /// See also:
+    /// * [Model::get_effective_style_for_cell]
+    pub fn get_effective_cell_style(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Style, String> {
+        self.model.get_effective_style_for_cell(sheet, row, column)
+    }
 
-                let old_style = self
-                    .model
-                    .get_style_for_cell(sheet, target_row, target_column)?;
+    /// Returns the data validations of the `sheet`
+    pub fn get_data_validations(&self, sheet: u32) -> Result<Vec<DataValidation>, String> {
+        self.model.get_data_validations(sheet)
+    }
 
-                self.model
-                    .set_user_input(sheet, target_row, target_column, new_value.clone())?;
-                self.model
-                    .set_cell_style(sheet, target_row, target_column, &value.style)?;
+    /// Returns the data validation that applies to the cell, if any.
+    /// The UI can use it to show the input message.
+    pub fn get_data_validation_for_cell(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<DataValidation>, String> {
+        self.model.get_data_validation_for_cell(sheet, row, column)
+    }
 
-                diff_list.push(Diff::SetCellValue {
-                    sheet,
-                    row: target_row,
-                    column: target_column,
-                    new_value,
-                    old_value: Box::new(old_value),
-                });
+    /// Returns the data validation the value of the cell does not satisfy, if any
+    ///
+    /// See also:
+    /// * [Model::check_data_validation]
+    pub fn check_data_validation(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<DataValidation>, String> {
+        self.model.check_data_validation(sheet, row, column)
+    }
 
-                diff_list.push(Diff::SetCellStyle {
-                    sheet,
-                    row: target_row,
-                    column: target_column,
-                    old_value: Box::new(old_style),
-                    new_value: Box::new(value.style.clone()),
-                });
-            }
-        }
-        if is_cut {
-            for row in source_first_row..=source_last_row {
-                for column in source_first_column..=source_last_column {
-                    let old_value = self
-                        .model
-                        .workbook
-                        .worksheet(sheet)?
-                        .cell(row, column)
-                        .cloned();
-                    diff_list.push(Diff::CellClearContents {
-                        sheet,
-                        row,
+    /// Returns the options of the dropdown of the cell
+    ///
+    /// See also:
+    /// * [Model::get_data_validation_options]
+    pub fn get_data_validation_options(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Vec<String>, String> {
+        self.model.get_data_validation_options(sheet, row, column)
+    }
+
+    /// Adds a data validation to the `sheet`
+    pub fn add_data_validation(
+        &mut self,
+        sheet: u32,
+        data_validation: DataValidation,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(sheet, "change data validations")?;
+        let index = self.model.workbook.worksheet(sheet)?.data_validations.len();
+        self.model
+            .insert_data_validation(sheet, index, data_validation.clone())?;
+        self.push_diff_list(vec![Diff::AddDataValidation {
+            sheet,
+            index,
+            value: Box::new(data_validation),
+        }]);
+        Ok(())
+    }
+
+    /// Replaces the data validation at `index` in the `sheet`
+    pub fn set_data_validation(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        data_validation: DataValidation,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(sheet, "change data validations")?;
+        let old_value = match self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .data_validations
+            .get(index)
+        {
+            Some(old_value) => old_value.clone(),
+            None => return Err("Invalid data validation index".to_string()),
+        };
+        self.model
+            .set_data_validation(sheet, index, data_validation.clone())?;
+        self.push_diff_list(vec![Diff::SetDataValidation {
+            sheet,
+            index,
+            old_value: Box::new(old_value),
+            new_value: Box::new(data_validation),
+        }]);
+        Ok(())
+    }
+
+    /// Deletes the data validation at `index` in the `sheet`
+    pub fn delete_data_validation(&mut self, sheet: u32, index: usize) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(sheet, "change data validations")?;
+        let old_value = match self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .data_validations
+            .get(index)
+        {
+            Some(old_value) => old_value.clone(),
+            None => return Err("Invalid data validation index".to_string()),
+        };
+        self.model.delete_data_validation(sheet, index)?;
+        self.push_diff_list(vec![Diff::DeleteDataValidation {
+            sheet,
+            index,
+            old_value: Box::new(old_value),
+        }]);
+        Ok(())
+    }
+
+    /// Returns the merged areas of the `sheet` as a list of ranges like "B2:C4"
+    pub fn get_merged_cells(&self, sheet: u32) -> Result<Vec<String>, String> {
+        self.model.get_merged_cells(sheet)
+    }
+
+    /// Merges the cells in `range`. Merged areas inside the range are absorbed.
+    /// Like in Excel only the value of the upper-left cell is kept.
+    ///
+    /// See also:
+    /// * [Model::merge_cells]
+    pub fn merge_cells(&mut self, range: &Area) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(range.sheet, "merge cells")?;
+        let sheet = range.sheet;
+        let last_row = range.row + range.height - 1;
+        let last_column = range.column + range.width - 1;
+        if !is_valid_row(range.row) || !is_valid_row(last_row) {
+            return Err(format!("Invalid row: '{last_row}'"));
+        }
+        if !is_valid_column_number(range.column) || !is_valid_column_number(last_column) {
+            return Err(format!("Invalid column: '{last_column}'"));
+        }
+        let bounds = (range.row, range.column, last_row, last_column);
+        let merged_range = range_to_string(bounds)?;
+        if self.model.expand_to_merged_cells(sheet, bounds)? != bounds {
+            return Err("The range partially overlaps a merged area".to_string());
+        }
+        let mut diff_list = Vec::new();
+        for merged in self.model.get_merged_cells_in_range(sheet, bounds)? {
+            self.model.unmerge_cells(sheet, &merged)?;
+            diff_list.push(Diff::UnmergeCells {
+                sheet,
+                range: merged,
+            });
+        }
+        let hidden_cells = self.model.get_cells_hidden_by_merge(sheet, &merged_range)?;
+        for &(row, column) in &hidden_cells {
+            let old_value = self
+                .model
+                .workbook
+                .worksheet(sheet)?
+                .cell(row, column)
+                .cloned();
+            diff_list.push(Diff::CellClearContents {
+                sheet,
+                row,
+                column,
+                old_value: Box::new(old_value),
+            });
+        }
+        self.model.merge_cells(sheet, &merged_range)?;
+        diff_list.push(Diff::MergeCells {
+            sheet,
+            range: merged_range,
+        });
+        self.push_diff_list(diff_list);
+        if !hidden_cells.is_empty() {
+            self.evaluate_if_not_paused();
+        }
+        Ok(())
+    }
+
+    /// Unmerges all the merged areas that intersect `range`
+    ///
+    /// See also:
+    /// * [Model::unmerge_cells]
+    pub fn unmerge_cells(&mut self, range: &Area) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(range.sheet, "unmerge cells")?;
+        let sheet = range.sheet;
+        let bounds = (
+            range.row,
+            range.column,
+            range.row + range.height - 1,
+            range.column + range.width - 1,
+        );
+        let mut diff_list = Vec::new();
+        for merged in self.model.get_merged_cells_in_range(sheet, bounds)? {
+            self.model.unmerge_cells(sheet, &merged)?;
+            diff_list.push(Diff::UnmergeCells {
+                sheet,
+                range: merged,
+            });
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(())
+    }
+
+    /// Returns all the comments in the `sheet`
+    pub fn get_comments(&self, sheet: u32) -> Result<Vec<Comment>, String> {
+        self.model.get_comments(sheet)
+    }
+
+    /// Returns the comment in the cell, if any
+    pub fn get_comment(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Comment>, String> {
+        self.model.get_comment(sheet, row, column)
+    }
+
+    /// Adds a comment to the cell
+    ///
+    /// See also:
+    /// * [Model::add_comment]
+    pub fn add_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_edit_objects, "edit comments")?;
+        self.model
+            .add_comment(sheet, row, column, text, author_name, author_id)?;
+        if let Some(comment) = self.model.get_comment(sheet, row, column)? {
+            self.push_diff_list(vec![Diff::AddComment {
+                sheet,
+                row,
+                column,
+                value: Box::new(comment),
+            }]);
+        }
+        Ok(())
+    }
+
+    /// Changes the text of the comment in the cell
+    ///
+    /// See also:
+    /// * [Model::edit_comment]
+    pub fn edit_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_edit_objects, "edit comments")?;
+        let old_value = self.model.get_comment(sheet, row, column)?;
+        self.model.edit_comment(sheet, row, column, text)?;
+        self.push_set_comment_diff(sheet, row, column, old_value)
+    }
+
+    /// Marks the comment in the cell as resolved (or not)
+    ///
+    /// See also:
+    /// * [Model::resolve_comment]
+    pub fn resolve_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        resolved: bool,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_edit_objects, "edit comments")?;
+        let old_value = self.model.get_comment(sheet, row, column)?;
+        self.model.resolve_comment(sheet, row, column, resolved)?;
+        self.push_set_comment_diff(sheet, row, column, old_value)
+    }
+
+    /// Adds a reply to the comment in the cell
+    ///
+    /// See also:
+    /// * [Model::add_comment_reply]
+    pub fn add_comment_reply(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_edit_objects, "edit comments")?;
+        let old_value = self.model.get_comment(sheet, row, column)?;
+        self.model
+            .add_comment_reply(sheet, row, column, text, author_name, author_id)?;
+        self.push_set_comment_diff(sheet, row, column, old_value)
+    }
+
+    /// Deletes the comment in the cell with all its replies
+    ///
+    /// See also:
+    /// * [Model::delete_comment]
+    pub fn delete_comment(&mut self, sheet: u32, row: i32, column: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_edit_objects, "edit comments")?;
+        let old_value = self.model.get_comment(sheet, row, column)?;
+        self.model.delete_comment(sheet, row, column)?;
+        if let Some(old_value) = old_value {
+            self.push_diff_list(vec![Diff::DeleteComment {
+                sheet,
+                row,
+                column,
+                old_value: Box::new(old_value),
+            }]);
+        }
+        Ok(())
+    }
+
+    /// Returns all the hyperlinks attached to cells in the `sheet`
+    pub fn get_hyperlinks(&self, sheet: u32) -> Result<Vec<Hyperlink>, String> {
+        self.model.get_hyperlinks(sheet)
+    }
+
+    /// Returns the hyperlink attached to the cell, if any
+    pub fn get_hyperlink(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Hyperlink>, String> {
+        self.model.get_hyperlink(sheet, row, column)
+    }
+
+    /// Returns the link that is followed when the cell is clicked, if any
+    ///
+    /// See also:
+    /// * [Model::get_cell_hyperlink]
+    pub fn get_cell_hyperlink(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<Hyperlink>, String> {
+        self.model.get_cell_hyperlink(sheet, row, column)
+    }
+
+    /// Attaches a hyperlink to the cell, replacing the existing one if any
+    ///
+    /// See also:
+    /// * [Model::set_hyperlink]
+    pub fn set_hyperlink(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        url: Option<String>,
+        location: Option<String>,
+        tooltip: Option<String>,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_insert_hyperlinks, "insert hyperlinks")?;
+        let old_value = self.model.get_hyperlink(sheet, row, column)?;
+        self.model
+            .set_hyperlink(sheet, row, column, url, location, tooltip)?;
+        let new_value = self.model.get_hyperlink(sheet, row, column)?;
+        self.push_diff_list(vec![Diff::SetHyperlink {
+            sheet,
+            row,
+            column,
+            old_value: Box::new(old_value),
+            new_value: Box::new(new_value),
+        }]);
+        Ok(())
+    }
+
+    /// Removes the hyperlink attached to the cell
+    ///
+    /// See also:
+    /// * [Model::delete_hyperlink]
+    pub fn delete_hyperlink(&mut self, sheet: u32, row: i32, column: i32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_insert_hyperlinks, "insert hyperlinks")?;
+        let old_value = self.model.get_hyperlink(sheet, row, column)?;
+        self.model.delete_hyperlink(sheet, row, column)?;
+        self.push_diff_list(vec![Diff::SetHyperlink {
+            sheet,
+            row,
+            column,
+            old_value: Box::new(old_value),
+            new_value: Box::new(None),
+        }]);
+        Ok(())
+    }
+
+    /// Returns the autofilter of the sheet, if any
+    ///
+    /// See also:
+    /// * [Model::get_auto_filter]
+    #[inline]
+    pub fn get_auto_filter(&self, sheet: u32) -> Result<Option<AutoFilter>, String> {
+        self.model.get_auto_filter(sheet)
+    }
+
+    /// Adds an autofilter without criteria to the `range`. The first row of the range is the header.
+    pub fn add_auto_filter(&mut self, range: &Area) -> Result<(), String> {
+        let sheet = range.sheet;
+        self.model
+            .check_sheet_not_protected(sheet, "add an autofilter")?;
+        if self.model.get_auto_filter(sheet)?.is_some() {
+            return Err("The sheet already has an autofilter".to_string());
+        }
+        let auto_filter = AutoFilter {
+            reference: range_to_string((
+                range.row,
+                range.column,
+                range.row + range.height - 1,
+                range.column + range.width - 1,
+            ))?,
+            columns: vec![],
+        };
+        self.model
+            .set_auto_filter(sheet, Some(auto_filter.clone()))?;
+        self.push_diff_list(vec![Diff::SetAutoFilter {
+            sheet,
+            old_value: Box::new(None),
+            new_value: Box::new(Some(auto_filter)),
+        }]);
+        Ok(())
+    }
+
+    /// Removes the autofilter of the sheet and shows the rows it hid
+    pub fn remove_auto_filter(&mut self, sheet: u32) -> Result<(), String> {
+        self.model
+            .check_sheet_not_protected(sheet, "remove the autofilter")?;
+        let old_value = self.get_auto_filter_or_error(sheet)?;
+        let mut diff_list = Vec::new();
+        if !old_value.columns.is_empty() {
+            for (row, _) in self.model.get_auto_filter_rows(sheet)? {
+                self.set_row_hidden_with_diff(&mut diff_list, sheet, row, false)?;
+            }
+        }
+        self.model.set_auto_filter(sheet, None)?;
+        diff_list.push(Diff::SetAutoFilter {
+            sheet,
+            old_value: Box::new(Some(old_value)),
+            new_value: Box::new(None),
+        });
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Sets the filter of the column `column_id` of the autofilter (0 is its first column), or
+    /// removes it if `filter` is None, and hides the rows that do not match the criteria
+    pub fn set_auto_filter_column(
+        &mut self,
+        sheet: u32,
+        column_id: u32,
+        filter: Option<ColumnFilter>,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_auto_filter, "filter")?;
+        let old_value = self.get_auto_filter_or_error(sheet)?;
+        let mut new_value = old_value.clone();
+        set_filter_column(&mut new_value.columns, column_id, filter);
+        self.model.set_auto_filter(sheet, Some(new_value.clone()))?;
+        let mut diff_list = vec![Diff::SetAutoFilter {
+            sheet,
+            old_value: Box::new(Some(old_value)),
+            new_value: Box::new(Some(new_value)),
+        }];
+        let rows = self.model.get_auto_filter_rows(sheet)?;
+        self.set_filtered_rows_with_diff(&mut diff_list, sheet, rows)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Applies the criteria of the autofilter again to the current values of the cells
+    pub fn reapply_auto_filter(&mut self, sheet: u32) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_auto_filter, "filter")?;
+        let mut diff_list = Vec::new();
+        let rows = self.model.get_auto_filter_rows(sheet)?;
+        self.set_filtered_rows_with_diff(&mut diff_list, sheet, rows)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the filters of the columns of the `table`
+    ///
+    /// See also:
+    /// * [Model::get_table_filter]
+    #[inline]
+    pub fn get_table_filter(&self, table: &str) -> Result<Vec<FilterColumn>, String> {
+        self.model.get_table_filter(table)
+    }
+
+    /// Sets the filter of the column `column_id` of the `table` (0 is its first column), or
+    /// removes it if `filter` is None, and hides the data rows that do not match the criteria
+    pub fn set_table_filter_column(
+        &mut self,
+        table: &str,
+        column_id: u32,
+        filter: Option<ColumnFilter>,
+    ) -> Result<(), String> {
+        let sheet = self.model.get_table_sheet(table)?;
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_auto_filter, "filter")?;
+        let old_value = self.model.get_table_filter(table)?;
+        let mut new_value = old_value.clone();
+        set_filter_column(&mut new_value, column_id, filter);
+        self.model.set_table_filter(table, new_value.clone())?;
+        let mut diff_list = vec![Diff::SetTableFilter {
+            table: table.to_string(),
+            old_value,
+            new_value,
+        }];
+        let (sheet, rows) = self.model.get_table_filter_rows(table)?;
+        self.set_filtered_rows_with_diff(&mut diff_list, sheet, rows)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Applies the filters of the `table` again to the current values of the cells
+    pub fn reapply_table_filter(&mut self, table: &str) -> Result<(), String> {
+        let (sheet, rows) = self.model.get_table_filter_rows(table)?;
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_auto_filter, "filter")?;
+        let mut diff_list = Vec::new();
+        self.set_filtered_rows_with_diff(&mut diff_list, sheet, rows)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the table called `name`
+    ///
+    /// See also:
+    /// * [Model::get_table]
+    #[inline]
+    pub fn get_table(&self, name: &str) -> Result<Table, String> {
+        self.model.get_table(name)
+    }
+
+    /// Returns the tables of the sheet sorted by name
+    ///
+    /// See also:
+    /// * [Model::get_tables]
+    #[inline]
+    pub fn get_tables(&self, sheet: u32) -> Result<Vec<Table>, String> {
+        self.model.get_tables(sheet)
+    }
+
+    /// Creates a table in the `range` and returns its name.
+    /// If `has_header_row` is true the first row has the names of the columns, empty or repeated
+    /// names are replaced by unique ones. Otherwise the columns are named "Column1", "Column2", ...
+    pub fn add_table(&mut self, range: &Area, has_header_row: bool) -> Result<String, String> {
+        let sheet = range.sheet;
+        self.model.check_sheet_not_protected(sheet, "add a table")?;
+        let row1 = range.row;
+        let column1 = range.column;
+        let column2 = range.column + range.width - 1;
+        // A table has at least one data row
+        let row2 = if has_header_row {
+            (range.row + range.height - 1).max(row1 + 1)
+        } else {
+            range.row + range.height - 1
+        };
+        self.model
+            .check_table_range(sheet, (row1, column1, row2, column2), None)?;
+        let header_row = if has_header_row { Some(row1) } else { None };
+        let mut names = Vec::new();
+        let mut columns = Vec::new();
+        for (index, column) in (column1..=column2).enumerate() {
+            let name = self
+                .model
+                .get_table_column_name(sheet, header_row, column, index, &names)?;
+            names.push(name.clone());
+            columns.push(TableColumn {
+                id: index as u32 + 1,
+                name,
+                ..Default::default()
+            });
+        }
+        let name = self.model.get_new_table_name();
+        let table = Table {
+            name: name.clone(),
+            display_name: name.clone(),
+            sheet_name: self.model.workbook.worksheet(sheet)?.get_name(),
+            reference: range_to_string((row1, column1, row2, column2))?,
+            totals_row_count: 0,
+            header_row_count: u32::from(has_header_row),
+            header_row_dxf_id: None,
+            data_dxf_id: None,
+            totals_row_dxf_id: None,
+            columns,
+            style_info: TableStyleInfo {
+                name: Some("TableStyleMedium2".to_string()),
+                show_row_stripes: true,
+                ..Default::default()
+            },
+            has_filters: false,
+            filter_columns: vec![],
+        };
+        let mut diff_list = Vec::new();
+        self.set_table_headers_with_diff(&mut diff_list, &table)?;
+        self.set_table_with_diff(&mut diff_list, &name, Some(table))?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(name)
+    }
+
+    /// Deletes the table called `name`. The cells keep their values and styles.
+    pub fn delete_table(&mut self, name: &str) -> Result<(), String> {
+        let table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        self.model
+            .check_sheet_not_protected(sheet, "delete a table")?;
+        let mut diff_list = Vec::new();
+        if table.has_filters {
+            let (_, rows) = self.model.get_table_filter_rows(name)?;
+            let rows = rows.into_iter().map(|(row, _)| (row, true)).collect();
+            self.set_filtered_rows_with_diff(&mut diff_list, sheet, rows)?;
+        }
+        self.set_table_with_diff(&mut diff_list, name, None)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Renames the table `name` to `new_name`, which must be a valid name not used by other
+    /// tables or defined names
+    pub fn rename_table(&mut self, name: &str, new_name: &str) -> Result<(), String> {
+        let table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        self.model
+            .check_sheet_not_protected(sheet, "rename a table")?;
+        if name == new_name {
+            return Ok(());
+        }
+        self.model.check_table_name(new_name, Some(name))?;
+        let new_table = Table {
+            name: new_name.to_string(),
+            display_name: new_name.to_string(),
+            ..table
+        };
+        let mut diff_list = Vec::new();
+        self.set_table_with_diff(&mut diff_list, name, None)?;
+        self.set_table_with_diff(&mut diff_list, new_name, Some(new_table))?;
+        self.rename_table_references_with_diff(&mut diff_list, name, new_name, None);
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Changes the range of the table `name`. The header row cannot move and the new range must
+    /// overlap the old one. Columns in both ranges keep their names.
+    pub fn resize_table(&mut self, name: &str, range: &Area) -> Result<(), String> {
+        let table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        self.model
+            .check_sheet_not_protected(sheet, "resize a table")?;
+        if range.sheet != sheet {
+            return Err("A table cannot move to another sheet".to_string());
+        }
+        let old_range = parse_range_reference(&table.reference)?;
+        let new_range = (
+            range.row,
+            range.column,
+            range.row + range.height - 1,
+            range.column + range.width - 1,
+        );
+        if table.header_row_count > 0 && new_range.0 != old_range.0 {
+            return Err("The header row of a table cannot move".to_string());
+        }
+        if !intersects(old_range, new_range) {
+            return Err("The new range must overlap the table".to_string());
+        }
+        if range.height <= (table.header_row_count + table.totals_row_count) as i32 {
+            return Err("A table needs at least one row of data".to_string());
+        }
+        self.model.check_table_range(sheet, new_range, Some(name))?;
+        let new_table = self.model.get_resized_table(&table, new_range)?;
+        let mut diff_list = Vec::new();
+        self.set_table_headers_with_diff(&mut diff_list, &new_table)?;
+        self.set_table_with_diff(&mut diff_list, name, Some(new_table.clone()))?;
+        self.set_table_totals_with_diff(&mut diff_list, &new_table)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Adds a column to the right of the table `name`
+    pub fn add_table_column(&mut self, name: &str) -> Result<(), String> {
+        let table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+        self.resize_table(
+            name,
+            &Area {
+                sheet,
+                row: row1,
+                column: column1,
+                width: column2 - column1 + 2,
+                height: row2 - row1 + 1,
+            },
+        )
+    }
+
+    /// Renames the column `column` of the table `name`, and its header cell if it has one
+    pub fn rename_table_column(
+        &mut self,
+        name: &str,
+        column: &str,
+        new_name: &str,
+    ) -> Result<(), String> {
+        let mut table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        self.model
+            .check_sheet_not_protected(sheet, "rename a table column")?;
+        let index = table
+            .columns
+            .iter()
+            .position(|table_column| table_column.name == column)
+            .ok_or_else(|| format!("Column not found: '{column}'"))?;
+        if new_name.trim().is_empty() {
+            return Err("The name of a column cannot be empty".to_string());
+        }
+        let lower = new_name.to_lowercase();
+        if table
+            .columns
+            .iter()
+            .enumerate()
+            .any(|(i, table_column)| i != index && table_column.name.to_lowercase() == lower)
+        {
+            return Err(format!("Column name already in use: '{new_name}'"));
+        }
+        let old_name = std::mem::replace(&mut table.columns[index].name, new_name.to_string());
+        let mut diff_list = Vec::new();
+        self.set_table_headers_with_diff(&mut diff_list, &table)?;
+        self.set_table_with_diff(&mut diff_list, name, Some(table))?;
+        self.rename_table_references_with_diff(
+            &mut diff_list,
+            name,
+            name,
+            Some((&old_name, new_name)),
+        );
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Shows or hides the header row of the table `name`.
+    /// The header row is the row above the data, its cells must be empty to show it.
+    /// When hidden the cells of the header row are cleared and no longer part of the table.
+    pub fn set_table_header_row(&mut self, name: &str, show: bool) -> Result<(), String> {
+        let table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        self.model
+            .check_sheet_not_protected(sheet, "change a table")?;
+        if show == (table.header_row_count > 0) {
+            return Ok(());
+        }
+        let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+        let mut diff_list = Vec::new();
+        let new_table = if show {
+            if row1 == 1 {
+                return Err("There is no row above the table for the header row".to_string());
+            }
+            self.check_cells_empty(sheet, row1 - 1, column1, column2)?;
+            self.model
+                .check_table_range(sheet, (row1 - 1, column1, row2, column2), Some(name))?;
+            Table {
+                reference: range_to_string((row1 - 1, column1, row2, column2))?,
+                header_row_count: 1,
+                ..table
+            }
+        } else {
+            self.clear_row_contents_with_diff(&mut diff_list, sheet, row1, column1, column2)?;
+            Table {
+                reference: range_to_string((row1 + 1, column1, row2, column2))?,
+                header_row_count: 0,
+                ..table
+            }
+        };
+        self.set_table_headers_with_diff(&mut diff_list, &new_table)?;
+        self.set_table_with_diff(&mut diff_list, name, Some(new_table))?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Shows or hides the totals row of the table `name`.
+    /// The totals row is the row below the data, its cells must be empty to show it.
+    /// When hidden the cells of the totals row are cleared and no longer part of the table.
+    pub fn set_table_totals_row(&mut self, name: &str, show: bool) -> Result<(), String> {
+        let table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        self.model
+            .check_sheet_not_protected(sheet, "change a table")?;
+        if show == (table.totals_row_count > 0) {
+            return Ok(());
+        }
+        let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+        let mut diff_list = Vec::new();
+        let mut columns = table.columns.clone();
+        for table_column in &mut columns {
+            table_column.totals_row_label = None;
+            table_column.totals_row_function = None;
+            table_column.totals_row_formula = None;
+        }
+        let new_table = if show {
+            if row2 == LAST_ROW {
+                return Err("There is no row below the table for the totals row".to_string());
+            }
+            self.check_cells_empty(sheet, row2 + 1, column1, column2)?;
+            self.model
+                .check_table_range(sheet, (row1, column1, row2 + 1, column2), Some(name))?;
+            // Like Excel, a label in the first column and the sum of the last one
+            if let Some(first_column) = columns.first_mut() {
+                first_column.totals_row_label = Some("Total".to_string());
+            }
+            if columns.len() > 1 {
+                if let Some(last_column) = columns.last_mut() {
+                    last_column.totals_row_function = Some(TotalsRowFunction::Sum);
+                }
+            }
+            Table {
+                reference: range_to_string((row1, column1, row2 + 1, column2))?,
+                totals_row_count: 1,
+                columns,
+                ..table
+            }
+        } else {
+            self.clear_row_contents_with_diff(&mut diff_list, sheet, row2, column1, column2)?;
+            Table {
+                reference: range_to_string((row1, column1, row2 - 1, column2))?,
+                totals_row_count: 0,
+                columns,
+                ..table
+            }
+        };
+        self.set_table_with_diff(&mut diff_list, name, Some(new_table.clone()))?;
+        self.set_table_totals_with_diff(&mut diff_list, &new_table)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Sets the function of the `column` of the table `name` in the totals row.
+    /// Custom functions are set typing a formula in the totals row.
+    pub fn set_table_totals_row_function(
+        &mut self,
+        name: &str,
+        column: &str,
+        function: Option<TotalsRowFunction>,
+    ) -> Result<(), String> {
+        let mut table = self.model.get_table(name)?;
+        let sheet = self.model.get_table_sheet(name)?;
+        self.model
+            .check_sheet_not_protected(sheet, "change a table")?;
+        if table.totals_row_count == 0 {
+            return Err(format!("Table '{name}' has no totals row"));
+        }
+        if function == Some(TotalsRowFunction::Custom) {
+            return Err("Type a formula in the totals row to use a custom function".to_string());
+        }
+        let index = table
+            .columns
+            .iter()
+            .position(|table_column| table_column.name == column)
+            .ok_or_else(|| format!("Column not found: '{column}'"))?;
+        let table_column = &mut table.columns[index];
+        table_column.totals_row_label = None;
+        table_column.totals_row_formula = None;
+        table_column.totals_row_function = function;
+        let mut diff_list = Vec::new();
+        if table_column.totals_row_function.is_none() {
+            let (_, column1, row2, _) = parse_range_reference(&table.reference)?;
+            let column = column1 + index as i32;
+            self.clear_row_contents_with_diff(&mut diff_list, sheet, row2, column, column)?;
+        }
+        self.set_table_with_diff(&mut diff_list, name, Some(table.clone()))?;
+        self.set_table_totals_with_diff(&mut diff_list, &table)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the pivot tables of the `sheet`
+    ///
+    /// See also:
+    /// * [Model::get_pivot_tables]
+    #[inline]
+    pub fn get_pivot_tables(&self, sheet: u32) -> Result<Vec<PivotTable>, String> {
+        self.model.get_pivot_tables(sheet)
+    }
+
+    /// Returns the pivot table called `name` in the `sheet`
+    ///
+    /// See also:
+    /// * [Model::get_pivot_table]
+    #[inline]
+    pub fn get_pivot_table(&self, sheet: u32, name: &str) -> Result<PivotTable, String> {
+        self.model.get_pivot_table(sheet, name)
+    }
+
+    /// Adds an empty pivot table of the data in `source` with its top left cell at (`row`,
+    /// `column`) of the `sheet` and returns its name.
+    /// Fields are added with [UserModel::update_pivot_table].
+    pub fn add_pivot_table(
+        &mut self,
+        source: &PivotSource,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<String, String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_pivot_tables, "add a pivot table")?;
+        self.model.get_pivot_data(source)?;
+        let location = range_to_string((row, column, row, column))?;
+        let name = self.model.get_new_pivot_table_name(sheet)?;
+        let pivot_table = PivotTable {
+            name: name.clone(),
+            source: source.clone(),
+            location: location.split(':').next().unwrap_or_default().to_string(),
+            reference: None,
+            row_fields: vec![],
+            column_fields: vec![],
+            value_fields: vec![],
+            filters: vec![],
+            row_grand_totals: true,
+            column_grand_totals: true,
+            subtotals: true,
+            xlsx_parts: None,
+        };
+        let mut diff_list = Vec::new();
+        self.set_pivot_table_with_diff(&mut diff_list, sheet, &name, Some(pivot_table))?;
+        self.push_diff_list(diff_list);
+        Ok(name)
+    }
+
+    /// Replaces the fields, filters and options of the pivot table `name` and renders it again.
+    /// The pivot table is renamed if the name of `pivot_table` is different.
+    pub fn update_pivot_table(
+        &mut self,
+        sheet: u32,
+        name: &str,
+        pivot_table: PivotTable,
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_pivot_tables, "change a pivot table")?;
+        let old_value = self.model.get_pivot_table(sheet, name)?;
+        if !pivot_table.name.eq_ignore_ascii_case(name)
+            && self.model.get_pivot_table(sheet, &pivot_table.name).is_ok()
+        {
+            return Err(format!(
+                "A pivot table called '{}' already exists",
+                pivot_table.name
+            ));
+        }
+        if pivot_table.name.trim().is_empty() {
+            return Err("The name of a pivot table cannot be empty".to_string());
+        }
+        self.model.check_pivot_table(&pivot_table)?;
+        let mut value_fields = pivot_table.value_fields.clone();
+        for value_field in &mut value_fields {
+            if value_field.name.trim().is_empty() {
+                value_field.name = get_value_field_name(value_field.function, &value_field.field);
+            }
+        }
+        let pivot_table = PivotTable {
+            value_fields,
+            reference: old_value.reference.clone(),
+            // The parts read from an xlsx file no longer describe the pivot table
+            xlsx_parts: None,
+            ..pivot_table
+        };
+        let mut diff_list = Vec::new();
+        self.render_pivot_table_with_diff(&mut diff_list, sheet, name, pivot_table)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Renders the pivot table `name` again with the current data of its source
+    pub fn refresh_pivot_table(&mut self, sheet: u32, name: &str) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_pivot_tables, "refresh a pivot table")?;
+        let pivot_table = self.model.get_pivot_table(sheet, name)?;
+        let mut diff_list = Vec::new();
+        self.render_pivot_table_with_diff(&mut diff_list, sheet, name, pivot_table)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Deletes the pivot table `name` and clears its cells
+    pub fn delete_pivot_table(&mut self, sheet: u32, name: &str) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_pivot_tables, "delete a pivot table")?;
+        let pivot_table = self.model.get_pivot_table(sheet, name)?;
+        let mut diff_list = Vec::new();
+        if let Some(reference) = &pivot_table.reference {
+            let (row1, column1, row2, column2) = parse_range_reference(reference)?;
+            for row in row1..=row2 {
+                self.clear_row_contents_with_diff(&mut diff_list, sheet, row, column1, column2)?;
+            }
+        }
+        self.set_pivot_table_with_diff(&mut diff_list, sheet, name, None)?;
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Sorts the rows of the `range` by the `keys`, the first key is the most significant.
+    /// If `has_header` is true the first row of the range is not sorted.
+    /// Styles move with the cells and references of formulas to their own row are displaced.
+    pub fn sort_range(
+        &mut self,
+        range: &Area,
+        keys: &[SortKey],
+        has_header: bool,
+    ) -> Result<(), String> {
+        let first_row = if has_header { range.row + 1 } else { range.row };
+        self.sort_rows(
+            range.sheet,
+            (
+                first_row,
+                range.column,
+                range.row + range.height - 1,
+                range.column + range.width - 1,
+            ),
+            keys,
+        )
+    }
+
+    /// Sorts the data rows of the `table` by the `keys`. The header and totals rows stay in place.
+    pub fn sort_table(&mut self, table: &str, keys: &[SortKey]) -> Result<(), String> {
+        let area = self.model.get_table_data_area(table)?;
+        self.sort_range(&area, keys, false)
+    }
+
+    /// Removes the rows of the `range` that repeat the values in the `key_columns` of a row above.
+    /// Text is compared case insensitive. The rows below move up inside the range.
+    /// Returns the number of rows removed.
+    pub fn remove_duplicates(
+        &mut self,
+        range: &Area,
+        key_columns: &[i32],
+        has_header: bool,
+    ) -> Result<usize, String> {
+        let sheet = range.sheet;
+        let row1 = if has_header { range.row + 1 } else { range.row };
+        let column1 = range.column;
+        let column2 = range.column + range.width - 1;
+        if key_columns.is_empty() {
+            return Err("At least one column is needed to find duplicates".to_string());
+        }
+        if let Some(column) = key_columns
+            .iter()
+            .find(|column| !(column1..=column2).contains(column))
+        {
+            return Err(format!("Column {column} is not in the range"));
+        }
+        // There are no duplicates beyond the cells with data
+        let dimension = self.model.workbook.worksheet(sheet)?.dimension();
+        let row2 = (range.row + range.height - 1).min(dimension.max_row);
+        if row1 >= row2 {
+            return Ok(0);
+        }
+        self.model
+            .check_range_editable(sheet, (row1, column1, row2, column2))?;
+        if !self
+            .model
+            .get_merged_cells_in_range(sheet, (row1, column1, row2, column2))?
+            .is_empty()
+        {
+            return Err("Cannot remove duplicates in a range with merged cells".to_string());
+        }
+        let unique_rows = self.model.get_unique_rows(sheet, row1, row2, key_columns)?;
+        let removed = (row2 - row1 + 1) as usize - unique_rows.len();
+        if removed > 0 {
+            self.set_rows_order(sheet, (row1, column1, row2, column2), &unique_rows)?;
+        }
+        Ok(removed)
+    }
+
+    /// Splits the text of the cells in the single column `range` into the columns starting at it,
+    /// replacing their content. Cells with formulas are left as they are.
+    /// Each column is converted with its format in `column_formats`, General if missing.
+    pub fn text_to_columns(
+        &mut self,
+        range: &Area,
+        split: &TextSplit,
+        column_formats: &[TextColumnFormat],
+    ) -> Result<(), String> {
+        if range.width != 1 {
+            return Err("Text to columns works on a single column".to_string());
+        }
+        let sheet = range.sheet;
+        let column = range.column;
+        let dimension = self.model.workbook.worksheet(sheet)?.dimension();
+        let row1 = range.row;
+        let row2 = (range.row + range.height - 1).min(dimension.max_row);
+        let mut split_rows = Vec::new();
+        let mut width = 1;
+        for row in row1..=row2 {
+            let content = self.model.get_cell_content(sheet, row, column)?;
+            if content.is_empty()
+                || self
+                    .model
+                    .workbook
+                    .worksheet(sheet)?
+                    .cell(row, column)
+                    .is_some_and(|cell| cell.has_formula())
+            {
+                continue;
+            }
+            let inputs: Vec<Option<String>> = split_text(&content, split)
+                .iter()
+                .enumerate()
+                .filter_map(|(index, text)| {
+                    let format = column_formats.get(index).copied().unwrap_or_default();
+                    if format == TextColumnFormat::Skip {
+                        None
+                    } else {
+                        Some(self.model.get_text_column_input(text, format))
+                    }
+                })
+                .collect();
+            width = width.max(inputs.len() as i32);
+            split_rows.push((row, inputs));
+        }
+        if split_rows.is_empty() {
+            return Ok(());
+        }
+        self.model
+            .check_range_editable(sheet, (row1, column, row2, column + width - 1))?;
+        let mut diff_list = Vec::new();
+        for (row, inputs) in split_rows {
+            for index in 0..width {
+                let column = column + index;
+                let old_value = self
+                    .model
+                    .workbook
+                    .worksheet(sheet)?
+                    .cell(row, column)
+                    .cloned();
+                match inputs.get(index as usize).cloned().flatten() {
+                    Some(new_value) => {
+                        self.model
+                            .set_user_input(sheet, row, column, new_value.clone())?;
+                        diff_list.push(Diff::SetCellValue {
+                            sheet,
+                            row,
+                            column,
+                            new_value,
+                            old_value: Box::new(old_value),
+                        });
+                    }
+                    None => {
+                        if !self.model.get_cell_content(sheet, row, column)?.is_empty() {
+                            self.model.cell_clear_contents(sheet, row, column)?;
+                            diff_list.push(Diff::CellClearContents {
+                                sheet,
+                                row,
+                                column,
+                                old_value: Box::new(old_value),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the protection of the sheet, None if it is not protected
+    ///
+    /// See also:
+    /// * [Model::get_sheet_protection]
+    #[inline]
+    pub fn get_sheet_protection(&self, sheet: u32) -> Result<Option<SheetProtection>, String> {
+        self.model.get_sheet_protection(sheet)
+    }
+
+    /// Protects the sheet. Locked cells cannot be changed and only the actions allowed in
+    /// `protection` can be done until the sheet is unprotected.
+    /// As in Excel the history is cleared, edits done before cannot be undone.
+    ///
+    /// See also:
+    /// * [Model::protect_sheet]
+    pub fn protect_sheet(
+        &mut self,
+        sheet: u32,
+        protection: SheetProtection,
+        password: Option<&str>,
+    ) -> Result<(), String> {
+        self.model.protect_sheet(sheet, protection, password)?;
+        let new_value = self.model.get_sheet_protection(sheet)?;
+        self.push_diff_list(vec![Diff::SetSheetProtection {
+            sheet,
+            old_value: Box::new(None),
+            new_value: Box::new(new_value),
+        }]);
+        self.history.clear();
+        Ok(())
+    }
+
+    /// Removes the protection of the sheet, the `password` must match if it has one
+    ///
+    /// See also:
+    /// * [Model::unprotect_sheet]
+    pub fn unprotect_sheet(&mut self, sheet: u32, password: Option<&str>) -> Result<(), String> {
+        let old_value = self.model.get_sheet_protection(sheet)?;
+        self.model.unprotect_sheet(sheet, password)?;
+        self.push_diff_list(vec![Diff::SetSheetProtection {
+            sheet,
+            old_value: Box::new(old_value),
+            new_value: Box::new(None),
+        }]);
+        self.history.clear();
+        Ok(())
+    }
+
+    /// Returns true if the cell can be changed: the sheet is not protected or the cell is not locked
+    ///
+    /// See also:
+    /// * [Model::is_cell_editable]
+    #[inline]
+    pub fn is_cell_editable(&self, sheet: u32, row: i32, column: i32) -> Result<bool, String> {
+        self.model.is_cell_editable(sheet, row, column)
+    }
+
+    /// Returns the protection of the workbook, None if it is not protected
+    ///
+    /// See also:
+    /// * [Model::get_workbook_protection]
+    #[inline]
+    pub fn get_workbook_protection(&self) -> Option<WorkbookProtection> {
+        self.model.get_workbook_protection()
+    }
+
+    /// Protects the structure (the sheets can't be added, deleted or renamed) and/or the windows
+    /// of the workbook
+    ///
+    /// See also:
+    /// * [Model::protect_workbook]
+    pub fn protect_workbook(
+        &mut self,
+        lock_structure: bool,
+        lock_windows: bool,
+        password: Option<&str>,
+    ) -> Result<(), String> {
+        self.model
+            .protect_workbook(lock_structure, lock_windows, password)?;
+        self.push_diff_list(vec![Diff::SetWorkbookProtection {
+            old_value: Box::new(None),
+            new_value: Box::new(self.model.get_workbook_protection()),
+        }]);
+        self.history.clear();
+        Ok(())
+    }
+
+    /// Removes the protection of the workbook, the `password` must match if it has one
+    ///
+    /// See also:
+    /// * [Model::unprotect_workbook]
+    pub fn unprotect_workbook(&mut self, password: Option<&str>) -> Result<(), String> {
+        let old_value = self.model.get_workbook_protection();
+        self.model.unprotect_workbook(password)?;
+        self.push_diff_list(vec![Diff::SetWorkbookProtection {
+            old_value: Box::new(old_value),
+            new_value: Box::new(None),
+        }]);
+        self.history.clear();
+        Ok(())
+    }
+
+    /// Returns a copy of the selected area
+    pub fn copy_to_clipboard(&self) -> Result<Clipboard, String> {
+        let selected_area = self.get_selected_view();
+        let sheet = selected_area.sheet;
+        let mut wtr = WriterBuilder::new().from_writer(vec![]);
+
+        let mut data = HashMap::new();
+        let [row_start, column_start, row_end, column_end] = selected_area.range;
+        for row in row_start..=row_end {
+            let mut data_row = HashMap::new();
+            let mut text_row = Vec::new();
+            for column in column_start..=column_end {
+                let text = self.get_formatted_cell_value(sheet, row, column)?;
+                let content = self.model.get_cell_content(sheet, row, column)?;
+                let style = self.get_cell_style(sheet, row, column)?;
+                let merge = match self.model.get_merged_area(sheet, row, column)? {
+                    Some((row1, column1, row2, column2)) if (row1, column1) == (row, column) => {
+                        Some((row2 - row1 + 1, column2 - column1 + 1))
+                    }
+                    _ => None,
+                };
+                data_row.insert(
+                    column,
+                    ClipboardCell {
+                        text: content,
+                        value: self.get_cell_value_as_input(sheet, row, column)?,
+                        style,
+                        merge,
+                        column_width: Some(self.model.get_column_width(sheet, column)?),
+                    },
+                );
+                text_row.push(text);
+            }
+            wtr.write_record(text_row).unwrap();
+            data.insert(row, data_row);
+        }
+
+        let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+
+        Ok(Clipboard {
+            csv,
+            data,
+            range: (row_start, column_start, row_end, column_end),
+        })
+    }
+
+    /// Paste text that we copied
+    pub fn paste_from_clipboard(
+        &mut self,
+        source_range: ClipboardTuple,
+        clipboard: &ClipboardData,
+        is_cut: bool,
+    ) -> Result<(), String> {
+        self.paste_clipboard_data(source_range, clipboard, &PasteOptions::default(), is_cut)
+    }
+
+    /// Pastes part of what we copied in the selected cell, see [PasteOptions].
+    /// As with [UserModel::paste_from_clipboard] the references in formulas are displaced.
+    /// When transposing, references relative in both the row and the column are transposed,
+    /// absolute and mixed references are displaced as in a normal paste.
+    pub fn paste_special_from_clipboard(
+        &mut self,
+        source_range: ClipboardTuple,
+        clipboard: &ClipboardData,
+        options: &PasteOptions,
+    ) -> Result<(), String> {
+        self.paste_clipboard_data(source_range, clipboard, options, false)
+    }
+
+    /// Pastes the clipboard in the selected cell. If `is_cut` the merged areas and the formulas
+    /// are moved and the source area is cleared.
+    fn paste_clipboard_data(
+        &mut self,
+        source_range: ClipboardTuple,
+        clipboard: &ClipboardData,
+        options: &PasteOptions,
+        is_cut: bool,
+    ) -> Result<(), String> {
+        let view = self.get_selected_view();
+        let sheet = view.sheet;
+        let [selected_row, selected_column, _, _] = view.range;
+        let (source_first_row, source_first_column, source_last_row, source_last_column) =
+            source_range;
+        let mut diff_list = Vec::new();
+        if options.mode == PasteMode::ColumnWidths {
+            self.model
+                .check_sheet_allows(sheet, |p| p.allow_format_columns, "format columns")?;
+            let mut column_widths = HashMap::new();
+            for data_row in clipboard.values() {
+                for (source_column, value) in data_row {
+                    if let Some(width) = value.column_width {
+                        column_widths.insert(*source_column, width);
+                    }
+                }
+            }
+            for (source_column, width) in column_widths {
+                let column = selected_column + source_column - source_first_column;
+                let old_value = self.model.get_column_width(sheet, column)?;
+                self.model.set_column_width(sheet, column, width)?;
+                diff_list.push(Diff::SetColumnWidth {
+                    sheet,
+                    column,
+                    new_value: width,
+                    old_value,
+                });
+            }
+            self.push_diff_list(diff_list);
+            return Ok(());
+        }
+        let area = &Area {
+            sheet,
+            row: source_first_row,
+            column: source_first_column,
+            width: source_last_column - source_first_column + 1,
+            height: source_last_row - source_first_row + 1,
+        };
+        let (mut height, mut width) = (area.height, area.width);
+        if options.transpose {
+            (height, width) = (width, height);
+        }
+        let target_range = (
+            selected_row,
+            selected_column,
+            selected_row + height - 1,
+            selected_column + width - 1,
+        );
+        self.model.check_range_editable(sheet, target_range)?;
+        if is_cut {
+            self.check_area_editable(area)?;
+        }
+        let paste_formats = matches!(options.mode, PasteMode::All | PasteMode::Formats);
+        // Merged areas are moved when cutting and the ones in the target area are replaced by
+        // the ones in the clipboard
+        let mut unmerged = Vec::new();
+        if is_cut {
+            unmerged.extend(self.model.get_merged_cells_in_range(
+                sheet,
+                (
+                    source_first_row,
+                    source_first_column,
+                    source_last_row,
+                    source_last_column,
+                ),
+            )?);
+        }
+        for merged in &unmerged {
+            self.model.unmerge_cells(sheet, merged)?;
+        }
+        if paste_formats {
+            for merged in self.model.get_merged_cells_in_range(sheet, target_range)? {
+                self.model.unmerge_cells(sheet, &merged)?;
+                unmerged.push(merged);
+            }
+        }
+        for range in unmerged {
+            diff_list.push(Diff::UnmergeCells { sheet, range });
+        }
+        let mut new_merged_cells = Vec::new();
+        for (source_row, data_row) in clipboard {
+            for (source_column, value) in data_row {
+                if options.skip_blanks && value.text.is_empty() {
+                    continue;
+                }
+                let mut delta_row = source_row - source_first_row;
+                let mut delta_column = source_column - source_first_column;
+                if options.transpose {
+                    (delta_row, delta_column) = (delta_column, delta_row);
+                }
+                let target_row = selected_row + delta_row;
+                let target_column = selected_column + delta_column;
+                let input = match options.mode {
+                    PasteMode::All | PasteMode::Formulas => {
+                        // We are copying the value in
+                        // (source_row, source_column) to (target_row , target_column)
+                        // References in formulas are displaced
+                        let source = &CellReferenceIndex {
+                            sheet,
+                            column: *source_column,
+                            row: *source_row,
+                        };
+                        let target = &CellReferenceIndex {
+                            sheet,
+                            column: target_column,
+                            row: target_row,
+                        };
+                        Some(if is_cut {
+                            self.model
+                                .move_cell_value_to_area(&value.text, source, target, area)?
+                        } else if options.transpose {
+                            self.model
+                                .transpose_copied_value(&value.text, source, target)?
+                        } else {
+                            self.model
+                                .extend_copied_value(&value.text, source, target)?
+                        })
+                    }
+                    PasteMode::Values => Some(value.value.clone()),
+                    PasteMode::Formats | PasteMode::ColumnWidths => None,
+                };
+                if let Some(input) = input {
+                    if let Some(new_value) = self.get_paste_operation_input(
+                        sheet,
+                        target_row,
+                        target_column,
+                        input,
+                        options.operation,
+                    )? {
+                        let old_value = self
+                            .model
+                            .workbook
+                            .worksheet(sheet)?
+                            .cell(target_row, target_column)
+                            .cloned();
+                        self.model.set_user_input(
+                            sheet,
+                            target_row,
+                            target_column,
+                            new_value.clone(),
+                        )?;
+                        diff_list.push(Diff::SetCellValue {
+                            sheet,
+                            row: target_row,
+                            column: target_column,
+                            new_value,
+                            old_value: Box::new(old_value),
+                        });
+                    }
+                }
+                if paste_formats {
+                    let old_style =
+                        self.model
+                            .get_style_for_cell(sheet, target_row, target_column)?;
+                    self.model
+                        .set_cell_style(sheet, target_row, target_column, &value.style)?;
+                    diff_list.push(Diff::SetCellStyle {
+                        sheet,
+                        row: target_row,
+                        column: target_column,
+                        old_value: Box::new(old_style),
+                        new_value: Box::new(value.style.clone()),
+                    });
+                    if let Some((mut merge_height, mut merge_width)) = value.merge {
+                        if options.transpose {
+                            (merge_height, merge_width) = (merge_width, merge_height);
+                        }
+                        new_merged_cells.push(range_to_string((
+                            target_row,
+                            target_column,
+                            target_row + merge_height - 1,
+                            target_column + merge_width - 1,
+                        ))?);
+                    }
+                }
+            }
+        }
+        for range in new_merged_cells {
+            self.model.merge_cells(sheet, &range)?;
+            diff_list.push(Diff::MergeCells { sheet, range });
+        }
+        if is_cut {
+            for row in source_first_row..=source_last_row {
+                for column in source_first_column..=source_last_column {
+                    let old_value = self
+                        .model
+                        .workbook
+                        .worksheet(sheet)?
+                        .cell(row, column)
+                        .cloned();
+                    diff_list.push(Diff::CellClearContents {
+                        sheet,
+                        row,
                         column,
                         old_value: Box::new(old_value),
                     });
@@ -1488,74 +4335,850 @@ impl UserModel {
                 }
             }
         }
-        self.push_diff_list(diff_list);
-        // select the pasted area
-        self.set_selected_range(selected_row, selected_column, max_row, max_column)?;
-        self.evaluate_if_not_paused();
+        self.push_diff_list(diff_list);
+        // select the pasted area
+        self.set_selected_range(
+            selected_row,
+            selected_column,
+            target_range.2,
+            target_range.3,
+        )?;
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Paste a csv-string into the model
+    pub fn paste_csv_string(&mut self, area: &Area, csv: &str) -> Result<(), String> {
+        let mut diff_list = Vec::new();
+        let sheet = area.sheet;
+        let mut row = area.row;
+        let mut column = area.column;
+        // Create a sniffer with default settings
+        let mut sniffer = Sniffer::new();
+        let mut csv_reader = Cursor::new(csv);
+
+        // Sniff the CSV metadata
+        let metadata = sniffer
+            .sniff_reader(&mut csv_reader)
+            .map_err(|_| "Failed")?;
+        // Reset the cursor to the beginning after sniffing
+        csv_reader.set_position(0);
+        let mut reader = ReaderBuilder::new()
+            .delimiter(metadata.dialect.delimiter)
+            .has_headers(false)
+            .from_reader(csv_reader);
+        let records: Vec<_> = reader.records().filter_map(|r| r.ok()).collect();
+        if let Some(width) = records.iter().map(|r| r.len()).max() {
+            self.model.check_range_editable(
+                sheet,
+                (
+                    area.row,
+                    area.column,
+                    area.row + records.len() as i32 - 1,
+                    area.column + width as i32 - 1,
+                ),
+            )?;
+        }
+        for r in records {
+            column = area.column;
+            for value in &r {
+                let old_value = self
+                    .model
+                    .workbook
+                    .worksheet(sheet)?
+                    .cell(row, column)
+                    .cloned();
+                // let old_style = self.model.get_style_for_cell(sheet, row, column)?;
+                let new_value = self.model.get_canonical_user_input(value);
+                self.model
+                    .set_user_input(sheet, row, column, new_value.clone())?;
+
+                diff_list.push(Diff::SetCellValue {
+                    sheet,
+                    row,
+                    column,
+                    new_value,
+                    old_value: Box::new(old_value),
+                });
+                column += 1;
+            }
+            row += 1;
+        }
+        self.push_diff_list(diff_list);
+        // select the pasted area
+        self.set_selected_range(area.row, area.column, row, column)?;
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    // **** Private methods ****** //
+
+    /// Returns the value of the cell as it would be typed in a cell, with text quoted so that
+    /// it is not read as a number, a boolean or a formula
+    fn get_cell_value_as_input(&self, sheet: u32, row: i32, column: i32) -> Result<String, String> {
+        let cell = match self.model.workbook.worksheet(sheet)?.cell(row, column) {
+            Some(cell) => cell,
+            None => return Ok("".to_string()),
+        };
+        let text = cell.get_text(&self.model.workbook.shared_strings, &self.model.language);
+        if cell.get_type() == CellType::Text && !text.is_empty() {
+            return Ok(format!("'{text}"));
+        }
+        Ok(text)
+    }
+
+    /// Returns the input for the cell that combines its value with the pasted `input`,
+    /// or None if the cell keeps its value.
+    /// Blank cells count as zero and text is pasted as is.
+    fn get_paste_operation_input(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        input: String,
+        operation: PasteOperation,
+    ) -> Result<Option<String>, String> {
+        let operator = match operation {
+            PasteOperation::None => return Ok(Some(input)),
+            PasteOperation::Add => "+",
+            PasteOperation::Subtract => "-",
+            PasteOperation::Multiply => "*",
+            PasteOperation::Divide => "/",
+        };
+        let pasted = if input.is_empty() {
+            "0".to_string()
+        } else if let Some(formula) = input.strip_prefix('=') {
+            formula.to_string()
+        } else if input.parse::<f64>().is_ok() {
+            input
+        } else {
+            return Ok(Some(input));
+        };
+        let target = match self.model.workbook.worksheet(sheet)?.cell(row, column) {
+            None => "0".to_string(),
+            Some(cell) if cell.get_formula().is_some() => {
+                let content = self.model.get_cell_content(sheet, row, column)?;
+                content.trim_start_matches('=').to_string()
+            }
+            Some(cell) => {
+                match cell.value(&self.model.workbook.shared_strings, &self.model.language) {
+                    CellValue::None => "0".to_string(),
+                    CellValue::Number(value) => to_excel_precision_str(value),
+                    CellValue::String(_) | CellValue::Boolean(_) => return Ok(None),
+                }
+            }
+        };
+        if let (Ok(left), Ok(right)) = (target.parse::<f64>(), pasted.parse::<f64>()) {
+            let result = match operation {
+                PasteOperation::Add => left + right,
+                PasteOperation::Subtract => left - right,
+                PasteOperation::Multiply => left * right,
+                _ if right == 0.0 => return Ok(Some("#DIV/0!".to_string())),
+                _ => left / right,
+            };
+            if !result.is_finite() {
+                return Ok(Some("#NUM!".to_string()));
+            }
+            return Ok(Some(to_excel_precision_str(result)));
+        }
+        Ok(Some(format!("=({target}){operator}({pasted})")))
+    }
+
+    /// Grows the area so that it doesn't cut through any merged area
+    fn expand_area_to_merged_cells(&self, area: &Area) -> Result<Area, String> {
+        let (row1, column1, row2, column2) = self.model.expand_to_merged_cells(
+            area.sheet,
+            (
+                area.row,
+                area.column,
+                area.row + area.height - 1,
+                area.column + area.width - 1,
+            ),
+        )?;
+        Ok(Area {
+            sheet: area.sheet,
+            row: row1,
+            column: column1,
+            width: column2 - column1 + 1,
+            height: row2 - row1 + 1,
+        })
+    }
+
+    /// Fails if the sheet is protected and any cell in the area is locked
+    fn check_area_editable(&self, area: &Area) -> Result<(), String> {
+        self.model.check_range_editable(
+            area.sheet,
+            (
+                area.row,
+                area.column,
+                area.row + area.height - 1,
+                area.column + area.width - 1,
+            ),
+        )
+    }
+
+    fn push_set_comment_diff(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        old_value: Option<Comment>,
+    ) -> Result<(), String> {
+        if let (Some(old_value), Some(new_value)) =
+            (old_value, self.model.get_comment(sheet, row, column)?)
+        {
+            self.push_diff_list(vec![Diff::SetComment {
+                sheet,
+                row,
+                column,
+                old_value: Box::new(old_value),
+                new_value: Box::new(new_value),
+            }]);
+        }
+        Ok(())
+    }
+
+    fn check_row_range(&self, row_start: i32, row_end: i32) -> Result<(), String> {
+        if !is_valid_row(row_start) || !is_valid_row(row_end) || row_start > row_end {
+            return Err(format!("Invalid rows: '{row_start}:{row_end}'"));
+        }
+        Ok(())
+    }
+
+    /// Returns the outline level and the first and last rows of the innermost group that contains `row`
+    fn get_innermost_row_group(&self, sheet: u32, row: i32) -> Result<(u8, i32, i32), String> {
+        let level = self.model.get_row_outline_level(sheet, row)?;
+        match self.model.get_row_group(sheet, row, level)? {
+            Some((first, last)) => Ok((level, first, last)),
+            None => Err("The row is not in a group".to_string()),
+        }
+    }
+
+    fn get_auto_filter_or_error(&self, sheet: u32) -> Result<AutoFilter, String> {
+        self.model
+            .get_auto_filter(sheet)?
+            .ok_or_else(|| "The sheet has no autofilter".to_string())
+    }
+
+    /// Sorts the rows of the `range` (row1, column1, row2, column2) by the `keys`.
+    /// Only the cells inside the range move.
+    fn sort_rows(
+        &mut self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+        keys: &[SortKey],
+    ) -> Result<(), String> {
+        self.model
+            .check_sheet_allows(sheet, |p| p.allow_sort, "sort")?;
+        self.model.check_range_editable(sheet, range)?;
+        if !self
+            .model
+            .get_merged_cells_in_range(sheet, range)?
+            .is_empty()
+        {
+            return Err("Cannot sort a range with merged cells".to_string());
+        }
+        // There is nothing to sort beyond the cells with data
+        let dimension = self.model.workbook.worksheet(sheet)?.dimension();
+        let (row1, column1) = (range.0, range.1);
+        let row2 = range.2.min(dimension.max_row);
+        let column2 = range.3.min(dimension.max_column);
+        if row1 >= row2 || column1 > column2 {
+            // We still validate the keys
+            self.model
+                .get_sorted_rows(sheet, (row1, column1, row1, range.3), keys)?;
+            return Ok(());
+        }
+        let sorted_rows =
+            self.model
+                .get_sorted_rows(sheet, (row1, column1, row2, column2), keys)?;
+        self.set_rows_order(sheet, (row1, column1, row2, column2), &sorted_rows)
+    }
+
+    /// Rewrites the rows of the `range` with the `source_rows` in order, references to cells in the
+    /// same row move with it. The rows left at the bottom are cleared.
+    ///
+    /// See also:
+    /// * [Model::set_rows_order_action]
+    fn set_rows_order(
+        &mut self,
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+        source_rows: &[i32],
+    ) -> Result<(), String> {
+        let old_data = self.move_with_data(sheet, range, |model| {
+            model.set_rows_order_action(sheet, range, source_rows)
+        })?;
+        self.push_diff_list(vec![Diff::SetRowsOrder {
+            sheet,
+            range,
+            source_rows: source_rows.to_vec(),
+            old_data,
+        }]);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    fn set_table_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        name: &str,
+        table: Option<Table>,
+    ) -> Result<(), String> {
+        let old_value = self.model.workbook.tables.get(name).cloned();
+        self.model.set_table(name, table.clone())?;
+        diff_list.push(Diff::SetTable {
+            name: name.to_string(),
+            old_value: Box::new(old_value),
+            new_value: Box::new(table),
+        });
+        Ok(())
+    }
+
+    fn rename_table_references_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        name: &str,
+        new_name: &str,
+        column: Option<(&str, &str)>,
+    ) {
+        self.model.rename_table_references(name, new_name, column);
+        diff_list.push(Diff::RenameTableReferences {
+            name: name.to_string(),
+            new_name: new_name.to_string(),
+            column: column
+                .map(|(old_column, new_column)| (old_column.to_string(), new_column.to_string())),
+        });
+    }
+
+    fn set_pivot_table_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        name: &str,
+        pivot_table: Option<PivotTable>,
+    ) -> Result<(), String> {
+        let old_value = self.model.get_pivot_table(sheet, name).ok();
+        self.model
+            .set_pivot_table(sheet, name, pivot_table.clone())?;
+        diff_list.push(Diff::SetPivotTable {
+            sheet,
+            name: name.to_string(),
+            old_value: Box::new(old_value),
+            new_value: Box::new(pivot_table),
+        });
+        Ok(())
+    }
+
+    /// Clears the cells of the pivot table `name` and writes the cells of `pivot_table` at its
+    /// location. Fails if there is other data in the way.
+    fn render_pivot_table_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        name: &str,
+        mut pivot_table: PivotTable,
+    ) -> Result<(), String> {
+        let cells = self.model.get_pivot_table_cells(&pivot_table)?;
+        let range = Model::get_pivot_table_range(&pivot_table.location, &cells)?;
+        let old_range = match &pivot_table.reference {
+            Some(reference) => Some(parse_range_reference(reference)?),
+            None => None,
+        };
+        if let Some((row1, column1, row2, column2)) = range {
+            let data = self.model.get_pivot_data(&pivot_table.source)?;
+            let (data_row1, data_column1, data_row2, data_column2) = data.range;
+            // The source includes the row with the names of the fields
+            let source = (data_row1 - 1, data_column1, data_row2, data_column2);
+            if data.sheet == sheet && intersects(source, (row1, column1, row2, column2)) {
+                return Err("A pivot table cannot overlap its source".to_string());
+            }
+            for row in row1..=row2 {
+                for column in column1..=column2 {
+                    let is_pivot_cell = old_range
+                        .is_some_and(|old_range| intersects(old_range, (row, column, row, column)));
+                    if !is_pivot_cell
+                        && !self.model.get_cell_content(sheet, row, column)?.is_empty()
+                    {
+                        return Err(
+                            "There is already data in the cells of the pivot table".to_string()
+                        );
+                    }
+                }
+            }
+        }
+        if let Some((row1, column1, row2, column2)) = old_range {
+            for row in row1..=row2 {
+                self.clear_row_contents_with_diff(diff_list, sheet, row, column1, column2)?;
+            }
+        }
+        if let Some((row1, column1, _, _)) = range {
+            for (row, values) in (row1..).zip(&cells) {
+                for (column, value) in (column1..).zip(values) {
+                    if *value != PivotCell::Empty {
+                        let input = value.to_user_input();
+                        self.set_user_input_with_diff(diff_list, sheet, row, column, &input)?;
+                    }
+                }
+            }
+        }
+        pivot_table.reference = range.map(range_to_string).transpose()?;
+        let new_name = pivot_table.name.clone();
+        if new_name != name {
+            self.set_pivot_table_with_diff(diff_list, sheet, name, None)?;
+        }
+        self.set_pivot_table_with_diff(diff_list, sheet, &new_name, Some(pivot_table))?;
+        Ok(())
+    }
+
+    fn set_user_input_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        value: &str,
+    ) -> Result<(), String> {
+        let old_value = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .cell(row, column)
+            .cloned();
+        self.model
+            .set_user_input(sheet, row, column, value.to_string())?;
+        diff_list.push(Diff::SetCellValue {
+            sheet,
+            row,
+            column,
+            new_value: value.to_string(),
+            old_value: Box::new(old_value),
+        });
+        Ok(())
+    }
+
+    /// Writes the names of the columns of the `table` in its header cells if they are different
+    fn set_table_headers_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        table: &Table,
+    ) -> Result<(), String> {
+        if table.header_row_count == 0 {
+            return Ok(());
+        }
+        let sheet = self.model.get_table_sheet(&table.name).or_else(|_| {
+            self.model
+                .get_sheet_index_by_name(&table.sheet_name)
+                .ok_or_else(|| format!("Sheet not found: '{}'", table.sheet_name))
+        })?;
+        let (row, column1, _, _) = parse_range_reference(&table.reference)?;
+        for (index, table_column) in table.columns.iter().enumerate() {
+            let column = column1 + index as i32;
+            if self.model.get_formatted_cell_value(sheet, row, column)? != table_column.name {
+                // Names are always text
+                let value = if table_column.name.parse::<f64>().is_ok() {
+                    format!("'{}", table_column.name)
+                } else {
+                    table_column.name.clone()
+                };
+                self.set_user_input_with_diff(diff_list, sheet, row, column, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the labels and formulas of the totals row of the `table`
+    fn set_table_totals_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        table: &Table,
+    ) -> Result<(), String> {
+        if table.totals_row_count == 0 {
+            return Ok(());
+        }
+        let sheet = self.model.get_table_sheet(&table.name)?;
+        let (_, column1, row, _) = parse_range_reference(&table.reference)?;
+        for (index, table_column) in table.columns.iter().enumerate() {
+            let column = column1 + index as i32;
+            let value = match (
+                &table_column.totals_row_label,
+                get_totals_row_formula(table, table_column),
+            ) {
+                (_, Some(formula)) => formula,
+                (Some(label), None) => label.clone(),
+                (None, None) => continue,
+            };
+            if self.model.get_cell_content(sheet, row, column)? != value {
+                self.set_user_input_with_diff(diff_list, sheet, row, column, &value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills the calculated columns of the tables in the new `row` of the `sheet`
+    fn fill_calculated_columns_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        row: i32,
+    ) -> Result<(), String> {
+        for table in self.model.get_tables(sheet)? {
+            let area = self.model.get_table_data_area(&table.name)?;
+            if row < area.row || row >= area.row + area.height {
+                continue;
+            }
+            for (index, table_column) in table.columns.iter().enumerate() {
+                let column = area.column + index as i32;
+                let formula = match &table_column.calculated_column_formula {
+                    Some(formula) => formula,
+                    None => continue,
+                };
+                if self.model.get_cell_content(sheet, row, column)?.is_empty() {
+                    let value = self.get_calculated_column_content(&area, column, formula, row)?;
+                    self.set_user_input_with_diff(diff_list, sheet, row, column, &value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the content of the cell in `row` of a calculated column with `formula`.
+    /// The formula is as in the first data row of the table, see [to_this_row_references].
+    fn get_calculated_column_content(
+        &mut self,
+        area: &Area,
+        column: i32,
+        formula: &str,
+        row: i32,
+    ) -> Result<String, String> {
+        self.model.extend_copied_value(
+            &format!("={formula}"),
+            &CellReferenceIndex {
+                sheet: area.sheet,
+                row: area.row,
+                column,
+            },
+            &CellReferenceIndex {
+                sheet: area.sheet,
+                row,
+                column,
+            },
+        )
+    }
+
+    /// A formula typed in the data cells of a column of a table is copied to all the cells of the
+    /// column if they are empty or if the column is calculated
+    fn set_calculated_column_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        mut table: Table,
+        index: usize,
+        row: i32,
+    ) -> Result<(), String> {
+        let area = self.model.get_table_data_area(&table.name)?;
+        let sheet = area.sheet;
+        let column = area.column + index as i32;
+        let content = self.model.get_cell_content(sheet, row, column)?;
+        if !content.starts_with('=') {
+            return Ok(());
+        }
+        let rows = area.row..area.row + area.height;
+        if table.columns[index].calculated_column_formula.is_none() {
+            for other_row in rows.clone() {
+                if other_row != row
+                    && !self
+                        .model
+                        .get_cell_content(sheet, other_row, column)?
+                        .is_empty()
+                {
+                    return Ok(());
+                }
+            }
+        }
+        // The formula is kept as if typed in the first data row, with structured references to
+        // the cells of its row, like Excel does
+        let first_row_content = self.model.extend_copied_value(
+            &content,
+            &CellReferenceIndex { sheet, row, column },
+            &CellReferenceIndex {
+                sheet,
+                row: area.row,
+                column,
+            },
+        )?;
+        let formula = to_this_row_references(
+            first_row_content
+                .strip_prefix('=')
+                .unwrap_or(&first_row_content),
+            &table,
+            area.row,
+            area.column,
+        );
+        for other_row in rows {
+            if other_row == row {
+                continue;
+            }
+            let value = self.get_calculated_column_content(&area, column, &formula, other_row)?;
+            if self.model.get_cell_content(sheet, other_row, column)? != value {
+                self.set_user_input_with_diff(diff_list, sheet, other_row, column, &value)?;
+            }
+        }
+        table.columns[index].calculated_column_formula = Some(formula);
+        let name = table.name.clone();
+        self.set_table_with_diff(diff_list, &name, Some(table))
+    }
+
+    fn clear_row_contents_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        row: i32,
+        column1: i32,
+        column2: i32,
+    ) -> Result<(), String> {
+        for column in column1..=column2 {
+            let old_value = self
+                .model
+                .workbook
+                .worksheet(sheet)?
+                .cell(row, column)
+                .cloned();
+            if old_value.is_some() {
+                self.model.cell_clear_contents(sheet, row, column)?;
+                diff_list.push(Diff::CellClearContents {
+                    sheet,
+                    row,
+                    column,
+                    old_value: Box::new(old_value),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_cells_empty(
+        &self,
+        sheet: u32,
+        row: i32,
+        column1: i32,
+        column2: i32,
+    ) -> Result<(), String> {
+        for column in column1..=column2 {
+            if !self.model.get_cell_content(sheet, row, column)?.is_empty() {
+                return Err(format!(
+                    "The cells of row {row} next to the table are not empty"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Typing in a header cell of a table renames its column and typing in the row below a table
+    /// without totals row adds the row to the table
+    fn update_tables_after_input(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<(), String> {
+        if self.model.is_sheet_protected(sheet)? {
+            return Ok(());
+        }
+        for mut table in self.model.get_tables(sheet)? {
+            let (row1, column1, row2, column2) = parse_range_reference(&table.reference)?;
+            if column < column1 || column > column2 {
+                continue;
+            }
+            let index = (column - column1) as usize;
+            if table.header_row_count > 0 && row == row1 {
+                let names: Vec<String> = table
+                    .columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != index)
+                    .map(|(_, table_column)| table_column.name.clone())
+                    .collect();
+                let name =
+                    self.model
+                        .get_table_column_name(sheet, Some(row), column, index, &names)?;
+                if table.columns[index].name != name {
+                    let old_name = std::mem::replace(&mut table.columns[index].name, name.clone());
+                    let table_name = table.name.clone();
+                    self.set_table_headers_with_diff(diff_list, &table)?;
+                    self.set_table_with_diff(diff_list, &table_name, Some(table))?;
+                    self.rename_table_references_with_diff(
+                        diff_list,
+                        &table_name,
+                        &table_name,
+                        Some((&old_name, &name)),
+                    );
+                }
+                return Ok(());
+            }
+            if table.totals_row_count > 0 && row == row2 {
+                // The totals row has a label, a custom formula or nothing
+                let content = self.model.get_cell_content(sheet, row, column)?;
+                let table_column = &mut table.columns[index];
+                table_column.totals_row_label = None;
+                table_column.totals_row_formula = None;
+                table_column.totals_row_function = None;
+                if let Some(formula) = content.strip_prefix('=') {
+                    table_column.totals_row_function = Some(TotalsRowFunction::Custom);
+                    table_column.totals_row_formula = Some(formula.to_string());
+                } else if !content.is_empty() {
+                    table_column.totals_row_label = Some(content);
+                }
+                let table_name = table.name.clone();
+                return self.set_table_with_diff(diff_list, &table_name, Some(table));
+            }
+            if (row1..=row2).contains(&row) {
+                return self.set_calculated_column_with_diff(diff_list, table, index, row);
+            }
+            if table.totals_row_count == 0
+                && row == row2 + 1
+                && !self.model.get_cell_content(sheet, row, column)?.is_empty()
+            {
+                let new_range = (row1, column1, row, column2);
+                if self
+                    .model
+                    .check_table_range(sheet, new_range, Some(&table.name))
+                    .is_ok()
+                {
+                    let table_name = table.name.clone();
+                    table.reference = range_to_string(new_range)?;
+                    self.set_table_with_diff(diff_list, &table_name, Some(table))?;
+                    self.fill_calculated_columns_with_diff(diff_list, sheet, row)?;
+                }
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    fn set_filtered_rows_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        rows: Vec<(i32, bool)>,
+    ) -> Result<(), String> {
+        for (row, visible) in rows {
+            self.set_row_hidden_with_diff(diff_list, sheet, row, !visible)?;
+        }
+        Ok(())
+    }
+
+    fn set_row_hidden_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        row: i32,
+        hidden: bool,
+    ) -> Result<(), String> {
+        let old_value = self.model.is_row_hidden(sheet, row)?;
+        if old_value != hidden {
+            self.model.set_row_hidden(sheet, row, hidden)?;
+            diff_list.push(Diff::SetRowHidden {
+                sheet,
+                row,
+                old_value,
+                new_value: hidden,
+            });
+        }
+        Ok(())
+    }
+
+    /// Sets the collapsed flag of the row after a group, if it exists
+    fn set_row_collapsed_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        row: i32,
+        collapsed: bool,
+    ) -> Result<(), String> {
+        if row > LAST_ROW {
+            return Ok(());
+        }
+        let old_value = self.model.is_row_collapsed(sheet, row)?;
+        if old_value != collapsed {
+            self.model.set_row_collapsed(sheet, row, collapsed)?;
+            diff_list.push(Diff::SetRowCollapsed {
+                sheet,
+                row,
+                old_value,
+                new_value: collapsed,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_column_range(&self, column_start: i32, column_end: i32) -> Result<(), String> {
+        if !is_valid_column_number(column_start)
+            || !is_valid_column_number(column_end)
+            || column_start > column_end
+        {
+            return Err(format!("Invalid columns: '{column_start}:{column_end}'"));
+        }
+        Ok(())
+    }
+
+    /// Returns the outline level and the first and last columns of the innermost group that contains `column`
+    fn get_innermost_column_group(
+        &self,
+        sheet: u32,
+        column: i32,
+    ) -> Result<(u8, i32, i32), String> {
+        let level = self.model.get_column_outline_level(sheet, column)?;
+        match self.model.get_column_group(sheet, column, level)? {
+            Some((first, last)) => Ok((level, first, last)),
+            None => Err("The column is not in a group".to_string()),
+        }
+    }
+
+    fn set_column_hidden_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        column: i32,
+        hidden: bool,
+    ) -> Result<(), String> {
+        let old_value = self.model.is_column_hidden(sheet, column)?;
+        if old_value != hidden {
+            self.model.set_column_hidden(sheet, column, hidden)?;
+            diff_list.push(Diff::SetColumnHidden {
+                sheet,
+                column,
+                old_value,
+                new_value: hidden,
+            });
+        }
         Ok(())
     }
 
-    /// Paste a csv-string into the model
-    pub fn paste_csv_string(&mut self, area: &Area, csv: &str) -> Result<(), String> {
-        let mut diff_list = Vec::new();
-        let sheet = area.sheet;
-        let mut row = area.row;
-        let mut column = area.column;
-        // Create a sniffer with default settings
-        let mut sniffer = Sniffer::new();
-        let mut csv_reader = Cursor::new(csv);
-
-        // Sniff the CSV metadata
-        let metadata = sniffer
-            .sniff_reader(&mut csv_reader)
-            .map_err(|_| "Failed")?;
-        // Reset the cursor to the beginning after sniffing
-        csv_reader.set_position(0);
-        let mut reader = ReaderBuilder::new()
-            .delimiter(metadata.dialect.delimiter)
-            .has_headers(false)
-            .from_reader(csv_reader);
-        for record in reader.records() {
-            match record {
-                Ok(r) => {
-                    column = area.column;
-                    for value in &r {
-                        let old_value = self
-                            .model
-                            .workbook
-                            .worksheet(sheet)?
-                            .cell(row, column)
-                            .cloned();
-                        // let old_style = self.model.get_style_for_cell(sheet, row, column)?;
-                        self.model
-                            .set_user_input(sheet, row, column, value.to_string())?;
-
-                        diff_list.push(Diff::SetCellValue {
-                            sheet,
-                            row,
-                            column,
-                            new_value: value.to_string(),
-                            old_value: Box::new(old_value),
-                        });
-                        column += 1;
-                    }
-                }
-                Err(_) => {
-                    // skip
-                    continue;
-                }
-            };
-            row += 1;
+    /// Sets the collapsed flag of the column after a group, if it exists
+    fn set_column_collapsed_with_diff(
+        &mut self,
+        diff_list: &mut DiffList,
+        sheet: u32,
+        column: i32,
+        collapsed: bool,
+    ) -> Result<(), String> {
+        if column > LAST_COLUMN {
+            return Ok(());
+        }
+        let old_value = self.model.is_column_collapsed(sheet, column)?;
+        if old_value != collapsed {
+            self.model.set_column_collapsed(sheet, column, collapsed)?;
+            diff_list.push(Diff::SetColumnCollapsed {
+                sheet,
+                column,
+                old_value,
+                new_value: collapsed,
+            });
         }
-        self.push_diff_list(diff_list);
-        // select the pasted area
-        self.set_selected_range(area.row, area.column, row, column)?;
-        self.evaluate_if_not_paused();
         Ok(())
     }
 
-    // **** Private methods ****** //
-
     fn push_diff_list(&mut self, diff_list: DiffList) {
         self.send_queue.push(QueueDiffs {
             r#type: DiffType::Redo,
@@ -1565,14 +5188,23 @@ impl UserModel {
     }
 
     fn evaluate_if_not_paused(&mut self) {
-        if !self.pause_evaluation {
-            self.model.evaluate();
+        if self.pause_evaluation || self.model.get_calc_mode() == CalcMode::Manual {
+            // Any formula might be out of date now
+            self.stale_sheets = self
+                .model
+                .workbook
+                .worksheets
+                .iter()
+                .map(|worksheet| worksheet.sheet_id)
+                .collect();
+        } else {
+            self.evaluate();
         }
     }
 
     fn apply_undo_diff_list(&mut self, diff_list: &DiffList) -> Result<(), String> {
         let mut needs_evaluation = false;
-        for diff in diff_list {
+        for diff in diff_list.iter().rev() {
             match diff {
                 Diff::SetCellValue {
                     sheet,
@@ -1580,6 +5212,13 @@ impl UserModel {
                     column,
                     new_value: _,
                     old_value,
+                }
+                | Diff::SetCellRichText {
+                    sheet,
+                    row,
+                    column,
+                    new_value: _,
+                    old_value,
                 } => {
                     needs_evaluation = true;
                     match *old_value.clone() {
@@ -1606,6 +5245,52 @@ impl UserModel {
                     new_value: _,
                     old_value,
                 } => self.model.set_row_height(*sheet, *row, *old_value)?,
+                Diff::SetRowHidden {
+                    sheet,
+                    row,
+                    new_value: _,
+                    old_value,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_row_hidden(*sheet, *row, *old_value)?;
+                }
+                Diff::SetColumnHidden {
+                    sheet,
+                    column,
+                    new_value: _,
+                    old_value,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_column_hidden(*sheet, *column, *old_value)?;
+                }
+                Diff::SetRowOutlineLevel {
+                    sheet,
+                    row,
+                    new_value: _,
+                    old_value,
+                } => self.model.set_row_outline_level(*sheet, *row, *old_value)?,
+                Diff::SetColumnOutlineLevel {
+                    sheet,
+                    column,
+                    new_value: _,
+                    old_value,
+                } => self
+                    .model
+                    .set_column_outline_level(*sheet, *column, *old_value)?,
+                Diff::SetRowCollapsed {
+                    sheet,
+                    row,
+                    new_value: _,
+                    old_value,
+                } => self.model.set_row_collapsed(*sheet, *row, *old_value)?,
+                Diff::SetColumnCollapsed {
+                    sheet,
+                    column,
+                    new_value: _,
+                    old_value,
+                } => self
+                    .model
+                    .set_column_collapsed(*sheet, *column, *old_value)?,
                 Diff::CellClearContents {
                     sheet,
                     row,
@@ -1646,6 +5331,20 @@ impl UserModel {
                 } => self
                     .model
                     .set_cell_style(*sheet, *row, *column, old_value)?,
+                Diff::ApplyNamedStyle {
+                    sheet,
+                    row,
+                    column,
+                    style_name: _,
+                    old_value,
+                    old_style_name,
+                } => self.model.set_cell_style_with_name(
+                    *sheet,
+                    *row,
+                    *column,
+                    old_value,
+                    old_style_name,
+                )?,
                 Diff::InsertRow { sheet, row } => {
                     self.model.delete_rows(*sheet, *row, 1)?;
                     needs_evaluation = true;
@@ -1662,67 +5361,300 @@ impl UserModel {
                         worksheet.rows.push(row_data);
                     }
                     worksheet.sheet_data.insert(*row, old_data.data.clone());
+                    worksheet.comments.extend(old_data.comments.clone());
+                    worksheet.hyperlinks.extend(old_data.hyperlinks.clone());
+                    for table in &old_data.tables {
+                        self.model.set_table(&table.name, Some(table.clone()))?;
+                    }
+                    self.model
+                        .set_all_conditional_formats(&old_data.conditional_formats);
+                    self.model
+                        .set_all_data_validations(&old_data.data_validations);
+                    self.model.set_all_pivot_tables(&old_data.pivot_tables);
                 }
                 Diff::InsertColumn { sheet, column } => {
                     self.model.delete_columns(*sheet, *column, 1)?;
                     needs_evaluation = true;
                 }
-                Diff::DeleteColumn {
+                Diff::DeleteColumn {
+                    sheet,
+                    column,
+                    old_data,
+                } => {
+                    needs_evaluation = true;
+                    // inserts an empty column
+                    self.model.insert_columns(*sheet, *column, 1)?;
+                    // puts all the data back
+                    let worksheet = self.model.workbook.worksheet_mut(*sheet)?;
+                    for (row, cell) in &old_data.data {
+                        worksheet.update_cell(*row, *column, cell.clone())?;
+                    }
+                    worksheet.comments.extend(old_data.comments.clone());
+                    worksheet.hyperlinks.extend(old_data.hyperlinks.clone());
+                    // makes sure that the width, style and visibility are correct
+                    if let Some(col) = &old_data.column {
+                        *worksheet.column_mut(*column)? = col.clone();
+                    }
+                    for table in &old_data.tables {
+                        self.model.set_table(&table.name, Some(table.clone()))?;
+                    }
+                    self.model
+                        .set_all_conditional_formats(&old_data.conditional_formats);
+                    self.model
+                        .set_all_data_validations(&old_data.data_validations);
+                    self.model.set_all_pivot_tables(&old_data.pivot_tables);
+                }
+                Diff::SetFrozenRowsCount {
+                    sheet,
+                    new_value: _,
+                    old_value,
+                } => self.model.set_frozen_rows(*sheet, *old_value)?,
+                Diff::SetFrozenColumnsCount {
+                    sheet,
+                    new_value: _,
+                    old_value,
+                } => self.model.set_frozen_columns(*sheet, *old_value)?,
+                Diff::DeleteSheet { sheet: _ } => {
+                    // do nothing
+                }
+                Diff::NewSheet { index, name: _ } => {
+                    self.model.delete_sheet(*index)?;
+                }
+                Diff::RenameSheet {
+                    index,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.rename_sheet_by_index(*index, old_value)?;
+                }
+                Diff::MoveSheet { sheet, new_index } => {
+                    self.model.move_sheet(*new_index, *sheet)?;
+                }
+                Diff::DuplicateSheet { sheet } => {
+                    self.model.delete_sheet(*sheet + 1)?;
+                }
+                Diff::CopySheet { index, .. } => {
+                    self.model.delete_sheet(*index)?;
+                }
+                Diff::SetSheetColor {
+                    index,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.set_sheet_color(*index, old_value)?;
+                }
+                Diff::SetShowGridLines {
+                    sheet,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.set_show_grid_lines(*sheet, *old_value)?;
+                }
+                Diff::SetDate1904 {
+                    old_value,
+                    new_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_date_1904(*old_value);
+                }
+                Diff::SetPrecisionAsDisplayed {
+                    old_value,
+                    new_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_precision_as_displayed(*old_value);
+                }
+                Diff::SetCalcMode {
+                    old_value,
+                    new_value: _,
+                } => {
+                    // Only evaluate if there are pending changes
+                    needs_evaluation |= !self.stale_sheets.is_empty();
+                    self.model.set_calc_mode(*old_value);
+                }
+                Diff::CreateNamedStyle {
+                    style_name,
+                    style: _,
+                } => {
+                    self.model.workbook.styles.delete_named_style(style_name)?;
+                }
+                Diff::SetNamedStyle {
+                    style_name,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.set_named_style(style_name, old_value)?;
+                }
+                Diff::SetTheme {
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.set_theme(old_value)?;
+                }
+                Diff::AddConditionalFormat {
+                    sheet,
+                    index,
+                    value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model.delete_conditional_format(*sheet, *index)?;
+                }
+                Diff::SetConditionalFormat {
+                    sheet,
+                    index,
+                    old_value,
+                    new_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .set_conditional_format(*sheet, *index, *old_value.clone())?;
+                }
+                Diff::DeleteConditionalFormat {
+                    sheet,
+                    index,
+                    old_value,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .insert_conditional_format(*sheet, *index, *old_value.clone())?;
+                }
+                Diff::AddDataValidation {
+                    sheet,
+                    index,
+                    value: _,
+                } => {
+                    self.model.delete_data_validation(*sheet, *index)?;
+                }
+                Diff::SetDataValidation {
+                    sheet,
+                    index,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model
+                        .set_data_validation(*sheet, *index, *old_value.clone())?;
+                }
+                Diff::DeleteDataValidation {
+                    sheet,
+                    index,
+                    old_value,
+                } => {
+                    self.model
+                        .insert_data_validation(*sheet, *index, *old_value.clone())?;
+                }
+                Diff::MergeCells { sheet, range } => {
+                    self.model.unmerge_cells(*sheet, range)?;
+                }
+                Diff::UnmergeCells { sheet, range } => {
+                    // The cells were merged before, there is nothing to clear
+                    self.model
+                        .workbook
+                        .worksheet_mut(*sheet)?
+                        .merge_cells
+                        .push(range.clone());
+                }
+                Diff::AddComment {
+                    sheet,
+                    row,
+                    column,
+                    value: _,
+                } => {
+                    self.model.set_comment(*sheet, *row, *column, None)?;
+                }
+                Diff::SetComment {
+                    sheet,
+                    row,
+                    column,
+                    old_value,
+                    new_value: _,
+                }
+                | Diff::DeleteComment {
+                    sheet,
+                    row,
+                    column,
+                    old_value,
+                } => {
+                    self.model
+                        .set_comment(*sheet, *row, *column, Some(*old_value.clone()))?;
+                }
+                Diff::SetHyperlink {
                     sheet,
+                    row,
                     column,
-                    old_data,
+                    old_value,
+                    new_value: _,
                 } => {
-                    needs_evaluation = true;
-                    // inserts an empty column
-                    self.model.insert_columns(*sheet, *column, 1)?;
-                    // puts all the data back
-                    let worksheet = self.model.workbook.worksheet_mut(*sheet)?;
-                    for (row, cell) in &old_data.data {
-                        worksheet.update_cell(*row, *column, cell.clone())?;
-                    }
-                    // makes sure that the width and style is correct
-                    if let Some(col) = &old_data.column {
-                        let width = col.width * constants::COLUMN_WIDTH_FACTOR;
-                        let style = col.style;
-                        worksheet.set_column_width_and_style(*column, width, style)?;
-                    }
+                    self.model
+                        .update_hyperlink(*sheet, *row, *column, *old_value.clone())?;
                 }
-                Diff::SetFrozenRowsCount {
+                Diff::SetSheetProtection {
                     sheet,
-                    new_value: _,
                     old_value,
-                } => self.model.set_frozen_rows(*sheet, *old_value)?,
-                Diff::SetFrozenColumnsCount {
-                    sheet,
                     new_value: _,
+                } => {
+                    self.model.workbook.worksheet_mut(*sheet)?.protection = *old_value.clone();
+                }
+                Diff::SetWorkbookProtection {
                     old_value,
-                } => self.model.set_frozen_columns(*sheet, *old_value)?,
-                Diff::DeleteSheet { sheet: _ } => {
-                    // do nothing
+                    new_value: _,
+                } => {
+                    self.model.workbook.protection = *old_value.clone();
                 }
-                Diff::NewSheet { index, name: _ } => {
-                    self.model.delete_sheet(*index)?;
+                Diff::SetAutoFilter {
+                    sheet,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.set_auto_filter(*sheet, *old_value.clone())?;
                 }
-                Diff::RenameSheet {
-                    index,
+                Diff::SetTableFilter {
+                    table,
                     old_value,
                     new_value: _,
                 } => {
-                    self.model.rename_sheet_by_index(*index, old_value)?;
+                    self.model.set_table_filter(table, old_value.clone())?;
                 }
-                Diff::SetSheetColor {
-                    index,
+                Diff::SetTable {
+                    name,
                     old_value,
                     new_value: _,
                 } => {
-                    self.model.set_sheet_color(*index, old_value)?;
+                    self.model.set_table(name, *old_value.clone())?;
                 }
-                Diff::SetShowGridLines {
+                Diff::RenameTableReferences {
+                    name,
+                    new_name,
+                    column,
+                } => {
+                    needs_evaluation = true;
+                    let column = column
+                        .as_ref()
+                        .map(|(old_column, new_column)| (new_column.as_str(), old_column.as_str()));
+                    self.model.rename_table_references(new_name, name, column);
+                }
+                Diff::SetPivotTable {
                     sheet,
+                    name,
                     old_value,
                     new_value: _,
                 } => {
-                    self.model.set_show_grid_lines(*sheet, *old_value)?;
+                    self.model
+                        .set_pivot_table(*sheet, name, *old_value.clone())?;
+                }
+                Diff::MoveRows {
+                    sheet, old_data, ..
+                }
+                | Diff::MoveColumns {
+                    sheet, old_data, ..
+                }
+                | Diff::MoveRange {
+                    sheet, old_data, ..
+                }
+                | Diff::SetRowsOrder {
+                    sheet, old_data, ..
+                } => {
+                    needs_evaluation = true;
+                    self.restore_move_data(*sheet, old_data)?;
                 }
             }
         }
@@ -1748,6 +5680,17 @@ impl UserModel {
                     self.model
                         .set_user_input(*sheet, *row, *column, new_value.to_string())?;
                 }
+                Diff::SetCellRichText {
+                    sheet,
+                    row,
+                    column,
+                    new_value,
+                    old_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .set_cell_rich_text(*sheet, *row, *column, new_value.clone())?;
+                }
                 Diff::SetColumnWidth {
                     sheet,
                     column,
@@ -1764,6 +5707,52 @@ impl UserModel {
                 } => {
                     self.model.set_row_height(*sheet, *row, *new_value)?;
                 }
+                Diff::SetRowHidden {
+                    sheet,
+                    row,
+                    new_value,
+                    old_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_row_hidden(*sheet, *row, *new_value)?;
+                }
+                Diff::SetColumnHidden {
+                    sheet,
+                    column,
+                    new_value,
+                    old_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_column_hidden(*sheet, *column, *new_value)?;
+                }
+                Diff::SetRowOutlineLevel {
+                    sheet,
+                    row,
+                    new_value,
+                    old_value: _,
+                } => self.model.set_row_outline_level(*sheet, *row, *new_value)?,
+                Diff::SetColumnOutlineLevel {
+                    sheet,
+                    column,
+                    new_value,
+                    old_value: _,
+                } => self
+                    .model
+                    .set_column_outline_level(*sheet, *column, *new_value)?,
+                Diff::SetRowCollapsed {
+                    sheet,
+                    row,
+                    new_value,
+                    old_value: _,
+                } => self.model.set_row_collapsed(*sheet, *row, *new_value)?,
+                Diff::SetColumnCollapsed {
+                    sheet,
+                    column,
+                    new_value,
+                    old_value: _,
+                } => self
+                    .model
+                    .set_column_collapsed(*sheet, *column, *new_value)?,
                 Diff::CellClearContents {
                     sheet,
                     row,
@@ -1792,6 +5781,16 @@ impl UserModel {
                 } => self
                     .model
                     .set_cell_style(*sheet, *row, *column, new_value)?,
+                Diff::ApplyNamedStyle {
+                    sheet,
+                    row,
+                    column,
+                    style_name,
+                    old_value: _,
+                    old_style_name: _,
+                } => self
+                    .model
+                    .apply_named_style(*sheet, *row, *column, style_name)?,
                 Diff::InsertRow { sheet, row } => {
                     self.model.insert_rows(*sheet, *row, 1)?;
                     needs_evaluation = true;
@@ -1837,6 +5836,25 @@ impl UserModel {
                 } => {
                     self.model.rename_sheet_by_index(*index, new_value)?;
                 }
+                Diff::MoveSheet { sheet, new_index } => {
+                    self.model.move_sheet(*sheet, *new_index)?;
+                }
+                Diff::DuplicateSheet { sheet } => {
+                    self.model.duplicate_sheet(*sheet)?;
+                }
+                Diff::CopySheet {
+                    index,
+                    worksheet,
+                    tables,
+                    defined_names,
+                } => {
+                    self.model.insert_worksheet(
+                        *index,
+                        worksheet.as_ref().clone(),
+                        tables.clone(),
+                        defined_names.clone(),
+                    )?;
+                }
                 Diff::SetSheetColor {
                     index,
                     old_value: _,
@@ -1851,6 +5869,245 @@ impl UserModel {
                 } => {
                     self.model.set_show_grid_lines(*sheet, *new_value)?;
                 }
+                Diff::SetDate1904 {
+                    old_value: _,
+                    new_value,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_date_1904(*new_value);
+                }
+                Diff::SetPrecisionAsDisplayed {
+                    old_value: _,
+                    new_value,
+                } => {
+                    needs_evaluation = true;
+                    self.model.set_precision_as_displayed(*new_value);
+                }
+                Diff::SetCalcMode {
+                    old_value: _,
+                    new_value,
+                } => {
+                    // Only evaluate if there are pending changes
+                    needs_evaluation |= !self.stale_sheets.is_empty();
+                    self.model.set_calc_mode(*new_value);
+                }
+                Diff::CreateNamedStyle { style_name, style } => match style {
+                    Some(style) => self.model.create_named_style(style_name, style)?,
+                    None => {
+                        self.model.add_builtin_named_style(style_name)?;
+                    }
+                },
+                Diff::SetNamedStyle {
+                    style_name,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.set_named_style(style_name, new_value)?;
+                }
+                Diff::SetTheme {
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.set_theme(new_value)?;
+                }
+                Diff::AddConditionalFormat {
+                    sheet,
+                    index,
+                    value,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .insert_conditional_format(*sheet, *index, *value.clone())?;
+                }
+                Diff::SetConditionalFormat {
+                    sheet,
+                    index,
+                    old_value: _,
+                    new_value,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .set_conditional_format(*sheet, *index, *new_value.clone())?;
+                }
+                Diff::DeleteConditionalFormat {
+                    sheet,
+                    index,
+                    old_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model.delete_conditional_format(*sheet, *index)?;
+                }
+                Diff::AddDataValidation {
+                    sheet,
+                    index,
+                    value,
+                } => {
+                    self.model
+                        .insert_data_validation(*sheet, *index, *value.clone())?;
+                }
+                Diff::SetDataValidation {
+                    sheet,
+                    index,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model
+                        .set_data_validation(*sheet, *index, *new_value.clone())?;
+                }
+                Diff::DeleteDataValidation {
+                    sheet,
+                    index,
+                    old_value: _,
+                } => {
+                    self.model.delete_data_validation(*sheet, *index)?;
+                }
+                Diff::MergeCells { sheet, range } => {
+                    self.model.merge_cells(*sheet, range)?;
+                }
+                Diff::UnmergeCells { sheet, range } => {
+                    self.model.unmerge_cells(*sheet, range)?;
+                }
+                Diff::AddComment {
+                    sheet,
+                    row,
+                    column,
+                    value: new_value,
+                }
+                | Diff::SetComment {
+                    sheet,
+                    row,
+                    column,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model
+                        .set_comment(*sheet, *row, *column, Some(*new_value.clone()))?;
+                }
+                Diff::DeleteComment {
+                    sheet,
+                    row,
+                    column,
+                    old_value: _,
+                } => {
+                    self.model.set_comment(*sheet, *row, *column, None)?;
+                }
+                Diff::SetHyperlink {
+                    sheet,
+                    row,
+                    column,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model
+                        .update_hyperlink(*sheet, *row, *column, *new_value.clone())?;
+                }
+                Diff::SetSheetProtection {
+                    sheet,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.workbook.worksheet_mut(*sheet)?.protection = *new_value.clone();
+                }
+                Diff::SetWorkbookProtection {
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.workbook.protection = *new_value.clone();
+                }
+                Diff::SetAutoFilter {
+                    sheet,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.set_auto_filter(*sheet, *new_value.clone())?;
+                }
+                Diff::SetTableFilter {
+                    table,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.set_table_filter(table, new_value.clone())?;
+                }
+                Diff::SetTable {
+                    name,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.set_table(name, *new_value.clone())?;
+                }
+                Diff::RenameTableReferences {
+                    name,
+                    new_name,
+                    column,
+                } => {
+                    needs_evaluation = true;
+                    let column = column
+                        .as_ref()
+                        .map(|(old_column, new_column)| (old_column.as_str(), new_column.as_str()));
+                    self.model.rename_table_references(name, new_name, column);
+                }
+                Diff::SetPivotTable {
+                    sheet,
+                    name,
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model
+                        .set_pivot_table(*sheet, name, *new_value.clone())?;
+                }
+                Diff::MoveRows {
+                    sheet,
+                    row,
+                    row_count,
+                    delta,
+                    old_data: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .move_rows_action(*sheet, *row, *row_count, *delta)?;
+                }
+                Diff::MoveColumns {
+                    sheet,
+                    column,
+                    column_count,
+                    delta,
+                    old_data: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .move_columns_action(*sheet, *column, *column_count, *delta)?;
+                }
+                Diff::MoveRange {
+                    sheet,
+                    row,
+                    column,
+                    width,
+                    height,
+                    target_row,
+                    target_column,
+                    old_data: _,
+                } => {
+                    needs_evaluation = true;
+                    let area = Area {
+                        sheet: *sheet,
+                        row: *row,
+                        column: *column,
+                        width: *width,
+                        height: *height,
+                    };
+                    self.model
+                        .move_range_action(&area, *target_row, *target_column)?;
+                }
+                Diff::SetRowsOrder {
+                    sheet,
+                    range,
+                    source_rows,
+                    old_data: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .set_rows_order_action(*sheet, *range, source_rows)?;
+                }
             }
         }
 
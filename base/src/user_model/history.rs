@@ -2,18 +2,66 @@ use std::collections::HashMap;
 
 use bitcode::{Decode, Encode};
 
-use crate::types::{Cell, Col, Row, Style};
+use crate::types::{
+    AutoFilter, CalcMode, Cell, Col, Comment, ConditionalFormat, DataValidation, DefinedName,
+    FilterColumn, Hyperlink, PivotTable, Row, SheetProtection, Style, Table, TextRun, Theme,
+    WorkbookProtection, Worksheet,
+};
 
 #[derive(Clone, Encode, Decode)]
 pub(crate) struct RowData {
     pub(crate) row: Option<Row>,
     pub(crate) data: HashMap<i32, Cell>,
+    pub(crate) comments: Vec<Comment>,
+    pub(crate) hyperlinks: Vec<Hyperlink>,
+    /// The tables of the sheet before deleting
+    pub(crate) tables: Vec<Table>,
+    /// The conditional formats of every sheet before deleting
+    pub(crate) conditional_formats: Vec<Vec<ConditionalFormat>>,
+    /// The data validations of every sheet before deleting
+    pub(crate) data_validations: Vec<Vec<DataValidation>>,
+    /// The pivot tables of every sheet before deleting
+    pub(crate) pivot_tables: Vec<Vec<PivotTable>>,
 }
 
 #[derive(Clone, Encode, Decode)]
 pub(crate) struct ColumnData {
     pub(crate) column: Option<Col>,
     pub(crate) data: HashMap<i32, Cell>,
+    pub(crate) comments: Vec<Comment>,
+    pub(crate) hyperlinks: Vec<Hyperlink>,
+    /// The tables of the sheet before deleting
+    pub(crate) tables: Vec<Table>,
+    /// The conditional formats of every sheet before deleting
+    pub(crate) conditional_formats: Vec<Vec<ConditionalFormat>>,
+    /// The data validations of every sheet before deleting
+    pub(crate) data_validations: Vec<Vec<DataValidation>>,
+    /// The pivot tables of every sheet before deleting
+    pub(crate) pivot_tables: Vec<Vec<PivotTable>>,
+}
+
+/// The parts of a sheet that change when moving rows, columns or a range, or sorting rows
+#[derive(Clone, Encode, Decode)]
+pub(crate) struct MoveData {
+    /// The range (row1, column1, row2, column2) with all the cells that move
+    pub(crate) range: (i32, i32, i32, i32),
+    /// The cells in the range before moving
+    pub(crate) cells: Vec<(i32, i32, Cell)>,
+    /// The formulas outside the range that changed, with their sheet, row and column
+    pub(crate) formulas: Vec<(u32, i32, i32, String)>,
+    pub(crate) comments: Vec<Comment>,
+    pub(crate) hyperlinks: Vec<Hyperlink>,
+    pub(crate) merge_cells: Vec<String>,
+    pub(crate) rows: Vec<Row>,
+    pub(crate) cols: Vec<Col>,
+    pub(crate) tables: Vec<Table>,
+    pub(crate) auto_filter: Option<AutoFilter>,
+    /// The conditional formats of every sheet before moving
+    pub(crate) conditional_formats: Vec<Vec<ConditionalFormat>>,
+    /// The data validations of every sheet before moving
+    pub(crate) data_validations: Vec<Vec<DataValidation>>,
+    /// The pivot tables of every sheet before moving
+    pub(crate) pivot_tables: Vec<Vec<PivotTable>>,
 }
 
 #[derive(Clone, Encode, Decode)]
@@ -26,6 +74,13 @@ pub(crate) enum Diff {
         new_value: String,
         old_value: Box<Option<Cell>>,
     },
+    SetCellRichText {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        new_value: Vec<TextRun>,
+        old_value: Box<Option<Cell>>,
+    },
     CellClearContents {
         sheet: u32,
         row: i32,
@@ -46,6 +101,15 @@ pub(crate) enum Diff {
         old_value: Box<Style>,
         new_value: Box<Style>,
     },
+    /// The cell had the `old_value` style based on the named style `old_style_name`
+    ApplyNamedStyle {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        style_name: String,
+        old_value: Box<Style>,
+        old_style_name: String,
+    },
     // Column and Row diffs
     SetColumnWidth {
         sheet: u32,
@@ -59,6 +123,42 @@ pub(crate) enum Diff {
         new_value: f64,
         old_value: f64,
     },
+    SetRowHidden {
+        sheet: u32,
+        row: i32,
+        old_value: bool,
+        new_value: bool,
+    },
+    SetColumnHidden {
+        sheet: u32,
+        column: i32,
+        old_value: bool,
+        new_value: bool,
+    },
+    SetRowOutlineLevel {
+        sheet: u32,
+        row: i32,
+        old_value: u8,
+        new_value: u8,
+    },
+    SetColumnOutlineLevel {
+        sheet: u32,
+        column: i32,
+        old_value: u8,
+        new_value: u8,
+    },
+    SetRowCollapsed {
+        sheet: u32,
+        row: i32,
+        old_value: bool,
+        new_value: bool,
+    },
+    SetColumnCollapsed {
+        sheet: u32,
+        column: i32,
+        old_value: bool,
+        new_value: bool,
+    },
     InsertRow {
         sheet: u32,
         row: i32,
@@ -99,6 +199,20 @@ pub(crate) enum Diff {
         old_value: String,
         new_value: String,
     },
+    MoveSheet {
+        sheet: u32,
+        new_index: u32,
+    },
+    DuplicateSheet {
+        sheet: u32,
+    },
+    /// A sheet copied from another workbook
+    CopySheet {
+        index: u32,
+        worksheet: Box<Worksheet>,
+        tables: Vec<Table>,
+        defined_names: Vec<DefinedName>,
+    },
     SetSheetColor {
         index: u32,
         old_value: String,
@@ -108,6 +222,165 @@ pub(crate) enum Diff {
         sheet: u32,
         old_value: bool,
         new_value: bool,
+    },
+    SetDate1904 {
+        old_value: bool,
+        new_value: bool,
+    },
+    SetPrecisionAsDisplayed {
+        old_value: bool,
+        new_value: bool,
+    },
+    SetCalcMode {
+        old_value: CalcMode,
+        new_value: CalcMode,
+    },
+    /// Adds a named style, or a built-in style if `style` is None
+    CreateNamedStyle {
+        style_name: String,
+        style: Option<Box<Style>>,
+    },
+    SetNamedStyle {
+        style_name: String,
+        old_value: Box<Style>,
+        new_value: Box<Style>,
+    },
+    SetTheme {
+        old_value: Box<Theme>,
+        new_value: Box<Theme>,
+    },
+    AddConditionalFormat {
+        sheet: u32,
+        index: usize,
+        value: Box<ConditionalFormat>,
+    },
+    SetConditionalFormat {
+        sheet: u32,
+        index: usize,
+        old_value: Box<ConditionalFormat>,
+        new_value: Box<ConditionalFormat>,
+    },
+    DeleteConditionalFormat {
+        sheet: u32,
+        index: usize,
+        old_value: Box<ConditionalFormat>,
+    },
+    AddDataValidation {
+        sheet: u32,
+        index: usize,
+        value: Box<DataValidation>,
+    },
+    SetDataValidation {
+        sheet: u32,
+        index: usize,
+        old_value: Box<DataValidation>,
+        new_value: Box<DataValidation>,
+    },
+    DeleteDataValidation {
+        sheet: u32,
+        index: usize,
+        old_value: Box<DataValidation>,
+    },
+    MergeCells {
+        sheet: u32,
+        range: String,
+    },
+    UnmergeCells {
+        sheet: u32,
+        range: String,
+    },
+    AddComment {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        value: Box<Comment>,
+    },
+    SetComment {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        old_value: Box<Comment>,
+        new_value: Box<Comment>,
+    },
+    DeleteComment {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        old_value: Box<Comment>,
+    },
+    SetHyperlink {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        old_value: Box<Option<Hyperlink>>,
+        new_value: Box<Option<Hyperlink>>,
+    },
+    SetSheetProtection {
+        sheet: u32,
+        old_value: Box<Option<SheetProtection>>,
+        new_value: Box<Option<SheetProtection>>,
+    },
+    SetWorkbookProtection {
+        old_value: Box<Option<WorkbookProtection>>,
+        new_value: Box<Option<WorkbookProtection>>,
+    },
+    SetAutoFilter {
+        sheet: u32,
+        old_value: Box<Option<AutoFilter>>,
+        new_value: Box<Option<AutoFilter>>,
+    },
+    SetTableFilter {
+        table: String,
+        old_value: Vec<FilterColumn>,
+        new_value: Vec<FilterColumn>,
+    },
+    SetTable {
+        name: String,
+        old_value: Box<Option<Table>>,
+        new_value: Box<Option<Table>>,
+    },
+    /// The table `name` is renamed to `new_name` and, if given, its column (old name, new name)
+    /// in the formulas kept as text
+    RenameTableReferences {
+        name: String,
+        new_name: String,
+        column: Option<(String, String)>,
+    },
+    SetPivotTable {
+        sheet: u32,
+        name: String,
+        old_value: Box<Option<PivotTable>>,
+        new_value: Box<Option<PivotTable>>,
+    },
+    MoveRows {
+        sheet: u32,
+        row: i32,
+        row_count: i32,
+        delta: i32,
+        old_data: Box<MoveData>,
+    },
+    MoveColumns {
+        sheet: u32,
+        column: i32,
+        column_count: i32,
+        delta: i32,
+        old_data: Box<MoveData>,
+    },
+    MoveRange {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        width: i32,
+        height: i32,
+        target_row: i32,
+        target_column: i32,
+        old_data: Box<MoveData>,
+    },
+    SetRowsOrder {
+        sheet: u32,
+        range: (i32, i32, i32, i32),
+        source_rows: Vec<i32>,
+        old_data: Box<MoveData>,
     }, // FIXME: we are missing SetViewDiffs
 }
 
@@ -11,3 +11,6 @@ pub use ui::SelectedView;
 
 pub use common::BorderArea;
 pub use common::ClipboardData;
+pub use common::PasteMode;
+pub use common::PasteOperation;
+pub use common::PasteOptions;
@@ -2,7 +2,14 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::expressions::utils::{is_valid_column_number, is_valid_row};
+use crate::{
+    expressions::{
+        types::CellReferenceIndex,
+        utils::{is_valid_column_number, is_valid_row},
+    },
+    find::get_find_regex,
+    types::{FindOptions, FindWithin, SearchOrder, SheetState},
+};
 
 use super::common::UserModel;
 
@@ -84,7 +91,8 @@ impl UserModel {
         Ok(())
     }
 
-    /// Sets the selected cell for the current view. Note that this also sets the selected range
+    /// Sets the selected cell for the current view. Note that this also sets the selected range.
+    /// If the cell is part of a merged area the whole area is selected.
     pub fn set_selected_cell(&mut self, row: i32, column: i32) -> Result<(), String> {
         let sheet = if let Some(view) = self.model.workbook.views.get(&self.model.view_id) {
             view.sheet
@@ -100,17 +108,19 @@ impl UserModel {
         if self.model.workbook.worksheet(sheet).is_err() {
             return Err(format!("Invalid worksheet index {}", sheet));
         }
+        let range = self.get_merged_range(sheet, row, column)?;
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&0) {
-                view.row = row;
-                view.column = column;
-                view.range = [row, column, row, column];
+                view.row = range[0];
+                view.column = range[1];
+                view.range = range;
             }
         }
         Ok(())
     }
 
     /// Sets the selected range. Note that the selected cell must be in one of the corners.
+    /// The range is expanded so that it doesn't cut through any merged area.
     pub fn set_selected_range(
         &mut self,
         start_row: i32,
@@ -137,27 +147,36 @@ impl UserModel {
         if !is_valid_row(end_row) {
             return Err(format!("Invalid row: '{end_row}'"));
         }
-        if self.model.workbook.worksheet(sheet).is_err() {
-            return Err(format!("Invalid worksheet index {}", sheet));
-        }
+        let worksheet = match self.model.workbook.worksheet(sheet) {
+            Ok(worksheet) => worksheet,
+            Err(_) => return Err(format!("Invalid worksheet index {}", sheet)),
+        };
+        let (selected_row, selected_column) = match worksheet.views.get(&0) {
+            Some(view) => (view.row, view.column),
+            None => return Ok(()),
+        };
+        // The selected cell (or the merged area it belongs to) must be on one of the corners of the selected range:
+        let [row1, column1, row2, column2] =
+            self.get_merged_range(sheet, selected_row, selected_column)?;
+        let is_in_area = |start: i32, end: i32, value: i32| (start..=end).contains(&value);
+        if !is_in_area(row1, row2, start_row) && !is_in_area(row1, row2, end_row) {
+            return Err(format!(
+                "The selected cells is not in one of the corners. Row: '{}' and row range '({}, {})'",
+                selected_row, start_row, end_row
+            ));
+        }
+        if !is_in_area(column1, column2, start_column) && !is_in_area(column1, column2, end_column)
+        {
+            return Err(format!(
+                "The selected cells is not in one of the corners. Column '{}' and column range '({}, {})'",
+                selected_column, start_column, end_column
+            ));
+        }
+        let range = self
+            .expand_range_to_merged_cells(sheet, [start_row, start_column, end_row, end_column])?;
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&0) {
-                let selected_row = view.row;
-                let selected_column = view.column;
-                // The selected cells must be on one of the corners of the selected range:
-                if selected_row != start_row && selected_row != end_row {
-                    return Err(format!(
-                        "The selected cells is not in one of the corners. Row: '{}' and row range '({}, {})'",
-                        selected_row, start_row, end_row
-                    ));
-                }
-                if selected_column != start_column && selected_column != end_column {
-                    return Err(format!(
-                        "The selected cells is not in one of the corners. Column '{}' and column range '({}, {})'",
-                        selected_column, start_column, end_column
-                    ));
-                }
-                view.range = [start_row, start_column, end_row, end_column];
+                view.range = range;
             }
         }
         Ok(())
@@ -192,35 +211,38 @@ impl UserModel {
                 return Ok(());
             };
         let [row_start, column_start, row_end, column_end] = range;
+        // If the selected cell is merged we need the whole merged area
+        let [selected_row_start, selected_column_start, selected_row_end, selected_column_end] =
+            self.get_merged_range(sheet, selected_row, selected_column)?;
 
         match key {
             "ArrowRight" => {
-                if selected_column > column_start {
+                if selected_column_start > column_start {
                     let new_column = column_start + 1;
                     if !(is_valid_column_number(new_column)) {
                         return Ok(());
                     }
                     self.set_selected_range(row_start, new_column, row_end, column_end)?;
                 } else {
-                    let new_column = column_end + 1;
-                    if !is_valid_column_number(new_column) {
-                        return Ok(());
-                    }
+                    let new_column = match self.get_visible_column(sheet, column_end + 1, 1)? {
+                        Some(column) => column,
+                        None => return Ok(()),
+                    };
                     // if the column is not fully visible we 'scroll' right until it is
                     let mut width = 0.0;
                     let mut c = left_column;
                     while c <= new_column {
-                        width += self.model.get_column_width(sheet, c)?;
+                        width += self.get_visible_column_width(sheet, c)?;
                         c += 1;
                     }
                     if width > window_width {
                         self.set_top_left_visible_cell(top_row, left_column + 1)?;
                     }
-                    self.set_selected_range(row_start, column_start, row_end, column_end + 1)?;
+                    self.set_selected_range(row_start, column_start, row_end, new_column)?;
                 }
             }
             "ArrowLeft" => {
-                if selected_column < column_end {
+                if selected_column_end < column_end {
                     let new_column = column_end - 1;
                     if !is_valid_column_number(new_column) {
                         return Ok(());
@@ -230,10 +252,10 @@ impl UserModel {
                     }
                     self.set_selected_range(row_start, column_start, row_end, new_column)?;
                 } else {
-                    let new_column = column_start - 1;
-                    if !is_valid_column_number(new_column) {
-                        return Ok(());
-                    }
+                    let new_column = match self.get_visible_column(sheet, column_start - 1, -1)? {
+                        Some(column) => column,
+                        None => return Ok(()),
+                    };
                     if new_column < left_column {
                         self.set_top_left_visible_cell(top_row, new_column)?;
                     }
@@ -241,17 +263,17 @@ impl UserModel {
                 }
             }
             "ArrowUp" => {
-                if selected_row < row_end {
+                if selected_row_end < row_end {
                     let new_row = row_end - 1;
                     if !is_valid_row(new_row) {
                         return Ok(());
                     }
                     self.set_selected_range(row_start, column_start, new_row, column_end)?;
                 } else {
-                    let new_row = row_start - 1;
-                    if !is_valid_row(new_row) {
-                        return Ok(());
-                    }
+                    let new_row = match self.get_visible_row(sheet, row_start - 1, -1)? {
+                        Some(row) => row,
+                        None => return Ok(()),
+                    };
                     if new_row < top_row {
                         self.set_top_left_visible_cell(new_row, left_column)?;
                     }
@@ -259,21 +281,21 @@ impl UserModel {
                 }
             }
             "ArrowDown" => {
-                if selected_row > row_start {
+                if selected_row_start > row_start {
                     let new_row = row_start + 1;
                     if !is_valid_row(new_row) {
                         return Ok(());
                     }
                     self.set_selected_range(new_row, column_start, row_end, column_end)?;
                 } else {
-                    let new_row = row_end + 1;
-                    if !is_valid_row(new_row) {
-                        return Ok(());
-                    }
+                    let new_row = match self.get_visible_row(sheet, row_end + 1, 1)? {
+                        Some(row) => row,
+                        None => return Ok(()),
+                    };
                     let mut height = 0.0;
                     let mut r = top_row;
                     while r <= new_row + 1 {
-                        height += self.model.get_row_height(sheet, r)?;
+                        height += self.get_visible_row_height(sheet, r)?;
                         r += 1;
                     }
                     if height >= window_height {
@@ -364,21 +386,26 @@ impl UserModel {
             Some(s) => s,
             None => return Err("View not found".to_string()),
         };
-        let new_column = view.column + 1;
-        if !is_valid_column_number(new_column) {
-            return Ok(());
-        }
+        // We jump over the merged area of the selected cell
+        let [_, _, _, last_column] = self.get_merged_range(sheet, view.row, view.column)?;
+        // Hidden columns are skipped
+        let new_column = match self.get_visible_column(sheet, last_column + 1, 1)? {
+            Some(column) => column,
+            None => return Ok(()),
+        };
+        let range = self.get_merged_range(sheet, view.row, new_column)?;
         // if the column is not fully visible we 'scroll' right until it is
         let mut width = 0.0;
         let mut column = view.left_column;
         while column <= new_column {
-            width += self.model.get_column_width(sheet, column)?;
+            width += self.get_visible_column_width(sheet, column)?;
             column += 1;
         }
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&self.model.view_id) {
-                view.column = new_column;
-                view.range = [view.row, new_column, view.row, new_column];
+                view.row = range[0];
+                view.column = range[1];
+                view.range = range;
                 if width > window_width as f64 {
                     view.left_column += 1;
                 }
@@ -402,17 +429,19 @@ impl UserModel {
             Some(s) => s,
             None => return Err("View not found".to_string()),
         };
-        let new_column = view.column - 1;
-        if !is_valid_column_number(new_column) {
-            return Ok(());
-        }
+        let new_column = match self.get_visible_column(sheet, view.column - 1, -1)? {
+            Some(column) => column,
+            None => return Ok(()),
+        };
+        let range = self.get_merged_range(sheet, view.row, new_column)?;
         // if the column is not fully visible we 'scroll' right until it is
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&self.model.view_id) {
-                view.column = new_column;
-                view.range = [view.row, new_column, view.row, new_column];
-                if new_column < view.left_column {
-                    view.left_column = new_column;
+                view.row = range[0];
+                view.column = range[1];
+                view.range = range;
+                if view.column < view.left_column {
+                    view.left_column = view.column;
                 }
             }
         }
@@ -434,17 +463,19 @@ impl UserModel {
             Some(s) => s,
             None => return Err("View not found".to_string()),
         };
-        let new_row = view.row - 1;
-        if !is_valid_row(new_row) {
-            return Ok(());
-        }
+        let new_row = match self.get_visible_row(sheet, view.row - 1, -1)? {
+            Some(row) => row,
+            None => return Ok(()),
+        };
+        let range = self.get_merged_range(sheet, new_row, view.column)?;
         // if the column is not fully visible we 'scroll' right until it is
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&self.model.view_id) {
-                view.row = new_row;
-                view.range = [new_row, view.column, new_row, view.column];
-                if new_row < view.top_row {
-                    view.top_row = new_row;
+                view.row = range[0];
+                view.column = range[1];
+                view.range = range;
+                if view.row < view.top_row {
+                    view.top_row = view.row;
                 }
             }
         }
@@ -467,21 +498,26 @@ impl UserModel {
             Some(s) => s,
             None => return Err("View not found".to_string()),
         };
-        let new_row = view.row + 1;
-        if !is_valid_row(new_row) {
-            return Ok(());
-        }
+        // We jump over the merged area of the selected cell
+        let [_, _, last_row, _] = self.get_merged_range(sheet, view.row, view.column)?;
+        // Hidden rows are skipped
+        let new_row = match self.get_visible_row(sheet, last_row + 1, 1)? {
+            Some(row) => row,
+            None => return Ok(()),
+        };
+        let range = self.get_merged_range(sheet, new_row, view.column)?;
         // if the row is not fully visible we 'scroll' down until it is
         let mut height = 0.0;
         let mut row = view.top_row;
         while row <= new_row + 1 {
-            height += self.model.get_row_height(sheet, row)?;
+            height += self.get_visible_row_height(sheet, row)?;
             row += 1;
         }
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&self.model.view_id) {
-                view.row = new_row;
-                view.range = [new_row, view.column, new_row, view.column];
+                view.row = range[0];
+                view.column = range[1];
+                view.range = range;
                 if height > window_height as f64 {
                     view.top_row += 1;
                 }
@@ -508,7 +544,7 @@ impl UserModel {
         };
         let mut scroll_x = 0.0;
         for column in 1..view.left_column {
-            scroll_x += self.model.get_column_width(sheet, column)?;
+            scroll_x += self.get_visible_column_width(sheet, column)?;
         }
         Ok(scroll_x)
     }
@@ -531,7 +567,7 @@ impl UserModel {
         };
         let mut scroll_y = 0.0;
         for row in 1..view.top_row {
-            scroll_y += self.model.get_row_height(sheet, row)?;
+            scroll_y += self.get_visible_row_height(sheet, row)?;
         }
         Ok(scroll_y)
     }
@@ -554,20 +590,26 @@ impl UserModel {
             None => return Err("View not found".to_string()),
         };
         let mut last_row = view.top_row;
-        let mut height = self.model.get_row_height(sheet, last_row)?;
+        let mut height = self.get_visible_row_height(sheet, last_row)?;
         while height <= window_height as f64 {
             last_row += 1;
-            height += self.model.get_row_height(sheet, last_row)?;
+            height += self.get_visible_row_height(sheet, last_row)?;
         }
         if !is_valid_row(last_row) {
             return Ok(());
         }
         let row_delta = view.row - view.top_row;
+        let new_row = match self.get_visible_row(sheet, last_row + row_delta, 1)? {
+            Some(row) => row,
+            None => return Ok(()),
+        };
+        let range = self.get_merged_range(sheet, new_row, view.column)?;
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&self.model.view_id) {
                 view.top_row = last_row;
-                view.row = view.top_row + row_delta;
-                view.range = [view.row, view.column, view.row, view.column];
+                view.row = range[0];
+                view.column = range[1];
+                view.range = range;
             }
         }
         Ok(())
@@ -591,18 +633,27 @@ impl UserModel {
         };
 
         let mut first_row = view.top_row;
-        let mut height = self.model.get_row_height(sheet, first_row)?;
+        let mut height = self.get_visible_row_height(sheet, first_row)?;
         while height <= window_height && first_row > 1 {
             first_row -= 1;
-            height += self.model.get_row_height(sheet, first_row)?;
+            height += self.get_visible_row_height(sheet, first_row)?;
         }
 
         let row_delta = view.row - view.top_row;
+        let new_row = match self.get_visible_row(sheet, first_row + row_delta, -1)? {
+            Some(row) => row,
+            None => match self.get_visible_row(sheet, first_row + row_delta, 1)? {
+                Some(row) => row,
+                None => return Ok(()),
+            },
+        };
+        let range = self.get_merged_range(sheet, new_row, view.column)?;
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&self.model.view_id) {
                 view.top_row = first_row;
-                view.row = view.top_row + row_delta;
-                view.range = [view.row, view.column, view.row, view.column];
+                view.row = range[0];
+                view.column = range[1];
+                view.range = range;
             }
         }
         Ok(())
@@ -643,12 +694,12 @@ impl UserModel {
             let mut width = 0.0;
             let mut column = left_column;
             while column <= target_column {
-                width += self.model.get_column_width(sheet, column)?;
+                width += self.get_visible_column_width(sheet, column)?;
                 column += 1;
             }
 
             while width > window_width {
-                width -= self.model.get_column_width(sheet, new_left_column)?;
+                width -= self.get_visible_column_width(sheet, new_left_column)?;
                 new_left_column += 1;
             }
         } else if target_column < new_left_column {
@@ -659,20 +710,24 @@ impl UserModel {
             let mut height = 0.0;
             let mut row = top_row;
             while row <= target_row {
-                height += self.model.get_row_height(sheet, row)?;
+                height += self.get_visible_row_height(sheet, row)?;
                 row += 1;
             }
             while height > window_height {
-                height -= self.model.get_row_height(sheet, new_top_row)?;
+                height -= self.get_visible_row_height(sheet, new_top_row)?;
                 new_top_row += 1;
             }
         } else if target_row < new_top_row {
             new_top_row = target_row;
         }
 
+        let range = self.expand_range_to_merged_cells(
+            sheet,
+            [row_start, column_start, target_row, target_column],
+        )?;
         if let Ok(worksheet) = self.model.workbook.worksheet_mut(sheet) {
             if let Some(view) = worksheet.views.get_mut(&self.model.view_id) {
-                view.range = [row_start, column_start, target_row, target_column];
+                view.range = range;
                 if new_top_row != top_row {
                     view.top_row = new_top_row;
                 }
@@ -684,4 +739,193 @@ impl UserModel {
 
         Ok(())
     }
+
+    /// Returns the height of the row as displayed, 0 if it is hidden
+    fn get_visible_row_height(&self, sheet: u32, row: i32) -> Result<f64, String> {
+        if self.model.is_row_hidden(sheet, row)? {
+            return Ok(0.0);
+        }
+        self.model.get_row_height(sheet, row)
+    }
+
+    /// Returns the width of the column as displayed, 0 if it is hidden
+    fn get_visible_column_width(&self, sheet: u32, column: i32) -> Result<f64, String> {
+        if self.model.is_column_hidden(sheet, column)? {
+            return Ok(0.0);
+        }
+        self.model.get_column_width(sheet, column)
+    }
+
+    /// Returns the first row that is not hidden starting at `row` and moving in steps of `delta`
+    fn get_visible_row(&self, sheet: u32, row: i32, delta: i32) -> Result<Option<i32>, String> {
+        let mut row = row;
+        while is_valid_row(row) {
+            if !self.model.is_row_hidden(sheet, row)? {
+                return Ok(Some(row));
+            }
+            row += delta;
+        }
+        Ok(None)
+    }
+
+    /// Returns the first column that is not hidden starting at `column` and moving in steps of `delta`
+    fn get_visible_column(
+        &self,
+        sheet: u32,
+        column: i32,
+        delta: i32,
+    ) -> Result<Option<i32>, String> {
+        let mut column = column;
+        while is_valid_column_number(column) {
+            if !self.model.is_column_hidden(sheet, column)? {
+                return Ok(Some(column));
+            }
+            column += delta;
+        }
+        Ok(None)
+    }
+
+    /// Returns the merged area that contains the cell, or the cell itself if it is not merged
+    /// Returns the sheets and the range [row1, column1, row2, column2] searched with the `options`
+    pub(crate) fn get_find_scope(&self, options: &FindOptions) -> (Vec<u32>, Option<[i32; 4]>) {
+        let view = self.get_selected_view();
+        match options.within {
+            FindWithin::Workbook => {
+                let sheets = self
+                    .model
+                    .workbook
+                    .worksheets
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, worksheet)| worksheet.state == SheetState::Visible)
+                    .map(|(index, _)| index as u32)
+                    .collect();
+                (sheets, None)
+            }
+            FindWithin::Selection => {
+                let [row1, column1, row2, column2] = view.range;
+                let range = if row1 == row2 && column1 == column2 {
+                    None
+                } else {
+                    Some([
+                        row1.min(row2),
+                        column1.min(column2),
+                        row1.max(row2),
+                        column1.max(column2),
+                    ])
+                };
+                (vec![view.sheet], range)
+            }
+            FindWithin::Sheet => (vec![view.sheet], None),
+        }
+    }
+
+    /// Selects the next cell after the selected one that matches `text` with the `options`.
+    /// The search wraps around the end. Returns the cell found, if any.
+    pub fn find_next(
+        &mut self,
+        text: &str,
+        options: &FindOptions,
+    ) -> Result<Option<CellReferenceIndex>, String> {
+        self.find_from_selected_cell(text, options, true)
+    }
+
+    /// Selects the previous cell before the selected one that matches `text` with the `options`.
+    /// The search wraps around the start. Returns the cell found, if any.
+    pub fn find_previous(
+        &mut self,
+        text: &str,
+        options: &FindOptions,
+    ) -> Result<Option<CellReferenceIndex>, String> {
+        self.find_from_selected_cell(text, options, false)
+    }
+
+    fn find_from_selected_cell(
+        &mut self,
+        text: &str,
+        options: &FindOptions,
+        forward: bool,
+    ) -> Result<Option<CellReferenceIndex>, String> {
+        let regex = get_find_regex(text, options)?;
+        let (sheets, range) = self.get_find_scope(options);
+        let cells = self.model.find_cells(&regex, options, &sheets, range)?;
+        let key = |cell: &CellReferenceIndex| {
+            let sheet = sheets.iter().position(|&sheet| sheet == cell.sheet);
+            match options.search_order {
+                SearchOrder::ByRows => (sheet, cell.row, cell.column),
+                SearchOrder::ByColumns => (sheet, cell.column, cell.row),
+            }
+        };
+        let view = self.get_selected_view();
+        let selected = key(&CellReferenceIndex {
+            sheet: view.sheet,
+            row: view.row,
+            column: view.column,
+        });
+        let found = if forward {
+            cells
+                .iter()
+                .find(|cell| key(cell) > selected)
+                .or(cells.first())
+        } else {
+            cells
+                .iter()
+                .rev()
+                .find(|cell| key(cell) < selected)
+                .or(cells.last())
+        }
+        .copied();
+        if let Some(cell) = found {
+            if range.is_some() {
+                // The selected range stays, only the selected cell moves
+                let view_id = self.model.view_id;
+                let worksheet = self.model.workbook.worksheet_mut(cell.sheet)?;
+                if let Some(view) = worksheet.views.get_mut(&view_id) {
+                    view.row = cell.row;
+                    view.column = cell.column;
+                }
+            } else {
+                self.set_selected_sheet(cell.sheet)?;
+                self.set_selected_cell(cell.row, cell.column)?;
+            }
+        }
+        Ok(found)
+    }
+
+    fn get_merged_range(&self, sheet: u32, row: i32, column: i32) -> Result<[i32; 4], String> {
+        Ok(match self.model.get_merged_area(sheet, row, column)? {
+            Some((row1, column1, row2, column2)) => [row1, column1, row2, column2],
+            None => [row, column, row, column],
+        })
+    }
+
+    /// Grows the range so that it doesn't cut through any merged area.
+    /// Note that the start of the range might be after its end.
+    fn expand_range_to_merged_cells(
+        &self,
+        sheet: u32,
+        range: [i32; 4],
+    ) -> Result<[i32; 4], String> {
+        let [start_row, start_column, end_row, end_column] = range;
+        let (row1, column1, row2, column2) = self.model.expand_to_merged_cells(
+            sheet,
+            (
+                start_row.min(end_row),
+                start_column.min(end_column),
+                start_row.max(end_row),
+                start_column.max(end_column),
+            ),
+        )?;
+        let (start_row, end_row) = if start_row <= end_row {
+            (row1, row2)
+        } else {
+            (row2, row1)
+        };
+        let (start_column, end_column) = if start_column <= end_column {
+            (column1, column2)
+        } else {
+            (column2, column1)
+        };
+        Ok([start_row, start_column, end_row, end_column])
+    }
 }
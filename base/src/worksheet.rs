@@ -111,6 +111,9 @@ impl Worksheet {
             width: constants::DEFAULT_COLUMN_WIDTH / constants::COLUMN_WIDTH_FACTOR,
             custom_width: true,
             style: Some(style_index),
+            hidden: false,
+            outline_level: 0,
+            collapsed: false,
         }];
         Ok(())
     }
@@ -135,6 +138,8 @@ impl Worksheet {
             custom_height: true,
             s: style_index,
             hidden: false,
+            outline_level: 0,
+            collapsed: false,
         });
         Ok(())
     }
@@ -275,6 +280,8 @@ impl Worksheet {
             custom_height: true,
             s: 0,
             hidden: false,
+            outline_level: 0,
+            collapsed: false,
         });
         Ok(())
     }
@@ -303,6 +310,9 @@ impl Worksheet {
             width: width / constants::COLUMN_WIDTH_FACTOR,
             custom_width: true,
             style,
+            hidden: false,
+            outline_level: 0,
+            collapsed: false,
         };
         let mut index = 0;
         let mut split = false;
@@ -312,6 +322,7 @@ impl Worksheet {
             if min <= column && column <= max {
                 if min == column && max == column {
                     c.width = width / constants::COLUMN_WIDTH_FACTOR;
+                    c.custom_width = true;
                     return Ok(());
                 }
                 split = true;
@@ -329,18 +340,17 @@ impl Worksheet {
             let pre = Col {
                 min,
                 max: column - 1,
-                width: cols[index].width,
-                custom_width: cols[index].custom_width,
-                style: cols[index].style,
+                ..cols[index].clone()
             };
             let post = Col {
                 min: column + 1,
                 max,
-                width: cols[index].width,
-                custom_width: cols[index].custom_width,
-                style: cols[index].style,
+                ..cols[index].clone()
             };
             col.style = cols[index].style;
+            col.hidden = cols[index].hidden;
+            col.outline_level = cols[index].outline_level;
+            col.collapsed = cols[index].collapsed;
             cols.remove(index);
             if column != max {
                 cols.insert(index, post);
@@ -355,6 +365,122 @@ impl Worksheet {
         Ok(())
     }
 
+    /// Returns the record of `row`, adding one with the default height if there is none
+    pub(crate) fn row_mut(&mut self, row: i32) -> Result<&mut Row, String> {
+        if !is_valid_row(row) {
+            return Err(format!("Row number '{row}' is not valid."));
+        }
+        let index = match self.rows.iter().position(|r| r.r == row) {
+            Some(index) => index,
+            None => {
+                self.rows.push(Row {
+                    r: row,
+                    height: constants::DEFAULT_ROW_HEIGHT / constants::ROW_HEIGHT_FACTOR,
+                    custom_format: false,
+                    custom_height: false,
+                    s: 0,
+                    hidden: false,
+                    outline_level: 0,
+                    collapsed: false,
+                });
+                self.rows.len() - 1
+            }
+        };
+        Ok(&mut self.rows[index])
+    }
+
+    /// Returns the record of `column`, splitting the range it belongs to if needed.
+    /// If the column has no record one with the default width is added.
+    pub(crate) fn column_mut(&mut self, column: i32) -> Result<&mut Col, String> {
+        if !is_valid_column_number(column) {
+            return Err(format!("Column number '{column}' is not valid."));
+        }
+        let cols = &mut self.cols;
+        let mut index = 0;
+        while index < cols.len() && cols[index].max < column {
+            index += 1;
+        }
+        if index < cols.len() && cols[index].min <= column {
+            let col = cols[index].clone();
+            if col.max > column {
+                cols.insert(
+                    index + 1,
+                    Col {
+                        min: column + 1,
+                        ..col.clone()
+                    },
+                );
+            }
+            if col.min < column {
+                cols.insert(
+                    index,
+                    Col {
+                        max: column - 1,
+                        ..col.clone()
+                    },
+                );
+                index += 1;
+            }
+            cols[index].min = column;
+            cols[index].max = column;
+        } else {
+            cols.insert(
+                index,
+                Col {
+                    min: column,
+                    max: column,
+                    width: constants::DEFAULT_COLUMN_WIDTH / constants::COLUMN_WIDTH_FACTOR,
+                    custom_width: false,
+                    style: None,
+                    hidden: false,
+                    outline_level: 0,
+                    collapsed: false,
+                },
+            );
+        }
+        Ok(&mut cols[index])
+    }
+
+    /// Returns the record of `row`, if any
+    pub(crate) fn get_row(&self, row: i32) -> Option<&Row> {
+        self.rows.iter().find(|r| r.r == row)
+    }
+
+    /// Returns the record of the range of columns that contains `column`, if any
+    pub(crate) fn get_column(&self, column: i32) -> Option<&Col> {
+        self.cols
+            .iter()
+            .find(|c| c.min <= column && column <= c.max)
+    }
+
+    /// Returns true if the row is hidden
+    pub fn is_row_hidden(&self, row: i32) -> Result<bool, String> {
+        if !is_valid_row(row) {
+            return Err(format!("Row number '{row}' is not valid."));
+        }
+        Ok(self.get_row(row).is_some_and(|r| r.hidden))
+    }
+
+    /// Hides or unhides a row
+    pub fn set_row_hidden(&mut self, row: i32, hidden: bool) -> Result<(), String> {
+        self.row_mut(row)?.hidden = hidden;
+        Ok(())
+    }
+
+    /// Returns true if the column is hidden
+    pub fn is_column_hidden(&self, column: i32) -> Result<bool, String> {
+        if !is_valid_column_number(column) {
+            return Err(format!("Column number '{column}' is not valid."));
+        }
+        Ok(self.get_column(column).is_some_and(|c| c.hidden))
+    }
+
+    /// Hides or unhides a column
+    pub fn set_column_hidden(&mut self, column: i32, hidden: bool) -> Result<(), String> {
+        self.column_mut(column)?.hidden = hidden;
+        Ok(())
+    }
+
     /// Return the width of a column in pixels
     pub fn get_column_width(&self, column: i32) -> Result<f64, String> {
         if !is_valid_column_number(column) {
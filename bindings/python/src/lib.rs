@@ -1,7 +1,7 @@
 use pyo3::exceptions::PyException;
 use pyo3::{create_exception, prelude::*, wrap_pyfunction};
 
-use types::{PySheetProperty, PyStyle};
+use types::{PyComment, PyHyperlink, PySheetProperty, PyStyle, PyTextRun};
 use xlsx::base::types::Style;
 use xlsx::base::Model;
 
@@ -139,6 +139,56 @@ impl PyModel {
             .map_err(|e| WorkbookError::new_err(e.to_string()))
     }
 
+    // hidden rows/columns and outline levels
+
+    pub fn is_row_hidden(&self, sheet: u32, row: i32) -> PyResult<bool> {
+        self.model
+            .is_row_hidden(sheet, row)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn set_row_hidden(&mut self, sheet: u32, row: i32, hidden: bool) -> PyResult<()> {
+        self.model
+            .set_row_hidden(sheet, row, hidden)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn is_column_hidden(&self, sheet: u32, column: i32) -> PyResult<bool> {
+        self.model
+            .is_column_hidden(sheet, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn set_column_hidden(&mut self, sheet: u32, column: i32, hidden: bool) -> PyResult<()> {
+        self.model
+            .set_column_hidden(sheet, column, hidden)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn get_row_outline_level(&self, sheet: u32, row: i32) -> PyResult<u8> {
+        self.model
+            .get_row_outline_level(sheet, row)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn set_row_outline_level(&mut self, sheet: u32, row: i32, level: u8) -> PyResult<()> {
+        self.model
+            .set_row_outline_level(sheet, row, level)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn get_column_outline_level(&self, sheet: u32, column: i32) -> PyResult<u8> {
+        self.model
+            .get_column_outline_level(sheet, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn set_column_outline_level(&mut self, sheet: u32, column: i32, level: u8) -> PyResult<()> {
+        self.model
+            .set_column_outline_level(sheet, column, level)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
     // frozen rows/columns
 
     pub fn get_frozen_columns_count(&self, sheet: u32) -> PyResult<i32> {
@@ -208,6 +258,211 @@ impl PyModel {
             .map_err(|e| WorkbookError::new_err(e.to_string()))
     }
 
+    /// Returns the runs of the cell if its value is a string with rich text
+    pub fn get_cell_rich_text(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> PyResult<Option<Vec<PyTextRun>>> {
+        let runs = self
+            .model
+            .get_cell_rich_text(sheet, row, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))?;
+        Ok(runs.map(|runs| runs.into_iter().map(|run| run.into()).collect()))
+    }
+
+    // Comments
+
+    /// Returns all the comments in the sheet
+    pub fn get_comments(&self, sheet: u32) -> PyResult<Vec<PyComment>> {
+        let comments = self
+            .model
+            .get_comments(sheet)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))?;
+        Ok(comments.into_iter().map(|c| c.into()).collect())
+    }
+
+    /// Returns the comment in the cell, if any
+    pub fn get_comment(&self, sheet: u32, row: i32, column: i32) -> PyResult<Option<PyComment>> {
+        let comment = self
+            .model
+            .get_comment(sheet, row, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))?;
+        Ok(comment.map(|c| c.into()))
+    }
+
+    #[pyo3(signature = (sheet, row, column, text, author_name, author_id=None))]
+    pub fn add_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> PyResult<()> {
+        self.model
+            .add_comment(sheet, row, column, text, author_name, author_id)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn edit_comment(&mut self, sheet: u32, row: i32, column: i32, text: &str) -> PyResult<()> {
+        self.model
+            .edit_comment(sheet, row, column, text)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn resolve_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        resolved: bool,
+    ) -> PyResult<()> {
+        self.model
+            .resolve_comment(sheet, row, column, resolved)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    #[pyo3(signature = (sheet, row, column, text, author_name, author_id=None))]
+    pub fn add_comment_reply(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> PyResult<()> {
+        self.model
+            .add_comment_reply(sheet, row, column, text, author_name, author_id)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn delete_comment(&mut self, sheet: u32, row: i32, column: i32) -> PyResult<()> {
+        self.model
+            .delete_comment(sheet, row, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    // Hyperlinks
+
+    /// Returns all the hyperlinks in the sheet
+    pub fn get_hyperlinks(&self, sheet: u32) -> PyResult<Vec<PyHyperlink>> {
+        let hyperlinks = self
+            .model
+            .get_hyperlinks(sheet)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))?;
+        Ok(hyperlinks.into_iter().map(|h| h.into()).collect())
+    }
+
+    /// Returns the hyperlink attached to the cell, if any
+    pub fn get_hyperlink(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> PyResult<Option<PyHyperlink>> {
+        let hyperlink = self
+            .model
+            .get_hyperlink(sheet, row, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))?;
+        Ok(hyperlink.map(|h| h.into()))
+    }
+
+    /// Returns the link followed when the cell is clicked, including HYPERLINK formulas
+    pub fn get_cell_hyperlink(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> PyResult<Option<PyHyperlink>> {
+        let hyperlink = self
+            .model
+            .get_cell_hyperlink(sheet, row, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))?;
+        Ok(hyperlink.map(|h| h.into()))
+    }
+
+    #[pyo3(signature = (sheet, row, column, url=None, location=None, tooltip=None))]
+    pub fn set_hyperlink(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        url: Option<String>,
+        location: Option<String>,
+        tooltip: Option<String>,
+    ) -> PyResult<()> {
+        self.model
+            .set_hyperlink(sheet, row, column, url, location, tooltip)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn delete_hyperlink(&mut self, sheet: u32, row: i32, column: i32) -> PyResult<()> {
+        self.model
+            .delete_hyperlink(sheet, row, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    // Protection
+
+    /// Returns true if the sheet is protected
+    pub fn is_sheet_protected(&self, sheet: u32) -> PyResult<bool> {
+        self.model
+            .is_sheet_protected(sheet)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    /// Protects the sheet with the default options: users can only select cells and edit the unlocked ones
+    #[pyo3(signature = (sheet, password=None))]
+    pub fn protect_sheet(&mut self, sheet: u32, password: Option<String>) -> PyResult<()> {
+        self.model
+            .protect_sheet(sheet, Default::default(), password.as_deref())
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    #[pyo3(signature = (sheet, password=None))]
+    pub fn unprotect_sheet(&mut self, sheet: u32, password: Option<String>) -> PyResult<()> {
+        self.model
+            .unprotect_sheet(sheet, password.as_deref())
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    /// Returns true if the cell is locked. Locked cells cannot be changed when the sheet is protected.
+    pub fn is_cell_locked(&self, sheet: u32, row: i32, column: i32) -> PyResult<bool> {
+        self.model
+            .is_cell_locked(sheet, row, column)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    /// Returns true if the structure of the workbook is protected
+    pub fn is_workbook_structure_protected(&self) -> bool {
+        self.model
+            .get_workbook_protection()
+            .is_some_and(|p| p.lock_structure)
+    }
+
+    #[pyo3(signature = (lock_structure, lock_windows, password=None))]
+    pub fn protect_workbook(
+        &mut self,
+        lock_structure: bool,
+        lock_windows: bool,
+        password: Option<String>,
+    ) -> PyResult<()> {
+        self.model
+            .protect_workbook(lock_structure, lock_windows, password.as_deref())
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    #[pyo3(signature = (password=None))]
+    pub fn unprotect_workbook(&mut self, password: Option<String>) -> PyResult<()> {
+        self.model
+            .unprotect_workbook(password.as_deref())
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
     pub fn test_panic(&self) -> PyResult<()> {
         panic!("This function panics for testing panic handling");
     }
@@ -1,6 +1,7 @@
 use pyo3::prelude::*;
 use xlsx::base::types::{
-    Alignment, Border, BorderItem, BorderStyle, Fill, Font, FontScheme, HorizontalAlignment, Style,
+    Alignment, Border, BorderItem, BorderStyle, Comment, CommentReply, Fill, Font, FontScheme,
+    FontVerticalAlignment, HorizontalAlignment, Hyperlink, Protection, Style, TextRun,
     VerticalAlignment,
 };
 
@@ -17,6 +18,87 @@ pub struct PySheetProperty {
     pub color: Option<String>,
 }
 
+#[derive(Clone)]
+#[pyclass]
+pub struct PyCommentReply {
+    #[pyo3(get)]
+    pub text: String,
+    #[pyo3(get)]
+    pub author_name: String,
+    #[pyo3(get)]
+    pub author_id: Option<String>,
+    #[pyo3(get)]
+    pub timestamp: Option<i64>,
+}
+
+impl From<CommentReply> for PyCommentReply {
+    fn from(reply: CommentReply) -> Self {
+        PyCommentReply {
+            text: reply.text,
+            author_name: reply.author_name,
+            author_id: reply.author_id,
+            timestamp: reply.timestamp,
+        }
+    }
+}
+
+#[derive(Clone)]
+#[pyclass]
+pub struct PyComment {
+    #[pyo3(get)]
+    pub text: String,
+    #[pyo3(get)]
+    pub author_name: String,
+    #[pyo3(get)]
+    pub author_id: Option<String>,
+    #[pyo3(get)]
+    pub cell_ref: String,
+    #[pyo3(get)]
+    pub timestamp: Option<i64>,
+    #[pyo3(get)]
+    pub resolved: bool,
+    #[pyo3(get)]
+    pub replies: Vec<PyCommentReply>,
+}
+
+impl From<Comment> for PyComment {
+    fn from(comment: Comment) -> Self {
+        PyComment {
+            text: comment.text,
+            author_name: comment.author_name,
+            author_id: comment.author_id,
+            cell_ref: comment.cell_ref,
+            timestamp: comment.timestamp,
+            resolved: comment.resolved,
+            replies: comment.replies.into_iter().map(|r| r.into()).collect(),
+        }
+    }
+}
+
+#[derive(Clone)]
+#[pyclass]
+pub struct PyHyperlink {
+    #[pyo3(get)]
+    pub cell_ref: String,
+    #[pyo3(get)]
+    pub url: Option<String>,
+    #[pyo3(get)]
+    pub location: Option<String>,
+    #[pyo3(get)]
+    pub tooltip: Option<String>,
+}
+
+impl From<Hyperlink> for PyHyperlink {
+    fn from(hyperlink: Hyperlink) -> Self {
+        PyHyperlink {
+            cell_ref: hyperlink.cell_ref,
+            url: hyperlink.url,
+            location: hyperlink.location,
+            tooltip: hyperlink.tooltip,
+        }
+    }
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct Cell {
@@ -60,12 +142,23 @@ pub struct PyAlignment {
     pub wrap_text: bool,
 }
 
+#[pyclass]
+#[derive(Clone)]
+pub struct PyProtection {
+    #[pyo3(get)]
+    pub locked: bool,
+    #[pyo3(get)]
+    pub hidden: bool,
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyStyle {
     #[pyo3(get)]
     pub alignment: Option<PyAlignment>,
     #[pyo3(get)]
+    pub protection: Option<PyProtection>,
+    #[pyo3(get)]
     pub num_fmt: String,
     #[pyo3(get)]
     pub fill: PyFill,
@@ -127,6 +220,14 @@ pub enum PyFontScheme {
     None,
 }
 
+#[pyclass(eq, eq_int)]
+#[derive(PartialEq, Clone)]
+pub enum PyFontVerticalAlignment {
+    Baseline,
+    Superscript,
+    Subscript,
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyFont {
@@ -148,6 +249,17 @@ pub struct PyFont {
     pub family: i32,
     #[pyo3(get)]
     pub scheme: PyFontScheme,
+    #[pyo3(get)]
+    pub vert_align: PyFontVerticalAlignment,
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyTextRun {
+    #[pyo3(get)]
+    pub text: String,
+    #[pyo3(get)]
+    pub font: Option<PyFont>,
 }
 
 #[pyclass]
@@ -202,6 +314,16 @@ impl From<PyFontScheme> for FontScheme {
     }
 }
 
+impl From<PyFontVerticalAlignment> for FontVerticalAlignment {
+    fn from(py_vert_align: PyFontVerticalAlignment) -> Self {
+        match py_vert_align {
+            PyFontVerticalAlignment::Baseline => FontVerticalAlignment::Baseline,
+            PyFontVerticalAlignment::Superscript => FontVerticalAlignment::Superscript,
+            PyFontVerticalAlignment::Subscript => FontVerticalAlignment::Subscript,
+        }
+    }
+}
+
 impl From<&PyBorderStyle> for BorderStyle {
     fn from(py_style: &PyBorderStyle) -> Self {
         match *py_style {
@@ -225,7 +347,9 @@ impl From<&PyFill> for Fill {
         Fill {
             pattern_type: py_fill.pattern_type.clone(),
             fg_color: py_fill.fg_color.clone(),
+            fg_color_theme: None,
             bg_color: py_fill.bg_color.clone(),
+            bg_color_theme: None,
         }
     }
 }
@@ -239,9 +363,11 @@ impl From<&PyFont> for Font {
             i: py_font.i,
             sz: py_font.sz,
             color: py_font.color.clone(),
+            color_theme: None,
             name: py_font.name.clone(),
             family: py_font.family,
             scheme: py_font.scheme.clone().into(),
+            vert_align: py_font.vert_align.clone().into(),
         }
     }
 }
@@ -251,6 +377,7 @@ impl From<&PyBorderItem> for BorderItem {
         BorderItem {
             style: (&py_item.style).into(),
             color: py_item.color.clone(),
+            color_theme: None,
         }
     }
 }
@@ -279,10 +406,20 @@ impl From<&PyAlignment> for Alignment {
     }
 }
 
+impl From<&PyProtection> for Protection {
+    fn from(py_protection: &PyProtection) -> Self {
+        Protection {
+            locked: py_protection.locked,
+            hidden: py_protection.hidden,
+        }
+    }
+}
+
 impl From<&PyStyle> for Style {
     fn from(py_style: &PyStyle) -> Self {
         Style {
             alignment: py_style.alignment.as_ref().map(|a| a.into()),
+            protection: py_style.protection.as_ref().map(|p| p.into()),
             num_fmt: py_style.num_fmt.clone(),
             fill: (&py_style.fill).into(),
             font: (&py_style.font).into(),
@@ -360,6 +497,17 @@ impl From<BorderStyle> for PyBorderStyle {
     }
 }
 
+// From non-Py to Py
+impl From<FontVerticalAlignment> for PyFontVerticalAlignment {
+    fn from(vert_align: FontVerticalAlignment) -> Self {
+        match vert_align {
+            FontVerticalAlignment::Baseline => PyFontVerticalAlignment::Baseline,
+            FontVerticalAlignment::Superscript => PyFontVerticalAlignment::Superscript,
+            FontVerticalAlignment::Subscript => PyFontVerticalAlignment::Subscript,
+        }
+    }
+}
+
 // From non-Py to Py
 impl From<Font> for PyFont {
     fn from(font: Font) -> Self {
@@ -373,6 +521,17 @@ impl From<Font> for PyFont {
             name: font.name,
             family: font.family,
             scheme: font.scheme.into(),
+            vert_align: font.vert_align.into(),
+        }
+    }
+}
+
+// From non-Py to Py
+impl From<TextRun> for PyTextRun {
+    fn from(run: TextRun) -> Self {
+        PyTextRun {
+            text: run.text,
+            font: run.font.map(|font| font.into()),
         }
     }
 }
@@ -413,11 +572,22 @@ impl From<Alignment> for PyAlignment {
     }
 }
 
+// From non-Py to Py
+impl From<Protection> for PyProtection {
+    fn from(protection: Protection) -> Self {
+        PyProtection {
+            locked: protection.locked,
+            hidden: protection.hidden,
+        }
+    }
+}
+
 // From non-Py to Py
 impl From<Style> for PyStyle {
     fn from(style: Style) -> Self {
         PyStyle {
             alignment: style.alignment.map(|a| a.into()),
+            protection: style.protection.map(|p| p.into()),
             num_fmt: style.num_fmt,
             fill: style.fill.into(),
             font: style.font.into(),
@@ -185,6 +185,702 @@ paste_from_clipboard_types = r"""
   pasteFromClipboard(source_range: [number, number, number, number], clipboard: ClipboardData, is_cut: boolean): void;
 """
 
+paste_special_from_clipboard = r"""
+/**
+* @param {any} source_range
+* @param {any} clipboard
+* @param {any} options
+*/
+  pasteSpecialFromClipboard(source_range: any, clipboard: any, options: any): void;
+"""
+
+paste_special_from_clipboard_types = r"""
+/**
+* @param {[number, number, number, number]} source_range
+* @param {ClipboardData} clipboard
+* @param {PasteOptions} options
+*/
+  pasteSpecialFromClipboard(source_range: [number, number, number, number], clipboard: ClipboardData, options: PasteOptions): void;
+"""
+
+get_comments = r"""
+* @returns {any}
+*/
+  getComments(sheet: number): any;
+""".strip()
+
+get_comments_types = r"""
+* @returns {Comment[]}
+*/
+  getComments(sheet: number): Comment[];
+""".strip()
+
+get_comment = r"""
+* @returns {any}
+*/
+  getComment(sheet: number, row: number, column: number): any;
+""".strip()
+
+get_comment_types = r"""
+* @returns {Comment | undefined}
+*/
+  getComment(sheet: number, row: number, column: number): Comment | undefined;
+""".strip()
+
+get_hyperlinks = r"""
+* @returns {any}
+*/
+  getHyperlinks(sheet: number): any;
+""".strip()
+
+get_hyperlinks_types = r"""
+* @returns {Hyperlink[]}
+*/
+  getHyperlinks(sheet: number): Hyperlink[];
+""".strip()
+
+get_hyperlink = r"""
+* @returns {any}
+*/
+  getHyperlink(sheet: number, row: number, column: number): any;
+""".strip()
+
+get_hyperlink_types = r"""
+* @returns {Hyperlink | undefined}
+*/
+  getHyperlink(sheet: number, row: number, column: number): Hyperlink | undefined;
+""".strip()
+
+get_cell_hyperlink = r"""
+* @returns {any}
+*/
+  getCellHyperlink(sheet: number, row: number, column: number): any;
+""".strip()
+
+get_cell_hyperlink_types = r"""
+* @returns {Hyperlink | undefined}
+*/
+  getCellHyperlink(sheet: number, row: number, column: number): Hyperlink | undefined;
+""".strip()
+
+stale_cells = r"""
+* @returns {any}
+*/
+  getStaleCells(): any;
+""".strip()
+
+stale_cells_types = r"""
+* @returns {CellReference[]}
+*/
+  getStaleCells(): CellReference[];
+""".strip()
+
+effective_style = r"""
+* @returns {any}
+*/
+  getEffectiveCellStyle(sheet: number, row: number, column: number): any;
+""".strip()
+
+effective_style_types = r"""
+* @returns {CellStyle}
+*/
+  getEffectiveCellStyle(sheet: number, row: number, column: number): CellStyle;
+""".strip()
+
+conditional_format_result = r"""
+* @returns {any}
+*/
+  getConditionalFormatResult(sheet: number, row: number, column: number): any;
+""".strip()
+
+conditional_format_result_types = r"""
+* @returns {ConditionalFormatResult | undefined}
+*/
+  getConditionalFormatResult(sheet: number, row: number, column: number): ConditionalFormatResult | undefined;
+""".strip()
+
+conditional_formats = r"""
+* @returns {any}
+*/
+  getConditionalFormats(sheet: number): any;
+""".strip()
+
+conditional_formats_types = r"""
+* @returns {ConditionalFormat[]}
+*/
+  getConditionalFormats(sheet: number): ConditionalFormat[];
+""".strip()
+
+data_validations = r"""
+* @returns {any}
+*/
+  getDataValidations(sheet: number): any;
+""".strip()
+
+data_validations_types = r"""
+* @returns {DataValidation[]}
+*/
+  getDataValidations(sheet: number): DataValidation[];
+""".strip()
+
+data_validation_for_cell = r"""
+* @returns {any}
+*/
+  getDataValidationForCell(sheet: number, row: number, column: number): any;
+""".strip()
+
+data_validation_for_cell_types = r"""
+* @returns {DataValidation | undefined}
+*/
+  getDataValidationForCell(sheet: number, row: number, column: number): DataValidation | undefined;
+""".strip()
+
+check_data_validation = r"""
+* @returns {any}
+*/
+  checkDataValidation(sheet: number, row: number, column: number): any;
+""".strip()
+
+check_data_validation_types = r"""
+* @returns {DataValidation | undefined}
+*/
+  checkDataValidation(sheet: number, row: number, column: number): DataValidation | undefined;
+""".strip()
+
+add_conditional_format = r"""
+* @param {any} conditional_format
+*/
+  addConditionalFormat(sheet: number, conditional_format: any): void;
+""".strip()
+
+add_conditional_format_types = r"""
+* @param {ConditionalFormat} conditional_format
+*/
+  addConditionalFormat(sheet: number, conditional_format: ConditionalFormat): void;
+""".strip()
+
+set_conditional_format = r"""
+* @param {any} conditional_format
+*/
+  setConditionalFormat(sheet: number, index: number, conditional_format: any): void;
+""".strip()
+
+set_conditional_format_types = r"""
+* @param {ConditionalFormat} conditional_format
+*/
+  setConditionalFormat(sheet: number, index: number, conditional_format: ConditionalFormat): void;
+""".strip()
+
+add_data_validation = r"""
+* @param {any} data_validation
+*/
+  addDataValidation(sheet: number, data_validation: any): void;
+""".strip()
+
+add_data_validation_types = r"""
+* @param {DataValidation} data_validation
+*/
+  addDataValidation(sheet: number, data_validation: DataValidation): void;
+""".strip()
+
+set_data_validation = r"""
+* @param {any} data_validation
+*/
+  setDataValidation(sheet: number, index: number, data_validation: any): void;
+""".strip()
+
+set_data_validation_types = r"""
+* @param {DataValidation} data_validation
+*/
+  setDataValidation(sheet: number, index: number, data_validation: DataValidation): void;
+""".strip()
+
+get_cell_rich_text = r"""
+* @returns {any}
+*/
+  getCellRichText(sheet: number, row: number, column: number): any;
+""".strip()
+
+get_cell_rich_text_types = r"""
+* @returns {TextRun[] | undefined}
+*/
+  getCellRichText(sheet: number, row: number, column: number): TextRun[] | undefined;
+""".strip()
+
+set_cell_rich_text = r"""
+* @param {any} runs
+*/
+  setCellRichText(sheet: number, row: number, column: number, runs: any): void;
+""".strip()
+
+set_cell_rich_text_types = r"""
+* @param {TextRun[]} runs
+*/
+  setCellRichText(sheet: number, row: number, column: number, runs: TextRun[]): void;
+""".strip()
+
+get_sheet_protection = r"""
+* @returns {any}
+*/
+  getSheetProtection(sheet: number): any;
+""".strip()
+
+get_sheet_protection_types = r"""
+* @returns {SheetProtection | undefined}
+*/
+  getSheetProtection(sheet: number): SheetProtection | undefined;
+""".strip()
+
+protect_sheet = r"""
+* @param {any} protection
+* @param {string | undefined} [password]
+*/
+  protectSheet(sheet: number, protection: any, password?: string): void;
+""".strip()
+
+protect_sheet_types = r"""
+* @param {SheetProtection} protection
+* @param {string | undefined} [password]
+*/
+  protectSheet(sheet: number, protection: SheetProtection, password?: string): void;
+""".strip()
+
+get_workbook_protection = r"""
+* @returns {any}
+*/
+  getWorkbookProtection(): any;
+""".strip()
+
+get_workbook_protection_types = r"""
+* @returns {WorkbookProtection | undefined}
+*/
+  getWorkbookProtection(): WorkbookProtection | undefined;
+""".strip()
+
+get_auto_filter = r"""
+* @returns {any}
+*/
+  getAutoFilter(sheet: number): any;
+""".strip()
+
+get_auto_filter_types = r"""
+* @returns {AutoFilter | undefined}
+*/
+  getAutoFilter(sheet: number): AutoFilter | undefined;
+""".strip()
+
+set_auto_filter_column = r"""
+* @param {any} filter
+*/
+  setAutoFilterColumn(sheet: number, column_id: number, filter: any): void;
+""".strip()
+
+set_auto_filter_column_types = r"""
+* @param {ColumnFilter | undefined} [filter]
+*/
+  setAutoFilterColumn(sheet: number, column_id: number, filter?: ColumnFilter): void;
+""".strip()
+
+get_table_filter = r"""
+* @returns {any}
+*/
+  getTableFilter(table: string): any;
+""".strip()
+
+get_table_filter_types = r"""
+* @returns {FilterColumn[]}
+*/
+  getTableFilter(table: string): FilterColumn[];
+""".strip()
+
+set_table_filter_column = r"""
+* @param {any} filter
+*/
+  setTableFilterColumn(table: string, column_id: number, filter: any): void;
+""".strip()
+
+set_table_filter_column_types = r"""
+* @param {ColumnFilter | undefined} [filter]
+*/
+  setTableFilterColumn(table: string, column_id: number, filter?: ColumnFilter): void;
+""".strip()
+
+sort_range = r"""
+* @param {any} keys
+* @param {boolean} has_header
+*/
+  sortRange(sheet: number, start_row: number, start_column: number, end_row: number, end_column: number, keys: any, has_header: boolean): void;
+""".strip()
+
+sort_range_types = r"""
+* @param {SortKey[]} keys
+* @param {boolean} has_header
+*/
+  sortRange(sheet: number, start_row: number, start_column: number, end_row: number, end_column: number, keys: SortKey[], has_header: boolean): void;
+""".strip()
+
+sort_table = r"""
+* @param {any} keys
+*/
+  sortTable(table: string, keys: any): void;
+""".strip()
+
+sort_table_types = r"""
+* @param {SortKey[]} keys
+*/
+  sortTable(table: string, keys: SortKey[]): void;
+""".strip()
+
+get_tables = r"""
+* @returns {any}
+*/
+  getTables(sheet: number): any;
+""".strip()
+
+get_tables_types = r"""
+* @returns {Table[]}
+*/
+  getTables(sheet: number): Table[];
+""".strip()
+
+get_table = r"""
+* @returns {any}
+*/
+  getTable(name: string): any;
+""".strip()
+
+get_table_types = r"""
+* @returns {Table}
+*/
+  getTable(name: string): Table;
+""".strip()
+
+set_table_totals_row_function = r"""
+* @param {any} totals_function
+*/
+  setTableTotalsRowFunction(name: string, column: string, totals_function: any): void;
+""".strip()
+
+set_table_totals_row_function_types = r"""
+* @param {TotalsRowFunction | undefined} totals_function
+*/
+  setTableTotalsRowFunction(name: string, column: string, totals_function: TotalsRowFunction | undefined): void;
+""".strip()
+
+get_pivot_tables = r"""
+* @returns {any}
+*/
+  getPivotTables(sheet: number): any;
+""".strip()
+
+get_pivot_tables_types = r"""
+* @returns {PivotTable[]}
+*/
+  getPivotTables(sheet: number): PivotTable[];
+""".strip()
+
+get_pivot_table = r"""
+* @returns {any}
+*/
+  getPivotTable(sheet: number, name: string): any;
+""".strip()
+
+get_pivot_table_types = r"""
+* @returns {PivotTable}
+*/
+  getPivotTable(sheet: number, name: string): PivotTable;
+""".strip()
+
+add_pivot_table = r"""
+* @param {any} source
+* @returns {string}
+*/
+  addPivotTable(sheet: number, row: number, column: number, source: any): string;
+""".strip()
+
+add_pivot_table_types = r"""
+* @param {PivotSource} source
+* @returns {string}
+*/
+  addPivotTable(sheet: number, row: number, column: number, source: PivotSource): string;
+""".strip()
+
+update_pivot_table = r"""
+* @param {any} pivot_table
+*/
+  updatePivotTable(sheet: number, name: string, pivot_table: any): void;
+""".strip()
+
+update_pivot_table_types = r"""
+* @param {PivotTable} pivot_table
+*/
+  updatePivotTable(sheet: number, name: string, pivot_table: PivotTable): void;
+""".strip()
+
+fill_series = r"""
+/**
+* @param {any} area
+* @param {boolean} down
+* @param {any} series_type
+* @param {number} step
+* @param {number | undefined} [stop]
+*/
+  fillSeries(area: any, down: boolean, series_type: any, step: number, stop?: number): void;
+"""
+
+fill_series_types = r"""
+/**
+* @param {Area} area
+* @param {boolean} down
+* @param {SeriesType} series_type
+* @param {number} step
+* @param {number | undefined} [stop]
+*/
+  fillSeries(area: Area, down: boolean, series_type: SeriesType, step: number, stop?: number): void;
+"""
+
+move_range = r"""
+/**
+* @param {any} area
+* @param {number} target_row
+* @param {number} target_column
+*/
+  moveRange(area: any, target_row: number, target_column: number): void;
+"""
+
+move_range_types = r"""
+/**
+* @param {Area} area
+* @param {number} target_row
+* @param {number} target_column
+*/
+  moveRange(area: Area, target_row: number, target_column: number): void;
+"""
+
+find_next = r"""
+/**
+* @param {string} text
+* @param {any} options
+* @returns {any}
+*/
+  findNext(text: string, options: any): any;
+"""
+
+find_next_types = r"""
+/**
+* @param {string} text
+* @param {FindOptions} options
+* @returns {CellReference | undefined}
+*/
+  findNext(text: string, options: FindOptions): CellReference | undefined;
+"""
+
+find_previous = r"""
+/**
+* @param {string} text
+* @param {any} options
+* @returns {any}
+*/
+  findPrevious(text: string, options: any): any;
+"""
+
+find_previous_types = r"""
+/**
+* @param {string} text
+* @param {FindOptions} options
+* @returns {CellReference | undefined}
+*/
+  findPrevious(text: string, options: FindOptions): CellReference | undefined;
+"""
+
+find_all = r"""
+/**
+* @param {string} text
+* @param {any} options
+* @returns {any}
+*/
+  findAll(text: string, options: any): any;
+"""
+
+find_all_types = r"""
+/**
+* @param {string} text
+* @param {FindOptions} options
+* @returns {CellReference[]}
+*/
+  findAll(text: string, options: FindOptions): CellReference[];
+"""
+
+replace = r"""
+/**
+* @param {string} text
+* @param {string} replacement
+* @param {any} options
+* @returns {any}
+*/
+  replace(text: string, replacement: string, options: any): any;
+"""
+
+replace_types = r"""
+/**
+* @param {string} text
+* @param {string} replacement
+* @param {FindOptions} options
+* @returns {CellReference | undefined}
+*/
+  replace(text: string, replacement: string, options: FindOptions): CellReference | undefined;
+"""
+
+replace_all = r"""
+/**
+* @param {string} text
+* @param {string} replacement
+* @param {any} options
+* @returns {number}
+*/
+  replaceAll(text: string, replacement: string, options: any): number;
+"""
+
+replace_all_types = r"""
+/**
+* @param {string} text
+* @param {string} replacement
+* @param {FindOptions} options
+* @returns {number}
+*/
+  replaceAll(text: string, replacement: string, options: FindOptions): number;
+"""
+
+remove_duplicates = r"""
+/**
+* @param {any} area
+* @param {any} key_columns
+* @param {boolean} has_header
+* @returns {number}
+*/
+  removeDuplicates(area: any, key_columns: any, has_header: boolean): number;
+"""
+
+remove_duplicates_types = r"""
+/**
+* @param {Area} area
+* @param {number[]} key_columns
+* @param {boolean} has_header
+* @returns {number}
+*/
+  removeDuplicates(area: Area, key_columns: number[], has_header: boolean): number;
+"""
+
+text_to_columns = r"""
+/**
+* @param {any} area
+* @param {any} split
+* @param {any} column_formats
+*/
+  textToColumns(area: any, split: any, column_formats: any): void;
+"""
+
+text_to_columns_types = r"""
+/**
+* @param {Area} area
+* @param {TextSplit} split
+* @param {TextColumnFormat[]} column_formats
+*/
+  textToColumns(area: Area, split: TextSplit, column_formats: TextColumnFormat[]): void;
+"""
+
+get_named_style = r"""
+/**
+* @param {string} style_name
+* @returns {any}
+*/
+  getNamedStyle(style_name: string): any;
+"""
+
+get_named_style_types = r"""
+/**
+* @param {string} style_name
+* @returns {CellStyle}
+*/
+  getNamedStyle(style_name: string): CellStyle;
+"""
+
+apply_named_style = r"""
+/**
+* @param {any} area
+* @param {string} style_name
+*/
+  applyNamedStyle(area: any, style_name: string): void;
+"""
+
+apply_named_style_types = r"""
+/**
+* @param {Area} area
+* @param {string} style_name
+*/
+  applyNamedStyle(area: Area, style_name: string): void;
+"""
+
+create_named_style = r"""
+/**
+* @param {string} style_name
+* @param {any} style
+*/
+  createNamedStyle(style_name: string, style: any): void;
+"""
+
+create_named_style_types = r"""
+/**
+* @param {string} style_name
+* @param {CellStyle} style
+*/
+  createNamedStyle(style_name: string, style: CellStyle): void;
+"""
+
+set_named_style = r"""
+/**
+* @param {string} style_name
+* @param {any} style
+*/
+  setNamedStyle(style_name: string, style: any): void;
+"""
+
+set_named_style_types = r"""
+/**
+* @param {string} style_name
+* @param {CellStyle} style
+*/
+  setNamedStyle(style_name: string, style: CellStyle): void;
+"""
+
+get_theme = r"""
+/**
+* @returns {any}
+*/
+  getTheme(): any;
+"""
+
+get_theme_types = r"""
+/**
+* @returns {Theme}
+*/
+  getTheme(): Theme;
+"""
+
+set_theme = r"""
+/**
+* @param {any} theme
+*/
+  setTheme(theme: any): void;
+"""
+
+set_theme_types = r"""
+/**
+* @param {Theme} theme
+*/
+  setTheme(theme: Theme): void;
+"""
+
 def fix_types(text):
     text = text.replace(get_tokens_str, get_tokens_str_types)
     text = text.replace(update_style_str, update_style_str_types)
@@ -198,13 +894,64 @@ def fix_types(text):
     text = text.replace(paste_csv_string, paste_csv_string_types)
     text = text.replace(clipboard, clipboard_types)
     text = text.replace(paste_from_clipboard, paste_from_clipboard_types)
+    text = text.replace(paste_special_from_clipboard, paste_special_from_clipboard_types)
+    text = text.replace(get_comments, get_comments_types)
+    text = text.replace(get_comment, get_comment_types)
+    text = text.replace(get_hyperlinks, get_hyperlinks_types)
+    text = text.replace(get_sheet_protection, get_sheet_protection_types)
+    text = text.replace(protect_sheet, protect_sheet_types)
+    text = text.replace(get_workbook_protection, get_workbook_protection_types)
+    text = text.replace(get_auto_filter, get_auto_filter_types)
+    text = text.replace(set_auto_filter_column, set_auto_filter_column_types)
+    text = text.replace(get_table_filter, get_table_filter_types)
+    text = text.replace(set_table_filter_column, set_table_filter_column_types)
+    text = text.replace(sort_range, sort_range_types)
+    text = text.replace(sort_table, sort_table_types)
+    text = text.replace(get_tables, get_tables_types)
+    text = text.replace(get_table, get_table_types)
+    text = text.replace(set_table_totals_row_function, set_table_totals_row_function_types)
+    text = text.replace(get_pivot_tables, get_pivot_tables_types)
+    text = text.replace(get_pivot_table, get_pivot_table_types)
+    text = text.replace(add_pivot_table, add_pivot_table_types)
+    text = text.replace(update_pivot_table, update_pivot_table_types)
+    text = text.replace(fill_series, fill_series_types)
+    text = text.replace(move_range, move_range_types)
+    text = text.replace(find_next, find_next_types)
+    text = text.replace(find_previous, find_previous_types)
+    text = text.replace(find_all, find_all_types)
+    text = text.replace(replace, replace_types)
+    text = text.replace(replace_all, replace_all_types)
+    text = text.replace(remove_duplicates, remove_duplicates_types)
+    text = text.replace(text_to_columns, text_to_columns_types)
+    text = text.replace(get_named_style, get_named_style_types)
+    text = text.replace(apply_named_style, apply_named_style_types)
+    text = text.replace(create_named_style, create_named_style_types)
+    text = text.replace(set_named_style, set_named_style_types)
+    text = text.replace(get_theme, get_theme_types)
+    text = text.replace(set_theme, set_theme_types)
+    text = text.replace(get_hyperlink, get_hyperlink_types)
+    text = text.replace(get_cell_hyperlink, get_cell_hyperlink_types)
+    text = text.replace(stale_cells, stale_cells_types)
+    text = text.replace(effective_style, effective_style_types)
+    text = text.replace(conditional_format_result, conditional_format_result_types)
+    text = text.replace(conditional_formats, conditional_formats_types)
+    text = text.replace(data_validations, data_validations_types)
+    text = text.replace(data_validation_for_cell, data_validation_for_cell_types)
+    text = text.replace(check_data_validation, check_data_validation_types)
+    text = text.replace(add_conditional_format, add_conditional_format_types)
+    text = text.replace(set_conditional_format, set_conditional_format_types)
+    text = text.replace(add_data_validation, add_data_validation_types)
+    text = text.replace(set_data_validation, set_data_validation_types)
+    text = text.replace(get_cell_rich_text, get_cell_rich_text_types)
+    text = text.replace(set_cell_rich_text, set_cell_rich_text_types)
+    # The check is done before adding our types, "any" is a valid data validation kind
+    if text.find("any") != -1:
+        print("There are 'unfixed' types. Please check.")
+        exit(1)
     with open("types.ts") as f:
         types_str = f.read()
         header_types = "{}\n\n{}".format(header, types_str)
     text = text.replace(header, header_types)
-    if text.find("any") != -1:
-        print("There are 'unfixed' types. Please check.")
-        exit(1)
     return text
     
 
@@ -5,8 +5,12 @@ use wasm_bindgen::{
 
 use ironcalc_base::{
     expressions::{lexer::util::get_tokens as tokenizer, types::Area, utils::number_to_column},
-    types::{CellType, Style},
-    BorderArea, ClipboardData, UserModel as BaseModel,
+    types::{
+        CalcMode, CellType, ColumnFilter, ConditionalFormat, DataValidation, FindOptions,
+        PivotSource, PivotTable, SeriesType, SheetProtection, SortKey, Style, TextColumnFormat,
+        TextRun, TextSplit, Theme, TotalsRowFunction,
+    },
+    BorderArea, ClipboardData, PasteOptions, UserModel as BaseModel,
 };
 
 fn to_js_error(error: String) -> JsError {
@@ -79,47 +83,916 @@ impl Model {
         self.model.evaluate();
     }
 
+    #[wasm_bindgen(js_name = "evaluateSheet")]
+    pub fn evaluate_sheet(&mut self, sheet: u32) -> Result<(), JsError> {
+        self.model.evaluate_sheet(sheet).map_err(to_js_error)
+    }
+
+    /// Sets the calculation mode: 'auto', 'autoNoTable' or 'manual'
+    #[wasm_bindgen(js_name = "setCalcMode")]
+    pub fn set_calc_mode(&mut self, calc_mode: &str) -> Result<(), JsError> {
+        let calc_mode = match calc_mode {
+            "auto" => CalcMode::Automatic,
+            "autoNoTable" => CalcMode::AutomaticExceptTables,
+            "manual" => CalcMode::Manual,
+            _ => {
+                return Err(JsError::new(&format!(
+                    "Invalid calculation mode: '{calc_mode}'"
+                )))
+            }
+        };
+        self.model.set_calc_mode(calc_mode);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "getCalcMode")]
+    pub fn get_calc_mode(&self) -> String {
+        self.model.get_calc_mode().to_string()
+    }
+
+    #[wasm_bindgen(js_name = "getStaleCells")]
+    pub fn get_stale_cells(&self) -> Result<JsValue, JsError> {
+        serde_wasm_bindgen::to_value(&self.model.get_stale_cells()).map_err(JsError::from)
+    }
+
     #[wasm_bindgen(js_name = "flushSendQueue")]
     pub fn flush_send_queue(&mut self) -> Vec<u8> {
         self.model.flush_send_queue()
     }
 
-    #[wasm_bindgen(js_name = "applyExternalDiffs")]
-    pub fn apply_external_diffs(&mut self, diffs: &[u8]) -> Result<(), JsError> {
-        self.model.apply_external_diffs(diffs).map_err(to_js_error)
+    #[wasm_bindgen(js_name = "applyExternalDiffs")]
+    pub fn apply_external_diffs(&mut self, diffs: &[u8]) -> Result<(), JsError> {
+        self.model.apply_external_diffs(diffs).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getCellContent")]
+    pub fn get_cell_content(&self, sheet: u32, row: i32, column: i32) -> Result<String, JsError> {
+        self.model
+            .get_cell_content(sheet, row, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getCellRichText")]
+    pub fn get_cell_rich_text(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        let runs = self
+            .model
+            .get_cell_rich_text(sheet, row, column)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&runs).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "setCellRichText")]
+    pub fn set_cell_rich_text(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        runs: JsValue,
+    ) -> Result<(), JsError> {
+        let runs: Vec<TextRun> =
+            serde_wasm_bindgen::from_value(runs).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .set_cell_rich_text(sheet, row, column, runs)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "newSheet")]
+    pub fn new_sheet(&mut self) -> Result<(), JsError> {
+        self.model.new_sheet().map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteSheet")]
+    pub fn delete_sheet(&mut self, sheet: u32) -> Result<(), JsError> {
+        self.model.delete_sheet(sheet).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "renameSheet")]
+    pub fn rename_sheet(&mut self, sheet: u32, name: &str) -> Result<(), JsError> {
+        self.model.rename_sheet(sheet, name).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "moveSheet")]
+    pub fn move_sheet(&mut self, sheet: u32, new_index: u32) -> Result<(), JsError> {
+        self.model.move_sheet(sheet, new_index).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "duplicateSheet")]
+    pub fn duplicate_sheet(&mut self, sheet: u32) -> Result<(), JsError> {
+        self.model.duplicate_sheet(sheet).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "copySheetFrom")]
+    pub fn copy_sheet_from(&mut self, source: &Model, sheet: u32) -> Result<(), JsError> {
+        self.model
+            .copy_sheet_from(&source.model, sheet)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setSheetColor")]
+    pub fn set_sheet_color(&mut self, sheet: u32, color: &str) -> Result<(), JsError> {
+        self.model
+            .set_sheet_color(sheet, color)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "rangeClearAll")]
+    pub fn range_clear_all(
+        &mut self,
+        sheet: u32,
+        start_row: i32,
+        start_column: i32,
+        end_row: i32,
+        end_column: i32,
+    ) -> Result<(), JsError> {
+        let range = Area {
+            sheet,
+            row: start_row,
+            column: start_column,
+            width: end_column - start_column + 1,
+            height: end_row - start_row + 1,
+        };
+        self.model.range_clear_all(&range).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "rangeClearContents")]
+    pub fn range_clear_contents(
+        &mut self,
+        sheet: u32,
+        start_row: i32,
+        start_column: i32,
+        end_row: i32,
+        end_column: i32,
+    ) -> Result<(), JsError> {
+        let range = Area {
+            sheet,
+            row: start_row,
+            column: start_column,
+            width: end_column - start_column + 1,
+            height: end_row - start_row + 1,
+        };
+        self.model.range_clear_contents(&range).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "insertRow")]
+    pub fn insert_row(&mut self, sheet: u32, row: i32) -> Result<(), JsError> {
+        self.model.insert_row(sheet, row).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "insertColumn")]
+    pub fn insert_column(&mut self, sheet: u32, column: i32) -> Result<(), JsError> {
+        self.model.insert_column(sheet, column).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteRow")]
+    pub fn delete_row(&mut self, sheet: u32, row: i32) -> Result<(), JsError> {
+        self.model.delete_row(sheet, row).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteColumn")]
+    pub fn delete_column(&mut self, sheet: u32, column: i32) -> Result<(), JsError> {
+        self.model.delete_column(sheet, column).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "moveRows")]
+    pub fn move_rows(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        row_count: i32,
+        delta: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .move_rows(sheet, row, row_count, delta)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "moveColumns")]
+    pub fn move_columns(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        column_count: i32,
+        delta: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .move_columns(sheet, column, column_count, delta)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "moveRange")]
+    pub fn move_range(
+        &mut self,
+        area: JsValue,
+        target_row: i32,
+        target_column: i32,
+    ) -> Result<(), JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .move_range(&area, target_row, target_column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setRowHeight")]
+    pub fn set_row_height(&mut self, sheet: u32, row: i32, height: f64) -> Result<(), JsError> {
+        self.model
+            .set_row_height(sheet, row, height)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setColumnWidth")]
+    pub fn set_column_width(&mut self, sheet: u32, column: i32, width: f64) -> Result<(), JsError> {
+        self.model
+            .set_column_width(sheet, column, width)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getRowHeight")]
+    pub fn get_row_height(&mut self, sheet: u32, row: i32) -> Result<f64, JsError> {
+        self.model.get_row_height(sheet, row).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getColumnWidth")]
+    pub fn get_column_width(&mut self, sheet: u32, column: i32) -> Result<f64, JsError> {
+        self.model
+            .get_column_width(sheet, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "isRowHidden")]
+    pub fn is_row_hidden(&self, sheet: u32, row: i32) -> Result<bool, JsError> {
+        self.model.is_row_hidden(sheet, row).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setRowsHidden")]
+    pub fn set_rows_hidden(
+        &mut self,
+        sheet: u32,
+        row_start: i32,
+        row_end: i32,
+        hidden: bool,
+    ) -> Result<(), JsError> {
+        self.model
+            .set_rows_hidden(sheet, row_start, row_end, hidden)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getRowOutlineLevel")]
+    pub fn get_row_outline_level(&self, sheet: u32, row: i32) -> Result<u8, JsError> {
+        self.model
+            .get_row_outline_level(sheet, row)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "isRowCollapsed")]
+    pub fn is_row_collapsed(&self, sheet: u32, row: i32) -> Result<bool, JsError> {
+        self.model.is_row_collapsed(sheet, row).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "groupRows")]
+    pub fn group_rows(&mut self, sheet: u32, row_start: i32, row_end: i32) -> Result<(), JsError> {
+        self.model
+            .group_rows(sheet, row_start, row_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "ungroupRows")]
+    pub fn ungroup_rows(
+        &mut self,
+        sheet: u32,
+        row_start: i32,
+        row_end: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .ungroup_rows(sheet, row_start, row_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "collapseRowGroup")]
+    pub fn collapse_row_group(&mut self, sheet: u32, row: i32) -> Result<(), JsError> {
+        self.model
+            .collapse_row_group(sheet, row)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "expandRowGroup")]
+    pub fn expand_row_group(&mut self, sheet: u32, row: i32) -> Result<(), JsError> {
+        self.model.expand_row_group(sheet, row).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "isColumnHidden")]
+    pub fn is_column_hidden(&self, sheet: u32, column: i32) -> Result<bool, JsError> {
+        self.model
+            .is_column_hidden(sheet, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setColumnsHidden")]
+    pub fn set_columns_hidden(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+        hidden: bool,
+    ) -> Result<(), JsError> {
+        self.model
+            .set_columns_hidden(sheet, column_start, column_end, hidden)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getColumnOutlineLevel")]
+    pub fn get_column_outline_level(&self, sheet: u32, column: i32) -> Result<u8, JsError> {
+        self.model
+            .get_column_outline_level(sheet, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "isColumnCollapsed")]
+    pub fn is_column_collapsed(&self, sheet: u32, column: i32) -> Result<bool, JsError> {
+        self.model
+            .is_column_collapsed(sheet, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "groupColumns")]
+    pub fn group_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .group_columns(sheet, column_start, column_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "ungroupColumns")]
+    pub fn ungroup_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .ungroup_columns(sheet, column_start, column_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "collapseColumnGroup")]
+    pub fn collapse_column_group(&mut self, sheet: u32, column: i32) -> Result<(), JsError> {
+        self.model
+            .collapse_column_group(sheet, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "expandColumnGroup")]
+    pub fn expand_column_group(&mut self, sheet: u32, column: i32) -> Result<(), JsError> {
+        self.model
+            .expand_column_group(sheet, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setUserInput")]
+    pub fn set_user_input(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        input: &str,
+    ) -> Result<(), JsError> {
+        self.model
+            .set_user_input(sheet, row, column, input)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getFormattedCellValue")]
+    pub fn get_formatted_cell_value(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<String, JsError> {
+        self.model
+            .get_formatted_cell_value(sheet, row, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getFrozenRowsCount")]
+    pub fn get_frozen_rows_count(&self, sheet: u32) -> Result<i32, JsError> {
+        self.model.get_frozen_rows_count(sheet).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getFrozenColumnsCount")]
+    pub fn get_frozen_columns_count(&self, sheet: u32) -> Result<i32, JsError> {
+        self.model
+            .get_frozen_columns_count(sheet)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setFrozenRowsCount")]
+    pub fn set_frozen_rows_count(&mut self, sheet: u32, count: i32) -> Result<(), JsError> {
+        self.model
+            .set_frozen_rows_count(sheet, count)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setFrozenColumnsCount")]
+    pub fn set_frozen_columns_count(&mut self, sheet: u32, count: i32) -> Result<(), JsError> {
+        self.model
+            .set_frozen_columns_count(sheet, count)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "updateRangeStyle")]
+    pub fn update_range_style(
+        &mut self,
+        range: JsValue,
+        style_path: &str,
+        value: &str,
+    ) -> Result<(), JsError> {
+        let range: Area =
+            serde_wasm_bindgen::from_value(range).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .update_range_style(&range, style_path, value)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getCellStyle")]
+    pub fn get_cell_style(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        self.model
+            .get_cell_style(sheet, row, column)
+            .map_err(to_js_error)
+            .map(|x| serde_wasm_bindgen::to_value(&x).unwrap())
+    }
+
+    #[wasm_bindgen(js_name = "getEffectiveCellStyle")]
+    pub fn get_effective_cell_style(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        self.model
+            .get_effective_cell_style(sheet, row, column)
+            .map_err(to_js_error)
+            .map(|x| serde_wasm_bindgen::to_value(&x).unwrap())
+    }
+
+    #[wasm_bindgen(js_name = "getNamedStyles")]
+    pub fn get_named_styles(&self) -> Vec<String> {
+        self.model.get_named_styles()
+    }
+
+    #[wasm_bindgen(js_name = "getNamedStyle")]
+    pub fn get_named_style(&self, style_name: &str) -> Result<JsValue, JsError> {
+        let style = self
+            .model
+            .get_named_style(style_name)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&style).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getCellStyleName")]
+    pub fn get_cell_style_name(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<String, JsError> {
+        self.model
+            .get_cell_style_name(sheet, row, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "applyNamedStyle")]
+    pub fn apply_named_style(&mut self, area: JsValue, style_name: &str) -> Result<(), JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .apply_named_style(&area, style_name)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "createNamedStyle")]
+    pub fn create_named_style(&mut self, style_name: &str, style: JsValue) -> Result<(), JsError> {
+        let style: Style =
+            serde_wasm_bindgen::from_value(style).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .create_named_style(style_name, &style)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setNamedStyle")]
+    pub fn set_named_style(&mut self, style_name: &str, style: JsValue) -> Result<(), JsError> {
+        let style: Style =
+            serde_wasm_bindgen::from_value(style).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .set_named_style(style_name, &style)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getTheme")]
+    pub fn get_theme(&self) -> Result<JsValue, JsError> {
+        serde_wasm_bindgen::to_value(self.model.get_theme()).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "setTheme")]
+    pub fn set_theme(&mut self, theme: JsValue) -> Result<(), JsError> {
+        let theme: Theme =
+            serde_wasm_bindgen::from_value(theme).map_err(|e| to_js_error(e.to_string()))?;
+        self.model.set_theme(&theme).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getConditionalFormatResult")]
+    pub fn get_conditional_format_result(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        serde_wasm_bindgen::to_value(&self.model.get_conditional_format_result(sheet, row, column))
+            .map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getConditionalFormats")]
+    pub fn get_conditional_formats(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let conditional_formats = self
+            .model
+            .get_conditional_formats(sheet)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&conditional_formats).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "addConditionalFormat")]
+    pub fn add_conditional_format(
+        &mut self,
+        sheet: u32,
+        conditional_format: JsValue,
+    ) -> Result<(), JsError> {
+        let conditional_format: ConditionalFormat =
+            serde_wasm_bindgen::from_value(conditional_format)
+                .map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .add_conditional_format(sheet, conditional_format)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setConditionalFormat")]
+    pub fn set_conditional_format(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        conditional_format: JsValue,
+    ) -> Result<(), JsError> {
+        let conditional_format: ConditionalFormat =
+            serde_wasm_bindgen::from_value(conditional_format)
+                .map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .set_conditional_format(sheet, index, conditional_format)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteConditionalFormat")]
+    pub fn delete_conditional_format(&mut self, sheet: u32, index: usize) -> Result<(), JsError> {
+        self.model
+            .delete_conditional_format(sheet, index)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getDataValidations")]
+    pub fn get_data_validations(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let data_validations = self
+            .model
+            .get_data_validations(sheet)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&data_validations).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getDataValidationForCell")]
+    pub fn get_data_validation_for_cell(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        let data_validation = self
+            .model
+            .get_data_validation_for_cell(sheet, row, column)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&data_validation).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "checkDataValidation")]
+    pub fn check_data_validation(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        let data_validation = self
+            .model
+            .check_data_validation(sheet, row, column)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&data_validation).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getDataValidationOptions")]
+    pub fn get_data_validation_options(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Vec<String>, JsError> {
+        self.model
+            .get_data_validation_options(sheet, row, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "addDataValidation")]
+    pub fn add_data_validation(
+        &mut self,
+        sheet: u32,
+        data_validation: JsValue,
+    ) -> Result<(), JsError> {
+        let data_validation: DataValidation = serde_wasm_bindgen::from_value(data_validation)
+            .map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .add_data_validation(sheet, data_validation)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setDataValidation")]
+    pub fn set_data_validation(
+        &mut self,
+        sheet: u32,
+        index: usize,
+        data_validation: JsValue,
+    ) -> Result<(), JsError> {
+        let data_validation: DataValidation = serde_wasm_bindgen::from_value(data_validation)
+            .map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .set_data_validation(sheet, index, data_validation)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteDataValidation")]
+    pub fn delete_data_validation(&mut self, sheet: u32, index: usize) -> Result<(), JsError> {
+        self.model
+            .delete_data_validation(sheet, index)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getMergedCells")]
+    pub fn get_merged_cells(&self, sheet: u32) -> Result<Vec<String>, JsError> {
+        self.model.get_merged_cells(sheet).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "mergeCells")]
+    pub fn merge_cells(
+        &mut self,
+        sheet: u32,
+        start_row: i32,
+        start_column: i32,
+        end_row: i32,
+        end_column: i32,
+    ) -> Result<(), JsError> {
+        let range = Area {
+            sheet,
+            row: start_row,
+            column: start_column,
+            width: end_column - start_column + 1,
+            height: end_row - start_row + 1,
+        };
+        self.model.merge_cells(&range).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "unmergeCells")]
+    pub fn unmerge_cells(
+        &mut self,
+        sheet: u32,
+        start_row: i32,
+        start_column: i32,
+        end_row: i32,
+        end_column: i32,
+    ) -> Result<(), JsError> {
+        let range = Area {
+            sheet,
+            row: start_row,
+            column: start_column,
+            width: end_column - start_column + 1,
+            height: end_row - start_row + 1,
+        };
+        self.model.unmerge_cells(&range).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getComments")]
+    pub fn get_comments(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let comments = self.model.get_comments(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&comments).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getComment")]
+    pub fn get_comment(&self, sheet: u32, row: i32, column: i32) -> Result<JsValue, JsError> {
+        let comment = self
+            .model
+            .get_comment(sheet, row, column)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&comment).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "addComment")]
+    pub fn add_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> Result<(), JsError> {
+        self.model
+            .add_comment(sheet, row, column, text, author_name, author_id)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "editComment")]
+    pub fn edit_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+    ) -> Result<(), JsError> {
+        self.model
+            .edit_comment(sheet, row, column, text)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "resolveComment")]
+    pub fn resolve_comment(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        resolved: bool,
+    ) -> Result<(), JsError> {
+        self.model
+            .resolve_comment(sheet, row, column, resolved)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "addCommentReply")]
+    pub fn add_comment_reply(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        text: &str,
+        author_name: &str,
+        author_id: Option<String>,
+    ) -> Result<(), JsError> {
+        self.model
+            .add_comment_reply(sheet, row, column, text, author_name, author_id)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteComment")]
+    pub fn delete_comment(&mut self, sheet: u32, row: i32, column: i32) -> Result<(), JsError> {
+        self.model
+            .delete_comment(sheet, row, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getHyperlinks")]
+    pub fn get_hyperlinks(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let hyperlinks = self.model.get_hyperlinks(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&hyperlinks).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getHyperlink")]
+    pub fn get_hyperlink(&self, sheet: u32, row: i32, column: i32) -> Result<JsValue, JsError> {
+        let hyperlink = self
+            .model
+            .get_hyperlink(sheet, row, column)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&hyperlink).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getCellHyperlink")]
+    pub fn get_cell_hyperlink(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        let hyperlink = self
+            .model
+            .get_cell_hyperlink(sheet, row, column)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&hyperlink).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "setHyperlink")]
+    pub fn set_hyperlink(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        url: Option<String>,
+        location: Option<String>,
+        tooltip: Option<String>,
+    ) -> Result<(), JsError> {
+        self.model
+            .set_hyperlink(sheet, row, column, url, location, tooltip)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteHyperlink")]
+    pub fn delete_hyperlink(&mut self, sheet: u32, row: i32, column: i32) -> Result<(), JsError> {
+        self.model
+            .delete_hyperlink(sheet, row, column)
+            .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "getCellContent")]
-    pub fn get_cell_content(&self, sheet: u32, row: i32, column: i32) -> Result<String, JsError> {
+    #[wasm_bindgen(js_name = "getSheetProtection")]
+    pub fn get_sheet_protection(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let protection = self
+            .model
+            .get_sheet_protection(sheet)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&protection).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "protectSheet")]
+    pub fn protect_sheet(
+        &mut self,
+        sheet: u32,
+        protection: JsValue,
+        password: Option<String>,
+    ) -> Result<(), JsError> {
+        let protection: SheetProtection =
+            serde_wasm_bindgen::from_value(protection).map_err(|e| to_js_error(e.to_string()))?;
         self.model
-            .get_cell_content(sheet, row, column)
+            .protect_sheet(sheet, protection, password.as_deref())
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "newSheet")]
-    pub fn new_sheet(&mut self) -> Result<(), JsError> {
-        self.model.new_sheet().map_err(to_js_error)
+    #[wasm_bindgen(js_name = "unprotectSheet")]
+    pub fn unprotect_sheet(&mut self, sheet: u32, password: Option<String>) -> Result<(), JsError> {
+        self.model
+            .unprotect_sheet(sheet, password.as_deref())
+            .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "deleteSheet")]
-    pub fn delete_sheet(&mut self, sheet: u32) -> Result<(), JsError> {
-        self.model.delete_sheet(sheet).map_err(to_js_error)
+    #[wasm_bindgen(js_name = "isCellEditable")]
+    pub fn is_cell_editable(&self, sheet: u32, row: i32, column: i32) -> Result<bool, JsError> {
+        self.model
+            .is_cell_editable(sheet, row, column)
+            .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "renameSheet")]
-    pub fn rename_sheet(&mut self, sheet: u32, name: &str) -> Result<(), JsError> {
-        self.model.rename_sheet(sheet, name).map_err(to_js_error)
+    #[wasm_bindgen(js_name = "getWorkbookProtection")]
+    pub fn get_workbook_protection(&self) -> Result<JsValue, JsError> {
+        serde_wasm_bindgen::to_value(&self.model.get_workbook_protection()).map_err(JsError::from)
     }
 
-    #[wasm_bindgen(js_name = "setSheetColor")]
-    pub fn set_sheet_color(&mut self, sheet: u32, color: &str) -> Result<(), JsError> {
+    #[wasm_bindgen(js_name = "protectWorkbook")]
+    pub fn protect_workbook(
+        &mut self,
+        lock_structure: bool,
+        lock_windows: bool,
+        password: Option<String>,
+    ) -> Result<(), JsError> {
         self.model
-            .set_sheet_color(sheet, color)
+            .protect_workbook(lock_structure, lock_windows, password.as_deref())
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "rangeClearAll")]
-    pub fn range_clear_all(
+    #[wasm_bindgen(js_name = "unprotectWorkbook")]
+    pub fn unprotect_workbook(&mut self, password: Option<String>) -> Result<(), JsError> {
+        self.model
+            .unprotect_workbook(password.as_deref())
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getAutoFilter")]
+    pub fn get_auto_filter(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let auto_filter = self.model.get_auto_filter(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&auto_filter).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "addAutoFilter")]
+    pub fn add_auto_filter(
         &mut self,
         sheet: u32,
         start_row: i32,
@@ -134,18 +1007,72 @@ impl Model {
             width: end_column - start_column + 1,
             height: end_row - start_row + 1,
         };
-        self.model.range_clear_all(&range).map_err(to_js_error)
+        self.model.add_auto_filter(&range).map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "rangeClearContents")]
-    pub fn range_clear_contents(
+    #[wasm_bindgen(js_name = "removeAutoFilter")]
+    pub fn remove_auto_filter(&mut self, sheet: u32) -> Result<(), JsError> {
+        self.model.remove_auto_filter(sheet).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "setAutoFilterColumn")]
+    pub fn set_auto_filter_column(
+        &mut self,
+        sheet: u32,
+        column_id: u32,
+        filter: JsValue,
+    ) -> Result<(), JsError> {
+        let filter: Option<ColumnFilter> =
+            serde_wasm_bindgen::from_value(filter).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .set_auto_filter_column(sheet, column_id, filter)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "reapplyAutoFilter")]
+    pub fn reapply_auto_filter(&mut self, sheet: u32) -> Result<(), JsError> {
+        self.model.reapply_auto_filter(sheet).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getTableFilter")]
+    pub fn get_table_filter(&self, table: &str) -> Result<JsValue, JsError> {
+        let filter_columns = self.model.get_table_filter(table).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&filter_columns).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "setTableFilterColumn")]
+    pub fn set_table_filter_column(
+        &mut self,
+        table: &str,
+        column_id: u32,
+        filter: JsValue,
+    ) -> Result<(), JsError> {
+        let filter: Option<ColumnFilter> =
+            serde_wasm_bindgen::from_value(filter).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .set_table_filter_column(table, column_id, filter)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "reapplyTableFilter")]
+    pub fn reapply_table_filter(&mut self, table: &str) -> Result<(), JsError> {
+        self.model.reapply_table_filter(table).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "sortRange")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn sort_range(
         &mut self,
         sheet: u32,
         start_row: i32,
         start_column: i32,
         end_row: i32,
         end_column: i32,
+        keys: JsValue,
+        has_header: bool,
     ) -> Result<(), JsError> {
+        let keys: Vec<SortKey> =
+            serde_wasm_bindgen::from_value(keys).map_err(|e| to_js_error(e.to_string()))?;
         let range = Area {
             sheet,
             row: start_row,
@@ -153,131 +1080,218 @@ impl Model {
             width: end_column - start_column + 1,
             height: end_row - start_row + 1,
         };
-        self.model.range_clear_contents(&range).map_err(to_js_error)
-    }
-
-    #[wasm_bindgen(js_name = "insertRow")]
-    pub fn insert_row(&mut self, sheet: u32, row: i32) -> Result<(), JsError> {
-        self.model.insert_row(sheet, row).map_err(to_js_error)
-    }
-
-    #[wasm_bindgen(js_name = "insertColumn")]
-    pub fn insert_column(&mut self, sheet: u32, column: i32) -> Result<(), JsError> {
-        self.model.insert_column(sheet, column).map_err(to_js_error)
-    }
-
-    #[wasm_bindgen(js_name = "deleteRow")]
-    pub fn delete_row(&mut self, sheet: u32, row: i32) -> Result<(), JsError> {
-        self.model.delete_row(sheet, row).map_err(to_js_error)
+        self.model
+            .sort_range(&range, &keys, has_header)
+            .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "deleteColumn")]
-    pub fn delete_column(&mut self, sheet: u32, column: i32) -> Result<(), JsError> {
-        self.model.delete_column(sheet, column).map_err(to_js_error)
+    #[wasm_bindgen(js_name = "sortTable")]
+    pub fn sort_table(&mut self, table: &str, keys: JsValue) -> Result<(), JsError> {
+        let keys: Vec<SortKey> =
+            serde_wasm_bindgen::from_value(keys).map_err(|e| to_js_error(e.to_string()))?;
+        self.model.sort_table(table, &keys).map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "setRowHeight")]
-    pub fn set_row_height(&mut self, sheet: u32, row: i32, height: f64) -> Result<(), JsError> {
+    #[wasm_bindgen(js_name = "removeDuplicates")]
+    pub fn remove_duplicates(
+        &mut self,
+        area: JsValue,
+        key_columns: JsValue,
+        has_header: bool,
+    ) -> Result<usize, JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        let key_columns: Vec<i32> =
+            serde_wasm_bindgen::from_value(key_columns).map_err(|e| to_js_error(e.to_string()))?;
         self.model
-            .set_row_height(sheet, row, height)
+            .remove_duplicates(&area, &key_columns, has_header)
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "setColumnWidth")]
-    pub fn set_column_width(&mut self, sheet: u32, column: i32, width: f64) -> Result<(), JsError> {
+    #[wasm_bindgen(js_name = "textToColumns")]
+    pub fn text_to_columns(
+        &mut self,
+        area: JsValue,
+        split: JsValue,
+        column_formats: JsValue,
+    ) -> Result<(), JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        let split: TextSplit =
+            serde_wasm_bindgen::from_value(split).map_err(|e| to_js_error(e.to_string()))?;
+        let column_formats: Vec<TextColumnFormat> = serde_wasm_bindgen::from_value(column_formats)
+            .map_err(|e| to_js_error(e.to_string()))?;
         self.model
-            .set_column_width(sheet, column, width)
+            .text_to_columns(&area, &split, &column_formats)
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "getRowHeight")]
-    pub fn get_row_height(&mut self, sheet: u32, row: i32) -> Result<f64, JsError> {
-        self.model.get_row_height(sheet, row).map_err(to_js_error)
+    #[wasm_bindgen(js_name = "getTables")]
+    pub fn get_tables(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let tables = self.model.get_tables(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&tables).map_err(JsError::from)
     }
 
-    #[wasm_bindgen(js_name = "getColumnWidth")]
-    pub fn get_column_width(&mut self, sheet: u32, column: i32) -> Result<f64, JsError> {
-        self.model
-            .get_column_width(sheet, column)
-            .map_err(to_js_error)
+    #[wasm_bindgen(js_name = "getTable")]
+    pub fn get_table(&self, name: &str) -> Result<JsValue, JsError> {
+        let table = self.model.get_table(name).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&table).map_err(JsError::from)
     }
 
-    #[wasm_bindgen(js_name = "setUserInput")]
-    pub fn set_user_input(
+    #[wasm_bindgen(js_name = "addTable")]
+    pub fn add_table(
         &mut self,
         sheet: u32,
-        row: i32,
-        column: i32,
-        input: &str,
-    ) -> Result<(), JsError> {
+        start_row: i32,
+        start_column: i32,
+        end_row: i32,
+        end_column: i32,
+        has_header_row: bool,
+    ) -> Result<String, JsError> {
+        let range = Area {
+            sheet,
+            row: start_row,
+            column: start_column,
+            width: end_column - start_column + 1,
+            height: end_row - start_row + 1,
+        };
         self.model
-            .set_user_input(sheet, row, column, input)
+            .add_table(&range, has_header_row)
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "getFormattedCellValue")]
-    pub fn get_formatted_cell_value(
-        &self,
+    #[wasm_bindgen(js_name = "deleteTable")]
+    pub fn delete_table(&mut self, name: &str) -> Result<(), JsError> {
+        self.model.delete_table(name).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "renameTable")]
+    pub fn rename_table(&mut self, name: &str, new_name: &str) -> Result<(), JsError> {
+        self.model.rename_table(name, new_name).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "resizeTable")]
+    pub fn resize_table(
+        &mut self,
+        name: &str,
         sheet: u32,
-        row: i32,
-        column: i32,
-    ) -> Result<String, JsError> {
-        self.model
-            .get_formatted_cell_value(sheet, row, column)
-            .map_err(to_js_error)
+        start_row: i32,
+        start_column: i32,
+        end_row: i32,
+        end_column: i32,
+    ) -> Result<(), JsError> {
+        let range = Area {
+            sheet,
+            row: start_row,
+            column: start_column,
+            width: end_column - start_column + 1,
+            height: end_row - start_row + 1,
+        };
+        self.model.resize_table(name, &range).map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "getFrozenRowsCount")]
-    pub fn get_frozen_rows_count(&self, sheet: u32) -> Result<i32, JsError> {
-        self.model.get_frozen_rows_count(sheet).map_err(to_js_error)
+    #[wasm_bindgen(js_name = "addTableColumn")]
+    pub fn add_table_column(&mut self, name: &str) -> Result<(), JsError> {
+        self.model.add_table_column(name).map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "getFrozenColumnsCount")]
-    pub fn get_frozen_columns_count(&self, sheet: u32) -> Result<i32, JsError> {
+    #[wasm_bindgen(js_name = "renameTableColumn")]
+    pub fn rename_table_column(
+        &mut self,
+        name: &str,
+        column: &str,
+        new_name: &str,
+    ) -> Result<(), JsError> {
         self.model
-            .get_frozen_columns_count(sheet)
+            .rename_table_column(name, column, new_name)
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "setFrozenRowsCount")]
-    pub fn set_frozen_rows_count(&mut self, sheet: u32, count: i32) -> Result<(), JsError> {
+    #[wasm_bindgen(js_name = "setTableHeaderRow")]
+    pub fn set_table_header_row(&mut self, name: &str, show: bool) -> Result<(), JsError> {
         self.model
-            .set_frozen_rows_count(sheet, count)
+            .set_table_header_row(name, show)
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "setFrozenColumnsCount")]
-    pub fn set_frozen_columns_count(&mut self, sheet: u32, count: i32) -> Result<(), JsError> {
+    #[wasm_bindgen(js_name = "setTableTotalsRow")]
+    pub fn set_table_totals_row(&mut self, name: &str, show: bool) -> Result<(), JsError> {
         self.model
-            .set_frozen_columns_count(sheet, count)
+            .set_table_totals_row(name, show)
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "updateRangeStyle")]
-    pub fn update_range_style(
+    #[wasm_bindgen(js_name = "setTableTotalsRowFunction")]
+    pub fn set_table_totals_row_function(
         &mut self,
-        range: JsValue,
-        style_path: &str,
-        value: &str,
+        name: &str,
+        column: &str,
+        totals_function: JsValue,
     ) -> Result<(), JsError> {
-        let range: Area =
-            serde_wasm_bindgen::from_value(range).map_err(|e| to_js_error(e.to_string()))?;
+        let totals_function: Option<TotalsRowFunction> =
+            serde_wasm_bindgen::from_value(totals_function)
+                .map_err(|e| to_js_error(e.to_string()))?;
         self.model
-            .update_range_style(&range, style_path, value)
+            .set_table_totals_row_function(name, column, totals_function)
             .map_err(to_js_error)
     }
 
-    #[wasm_bindgen(js_name = "getCellStyle")]
-    pub fn get_cell_style(
+    #[wasm_bindgen(js_name = "getPivotTables")]
+    pub fn get_pivot_tables(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let pivot_tables = self.model.get_pivot_tables(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&pivot_tables).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getPivotTable")]
+    pub fn get_pivot_table(&self, sheet: u32, name: &str) -> Result<JsValue, JsError> {
+        let pivot_table = self
+            .model
+            .get_pivot_table(sheet, name)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&pivot_table).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "addPivotTable")]
+    pub fn add_pivot_table(
         &mut self,
         sheet: u32,
         row: i32,
         column: i32,
-    ) -> Result<JsValue, JsError> {
+        source: JsValue,
+    ) -> Result<String, JsError> {
+        let source: PivotSource =
+            serde_wasm_bindgen::from_value(source).map_err(|e| to_js_error(e.to_string()))?;
         self.model
-            .get_cell_style(sheet, row, column)
+            .add_pivot_table(&source, sheet, row, column)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "updatePivotTable")]
+    pub fn update_pivot_table(
+        &mut self,
+        sheet: u32,
+        name: &str,
+        pivot_table: JsValue,
+    ) -> Result<(), JsError> {
+        let pivot_table: PivotTable =
+            serde_wasm_bindgen::from_value(pivot_table).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .update_pivot_table(sheet, name, pivot_table)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "refreshPivotTable")]
+    pub fn refresh_pivot_table(&mut self, sheet: u32, name: &str) -> Result<(), JsError> {
+        self.model
+            .refresh_pivot_table(sheet, name)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deletePivotTable")]
+    pub fn delete_pivot_table(&mut self, sheet: u32, name: &str) -> Result<(), JsError> {
+        self.model
+            .delete_pivot_table(sheet, name)
             .map_err(to_js_error)
-            .map(|x| serde_wasm_bindgen::to_value(&x).unwrap())
     }
 
     #[wasm_bindgen(js_name = "onPasteStyles")]
@@ -377,6 +1391,27 @@ impl Model {
         self.model.get_show_grid_lines(sheet).map_err(to_js_error)
     }
 
+    #[wasm_bindgen(js_name = "setDate1904")]
+    pub fn set_date_1904(&mut self, date_1904: bool) {
+        self.model.set_date_1904(date_1904)
+    }
+
+    #[wasm_bindgen(js_name = "isDate1904")]
+    pub fn is_date_1904(&self) -> bool {
+        self.model.is_date_1904()
+    }
+
+    #[wasm_bindgen(js_name = "setPrecisionAsDisplayed")]
+    pub fn set_precision_as_displayed(&mut self, precision_as_displayed: bool) {
+        self.model
+            .set_precision_as_displayed(precision_as_displayed)
+    }
+
+    #[wasm_bindgen(js_name = "isPrecisionAsDisplayed")]
+    pub fn is_precision_as_displayed(&self) -> bool {
+        self.model.is_precision_as_displayed()
+    }
+
     #[wasm_bindgen(js_name = "autoFillRows")]
     pub fn auto_fill_rows(&mut self, source_area: JsValue, to_row: i32) -> Result<(), JsError> {
         let area: Area =
@@ -386,6 +1421,80 @@ impl Model {
             .map_err(to_js_error)
     }
 
+    #[wasm_bindgen(js_name = "fillSeries")]
+    pub fn fill_series(
+        &mut self,
+        area: JsValue,
+        down: bool,
+        series_type: JsValue,
+        step: f64,
+        stop: Option<f64>,
+    ) -> Result<(), JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        let series_type: SeriesType =
+            serde_wasm_bindgen::from_value(series_type).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .fill_series(&area, down, series_type, step, stop)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "findNext")]
+    pub fn find_next(&mut self, text: &str, options: JsValue) -> Result<JsValue, JsError> {
+        let options: FindOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        let cell = self.model.find_next(text, &options).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&cell).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "findPrevious")]
+    pub fn find_previous(&mut self, text: &str, options: JsValue) -> Result<JsValue, JsError> {
+        let options: FindOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        let cell = self
+            .model
+            .find_previous(text, &options)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&cell).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "findAll")]
+    pub fn find_all(&self, text: &str, options: JsValue) -> Result<JsValue, JsError> {
+        let options: FindOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        let cells = self.model.find_all(text, &options).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&cells).map_err(JsError::from)
+    }
+
+    pub fn replace(
+        &mut self,
+        text: &str,
+        replacement: &str,
+        options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let options: FindOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        let cell = self
+            .model
+            .replace(text, replacement, &options)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&cell).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "replaceAll")]
+    pub fn replace_all(
+        &mut self,
+        text: &str,
+        replacement: &str,
+        options: JsValue,
+    ) -> Result<usize, JsError> {
+        let options: FindOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .replace_all(text, replacement, &options)
+            .map_err(to_js_error)
+    }
+
     #[wasm_bindgen(js_name = "autoFillColumns")]
     pub fn auto_fill_columns(
         &mut self,
@@ -498,6 +1607,11 @@ impl Model {
         self.model.set_name(name);
     }
 
+    #[wasm_bindgen(js_name = "setLanguage")]
+    pub fn set_language(&mut self, language_id: &str) -> Result<(), JsError> {
+        self.model.set_language(language_id).map_err(to_js_error)
+    }
+
     #[wasm_bindgen(js_name = "copyToClipboard")]
     pub fn copy_to_clipboard(&self) -> Result<JsValue, JsError> {
         let data = self
@@ -523,6 +1637,24 @@ impl Model {
             .map_err(|e| to_js_error(e.to_string()))
     }
 
+    #[wasm_bindgen(js_name = "pasteSpecialFromClipboard")]
+    pub fn paste_special_from_clipboard(
+        &mut self,
+        source_range: JsValue,
+        clipboard: JsValue,
+        options: JsValue,
+    ) -> Result<(), JsError> {
+        let source_range: (i32, i32, i32, i32) =
+            serde_wasm_bindgen::from_value(source_range).map_err(|e| to_js_error(e.to_string()))?;
+        let clipboard: ClipboardData =
+            serde_wasm_bindgen::from_value(clipboard).map_err(|e| to_js_error(e.to_string()))?;
+        let options: PasteOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .paste_special_from_clipboard(source_range, &clipboard, &options)
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
     #[wasm_bindgen(js_name = "pasteCsvText")]
     pub fn paste_csv_string(&mut self, area: JsValue, csv: &str) -> Result<(), JsError> {
         let range: Area =
@@ -115,10 +115,18 @@ export interface WorksheetProperties {
   sheet_id: number;
 }
 
+// The color of the theme a color of the styles comes from
+interface ThemeColor {
+  theme: number;
+  tint?: string;
+}
+
 interface CellStyleFill {
   pattern_type: string;
   fg_color?: string;
+  fg_color_theme?: ThemeColor;
   bg_color?: string;
+  bg_color_theme?: ThemeColor;
 }
 
 interface CellStyleFont {
@@ -128,9 +136,16 @@ interface CellStyleFont {
   strike: boolean;
   sz: number;
   color: string;
+  color_theme?: ThemeColor;
   name: string;
   family: number;
   scheme: string;
+  vert_align?: "superscript" | "subscript";
+}
+
+export interface TextRun {
+  text: string;
+  font?: CellStyleFont;
 }
 
 export interface BorderOptions {
@@ -152,6 +167,7 @@ export enum BorderStyle {
 interface BorderItem {
   style: string;
   color: string;
+  color_theme?: ThemeColor;
 }
 
 interface CellStyleBorder {
@@ -195,6 +211,12 @@ export interface CellStyle {
   border: CellStyleBorder;
   num_fmt: string;
   alignment?: Alignment;
+  protection?: Protection;
+}
+
+interface Protection {
+  locked?: boolean;
+  hidden?: boolean;
 }
 
 export interface SelectedView {
@@ -217,11 +239,387 @@ type ClipboardData = Map<number, Map <number, ClipboardCell>>;
 
 export interface ClipboardCell {
   text: string;
+  value: string;
   style: CellStyle;
+  merge?: [number, number];
+  column_width?: number;
 }
 
 export interface Clipboard {
   csv: string;
   data: ClipboardData;
   range: [number, number, number, number];
-}
\ No newline at end of file
+}
+
+export type PasteMode =
+  | "all"
+  | "values"
+  | "formulas"
+  | "formats"
+  | "columnWidths";
+
+export type PasteOperation =
+  | "none"
+  | "add"
+  | "subtract"
+  | "multiply"
+  | "divide";
+
+export interface PasteOptions {
+  mode: PasteMode;
+  operation: PasteOperation;
+  skip_blanks: boolean;
+  transpose: boolean;
+}
+
+export interface CommentReply {
+  text: string;
+  author_name: string;
+  author_id?: string;
+  timestamp?: number;
+}
+
+export interface Comment {
+  text: string;
+  author_name: string;
+  author_id?: string;
+  cell_ref: string;
+  timestamp?: number;
+  resolved: boolean;
+  replies: CommentReply[];
+}
+
+export interface Hyperlink {
+  cell_ref: string;
+  url?: string;
+  location?: string;
+  tooltip?: string;
+}
+
+export type PasswordHash =
+  | { legacy: string }
+  | {
+      salted: {
+        algorithm_name: string;
+        hash_value: string;
+        salt_value: string;
+        spin_count: number;
+      };
+    };
+
+export interface SheetProtection {
+  password_hash?: PasswordHash;
+  allow_select_locked_cells: boolean;
+  allow_select_unlocked_cells: boolean;
+  allow_format_cells: boolean;
+  allow_format_columns: boolean;
+  allow_format_rows: boolean;
+  allow_insert_columns: boolean;
+  allow_insert_rows: boolean;
+  allow_insert_hyperlinks: boolean;
+  allow_delete_columns: boolean;
+  allow_delete_rows: boolean;
+  allow_sort: boolean;
+  allow_auto_filter: boolean;
+  allow_pivot_tables: boolean;
+  allow_edit_objects: boolean;
+  allow_edit_scenarios: boolean;
+}
+
+export interface WorkbookProtection {
+  password_hash?: PasswordHash;
+  lock_structure: boolean;
+  lock_windows: boolean;
+}
+
+export type FilterOperator =
+  | "equal"
+  | "notEqual"
+  | "greaterThan"
+  | "greaterThanOrEqual"
+  | "lessThan"
+  | "lessThanOrEqual";
+
+export interface DateGroup {
+  grouping: "year" | "month" | "day" | "hour" | "minute" | "second";
+  year: number;
+  month: number;
+  day: number;
+  hour: number;
+  minute: number;
+  second: number;
+}
+
+export type ColumnFilter =
+  | {
+      type: "values";
+      values: string[];
+      date_groups: DateGroup[];
+      blank: boolean;
+    }
+  | {
+      type: "custom";
+      conditions: { operator: FilterOperator; value: string }[];
+      and: boolean;
+    }
+  | { type: "top10"; rank: number; percent: boolean; bottom: boolean }
+  | { type: "color"; color: string; cell_color: boolean };
+
+export interface FilterColumn {
+  column_id: number;
+  filter: ColumnFilter;
+}
+
+export interface AutoFilter {
+  reference: string;
+  columns: FilterColumn[];
+}
+
+export type SortBy =
+  | { type: "value" }
+  | { type: "cellColor"; color: string }
+  | { type: "customList"; list: string[] };
+
+export interface SortKey {
+  column: number;
+  descending: boolean;
+  sort_by: SortBy;
+}
+
+export type TotalsRowFunction =
+  | "average"
+  | "count"
+  | "countNums"
+  | "custom"
+  | "max"
+  | "min"
+  | "stdDev"
+  | "sum"
+  | "var";
+
+export interface TableColumn {
+  id: number;
+  name: string;
+  totals_row_label?: string;
+  header_row_dxf_id?: number;
+  data_dxf_id?: number;
+  totals_row_dxf_id?: number;
+  totals_row_function?: TotalsRowFunction;
+  totals_row_formula?: string;
+  calculated_column_formula?: string;
+}
+
+export interface TableStyleInfo {
+  name?: string;
+  show_first_column: boolean;
+  show_last_column: boolean;
+  show_row_stripes: boolean;
+  show_column_stripes: boolean;
+}
+
+export interface Table {
+  name: string;
+  display_name: string;
+  sheet_name: string;
+  reference: string;
+  totals_row_count: number;
+  header_row_count: number;
+  header_row_dxf_id?: number;
+  data_dxf_id?: number;
+  totals_row_dxf_id?: number;
+  columns: TableColumn[];
+  style_info: TableStyleInfo;
+  has_filters: boolean;
+  filter_columns: FilterColumn[];
+}
+
+export type PivotSource =
+  | { type: "range"; sheet_name: string; reference: string }
+  | { type: "table"; name: string };
+
+export type PivotFunction =
+  | "average"
+  | "count"
+  | "countNums"
+  | "max"
+  | "min"
+  | "product"
+  | "stdDev"
+  | "stdDevp"
+  | "sum"
+  | "var"
+  | "varp";
+
+export interface PivotValueField {
+  field: string;
+  function: PivotFunction;
+  name: string;
+}
+
+export interface PivotFilter {
+  field: string;
+  hidden_items: string[];
+}
+
+export interface PivotTableParts {
+  definition: string;
+  cache_definition: string;
+  cache_records?: string;
+}
+
+export interface PivotTable {
+  name: string;
+  source: PivotSource;
+  location: string;
+  reference?: string;
+  row_fields: string[];
+  column_fields: string[];
+  value_fields: PivotValueField[];
+  filters: PivotFilter[];
+  row_grand_totals: boolean;
+  column_grand_totals: boolean;
+  subtotals: boolean;
+  xlsx_parts?: PivotTableParts;
+}
+
+export type SeriesType =
+  | "linear"
+  | "growth"
+  | "day"
+  | "weekday"
+  | "month"
+  | "year";
+
+export type FindLookIn = "formulas" | "values";
+
+export type FindMode = "text" | "wildcard" | "regex";
+
+export type SearchOrder = "byRows" | "byColumns";
+
+export type FindWithin = "selection" | "sheet" | "workbook";
+
+export interface FindOptions {
+  look_in: FindLookIn;
+  match_entire_cell: boolean;
+  match_case: boolean;
+  mode: FindMode;
+  search_order: SearchOrder;
+  within: FindWithin;
+}
+
+export type TextSplit =
+  | {
+      type: "delimited";
+      delimiters: string;
+      treat_consecutive_as_one: boolean;
+      text_qualifier: string | null;
+    }
+  | { type: "fixedWidth"; breaks: number[] };
+
+export type TextColumnFormat = "general" | "text" | "skip";
+
+export interface Theme {
+  name: string;
+  // light 1, dark 1, light 2, dark 2, accent 1 to 6, hyperlink and followed hyperlink
+  colors: string[];
+  major_font: string;
+  minor_font: string;
+}
+
+export interface CellReference {
+  sheet: number;
+  row: number;
+  column: number;
+}
+
+export type CellIsOperator =
+  | "between"
+  | "notBetween"
+  | "equal"
+  | "notEqual"
+  | "greaterThan"
+  | "lessThan"
+  | "greaterThanOrEqual"
+  | "lessThanOrEqual";
+
+export interface Cfvo {
+  kind: "min" | "max" | "number" | "percent" | "percentile" | "formula";
+  value?: string;
+}
+
+export type ConditionalFormatRule =
+  | { type: "cellIs"; operator: CellIsOperator; formulas: string[] }
+  | { type: "expression"; formula: string }
+  | { type: "top10"; rank: number; percent: boolean; bottom: boolean }
+  | {
+      type: "aboveAverage";
+      above_average: boolean;
+      equal_average: boolean;
+      std_dev: number;
+    }
+  | { type: "duplicateValues" }
+  | { type: "uniqueValues" }
+  | { type: "colorScale"; cfvos: Cfvo[]; colors: string[] }
+  | { type: "dataBar"; min: Cfvo; max: Cfvo; color: string }
+  | {
+      type: "iconSet";
+      icon_set: string;
+      cfvos: Cfvo[];
+      reverse: boolean;
+      show_value: boolean;
+    };
+
+interface DxfFont {
+  b?: boolean;
+  i?: boolean;
+  u?: boolean;
+  strike?: boolean;
+  color?: string;
+}
+
+export interface Dxf {
+  num_fmt?: string;
+  font?: DxfFont;
+  fill_color?: string;
+  border?: CellStyleBorder;
+}
+
+export interface ConditionalFormat {
+  sqref: string;
+  rule: ConditionalFormatRule;
+  style?: Dxf;
+  stop_if_true: boolean;
+}
+
+export interface ConditionalFormatResult {
+  style?: Dxf;
+  fill_color?: string;
+  data_bar?: { color: string; length: number };
+  icon?: { icon_set: string; index: number; show_value: boolean };
+}
+
+export type DataValidationKind =
+  | "any"
+  | "whole"
+  | "decimal"
+  | "list"
+  | "date"
+  | "time"
+  | "textLength"
+  | "custom";
+
+export interface DataValidation {
+  sqref: string;
+  kind: DataValidationKind;
+  operator: CellIsOperator;
+  formulas: string[];
+  allow_blank: boolean;
+  show_dropdown: boolean;
+  show_input_message: boolean;
+  input_title?: string;
+  input_message?: string;
+  show_error_message: boolean;
+  error_style: "stop" | "warning" | "information";
+  error_title?: string;
+  error_message?: string;
+}
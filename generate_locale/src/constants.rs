@@ -1,21 +1,24 @@
+use std::collections::HashMap;
+
+use bitcode::Encode;
 use serde::{Deserialize, Serialize};
 
 pub const LOCAL_TYPE: &str = "modern"; // or "full"
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Encode)]
 pub struct Locale {
     pub dates: Dates,
     pub numbers: NumbersProperties,
     pub currency: Currency
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Encode)]
 pub struct Currency {
     pub iso: String,
     pub symbol: String,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Encode, Clone)]
 pub struct NumbersProperties {
     #[serde(rename = "symbols-numberSystem-latn")]
     pub symbols: NumbersSymbols,
@@ -25,16 +28,28 @@ pub struct NumbersProperties {
     pub currency_formats: CurrencyFormats,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Encode)]
 pub struct Dates {
     pub day_names: Vec<String>,
     pub day_names_short: Vec<String>,
     pub months: Vec<String>,
     pub months_short: Vec<String>,
     pub months_letter: Vec<String>,
+    pub date_formats: DateFormats,
+    /// 0 for Sunday, 1 for Monday, ...
+    pub first_day_of_week: u8,
+}
+
+// See: https://cldr.unicode.org/translation/date-time/date-time-patterns
+#[derive(Serialize, Deserialize, Encode)]
+pub struct DateFormats {
+    pub full: String,
+    pub long: String,
+    pub medium: String,
+    pub short: String,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Encode, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct NumbersSymbols {
     pub decimal: String,
@@ -55,7 +70,7 @@ pub struct NumbersSymbols {
 
 
 // See: https://cldr.unicode.org/translation/number-currency-formats/number-and-currency-patterns
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Encode, Clone)]
 pub struct CurrencyFormats {
     pub standard: String,
     #[serde(rename = "standard-alphaNextToNumber")]
@@ -71,8 +86,39 @@ pub struct CurrencyFormats {
     pub accounting_no_currency: String,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Encode, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DecimalFormats {
     pub standard: String,
 }
+
+// Languages are not part of the CLDR. They are maintained by hand in `language.json`
+
+#[derive(Deserialize, Encode)]
+pub struct Booleans {
+    pub r#true: String,
+    pub r#false: String,
+}
+
+#[derive(Deserialize, Encode)]
+pub struct Errors {
+    pub r#ref: String,
+    pub name: String,
+    pub value: String,
+    pub div: String,
+    pub na: String,
+    pub num: String,
+    pub nimpl: String,
+    pub spill: String,
+    pub calc: String,
+    pub circ: String,
+    pub error: String,
+    pub null: String,
+}
+
+#[derive(Deserialize, Encode)]
+pub struct Language {
+    pub booleans: Booleans,
+    pub errors: Errors,
+    pub functions: HashMap<String, String>,
+}
@@ -4,7 +4,7 @@ use std::fs;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::constants::{Dates, LOCAL_TYPE};
+use crate::constants::{DateFormats, Dates, LOCAL_TYPE};
 
 #[derive(Serialize, Deserialize)]
 struct CaGCalendarsFormat {
@@ -14,6 +14,8 @@ struct CaGCalendarsFormat {
 struct CaGCalendarsII {
     months: CaGCalendarsFormat,
     days: CaGCalendarsFormat,
+    #[serde(rename = "dateFormats")]
+    date_formats: HashMap<String, Value>,
 }
 #[derive(Serialize, Deserialize)]
 struct CaGCalendarsI {
@@ -36,17 +38,117 @@ struct CaGregorian {
     main: HashMap<String, CaGId>,
 }
 
-pub fn get_dates_formatting(cldr_dir: &str, locale_id: &str) -> Result<Dates, &'static str> {
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WeekDataI {
+    first_day: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WeekDataII {
+    week_data: WeekDataI,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WeekData {
+    supplemental: WeekDataII,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LikelySubtagsI {
+    likely_subtags: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LikelySubtags {
+    supplemental: LikelySubtagsI,
+}
+
+const DAY_INDEX: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+fn is_territory(subtag: &str) -> bool {
+    (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_uppercase()))
+        || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn find_territory(locale_id: &str) -> Option<String> {
+    locale_id
+        .split('-')
+        .skip(1)
+        .find(|subtag| is_territory(subtag))
+        .map(|subtag| subtag.to_string())
+}
+
+/// Returns the territory of a locale. If it is not explicit (`pt-BR`) we use the most likely one
+/// (`fr` is `fr-Latn-FR`) and fall back to the world (`001`).
+fn get_territory(cldr_dir: &str, locale_id: &str) -> Result<String, &'static str> {
+    if let Some(territory) = find_territory(locale_id) {
+        return Ok(territory);
+    }
+    let subtags_file = format!(
+        "{}cldr-json/cldr-core/supplemental/likelySubtags.json",
+        cldr_dir
+    );
+    let contents =
+        fs::read_to_string(subtags_file).or(Err("Failed reading 'likelySubtags' file"))?;
+    let likely_subtags: LikelySubtags =
+        serde_json::from_str(&contents).or(Err("Failed parsing 'likelySubtags' file"))?;
+    let territory = likely_subtags
+        .supplemental
+        .likely_subtags
+        .get(locale_id)
+        .and_then(|tags| find_territory(tags))
+        .unwrap_or_else(|| "001".to_string());
+    Ok(territory)
+}
+
+fn get_first_day_of_week(cldr_dir: &str, locale_id: &str) -> Result<u8, &'static str> {
+    let territory = get_territory(cldr_dir, locale_id)?;
+    let week_data_file = format!("{}cldr-json/cldr-core/supplemental/weekData.json", cldr_dir);
+    let contents = fs::read_to_string(week_data_file).or(Err("Failed reading 'weekData' file"))?;
+    let week_data: WeekData =
+        serde_json::from_str(&contents).or(Err("Failed parsing 'weekData' file"))?;
+    let first_day = &week_data.supplemental.week_data.first_day;
+    let day = first_day
+        .get(&territory)
+        .or_else(|| first_day.get("001"))
+        .ok_or("Missing first day of the week")?;
+    let index = DAY_INDEX
+        .iter()
+        .position(|d| d == day)
+        .ok_or("Unexpected first day of the week")?;
+    Ok(index as u8)
+}
+
+fn get_date_format(
+    date_formats: &HashMap<String, Value>,
+    length: &str,
+) -> Result<String, &'static str> {
+    date_formats
+        .get(length)
+        .and_then(|format| format.as_str())
+        .map(|format| format.to_string())
+        .ok_or("Missing date format")
+}
+
+/// `locale_id` is the IronCalc locale (`pt-BR`) and `cldr_id` the folder with its data in the CLDR (`pt`)
+pub fn get_dates_formatting(
+    cldr_dir: &str,
+    locale_id: &str,
+    cldr_id: &str,
+) -> Result<Dates, &'static str> {
     let calendar_file = format!(
         "{}cldr-json/cldr-dates-{}/main/{}/ca-gregorian.json",
-        cldr_dir, LOCAL_TYPE, locale_id
+        cldr_dir, LOCAL_TYPE, cldr_id
     );
 
     let contents =
         fs::read_to_string(calendar_file).or(Err("Failed reading 'ca-gregorian' file"))?;
     let ca_gregorian: CaGregorian =
         serde_json::from_str(&contents).or(Err("Failed parsing 'ca-gregorian' file"))?;
-    let gregorian = &ca_gregorian.main[locale_id].dates.calendars.gregorian;
+    let gregorian = &ca_gregorian.main[cldr_id].dates.calendars.gregorian;
     // See: http://cldr.unicode.org/translation/date-time-1/date-time-patterns
     // for the difference between stand-alone and format. We will use only the format mode
     let months_format = &gregorian.months.format;
@@ -67,17 +169,27 @@ pub fn get_dates_formatting(cldr_dir: &str, locale_id: &str) -> Result<Dates, &'
         months.push(months_format["wide"][index].to_owned());
     }
 
-    let day_index = vec!["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
-    for day in day_index {
+    for day in DAY_INDEX {
         day_names_short.push(days_format["abbreviated"][day].to_owned());
         day_names.push(days_format["wide"][day].to_owned());
     }
 
+    let date_formats = DateFormats {
+        full: get_date_format(&gregorian.date_formats, "full")?,
+        long: get_date_format(&gregorian.date_formats, "long")?,
+        medium: get_date_format(&gregorian.date_formats, "medium")?,
+        short: get_date_format(&gregorian.date_formats, "short")?,
+    };
+
+    let first_day_of_week = get_first_day_of_week(cldr_dir, locale_id)?;
+
     Ok(Dates {
         day_names,
         day_names_short,
         months,
         months_short,
         months_letter,
+        date_formats,
+        first_day_of_week,
     })
 }
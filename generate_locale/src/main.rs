@@ -1,7 +1,7 @@
 use std::fs;
 use std::{collections::HashMap, io::Write, path::PathBuf};
 
-use constants::{Locale, Currency};
+use constants::{Currency, Language, Locale};
 
 use clap::Parser;
 use numbers::get_numbers_formatting;
@@ -12,7 +12,7 @@ mod numbers;
 mod util;
 
 use dates::get_dates_formatting;
-use util::get_all_locales_id;
+use util::{get_all_locales_id, get_cldr_id};
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -22,29 +22,53 @@ pub struct Opt {
     locales: Option<PathBuf>,
 
     /// Folder with the cldr data
+    #[clap(long, value_parser, required_unless_present = "from-json")]
+    cldr_dir: Option<String>,
+
+    /// json file with the locale data (like a previous `--output`) to use instead of the cldr
     #[clap(long, value_parser)]
-    cldr_dir: String,
+    from_json: Option<PathBuf>,
 
     /// output json file with all locale info
     #[clap(long, value_parser)]
     output: PathBuf,
+
+    /// output folder with a bitcode file (`<locale>.bin`) for each locale
+    #[clap(long, value_parser)]
+    bin_dir: Option<PathBuf>,
+
+    /// json file with the languages
+    #[clap(long, value_parser, requires = "languages-output")]
+    languages: Option<PathBuf>,
+
+    /// output bitcode file with all the languages
+    #[clap(long, value_parser)]
+    languages_output: Option<PathBuf>,
 }
 
-fn main() -> Result<(), String> {
-    let opt = Opt::from_args();
-    let cldr_dir = opt.cldr_dir;
-    let locales_list: Vec<String> = if let Some(locales_path) = opt.locales {
+fn write_file(path: PathBuf, bytes: &[u8]) -> Result<(), String> {
+    let mut f = fs::File::create(path).or(Err("Failed to create file"))?;
+    f.write_all(bytes).or(Err("Failed writing"))?;
+    Ok(())
+}
+
+fn load_from_cldr(
+    cldr_dir: &str,
+    locales_path: Option<PathBuf>,
+) -> Result<HashMap<String, Locale>, String> {
+    let locales_list: Vec<String> = if let Some(locales_path) = locales_path {
         let contents = fs::read_to_string(locales_path).or(Err("Failed reading file"))?;
         serde_json::from_str(&contents).or(Err("Failed parsing file"))?
     } else {
-        get_all_locales_id(&cldr_dir)
+        get_all_locales_id(cldr_dir)
     };
 
     let mut locales = HashMap::new();
 
     for locale_id in locales_list {
-        let dates = get_dates_formatting(&cldr_dir, &locale_id)?;
-        let numbers = get_numbers_formatting(&cldr_dir, &locale_id)?;
+        let cldr_id = get_cldr_id(cldr_dir, &locale_id);
+        let dates = get_dates_formatting(cldr_dir, &locale_id, &cldr_id)?;
+        let numbers = get_numbers_formatting(cldr_dir, &locale_id, &cldr_id)?;
         // HACK: the currency is not a part of the cldr locale
         // We just stick here one and make this adaptable in the calc module for now
         let currency = Currency {
@@ -53,9 +77,39 @@ fn main() -> Result<(), String> {
         };
         locales.insert(locale_id, Locale { dates, numbers, currency });
     }
+    Ok(locales)
+}
+
+fn main() -> Result<(), String> {
+    let opt = Opt::from_args();
+    let locales: HashMap<String, Locale> = match (&opt.from_json, &opt.cldr_dir) {
+        (Some(json_path), _) => {
+            // Re-encodes the data of a previous run, e.g. after editing it by hand
+            let contents = fs::read_to_string(json_path).or(Err("Failed reading json file"))?;
+            serde_json::from_str(&contents).or(Err("Failed parsing json file"))?
+        }
+        (None, Some(cldr_dir)) => load_from_cldr(cldr_dir, opt.locales.clone())?,
+        (None, None) => return Err("Either the cldr folder or a json file is needed".to_string()),
+    };
+
+    if let Some(bin_dir) = opt.bin_dir {
+        fs::create_dir_all(&bin_dir).or(Err("Failed to create folder"))?;
+        for (locale_id, locale) in &locales {
+            write_file(
+                bin_dir.join(format!("{}.bin", locale_id)),
+                &bitcode::encode(locale),
+            )?;
+        }
+    }
 
     let s = serde_json::to_string(&locales).or(Err("Failed to stringify data"))?;
-    let mut f = fs::File::create(opt.output).or(Err("Failed to create file"))?;
-    f.write_all(s.as_bytes()).or(Err("Failed writing"))?;
+    write_file(opt.output, s.as_bytes())?;
+
+    if let (Some(languages), Some(languages_output)) = (opt.languages, opt.languages_output) {
+        let contents = fs::read_to_string(languages).or(Err("Failed reading languages file"))?;
+        let languages: HashMap<String, Language> =
+            serde_json::from_str(&contents).or(Err("Failed parsing languages file"))?;
+        write_file(languages_output, &bitcode::encode(&languages))?;
+    }
     Ok(())
 }
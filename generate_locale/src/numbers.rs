@@ -30,10 +30,11 @@ struct NumbersJSON {
 pub fn get_numbers_formatting(
     cldr_dir: &str,
     locale_id: &str,
+    cldr_id: &str,
 ) -> Result<NumbersProperties, String> {
     let numbers_file = format!(
         "{}cldr-json/cldr-numbers-{}/main/{}/numbers.json",
-        cldr_dir, LOCAL_TYPE, locale_id
+        cldr_dir, LOCAL_TYPE, cldr_id
     );
 
     let contents = fs::read_to_string(numbers_file).or(Err("Failed reading 'numbers.json'"))?;
@@ -44,10 +45,7 @@ pub fn get_numbers_formatting(
     // * #,##0.### (standard)
     // * 0.###### (posix)
     // anything else is an error
-    let grouping_str = &numbers_json.main[locale_id]
-        .numbers
-        .decimal_formats
-        .standard;
+    let grouping_str = &numbers_json.main[cldr_id].numbers.decimal_formats.standard;
     let _grouping = if grouping_str == "#,##0.###" {
         "standard"
     } else if grouping_str == "#,##,##0.###" {
@@ -61,5 +59,5 @@ pub fn get_numbers_formatting(
         );
         return Err(message);
     };
-    Ok(numbers_json.main[locale_id].numbers.clone())
+    Ok(numbers_json.main[cldr_id].numbers.clone())
 }
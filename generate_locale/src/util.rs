@@ -1,4 +1,5 @@
 use std::fs;
+use std::path::Path;
 
 use crate::constants::LOCAL_TYPE;
 use serde::{Deserialize, Serialize};
@@ -24,3 +25,16 @@ pub fn get_all_locales_id(cldr_dir: &str) -> Vec<String> {
         locales.available_locales.full
     }
 }
+
+/// Returns the folder in the CLDR with the data of a locale.
+/// The CLDR has no folder for the default territory of a language, there is `pt` but not `pt-BR`.
+pub fn get_cldr_id(cldr_dir: &str, locale_id: &str) -> String {
+    let main_dir = format!("{}cldr-json/cldr-numbers-{}/main/", cldr_dir, LOCAL_TYPE);
+    if Path::new(&format!("{}{}", main_dir, locale_id)).exists() {
+        return locale_id.to_string();
+    }
+    match locale_id.split_once('-') {
+        Some((language, _)) => language.to_string(),
+        None => locale_id.to_string(),
+    }
+}
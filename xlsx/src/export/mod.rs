@@ -1,10 +1,13 @@
 mod _rels;
 mod doc_props;
 mod escape;
+mod pivot_tables;
 mod shared_strings;
 mod styles;
+mod theme;
 mod workbook;
 mod workbook_xml_rels;
+mod worksheet_xml_rels;
 mod worksheets;
 mod xml_constants;
 
@@ -18,6 +21,7 @@ use ironcalc_base::expressions::utils::number_to_column;
 use ironcalc_base::types::Workbook;
 use ironcalc_base::{get_milliseconds_since_epoch, Model};
 
+use self::pivot_tables::PivotTableXml;
 use self::xml_constants::XML_DECLARATION;
 
 use crate::error::XlsxError;
@@ -25,7 +29,7 @@ use crate::error::XlsxError;
 #[cfg(test)]
 mod test;
 
-fn get_content_types_xml(workbook: &Workbook) -> String {
+fn get_content_types_xml(workbook: &Workbook, pivot_tables: &[PivotTableXml]) -> String {
     // A list of all files in the zip
     let mut content = vec![
         r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#.to_string(),
@@ -40,10 +44,24 @@ fn get_content_types_xml(workbook: &Workbook) -> String {
         );
         content.push(sheet);
     }
-    // we skip the theme and calcChain
-    // r#"<Override PartName="/xl/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>"#,
+    for (index, pivot_table) in pivot_tables.iter().enumerate() {
+        let id = index + 1;
+        content.push(format!(
+            r#"<Override PartName="/xl/pivotTables/pivotTable{id}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.pivotTable+xml"/>"#
+        ));
+        content.push(format!(
+            r#"<Override PartName="/xl/pivotCache/pivotCacheDefinition{id}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheDefinition+xml"/>"#
+        ));
+        if pivot_table.cache_records.is_some() {
+            content.push(format!(
+                r#"<Override PartName="/xl/pivotCache/pivotCacheRecords{id}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheRecords+xml"/>"#
+            ));
+        }
+    }
+    // we skip the calcChain
     // r#"<Override PartName="/xl/calcChain.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.calcChain+xml"/>"#,
     content.extend([
+        r#"<Override PartName="/xl/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>"#.to_string(),
         r#"<Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>"#.to_string(),
         r#"<Override PartName="/xl/sharedStrings.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sharedStrings+xml"/>"#.to_string(),
         r#"<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>"#.to_string(),
@@ -72,13 +90,14 @@ pub fn save_xlsx_to_writer<W: Write + Seek>(model: &Model, writer: W) -> Result<
         Some(view) => view.sheet,
         _ => 0,
     };
+    let pivot_tables = pivot_tables::get_pivot_tables_xml(model);
     let mut zip = zip::ZipWriter::new(writer);
 
     let options = zip::write::FileOptions::default();
 
     // root folder
     zip.start_file("[Content_Types].xml", options)?;
-    zip.write_all(get_content_types_xml(workbook).as_bytes())?;
+    zip.write_all(get_content_types_xml(workbook, &pivot_tables).as_bytes())?;
 
     zip.add_directory("docProps", options)?;
     zip.start_file("docProps/app.xml", options)?;
@@ -97,14 +116,24 @@ pub fn save_xlsx_to_writer<W: Write + Seek>(model: &Model, writer: W) -> Result<
     zip.write_all(shared_strings::get_shared_strings_xml(workbook).as_bytes())?;
     zip.start_file("xl/styles.xml", options)?;
     zip.write_all(styles::get_styles_xml(workbook).as_bytes())?;
+    zip.add_directory("xl/theme", options)?;
+    zip.start_file("xl/theme/theme1.xml", options)?;
+    zip.write_all(theme::get_theme_xml(&workbook.theme).as_bytes())?;
     zip.start_file("xl/workbook.xml", options)?;
-    zip.write_all(workbook::get_workbook_xml(workbook, selected_sheet).as_bytes())?;
+    zip.write_all(
+        workbook::get_workbook_xml(workbook, selected_sheet, pivot_tables.len()).as_bytes(),
+    )?;
 
     zip.add_directory("xl/_rels", options)?;
     zip.start_file("xl/_rels/workbook.xml.rels", options)?;
-    zip.write_all(workbook_xml_rels::get_workbook_xml_rels(workbook).as_bytes())?;
+    zip.write_all(
+        workbook_xml_rels::get_workbook_xml_rels(workbook, pivot_tables.len()).as_bytes(),
+    )?;
 
     zip.add_directory("xl/worksheets", options)?;
+    // The differential formats of the conditional formats and color filters of all sheets are
+    // stored together in styles.xml
+    let mut dxf_offset = 0;
     for (sheet_index, worksheet) in workbook.worksheets.iter().enumerate() {
         let id = sheet_index + 1;
         zip.start_file(format!("xl/worksheets/sheet{id}.xml"), options)?;
@@ -125,9 +154,59 @@ pub fn save_xlsx_to_writer<W: Write + Seek>(model: &Model, writer: W) -> Result<
                 &model.parsed_formulas[sheet_index],
                 sheet_dimension_str,
                 is_sheet_selected,
+                dxf_offset,
             )
             .as_bytes(),
         )?;
+        let sheet_pivot_tables: Vec<usize> = pivot_tables
+            .iter()
+            .enumerate()
+            .filter(|(_, pivot_table)| pivot_table.sheet == sheet_index)
+            .map(|(index, _)| index + 1)
+            .collect();
+        if let Some(rels) =
+            worksheet_xml_rels::get_worksheet_xml_rels(worksheet, &sheet_pivot_tables)
+        {
+            zip.start_file(format!("xl/worksheets/_rels/sheet{id}.xml.rels"), options)?;
+            zip.write_all(rels.as_bytes())?;
+        }
+        dxf_offset += worksheet
+            .conditional_formats
+            .iter()
+            .filter(|conditional_format| conditional_format.style.is_some())
+            .count()
+            + worksheets::get_color_filter_dxfs(worksheet).len();
+    }
+
+    if !pivot_tables.is_empty() {
+        zip.add_directory("xl/pivotTables", options)?;
+        zip.add_directory("xl/pivotTables/_rels", options)?;
+        zip.add_directory("xl/pivotCache", options)?;
+        zip.add_directory("xl/pivotCache/_rels", options)?;
+    }
+    for (index, pivot_table) in pivot_tables.iter().enumerate() {
+        let id = index + 1;
+        zip.start_file(format!("xl/pivotTables/pivotTable{id}.xml"), options)?;
+        zip.write_all(pivot_table.definition.as_bytes())?;
+        zip.start_file(
+            format!("xl/pivotTables/_rels/pivotTable{id}.xml.rels"),
+            options,
+        )?;
+        zip.write_all(pivot_tables::get_pivot_table_xml_rels(id).as_bytes())?;
+        zip.start_file(
+            format!("xl/pivotCache/pivotCacheDefinition{id}.xml"),
+            options,
+        )?;
+        zip.write_all(pivot_table.cache_definition.as_bytes())?;
+        if let Some(cache_records) = &pivot_table.cache_records {
+            zip.start_file(
+                format!("xl/pivotCache/_rels/pivotCacheDefinition{id}.xml.rels"),
+                options,
+            )?;
+            zip.write_all(pivot_tables::get_pivot_cache_definition_xml_rels(id).as_bytes())?;
+            zip.start_file(format!("xl/pivotCache/pivotCacheRecords{id}.xml"), options)?;
+            zip.write_all(cache_records.as_bytes())?;
+        }
     }
 
     let writer = zip.finish()?;
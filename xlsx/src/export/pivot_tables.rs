@@ -0,0 +1,363 @@
+//! A pivot table is stored in three parts:
+//!
+//! * xl/pivotTables/pivotTable1.xml with the layout: the fields in the rows, columns and values
+//! * xl/pivotCache/pivotCacheDefinition1.xml with the source and the items of each field
+//! * xl/pivotCache/pivotCacheRecords1.xml with a copy of the data, optional
+//!
+//! We do not save a copy of the data, Excel reads the source again when it opens the file.
+//! Pivot tables imported from an xlsx file are saved as they were read unless they changed.
+
+use ironcalc_base::{
+    types::{PivotFunction, PivotSource, PivotTable},
+    Model,
+};
+
+use super::{
+    escape::escape_xml,
+    xml_constants::{XML_DECLARATION, XML_PIVOT_CACHE_DEFINITION, XML_PIVOT_CACHE_RECORDS},
+};
+
+const XML_MAIN: &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const XML_RELATIONSHIPS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// The parts of a pivot table. The n-th pivot table of the workbook has cacheId n.
+pub(crate) struct PivotTableXml {
+    /// Index of the sheet of the pivot table
+    pub(crate) sheet: usize,
+    pub(crate) definition: String,
+    pub(crate) cache_definition: String,
+    pub(crate) cache_records: Option<String>,
+}
+
+/// Returns the parts of all the pivot tables of the workbook, sheet by sheet.
+/// Pivot tables whose source cannot be read are left out.
+pub(crate) fn get_pivot_tables_xml(model: &Model) -> Vec<PivotTableXml> {
+    let mut pivot_tables = Vec::new();
+    for (sheet, worksheet) in model.workbook.worksheets.iter().enumerate() {
+        for pivot_table in &worksheet.pivot_tables {
+            let cache_id = pivot_tables.len() + 1;
+            if let Some(parts) = &pivot_table.xlsx_parts {
+                let cache_definition = match parts.cache_records {
+                    Some(_) => set_root_attribute(&parts.cache_definition, "r:id", "rId1"),
+                    None => parts.cache_definition.clone(),
+                };
+                pivot_tables.push(PivotTableXml {
+                    sheet,
+                    definition: set_root_attribute(
+                        &parts.definition,
+                        "cacheId",
+                        &cache_id.to_string(),
+                    ),
+                    cache_definition,
+                    cache_records: parts.cache_records.clone(),
+                });
+                continue;
+            }
+            let fields = match model.get_pivot_fields(&pivot_table.source) {
+                Ok(fields) => fields,
+                Err(_) => continue,
+            };
+            pivot_tables.push(PivotTableXml {
+                sheet,
+                definition: get_pivot_table_xml(pivot_table, &fields, cache_id),
+                cache_definition: get_pivot_cache_definition_xml(pivot_table, &fields),
+                cache_records: None,
+            });
+        }
+    }
+    pivot_tables
+}
+
+/// Sets the value of the attribute `name` of the root element of the `xml`
+fn set_root_attribute(xml: &str, name: &str, value: &str) -> String {
+    // Skip the XML declaration
+    let start = match xml.find("?>") {
+        Some(index) => index + 2,
+        None => 0,
+    };
+    let end = match xml[start..].find('>') {
+        Some(index) => start + index,
+        None => return xml.to_string(),
+    };
+    let pattern = format!(" {name}=\"");
+    if let Some(index) = xml[start..end].find(&pattern) {
+        let value_start = start + index + pattern.len();
+        if let Some(length) = xml[value_start..].find('"') {
+            return format!(
+                "{}{value}{}",
+                &xml[..value_start],
+                &xml[value_start + length..]
+            );
+        }
+    }
+    let end = if xml[..end].ends_with('/') {
+        end - 1
+    } else {
+        end
+    };
+    format!("{} {name}=\"{value}\"{}", &xml[..end], &xml[end..])
+}
+
+fn get_function_name(function: PivotFunction) -> &'static str {
+    match function {
+        PivotFunction::Average => "average",
+        PivotFunction::Count => "count",
+        PivotFunction::CountNums => "countNums",
+        PivotFunction::Max => "max",
+        PivotFunction::Min => "min",
+        PivotFunction::Product => "product",
+        PivotFunction::StdDev => "stdDev",
+        PivotFunction::StdDevp => "stdDevp",
+        PivotFunction::Sum => "sum",
+        PivotFunction::Var => "var",
+        PivotFunction::Varp => "varp",
+    }
+}
+
+fn get_field_index(fields: &[(String, Vec<String>)], name: &str) -> Option<usize> {
+    let lower = name.to_lowercase();
+    fields.iter().position(|(f, _)| f.to_lowercase() == lower)
+}
+
+// <pivotTableDefinition name="PivotTable1" cacheId="1" dataCaption="Values">
+//   <location ref="F3:H8" firstHeaderRow="1" firstDataRow="2" firstDataCol="1"/>
+//   <pivotFields count="3">
+//     <pivotField axis="axisRow" showAll="0"><items count="3"><item x="0"/>...</items></pivotField>
+//     <pivotField dataField="1" showAll="0"/>
+//     ...
+//   </pivotFields>
+//   <rowFields count="1"><field x="0"/></rowFields>
+//   <dataFields count="1"><dataField name="Sum of Sales" fld="1" subtotal="sum"/></dataFields>
+// </pivotTableDefinition>
+fn get_pivot_table_xml(
+    pivot_table: &PivotTable,
+    fields: &[(String, Vec<String>)],
+    cache_id: usize,
+) -> String {
+    let indices = |names: &[String]| -> Vec<usize> {
+        names
+            .iter()
+            .filter_map(|name| get_field_index(fields, name))
+            .collect()
+    };
+    let row_fields = indices(&pivot_table.row_fields);
+    let column_fields = indices(&pivot_table.column_fields);
+    let page_fields: Vec<usize> = pivot_table
+        .filters
+        .iter()
+        .filter_map(|filter| get_field_index(fields, &filter.field))
+        .filter(|index| !row_fields.contains(index) && !column_fields.contains(index))
+        .collect();
+    let data_fields: Vec<(usize, &str, PivotFunction)> = pivot_table
+        .value_fields
+        .iter()
+        .filter_map(|value_field| {
+            get_field_index(fields, &value_field.field)
+                .map(|index| (index, value_field.name.as_str(), value_field.function))
+        })
+        .collect();
+
+    let mut pivot_fields = Vec::new();
+    for (index, (name, items)) in fields.iter().enumerate() {
+        let axis = if row_fields.contains(&index) {
+            " axis=\"axisRow\""
+        } else if column_fields.contains(&index) {
+            " axis=\"axisCol\""
+        } else if page_fields.contains(&index) {
+            " axis=\"axisPage\" multipleItemSelectionAllowed=\"1\""
+        } else {
+            ""
+        };
+        let data_field = if data_fields.iter().any(|(f, _, _)| *f == index) {
+            " dataField=\"1\""
+        } else {
+            ""
+        };
+        if axis.is_empty() {
+            pivot_fields.push(format!("<pivotField{data_field} showAll=\"0\"/>"));
+            continue;
+        }
+        let hidden: Vec<String> = pivot_table
+            .filters
+            .iter()
+            .filter(|filter| filter.field.to_lowercase() == name.to_lowercase())
+            .flat_map(|filter| filter.hidden_items.iter().map(|item| item.to_lowercase()))
+            .collect();
+        let mut items_str: Vec<String> = items
+            .iter()
+            .enumerate()
+            .map(|(x, item)| {
+                if hidden.contains(&item.to_lowercase()) {
+                    format!("<item h=\"1\" x=\"{x}\"/>")
+                } else {
+                    format!("<item x=\"{x}\"/>")
+                }
+            })
+            .collect();
+        let default_subtotal = if pivot_table.subtotals {
+            items_str.push("<item t=\"default\"/>".to_string());
+            ""
+        } else {
+            " defaultSubtotal=\"0\""
+        };
+        pivot_fields.push(format!(
+            "<pivotField{axis}{data_field} showAll=\"0\"{default_subtotal}><items count=\"{}\">{}</items></pivotField>",
+            items_str.len(),
+            items_str.join("")
+        ));
+    }
+
+    let fields_xml = |tag: &str, indices: &[usize], values: bool| {
+        let mut fields: Vec<String> = indices
+            .iter()
+            .map(|x| format!("<field x=\"{x}\"/>"))
+            .collect();
+        // x="-2" is the position of the values when there are more than one
+        if values {
+            fields.push("<field x=\"-2\"/>".to_string());
+        }
+        if fields.is_empty() {
+            return "".to_string();
+        }
+        format!(
+            "<{tag} count=\"{}\">{}</{tag}>",
+            fields.len(),
+            fields.join("")
+        )
+    };
+    let rows_str = fields_xml("rowFields", &row_fields, false);
+    let columns_str = fields_xml("colFields", &column_fields, data_fields.len() > 1);
+    let pages_str = if page_fields.is_empty() {
+        "".to_string()
+    } else {
+        let pages: Vec<String> = page_fields
+            .iter()
+            .map(|x| format!("<pageField fld=\"{x}\" hier=\"-1\"/>"))
+            .collect();
+        format!(
+            "<pageFields count=\"{}\">{}</pageFields>",
+            pages.len(),
+            pages.join("")
+        )
+    };
+    let data_str = if data_fields.is_empty() {
+        "".to_string()
+    } else {
+        let data: Vec<String> = data_fields
+            .iter()
+            .map(|(fld, name, function)| {
+                format!(
+                    "<dataField name=\"{}\" fld=\"{fld}\" subtotal=\"{}\" baseField=\"0\" baseItem=\"0\"/>",
+                    escape_xml(name),
+                    get_function_name(*function)
+                )
+            })
+            .collect();
+        format!(
+            "<dataFields count=\"{}\">{}</dataFields>",
+            data.len(),
+            data.join("")
+        )
+    };
+    let reference = pivot_table
+        .reference
+        .clone()
+        .unwrap_or_else(|| pivot_table.location.clone());
+    let header_rows = column_fields.len() + usize::from(data_fields.len() > 1);
+    let row_grand_totals = if pivot_table.row_grand_totals {
+        ""
+    } else {
+        " rowGrandTotals=\"0\""
+    };
+    let column_grand_totals = if pivot_table.column_grand_totals {
+        ""
+    } else {
+        " colGrandTotals=\"0\""
+    };
+    format!(
+        "{XML_DECLARATION}\n\
+        <pivotTableDefinition xmlns=\"{XML_MAIN}\" name=\"{}\" cacheId=\"{cache_id}\" dataCaption=\"Values\" \
+        applyNumberFormats=\"0\" applyBorderFormats=\"0\" applyFontFormats=\"0\" applyPatternFormats=\"0\" \
+        applyAlignmentFormats=\"0\" applyWidthHeightFormats=\"1\" updatedVersion=\"6\" minRefreshableVersion=\"3\" \
+        createdVersion=\"6\" outline=\"1\" outlineData=\"1\"{row_grand_totals}{column_grand_totals}>\
+        <location ref=\"{reference}\" firstHeaderRow=\"{}\" firstDataRow=\"{}\" firstDataCol=\"{}\"/>\
+        <pivotFields count=\"{}\">{}</pivotFields>\
+        {rows_str}{columns_str}{pages_str}{data_str}\
+        <pivotTableStyleInfo name=\"PivotStyleLight16\" showRowHeaders=\"1\" showColHeaders=\"1\" showRowStripes=\"0\" showColStripes=\"0\" showLastColumn=\"1\"/>\
+        </pivotTableDefinition>",
+        escape_xml(&pivot_table.name),
+        usize::from(header_rows > 0),
+        header_rows + 1,
+        row_fields.len().max(1),
+        pivot_fields.len(),
+        pivot_fields.join(""),
+    )
+}
+
+// <pivotCacheDefinition refreshOnLoad="1" saveData="0">
+//   <cacheSource type="worksheet"><worksheetSource ref="A1:C20" sheet="Sheet1"/></cacheSource>
+//   <cacheFields count="3">
+//     <cacheField name="Region" numFmtId="0"><sharedItems count="2"><s v="East"/>...</sharedItems></cacheField>
+//     ...
+//   </cacheFields>
+// </pivotCacheDefinition>
+fn get_pivot_cache_definition_xml(
+    pivot_table: &PivotTable,
+    fields: &[(String, Vec<String>)],
+) -> String {
+    let source = match &pivot_table.source {
+        PivotSource::Range {
+            sheet_name,
+            reference,
+        } => format!(
+            "<worksheetSource ref=\"{}\" sheet=\"{}\"/>",
+            escape_xml(reference),
+            escape_xml(sheet_name)
+        ),
+        PivotSource::Table { name } => format!("<worksheetSource name=\"{}\"/>", escape_xml(name)),
+    };
+    let cache_fields: Vec<String> = fields
+        .iter()
+        .map(|(name, items)| {
+            let items: Vec<String> = items
+                .iter()
+                .map(|item| format!("<s v=\"{}\"/>", escape_xml(item)))
+                .collect();
+            format!(
+                "<cacheField name=\"{}\" numFmtId=\"0\"><sharedItems count=\"{}\">{}</sharedItems></cacheField>",
+                escape_xml(name),
+                items.len(),
+                items.join("")
+            )
+        })
+        .collect();
+    format!(
+        "{XML_DECLARATION}\n\
+        <pivotCacheDefinition xmlns=\"{XML_MAIN}\" xmlns:r=\"{XML_RELATIONSHIPS}\" refreshOnLoad=\"1\" saveData=\"0\" \
+        createdVersion=\"6\" refreshedVersion=\"6\" minRefreshableVersion=\"3\" recordCount=\"0\">\
+        <cacheSource type=\"worksheet\">{source}</cacheSource>\
+        <cacheFields count=\"{}\">{}</cacheFields>\
+        </pivotCacheDefinition>",
+        cache_fields.len(),
+        cache_fields.join("")
+    )
+}
+
+/// Relationships of xl/pivotTables/pivotTable{id}.xml
+pub(crate) fn get_pivot_table_xml_rels(id: usize) -> String {
+    format!(
+        "{XML_DECLARATION}\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+        <Relationship Id=\"rId1\" Type=\"{XML_PIVOT_CACHE_DEFINITION}\" Target=\"../pivotCache/pivotCacheDefinition{id}.xml\"/>\
+        </Relationships>"
+    )
+}
+
+/// Relationships of xl/pivotCache/pivotCacheDefinition{id}.xml, if it has records
+pub(crate) fn get_pivot_cache_definition_xml_rels(id: usize) -> String {
+    format!(
+        "{XML_DECLARATION}\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+        <Relationship Id=\"rId1\" Type=\"{XML_PIVOT_CACHE_RECORDS}\" Target=\"pivotCacheRecords{id}.xml\"/>\
+        </Relationships>"
+    )
+}
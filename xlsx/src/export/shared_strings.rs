@@ -1,13 +1,53 @@
-use ironcalc_base::types::Workbook;
+use ironcalc_base::types::{Font, FontVerticalAlignment, TextRun, Workbook};
 
 use super::{escape::escape_xml, xml_constants::XML_DECLARATION};
 
+/// Returns the run properties (18.4.7 rPr) of a run of rich text
+fn get_run_properties_xml(font: &Font) -> String {
+    let name = format!("<rFont val=\"{}\"/>", escape_xml(&font.name));
+    let family = format!("<family val=\"{}\"/>", font.family);
+    let bold = if font.b { "<b/>" } else { "" };
+    let italic = if font.i { "<i/>" } else { "" };
+    let strike = if font.strike { "<strike/>" } else { "" };
+    let color = match &font.color {
+        Some(color) => format!("<color rgb=\"FF{}\"/>", color.trim_start_matches('#')),
+        None => "".to_string(),
+    };
+    let size = format!("<sz val=\"{}\"/>", font.sz);
+    let underline = if font.u { "<u/>" } else { "" };
+    let vert_align = match font.vert_align {
+        FontVerticalAlignment::Baseline => "".to_string(),
+        _ => format!("<vertAlign val=\"{}\"/>", font.vert_align),
+    };
+    let scheme = format!("<scheme val=\"{}\"/>", font.scheme);
+    format!("<rPr>{name}{family}{bold}{italic}{strike}{color}{size}{underline}{vert_align}{scheme}</rPr>")
+}
+
+fn get_runs_xml(runs: &[TextRun]) -> String {
+    runs.iter()
+        .map(|run| {
+            let properties = run
+                .font
+                .as_ref()
+                .map(get_run_properties_xml)
+                .unwrap_or_default();
+            format!(
+                "<r>{properties}<t xml:space=\"preserve\">{}</t></r>",
+                escape_xml(&run.text)
+            )
+        })
+        .collect()
+}
+
 pub(crate) fn get_shared_strings_xml(model: &Workbook) -> String {
     let mut shared_strings: Vec<String> = vec![];
     let count = &model.shared_strings.len();
     let unique_count = &model.shared_strings.len();
-    for shared_string in &model.shared_strings {
-        shared_strings.push(format!("<si><t>{}</t></si>", escape_xml(shared_string)));
+    for (index, shared_string) in model.shared_strings.iter().enumerate() {
+        match model.rich_text.get(&(index as i32)) {
+            Some(runs) => shared_strings.push(format!("<si>{}</si>", get_runs_xml(runs))),
+            None => shared_strings.push(format!("<si><t>{}</t></si>", escape_xml(shared_string))),
+        }
     }
     format!("{}\n\
       <sst xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" count=\"{count}\" uniqueCount=\"{unique_count}\">\
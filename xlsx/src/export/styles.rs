@@ -1,24 +1,27 @@
 use ironcalc_base::types::{
-    Alignment, BorderItem, HorizontalAlignment, Styles, VerticalAlignment, Workbook,
+    Alignment, BorderItem, Dxf, FontVerticalAlignment, HorizontalAlignment, Protection, Styles,
+    ThemeColor, VerticalAlignment, Workbook,
 };
 
-use super::{escape::escape_xml, xml_constants::XML_DECLARATION};
+use super::{
+    escape::escape_xml, worksheets::get_color_filter_dxfs, xml_constants::XML_DECLARATION,
+};
 
 fn get_fonts_xml(styles: &Styles) -> String {
     let fonts = &styles.fonts;
     let mut fonts_str: Vec<String> = vec![];
     for font in fonts {
         let size = format!("<sz val=\"{}\"/>", font.sz);
-        let color = if let Some(some_color) = &font.color {
-            format!("<color rgb=\"FF{}\"/>", some_color.trim_start_matches('#'))
-        } else {
-            "".to_string()
-        };
+        let color = get_style_color_xml(&font.color, &font.color_theme, "color");
         let name = format!("<name val=\"{}\"/>", escape_xml(&font.name));
         let bold = if font.b { "<b/>" } else { "" };
         let italic = if font.i { "<i/>" } else { "" };
         let underline = if font.u { "<u/>" } else { "" };
         let strike = if font.strike { "<strike/>" } else { "" };
+        let vert_align = match font.vert_align {
+            FontVerticalAlignment::Baseline => "".to_string(),
+            _ => format!("<vertAlign val=\"{}\"/>", font.vert_align),
+        };
         let family = format!("<family val=\"{}\"/>", font.family);
         let scheme = format!("<scheme val=\"{}\"/>", font.scheme);
         fonts_str.push(format!(
@@ -30,6 +33,7 @@ fn get_fonts_xml(styles: &Styles) -> String {
                 {italic}\
                 {underline}\
                 {strike}\
+                {vert_align}\
                 {family}\
                 {scheme}\
              </font>"
@@ -51,13 +55,31 @@ fn get_color_xml(color: &Option<String>, name: &str) -> String {
     }
 }
 
+/// Colors taken from the theme are written as references to the theme
+fn get_style_color_xml(
+    color: &Option<String>,
+    color_theme: &Option<ThemeColor>,
+    name: &str,
+) -> String {
+    match (color, color_theme) {
+        (Some(_), Some(theme_color)) => {
+            let tint = match &theme_color.tint {
+                Some(tint) => format!(" tint=\"{tint}\""),
+                None => "".to_string(),
+            };
+            format!("<{name} theme=\"{}\"{tint}/>", theme_color.theme)
+        }
+        _ => get_color_xml(color, name),
+    }
+}
+
 fn get_fills_xml(styles: &Styles) -> String {
     let fills = &styles.fills;
     let mut fills_str: Vec<String> = vec![];
     for fill in fills {
         let pattern_type = &fill.pattern_type;
-        let fg_color = get_color_xml(&fill.fg_color, "fgColor");
-        let bg_color = get_color_xml(&fill.bg_color, "bgColor");
+        let fg_color = get_style_color_xml(&fill.fg_color, &fill.fg_color_theme, "fgColor");
+        let bg_color = get_style_color_xml(&fill.bg_color, &fill.bg_color_theme, "bgColor");
         fills_str.push(format!(
             "<fill><patternFill patternType=\"{pattern_type}\">{fg_color}{bg_color}</patternFill></fill>"
         ));
@@ -71,7 +93,7 @@ fn get_fills_xml(styles: &Styles) -> String {
 
 fn get_border_xml(border: &Option<BorderItem>, name: &str) -> String {
     if let Some(border_item) = border {
-        let color = get_color_xml(&border_item.color, "color");
+        let color = get_style_color_xml(&border_item.color, &border_item.color_theme, "color");
         return format!("<{name} style=\"{}\">{color}</{name}>", border_item.style);
     }
     format!("<{name}/>")
@@ -141,6 +163,20 @@ fn get_alignment(alignment: &Alignment) -> String {
     format!("<alignment{wrap_text}{horizontal}{vertical}/>")
 }
 
+fn get_protection(protection: &Protection) -> String {
+    let locked = if protection.locked {
+        ""
+    } else {
+        " locked=\"0\""
+    };
+    let hidden = if protection.hidden {
+        " hidden=\"1\""
+    } else {
+        ""
+    };
+    format!("<protection{locked}{hidden}/>")
+}
+
 fn get_cell_style_xfs_xml(styles: &Styles) -> String {
     let cell_style_xfs = &styles.cell_style_xfs;
     let mut cell_style_str: Vec<String> = vec![];
@@ -164,16 +200,30 @@ fn get_cell_style_xfs_xml(styles: &Styles) -> String {
         } else {
             ""
         };
-        cell_style_str.push(format!(
-            "<xf \
-              borderId=\"{border_id}\" \
+        let properties = format!(
+            "borderId=\"{border_id}\" \
               fillId=\"{fill_id}\" \
               fontId=\"{font_id}\" \
               numFmtId=\"{num_fmt_id}\"\
               {apply_alignment_str}\
               {apply_font_str}\
-              {apply_fill_str}/>"
-        ));
+              {apply_fill_str}"
+        );
+        let alignment = cell_style_xf
+            .alignment
+            .as_ref()
+            .map(get_alignment)
+            .unwrap_or_default();
+        let protection = cell_style_xf
+            .protection
+            .as_ref()
+            .map(get_protection)
+            .unwrap_or_default();
+        if alignment.is_empty() && protection.is_empty() {
+            cell_style_str.push(format!("<xf {properties}/>"));
+        } else {
+            cell_style_str.push(format!("<xf {properties}>{alignment}{protection}</xf>"));
+        }
     }
     let style_count = cell_style_xfs.len();
     format!(
@@ -211,6 +261,12 @@ fn get_cell_xfs_xml(styles: &Styles) -> String {
         } else {
             ""
         };
+        // Excel ignores the protection of the cell unless applyProtection is set
+        let apply_protection_str = if cell_xf.protection.is_some() {
+            r#" applyProtection="1""#
+        } else {
+            ""
+        };
         let properties = format!(
             "xfId=\"{xf_id}\" \
                 borderId=\"{border_id}\" \
@@ -220,13 +276,23 @@ fn get_cell_xfs_xml(styles: &Styles) -> String {
                 {quote_prefix_str}\
                 {apply_alignment_str}\
                 {apply_font_str}\
-                {apply_fill_str}"
+                {apply_fill_str}\
+                {apply_protection_str}"
         );
-        if let Some(alignment) = &cell_xf.alignment {
-            let alignment = get_alignment(alignment);
-            cell_xfs_str.push(format!("<xf {properties}>{alignment}</xf>"));
-        } else {
+        let alignment = cell_xf
+            .alignment
+            .as_ref()
+            .map(get_alignment)
+            .unwrap_or_default();
+        let protection = cell_xf
+            .protection
+            .as_ref()
+            .map(get_protection)
+            .unwrap_or_default();
+        if alignment.is_empty() && protection.is_empty() {
             cell_xfs_str.push(format!("<xf {properties}/>"));
+        } else {
+            cell_xfs_str.push(format!("<xf {properties}>{alignment}{protection}</xf>"));
         }
     }
     let style_count = cell_xfs.len();
@@ -244,9 +310,12 @@ fn get_cell_styles_xml(styles: &Styles) -> String {
         let xf_id = cell_style.xf_id;
         let name = &cell_style.name;
         let name = escape_xml(name);
-        let builtin_id = cell_style.builtin_id;
+        let builtin_id = match cell_style.builtin_id {
+            Some(builtin_id) => format!(" builtinId=\"{builtin_id}\""),
+            None => "".to_string(),
+        };
         cell_styles_str.push(format!(
-            "<cellStyle xfId=\"{xf_id}\" name=\"{name}\" builtinId=\"{builtin_id}\"/>"
+            "<cellStyle xfId=\"{xf_id}\" name=\"{name}\"{builtin_id}/>"
         ));
     }
     let style_count = cell_styles.len();
@@ -256,6 +325,93 @@ fn get_cell_styles_xml(styles: &Styles) -> String {
     )
 }
 
+fn get_dxf_xml(dxf: &Dxf, num_fmt_id: i32) -> String {
+    let font = match &dxf.font {
+        Some(font) => {
+            let flag = |name: &str, value: Option<bool>| match value {
+                Some(true) => format!("<{name}/>"),
+                Some(false) => format!("<{name} val=\"0\"/>"),
+                None => "".to_string(),
+            };
+            let underline = match font.u {
+                Some(true) => "<u/>",
+                Some(false) => "<u val=\"none\"/>",
+                None => "",
+            };
+            format!(
+                "<font>{}{}{underline}{}{}</font>",
+                flag("b", font.b),
+                flag("i", font.i),
+                flag("strike", font.strike),
+                get_color_xml(&font.color, "color")
+            )
+        }
+        None => "".to_string(),
+    };
+    let num_fmt = match &dxf.num_fmt {
+        Some(format_code) => format!(
+            "<numFmt numFmtId=\"{num_fmt_id}\" formatCode=\"{}\"/>",
+            escape_xml(format_code)
+        ),
+        None => "".to_string(),
+    };
+    let fill = if dxf.fill_color.is_some() {
+        format!(
+            "<fill><patternFill>{}</patternFill></fill>",
+            get_color_xml(&dxf.fill_color, "bgColor")
+        )
+    } else {
+        "".to_string()
+    };
+    let border = match &dxf.border {
+        Some(border) => format!(
+            "<border>{}{}{}{}</border>",
+            get_border_xml(&border.left, "left"),
+            get_border_xml(&border.right, "right"),
+            get_border_xml(&border.top, "top"),
+            get_border_xml(&border.bottom, "bottom")
+        ),
+        None => "".to_string(),
+    };
+    format!("<dxf>{font}{num_fmt}{fill}{border}</dxf>")
+}
+
+// The differential formats of the conditional formats and color filters of all the sheets, in order
+fn get_dxfs_xml(model: &Workbook) -> String {
+    // Number formats in differential formats need an id not used by the cell formats
+    let mut num_fmt_id = model
+        .styles
+        .num_fmts
+        .iter()
+        .map(|num_fmt| num_fmt.num_fmt_id)
+        .max()
+        .unwrap_or(0)
+        .max(163)
+        + 1;
+    let mut dxfs_str: Vec<String> = vec![];
+    for worksheet in &model.worksheets {
+        for conditional_format in &worksheet.conditional_formats {
+            if let Some(dxf) = &conditional_format.style {
+                dxfs_str.push(get_dxf_xml(dxf, num_fmt_id));
+                if dxf.num_fmt.is_some() {
+                    num_fmt_id += 1;
+                }
+            }
+        }
+        for dxf in get_color_filter_dxfs(worksheet) {
+            dxfs_str.push(get_dxf_xml(&dxf, num_fmt_id));
+        }
+    }
+    if dxfs_str.is_empty() {
+        return "<dxfs count=\"0\"/>".to_string();
+    }
+    format!(
+        "<dxfs count=\"{}\">{}</dxfs>",
+        dxfs_str.len(),
+        dxfs_str.join("")
+    )
+}
+
 pub(crate) fn get_styles_xml(model: &Workbook) -> String {
     let styles = &model.styles;
     let fonts = get_fonts_xml(styles);
@@ -265,6 +421,7 @@ pub(crate) fn get_styles_xml(model: &Workbook) -> String {
     let cell_style_xfs = get_cell_style_xfs_xml(styles);
     let cell_xfs = get_cell_xfs_xml(styles);
     let cell_styles = get_cell_styles_xml(styles);
+    let dxfs = get_dxfs_xml(model);
 
     format!(
         "{XML_DECLARATION}
@@ -276,7 +433,7 @@ pub(crate) fn get_styles_xml(model: &Workbook) -> String {
 {cell_style_xfs}\
 {cell_xfs}\
 {cell_styles}\
-<dxfs count=\"0\"/>\
+{dxfs}\
 </styleSheet>"
     )
 }
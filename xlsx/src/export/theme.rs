@@ -0,0 +1,73 @@
+use ironcalc_base::types::Theme;
+
+use super::{escape::escape_xml, xml_constants::XML_DECLARATION};
+
+// The elements of the color scheme in the order of the colors of the theme
+const COLOR_NAMES: [&str; 12] = [
+    "lt1", "dk1", "lt2", "dk2", "accent1", "accent2", "accent3", "accent4", "accent5", "accent6",
+    "hlink", "folHlink",
+];
+
+// The color scheme is written dark 1, light 1, dark 2, light 2, accents and hyperlinks
+const COLOR_ORDER: [usize; 12] = [1, 0, 3, 2, 4, 5, 6, 7, 8, 9, 10, 11];
+
+// We do not keep the formatting of shapes, the schema needs at least three styles of each kind
+const FORMAT_SCHEME: &str = "<a:fmtScheme name=\"Office\">\
+<a:fillStyleLst>\
+<a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill>\
+<a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill>\
+<a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill>\
+</a:fillStyleLst>\
+<a:lnStyleLst>\
+<a:ln w=\"6350\"><a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill></a:ln>\
+<a:ln w=\"12700\"><a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill></a:ln>\
+<a:ln w=\"19050\"><a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill></a:ln>\
+</a:lnStyleLst>\
+<a:effectStyleLst>\
+<a:effectStyle><a:effectLst/></a:effectStyle>\
+<a:effectStyle><a:effectLst/></a:effectStyle>\
+<a:effectStyle><a:effectLst/></a:effectStyle>\
+</a:effectStyleLst>\
+<a:bgFillStyleLst>\
+<a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill>\
+<a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill>\
+<a:solidFill><a:schemeClr val=\"phClr\"/></a:solidFill>\
+</a:bgFillStyleLst>\
+</a:fmtScheme>";
+
+fn get_font_xml(name: &str, typeface: &str) -> String {
+    format!(
+        "<a:{name}><a:latin typeface=\"{}\"/><a:ea typeface=\"\"/><a:cs typeface=\"\"/></a:{name}>",
+        escape_xml(typeface)
+    )
+}
+
+// 20.1.6.9 theme (Theme)
+pub(crate) fn get_theme_xml(theme: &Theme) -> String {
+    let name = escape_xml(&theme.name);
+    let colors: String = COLOR_ORDER
+        .iter()
+        .map(|&index| {
+            let color = theme
+                .colors
+                .get(index)
+                .map_or("000000", |color| color.trim_start_matches('#'));
+            let element = COLOR_NAMES[index];
+            format!("<a:{element}><a:srgbClr val=\"{color}\"/></a:{element}>")
+        })
+        .collect();
+    let major_font = get_font_xml("majorFont", &theme.major_font);
+    let minor_font = get_font_xml("minorFont", &theme.minor_font);
+    format!(
+        "{XML_DECLARATION}\n\
+<a:theme xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" name=\"{name}\">\
+<a:themeElements>\
+<a:clrScheme name=\"{name}\">{colors}</a:clrScheme>\
+<a:fontScheme name=\"{name}\">{major_font}{minor_font}</a:fontScheme>\
+{FORMAT_SCHEME}\
+</a:themeElements>\
+<a:objectDefaults/>\
+<a:extraClrSchemeLst/>\
+</a:theme>"
+    )
+}
@@ -29,12 +29,41 @@
 
 use std::collections::HashMap;
 
-use ironcalc_base::types::{SheetState, Workbook};
+use ironcalc_base::types::{CalcMode, PasswordHash, SheetState, Workbook};
 
 use super::escape::escape_xml;
 use super::xml_constants::XML_DECLARATION;
 
-pub(crate) fn get_workbook_xml(workbook: &Workbook, selected_sheet: u32) -> String {
+/// Returns the attributes with the hash of the password of a sheet or workbook protection.
+/// `names` are the attributes with the legacy hash, the algorithm, the hash, the salt and the spin count.
+pub(crate) fn get_password_hash_attributes(
+    password_hash: &Option<PasswordHash>,
+    names: [&str; 5],
+) -> String {
+    let [password, algorithm_name_attr, hash_value_attr, salt_value_attr, spin_count_attr] = names;
+    match password_hash {
+        None => "".to_string(),
+        Some(PasswordHash::Legacy(hash)) => format!(" {password}=\"{}\"", escape_xml(hash)),
+        Some(PasswordHash::Salted {
+            algorithm_name,
+            hash_value,
+            salt_value,
+            spin_count,
+        }) => format!(
+            " {algorithm_name_attr}=\"{}\" {hash_value_attr}=\"{}\" {salt_value_attr}=\"{}\" {spin_count_attr}=\"{spin_count}\"",
+            escape_xml(algorithm_name),
+            escape_xml(hash_value),
+            escape_xml(salt_value),
+        ),
+    }
+}
+
+/// The workbook has `pivot_cache_count` pivot caches, one for each pivot table
+pub(crate) fn get_workbook_xml(
+    workbook: &Workbook,
+    selected_sheet: u32,
+    pivot_cache_count: usize,
+) -> String {
     // sheets
     // <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
     let mut sheets_str: Vec<String> = vec![];
@@ -78,8 +107,59 @@ pub(crate) fn get_workbook_xml(workbook: &Workbook, selected_sheet: u32) -> Stri
 
     let sheets = sheets_str.join("");
     let defined_names = defined_names_str.join("");
+    let workbook_pr = if workbook.settings.date_1904 {
+        "<workbookPr date1904=\"1\"/>"
+    } else {
+        ""
+    };
+    let calc_mode = match workbook.settings.calc_mode {
+        CalcMode::Automatic => "".to_string(),
+        calc_mode => format!(" calcMode=\"{calc_mode}\""),
+    };
+    let full_precision = if workbook.settings.precision_as_displayed {
+        " fullPrecision=\"0\""
+    } else {
+        ""
+    };
+    let workbook_protection = match &workbook.protection {
+        Some(protection) => {
+            let password = get_password_hash_attributes(
+                &protection.password_hash,
+                [
+                    "workbookPassword",
+                    "workbookAlgorithmName",
+                    "workbookHashValue",
+                    "workbookSaltValue",
+                    "workbookSpinCount",
+                ],
+            );
+            format!(
+                "<workbookProtection{password} lockStructure=\"{}\" lockWindows=\"{}\"/>",
+                i32::from(protection.lock_structure),
+                i32::from(protection.lock_windows)
+            )
+        }
+        None => "".to_string(),
+    };
+    // <pivotCache cacheId="1" r:id="rId4"/>, the relationships go after the styles and shared strings
+    let pivot_caches = if pivot_cache_count == 0 {
+        "".to_string()
+    } else {
+        let first_id = workbook.worksheets.len() + 3;
+        let caches: Vec<String> = (1..=pivot_cache_count)
+            .map(|cache_id| {
+                format!(
+                    "<pivotCache cacheId=\"{cache_id}\" r:id=\"rId{}\"/>",
+                    first_id + cache_id - 1
+                )
+            })
+            .collect();
+        format!("<pivotCaches>{}</pivotCaches>", caches.join(""))
+    };
     format!("{XML_DECLARATION}\n\
     <workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+    {workbook_pr}\
+    {workbook_protection}\
     <bookViews>
     <workbookView activeTab=\"{selected_sheet}\"/>\
     </bookViews>
@@ -89,6 +169,7 @@ pub(crate) fn get_workbook_xml(workbook: &Workbook, selected_sheet: u32) -> Stri
       <definedNames>\
         {defined_names}\
       </definedNames>\
-      <calcPr/>\
+      <calcPr{calc_mode}{full_precision}/>\
+      {pivot_caches}\
     </workbook>")
 }
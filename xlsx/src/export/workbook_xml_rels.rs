@@ -1,8 +1,10 @@
 use ironcalc_base::types::Workbook;
 
-use super::xml_constants::{XML_DECLARATION, XML_WORKSHEET};
+use super::xml_constants::{XML_DECLARATION, XML_PIVOT_CACHE_DEFINITION, XML_WORKSHEET};
 
-pub(crate) fn get_workbook_xml_rels(workbook: &Workbook) -> String {
+/// The ids are the sheets, the styles, the shared strings, the `pivot_cache_count` pivot caches
+/// and the theme
+pub(crate) fn get_workbook_xml_rels(workbook: &Workbook, pivot_cache_count: usize) -> String {
     let mut relationships_str: Vec<String> = vec![];
     let worksheet_count = workbook.worksheets.len() + 1;
     for id in 1..worksheet_count {
@@ -18,6 +20,16 @@ pub(crate) fn get_workbook_xml_rels(workbook: &Workbook) -> String {
     relationships_str.push(
         format!("<Relationship Id=\"rId{id}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings\" Target=\"sharedStrings.xml\"/>")
     );
+    for cache_id in 1..=pivot_cache_count {
+        id += 1;
+        relationships_str.push(format!(
+            "<Relationship Id=\"rId{id}\" Type=\"{XML_PIVOT_CACHE_DEFINITION}\" Target=\"pivotCache/pivotCacheDefinition{cache_id}.xml\"/>"
+        ));
+    }
+    id += 1;
+    relationships_str.push(
+        format!("<Relationship Id=\"rId{id}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme\" Target=\"theme/theme1.xml\"/>")
+    );
     format!(
         "{XML_DECLARATION}\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">{}</Relationships>",
         relationships_str.join("")
@@ -0,0 +1,41 @@
+use ironcalc_base::types::Worksheet;
+
+use super::{
+    escape::escape_xml,
+    xml_constants::{XML_DECLARATION, XML_HYPERLINK, XML_PIVOT_TABLE},
+};
+
+/// Returns the relationships of the worksheet, if it has any.
+/// Those are the targets of the external hyperlinks, with ids rId1, rId2, ... in order, followed
+/// by the `pivot_tables` of the sheet (the numbers of their parts).
+pub(crate) fn get_worksheet_xml_rels(
+    worksheet: &Worksheet,
+    pivot_tables: &[usize],
+) -> Option<String> {
+    let mut relationships_str: Vec<String> = worksheet
+        .hyperlinks
+        .iter()
+        .filter_map(|hyperlink| hyperlink.url.as_ref())
+        .enumerate()
+        .map(|(index, url)| {
+            format!(
+                "<Relationship Id=\"rId{}\" Type=\"{XML_HYPERLINK}\" Target=\"{}\" TargetMode=\"External\"/>",
+                index + 1,
+                escape_xml(url)
+            )
+        })
+        .collect();
+    for id in pivot_tables {
+        relationships_str.push(format!(
+            "<Relationship Id=\"rId{}\" Type=\"{XML_PIVOT_TABLE}\" Target=\"../pivotTables/pivotTable{id}.xml\"/>",
+            relationships_str.len() + 1
+        ));
+    }
+    if relationships_str.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{XML_DECLARATION}\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">{}</Relationships>",
+        relationships_str.join("")
+    ))
+}
@@ -23,10 +23,16 @@ use ironcalc_base::{
         types::CellReferenceRC,
         utils::number_to_column,
     },
-    types::{Cell, Worksheet},
+    types::{
+        AutoFilter, Cell, Cfvo, ColumnFilter, ConditionalFormat, ConditionalFormatRule,
+        DataValidation, DataValidationKind, DateTimeGrouping, Dxf, DxfFont, Hyperlink,
+        SheetProtection, Worksheet,
+    },
 };
 
-use super::{escape::escape_xml, xml_constants::XML_DECLARATION};
+use super::{
+    escape::escape_xml, workbook::get_password_hash_attributes, xml_constants::XML_DECLARATION,
+};
 
 fn get_cell_style_attribute(s: i32) -> String {
     if s == 0 {
@@ -51,11 +57,419 @@ fn get_formula_attribute(
     escape_xml(formula).to_string()
 }
 
+fn get_cfvos_xml(cfvos: &[&Cfvo]) -> String {
+    cfvos
+        .iter()
+        .map(|cfvo| match &cfvo.value {
+            Some(value) => format!(
+                "<cfvo type=\"{}\" val=\"{}\"/>",
+                cfvo.kind,
+                escape_xml(value)
+            ),
+            None => format!("<cfvo type=\"{}\"/>", cfvo.kind),
+        })
+        .join("")
+}
+
+fn get_rgb_color_xml(color: &str) -> String {
+    format!("<color rgb=\"FF{}\"/>", color.trim_start_matches('#'))
+}
+
+/// Returns the conditionalFormatting elements of the worksheet.
+/// The rules with a style use the differential formats starting at `dxf_offset`.
+fn get_conditional_formatting_xml(
+    conditional_formats: &[ConditionalFormat],
+    dxf_offset: usize,
+) -> String {
+    let mut dxf_id = dxf_offset;
+    let mut conditional_formatting_str = Vec::new();
+    for (index, conditional_format) in conditional_formats.iter().enumerate() {
+        let priority = index + 1;
+        let dxf = if conditional_format.style.is_some() {
+            dxf_id += 1;
+            format!(" dxfId=\"{}\"", dxf_id - 1)
+        } else {
+            "".to_string()
+        };
+        let stop_if_true = if conditional_format.stop_if_true {
+            " stopIfTrue=\"1\""
+        } else {
+            ""
+        };
+        let formulas_xml = |formulas: &[&String]| {
+            formulas
+                .iter()
+                .map(|formula| format!("<formula>{}</formula>", escape_xml(formula)))
+                .join("")
+        };
+        let (attributes, content) = match &conditional_format.rule {
+            ConditionalFormatRule::CellIs { operator, formulas } => (
+                format!("type=\"cellIs\"{dxf} priority=\"{priority}\" operator=\"{operator}\""),
+                formulas_xml(&formulas.iter().collect::<Vec<_>>()),
+            ),
+            ConditionalFormatRule::Expression { formula } => (
+                format!("type=\"expression\"{dxf} priority=\"{priority}\""),
+                formulas_xml(&[formula]),
+            ),
+            ConditionalFormatRule::Top10 {
+                rank,
+                percent,
+                bottom,
+            } => {
+                let percent = if *percent { " percent=\"1\"" } else { "" };
+                let bottom = if *bottom { " bottom=\"1\"" } else { "" };
+                (
+                    format!(
+                        "type=\"top10\"{dxf} priority=\"{priority}\" rank=\"{rank}\"{percent}{bottom}"
+                    ),
+                    "".to_string(),
+                )
+            }
+            ConditionalFormatRule::AboveAverage {
+                above_average,
+                equal_average,
+                std_dev,
+            } => {
+                let above_average = if *above_average {
+                    ""
+                } else {
+                    " aboveAverage=\"0\""
+                };
+                let equal_average = if *equal_average {
+                    " equalAverage=\"1\""
+                } else {
+                    ""
+                };
+                let std_dev = if *std_dev > 0 {
+                    format!(" stdDev=\"{std_dev}\"")
+                } else {
+                    "".to_string()
+                };
+                (
+                    format!(
+                        "type=\"aboveAverage\"{dxf} priority=\"{priority}\"{above_average}{equal_average}{std_dev}"
+                    ),
+                    "".to_string(),
+                )
+            }
+            ConditionalFormatRule::DuplicateValues => (
+                format!("type=\"duplicateValues\"{dxf} priority=\"{priority}\""),
+                "".to_string(),
+            ),
+            ConditionalFormatRule::UniqueValues => (
+                format!("type=\"uniqueValues\"{dxf} priority=\"{priority}\""),
+                "".to_string(),
+            ),
+            ConditionalFormatRule::ColorScale { cfvos, colors } => {
+                let cfvos = get_cfvos_xml(&cfvos.iter().collect::<Vec<_>>());
+                let colors = colors.iter().map(|c| get_rgb_color_xml(c)).join("");
+                (
+                    format!("type=\"colorScale\" priority=\"{priority}\""),
+                    format!("<colorScale>{cfvos}{colors}</colorScale>"),
+                )
+            }
+            ConditionalFormatRule::DataBar { min, max, color } => {
+                let cfvos = get_cfvos_xml(&[min, max]);
+                let color = get_rgb_color_xml(color);
+                (
+                    format!("type=\"dataBar\" priority=\"{priority}\""),
+                    format!("<dataBar>{cfvos}{color}</dataBar>"),
+                )
+            }
+            ConditionalFormatRule::IconSet {
+                icon_set,
+                cfvos,
+                reverse,
+                show_value,
+            } => {
+                let cfvos = get_cfvos_xml(&cfvos.iter().collect::<Vec<_>>());
+                let reverse = if *reverse { " reverse=\"1\"" } else { "" };
+                let show_value = if *show_value { "" } else { " showValue=\"0\"" };
+                (
+                    format!("type=\"iconSet\" priority=\"{priority}\""),
+                    format!(
+                        "<iconSet iconSet=\"{}\"{reverse}{show_value}>{cfvos}</iconSet>",
+                        escape_xml(icon_set)
+                    ),
+                )
+            }
+        };
+        conditional_formatting_str.push(format!(
+            "<conditionalFormatting sqref=\"{}\"><cfRule {attributes}{stop_if_true}>{content}</cfRule></conditionalFormatting>",
+            escape_xml(&conditional_format.sqref)
+        ));
+    }
+    conditional_formatting_str.join("")
+}
+
+fn get_data_validations_xml(data_validations: &[DataValidation]) -> String {
+    if data_validations.is_empty() {
+        return "".to_string();
+    }
+    let mut data_validations_str = Vec::new();
+    for data_validation in data_validations {
+        let mut attributes = Vec::new();
+        if data_validation.kind != DataValidationKind::Any {
+            attributes.push(format!("type=\"{}\"", data_validation.kind));
+        }
+        if !matches!(
+            data_validation.kind,
+            DataValidationKind::Any | DataValidationKind::List | DataValidationKind::Custom
+        ) {
+            attributes.push(format!("operator=\"{}\"", data_validation.operator));
+        }
+        attributes.push(format!("errorStyle=\"{}\"", data_validation.error_style));
+        for (name, value) in [
+            ("allowBlank", data_validation.allow_blank),
+            ("showDropDown", !data_validation.show_dropdown),
+            ("showInputMessage", data_validation.show_input_message),
+            ("showErrorMessage", data_validation.show_error_message),
+        ] {
+            if value {
+                attributes.push(format!("{name}=\"1\""));
+            }
+        }
+        for (name, value) in [
+            ("errorTitle", &data_validation.error_title),
+            ("error", &data_validation.error_message),
+            ("promptTitle", &data_validation.input_title),
+            ("prompt", &data_validation.input_message),
+        ] {
+            if let Some(value) = value {
+                attributes.push(format!("{name}=\"{}\"", escape_xml(value)));
+            }
+        }
+        attributes.push(format!("sqref=\"{}\"", escape_xml(&data_validation.sqref)));
+        let formulas = data_validation
+            .formulas
+            .iter()
+            .enumerate()
+            .map(|(index, formula)| {
+                format!(
+                    "<formula{index}>{}</formula{index}>",
+                    escape_xml(formula),
+                    index = index + 1
+                )
+            })
+            .join("");
+        data_validations_str.push(format!(
+            "<dataValidation {}>{formulas}</dataValidation>",
+            attributes.join(" ")
+        ));
+    }
+    format!(
+        "<dataValidations count=\"{}\">{}</dataValidations>",
+        data_validations.len(),
+        data_validations_str.join("")
+    )
+}
+
+/// Returns the hyperlinks element of the worksheet.
+/// External links point to their relationship in the order of [get_worksheet_xml_rels].
+///
+/// [get_worksheet_xml_rels]: super::worksheet_xml_rels::get_worksheet_xml_rels
+fn get_hyperlinks_xml(hyperlinks: &[Hyperlink]) -> String {
+    if hyperlinks.is_empty() {
+        return "".to_string();
+    }
+    let mut rel_id = 0;
+    let mut hyperlinks_str: Vec<String> = vec![];
+    for hyperlink in hyperlinks {
+        let mut attributes = vec![format!("ref=\"{}\"", hyperlink.cell_ref)];
+        if hyperlink.url.is_some() {
+            rel_id += 1;
+            attributes.push(format!("r:id=\"rId{rel_id}\""));
+        }
+        if let Some(location) = &hyperlink.location {
+            attributes.push(format!("location=\"{}\"", escape_xml(location)));
+        }
+        if let Some(tooltip) = &hyperlink.tooltip {
+            attributes.push(format!("tooltip=\"{}\"", escape_xml(tooltip)));
+        }
+        hyperlinks_str.push(format!("<hyperlink {}/>", attributes.join(" ")));
+    }
+    format!("<hyperlinks>{}</hyperlinks>", hyperlinks_str.join(""))
+}
+
+/// Returns the sheetProtection element of the worksheet.
+/// Note that in Excel the attributes tell what is *not* allowed.
+///
+/// ECMA-376-1:2016 section 18.3.1.85
+fn get_sheet_protection_xml(protection: &Option<SheetProtection>) -> String {
+    let protection = match protection {
+        Some(protection) => protection,
+        None => return "".to_string(),
+    };
+    let password = get_password_hash_attributes(
+        &protection.password_hash,
+        [
+            "password",
+            "algorithmName",
+            "hashValue",
+            "saltValue",
+            "spinCount",
+        ],
+    );
+    let options = [
+        ("objects", !protection.allow_edit_objects),
+        ("scenarios", !protection.allow_edit_scenarios),
+        ("formatCells", !protection.allow_format_cells),
+        ("formatColumns", !protection.allow_format_columns),
+        ("formatRows", !protection.allow_format_rows),
+        ("insertColumns", !protection.allow_insert_columns),
+        ("insertRows", !protection.allow_insert_rows),
+        ("insertHyperlinks", !protection.allow_insert_hyperlinks),
+        ("deleteColumns", !protection.allow_delete_columns),
+        ("deleteRows", !protection.allow_delete_rows),
+        ("selectLockedCells", !protection.allow_select_locked_cells),
+        ("sort", !protection.allow_sort),
+        ("autoFilter", !protection.allow_auto_filter),
+        ("pivotTables", !protection.allow_pivot_tables),
+        (
+            "selectUnlockedCells",
+            !protection.allow_select_unlocked_cells,
+        ),
+    ]
+    .iter()
+    .map(|(name, value)| format!(" {name}=\"{}\"", i32::from(*value)))
+    .collect::<String>();
+    format!("<sheetProtection{password} sheet=\"1\"{options}/>")
+}
+
+/// The differential formats with the colors of the color filters of the autofilter, in order
+pub(crate) fn get_color_filter_dxfs(worksheet: &Worksheet) -> Vec<Dxf> {
+    let columns = match &worksheet.auto_filter {
+        Some(auto_filter) => &auto_filter.columns,
+        None => return Vec::new(),
+    };
+    columns
+        .iter()
+        .filter_map(|filter_column| match &filter_column.filter {
+            ColumnFilter::Color { color, cell_color } if *cell_color => Some(Dxf {
+                fill_color: Some(color.clone()),
+                ..Default::default()
+            }),
+            ColumnFilter::Color { color, .. } => Some(Dxf {
+                font: Some(DxfFont {
+                    color: Some(color.clone()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the autoFilter element of the worksheet.
+/// Color filters use the differential formats starting at `dxf_offset`.
+///
+/// ECMA-376-1:2016 section 18.3.1.2
+fn get_auto_filter_xml(auto_filter: &Option<AutoFilter>, dxf_offset: usize) -> String {
+    let auto_filter = match auto_filter {
+        Some(auto_filter) => auto_filter,
+        None => return "".to_string(),
+    };
+    let mut dxf_id = dxf_offset;
+    let mut columns_str = Vec::new();
+    for filter_column in &auto_filter.columns {
+        let filter = match &filter_column.filter {
+            ColumnFilter::Values {
+                values,
+                date_groups,
+                blank,
+            } => {
+                let blank = if *blank { " blank=\"1\"" } else { "" };
+                let mut items: Vec<String> = values
+                    .iter()
+                    .map(|value| format!("<filter val=\"{}\"/>", escape_xml(value)))
+                    .collect();
+                for date_group in date_groups {
+                    // Only the fields up to the grouping are written
+                    let fields = [
+                        ("month", date_group.month, DateTimeGrouping::Month),
+                        ("day", date_group.day, DateTimeGrouping::Day),
+                        ("hour", date_group.hour, DateTimeGrouping::Hour),
+                        ("minute", date_group.minute, DateTimeGrouping::Minute),
+                        ("second", date_group.second, DateTimeGrouping::Second),
+                    ];
+                    let depth = fields
+                        .iter()
+                        .position(|(_, _, grouping)| *grouping == date_group.grouping)
+                        .map_or(0, |index| index + 1);
+                    let attributes = fields[..depth]
+                        .iter()
+                        .map(|(name, value, _)| format!(" {name}=\"{value}\""))
+                        .collect::<String>();
+                    items.push(format!(
+                        "<dateGroupItem year=\"{}\"{attributes} dateTimeGrouping=\"{}\"/>",
+                        date_group.year, date_group.grouping
+                    ));
+                }
+                format!("<filters{blank}>{}</filters>", items.join(""))
+            }
+            ColumnFilter::Custom { conditions, and } => {
+                let and = if *and { " and=\"1\"" } else { "" };
+                let conditions = conditions
+                    .iter()
+                    .map(|condition| {
+                        format!(
+                            "<customFilter operator=\"{}\" val=\"{}\"/>",
+                            condition.operator,
+                            escape_xml(&condition.value)
+                        )
+                    })
+                    .collect::<String>();
+                format!("<customFilters{and}>{conditions}</customFilters>")
+            }
+            ColumnFilter::Top10 {
+                rank,
+                percent,
+                bottom,
+            } => {
+                let top = if *bottom { " top=\"0\"" } else { "" };
+                let percent = if *percent { " percent=\"1\"" } else { "" };
+                format!("<top10{top}{percent} val=\"{rank}\"/>")
+            }
+            ColumnFilter::Color { cell_color, .. } => {
+                let cell_color = if *cell_color { "" } else { " cellColor=\"0\"" };
+                dxf_id += 1;
+                format!("<colorFilter dxfId=\"{}\"{cell_color}/>", dxf_id - 1)
+            }
+        };
+        columns_str.push(format!(
+            "<filterColumn colId=\"{}\">{filter}</filterColumn>",
+            filter_column.column_id
+        ));
+    }
+    format!(
+        "<autoFilter ref=\"{}\">{}</autoFilter>",
+        auto_filter.reference,
+        columns_str.join("")
+    )
+}
+
+// Attributes `hidden`, `outlineLevel` and `collapsed` of rows and columns
+fn get_outline_attributes(hidden: bool, outline_level: u8, collapsed: bool) -> String {
+    let mut attributes = String::new();
+    if hidden {
+        attributes.push_str(" hidden=\"1\"");
+    }
+    if outline_level > 0 {
+        attributes.push_str(&format!(" outlineLevel=\"{outline_level}\""));
+    }
+    if collapsed {
+        attributes.push_str(" collapsed=\"1\"");
+    }
+    attributes
+}
+
 pub(crate) fn get_worksheet_xml(
     worksheet: &Worksheet,
     parsed_formulas: &[Node],
     dimension: &str,
     is_sheet_selected: bool,
+    dxf_offset: usize,
 ) -> String {
     let mut sheet_data_str: Vec<String> = vec![];
     let mut cols_str: Vec<String> = vec![];
@@ -71,8 +485,9 @@ pub(crate) fn get_worksheet_xml(
             Some(s) => format!(" style=\"{s}\""),
             None => "".to_string(),
         };
+        let outline = get_outline_attributes(col.hidden, col.outline_level, col.collapsed);
         cols_str.push(format!(
-            "<col min=\"{min}\" max=\"{max}\" width=\"{width}\" customWidth=\"{custom_width}\"{column_style}/>"
+            "<col min=\"{min}\" max=\"{max}\" width=\"{width}\" customWidth=\"{custom_width}\"{column_style}{outline}/>"
         ));
     }
 
@@ -90,7 +505,18 @@ pub(crate) fn get_worksheet_xml(
         row_style_dict.insert(row.r, row.clone());
     }
 
-    for (row_index, row_data) in worksheet.sheet_data.iter().sorted_by_key(|x| x.0) {
+    // Rows with a style but without cells (like hidden or grouped rows) are exported too
+    let empty_row = HashMap::new();
+    let row_indices = worksheet
+        .sheet_data
+        .keys()
+        .chain(row_style_dict.keys())
+        .copied()
+        .sorted()
+        .dedup()
+        .collect::<Vec<i32>>();
+    for row_index in &row_indices {
+        let row_data = worksheet.sheet_data.get(row_index).unwrap_or(&empty_row);
         let mut row_data_str: Vec<String> = vec![];
         for (column_index, cell) in row_data.iter().sorted_by_key(|x| x.0) {
             let column_name = number_to_column(*column_index).unwrap();
@@ -220,11 +646,11 @@ pub(crate) fn get_worksheet_xml(
         }
         let row_style_str = match row_style_dict.get(row_index) {
             Some(row_style) => {
-                let hidden_str = if row_style.hidden {
-                    r#" hidden="1""#
-                } else {
-                    ""
-                };
+                let hidden_str = get_outline_attributes(
+                    row_style.hidden,
+                    row_style.outline_level,
+                    row_style.collapsed,
+                );
                 format!(
                     r#" s="{}" ht="{}" customHeight="{}" customFormat="{}"{}"#,
                     row_style.s,
@@ -297,6 +723,39 @@ pub(crate) fn get_worksheet_xml(
         "".to_string()
     };
 
+    // Excel needs the maximum outline levels to show the outline symbols
+    let outline_level_row = worksheet
+        .rows
+        .iter()
+        .map(|row| row.outline_level)
+        .max()
+        .unwrap_or(0);
+    let outline_level_col = worksheet
+        .cols
+        .iter()
+        .map(|col| col.outline_level)
+        .max()
+        .unwrap_or(0);
+    let sheet_format = if outline_level_row > 0 || outline_level_col > 0 {
+        format!("<sheetFormatPr defaultRowHeight=\"14.5\" outlineLevelRow=\"{outline_level_row}\" outlineLevelCol=\"{outline_level_col}\"/>")
+    } else {
+        "".to_string()
+    };
+
+    let conditional_formatting =
+        get_conditional_formatting_xml(&worksheet.conditional_formats, dxf_offset);
+    let data_validations = get_data_validations_xml(&worksheet.data_validations);
+    let hyperlinks = get_hyperlinks_xml(&worksheet.hyperlinks);
+    let sheet_protection = get_sheet_protection_xml(&worksheet.protection);
+    // The color filters use the differential formats after the ones of the conditional formats
+    let color_filter_dxf_offset = dxf_offset
+        + worksheet
+            .conditional_formats
+            .iter()
+            .filter(|conditional_format| conditional_format.style.is_some())
+            .count();
+    let auto_filter = get_auto_filter_xml(&worksheet.auto_filter, color_filter_dxf_offset);
+
     format!(
         "{XML_DECLARATION}
 <worksheet \
@@ -308,11 +767,17 @@ xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">
         <selection activeCell=\"{active_cell}\" sqref=\"{sqref}\"/>\
     </sheetView>\
   </sheetViews>\
+  {sheet_format}\
   {cols}\
   <sheetData>\
   {sheet_data}\
   </sheetData>\
+  {sheet_protection}\
+  {auto_filter}\
   {merge_cells_section}\
+  {conditional_formatting}\
+  {data_validations}\
+  {hyperlinks}\
 </worksheet>"
     )
 }
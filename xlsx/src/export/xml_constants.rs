@@ -3,3 +3,15 @@ pub(crate) const XML_DECLARATION: &str =
 
 pub(crate) const XML_WORKSHEET: &str =
     r#"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet"#;
+
+pub(crate) const XML_HYPERLINK: &str =
+    r#"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"#;
+
+pub(crate) const XML_PIVOT_TABLE: &str =
+    r#"http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotTable"#;
+
+pub(crate) const XML_PIVOT_CACHE_DEFINITION: &str =
+    r#"http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheDefinition"#;
+
+pub(crate) const XML_PIVOT_CACHE_RECORDS: &str =
+    r#"http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheRecords"#;
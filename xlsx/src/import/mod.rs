@@ -1,8 +1,10 @@
 mod colors;
 mod metadata;
+mod pivot_tables;
 mod shared_strings;
 mod styles;
 mod tables;
+mod theme;
 mod util;
 mod workbook;
 mod worksheets;
@@ -26,6 +28,7 @@ use shared_strings::read_shared_strings;
 
 use metadata::load_metadata;
 use styles::load_styles;
+use theme::load_theme;
 use util::get_attribute;
 use workbook::load_workbook;
 use worksheets::{load_sheets, Relationship};
@@ -62,18 +65,21 @@ fn load_xlsx_from_reader<R: Read + std::io::Seek>(
 ) -> Result<Workbook, XlsxError> {
     let mut archive = zip::ZipArchive::new(reader)?;
 
-    let mut shared_strings = read_shared_strings(&mut archive)?;
-    let workbook = load_workbook(&mut archive)?;
     let rels = load_relationships(&mut archive)?;
+    let theme = load_theme(&mut archive, &rels)?;
+    let (mut shared_strings, rich_text) = read_shared_strings(&mut archive, &theme)?;
+    let workbook = load_workbook(&mut archive)?;
     let mut tables = HashMap::new();
+    let (styles, dxfs) = load_styles(&mut archive, &theme)?;
     let (worksheets, selected_sheet) = load_sheets(
         &mut archive,
         &rels,
         &workbook,
         &mut tables,
         &mut shared_strings,
+        &dxfs,
+        &theme,
     )?;
-    let styles = load_styles(&mut archive)?;
     let metadata = match load_metadata(&mut archive) {
         Ok(metadata) => metadata,
         Err(_) => {
@@ -99,17 +105,23 @@ fn load_xlsx_from_reader<R: Read + std::io::Seek>(
     );
     Ok(Workbook {
         shared_strings,
+        rich_text,
         defined_names: workbook.defined_names,
         worksheets,
         styles,
+        theme,
         name,
         settings: WorkbookSettings {
             tz: tz.to_string(),
             locale: locale.to_string(),
+            date_1904: workbook.date_1904,
+            precision_as_displayed: workbook.precision_as_displayed,
+            calc_mode: workbook.calc_mode,
         },
         metadata,
         tables,
         views,
+        protection: workbook.protection,
     })
 }
 
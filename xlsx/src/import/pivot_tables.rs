@@ -0,0 +1,256 @@
+use std::io::Read;
+
+use ironcalc_base::types::{
+    PivotFilter, PivotFunction, PivotSource, PivotTable, PivotTableParts, PivotValueField,
+};
+use roxmltree::Node;
+
+use crate::error::XlsxError;
+
+use super::util::get_attribute;
+
+const XML_RELATIONSHIPS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+fn read_file<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    path: &str,
+) -> Result<String, XlsxError> {
+    let mut file = archive.by_name(path)?;
+    let mut text = String::new();
+    file.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// Returns the path of the `target` of a relationship of the part in `path`
+fn resolve_target(path: &str, target: &str) -> String {
+    if let Some(target) = target.strip_prefix('/') {
+        return target.to_string();
+    }
+    let mut parts: Vec<&str> = path.split('/').collect();
+    // The name of the part
+    parts.pop();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                parts.pop();
+            }
+            "." => {}
+            segment => parts.push(segment),
+        }
+    }
+    parts.join("/")
+}
+
+/// Returns the path of the relationship of the part in `path` with the type ending in `kind`
+/// or with the `id`
+fn get_related_part<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    path: &str,
+    kind: &str,
+    id: Option<&str>,
+) -> Result<Option<String>, XlsxError> {
+    // xl/pivotTables/pivotTable1.xml -> xl/pivotTables/_rels/pivotTable1.xml.rels
+    let (folder, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let rels_path = format!("{folder}/_rels/{name}.rels");
+    let text = match read_file(archive, &rels_path) {
+        Ok(text) => text,
+        Err(_) => return Ok(None),
+    };
+    let document = roxmltree::Document::parse(&text)?;
+    for rel in document
+        .descendants()
+        .filter(|n| n.has_tag_name("Relationship"))
+    {
+        let matches = match id {
+            Some(id) => rel.attribute("Id") == Some(id),
+            None => get_attribute(&rel, "Type")?.ends_with(kind),
+        };
+        if matches {
+            let target = get_attribute(&rel, "Target")?;
+            return Ok(Some(resolve_target(path, target)));
+        }
+    }
+    Ok(None)
+}
+
+fn get_flag(node: Node, name: &str) -> bool {
+    // defaults to true
+    !matches!(node.attribute(name), Some("0") | Some("false"))
+}
+
+fn get_index(node: Node, name: &str) -> Option<i32> {
+    node.attribute(name)?.parse::<i32>().ok()
+}
+
+fn load_pivot_function(subtotal: Option<&str>) -> PivotFunction {
+    match subtotal.unwrap_or("sum") {
+        "average" => PivotFunction::Average,
+        "count" => PivotFunction::Count,
+        "countNums" => PivotFunction::CountNums,
+        "max" => PivotFunction::Max,
+        "min" => PivotFunction::Min,
+        "product" => PivotFunction::Product,
+        "stdDev" => PivotFunction::StdDev,
+        "stdDevp" => PivotFunction::StdDevp,
+        "var" => PivotFunction::Var,
+        "varp" => PivotFunction::Varp,
+        _ => PivotFunction::Sum,
+    }
+}
+
+// <cacheField name="Region" numFmtId="0">
+//   <sharedItems count="2"><s v="East"/><s v="West"/></sharedItems>
+// </cacheField>
+fn load_cache_field(node: Node) -> Result<(String, Vec<String>), XlsxError> {
+    let name = get_attribute(&node, "name")?.to_string();
+    let mut items = Vec::new();
+    if let Some(shared_items) = node.children().find(|n| n.has_tag_name("sharedItems")) {
+        for item in shared_items.children().filter(|n| n.is_element()) {
+            let value = item.attribute("v").unwrap_or_default();
+            let text = match item.tag_name().name() {
+                "m" => "(blank)".to_string(),
+                "b" if value == "1" || value == "true" => "TRUE".to_string(),
+                "b" => "FALSE".to_string(),
+                _ => value.to_string(),
+            };
+            items.push(text);
+        }
+    }
+    Ok((name, items))
+}
+
+/// Reads the pivot table in `path` with its cache definition and records
+pub(crate) fn load_pivot_table<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    path: &str,
+) -> Result<PivotTable, XlsxError> {
+    let definition = read_file(archive, path)?;
+    let cache_path = get_related_part(archive, path, "pivotCacheDefinition", None)?
+        .ok_or_else(|| XlsxError::Xml("Missing pivot cache definition".to_string()))?;
+    let cache_definition = read_file(archive, &cache_path)?;
+
+    // Cache definition: the source and the fields
+    let document = roxmltree::Document::parse(&cache_definition)?;
+    let cache = document.root_element();
+    let records_id = cache.attribute((XML_RELATIONSHIPS, "id"));
+    let source = cache
+        .descendants()
+        .find(|n| n.has_tag_name("worksheetSource"))
+        .ok_or_else(|| XlsxError::Xml("Unsupported pivot table source".to_string()))?;
+    let source = match (source.attribute("name"), source.attribute("ref")) {
+        (Some(name), _) => PivotSource::Table {
+            name: name.to_string(),
+        },
+        (None, Some(reference)) => PivotSource::Range {
+            sheet_name: get_attribute(&source, "sheet")?.to_string(),
+            reference: reference.to_string(),
+        },
+        (None, None) => {
+            return Err(XlsxError::Xml("Missing pivot table source".to_string()));
+        }
+    };
+    let fields = cache
+        .descendants()
+        .filter(|n| n.has_tag_name("cacheField"))
+        .map(load_cache_field)
+        .collect::<Result<Vec<_>, XlsxError>>()?;
+    let cache_records = match records_id {
+        Some(id) => match get_related_part(archive, &cache_path, "", Some(id))? {
+            Some(records_path) => Some(read_file(archive, &records_path)?),
+            None => None,
+        },
+        None => None,
+    };
+
+    // Pivot table definition
+    let document = roxmltree::Document::parse(&definition)?;
+    let pivot = document.root_element();
+    let name = get_attribute(&pivot, "name")?.to_string();
+    let reference = pivot
+        .children()
+        .find(|n| n.has_tag_name("location"))
+        .and_then(|n| n.attribute("ref"))
+        .ok_or_else(|| XlsxError::Xml("Missing pivot table location".to_string()))?
+        .to_string();
+    let location = reference.split(':').next().unwrap_or_default().to_string();
+    let field_name = |index: Option<i32>| -> Option<String> {
+        fields
+            .get(usize::try_from(index?).ok()?)
+            .map(|(name, _)| name.clone())
+    };
+    // Field indices of the <field x="0"/> in the rowFields and colFields, "-2" are the values
+    let axis_fields = |tag: &str| -> Vec<String> {
+        pivot
+            .children()
+            .filter(|n| n.has_tag_name(tag))
+            .flat_map(|n| n.children().filter(|n| n.has_tag_name("field")))
+            .filter_map(|n| field_name(get_index(n, "x")))
+            .collect()
+    };
+    let row_fields = axis_fields("rowFields");
+    let column_fields = axis_fields("colFields");
+    let value_fields = pivot
+        .descendants()
+        .filter(|n| n.has_tag_name("dataField"))
+        .filter_map(|n| {
+            let field = field_name(get_index(n, "fld"))?;
+            let function = load_pivot_function(n.attribute("subtotal"));
+            let name = n
+                .attribute("name")
+                .map(|s| s.to_string())
+                .unwrap_or_default();
+            Some(PivotValueField {
+                field,
+                function,
+                name,
+            })
+        })
+        .collect();
+    let mut filters = Vec::new();
+    let mut subtotals = true;
+    let pivot_fields = pivot.descendants().filter(|n| n.has_tag_name("pivotField"));
+    for (index, pivot_field) in pivot_fields.enumerate() {
+        let (name, items) = match fields.get(index) {
+            Some(field) => field,
+            None => break,
+        };
+        if row_fields.contains(name) && !get_flag(pivot_field, "defaultSubtotal") {
+            subtotals = false;
+        }
+        // <item h="1" x="2"/> hides the third item of the field
+        let hidden_items: Vec<String> = pivot_field
+            .descendants()
+            .filter(|n| n.has_tag_name("item") && n.attribute("h") == Some("1"))
+            .filter_map(|n| {
+                items
+                    .get(usize::try_from(get_index(n, "x")?).ok()?)
+                    .cloned()
+            })
+            .collect();
+        if !hidden_items.is_empty() {
+            filters.push(PivotFilter {
+                field: name.clone(),
+                hidden_items,
+            });
+        }
+    }
+    Ok(PivotTable {
+        name,
+        source,
+        location,
+        reference: Some(reference),
+        row_fields,
+        column_fields,
+        value_fields,
+        filters,
+        row_grand_totals: get_flag(pivot, "rowGrandTotals"),
+        column_grand_totals: get_flag(pivot, "colGrandTotals"),
+        subtotals,
+        xlsx_parts: Some(PivotTableParts {
+            definition,
+            cache_definition,
+            cache_records,
+        }),
+    })
+}
@@ -1,39 +1,71 @@
-use std::io::Read;
+use std::{collections::HashMap, io::Read};
 
+use ironcalc_base::types::{TextRun, Theme};
 use roxmltree::Node;
 
 use crate::error::XlsxError;
 
+use super::styles::load_font;
+
+/// The plain text of the shared strings and the runs of those with rich text, by index
+type SharedStrings = (Vec<String>, HashMap<i32, Vec<TextRun>>);
+
 /// Reads the list of shared strings in an Excel workbook
-/// Note than in IronCalc we lose _internal_ styling of a string
 /// See Section 18.4
 pub(crate) fn read_shared_strings<R: Read + std::io::Seek>(
     archive: &mut zip::read::ZipArchive<R>,
-) -> Result<Vec<String>, XlsxError> {
+    theme: &Theme,
+) -> Result<SharedStrings, XlsxError> {
     match archive.by_name("xl/sharedStrings.xml") {
         Ok(mut file) => {
             let mut text = String::new();
             file.read_to_string(&mut text)?;
-            read_shared_strings_from_string(&text)
+            read_shared_strings_from_string(&text, theme)
         }
-        Err(_e) => Ok(Vec::new()),
+        Err(_e) => Ok((Vec::new(), HashMap::new())),
     }
 }
 
-fn read_shared_strings_from_string(text: &str) -> Result<Vec<String>, XlsxError> {
+/// Returns the text of the `t` children of the node
+fn get_text(node: Node) -> String {
+    node.children()
+        .filter(|n| n.has_tag_name("t"))
+        .map(|n| n.text().unwrap_or(""))
+        .collect()
+}
+
+fn read_shared_strings_from_string(text: &str, theme: &Theme) -> Result<SharedStrings, XlsxError> {
     let doc = roxmltree::Document::parse(text)?;
     let mut shared_strings = Vec::new();
+    let mut rich_text = HashMap::new();
     let nodes: Vec<Node> = doc.descendants().filter(|n| n.has_tag_name("si")).collect();
     for node in nodes {
-        let text = node
-            .descendants()
-            .filter(|n| n.has_tag_name("t"))
-            .map(|n| n.text().unwrap_or("").to_string())
-            .collect::<Vec<String>>()
-            .join("");
+        // 18.4.8 si (String Item)
+        // The text is either in a `t` element or in a list of runs `r` (18.4.4)
+        // We skip the phonetic runs `rPh`
+        let mut runs = Vec::new();
+        for run in node.children().filter(|n| n.has_tag_name("r")) {
+            let font = match run.children().find(|n| n.has_tag_name("rPr")) {
+                Some(properties) => {
+                    let mut font = load_font(properties, theme)?;
+                    // The colors of rich text do not change with the theme
+                    font.color_theme = None;
+                    Some(font)
+                }
+                None => None,
+            };
+            runs.push(TextRun {
+                text: get_text(run),
+                font,
+            });
+        }
+        let text = get_text(node) + &runs.iter().map(|run| run.text.as_str()).collect::<String>();
+        if runs.iter().any(|run| run.font.is_some()) {
+            rich_text.insert(shared_strings.len() as i32, runs);
+        }
         shared_strings.push(text);
     }
-    Ok(shared_strings)
+    Ok((shared_strings, rich_text))
 }
 
 #[cfg(test)]
@@ -67,7 +99,8 @@ mod tests {
         </r>
     </si>
 </sst>"#;
-        let shared_strings = read_shared_strings_from_string(xml_string.trim()).unwrap();
+        let (shared_strings, rich_text) =
+            read_shared_strings_from_string(xml_string.trim(), &Theme::default()).unwrap();
         assert_eq!(
             shared_strings,
             [
@@ -76,5 +109,14 @@ mod tests {
                 "Hello World".to_string()
             ]
         );
+        let runs = &rich_text[&2];
+        assert_eq!(rich_text.len(), 1);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].text, "Hello");
+        assert_eq!(runs[0].font, None);
+        let font = runs[1].font.as_ref().unwrap();
+        assert_eq!(runs[1].text, " World");
+        assert!(font.b);
+        assert_eq!(font.color, Some("#FF0000".to_string()));
     }
 }
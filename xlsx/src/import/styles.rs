@@ -1,18 +1,22 @@
 use std::{collections::HashMap, io::Read};
 
 use ironcalc_base::types::{
-    Alignment, Border, BorderItem, BorderStyle, CellStyleXfs, CellStyles, CellXfs, Fill, Font,
-    FontScheme, HorizontalAlignment, NumFmt, Styles, VerticalAlignment,
+    Alignment, Border, BorderItem, BorderStyle, CellStyleXfs, CellStyles, CellXfs, Dxf, DxfFont,
+    Fill, Font, FontScheme, FontVerticalAlignment, HorizontalAlignment, NumFmt, Protection, Styles,
+    Theme, VerticalAlignment,
 };
 use roxmltree::Node;
 
 use crate::error::XlsxError;
 
-use super::util::{get_attribute, get_bool, get_bool_false, get_color, get_number};
+use super::util::{
+    get_attribute, get_bool, get_bool_false, get_color, get_number, get_theme_color,
+};
 
-fn get_border(node: Node, name: &str) -> Result<Option<BorderItem>, XlsxError> {
+fn get_border(node: Node, name: &str, theme: &Theme) -> Result<Option<BorderItem>, XlsxError> {
     let style;
     let color;
+    let color_theme;
     let border_nodes = node
         .children()
         .filter(|n| n.has_tag_name(name))
@@ -40,19 +44,241 @@ fn get_border(node: Node, name: &str) -> Result<Option<BorderItem>, XlsxError> {
             .filter(|n| n.has_tag_name("color"))
             .collect::<Vec<Node>>();
         if color_node.len() == 1 {
-            color = get_color(color_node[0])?;
+            color = get_color(color_node[0], theme)?;
+            color_theme = get_theme_color(color_node[0])?;
         } else {
             color = None;
+            color_theme = None;
         }
     } else {
         return Ok(None);
     }
-    Ok(Some(BorderItem { style, color }))
+    Ok(Some(BorderItem {
+        style,
+        color,
+        color_theme,
+    }))
+}
+
+// <b/> and <b val="1"/> are true, <b val="0"/> is false
+fn get_dxf_bool(node: Node) -> bool {
+    !matches!(node.attribute("val"), Some("0") | Some("false"))
+}
+
+// Differential formats used by conditional formats
+// <dxf>
+//     <font><b/><color rgb="FF9C0006"/></font>
+//     <numFmt numFmtId="164" formatCode="0.00"/>
+//     <fill><patternFill><bgColor rgb="FFFFC7CE"/></patternFill></fill>
+// </dxf>
+fn load_dxf(dxf: Node, theme: &Theme) -> Result<Dxf, XlsxError> {
+    let mut result = Dxf::default();
+    for feature in dxf.children() {
+        match feature.tag_name().name() {
+            "font" => {
+                let mut font = DxfFont::default();
+                for property in feature.children() {
+                    match property.tag_name().name() {
+                        "b" => font.b = Some(get_dxf_bool(property)),
+                        "i" => font.i = Some(get_dxf_bool(property)),
+                        "strike" => font.strike = Some(get_dxf_bool(property)),
+                        "u" => font.u = Some(property.attribute("val") != Some("none")),
+                        "color" => font.color = get_color(property, theme)?,
+                        _ => {}
+                    }
+                }
+                result.font = Some(font);
+            }
+            "numFmt" => {
+                result.num_fmt = feature.attribute("formatCode").map(|s| s.to_string());
+            }
+            "fill" => {
+                // In a dxf the color of a solid fill is the background color
+                for pattern_fill in feature.children().filter(|n| n.has_tag_name("patternFill")) {
+                    for color in pattern_fill.children() {
+                        match color.tag_name().name() {
+                            "bgColor" => result.fill_color = get_color(color, theme)?,
+                            "fgColor" if result.fill_color.is_none() => {
+                                result.fill_color = get_color(color, theme)?
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            "border" => {
+                // The colors of differential formats do not change with the theme
+                let get_dxf_border = |name| -> Result<Option<BorderItem>, XlsxError> {
+                    Ok(get_border(feature, name, theme)?.map(|item| BorderItem {
+                        color_theme: None,
+                        ..item
+                    }))
+                };
+                result.border = Some(Border {
+                    diagonal_up: false,
+                    diagonal_down: false,
+                    left: get_dxf_border("left")?,
+                    right: get_dxf_border("right")?,
+                    top: get_dxf_border("top")?,
+                    bottom: get_dxf_border("bottom")?,
+                    diagonal: None,
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(result)
+}
+
+/// Reads a font of the stylesheet or the properties of a rich text run (18.4.7 rPr)
+pub(super) fn load_font(font: Node, theme: &Theme) -> Result<Font, XlsxError> {
+    let mut sz = 11;
+    let mut name = "Calibri".to_string();
+    // NOTE: In Excel you can have simple underline or double underline
+    // In IronCalc convert double underline to simple
+    // This in excel is u with a value of "double"
+    let mut u = false;
+    let mut b = false;
+    let mut i = false;
+    let mut strike = false;
+    let mut color = Some("FFFFFF00".to_string());
+    let mut color_theme = None;
+    let mut family = 2;
+    let mut scheme = FontScheme::default();
+    let mut vert_align = FontVerticalAlignment::default();
+    for feature in font.children().filter(|n| n.is_element()) {
+        match feature.tag_name().name() {
+            "sz" => {
+                sz = feature
+                    .attribute("val")
+                    .unwrap_or("11")
+                    .parse::<i32>()
+                    .unwrap_or(11);
+            }
+            "color" => {
+                color = get_color(feature, theme)?;
+                color_theme = get_theme_color(feature)?;
+            }
+            "u" => {
+                u = true;
+            }
+            "b" => {
+                b = true;
+            }
+            "i" => {
+                i = true;
+            }
+            "strike" => {
+                strike = true;
+            }
+            // The font name is `rFont` in rich text runs
+            "name" | "rFont" => name = feature.attribute("val").unwrap_or("Calibri").to_string(),
+            "vertAlign" => {
+                vert_align = match feature.attribute("val") {
+                    Some("superscript") => FontVerticalAlignment::Superscript,
+                    Some("subscript") => FontVerticalAlignment::Subscript,
+                    _ => FontVerticalAlignment::Baseline,
+                }
+            }
+            // If there is a theme the font scheme and family overrides other properties like the name
+            "family" => {
+                family = feature
+                    .attribute("val")
+                    .unwrap_or("2")
+                    .parse::<i32>()
+                    .unwrap_or(2);
+            }
+            "scheme" => {
+                scheme = match feature.attribute("val") {
+                    None => FontScheme::default(),
+                    Some("minor") => FontScheme::Minor,
+                    Some("major") => FontScheme::Major,
+                    Some("none") => FontScheme::None,
+                    // TODO: Should we fail?
+                    Some(_) => FontScheme::default(),
+                }
+            }
+            "charset" => {}
+            _ => {
+                println!("Unexpected feature {:?}", feature);
+            }
+        }
+    }
+    Ok(Font {
+        strike,
+        u,
+        b,
+        i,
+        sz,
+        color,
+        color_theme,
+        name,
+        family,
+        scheme,
+        vert_align,
+    })
 }
 
+/// Reads the alignment of a cell format (18.8.1 alignment)
+fn load_alignment(xf: Node) -> Option<Alignment> {
+    let alignment_nodes = xf
+        .children()
+        .filter(|n| n.has_tag_name("alignment"))
+        .collect::<Vec<Node>>();
+    if alignment_nodes.len() == 1 {
+        let alignment_node = alignment_nodes[0];
+        let wrap_text = get_bool_false(alignment_node, "wrapText");
+
+        let horizontal = match alignment_node.attribute("horizontal") {
+            Some("center") => HorizontalAlignment::Center,
+            Some("centerContinuous") => HorizontalAlignment::CenterContinuous,
+            Some("distributed") => HorizontalAlignment::Distributed,
+            Some("fill") => HorizontalAlignment::Fill,
+            Some("general") => HorizontalAlignment::General,
+            Some("justify") => HorizontalAlignment::Justify,
+            Some("left") => HorizontalAlignment::Left,
+            Some("right") => HorizontalAlignment::Right,
+            // TODO: Should we fail in this case or set the alignment to default?
+            Some(_) => HorizontalAlignment::default(),
+            None => HorizontalAlignment::default(),
+        };
+
+        let vertical = match alignment_node.attribute("vertical") {
+            Some("bottom") => VerticalAlignment::Bottom,
+            Some("center") => VerticalAlignment::Center,
+            Some("distributed") => VerticalAlignment::Distributed,
+            Some("justify") => VerticalAlignment::Justify,
+            Some("top") => VerticalAlignment::Top,
+            // TODO: Should we fail in this case or set the alignment to default?
+            Some(_) => VerticalAlignment::default(),
+            None => VerticalAlignment::default(),
+        };
+
+        Some(Alignment {
+            horizontal,
+            vertical,
+            wrap_text,
+        })
+    } else {
+        None
+    }
+}
+
+/// Reads the protection of a cell format (18.8.33 protection)
+fn load_protection(xf: Node) -> Option<Protection> {
+    xf.children()
+        .find(|n| n.has_tag_name("protection"))
+        .map(|protection_node| Protection {
+            locked: get_bool(protection_node, "locked"),
+            hidden: get_bool_false(protection_node, "hidden"),
+        })
+}
+
+/// Returns the styles of the workbook and the list of differential formats
 pub(super) fn load_styles<R: Read + std::io::Seek>(
     archive: &mut zip::read::ZipArchive<R>,
-) -> Result<Styles, XlsxError> {
+    theme: &Theme,
+) -> Result<(Styles, Vec<Dxf>), XlsxError> {
     let mut file = archive.by_name("xl/styles.xml")?;
     let mut text = String::new();
     file.read_to_string(&mut text)?;
@@ -84,78 +310,7 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
         .filter(|n| n.has_tag_name("fonts"))
         .collect::<Vec<Node>>()[0];
     for font in font_nodes.children() {
-        let mut sz = 11;
-        let mut name = "Calibri".to_string();
-        // NOTE: In Excel you can have simple underline or double underline
-        // In IronCalc convert double underline to simple
-        // This in excel is u with a value of "double"
-        let mut u = false;
-        let mut b = false;
-        let mut i = false;
-        let mut strike = false;
-        let mut color = Some("FFFFFF00".to_string());
-        let mut family = 2;
-        let mut scheme = FontScheme::default();
-        for feature in font.children() {
-            match feature.tag_name().name() {
-                "sz" => {
-                    sz = feature
-                        .attribute("val")
-                        .unwrap_or("11")
-                        .parse::<i32>()
-                        .unwrap_or(11);
-                }
-                "color" => {
-                    color = get_color(feature)?;
-                }
-                "u" => {
-                    u = true;
-                }
-                "b" => {
-                    b = true;
-                }
-                "i" => {
-                    i = true;
-                }
-                "strike" => {
-                    strike = true;
-                }
-                "name" => name = feature.attribute("val").unwrap_or("Calibri").to_string(),
-                // If there is a theme the font scheme and family overrides other properties like the name
-                "family" => {
-                    family = feature
-                        .attribute("val")
-                        .unwrap_or("2")
-                        .parse::<i32>()
-                        .unwrap_or(2);
-                }
-                "scheme" => {
-                    scheme = match feature.attribute("val") {
-                        None => FontScheme::default(),
-                        Some("minor") => FontScheme::Minor,
-                        Some("major") => FontScheme::Major,
-                        Some("none") => FontScheme::None,
-                        // TODO: Should we fail?
-                        Some(_) => FontScheme::default(),
-                    }
-                }
-                "charset" => {}
-                _ => {
-                    println!("Unexpected feature {:?}", feature);
-                }
-            }
-        }
-        fonts.push(Font {
-            strike,
-            u,
-            b,
-            i,
-            sz,
-            color,
-            name,
-            family,
-            scheme,
-        });
+        fonts.push(load_font(font, theme)?);
     }
 
     let mut fills = Vec::new();
@@ -173,8 +328,7 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
             // Some fills do not have a patternFill, but they have gradientFill
             fills.push(Fill {
                 pattern_type: "solid".to_string(),
-                fg_color: None,
-                bg_color: None,
+                ..Default::default()
             });
             continue;
         }
@@ -185,14 +339,18 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
             .unwrap_or("none")
             .to_string();
         let mut fg_color = None;
+        let mut fg_color_theme = None;
         let mut bg_color = None;
+        let mut bg_color_theme = None;
         for feature in pattern_fill.children() {
             match feature.tag_name().name() {
                 "fgColor" => {
-                    fg_color = get_color(feature)?;
+                    fg_color = get_color(feature, theme)?;
+                    fg_color_theme = get_theme_color(feature)?;
                 }
                 "bgColor" => {
-                    bg_color = get_color(feature)?;
+                    bg_color = get_color(feature, theme)?;
+                    bg_color_theme = get_theme_color(feature)?;
                 }
                 _ => {
                     println!("Unexpected pattern");
@@ -203,7 +361,9 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
         fills.push(Fill {
             pattern_type,
             fg_color,
+            fg_color_theme,
             bg_color,
+            bg_color_theme,
         })
     }
 
@@ -215,11 +375,11 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
     for border in border_nodes.children() {
         let diagonal_up = get_bool_false(border, "diagonal_up");
         let diagonal_down = get_bool_false(border, "diagonal_down");
-        let left = get_border(border, "left")?;
-        let right = get_border(border, "right")?;
-        let top = get_border(border, "top")?;
-        let bottom = get_border(border, "bottom")?;
-        let diagonal = get_border(border, "diagonal")?;
+        let left = get_border(border, "left", theme)?;
+        let right = get_border(border, "right", theme)?;
+        let top = get_border(border, "top", theme)?;
+        let bottom = get_border(border, "bottom", theme)?;
+        let diagonal = get_border(border, "diagonal", theme)?;
         borders.push(Border {
             diagonal_up,
             diagonal_down,
@@ -259,6 +419,8 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
             apply_protection,
             apply_font,
             apply_fill,
+            alignment: load_alignment(xfs),
+            protection: load_protection(xfs),
         });
     }
 
@@ -271,7 +433,10 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
     for cell_style in cell_style_nodes.children() {
         let name = get_attribute(&cell_style, "name")?.to_string();
         let xf_id = get_number(cell_style, "xfId");
-        let builtin_id = get_number(cell_style, "builtinId");
+        let builtin_id = match cell_style.attribute("builtinId") {
+            Some(builtin_id) => Some(builtin_id.parse::<i32>()?),
+            None => None,
+        };
         style_names.insert(xf_id, name.clone());
         cell_styles.push(CellStyles {
             name,
@@ -302,47 +467,8 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
         // TODO: Pivot Tables
         // let pivotButton = get_bool(xfs, "pivotButton");
 
-        let alignment_nodes = xfs
-            .children()
-            .filter(|n| n.has_tag_name("alignment"))
-            .collect::<Vec<Node>>();
-        let alignment = if alignment_nodes.len() == 1 {
-            let alignment_node = alignment_nodes[0];
-            let wrap_text = get_bool_false(alignment_node, "wrapText");
-
-            let horizontal = match alignment_node.attribute("horizontal") {
-                Some("center") => HorizontalAlignment::Center,
-                Some("centerContinuous") => HorizontalAlignment::CenterContinuous,
-                Some("distributed") => HorizontalAlignment::Distributed,
-                Some("fill") => HorizontalAlignment::Fill,
-                Some("general") => HorizontalAlignment::General,
-                Some("justify") => HorizontalAlignment::Justify,
-                Some("left") => HorizontalAlignment::Left,
-                Some("right") => HorizontalAlignment::Right,
-                // TODO: Should we fail in this case or set the alignment to default?
-                Some(_) => HorizontalAlignment::default(),
-                None => HorizontalAlignment::default(),
-            };
-
-            let vertical = match alignment_node.attribute("vertical") {
-                Some("bottom") => VerticalAlignment::Bottom,
-                Some("center") => VerticalAlignment::Center,
-                Some("distributed") => VerticalAlignment::Distributed,
-                Some("justify") => VerticalAlignment::Justify,
-                Some("top") => VerticalAlignment::Top,
-                // TODO: Should we fail in this case or set the alignment to default?
-                Some(_) => VerticalAlignment::default(),
-                None => VerticalAlignment::default(),
-            };
-
-            Some(Alignment {
-                horizontal,
-                vertical,
-                wrap_text,
-            })
-        } else {
-            None
-        };
+        let alignment = load_alignment(xfs);
+        let protection = load_protection(xfs);
 
         cell_xfs.push(CellXfs {
             xf_id,
@@ -358,11 +484,18 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
             apply_fill,
             quote_prefix,
             alignment,
+            protection,
         });
     }
 
+    let mut dxfs = Vec::new();
+    for dxfs_node in style_sheet.children().filter(|n| n.has_tag_name("dxfs")) {
+        for dxf in dxfs_node.children().filter(|n| n.has_tag_name("dxf")) {
+            dxfs.push(load_dxf(dxf, theme)?);
+        }
+    }
+
     // TODO
-    // let mut dxfs = Vec::new();
     // let mut tableStyles = Vec::new();
     // let mut colors = Vec::new();
     // <colors>
@@ -374,13 +507,16 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
     //     </mruColors>
     // </colors>
 
-    Ok(Styles {
-        num_fmts,
-        fonts,
-        fills,
-        borders,
-        cell_style_xfs,
-        cell_xfs,
-        cell_styles,
-    })
+    Ok((
+        Styles {
+            num_fmts,
+            fonts,
+            fills,
+            borders,
+            cell_style_xfs,
+            cell_xfs,
+            cell_styles,
+        },
+        dxfs,
+    ))
 }
@@ -1,6 +1,9 @@
 use std::io::Read;
 
-use ironcalc_base::types::{Table, TableColumn, TableStyleInfo};
+use ironcalc_base::types::{
+    ColumnFilter, CustomFilter, DateGroup, DateTimeGrouping, Dxf, FilterColumn, FilterOperator,
+    Table, TableColumn, TableStyleInfo, TotalsRowFunction,
+};
 use roxmltree::Node;
 
 use crate::error::XlsxError;
@@ -20,11 +23,137 @@ use super::util::{get_bool, get_bool_false};
 //   <tableStyleInfo name="TableStyle5"/>
 // </table>
 
+fn load_filter_operator(operator: Option<&str>) -> Option<FilterOperator> {
+    match operator.unwrap_or("equal") {
+        "equal" => Some(FilterOperator::Equal),
+        "notEqual" => Some(FilterOperator::NotEqual),
+        "greaterThan" => Some(FilterOperator::GreaterThan),
+        "greaterThanOrEqual" => Some(FilterOperator::GreaterThanOrEqual),
+        "lessThan" => Some(FilterOperator::LessThan),
+        "lessThanOrEqual" => Some(FilterOperator::LessThanOrEqual),
+        _ => None,
+    }
+}
+
+// <dateGroupItem year="2024" month="3" dateTimeGrouping="month"/>
+fn load_date_group(node: Node) -> Option<DateGroup> {
+    let grouping = match node.attribute("dateTimeGrouping")? {
+        "year" => DateTimeGrouping::Year,
+        "month" => DateTimeGrouping::Month,
+        "day" => DateTimeGrouping::Day,
+        "hour" => DateTimeGrouping::Hour,
+        "minute" => DateTimeGrouping::Minute,
+        "second" => DateTimeGrouping::Second,
+        _ => return None,
+    };
+    let number = |name: &str| node.attribute(name).and_then(|s| s.parse::<u32>().ok());
+    Some(DateGroup {
+        grouping,
+        year: node.attribute("year")?.parse::<i32>().ok()?,
+        month: number("month").unwrap_or(0),
+        day: number("day").unwrap_or(0),
+        hour: number("hour").unwrap_or(0),
+        minute: number("minute").unwrap_or(0),
+        second: number("second").unwrap_or(0),
+    })
+}
+
+/// Reads the filters of the columns of an autofilter of a worksheet or a table.
+/// Dynamic and icon filters are not supported and ignored.
+/// The colors of color filters are in the differential formats `dxfs`.
+///
+/// ECMA-376-1:2016 section 18.3.1.2
+pub(crate) fn load_filter_columns(auto_filter: Node, dxfs: &[Dxf]) -> Vec<FilterColumn> {
+    let mut columns = Vec::new();
+    for filter_column in auto_filter
+        .children()
+        .filter(|n| n.has_tag_name("filterColumn"))
+    {
+        let column_id = match filter_column
+            .attribute("colId")
+            .and_then(|s| s.parse::<u32>().ok())
+        {
+            Some(column_id) => column_id,
+            None => continue,
+        };
+        let node = match filter_column.children().find(|n| n.is_element()) {
+            Some(node) => node,
+            None => continue,
+        };
+        let filter = match node.tag_name().name() {
+            "filters" => ColumnFilter::Values {
+                values: node
+                    .children()
+                    .filter(|n| n.has_tag_name("filter"))
+                    .filter_map(|n| n.attribute("val").map(|s| s.to_string()))
+                    .collect(),
+                date_groups: node
+                    .children()
+                    .filter(|n| n.has_tag_name("dateGroupItem"))
+                    .filter_map(load_date_group)
+                    .collect(),
+                blank: get_bool_false(node, "blank"),
+            },
+            "customFilters" => {
+                let mut conditions = Vec::new();
+                for custom_filter in node.children().filter(|n| n.has_tag_name("customFilter")) {
+                    if let Some(operator) =
+                        load_filter_operator(custom_filter.attribute("operator"))
+                    {
+                        conditions.push(CustomFilter {
+                            operator,
+                            value: custom_filter.attribute("val").unwrap_or("").to_string(),
+                        });
+                    }
+                }
+                if conditions.is_empty() {
+                    continue;
+                }
+                ColumnFilter::Custom {
+                    conditions,
+                    and: get_bool_false(node, "and"),
+                }
+            }
+            "top10" => {
+                let rank = node
+                    .attribute("val")
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .unwrap_or(10.0);
+                ColumnFilter::Top10 {
+                    rank: rank.max(1.0) as u32,
+                    percent: get_bool_false(node, "percent"),
+                    bottom: !get_bool(node, "top"),
+                }
+            }
+            "colorFilter" => {
+                let cell_color = get_bool(node, "cellColor");
+                let dxf = node
+                    .attribute("dxfId")
+                    .and_then(|s| s.parse::<usize>().ok())
+                    .and_then(|index| dxfs.get(index));
+                let color = match dxf {
+                    Some(dxf) if cell_color => dxf.fill_color.clone(),
+                    Some(dxf) => dxf.font.as_ref().and_then(|font| font.color.clone()),
+                    None => None,
+                };
+                match color {
+                    Some(color) => ColumnFilter::Color { color, cell_color },
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+        columns.push(FilterColumn { column_id, filter });
+    }
+    columns
+}
+
 /// Reads a table in an Excel workbook
 pub(crate) fn load_table<R: Read + std::io::Seek>(
     archive: &mut zip::read::ZipArchive<R>,
     path: &str,
     sheet_name: &str,
+    dxfs: &[Dxf],
 ) -> Result<Table, XlsxError> {
     let mut file = archive.by_name(path)?;
     let mut text = String::new();
@@ -105,18 +234,12 @@ pub(crate) fn load_table<R: Read + std::io::Seek>(
 
     // TODO: Conformant implementations should panic if header_row_dxf_id or data_dxf_id are out of bounds.
 
-    // Note that filters are non dynamic
-    // The only thing important for us is whether or not it has filters
-    let auto_filter = table
-        .descendants()
-        .filter(|n| n.has_tag_name("autoFilter"))
-        .collect::<Vec<Node>>();
-
-    let has_filters = if let Some(filter) = auto_filter.first() {
-        filter.children().count() > 0
-    } else {
-        false
+    // Note that filters are non dynamic, the rows are already hidden in the worksheet
+    let filter_columns = match table.children().find(|n| n.has_tag_name("autoFilter")) {
+        Some(auto_filter) => load_filter_columns(auto_filter, dxfs),
+        None => Vec::new(),
     };
+    let has_filters = !filter_columns.is_empty();
 
     // tableColumn
     let table_column = table
@@ -163,14 +286,39 @@ pub(crate) fn load_table<R: Read + std::io::Seek>(
         // Missing in Calc: styles can also be defined via a name:
         // headerRowCellStyle, dataCellStyle, totalsRowCellStyle
 
+        let totals_row_function = match table_column.attribute("totalsRowFunction") {
+            Some("average") => Some(TotalsRowFunction::Average),
+            Some("count") => Some(TotalsRowFunction::Count),
+            Some("countNums") => Some(TotalsRowFunction::CountNums),
+            Some("custom") => Some(TotalsRowFunction::Custom),
+            Some("max") => Some(TotalsRowFunction::Max),
+            Some("min") => Some(TotalsRowFunction::Min),
+            Some("stdDev") => Some(TotalsRowFunction::StdDev),
+            Some("sum") => Some(TotalsRowFunction::Sum),
+            Some("var") => Some(TotalsRowFunction::Var),
+            _ => None,
+        };
+        // <calculatedColumnFormula>Table1[[#This Row],[Price]]*2</calculatedColumnFormula>
+        let child_text = |name: &str| {
+            table_column
+                .children()
+                .find(|n| n.has_tag_name(name))
+                .and_then(|n| n.text())
+                .map(|s| s.to_string())
+        };
+
         columns.push(TableColumn {
             id,
             name: column_name.to_string(),
-            totals_row_label: None,
+            totals_row_label: table_column
+                .attribute("totalsRowLabel")
+                .map(|s| s.to_string()),
             header_row_dxf_id,
             data_dxf_id,
-            totals_row_function: None,
+            totals_row_function,
             totals_row_dxf_id,
+            totals_row_formula: child_text("totalsRowFormula"),
+            calculated_column_formula: child_text("calculatedColumnFormula"),
         });
     }
 
@@ -210,6 +358,7 @@ pub(crate) fn load_table<R: Read + std::io::Seek>(
         columns,
         style_info,
         has_filters,
+        filter_columns,
         sheet_name: sheet_name.to_string(),
     })
 }
@@ -0,0 +1,135 @@
+use std::{collections::HashMap, io::Read};
+
+use ironcalc_base::types::Theme;
+use roxmltree::Node;
+
+use crate::error::XlsxError;
+
+use super::worksheets::Relationship;
+
+// The elements of the color scheme in the order the styles refer to them (18.8.3 theme)
+const COLOR_NAMES: [&str; 12] = [
+    "lt1", "dk1", "lt2", "dk2", "accent1", "accent2", "accent3", "accent4", "accent5", "accent6",
+    "hlink", "folHlink",
+];
+
+// <a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1>
+// <a:accent1><a:srgbClr val="4472C4"/></a:accent1>
+fn load_scheme_color(node: Node) -> Option<String> {
+    let color = node.children().find(|n| n.is_element())?;
+    let value = match color.tag_name().name() {
+        "srgbClr" => color.attribute("val")?,
+        "sysClr" => color.attribute("lastClr")?,
+        _ => return None,
+    };
+    Some(format!("#{}", value.to_uppercase()))
+}
+
+// <a:majorFont><a:latin typeface="Calibri Light" panose="020F0302020204030204"/>...
+fn load_font_face(font_scheme: Node, name: &str) -> Option<String> {
+    font_scheme
+        .children()
+        .find(|n| n.has_tag_name(name))?
+        .children()
+        .find(|n| n.has_tag_name("latin"))?
+        .attribute("typeface")
+        .map(|typeface| typeface.to_string())
+}
+
+fn load_theme_from_string(text: &str) -> Result<Theme, XlsxError> {
+    let doc = roxmltree::Document::parse(text)?;
+    let mut theme = Theme::default();
+    let root = doc.root_element();
+    if let Some(name) = root.attribute("name") {
+        theme.name = name.to_string();
+    }
+    if let Some(color_scheme) = root.descendants().find(|n| n.has_tag_name("clrScheme")) {
+        for (index, name) in COLOR_NAMES.iter().enumerate() {
+            let color = color_scheme
+                .children()
+                .find(|n| n.has_tag_name(*name))
+                .and_then(load_scheme_color);
+            if let Some(color) = color {
+                theme.colors[index] = color;
+            }
+        }
+    }
+    if let Some(font_scheme) = root.descendants().find(|n| n.has_tag_name("fontScheme")) {
+        if let Some(font) = load_font_face(font_scheme, "majorFont") {
+            theme.major_font = font;
+        }
+        if let Some(font) = load_font_face(font_scheme, "minorFont") {
+            theme.minor_font = font;
+        }
+    }
+    Ok(theme)
+}
+
+/// Loads the theme of the workbook, the default theme if there is none
+pub(super) fn load_theme<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    rels: &HashMap<String, Relationship>,
+) -> Result<Theme, XlsxError> {
+    let target = rels
+        .values()
+        .find(|rel| rel.rel_type.ends_with("/theme"))
+        .map(|rel| rel.target.clone());
+    let path = match target {
+        Some(target) => match target.strip_prefix('/') {
+            Some(path) => path.to_string(),
+            None => format!("xl/{target}"),
+        },
+        None => return Ok(Theme::default()),
+    };
+    let mut text = String::new();
+    match archive.by_name(&path) {
+        Ok(mut file) => {
+            file.read_to_string(&mut text)?;
+        }
+        Err(_) => return Ok(Theme::default()),
+    }
+    load_theme_from_string(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_theme() {
+        let xml_string = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Violet">
+  <a:themeElements>
+    <a:clrScheme name="Violet">
+      <a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1>
+      <a:lt1><a:sysClr val="window" lastClr="FFFFFF"/></a:lt1>
+      <a:dk2><a:srgbClr val="373545"/></a:dk2>
+      <a:lt2><a:srgbClr val="dcd8dc"/></a:lt2>
+      <a:accent1><a:srgbClr val="AD84C6"/></a:accent1>
+      <a:accent2><a:srgbClr val="8784C7"/></a:accent2>
+      <a:accent3><a:srgbClr val="5D739A"/></a:accent3>
+      <a:accent4><a:srgbClr val="6997AF"/></a:accent4>
+      <a:accent5><a:srgbClr val="84ACB6"/></a:accent5>
+      <a:accent6><a:srgbClr val="6F8183"/></a:accent6>
+      <a:hlink><a:srgbClr val="69A020"/></a:hlink>
+      <a:folHlink><a:srgbClr val="8C8C8C"/></a:folHlink>
+    </a:clrScheme>
+    <a:fontScheme name="Violet">
+      <a:majorFont><a:latin typeface="Century Gothic"/><a:ea typeface=""/><a:cs typeface=""/></a:majorFont>
+      <a:minorFont><a:latin typeface="Century Gothic"/><a:ea typeface=""/><a:cs typeface=""/></a:minorFont>
+    </a:fontScheme>
+  </a:themeElements>
+</a:theme>"#;
+        let theme = load_theme_from_string(xml_string).unwrap();
+        assert_eq!(theme.name, "Violet");
+        assert_eq!(
+            theme.colors,
+            vec![
+                "#FFFFFF", "#000000", "#DCD8DC", "#373545", "#AD84C6", "#8784C7", "#5D739A",
+                "#6997AF", "#84ACB6", "#6F8183", "#69A020", "#8C8C8C"
+            ]
+        );
+        assert_eq!(theme.major_font, "Century Gothic");
+        assert_eq!(theme.minor_font, "Century Gothic");
+    }
+}
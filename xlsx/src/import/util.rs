@@ -1,4 +1,5 @@
-use colors::{get_indexed_color, get_themed_color};
+use colors::get_indexed_color;
+use ironcalc_base::types::{PasswordHash, Theme, ThemeColor};
 use roxmltree::{ExpandedName, Node};
 
 use crate::error::XlsxError;
@@ -34,7 +35,7 @@ pub(super) fn get_value_or_default(node: &Node, tag_name: &str, default: &str) -
     }
 }
 
-pub(super) fn get_color(node: Node) -> Result<Option<String>, XlsxError> {
+pub(super) fn get_color(node: Node, theme: &Theme) -> Result<Option<String>, XlsxError> {
     // 18.3.1.15 color (Data Bar Color)
     if node.has_attribute("rgb") {
         let mut val = node.attribute("rgb").unwrap().to_string();
@@ -49,12 +50,12 @@ pub(super) fn get_color(node: Node) -> Result<Option<String>, XlsxError> {
         Ok(Some(rgb))
     // Color::Indexed(val)
     } else if node.has_attribute("theme") {
-        let theme = node.attribute("theme").unwrap().parse::<i32>()?;
+        let index = node.attribute("theme").unwrap().parse::<i32>()?;
         let tint = match node.attribute("tint") {
             Some(t) => t.parse::<f64>().unwrap_or(0.0),
             None => 0.0,
         };
-        let rgb = get_themed_color(theme, tint);
+        let rgb = theme.get_color(index, tint);
         Ok(Some(rgb))
     // Color::Theme { theme, tint }
     } else if node.has_attribute("auto") {
@@ -67,6 +68,20 @@ pub(super) fn get_color(node: Node) -> Result<Option<String>, XlsxError> {
     }
 }
 
+/// Returns the color of the theme of a color node, if it is one
+pub(super) fn get_theme_color(node: Node) -> Result<Option<ThemeColor>, XlsxError> {
+    if node.has_attribute("rgb") || node.has_attribute("indexed") {
+        return Ok(None);
+    }
+    match node.attribute("theme") {
+        Some(index) => Ok(Some(ThemeColor {
+            theme: index.parse::<i32>()?,
+            tint: node.attribute("tint").map(|t| t.to_string()),
+        })),
+        None => Ok(None),
+    }
+}
+
 pub(super) fn get_bool(node: Node, s: &str) -> bool {
     // defaults to true
     !matches!(node.attribute(s), Some("0"))
@@ -76,3 +91,27 @@ pub(super) fn get_bool_false(node: Node, s: &str) -> bool {
     // defaults to false
     matches!(node.attribute(s), Some("1"))
 }
+
+/// Reads the hash of the password of a sheet or workbook protection.
+/// `names` are the attributes with the legacy hash, the algorithm, the hash, the salt and the spin count.
+pub(super) fn get_password_hash(node: Node, names: [&str; 5]) -> Option<PasswordHash> {
+    let [password, algorithm_name, hash_value, salt_value, spin_count] = names;
+    match (
+        node.attribute(algorithm_name),
+        node.attribute(hash_value),
+        node.attribute(salt_value),
+    ) {
+        (Some(algorithm_name), Some(hash_value), Some(salt_value)) => Some(PasswordHash::Salted {
+            algorithm_name: algorithm_name.to_string(),
+            hash_value: hash_value.to_string(),
+            salt_value: salt_value.to_string(),
+            spin_count: node
+                .attribute(spin_count)
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+        }),
+        _ => node
+            .attribute(password)
+            .map(|hash| PasswordHash::Legacy(hash.to_string())),
+    }
+}
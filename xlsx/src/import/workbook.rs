@@ -1,12 +1,12 @@
 use std::io::Read;
 
-use ironcalc_base::types::{DefinedName, SheetState};
+use ironcalc_base::types::{CalcMode, DefinedName, SheetState, WorkbookProtection};
 use roxmltree::Node;
 
 use crate::error::XlsxError;
 
 use super::{
-    util::get_attribute,
+    util::{get_attribute, get_password_hash},
     worksheets::{Sheet, WorkbookXML},
 };
 
@@ -71,9 +71,48 @@ pub(super) fn load_workbook<R: Read + std::io::Seek>(
             sheet_id,
         })
     }
+    // <workbookPr date1904="1"/>
+    let date_1904 = doc
+        .descendants()
+        .find(|n| n.has_tag_name("workbookPr"))
+        .map(|node| matches!(node.attribute("date1904"), Some("1") | Some("true")))
+        .unwrap_or(false);
+    // <calcPr calcMode="manual" fullPrecision="0"/>
+    let calc_pr = doc.descendants().find(|n| n.has_tag_name("calcPr"));
+    let precision_as_displayed = calc_pr
+        .map(|node| matches!(node.attribute("fullPrecision"), Some("0") | Some("false")))
+        .unwrap_or(false);
+    // Unknown modes are read as the default (automatic)
+    let calc_mode = match calc_pr.and_then(|node| node.attribute("calcMode")) {
+        Some("autoNoTable") => CalcMode::AutomaticExceptTables,
+        Some("manual") => CalcMode::Manual,
+        _ => CalcMode::Automatic,
+    };
+    // <workbookProtection lockStructure="1" workbookAlgorithmName="SHA-512" ... />
+    let protection = doc
+        .descendants()
+        .find(|n| n.has_tag_name("workbookProtection"))
+        .map(|node| WorkbookProtection {
+            password_hash: get_password_hash(
+                node,
+                [
+                    "workbookPassword",
+                    "workbookAlgorithmName",
+                    "workbookHashValue",
+                    "workbookSaltValue",
+                    "workbookSpinCount",
+                ],
+            ),
+            lock_structure: matches!(node.attribute("lockStructure"), Some("1") | Some("true")),
+            lock_windows: matches!(node.attribute("lockWindows"), Some("1") | Some("true")),
+        });
     // read the relationships file
     Ok(WorkbookXML {
         worksheets: sheets,
         defined_names,
+        date_1904,
+        precision_as_displayed,
+        calc_mode,
+        protection,
     })
 }
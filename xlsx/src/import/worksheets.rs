@@ -1,5 +1,6 @@
 use std::{collections::HashMap, io::Read, num::ParseIntError};
 
+use chrono::NaiveDateTime;
 use ironcalc_base::{
     expressions::{
         parser::{stringify::to_rc_format, Parser},
@@ -8,8 +9,10 @@ use ironcalc_base::{
         utils::{column_to_number, parse_reference_a1},
     },
     types::{
-        Cell, Col, Comment, DefinedName, Row, SheetData, SheetState, Table, Worksheet,
-        WorksheetView,
+        AutoFilter, CalcMode, Cell, CellIsOperator, Cfvo, CfvoKind, Col, Comment, CommentReply,
+        ConditionalFormat, ConditionalFormatRule, DataValidation, DataValidationErrorStyle,
+        DataValidationKind, DefinedName, Dxf, Hyperlink, PivotTable, Row, SheetData,
+        SheetProtection, SheetState, Table, Theme, WorkbookProtection, Worksheet, WorksheetView,
     },
 };
 use roxmltree::Node;
@@ -18,8 +21,9 @@ use thiserror::Error;
 use crate::error::XlsxError;
 
 use super::{
-    tables::load_table,
-    util::{get_attribute, get_color, get_number},
+    pivot_tables::load_pivot_table,
+    tables::{load_filter_columns, load_table},
+    util::{get_attribute, get_bool, get_bool_false, get_color, get_number, get_password_hash},
 };
 
 pub(crate) struct Sheet {
@@ -32,6 +36,10 @@ pub(crate) struct Sheet {
 pub(crate) struct WorkbookXML {
     pub(crate) worksheets: Vec<Sheet>,
     pub(crate) defined_names: Vec<DefinedName>,
+    pub(crate) date_1904: bool,
+    pub(crate) precision_as_displayed: bool,
+    pub(crate) calc_mode: CalcMode,
+    pub(crate) protection: Option<WorkbookProtection>,
 }
 
 pub(crate) struct Relationship {
@@ -134,12 +142,20 @@ fn load_columns(ws: Node) -> Result<Vec<Col>, XlsxError> {
             let style = col
                 .attribute("style")
                 .map(|s| s.parse::<i32>().unwrap_or(0));
+            let hidden = matches!(col.attribute("hidden"), Some("1"));
+            let outline_level = col
+                .attribute("outlineLevel")
+                .map_or(0, |s| s.parse::<u8>().unwrap_or(0));
+            let collapsed = matches!(col.attribute("collapsed"), Some("1"));
             cols.push(Col {
                 min,
                 max,
                 width,
                 custom_width,
                 style,
+                hidden,
+                outline_level,
+                collapsed,
             })
         }
     }
@@ -165,7 +181,324 @@ fn load_merge_cells(ws: Node) -> Result<Vec<String>, XlsxError> {
     Ok(merge_cells)
 }
 
-fn load_sheet_color(ws: Node) -> Result<Option<String>, XlsxError> {
+fn load_sheet_protection(ws: Node) -> Option<SheetProtection> {
+    // 18.3.1.85 sheetProtection (Sheet Protection Options)
+    // <sheetProtection algorithmName="SHA-512" hashValue="..." saltValue="..." spinCount="100000"
+    //      sheet="1" objects="1" scenarios="1" formatCells="0"/>
+    // Note that the attributes tell what is *not* allowed
+    let node = ws.children().find(|n| n.has_tag_name("sheetProtection"))?;
+    if !get_bool_false(node, "sheet") {
+        return None;
+    }
+    Some(SheetProtection {
+        password_hash: get_password_hash(
+            node,
+            [
+                "password",
+                "algorithmName",
+                "hashValue",
+                "saltValue",
+                "spinCount",
+            ],
+        ),
+        allow_select_locked_cells: !get_bool_false(node, "selectLockedCells"),
+        allow_select_unlocked_cells: !get_bool_false(node, "selectUnlockedCells"),
+        allow_format_cells: !get_bool(node, "formatCells"),
+        allow_format_columns: !get_bool(node, "formatColumns"),
+        allow_format_rows: !get_bool(node, "formatRows"),
+        allow_insert_columns: !get_bool(node, "insertColumns"),
+        allow_insert_rows: !get_bool(node, "insertRows"),
+        allow_insert_hyperlinks: !get_bool(node, "insertHyperlinks"),
+        allow_delete_columns: !get_bool(node, "deleteColumns"),
+        allow_delete_rows: !get_bool(node, "deleteRows"),
+        allow_sort: !get_bool(node, "sort"),
+        allow_auto_filter: !get_bool(node, "autoFilter"),
+        allow_pivot_tables: !get_bool(node, "pivotTables"),
+        allow_edit_objects: !get_bool_false(node, "objects"),
+        allow_edit_scenarios: !get_bool_false(node, "scenarios"),
+    })
+}
+
+fn load_cfvos(node: Node) -> Result<Vec<Cfvo>, XlsxError> {
+    // 18.3.1.11 cfvo (Conditional Format Value Object)
+    let mut cfvos = Vec::new();
+    for cfvo in node.children().filter(|n| n.has_tag_name("cfvo")) {
+        let kind = match get_attribute(&cfvo, "type")? {
+            "min" => CfvoKind::Min,
+            "max" => CfvoKind::Max,
+            "num" => CfvoKind::Number,
+            "percent" => CfvoKind::Percent,
+            "percentile" => CfvoKind::Percentile,
+            "formula" => CfvoKind::Formula,
+            kind => {
+                return Err(XlsxError::Xml(format!("Invalid cfvo type: '{kind}'")));
+            }
+        };
+        let value = cfvo.attribute("val").map(|s| s.to_string());
+        cfvos.push(Cfvo { kind, value });
+    }
+    Ok(cfvos)
+}
+
+fn load_colors(node: Node, theme: &Theme) -> Result<Vec<String>, XlsxError> {
+    let mut colors = Vec::new();
+    for color in node.children().filter(|n| n.has_tag_name("color")) {
+        colors.push(get_color(color, theme)?.unwrap_or_else(|| "#000000".to_string()));
+    }
+    Ok(colors)
+}
+
+fn load_conditional_formats(
+    ws: Node,
+    dxfs: &[Dxf],
+    theme: &Theme,
+) -> Result<Vec<ConditionalFormat>, XlsxError> {
+    // 18.3.1.18 conditionalFormatting
+    // <conditionalFormatting sqref="B1:B9">
+    //     <cfRule type="colorScale" priority="1">
+    //         <colorScale>
+    //             <cfvo type="min"/>
+    //             <cfvo type="max"/>
+    //             <color rgb="FFF8696B"/>
+    //             <color rgb="FFFCFCFF"/>
+    //         </colorScale>
+    //     </cfRule>
+    // </conditionalFormatting>
+    let mut rules = Vec::new();
+    for conditional_formatting in ws
+        .children()
+        .filter(|n| n.has_tag_name("conditionalFormatting"))
+    {
+        let sqref = get_attribute(&conditional_formatting, "sqref")?.to_string();
+        for cf_rule in conditional_formatting
+            .children()
+            .filter(|n| n.has_tag_name("cfRule"))
+        {
+            let priority = get_number(cf_rule, "priority");
+            let formulas: Vec<String> = cf_rule
+                .children()
+                .filter(|n| n.has_tag_name("formula"))
+                .map(|n| n.text().unwrap_or("").to_string())
+                .collect();
+            let child = |name: &str| cf_rule.children().find(|n| n.has_tag_name(name));
+            let rule = match get_attribute(&cf_rule, "type")? {
+                "cellIs" => {
+                    let operator = match cf_rule.attribute("operator") {
+                        Some("between") => CellIsOperator::Between,
+                        Some("notBetween") => CellIsOperator::NotBetween,
+                        Some("equal") => CellIsOperator::Equal,
+                        Some("notEqual") => CellIsOperator::NotEqual,
+                        Some("greaterThan") => CellIsOperator::GreaterThan,
+                        Some("lessThan") => CellIsOperator::LessThan,
+                        Some("greaterThanOrEqual") => CellIsOperator::GreaterThanOrEqual,
+                        Some("lessThanOrEqual") => CellIsOperator::LessThanOrEqual,
+                        operator => {
+                            return Err(XlsxError::Xml(format!(
+                                "Invalid conditional format operator: {operator:?}"
+                            )));
+                        }
+                    };
+                    ConditionalFormatRule::CellIs { operator, formulas }
+                }
+                "top10" => ConditionalFormatRule::Top10 {
+                    rank: get_number(cf_rule, "rank") as u32,
+                    percent: get_bool_false(cf_rule, "percent"),
+                    bottom: get_bool_false(cf_rule, "bottom"),
+                },
+                "aboveAverage" => ConditionalFormatRule::AboveAverage {
+                    above_average: get_bool(cf_rule, "aboveAverage"),
+                    equal_average: get_bool_false(cf_rule, "equalAverage"),
+                    std_dev: get_number(cf_rule, "stdDev") as u32,
+                },
+                "duplicateValues" => ConditionalFormatRule::DuplicateValues,
+                "uniqueValues" => ConditionalFormatRule::UniqueValues,
+                "colorScale" => match child("colorScale") {
+                    Some(color_scale) => ConditionalFormatRule::ColorScale {
+                        cfvos: load_cfvos(color_scale)?,
+                        colors: load_colors(color_scale, theme)?,
+                    },
+                    None => continue,
+                },
+                "dataBar" => match child("dataBar") {
+                    Some(data_bar) => {
+                        let mut cfvos = load_cfvos(data_bar)?;
+                        if cfvos.len() != 2 {
+                            return Err(XlsxError::Xml("Invalid data bar".to_string()));
+                        }
+                        let max = cfvos.remove(1);
+                        let min = cfvos.remove(0);
+                        let color = load_colors(data_bar, theme)?
+                            .pop()
+                            .unwrap_or_else(|| "#638EC6".to_string());
+                        ConditionalFormatRule::DataBar { min, max, color }
+                    }
+                    None => continue,
+                },
+                "iconSet" => match child("iconSet") {
+                    Some(icon_set) => ConditionalFormatRule::IconSet {
+                        icon_set: icon_set
+                            .attribute("iconSet")
+                            .unwrap_or("3TrafficLights1")
+                            .to_string(),
+                        cfvos: load_cfvos(icon_set)?,
+                        reverse: get_bool_false(icon_set, "reverse"),
+                        show_value: get_bool(icon_set, "showValue"),
+                    },
+                    None => continue,
+                },
+                // Rules like containsText, beginsWith or timePeriod are written together with
+                // an equivalent formula, we keep the formula
+                _ => match formulas.first() {
+                    Some(formula) => ConditionalFormatRule::Expression {
+                        formula: formula.to_string(),
+                    },
+                    None => continue,
+                },
+            };
+            let style = match cf_rule.attribute("dxfId") {
+                Some(dxf_id) => dxf_id
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| dxfs.get(index).cloned()),
+                None => None,
+            };
+            rules.push((
+                priority,
+                ConditionalFormat {
+                    sqref: sqref.clone(),
+                    rule,
+                    style,
+                    stop_if_true: get_bool_false(cf_rule, "stopIfTrue"),
+                },
+            ));
+        }
+    }
+    rules.sort_by_key(|(priority, _)| *priority);
+    Ok(rules.into_iter().map(|(_, rule)| rule).collect())
+}
+
+fn load_data_validations(ws: Node) -> Result<Vec<DataValidation>, XlsxError> {
+    // 18.3.1.33 dataValidations
+    // <dataValidations count="1">
+    //     <dataValidation type="list" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A1:A10">
+    //         <formula1>"Yes,No"</formula1>
+    //     </dataValidation>
+    // </dataValidations>
+    let mut data_validations = Vec::new();
+    for data_validations_node in ws.children().filter(|n| n.has_tag_name("dataValidations")) {
+        for data_validation in data_validations_node
+            .children()
+            .filter(|n| n.has_tag_name("dataValidation"))
+        {
+            let sqref = get_attribute(&data_validation, "sqref")?.to_string();
+            let kind = match data_validation.attribute("type") {
+                None | Some("none") => DataValidationKind::Any,
+                Some("whole") => DataValidationKind::Whole,
+                Some("decimal") => DataValidationKind::Decimal,
+                Some("list") => DataValidationKind::List,
+                Some("date") => DataValidationKind::Date,
+                Some("time") => DataValidationKind::Time,
+                Some("textLength") => DataValidationKind::TextLength,
+                Some("custom") => DataValidationKind::Custom,
+                Some(kind) => {
+                    return Err(XlsxError::Xml(format!(
+                        "Invalid data validation type: '{kind}'"
+                    )));
+                }
+            };
+            let operator = match data_validation.attribute("operator") {
+                None | Some("between") => CellIsOperator::Between,
+                Some("notBetween") => CellIsOperator::NotBetween,
+                Some("equal") => CellIsOperator::Equal,
+                Some("notEqual") => CellIsOperator::NotEqual,
+                Some("greaterThan") => CellIsOperator::GreaterThan,
+                Some("lessThan") => CellIsOperator::LessThan,
+                Some("greaterThanOrEqual") => CellIsOperator::GreaterThanOrEqual,
+                Some("lessThanOrEqual") => CellIsOperator::LessThanOrEqual,
+                Some(operator) => {
+                    return Err(XlsxError::Xml(format!(
+                        "Invalid data validation operator: '{operator}'"
+                    )));
+                }
+            };
+            let error_style = match data_validation.attribute("errorStyle") {
+                None | Some("stop") => DataValidationErrorStyle::Stop,
+                Some("warning") => DataValidationErrorStyle::Warning,
+                Some("information") => DataValidationErrorStyle::Information,
+                Some(error_style) => {
+                    return Err(XlsxError::Xml(format!(
+                        "Invalid data validation error style: '{error_style}'"
+                    )));
+                }
+            };
+            let formulas = data_validation
+                .children()
+                .filter(|n| n.has_tag_name("formula1") || n.has_tag_name("formula2"))
+                .filter_map(|n| n.text().map(|s| s.to_string()))
+                .collect();
+            let get_text = |name: &str| data_validation.attribute(name).map(|s| s.to_string());
+            data_validations.push(DataValidation {
+                sqref,
+                kind,
+                operator,
+                formulas,
+                allow_blank: get_bool_false(data_validation, "allowBlank"),
+                // NB: showDropDown="1" hides the dropdown
+                show_dropdown: !get_bool_false(data_validation, "showDropDown"),
+                show_input_message: get_bool_false(data_validation, "showInputMessage"),
+                input_title: get_text("promptTitle"),
+                input_message: get_text("prompt"),
+                show_error_message: get_bool_false(data_validation, "showErrorMessage"),
+                error_style,
+                error_title: get_text("errorTitle"),
+                error_message: get_text("error"),
+            });
+        }
+    }
+    Ok(data_validations)
+}
+
+fn load_hyperlinks(
+    ws: Node,
+    hyperlink_targets: &HashMap<String, String>,
+) -> Result<Vec<Hyperlink>, XlsxError> {
+    // 18.3.1.47 hyperlink
+    // <hyperlinks>
+    //     <hyperlink ref="A1" r:id="rId1" tooltip="Our web page"/>
+    //     <hyperlink ref="A2" location="Sheet2!B3" display="Sheet2!B3"/>
+    // </hyperlinks>
+    // The target of the external links is in the relationships of the sheet
+    let mut hyperlinks = Vec::new();
+    for hyperlinks_node in ws.children().filter(|n| n.has_tag_name("hyperlinks")) {
+        for hyperlink in hyperlinks_node
+            .children()
+            .filter(|n| n.has_tag_name("hyperlink"))
+        {
+            // If the hyperlink covers a range we attach it to the upper-left cell
+            let reference = get_attribute(&hyperlink, "ref")?;
+            let cell_ref = reference.split(':').next().unwrap_or(reference).to_string();
+            let url = hyperlink
+                .attribute((
+                    "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
+                    "id",
+                ))
+                .and_then(|id| hyperlink_targets.get(id).cloned());
+            let location = hyperlink.attribute("location").map(|s| s.to_string());
+            if url.is_none() && location.is_none() {
+                continue;
+            }
+            hyperlinks.push(Hyperlink {
+                cell_ref,
+                url,
+                location,
+                tooltip: hyperlink.attribute("tooltip").map(|s| s.to_string()),
+            });
+        }
+    }
+    Ok(hyperlinks)
+}
+
+fn load_sheet_color(ws: Node, theme: &Theme) -> Result<Option<String>, XlsxError> {
     // <sheetPr>
     //     <tabColor theme="5" tint="-0.249977111117893"/>
     // </sheetPr>
@@ -180,7 +513,7 @@ fn load_sheet_color(ws: Node) -> Result<Option<String>, XlsxError> {
             .filter(|n| n.has_tag_name("tabColor"))
             .collect::<Vec<Node>>();
         if tabs.len() == 1 {
-            color = get_color(tabs[0])?;
+            color = get_color(tabs[0], theme)?;
         }
     }
     Ok(color)
@@ -203,6 +536,13 @@ fn load_comments<R: Read + std::io::Seek>(
         .children()
         .filter(|n| n.has_tag_name("commentList"))
         .collect::<Vec<Node>>();
+    // <authors><author>Jane Doe</author></authors>
+    let authors = ws
+        .children()
+        .filter(|n| n.has_tag_name("authors"))
+        .flat_map(|n| n.children().filter(|n| n.has_tag_name("author")))
+        .map(|n| n.text().unwrap_or("").to_string())
+        .collect::<Vec<String>>();
     if comment_list.len() == 1 {
         for comment in comment_list[0].children() {
             let text = comment
@@ -212,13 +552,20 @@ fn load_comments<R: Read + std::io::Seek>(
                 .collect::<Vec<String>>()
                 .join("");
             let cell_ref = get_attribute(&comment, "ref")?.to_string();
-            // TODO: Read author_name from the list of authors
-            let author_name = "".to_string();
+            let author_name = comment
+                .attribute("authorId")
+                .and_then(|id| id.parse::<usize>().ok())
+                .and_then(|id| authors.get(id))
+                .cloned()
+                .unwrap_or_default();
             comments.push(Comment {
                 text,
                 author_name,
                 author_id: None,
                 cell_ref,
+                timestamp: None,
+                resolved: false,
+                replies: vec![],
             });
         }
     }
@@ -226,6 +573,111 @@ fn load_comments<R: Read + std::io::Seek>(
     Ok(comments)
 }
 
+/// Loads the display names of the people that wrote threaded comments, by their id
+fn load_persons<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    rels: &HashMap<String, Relationship>,
+) -> Result<HashMap<String, String>, XlsxError> {
+    // <personList>
+    //     <person displayName="Jane Doe" id="{2D1E3B27-...}" userId="jane@example.com" providerId="None"/>
+    // </personList>
+    let mut persons = HashMap::new();
+    for rel in rels.values() {
+        if !rel.rel_type.ends_with("/person") {
+            continue;
+        }
+        let path = match rel.target.strip_prefix('/') {
+            Some(p) => p.to_string(),
+            None => format!("xl/{}", rel.target),
+        };
+        let mut file = archive.by_name(&path)?;
+        let mut text = String::new();
+        file.read_to_string(&mut text)?;
+        let doc = roxmltree::Document::parse(&text)?;
+        for person in doc.descendants().filter(|n| n.has_tag_name("person")) {
+            persons.insert(
+                get_attribute(&person, "id")?.to_string(),
+                person.attribute("displayName").unwrap_or("").to_string(),
+            );
+        }
+    }
+    Ok(persons)
+}
+
+/// Threaded comments use timestamps like "2023-05-01T10:00:00.00"
+fn parse_comment_timestamp(timestamp: &str) -> Option<i64> {
+    NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|t| t.and_utc().timestamp_millis())
+}
+
+fn load_threaded_comments<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    path: &str,
+    persons: &HashMap<String, String>,
+) -> Result<Vec<Comment>, XlsxError> {
+    // <ThreadedComments>
+    //     <threadedComment ref="A1" dT="2023-05-01T10:00:00.00" personId="{..}" id="{1}" done="1">
+    //         <text>Can you check this?</text>
+    //     </threadedComment>
+    //     <threadedComment ref="A1" dT="2023-05-02T09:30:00.00" personId="{..}" id="{2}" parentId="{1}">
+    //         <text>Done</text>
+    //     </threadedComment>
+    // </ThreadedComments>
+    let mut file = archive.by_name(path)?;
+    let mut text = String::new();
+    file.read_to_string(&mut text)?;
+    let doc = roxmltree::Document::parse(&text)?;
+    let mut comments: Vec<Comment> = Vec::new();
+    // id of the comment -> index in `comments`
+    let mut threads: HashMap<String, usize> = HashMap::new();
+    for node in doc
+        .descendants()
+        .filter(|n| n.has_tag_name("threadedComment"))
+    {
+        let text = node
+            .children()
+            .filter(|n| n.has_tag_name("text"))
+            .filter_map(|n| n.text())
+            .collect::<Vec<&str>>()
+            .join("");
+        let author_id = node.attribute("personId").map(|s| s.to_string());
+        let author_name = author_id
+            .as_ref()
+            .and_then(|id| persons.get(id))
+            .cloned()
+            .unwrap_or_default();
+        let timestamp = node.attribute("dT").and_then(parse_comment_timestamp);
+        match node.attribute("parentId") {
+            Some(parent_id) => {
+                if let Some(&index) = threads.get(parent_id) {
+                    comments[index].replies.push(CommentReply {
+                        text,
+                        author_name,
+                        author_id,
+                        timestamp,
+                    });
+                }
+            }
+            None => {
+                if let Some(id) = node.attribute("id") {
+                    threads.insert(id.to_string(), comments.len());
+                }
+                comments.push(Comment {
+                    text,
+                    author_name,
+                    author_id,
+                    cell_ref: get_attribute(&node, "ref")?.to_string(),
+                    timestamp,
+                    resolved: get_bool_false(node, "done"),
+                    replies: vec![],
+                });
+            }
+        }
+    }
+    Ok(comments)
+}
+
 #[derive(Error, Debug, PartialEq)]
 enum ParseReferenceError {
     #[error("RowError: {0}")]
@@ -485,14 +937,27 @@ fn get_cell_from_excel(
     }
 }
 
+/// The parts of a worksheet that are stored in its relationships
+struct SheetRels {
+    comments: Vec<Comment>,
+    /// Relationship id -> target of the external hyperlinks
+    hyperlinks: HashMap<String, String>,
+    pivot_tables: Vec<PivotTable>,
+}
+
 fn load_sheet_rels<R: Read + std::io::Seek>(
     archive: &mut zip::read::ZipArchive<R>,
     path: &str,
     tables: &mut HashMap<String, Table>,
     sheet_name: &str,
-) -> Result<Vec<Comment>, XlsxError> {
+    persons: &HashMap<String, String>,
+    dxfs: &[Dxf],
+) -> Result<SheetRels, XlsxError> {
     // ...xl/worksheets/sheet6.xml -> xl/worksheets/_rels/sheet6.xml.rels
     let mut comments = Vec::new();
+    let mut hyperlinks = HashMap::new();
+    let mut threaded_comments = Vec::new();
+    let mut pivot_tables = Vec::new();
     let v: Vec<&str> = path.split("/worksheets/").collect();
     let mut path = v[0].to_string();
     path.push_str("/worksheets/_rels/");
@@ -500,7 +965,11 @@ fn load_sheet_rels<R: Read + std::io::Seek>(
     path.push_str(".rels");
     let file = archive.by_name(&path);
     if file.is_err() {
-        return Ok(comments);
+        return Ok(SheetRels {
+            comments,
+            hyperlinks,
+            pivot_tables,
+        });
     }
     let mut text = String::new();
     file.unwrap().read_to_string(&mut text)?;
@@ -519,6 +988,16 @@ fn load_sheet_rels<R: Read + std::io::Seek>(
             // Target="../comments1.xlsx"
             target.replace_range(..2, v[0]);
             comments = load_comments(archive, &target)?;
+        } else if t.ends_with("threadedComment") {
+            let mut target = get_attribute(&rel, "Target")?.to_string();
+            // Target="../threadedComments/threadedComment1.xml"
+            target.replace_range(..2, v[0]);
+            threaded_comments = load_threaded_comments(archive, &target, persons)?;
+        } else if t.ends_with("hyperlink") {
+            // <Relationship Id="rId1" Type="..." Target="https://ironcalc.com" TargetMode="External"/>
+            let id = get_attribute(&rel, "Id")?.to_string();
+            let target = get_attribute(&rel, "Target")?.to_string();
+            hyperlinks.insert(id, target);
         } else if t.ends_with("table") {
             let mut target = get_attribute(&rel, "Target")?.to_string();
 
@@ -530,11 +1009,35 @@ fn load_sheet_rels<R: Read + std::io::Seek>(
                 target
             };
 
-            let table = load_table(archive, &path, sheet_name)?;
+            let table = load_table(archive, &path, sheet_name, dxfs)?;
             tables.insert(table.name.clone(), table);
+        } else if t.ends_with("pivotTable") {
+            let mut target = get_attribute(&rel, "Target")?.to_string();
+            let path = if let Some(p) = target.strip_prefix('/') {
+                p.to_string()
+            } else {
+                // Target="../pivotTables/pivotTable1.xml"
+                target.replace_range(..2, v[0]);
+                target
+            };
+            pivot_tables.push(load_pivot_table(archive, &path)?);
         }
     }
-    Ok(comments)
+    // Threaded comments come with a legacy comment for older versions of Excel that we replace
+    for threaded_comment in threaded_comments {
+        match comments
+            .iter_mut()
+            .find(|c| c.cell_ref == threaded_comment.cell_ref)
+        {
+            Some(comment) => *comment = threaded_comment,
+            None => comments.push(threaded_comment),
+        }
+    }
+    Ok(SheetRels {
+        comments,
+        hyperlinks,
+        pivot_tables,
+    })
 }
 
 struct SheetView {
@@ -670,8 +1173,12 @@ pub(super) struct SheetSettings {
     pub name: String,
     pub state: SheetState,
     pub comments: Vec<Comment>,
+    /// Relationship id -> target of the external hyperlinks
+    pub hyperlink_targets: HashMap<String, String>,
+    pub pivot_tables: Vec<PivotTable>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn load_sheet<R: Read + std::io::Seek>(
     archive: &mut zip::read::ZipArchive<R>,
     path: &str,
@@ -679,6 +1186,8 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
     worksheets: &[String],
     tables: &HashMap<String, Table>,
     shared_strings: &mut Vec<String>,
+    dxfs: &[Dxf],
+    theme: &Theme,
 ) -> Result<(Worksheet, bool), XlsxError> {
     let sheet_name = &settings.name;
     let sheet_id = settings.id;
@@ -699,7 +1208,7 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
     let sheet_view = get_sheet_view(ws);
 
     let cols = load_columns(ws)?;
-    let color = load_sheet_color(ws)?;
+    let color = load_sheet_color(ws, theme)?;
 
     // sheetData
     // <row r="1" spans="1:15" x14ac:dyDescent="0.35">
@@ -751,8 +1260,19 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
         };
         let custom_format = matches!(row.attribute("customFormat"), Some("1"));
         let hidden = matches!(row.attribute("hidden"), Some("1"));
-
-        if custom_height || custom_format || row_style != 0 || has_height_attribute || hidden {
+        let outline_level = row
+            .attribute("outlineLevel")
+            .map_or(0, |s| s.parse::<u8>().unwrap_or(0));
+        let collapsed = matches!(row.attribute("collapsed"), Some("1"));
+
+        if custom_height
+            || custom_format
+            || row_style != 0
+            || has_height_attribute
+            || hidden
+            || outline_level != 0
+            || collapsed
+        {
             rows.push(Row {
                 r: row_index,
                 height,
@@ -760,11 +1280,13 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
                 custom_height,
                 custom_format,
                 hidden,
+                outline_level,
+                collapsed,
             });
         }
 
         // Unused attributes:
-        // * thickBot, thickTop, ph, collapsed, outlineLevel
+        // * thickBot, thickTop, ph
 
         let mut data_row = HashMap::new();
 
@@ -943,17 +1465,25 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
 
     let merge_cells = load_merge_cells(ws)?;
 
-    // Conditional Formatting
-    // <conditionalFormatting sqref="B1:B9">
-    //     <cfRule type="colorScale" priority="1">
-    //         <colorScale>
-    //             <cfvo type="min"/>
-    //             <cfvo type="max"/>
-    //             <color rgb="FFF8696B"/>
-    //             <color rgb="FFFCFCFF"/>
-    //         </colorScale>
-    //     </cfRule>
-    // </conditionalFormatting>
+    let conditional_formats = load_conditional_formats(ws, dxfs, theme)?;
+
+    let data_validations = load_data_validations(ws)?;
+
+    let hyperlinks = load_hyperlinks(ws, &settings.hyperlink_targets)?;
+
+    let protection = load_sheet_protection(ws);
+
+    // <autoFilter ref="A1:D20"><filterColumn colId="0">...</filterColumn></autoFilter>
+    let auto_filter = ws
+        .children()
+        .find(|n| n.has_tag_name("autoFilter"))
+        .and_then(|node| {
+            Some(AutoFilter {
+                reference: node.attribute("ref")?.to_string(),
+                columns: load_filter_columns(node, dxfs),
+            })
+        });
+
     // pageSetup
     // <pageSetup orientation="portrait" r:id="rId1"/>
 
@@ -982,10 +1512,16 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
             color,
             merge_cells,
             comments: settings.comments,
+            hyperlinks,
+            conditional_formats,
+            data_validations,
+            auto_filter,
             frozen_rows: sheet_view.frozen_rows,
             frozen_columns: sheet_view.frozen_columns,
             show_grid_lines: sheet_view.show_grid_lines,
             views,
+            protection,
+            pivot_tables: settings.pivot_tables,
         },
         sheet_view.is_selected,
     ))
@@ -997,9 +1533,12 @@ pub(super) fn load_sheets<R: Read + std::io::Seek>(
     workbook: &WorkbookXML,
     tables: &mut HashMap<String, Table>,
     shared_strings: &mut Vec<String>,
+    dxfs: &[Dxf],
+    theme: &Theme,
 ) -> Result<(Vec<Worksheet>, u32), XlsxError> {
     // load comments and tables
-    let mut comments = HashMap::new();
+    let persons = load_persons(archive, rels)?;
+    let mut sheet_rels = HashMap::new();
     for sheet in &workbook.worksheets {
         let rel = &rels[&sheet.id];
         if rel.rel_type.ends_with("worksheet") {
@@ -1009,9 +1548,9 @@ pub(super) fn load_sheets<R: Read + std::io::Seek>(
             } else {
                 format!("xl/{path}")
             };
-            comments.insert(
+            sheet_rels.insert(
                 &sheet.id,
-                load_sheet_rels(archive, &path, tables, &sheet.name)?,
+                load_sheet_rels(archive, &path, tables, &sheet.name, &persons, dxfs)?,
             );
         }
     }
@@ -1033,14 +1572,29 @@ pub(super) fn load_sheets<R: Read + std::io::Seek>(
             } else {
                 format!("xl/{path}")
             };
+            let SheetRels {
+                comments,
+                hyperlinks,
+                pivot_tables,
+            } = sheet_rels.remove(rel_id).expect("");
             let settings = SheetSettings {
                 name: sheet_name.to_string(),
                 id: sheet.sheet_id,
                 state: state.clone(),
-                comments: comments.get(rel_id).expect("").to_vec(),
+                comments,
+                hyperlink_targets: hyperlinks,
+                pivot_tables,
             };
-            let (s, is_selected) =
-                load_sheet(archive, &path, settings, worksheets, tables, shared_strings)?;
+            let (s, is_selected) = load_sheet(
+                archive,
+                &path,
+                settings,
+                worksheets,
+                tables,
+                shared_strings,
+                dxfs,
+                theme,
+            )?;
             if is_selected {
                 selected_sheet = sheet_index;
             }
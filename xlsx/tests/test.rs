@@ -5,7 +5,14 @@ use uuid::Uuid;
 use ironcalc::compare::{test_file, test_load_and_saving};
 use ironcalc::export::save_to_xlsx;
 use ironcalc::import::{load_from_icalc, load_from_xlsx, load_from_xlsx_bytes};
-use ironcalc_base::types::{HorizontalAlignment, VerticalAlignment};
+use ironcalc_base::types::{
+    AutoFilter, CalcMode, CellIsOperator, Cfvo, CfvoKind, ColumnFilter, Comment, CommentReply,
+    ConditionalFormat, ConditionalFormatRule, CustomFilter, DataValidation,
+    DataValidationErrorStyle, DataValidationKind, DateGroup, DateTimeGrouping, Dxf, DxfFont,
+    FilterColumn, FilterOperator, Font, FontVerticalAlignment, HorizontalAlignment, Hyperlink,
+    PasswordHash, PivotFilter, PivotFunction, PivotSource, PivotTable, PivotValueField, Protection,
+    SheetProtection, TextRun, Theme, VerticalAlignment,
+};
 use ironcalc_base::Model;
 
 // This is a functional test.
@@ -452,3 +459,696 @@ fn test_exporting_merged_cells() {
 
     fs::remove_file(temp_file_name).unwrap();
 }
+
+#[test]
+fn test_date_1904_roundtrip() {
+    let temp_file_name = "temp_file_test_date_1904.xlsx";
+    let mut model = Model::new_empty("date1904", "en", "UTC").unwrap();
+    model.set_date_1904(true);
+    model
+        .set_user_input(0, 1, 1, "=DATE(2023, 3, 20)".to_string())
+        .unwrap();
+    model.evaluate();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert!(model.is_date_1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("20/03/2023".to_string())
+    );
+    fs::remove_file(temp_file_name).unwrap();
+
+    // The default is the 1900 date system
+    let model = load_from_xlsx("tests/example.xlsx", "en", "UTC").unwrap();
+    assert!(!model.is_date_1904());
+}
+
+#[test]
+fn test_precision_as_displayed_roundtrip() {
+    let temp_file_name = "temp_file_test_precision_as_displayed.xlsx";
+    let mut model = Model::new_empty("precision", "en", "UTC").unwrap();
+    model.set_precision_as_displayed(true);
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert!(model.is_precision_as_displayed());
+    fs::remove_file(temp_file_name).unwrap();
+
+    let model = load_from_xlsx("tests/example.xlsx", "en", "UTC").unwrap();
+    assert!(!model.is_precision_as_displayed());
+}
+
+#[test]
+fn test_calc_mode_roundtrip() {
+    let temp_file_name = "temp_file_test_calc_mode.xlsx";
+    let mut model = Model::new_empty("calc_mode", "en", "UTC").unwrap();
+    model.set_calc_mode(CalcMode::Manual);
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert_eq!(model.get_calc_mode(), CalcMode::Manual);
+    fs::remove_file(temp_file_name).unwrap();
+
+    let model = load_from_xlsx("tests/example.xlsx", "en", "UTC").unwrap();
+    assert_eq!(model.get_calc_mode(), CalcMode::Automatic);
+}
+
+#[test]
+fn test_conditional_formatting_roundtrip() {
+    let model = load_from_xlsx("tests/example.xlsx", "en", "UTC").unwrap();
+    // Sheet "Second"
+    let conditional_formats = model.get_conditional_formats(1).unwrap();
+    // Sorted by priority
+    assert_eq!(conditional_formats[0].sqref, "D1:D6");
+    assert!(matches!(
+        conditional_formats[0].rule,
+        ConditionalFormatRule::DataBar { .. }
+    ));
+    assert_eq!(
+        conditional_formats[1],
+        ConditionalFormat {
+            sqref: "B1:B9".to_string(),
+            rule: ConditionalFormatRule::ColorScale {
+                cfvos: vec![
+                    Cfvo {
+                        kind: CfvoKind::Min,
+                        value: None
+                    },
+                    Cfvo {
+                        kind: CfvoKind::Max,
+                        value: None
+                    }
+                ],
+                colors: vec!["#F8696B".to_string(), "#FCFCFF".to_string()]
+            },
+            style: None,
+            stop_if_true: false
+        }
+    );
+
+    let temp_file_name = "temp_file_test_conditional_formatting.xlsx";
+    let mut model = Model::new_empty("conditional_formatting", "en", "UTC").unwrap();
+    model.new_sheet();
+    let cell_is = ConditionalFormat {
+        sqref: "A1:A10 C1:C10".to_string(),
+        rule: ConditionalFormatRule::CellIs {
+            operator: CellIsOperator::Between,
+            formulas: vec!["1".to_string(), "$B$1&\"<\"".to_string()],
+        },
+        style: Some(Dxf {
+            num_fmt: Some("0.00%".to_string()),
+            font: Some(DxfFont {
+                b: Some(true),
+                i: Some(false),
+                color: Some("#9C0006".to_string()),
+                ..Default::default()
+            }),
+            fill_color: Some("#FFC7CE".to_string()),
+            border: None,
+        }),
+        stop_if_true: true,
+    };
+    let above_average = ConditionalFormat {
+        sqref: "B1:B10".to_string(),
+        rule: ConditionalFormatRule::AboveAverage {
+            above_average: false,
+            equal_average: true,
+            std_dev: 2,
+        },
+        style: Some(Dxf {
+            fill_color: Some("#00FF00".to_string()),
+            ..Default::default()
+        }),
+        stop_if_true: false,
+    };
+    let icon_set = ConditionalFormat {
+        sqref: "D1:D10".to_string(),
+        rule: ConditionalFormatRule::IconSet {
+            icon_set: "3Arrows".to_string(),
+            cfvos: vec![
+                Cfvo {
+                    kind: CfvoKind::Percent,
+                    value: Some("0".to_string()),
+                },
+                Cfvo {
+                    kind: CfvoKind::Formula,
+                    value: Some("$E$1".to_string()),
+                },
+                Cfvo {
+                    kind: CfvoKind::Percentile,
+                    value: Some("80".to_string()),
+                },
+            ],
+            reverse: true,
+            show_value: false,
+        },
+        style: None,
+        stop_if_true: false,
+    };
+    let top10 = ConditionalFormat {
+        sqref: "A1:A5".to_string(),
+        rule: ConditionalFormatRule::Top10 {
+            rank: 10,
+            percent: true,
+            bottom: true,
+        },
+        style: Some(Dxf {
+            font: Some(DxfFont {
+                u: Some(true),
+                strike: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        stop_if_true: false,
+    };
+    model
+        .insert_conditional_format(0, 0, cell_is.clone())
+        .unwrap();
+    model
+        .insert_conditional_format(0, 1, icon_set.clone())
+        .unwrap();
+    model
+        .insert_conditional_format(0, 2, above_average.clone())
+        .unwrap();
+    model
+        .insert_conditional_format(1, 0, top10.clone())
+        .unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert_eq!(
+        model.get_conditional_formats(0).unwrap(),
+        vec![cell_is, icon_set, above_average]
+    );
+    assert_eq!(model.get_conditional_formats(1).unwrap(), vec![top10]);
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_data_validation_roundtrip() {
+    let temp_file_name = "temp_file_test_data_validation.xlsx";
+    let mut model = Model::new_empty("data_validation", "en", "UTC").unwrap();
+    let list = DataValidation {
+        sqref: "A1:A10".to_string(),
+        kind: DataValidationKind::List,
+        operator: CellIsOperator::Between,
+        formulas: vec!["\"Yes,No & maybe\"".to_string()],
+        allow_blank: true,
+        show_dropdown: true,
+        show_input_message: true,
+        input_title: Some("Answer".to_string()),
+        input_message: Some("Pick <one>".to_string()),
+        show_error_message: true,
+        error_style: DataValidationErrorStyle::Stop,
+        error_title: None,
+        error_message: Some("Not in the list".to_string()),
+    };
+    let whole = DataValidation {
+        sqref: "B1:B10 D1".to_string(),
+        kind: DataValidationKind::Whole,
+        operator: CellIsOperator::GreaterThanOrEqual,
+        formulas: vec!["$C$1".to_string()],
+        allow_blank: false,
+        show_dropdown: false,
+        show_input_message: false,
+        input_title: None,
+        input_message: None,
+        show_error_message: true,
+        error_style: DataValidationErrorStyle::Warning,
+        error_title: Some("Too small".to_string()),
+        error_message: None,
+    };
+    let text_length = DataValidation {
+        sqref: "E1".to_string(),
+        kind: DataValidationKind::TextLength,
+        operator: CellIsOperator::NotBetween,
+        formulas: vec!["2".to_string(), "5".to_string()],
+        allow_blank: true,
+        show_dropdown: true,
+        show_input_message: false,
+        input_title: None,
+        input_message: None,
+        show_error_message: false,
+        error_style: DataValidationErrorStyle::Information,
+        error_title: None,
+        error_message: None,
+    };
+    model.insert_data_validation(0, 0, list.clone()).unwrap();
+    model.insert_data_validation(0, 1, whole.clone()).unwrap();
+    model
+        .insert_data_validation(0, 2, text_length.clone())
+        .unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert_eq!(
+        model.get_data_validations(0).unwrap(),
+        vec![list, whole, text_length]
+    );
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_hidden_and_grouped_rows_and_columns_roundtrip() {
+    let temp_file_name = "temp_file_test_outline.xlsx";
+    let mut model = Model::new_empty("outline", "en", "UTC").unwrap();
+    model.set_user_input(0, 1, 1, "Hello".to_string()).unwrap();
+    for row in 2..=4 {
+        model.set_row_outline_level(0, row, 1).unwrap();
+        model.set_row_hidden(0, row, true).unwrap();
+    }
+    model.set_row_collapsed(0, 5, true).unwrap();
+    model.set_column_width(0, 3, 200.0).unwrap();
+    model.set_column_hidden(0, 3, true).unwrap();
+    model.set_column_outline_level(0, 5, 2).unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    for row in 2..=4 {
+        assert!(model.is_row_hidden(0, row).unwrap());
+        assert_eq!(model.get_row_outline_level(0, row).unwrap(), 1);
+    }
+    assert!(!model.is_row_hidden(0, 5).unwrap());
+    assert!(model.is_row_collapsed(0, 5).unwrap());
+    assert!(model.is_column_hidden(0, 3).unwrap());
+    assert_eq!(model.get_column_width(0, 3).unwrap(), 200.0);
+    assert!(!model.is_column_hidden(0, 4).unwrap());
+    assert_eq!(model.get_column_outline_level(0, 5).unwrap(), 2);
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "Hello".to_string()
+    );
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_hyperlinks_roundtrip() {
+    let temp_file_name = "temp_file_test_hyperlinks.xlsx";
+    let mut model = Model::new_empty("hyperlinks", "en", "UTC").unwrap();
+    model
+        .set_user_input(0, 1, 1, "IronCalc".to_string())
+        .unwrap();
+    model
+        .set_hyperlink(
+            0,
+            1,
+            1,
+            Some("https://ironcalc.com/?a=1&b=2".to_string()),
+            None,
+            Some("Our web page".to_string()),
+        )
+        .unwrap();
+    model
+        .set_hyperlink(0, 2, 1, None, Some("'Sheet1'!C3".to_string()), None)
+        .unwrap();
+    model
+        .set_hyperlink(
+            0,
+            3,
+            2,
+            Some("mailto:info@ironcalc.com".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert_eq!(
+        model.get_hyperlinks(0).unwrap(),
+        vec![
+            Hyperlink {
+                cell_ref: "A1".to_string(),
+                url: Some("https://ironcalc.com/?a=1&b=2".to_string()),
+                location: None,
+                tooltip: Some("Our web page".to_string()),
+            },
+            Hyperlink {
+                cell_ref: "A2".to_string(),
+                url: None,
+                location: Some("'Sheet1'!C3".to_string()),
+                tooltip: None,
+            },
+            Hyperlink {
+                cell_ref: "B3".to_string(),
+                url: Some("mailto:info@ironcalc.com".to_string()),
+                location: None,
+                tooltip: None,
+            },
+        ]
+    );
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_pivot_tables_roundtrip() {
+    let temp_file_name = "temp_file_test_pivot_tables.xlsx";
+    let second_file_name = "temp_file_test_pivot_tables_2.xlsx";
+    let mut model = Model::new_empty("pivot_tables", "en", "UTC").unwrap();
+    let data = [
+        ["Region", "Product", "Sales"],
+        ["East", "Apple", "10"],
+        ["West", "Pear", "20"],
+        ["East", "Pear", "5"],
+    ];
+    for (row, values) in data.iter().enumerate() {
+        for (column, value) in values.iter().enumerate() {
+            model
+                .set_user_input(0, row as i32 + 1, column as i32 + 1, value.to_string())
+                .unwrap();
+        }
+    }
+    let pivot_table = PivotTable {
+        name: "Sales by region".to_string(),
+        source: PivotSource::Range {
+            sheet_name: "Sheet1".to_string(),
+            reference: "A1:C4".to_string(),
+        },
+        location: "E1".to_string(),
+        reference: Some("E1:G4".to_string()),
+        row_fields: vec!["Region".to_string()],
+        column_fields: vec![],
+        value_fields: vec![
+            PivotValueField {
+                field: "Sales".to_string(),
+                function: PivotFunction::Sum,
+                name: "Sum of Sales".to_string(),
+            },
+            PivotValueField {
+                field: "Sales".to_string(),
+                function: PivotFunction::Average,
+                name: "Average of Sales".to_string(),
+            },
+        ],
+        filters: vec![PivotFilter {
+            field: "Product".to_string(),
+            hidden_items: vec!["Pear".to_string()],
+        }],
+        row_grand_totals: true,
+        column_grand_totals: false,
+        subtotals: true,
+        xlsx_parts: None,
+    };
+    model
+        .set_pivot_table(0, "Sales by region", Some(pivot_table.clone()))
+        .unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    let pivot_tables = model.get_pivot_tables(0).unwrap();
+    assert_eq!(pivot_tables.len(), 1);
+    assert!(pivot_tables[0].xlsx_parts.is_some());
+    assert_eq!(
+        PivotTable {
+            xlsx_parts: None,
+            ..pivot_tables[0].clone()
+        },
+        pivot_table
+    );
+
+    // Pivot tables read from a file are saved as they were
+    save_to_xlsx(&model, second_file_name).unwrap();
+    let second_model = load_from_xlsx(second_file_name, "en", "UTC").unwrap();
+    assert_eq!(second_model.get_pivot_tables(0).unwrap(), pivot_tables);
+
+    fs::remove_file(temp_file_name).unwrap();
+    fs::remove_file(second_file_name).unwrap();
+}
+
+#[test]
+fn test_rich_text_roundtrip() {
+    let temp_file_name = "temp_file_test_rich_text.xlsx";
+    let mut model = Model::new_empty("rich_text", "en", "UTC").unwrap();
+    let runs = vec![
+        TextRun {
+            text: "E = mc".to_string(),
+            font: None,
+        },
+        TextRun {
+            text: "2".to_string(),
+            font: Some(Font {
+                vert_align: FontVerticalAlignment::Superscript,
+                ..Default::default()
+            }),
+        },
+        TextRun {
+            text: " & more".to_string(),
+            font: Some(Font {
+                b: true,
+                i: true,
+                color: Some("#FF0000".to_string()),
+                sz: 14,
+                name: "Arial".to_string(),
+                ..Default::default()
+            }),
+        },
+    ];
+    model.set_cell_rich_text(0, 1, 1, runs.clone()).unwrap();
+    model
+        .set_user_input(0, 2, 1, "E = mc2 & more".to_string())
+        .unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert_eq!(model.get_cell_rich_text(0, 1, 1).unwrap(), Some(runs));
+    assert_eq!(model.get_cell_rich_text(0, 2, 1).unwrap(), None);
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "E = mc2 & more"
+    );
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_protection_roundtrip() {
+    let temp_file_name = "temp_file_test_protection.xlsx";
+    let mut model = Model::new_empty("protection", "en", "UTC").unwrap();
+    let mut style = model.get_style_for_cell(0, 2, 2).unwrap();
+    style.protection = Some(Protection {
+        locked: false,
+        hidden: true,
+    });
+    model.set_cell_style(0, 2, 2, &style).unwrap();
+    let protection = SheetProtection {
+        allow_format_columns: true,
+        allow_insert_rows: true,
+        allow_select_locked_cells: false,
+        ..Default::default()
+    };
+    model
+        .protect_sheet(0, protection.clone(), Some("secret"))
+        .unwrap();
+    model.protect_workbook(true, false, None).unwrap();
+    model.new_sheet();
+    model.workbook.worksheets[1].protection = Some(SheetProtection {
+        password_hash: Some(PasswordHash::Legacy("DAA7".to_string())),
+        ..Default::default()
+    });
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let mut model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert!(!model.is_cell_locked(0, 2, 2).unwrap());
+    assert!(model.is_cell_hidden(0, 2, 2).unwrap());
+    assert!(model.is_cell_locked(0, 1, 1).unwrap());
+    let loaded = model.get_sheet_protection(0).unwrap().unwrap();
+    assert_eq!(
+        loaded,
+        SheetProtection {
+            password_hash: loaded.password_hash.clone(),
+            ..protection
+        }
+    );
+    let workbook_protection = model.get_workbook_protection().unwrap();
+    assert!(workbook_protection.lock_structure);
+    assert!(!workbook_protection.lock_windows);
+
+    assert!(model.unprotect_sheet(0, Some("wrong")).is_err());
+    model.unprotect_sheet(0, Some("secret")).unwrap();
+    model.unprotect_sheet(1, Some("secret")).unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_named_styles_and_theme_roundtrip() {
+    let temp_file_name = "temp_file_test_named_styles.xlsx";
+    let mut model = Model::new_empty("named_styles", "en", "UTC").unwrap();
+    let mut theme = Theme {
+        name: "Custom".to_string(),
+        ..Default::default()
+    };
+    theme.colors[3] = "#17406D".to_string();
+    theme.minor_font = "Arial".to_string();
+    model.set_theme(&theme).unwrap();
+    model.set_user_input(0, 1, 1, "Title".to_string()).unwrap();
+    model.apply_named_style(0, 1, 1, "Title").unwrap();
+    model.apply_named_style(0, 2, 1, "Heading 1").unwrap();
+    let mut style = model.get_named_style("Normal").unwrap();
+    style.font.i = true;
+    model.create_named_style("Remark", &style).unwrap();
+    model.apply_named_style(0, 3, 1, "Remark").unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let file = fs::File::open(temp_file_name).unwrap();
+    let mut archive = zip::ZipArchive::new(file).unwrap();
+    let mut theme_xml = String::new();
+    archive
+        .by_name("xl/theme/theme1.xml")
+        .unwrap()
+        .read_to_string(&mut theme_xml)
+        .unwrap();
+    assert!(theme_xml.contains("<a:dk2><a:srgbClr val=\"17406D\"/></a:dk2>"));
+    let mut styles_xml = String::new();
+    archive
+        .by_name("xl/styles.xml")
+        .unwrap()
+        .read_to_string(&mut styles_xml)
+        .unwrap();
+    assert!(styles_xml.contains("<cellStyle xfId=\"2\" name=\"Heading 1\" builtinId=\"16\"/>"));
+    // Custom styles do not have a built-in id
+    assert!(styles_xml.contains("<cellStyle xfId=\"3\" name=\"Remark\"/>"));
+
+    let mut model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert_eq!(model.workbook.theme, theme);
+    assert_eq!(model.get_cell_style_name(0, 1, 1).unwrap(), "Title");
+    assert_eq!(model.get_cell_style_name(0, 2, 1).unwrap(), "Heading 1");
+    assert_eq!(model.get_cell_style_name(0, 3, 1).unwrap(), "Remark");
+    assert!(model.get_style_for_cell(0, 3, 1).unwrap().font.i);
+
+    // The colors of the theme are kept as references to the theme
+    let mut theme = model.workbook.theme.clone();
+    theme.colors[3] = "#7030A0".to_string();
+    model.set_theme(&theme).unwrap();
+    assert_eq!(
+        model.get_style_for_cell(0, 1, 1).unwrap().font.color,
+        Some("#7030A0".to_string())
+    );
+    assert_eq!(
+        model.get_style_for_cell(0, 3, 1).unwrap().font.name,
+        "Arial"
+    );
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_auto_filter_roundtrip() {
+    let temp_file_name = "temp_file_test_auto_filter.xlsx";
+    let mut model = Model::new_empty("auto_filter", "en", "UTC").unwrap();
+    let auto_filter = AutoFilter {
+        reference: "A1:E20".to_string(),
+        columns: vec![
+            FilterColumn {
+                column_id: 0,
+                filter: ColumnFilter::Values {
+                    values: vec!["Apple".to_string(), "Pear".to_string()],
+                    date_groups: vec![],
+                    blank: true,
+                },
+            },
+            FilterColumn {
+                column_id: 1,
+                filter: ColumnFilter::Custom {
+                    conditions: vec![
+                        CustomFilter {
+                            operator: FilterOperator::GreaterThanOrEqual,
+                            value: "10".to_string(),
+                        },
+                        CustomFilter {
+                            operator: FilterOperator::NotEqual,
+                            value: "15".to_string(),
+                        },
+                    ],
+                    and: true,
+                },
+            },
+            FilterColumn {
+                column_id: 2,
+                filter: ColumnFilter::Top10 {
+                    rank: 25,
+                    percent: true,
+                    bottom: true,
+                },
+            },
+            FilterColumn {
+                column_id: 3,
+                filter: ColumnFilter::Color {
+                    color: "#FF0000".to_string(),
+                    cell_color: true,
+                },
+            },
+            FilterColumn {
+                column_id: 4,
+                filter: ColumnFilter::Values {
+                    values: vec![],
+                    date_groups: vec![DateGroup {
+                        grouping: DateTimeGrouping::Month,
+                        year: 2024,
+                        month: 3,
+                        day: 0,
+                        hour: 0,
+                        minute: 0,
+                        second: 0,
+                    }],
+                    blank: false,
+                },
+            },
+        ],
+    };
+    model.set_auto_filter(0, Some(auto_filter.clone())).unwrap();
+    model.new_sheet();
+    let second_auto_filter = AutoFilter {
+        reference: "B2:C8".to_string(),
+        columns: vec![FilterColumn {
+            column_id: 1,
+            filter: ColumnFilter::Color {
+                color: "#0000FF".to_string(),
+                cell_color: false,
+            },
+        }],
+    };
+    model
+        .set_auto_filter(1, Some(second_auto_filter.clone()))
+        .unwrap();
+    save_to_xlsx(&model, temp_file_name).unwrap();
+
+    let model = load_from_xlsx(temp_file_name, "en", "UTC").unwrap();
+    assert_eq!(model.get_auto_filter(0).unwrap(), Some(auto_filter));
+    assert_eq!(model.get_auto_filter(1).unwrap(), Some(second_auto_filter));
+    fs::remove_file(temp_file_name).unwrap();
+}
+
+#[test]
+fn test_threaded_comments() {
+    let model = load_from_xlsx("tests/comments.xlsx", "en", "UTC").unwrap();
+    let comments = model.get_comments(0).unwrap();
+    assert_eq!(
+        comments,
+        vec![
+            Comment {
+                text: "Is this right?".to_string(),
+                author_name: "Marie Curie".to_string(),
+                author_id: Some("{6F9619FF-8B86-D011-B42D-00C04FC964FF}".to_string()),
+                cell_ref: "B2".to_string(),
+                // 2024-03-01T10:30:00
+                timestamp: Some(1709289000000),
+                resolved: true,
+                replies: vec![CommentReply {
+                    text: "Yes".to_string(),
+                    author_name: "Ada Lovelace".to_string(),
+                    author_id: Some("{7A1B2C3D-0000-4000-8000-000000000001}".to_string()),
+                    timestamp: Some(1709366400000),
+                }],
+            },
+            Comment {
+                text: "Nikola Tesla:\nA note".to_string(),
+                author_name: "Nikola Tesla".to_string(),
+                author_id: None,
+                cell_ref: "C5".to_string(),
+                timestamp: None,
+                resolved: false,
+                replies: vec![],
+            }
+        ]
+    );
+}